@@ -0,0 +1,143 @@
+// Implementations of the `approx` traits, so tests can compare geometric results with a tolerance using
+// `assert_abs_diff_eq!` and `assert_relative_eq!`. Values with several coordinates are equal if all of them are.
+
+use approx::{AbsDiffEq, RelativeEq};
+use num_traits::real::Real;
+
+use crate::{components::ComponentSummary, mesh::Mesh, Coords, Scalar, TrapezoidGeometry, Vertex};
+
+fn all_abs_diff_eq<'a, C: AbsDiffEq + 'a>(a: impl IntoIterator<Item=&'a C>, b: impl IntoIterator<Item=&'a C>, epsilon: C::Epsilon) -> bool
+where C::Epsilon: Clone {
+    a.into_iter().zip(b).all(|(a, b)| a.abs_diff_eq(b, epsilon.clone()))
+}
+
+fn all_relative_eq<'a, C: RelativeEq + 'a>(a: impl IntoIterator<Item=&'a C>, b: impl IntoIterator<Item=&'a C>, epsilon: C::Epsilon, max_relative: C::Epsilon) -> bool
+where C::Epsilon: Clone {
+    a.into_iter().zip(b).all(|(a, b)| a.relative_eq(b, epsilon.clone(), max_relative.clone()))
+}
+
+// Options are equal if both are `None`, or both are `Some` with equal values
+fn option_eq<T>(a: &Option<T>, b: &Option<T>, eq: impl Fn(&T, &T) -> bool) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => eq(a, b),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+impl<C: Scalar + AbsDiffEq> AbsDiffEq for Coords<C>
+where C::Epsilon: Clone {
+    type Epsilon = C::Epsilon;
+
+    fn default_epsilon() -> Self::Epsilon {
+        C::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        all_abs_diff_eq(self.as_array(), other.as_array(), epsilon)
+    }
+}
+
+impl<C: Scalar + RelativeEq> RelativeEq for Coords<C>
+where C::Epsilon: Clone {
+    fn default_max_relative() -> Self::Epsilon {
+        C::default_max_relative()
+    }
+
+    fn relative_eq(&self, other: &Self, epsilon: Self::Epsilon, max_relative: Self::Epsilon) -> bool {
+        all_relative_eq(self.as_array(), other.as_array(), epsilon, max_relative)
+    }
+}
+
+impl<C: Scalar + AbsDiffEq> AbsDiffEq for TrapezoidGeometry<C>
+where C::Epsilon: Clone {
+    type Epsilon = C::Epsilon;
+
+    fn default_epsilon() -> Self::Epsilon {
+        C::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        let point = |a: &[C; 2], b: &[C; 2]| all_abs_diff_eq(a, b, epsilon.clone());
+        let segment = |a: &[[C; 2]; 2], b: &[[C; 2]; 2]| all_abs_diff_eq(a.iter().flatten(), b.iter().flatten(), epsilon.clone());
+        self.inside == other.inside
+            && option_eq(&self.bottom, &other.bottom, point)
+            && option_eq(&self.top, &other.top, point)
+            && option_eq(&self.left, &other.left, segment)
+            && option_eq(&self.right, &other.right, segment)
+    }
+}
+
+impl<C: Scalar + RelativeEq> RelativeEq for TrapezoidGeometry<C>
+where C::Epsilon: Clone {
+    fn default_max_relative() -> Self::Epsilon {
+        C::default_max_relative()
+    }
+
+    fn relative_eq(&self, other: &Self, epsilon: Self::Epsilon, max_relative: Self::Epsilon) -> bool {
+        let point = |a: &[C; 2], b: &[C; 2]| all_relative_eq(a, b, epsilon.clone(), max_relative.clone());
+        let segment = |a: &[[C; 2]; 2], b: &[[C; 2]; 2]| all_relative_eq(a.iter().flatten(), b.iter().flatten(), epsilon.clone(), max_relative.clone());
+        self.inside == other.inside
+            && option_eq(&self.bottom, &other.bottom, point)
+            && option_eq(&self.top, &other.top, point)
+            && option_eq(&self.left, &other.left, segment)
+            && option_eq(&self.right, &other.right, segment)
+    }
+}
+
+impl<C: Real + AbsDiffEq> AbsDiffEq for ComponentSummary<C>
+where C::Epsilon: Clone {
+    type Epsilon = C::Epsilon;
+
+    fn default_epsilon() -> Self::Epsilon {
+        C::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        self.holes == other.holes
+            && all_abs_diff_eq([&self.area, &self.perimeter], [&other.area, &other.perimeter], epsilon.clone())
+            && all_abs_diff_eq(self.bounds.iter().flatten(), other.bounds.iter().flatten(), epsilon)
+    }
+}
+
+impl<C: Real + RelativeEq> RelativeEq for ComponentSummary<C>
+where C::Epsilon: Clone {
+    fn default_max_relative() -> Self::Epsilon {
+        C::default_max_relative()
+    }
+
+    fn relative_eq(&self, other: &Self, epsilon: Self::Epsilon, max_relative: Self::Epsilon) -> bool {
+        self.holes == other.holes
+            && all_relative_eq([&self.area, &self.perimeter], [&other.area, &other.perimeter], epsilon.clone(), max_relative.clone())
+            && all_relative_eq(self.bounds.iter().flatten(), other.bounds.iter().flatten(), epsilon, max_relative)
+    }
+}
+
+// Meshes are equal if they have the same triangles, and vertices with equal coordinates
+impl<V: Vertex + PartialEq> AbsDiffEq for Mesh<V>
+where V::Coordinate: AbsDiffEq, <V::Coordinate as AbsDiffEq>::Epsilon: Clone {
+    type Epsilon = <V::Coordinate as AbsDiffEq>::Epsilon;
+
+    fn default_epsilon() -> Self::Epsilon {
+        V::Coordinate::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        self.triangles == other.triangles
+            && self.vertices.len() == other.vertices.len()
+            && self.vertices.iter().zip(&other.vertices).all(|(a, b)| all_abs_diff_eq(&[a.x(), a.y()], &[b.x(), b.y()], epsilon.clone()))
+    }
+}
+
+impl<V: Vertex + PartialEq> RelativeEq for Mesh<V>
+where V::Coordinate: RelativeEq, <V::Coordinate as AbsDiffEq>::Epsilon: Clone {
+    fn default_max_relative() -> Self::Epsilon {
+        V::Coordinate::default_max_relative()
+    }
+
+    fn relative_eq(&self, other: &Self, epsilon: Self::Epsilon, max_relative: Self::Epsilon) -> bool {
+        self.triangles == other.triangles
+            && self.vertices.len() == other.vertices.len()
+            && self.vertices.iter().zip(&other.vertices).all(|(a, b)| all_relative_eq(&[a.x(), a.y()], &[b.x(), b.y()], epsilon.clone(), max_relative.clone()))
+    }
+}
@@ -0,0 +1,67 @@
+//! Signed areas and centroids of polygons, accurate for coordinates far from the origin.
+//!
+//! The shoelace formula sums products of coordinates, which for projected coordinates (such as UTM, in the millions of
+//! meters) are far larger than the area of a slender polygon, so naive summation can get even its sign wrong. Here the
+//! vertices are taken relative to the first vertex, each product is added with its rounding error, and the terms are
+//! summed with Neumaier's compensated summation. Triangulation and the other modules use the same summation to decide
+//! the winding of contours.
+//!
+//! Counter-clockwise contours count positively and clockwise contours negatively, so holes wound opposite to their
+//! outer ring are subtracted.
+//!
+//! ```
+//! # use triangulate::area;
+//! let polygons = vec![
+//!     vec![[0f64, 0.], [4., 0.], [4., 4.], [0., 4.]],
+//!     vec![[1., 1.], [1., 2.], [2., 2.], [2., 1.]],
+//! ];
+//! assert_eq!(area::signed_area(&polygons), 15.);
+//! let [x, y] = area::centroid(&polygons).expect("Polygons have no area");
+//! assert!((x - 61. / 30.).abs() < 1e-12 && (y - 61. / 30.).abs() < 1e-12);
+//! ```
+
+use num_traits::{real::Real, One, Zero};
+
+use crate::{math::{self, CompensatedSum}, PolygonList, PolygonListExt, Vertex};
+
+/// The signed area of the contours of `polygons`, positive when counter-clockwise
+pub fn signed_area<'p, P: PolygonList<'p> + ?Sized>(polygons: &'p P) -> <P::Vertex as Vertex>::Coordinate
+where <P::Vertex as Vertex>::Coordinate: Real {
+    let contours = PolygonListExt::new(polygons).contours();
+    let mut area = CompensatedSum::new();
+    for contour in contours.iter() {
+        area.add(math::signed_area_2(contour));
+    }
+    let two = <P::Vertex as Vertex>::Coordinate::one() + <P::Vertex as Vertex>::Coordinate::one();
+    area.value() / two
+}
+
+/// The centroid of the area of `polygons`, with contours weighted by their signed area as in [signed_area]. `None` if
+/// the signed area is zero
+pub fn centroid<'p, P: PolygonList<'p> + ?Sized>(polygons: &'p P) -> Option<[<P::Vertex as Vertex>::Coordinate; 2]>
+where <P::Vertex as Vertex>::Coordinate: Real {
+    let contours = PolygonListExt::new(polygons).contours();
+    let origin = *contours.first()?.first()?;
+    let (mut area, mut moment_x, mut moment_y) = (CompensatedSum::new(), CompensatedSum::new(), CompensatedSum::new());
+    for contour in contours.iter() {
+        for (i, c0) in contour.iter().enumerate() {
+            let c1 = contour[(i + 1) % contour.len()];
+            let [p, q] = [*c0, c1].map(|c| [c[0] - origin[0], c[1] - origin[1]]);
+            let mut cross = CompensatedSum::new();
+            cross.add_product(p[0], q[1]);
+            cross.add_product(-q[0], p[1]);
+            let cross = cross.value();
+            area.add(cross);
+            moment_x.add_product(p[0] + q[0], cross);
+            moment_y.add_product(p[1] + q[1], cross);
+        }
+    }
+
+    let area = area.value();
+    if area == <P::Vertex as Vertex>::Coordinate::zero() {
+        return None;
+    }
+    let three = <P::Vertex as Vertex>::Coordinate::one() + <P::Vertex as Vertex>::Coordinate::one()
+        + <P::Vertex as Vertex>::Coordinate::one();
+    Some([origin[0] + moment_x.value() / (three * area), origin[1] + moment_y.value() / (three * area)])
+}
@@ -0,0 +1,342 @@
+//! Boolean operations on polygon sets.
+//!
+//! Each operand is a [PolygonList] interpreted with the even-odd rule (the same way it would be triangulated).
+//! Results are returned as contours with counter-clockwise outer boundaries and clockwise holes, which can be
+//! triangulated directly.
+//!
+//! ```
+//! # use triangulate::{boolean, formats, ListFormat, PolygonList};
+//! let shapes = vec![
+//!     vec![vec![[0f64, 0.], [2., 0.], [2., 2.], [0., 2.]]],
+//!     vec![vec![[1., 1.], [3., 1.], [3., 3.], [1., 3.]]],
+//! ];
+//! let union = boolean::union_all(&shapes);
+//! assert_eq!(union.len(), 1);
+//!
+//! let mut triangles = Vec::<[usize; 2]>::new();
+//! union.triangulate(formats::IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+//! ```
+
+use core::{cmp, fmt, iter};
+use alloc::{vec, vec::Vec};
+
+use num_traits::real::Real;
+
+use crate::{PolygonList, PolygonListExt, Vertex, GeometryKernel, DefaultKernel, Orientation, intersection, math};
+
+/// A set of contours produced by a boolean operation
+pub type Contours<C> = Vec<Vec<[C; 2]>>;
+
+// A segment between two points
+type Segment<C> = [[C; 2]; 2];
+
+/// The operation performed by [boolean]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum BooleanOp {
+    /// Area covered by either operand
+    Union,
+    /// Area covered by both operands
+    Intersection,
+    /// Area covered by the first operand but not the second
+    Difference,
+    /// Area covered by exactly one operand. Parts of the result may touch at single vertices, which must be resolved before triangulating
+    Xor,
+}
+
+/// Perform the [BooleanOp] `op` on two polygon sets
+pub fn boolean<'a, 'b, A, B, C>(a: &'a A, b: &'b B, op: BooleanOp) -> Contours<C>
+where A: PolygonList<'a> + ?Sized,
+      B: PolygonList<'b> + ?Sized,
+      A::Vertex: Vertex<Coordinate=C>,
+      B::Vertex: Vertex<Coordinate=C>,
+      C: Real + fmt::Debug {
+    overlay(&PolygonListExt::new(a).contours(), &PolygonListExt::new(b).contours(), op)
+}
+
+/// The union of many polygon sets.
+///
+/// The shapes are combined pairwise in a balanced tree (a 'cascaded' union), so each edge takes part in
+/// O(log *n*) overlays rather than *n*. Shapes whose bounding boxes do not overlap are combined without an overlay.
+pub fn union_all<'p, P, C>(shapes: &'p [P]) -> Contours<C>
+where P: PolygonList<'p>,
+      P::Vertex: Vertex<Coordinate=C>,
+      C: Real + fmt::Debug {
+    let shapes: Vec<_> = shapes.iter().map(|shape| PolygonListExt::new(shape).contours()).collect();
+    union_range(&shapes)
+}
+
+pub(crate) fn union_range<C: Real + fmt::Debug>(shapes: &[Contours<C>]) -> Contours<C> {
+    match shapes.len() {
+        0 => Vec::new(),
+        1 => overlay(&shapes[0], &Vec::new(), BooleanOp::Union),
+        len => {
+            let (left, right) = shapes.split_at(len / 2);
+            let left = union_range(left);
+            let right = union_range(right);
+            let bb_left = math::bounding_box(left.iter().flatten());
+            let bb_right = math::bounding_box(right.iter().flatten());
+            match (bb_left, bb_right) {
+                (Some([min0, max0]), Some([min1, max1])) if max0[0] < min1[0] || max1[0] < min0[0] || max0[1] < min1[1] || max1[1] < min0[1] => {
+                    let mut union = left;
+                    union.extend(right);
+                    union
+                }
+                _ => overlay(&left, &right, BooleanOp::Union),
+            }
+        }
+    }
+}
+
+fn cmp_point<C: Real + fmt::Debug>(a: [C; 2], b: [C; 2]) -> cmp::Ordering {
+    <DefaultKernel as GeometryKernel<C>>::compare_y(&a, &b)
+}
+
+// An undirected identity for a segment, used to find edges shared by both operands
+pub(crate) fn segment_key<C: Real + fmt::Debug>(segment: [[C; 2]; 2]) -> [[C; 2]; 2] {
+    if cmp_point(segment[0], segment[1]) == cmp::Ordering::Greater {
+        [segment[1], segment[0]]
+    } else {
+        segment
+    }
+}
+
+pub(crate) fn cmp_key<C: Real + fmt::Debug>(a: &[[C; 2]; 2], b: &[[C; 2]; 2]) -> cmp::Ordering {
+    cmp_point(a[0], b[0]).then_with(|| cmp_point(a[1], b[1]))
+}
+
+/// Orient the edges of each contour so the filled area (by the even-odd rule) lies on their left
+pub(crate) fn oriented_edges<C: Real + fmt::Debug>(contours: &[Vec<[C; 2]>]) -> Vec<[[C; 2]; 2]> {
+    let mut edges = Vec::new();
+    for (i, contour) in contours.iter().enumerate() {
+        if contour.len() < 3 {
+            continue;
+        }
+        let area = math::signed_area_2(contour);
+        if area == C::zero() {
+            continue;
+        }
+        let depth = contours.iter().enumerate().filter(|(j, other)| *j != i && math::contour_contains(other, contour[0])).count();
+        let interior_left = (area > C::zero()) ^ (depth % 2 == 1);
+        for (k, c0) in contour.iter().enumerate() {
+            let c1 = contour[(k + 1) % contour.len()];
+            if *c0 != c1 {
+                edges.push(if interior_left { [*c0, c1] } else { [c1, *c0] });
+            }
+        }
+    }
+    edges
+}
+
+/// Overlay two sets of contours, keeping the boundary of the result of `op`
+pub(crate) fn overlay<C: Real + fmt::Debug>(a: &[Vec<[C; 2]>], b: &[Vec<[C; 2]>], op: BooleanOp) -> Contours<C> {
+    overlay_edges(oriented_edges(a), oriented_edges(b), |shape, point| math::contours_contain(if shape == 0 { a } else { b }, point), op)
+}
+
+/// Overlay two sets of directed edges with the filled area on their left, keeping the boundary of the result of `op`.
+/// `contains(shape, point)` tells whether the operand `shape` (0 or 1) covers `point`
+pub(crate) fn overlay_edges<C, F>(edges_a: Vec<[[C; 2]; 2]>, edges_b: Vec<[[C; 2]; 2]>, contains: F, op: BooleanOp) -> Contours<C>
+where C: Real + fmt::Debug,
+      F: Fn(usize, [C; 2]) -> bool {
+    assemble_rings(overlay_pieces(edges_a, edges_b, contains, op))
+}
+
+/// [overlay_edges], returning the directed pieces of the boundary of the result without linking them into rings
+pub(crate) fn overlay_pieces<C, F>(edges_a: Vec<[[C; 2]; 2]>, edges_b: Vec<[[C; 2]; 2]>, contains: F, op: BooleanOp) -> Vec<[[C; 2]; 2]>
+where C: Real + fmt::Debug,
+      F: Fn(usize, [C; 2]) -> bool {
+    let count_a = edges_a.len();
+    let edges: Vec<[[C; 2]; 2]> = edges_a.into_iter().chain(edges_b).collect();
+
+    let pieces = intersection::split_segments(&edges);
+
+    // (undirected key, directed piece) for each operand
+    let mut keys: [Vec<(Segment<C>, Segment<C>)>; 2] = [Vec::new(), Vec::new()];
+    for piece in pieces.iter() {
+        let shape = usize::from(piece.source >= count_a);
+        keys[shape].push((segment_key(piece.segment), piece.segment));
+    }
+    for k in keys.iter_mut() {
+        k.sort_by(|(a, _), (b, _)| cmp_key(a, b));
+    }
+
+    let mut kept = Vec::new();
+    for piece in pieces.iter() {
+        let shape = usize::from(piece.source >= count_a);
+        let other_keys = &keys[1 - shape];
+        let key = segment_key(piece.segment);
+
+        let keep = match other_keys.binary_search_by(|(k, _)| cmp_key(k, &key)) {
+            Ok(i) => {
+                // The edge is shared by both operands. Only the first operand's copy is ever kept
+                let same_side = other_keys[i].1 == piece.segment;
+                match (shape, op, same_side) {
+                    (0, BooleanOp::Union | BooleanOp::Intersection, true) => Some(false),
+                    (0, BooleanOp::Difference, false) => Some(false),
+                    _ => None,
+                }
+            }
+            Err(_) => {
+                let [c0, c1] = piece.segment;
+                let two = C::one() + C::one();
+                let midpoint = [(c0[0] + c1[0]) / two, (c0[1] + c1[1]) / two];
+                let inside_other = contains(1 - shape, midpoint);
+                match (shape, op) {
+                    (_, BooleanOp::Union) => (!inside_other).then_some(false),
+                    (_, BooleanOp::Intersection) => inside_other.then_some(false),
+                    (0, BooleanOp::Difference) => (!inside_other).then_some(false),
+                    (_, BooleanOp::Difference) => inside_other.then_some(true),
+                    (_, BooleanOp::Xor) => Some(inside_other),
+                }
+            }
+        };
+
+        if let Some(reverse) = keep {
+            let [c0, c1] = piece.segment;
+            kept.push(if reverse { [c1, c0] } else { [c0, c1] });
+        }
+    }
+
+    kept
+}
+
+/// Link directed edges (with the filled area on their left) into closed rings, removing vertices in the middle of
+/// straight edges
+pub(crate) fn assemble_rings<C: Real + fmt::Debug>(edges: Vec<[[C; 2]; 2]>) -> Contours<C> {
+    link_rings(edges).into_iter().map(remove_collinear).filter(|ring| ring.len() >= 3).collect()
+}
+
+/// Link directed edges (with the filled area on their left) into closed rings, keeping every vertex, so the rings
+/// still meet triangles which use the vertices in the middle of straight edges
+pub(crate) fn link_rings<C: Real + fmt::Debug>(mut edges: Vec<[[C; 2]; 2]>) -> Contours<C> {
+    edges.sort_by(|a, b| cmp_key(a, b));
+    let mut used = vec![false; edges.len()];
+    let mut rings = Vec::new();
+
+    for start in 0..edges.len() {
+        if used[start] {
+            continue;
+        }
+        used[start] = true;
+        let origin = edges[start][0];
+        let mut ring = vec![origin];
+        let mut current = edges[start];
+
+        loop {
+            let c = current[1];
+            if c == origin {
+                break;
+            }
+            ring.push(c);
+
+            // Of all unused edges leaving this point, take the sharpest right turn so touching rings stay separate
+            let first = edges.partition_point(|e| cmp_point(e[0], c) == cmp::Ordering::Less);
+            let d_in = [c[0] - current[0][0], c[1] - current[0][1]];
+            let mut best: Option<(usize, C)> = None;
+            for (i, e) in edges.iter().enumerate().skip(first) {
+                if e[0] != c {
+                    break;
+                }
+                if used[i] {
+                    continue;
+                }
+                let d_out = [e[1][0] - c[0], e[1][1] - c[1]];
+                let turn = (d_in[0] * d_out[1] - d_in[1] * d_out[0]).atan2(d_in[0] * d_out[0] + d_in[1] * d_out[1]);
+                if best.is_none_or(|(_, best_turn)| turn < best_turn) {
+                    best = Some((i, turn));
+                }
+            }
+
+            match best {
+                Some((i, _)) => {
+                    used[i] = true;
+                    current = edges[i];
+                }
+                None => {
+                    // An open chain can only come from inconsistent input; drop it
+                    ring.clear();
+                    break;
+                }
+            }
+        }
+
+        rings.extend(split_pinches(ring).into_iter().filter(|ring| ring.len() >= 3));
+    }
+
+    rings
+}
+
+// Split a ring which passes through the same point more than once, as where two filled areas touch at a corner, into
+// loops which each pass through it once
+fn split_pinches<C: Real + fmt::Debug>(ring: Vec<[C; 2]>) -> Vec<Vec<[C; 2]>> {
+    // The first position of each point in the ring
+    let mut order: Vec<usize> = (0..ring.len()).collect();
+    order.sort_by(|&a, &b| cmp_point(ring[a], ring[b]));
+    let mut first: Vec<usize> = (0..ring.len()).collect();
+    for pair in order.windows(2) {
+        if ring[pair[0]] == ring[pair[1]] {
+            first[pair[1]] = first[pair[0]];
+        }
+    }
+    if first.iter().enumerate().all(|(i, f)| i == *f) {
+        return vec![ring];
+    }
+
+    // Cut off a loop whenever the walk returns to a point still on the stack
+    let mut loops = Vec::new();
+    let mut stack: Vec<usize> = Vec::new();
+    let mut on_stack = vec![None; ring.len()];
+    for i in 0..ring.len() {
+        match on_stack[first[i]] {
+            Some(position) => {
+                let cut: Vec<usize> = stack.drain(position + 1..).collect();
+                for j in &cut {
+                    on_stack[first[*j]] = None;
+                }
+                loops.push(iter::once(ring[i]).chain(cut.into_iter().map(|j| ring[j])).collect());
+            }
+            None => {
+                on_stack[first[i]] = Some(stack.len());
+                stack.push(i);
+            }
+        }
+    }
+    loops.push(stack.into_iter().map(|i| ring[i]).collect());
+    loops
+}
+
+fn remove_collinear<C: Real + fmt::Debug>(mut ring: Vec<[C; 2]>) -> Vec<[C; 2]> {
+    let mut i = 0;
+    while ring.len() >= 3 && i < ring.len() {
+        let len = ring.len();
+        let prev = ring[(i + len - 1) % len];
+        let c = ring[i];
+        let next = ring[(i + 1) % len];
+        let forward = (c[0] - prev[0]) * (next[0] - c[0]) + (c[1] - prev[1]) * (next[1] - c[1]) > C::zero();
+        if <DefaultKernel as GeometryKernel<C>>::orientation(&prev, &c, &next) == Orientation::Collinear && forward {
+            ring.remove(i);
+            i = i.saturating_sub(1);
+        } else {
+            i += 1;
+        }
+    }
+    ring
+}
+
+/// Split the result of a boolean operation into its connected parts: each counter-clockwise outer boundary, followed by the
+/// clockwise holes directly inside it
+pub(crate) fn group_rings<C: Real + fmt::Debug>(contours: Contours<C>) -> Vec<Contours<C>> {
+    let (outers, holes): (Vec<_>, Vec<_>) = contours.into_iter().partition(|contour| math::signed_area_2(contour) > C::zero());
+    let areas: Vec<C> = outers.iter().map(|outer| math::signed_area_2(outer)).collect();
+    let mut groups: Vec<Contours<C>> = outers.into_iter().map(|outer| vec![outer]).collect();
+    for hole in holes {
+        // The smallest outer boundary containing the hole is the one directly around it
+        let parent = groups.iter().enumerate()
+            .filter(|(_, group)| hole.iter().any(|c| math::contour_contains(&group[0], *c)))
+            .min_by(|(i, _), (j, _)| areas[*i].partial_cmp(&areas[*j]).unwrap_or(cmp::Ordering::Equal))
+            .map(|(i, _)| i);
+        if let Some(i) = parent {
+            groups[i].push(hole);
+        }
+    }
+    groups
+}
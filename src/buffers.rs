@@ -0,0 +1,183 @@
+//! Flat vertex and index buffers, ready to upload to the GPU or to pass to the [meshopt](https://crates.io/crates/meshopt)
+//! crate.
+//!
+//! [IndexBuffers] stores `[x, y, z]` positions as `f32` (a 12 byte stride) and triangles as a flat list of `u32`
+//! indices, which is the layout meshopt's functions and its `VertexDataAdapter` expect. With the `meshopt` feature,
+//! [IndexBuffers::optimize] runs meshopt's vertex cache, overdraw and vertex fetch optimizations on the buffers.
+//! The [payload](crate::Vertex::Payload) of each vertex is kept in a parallel buffer, which every operation reorders
+//! along with the positions.
+//!
+//! ```
+//! # use triangulate::{buffers::IndexBuffers, mesh::Mesh};
+//! let polygons = vec![vec![[0f64, 0.], [1., 0.], [1., 1.], [0., 1.]]];
+//! let mesh = Mesh::from_polygons(&polygons).expect("Triangulation failed");
+//! let buffers = IndexBuffers::from_mesh(&mesh).expect("Too many vertices");
+//! assert_eq!(buffers.positions.len(), 4);
+//! assert_eq!(buffers.indices.len(), 6);
+//! assert!(buffers.positions.iter().all(|p| p[2] == 0.));
+//! ```
+
+use core::ops::Range;
+use alloc::{vec, vec::Vec};
+
+use num_traits::{Zero, real::Real};
+
+use crate::{formats::IndexBufferError, mesh::Mesh, solid::Solid, Vertex};
+
+/// Positions, payloads and triangle indices in flat buffers
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IndexBuffers<T = ()> {
+    /// The `[x, y, z]` position of each vertex
+    pub positions: Vec<[f32; 3]>,
+    /// The [payload](crate::Vertex::Payload) of each vertex, in the same order as [IndexBuffers::positions]
+    pub payloads: Vec<T>,
+    /// The indices into [IndexBuffers::positions] of the corners of each triangle, three at a time
+    pub indices: Vec<u32>,
+}
+
+impl<T> IndexBuffers<T> {
+    /// The buffers of a [Mesh], in the plane `z = 0`, with the vertices in the same order and their payloads cloned.
+    ///
+    /// Fails with [IndexBufferError::IndexOverflow] if the mesh has more than `u32::MAX` vertices.
+    pub fn from_mesh<V: Vertex<Payload=T>>(mesh: &Mesh<V>) -> Result<Self, IndexBufferError>
+    where V::Coordinate: Real, T: Clone {
+        Self::new(mesh.vertices.iter().map(|v| [v.x(), v.y(), V::Coordinate::zero()]), mesh.vertices.iter().map(|v| v.payload().clone()).collect(), &mesh.triangles)
+    }
+
+    fn new<C: Real>(positions: impl ExactSizeIterator<Item=[C; 3]>, payloads: Vec<T>, triangles: &[[usize; 3]]) -> Result<Self, IndexBufferError> {
+        if u32::try_from(positions.len()).is_err() {
+            return Err(IndexBufferError::IndexOverflow);
+        }
+        Ok(Self {
+            positions: positions.map(|p| p.map(|c| c.to_f32().unwrap_or(f32::NAN))).collect(),
+            payloads,
+            indices: triangles.iter().flatten().map(|vi| *vi as u32).collect(),
+        })
+    }
+
+    /// Copy the vertices and triangles of `other` to the end of these buffers, such as to draw several meshes with one
+    /// draw call.
+    ///
+    /// Fails with [IndexBufferError::IndexOverflow], leaving these buffers unchanged, if the merged buffers would have
+    /// more than `u32::MAX` vertices.
+    pub fn append(&mut self, other: &IndexBuffers<T>) -> Result<(), IndexBufferError>
+    where T: Clone {
+        self.append_translated(other, [0.; 3])
+    }
+
+    /// Copy the vertices and triangles of `other` to the end of these buffers, moving its positions by `offset`.
+    ///
+    /// Fails with [IndexBufferError::IndexOverflow], leaving these buffers unchanged, if the merged buffers would have
+    /// more than `u32::MAX` vertices.
+    pub fn append_translated(&mut self, other: &IndexBuffers<T>, offset: [f32; 3]) -> Result<(), IndexBufferError>
+    where T: Clone {
+        let base = self.vertex_count()?;
+        if base.checked_add(other.vertex_count()?).is_none() {
+            return Err(IndexBufferError::IndexOverflow);
+        }
+        self.positions.extend(other.positions.iter().map(|p| [p[0] + offset[0], p[1] + offset[1], p[2] + offset[2]]));
+        self.payloads.extend_from_slice(&other.payloads);
+        self.indices.extend(other.indices.iter().map(|vi| vi + base));
+        Ok(())
+    }
+
+    /// Add `base` to every index, such as to place the vertices after `base` others in a shared vertex buffer.
+    ///
+    /// Fails with [IndexBufferError::IndexOverflow], leaving the indices unchanged, if the vertices would not fit in
+    /// `u32` indices after `base`.
+    pub fn rebase(&mut self, base: u32) -> Result<(), IndexBufferError> {
+        if base.checked_add(self.vertex_count()?).is_none() {
+            return Err(IndexBufferError::IndexOverflow);
+        }
+        for vi in self.indices.iter_mut() {
+            *vi += base;
+        }
+        Ok(())
+    }
+
+    /// Add `vertex_count` vertices at the origin with default payloads and `triangle_count` triangles with all corners
+    /// at index 0, to fill in later. Returns the indices of the new vertices, and the range of [IndexBuffers::indices]
+    /// of the new triangles.
+    ///
+    /// Fails with [IndexBufferError::IndexOverflow], leaving the buffers unchanged, if they would have more than
+    /// `u32::MAX` vertices.
+    pub fn reserve(&mut self, vertex_count: usize, triangle_count: usize) -> Result<(Range<u32>, Range<usize>), IndexBufferError>
+    where T: Default {
+        let start = self.vertex_count()?;
+        let end = u32::try_from(vertex_count).ok().and_then(|count| start.checked_add(count)).ok_or(IndexBufferError::IndexOverflow)?;
+        self.positions.resize(self.positions.len() + vertex_count, [0.; 3]);
+        self.payloads.resize_with(self.positions.len(), T::default);
+        let indices = self.indices.len()..self.indices.len() + 3 * triangle_count;
+        self.indices.resize(indices.end, 0);
+        Ok((start..end, indices))
+    }
+
+    /// The buffers of the triangles in `triangles` (by their position, not the range of [IndexBuffers::indices]), with
+    /// only the vertices they use, in the same order.
+    ///
+    /// # Panics
+    /// Panics if the range is out of bounds.
+    pub fn extract(&self, triangles: Range<usize>) -> IndexBuffers<T>
+    where T: Clone {
+        let indices = &self.indices[3 * triangles.start..3 * triangles.end];
+        let mut remap = vec![u32::MAX; self.positions.len()];
+        for vi in indices {
+            remap[*vi as usize] = 0;
+        }
+        let (mut positions, mut payloads) = (Vec::new(), Vec::new());
+        for ((position, payload), new) in self.positions.iter().zip(&self.payloads).zip(remap.iter_mut()) {
+            if *new != u32::MAX {
+                *new = positions.len() as u32;
+                positions.push(*position);
+                payloads.push(payload.clone());
+            }
+        }
+        IndexBuffers { positions, payloads, indices: indices.iter().map(|vi| remap[*vi as usize]).collect() }
+    }
+
+    fn vertex_count(&self) -> Result<u32, IndexBufferError> {
+        u32::try_from(self.positions.len()).map_err(|_| IndexBufferError::IndexOverflow)
+    }
+
+    /// Reorder the triangles and vertices for rendering with meshopt: for the post-transform vertex cache, then to
+    /// reduce overdraw (allowing the cache hit ratio to get up to 5% worse), then for locality of vertex fetches.
+    ///
+    /// Returns the new index of each old vertex, to reorder other vertex attributes the same way. Vertices which are
+    /// not used by any triangle are removed, and map to `u32::MAX`.
+    #[cfg(feature = "meshopt")]
+    pub fn optimize(&mut self) -> Vec<u32> {
+        let vertex_count = self.positions.len();
+        self.indices = meshopt::optimize_vertex_cache(&self.indices, vertex_count);
+        if let Ok(adapter) = meshopt::VertexDataAdapter::new(meshopt::typed_to_bytes(&self.positions), core::mem::size_of::<[f32; 3]>(), 0) {
+            meshopt::optimize_overdraw_in_place(&mut self.indices, &adapter, 1.05);
+        }
+
+        let remap = meshopt::optimize_vertex_fetch_remap(&self.indices, vertex_count);
+        let mut positions = vec![[0.; 3]; remap.iter().filter(|vi| **vi != u32::MAX).count()];
+        for (position, new) in self.positions.iter().zip(&remap) {
+            if *new != u32::MAX {
+                positions[*new as usize] = *position;
+            }
+        }
+        self.positions = positions;
+        let mut payloads: Vec<(u32, T)> = core::mem::take(&mut self.payloads).into_iter().zip(&remap)
+            .filter(|(_, new)| **new != u32::MAX)
+            .map(|(payload, new)| (*new, payload))
+            .collect();
+        payloads.sort_unstable_by_key(|(new, _)| *new);
+        self.payloads = payloads.into_iter().map(|(_, payload)| payload).collect();
+        for vi in self.indices.iter_mut() {
+            *vi = remap[*vi as usize];
+        }
+        remap
+    }
+}
+
+impl IndexBuffers {
+    /// The buffers of a [Solid], such as one made by [Mesh::elevate], with the vertices in the same order.
+    ///
+    /// Fails with [IndexBufferError::IndexOverflow] if the solid has more than `u32::MAX` vertices.
+    pub fn from_solid<C: Real>(solid: &Solid<C>) -> Result<Self, IndexBufferError> {
+        Self::new(solid.positions.iter().copied(), vec![(); solid.positions.len()], &solid.triangles)
+    }
+}
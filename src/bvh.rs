@@ -0,0 +1,176 @@
+//! A bounding volume hierarchy over the triangles of a [Mesh], for fast collision and picking queries.
+//!
+//! The hierarchy only stores triangle indices and bounding boxes, so queries take the mesh it was built from rather
+//! than a copy of it.
+//!
+//! ```
+//! # use triangulate::{bvh::Bvh, mesh::Mesh};
+//! let polygons = vec![vec![[0f64, 0.], [2., 0.], [2., 2.], [0., 2.]]];
+//! let mesh = Mesh::from_polygons(&polygons).expect("Triangulation failed");
+//! let bvh = Bvh::new(&mesh);
+//! assert!(bvh.query_point(&mesh, [0.5, 1.5]).is_some());
+//! assert!(bvh.query_point(&mesh, [3., 1.]).is_none());
+//! assert_eq!(bvh.query_aabb(&mesh, [-1., -1.], [3., 3.]).len(), 2);
+//! ```
+
+use core::{cmp, ops::Range};
+use alloc::{vec, vec::Vec};
+
+use num_traits::real::Real;
+
+use crate::{mesh::Mesh, Vertex};
+
+// The most triangles in a leaf
+const LEAF_SIZE: usize = 4;
+
+/// A hierarchy of axis-aligned bounding boxes over the triangles of a [Mesh], split along the longer axis of each box
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bvh<C: Real> {
+    nodes: Vec<Node<C>>,
+    triangles: Vec<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Node<C: Real> {
+    bounds: [[C; 2]; 2],
+    kind: NodeKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum NodeKind {
+    // A range of `Bvh::triangles`
+    Leaf(Range<usize>),
+    Branch(usize, usize),
+}
+
+impl<C: Real> Bvh<C> {
+    /// Build the hierarchy over the triangles of `mesh`
+    pub fn new<V: Vertex<Coordinate=C>>(mesh: &Mesh<V>) -> Self {
+        let boxes: Vec<[[C; 2]; 2]> = mesh.iter_triangles().map(|t| triangle_bounds(corners(t))).collect();
+        let mut bvh = Self { nodes: Vec::new(), triangles: (0..boxes.len()).collect() };
+        if !boxes.is_empty() {
+            bvh.build(&boxes, 0..boxes.len());
+        }
+        bvh
+    }
+
+    // Build the node over a range of `triangles`, returning its index
+    fn build(&mut self, boxes: &[[[C; 2]; 2]], range: Range<usize>) -> usize {
+        let triangles = &mut self.triangles[range.clone()];
+        let bounds = triangles.iter().map(|ti| boxes[*ti]).reduce(union).unwrap_or([[C::zero(); 2]; 2]);
+        let two = C::one() + C::one();
+        let centroid = |ti: usize, axis: usize| (boxes[ti][0][axis] + boxes[ti][1][axis]) / two;
+        let [min, max] = triangles.iter().map(|ti| [centroid(*ti, 0), centroid(*ti, 1)]).fold([[C::max_value(); 2], [C::min_value(); 2]], |[min, max], c| {
+            [[min[0].min(c[0]), min[1].min(c[1])], [max[0].max(c[0]), max[1].max(c[1])]]
+        });
+        let axis = if max[0] - min[0] >= max[1] - min[1] { 0 } else { 1 };
+
+        let ni = self.nodes.len();
+        // Triangles with the same centroid cannot be separated, so they stay in one leaf
+        if triangles.len() <= LEAF_SIZE || max[axis] <= min[axis] {
+            self.nodes.push(Node { bounds, kind: NodeKind::Leaf(range) });
+            return ni;
+        }
+        let middle = triangles.len() / 2;
+        triangles.select_nth_unstable_by(middle, |a, b| centroid(*a, axis).partial_cmp(&centroid(*b, axis)).unwrap_or(cmp::Ordering::Equal));
+        self.nodes.push(Node { bounds, kind: NodeKind::Branch(0, 0) });
+        let left = self.build(boxes, range.start..range.start + middle);
+        let right = self.build(boxes, range.start + middle..range.end);
+        self.nodes[ni].kind = NodeKind::Branch(left, right);
+        ni
+    }
+
+    /// The bounding box of the whole mesh as `[min, max]`, or `None` if it has no triangles
+    pub fn bounds(&self) -> Option<[[C; 2]; 2]> {
+        self.nodes.first().map(|node| node.bounds)
+    }
+
+    /// The triangles of `mesh` which overlap the box from `min` to `max`, including those which only touch it.
+    ///
+    /// `mesh` must be the mesh the hierarchy was built from.
+    pub fn query_aabb<V: Vertex<Coordinate=C>>(&self, mesh: &Mesh<V>, min: [C; 2], max: [C; 2]) -> Vec<usize> {
+        let query = [min, max];
+        let mut found = Vec::new();
+        self.visit(|bounds| overlaps(bounds, query), |ti| {
+            if triangle_overlaps_box(corners(mesh.triangles[ti].map(|vi| &mesh.vertices[vi])), query) {
+                found.push(ti);
+            }
+            false
+        });
+        found.sort_unstable();
+        found
+    }
+
+    /// A triangle of `mesh` which contains `point`, including on its boundary, or `None` if the point is outside the
+    /// mesh. Where triangles overlap or the point is on an edge between triangles, any of them may be returned.
+    ///
+    /// `mesh` must be the mesh the hierarchy was built from.
+    pub fn query_point<V: Vertex<Coordinate=C>>(&self, mesh: &Mesh<V>, point: [C; 2]) -> Option<usize> {
+        let mut found = None;
+        self.visit(|bounds| overlaps(bounds, [point, point]), |ti| {
+            if triangle_contains(corners(mesh.triangles[ti].map(|vi| &mesh.vertices[vi])), point) {
+                found = Some(ti);
+            }
+            found.is_some()
+        });
+        found
+    }
+
+    // Call `triangle` for every triangle in a leaf whose bounds satisfy `enter`, until it returns true
+    fn visit(&self, enter: impl Fn([[C; 2]; 2]) -> bool, mut triangle: impl FnMut(usize) -> bool) {
+        let mut stack: Vec<usize> = if self.nodes.is_empty() { Vec::new() } else { vec![0] };
+        while let Some(ni) = stack.pop() {
+            let node = &self.nodes[ni];
+            if !enter(node.bounds) {
+                continue;
+            }
+            match &node.kind {
+                NodeKind::Leaf(range) => {
+                    if self.triangles[range.clone()].iter().any(|ti| triangle(*ti)) {
+                        return;
+                    }
+                }
+                NodeKind::Branch(left, right) => stack.extend([*right, *left]),
+            }
+        }
+    }
+}
+
+fn corners<V: Vertex>(t: [&V; 3]) -> [[V::Coordinate; 2]; 3] {
+    t.map(|v| [v.x(), v.y()])
+}
+
+fn triangle_bounds<C: Real>([a, b, c]: [[C; 2]; 3]) -> [[C; 2]; 2] {
+    [[a[0].min(b[0]).min(c[0]), a[1].min(b[1]).min(c[1])], [a[0].max(b[0]).max(c[0]), a[1].max(b[1]).max(c[1])]]
+}
+
+fn union<C: Real>(a: [[C; 2]; 2], b: [[C; 2]; 2]) -> [[C; 2]; 2] {
+    [[a[0][0].min(b[0][0]), a[0][1].min(b[0][1])], [a[1][0].max(b[1][0]), a[1][1].max(b[1][1])]]
+}
+
+fn overlaps<C: Real>(a: [[C; 2]; 2], b: [[C; 2]; 2]) -> bool {
+    a[0][0] <= b[1][0] && b[0][0] <= a[1][0] && a[0][1] <= b[1][1] && b[0][1] <= a[1][1]
+}
+
+fn cross<C: Real>(a: [C; 2], b: [C; 2], c: [C; 2]) -> C {
+    (b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1])
+}
+
+fn triangle_contains<C: Real>(t: [[C; 2]; 3], p: [C; 2]) -> bool {
+    let sides = [0, 1, 2].map(|k| cross(t[k], t[(k + 1) % 3], p));
+    sides.iter().all(|side| *side >= C::zero()) || sides.iter().all(|side| *side <= C::zero())
+}
+
+// Separating axis test between a triangle and a box: the axes of the box, then the edges of the triangle
+fn triangle_overlaps_box<C: Real>(t: [[C; 2]; 3], bounds: [[C; 2]; 2]) -> bool {
+    if !overlaps(triangle_bounds(t), bounds) {
+        return false;
+    }
+    let orientation = cross(t[0], t[1], t[2]);
+    if orientation == C::zero() {
+        return true;
+    }
+    let [min, max] = bounds;
+    let box_corners = [min, [max[0], min[1]], max, [min[0], max[1]]];
+    (0..3).all(|k| box_corners.iter().any(|c| cross(t[k], t[(k + 1) % 3], *c) * orientation >= C::zero()))
+}
@@ -0,0 +1,12 @@
+// Stand-ins for the parts of `std` used by the crate, for builds without the `std` feature
+
+#[cfg(feature = "std")]
+pub(crate) use std::{collections::{HashMap, HashSet, hash_map::Entry}, sync::OnceLock};
+
+// Without a random hasher, ordered maps take the place of hash maps. Their keys are all small integer arrays and tuples,
+// and results never depend on the iteration order.
+#[cfg(not(feature = "std"))]
+pub(crate) use alloc::collections::{BTreeMap as HashMap, BTreeSet as HashSet, btree_map::Entry};
+// Without `std` there is no thread-safe lazy initialization, so lazily computed values can't be shared between threads
+#[cfg(not(feature = "std"))]
+pub(crate) use core::cell::OnceCell as OnceLock;
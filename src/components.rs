@@ -0,0 +1,121 @@
+//! Connected components of the filled area: the parts of the inside of the polygons which are disjoint from each
+//! other, such as to check that an imported shape is in one piece, or to process each piece separately.
+//!
+//! Components are found over the trapezoids of a [Trapezoidation](crate::Trapezoidation) with
+//! [Trapezoidation::components](crate::Trapezoidation::components), or over the triangles of a
+//! [Mesh](crate::mesh::Mesh) with [Mesh::components](crate::mesh::Mesh::components). Parts which only touch at a
+//! vertex are separate components.
+//!
+//! ```
+//! # use triangulate::{mesh::Mesh, PolygonList};
+//! let polygons = vec![
+//!     vec![[0f64, 0.], [4., 0.], [4., 4.], [0., 4.]],
+//!     vec![[1., 1.], [1., 3.], [3., 3.], [3., 1.]],
+//!     vec![[5., 0.], [7., 0.], [6., 2.]],
+//! ];
+//! let components = polygons.trapezoidize().expect("Trapezoidation failed").components().expect("Trapezoidation failed");
+//! assert_eq!(components.count, 2);
+//! assert!(!components.is_connected());
+//!
+//! let mesh = Mesh::from_polygons(&polygons).expect("Triangulation failed");
+//! let components = mesh.components();
+//! assert_eq!(components.count, 2);
+//! assert_eq!((0..2).map(|c| components.members(c).count()).min(), Some(1));
+//! ```
+
+use alloc::{vec, vec::Vec};
+
+use num_traits::real::Real;
+
+/// The connected components of a set of elements, such as trapezoids or triangles
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Components {
+    /// The number of components
+    pub count: usize,
+    /// The component of each element, by index, or `None` for elements which are not part of the filled area, such as
+    /// the trapezoids outside the polygons. Components are numbered in the order of their first element
+    pub labels: Vec<Option<usize>>,
+}
+
+impl Components {
+    /// Whether the filled area is in one piece. An empty area is not connected
+    pub fn is_connected(&self) -> bool {
+        self.count == 1
+    }
+
+    /// The indices of the elements in `component`
+    pub fn members(&self, component: usize) -> impl Iterator<Item=usize> + '_ {
+        self.labels.iter().enumerate().filter(move |(_, label)| **label == Some(component)).map(|(i, _)| i)
+    }
+
+    /// Number the components of `filled` elements, joined by the unions made in `sets`
+    pub(crate) fn from_union_find(sets: &mut UnionFind, filled: impl Fn(usize) -> bool) -> Self {
+        let mut numbers = vec![None; sets.len()];
+        let mut count = 0;
+        let labels = (0..sets.len()).map(|i| {
+            if !filled(i) {
+                return None;
+            }
+            let root = sets.find(i);
+            Some(*numbers[root].get_or_insert_with(|| {
+                count += 1;
+                count - 1
+            }))
+        }).collect();
+        Self { count, labels }
+    }
+}
+
+/// Descriptive statistics of one connected component, found by
+/// [Trapezoidation::component_summaries](crate::Trapezoidation::component_summaries)
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ComponentSummary<C: Real> {
+    /// The filled area
+    pub area: C,
+    /// The total length of the outer boundary and the boundaries of the holes
+    pub perimeter: C,
+    /// The `[min, max]` corners of the bounding box
+    pub bounds: [[C; 2]; 2],
+    /// The number of holes
+    pub holes: usize,
+}
+
+/// Disjoint sets of elements, merged by union by size with path halving
+#[derive(Debug, Clone)]
+pub(crate) struct UnionFind {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+}
+
+impl UnionFind {
+    /// Each of `len` elements in a set of its own
+    pub fn new(len: usize) -> Self {
+        Self { parents: (0..len).collect(), sizes: vec![1; len] }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    /// The representative element of the set containing `i`
+    pub fn find(&mut self, mut i: usize) -> usize {
+        while self.parents[i] != i {
+            self.parents[i] = self.parents[self.parents[i]];
+            i = self.parents[i];
+        }
+        i
+    }
+
+    /// Merge the sets containing `i` and `j`
+    pub fn union(&mut self, i: usize, j: usize) {
+        let (mut i, mut j) = (self.find(i), self.find(j));
+        if i == j {
+            return;
+        }
+        if self.sizes[i] < self.sizes[j] {
+            core::mem::swap(&mut i, &mut j);
+        }
+        self.parents[j] = i;
+        self.sizes[i] += self.sizes[j];
+    }
+}
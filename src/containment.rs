@@ -0,0 +1,265 @@
+//! The containment hierarchy of polygons: which contours are nested inside which.
+//!
+//! Contours are numbered by the non-empty polygons of a [PolygonList] in iteration order. By the even-odd rule, as in
+//! triangulation, contours at an odd depth are holes, and contours nested inside holes are islands ('enclaves') again.
+//!
+//! ```
+//! # use triangulate::containment::ContainmentTree;
+//! let polygons = vec![
+//!     vec![[0f64, 0.], [9., 0.], [9., 9.], [0., 9.]],
+//!     vec![[1., 1.], [1., 8.], [8., 8.], [8., 1.]],
+//!     vec![[3., 3.], [6., 3.], [6., 6.], [3., 6.]],
+//!     vec![[10., 0.], [12., 0.], [12., 2.]],
+//! ];
+//! let tree = ContainmentTree::new(&polygons);
+//! assert_eq!(tree.roots(), &[0, 3]);
+//! assert_eq!(tree.children(0), &[1]);
+//! assert_eq!(tree.parent(2), Some(1));
+//! assert!(tree.is_hole(1));
+//! assert_eq!(tree.depth(2), 2);
+//! ```
+//!
+//! [paint_order] instead treats each contour as a separate filled shape, and orders overlapping shapes for renderers
+//! without a depth buffer, which paint them back to front. [group_rings] assembles unordered rings into polygons with
+//! holes.
+
+use core::{cmp::Reverse, fmt, iter};
+use alloc::{collections::{BTreeSet, BinaryHeap}, vec, vec::Vec};
+
+use num_traits::real::Real;
+
+use crate::{boolean::Contours, intersection, math, PolygonList, PolygonListExt, Vertex};
+
+/// The tree of contours nested inside each other
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainmentTree {
+    parents: Vec<Option<usize>>,
+    children: Vec<Vec<usize>>,
+    roots: Vec<usize>,
+}
+
+impl ContainmentTree {
+    /// Find the containment hierarchy of the contours of `polygons`.
+    ///
+    /// A contour's parent is the smallest other contour which contains its first vertex. Contours which cross each
+    /// other do not have a well-defined hierarchy.
+    pub fn new<'p, P: PolygonList<'p> + ?Sized>(polygons: &'p P) -> Self
+    where <P::Vertex as Vertex>::Coordinate: Real {
+        Self::from_contours(&PolygonListExt::new(polygons).contours())
+    }
+
+    pub(crate) fn from_contours<C: Real>(contours: &[Vec<[C; 2]>]) -> Self {
+        let parents = math::contour_parents(contours);
+        let mut children = vec![Vec::new(); parents.len()];
+        let mut roots = Vec::new();
+        for (ci, parent) in parents.iter().enumerate() {
+            match parent {
+                Some(cj) => children[*cj].push(ci),
+                None => roots.push(ci),
+            }
+        }
+        Self { parents, children, roots }
+    }
+
+    /// The number of contours
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    /// Whether there are no contours
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// The contours which are not inside any other, in order
+    pub fn roots(&self) -> &[usize] {
+        &self.roots
+    }
+
+    /// The innermost contour containing contour `ci`
+    pub fn parent(&self, ci: usize) -> Option<usize> {
+        self.parents[ci]
+    }
+
+    /// The contours directly inside contour `ci`, in order
+    pub fn children(&self, ci: usize) -> &[usize] {
+        &self.children[ci]
+    }
+
+    /// The contours containing contour `ci`, from its parent outwards
+    pub fn ancestors(&self, ci: usize) -> impl Iterator<Item=usize> + '_ {
+        iter::successors(self.parents[ci], |&cj| self.parents[cj])
+    }
+
+    /// The number of contours containing contour `ci`
+    pub fn depth(&self, ci: usize) -> usize {
+        self.ancestors(ci).count()
+    }
+
+    /// Whether contour `ci` is a hole, being nested at an odd depth
+    pub fn is_hole(&self, ci: usize) -> bool {
+        self.depth(ci) % 2 == 1
+    }
+
+    /// The contours inside contour `ci` at any depth, in depth-first pre-order
+    pub fn descendants(&self, ci: usize) -> impl Iterator<Item=usize> + '_ {
+        DepthFirst { tree: self, stack: self.children[ci].iter().rev().copied().collect() }
+    }
+
+    /// All contours in depth-first pre-order, so each contour comes before the contours inside it
+    pub fn iter_depth_first(&self) -> impl Iterator<Item=usize> + '_ {
+        DepthFirst { tree: self, stack: self.roots.iter().rev().copied().collect() }
+    }
+
+    /// The contours grouped into polygons with holes: each contour which is not a hole, followed by its holes, in
+    /// depth-first pre-order
+    pub fn groups(&self) -> Vec<Vec<usize>> {
+        self.iter_depth_first()
+            .filter(|&ci| !self.is_hole(ci))
+            .map(|ci| iter::once(ci).chain(self.children[ci].iter().copied()).collect())
+            .collect()
+    }
+}
+
+struct DepthFirst<'t> {
+    tree: &'t ContainmentTree,
+    stack: Vec<usize>,
+}
+
+impl Iterator for DepthFirst<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let ci = self.stack.pop()?;
+        self.stack.extend(self.tree.children[ci].iter().rev());
+        Some(ci)
+    }
+}
+
+/// Assemble unordered rings, as GIS data often arrives, into polygons with holes, each as its outer ring followed by
+/// its holes, in the order of [ContainmentTree::groups].
+///
+/// Roles are found from the nesting of the rings by the even-odd rule, not from their winding, and the rings are
+/// rewound so that outer rings are counter-clockwise and holes clockwise. Empty rings are skipped.
+///
+/// ```
+/// # use triangulate::{containment, PolygonList};
+/// let rings = vec![
+///     vec![[1f64, 1.], [3., 1.], [3., 3.], [1., 3.]],
+///     vec![[5., 0.], [6., 0.], [6., 1.]],
+///     vec![[0., 0.], [0., 4.], [4., 4.], [4., 0.]],
+/// ];
+/// let polygons = containment::group_rings(&rings);
+/// assert_eq!(polygons.len(), 2);
+/// assert_eq!(polygons[0], vec![rings[1].clone()]);
+/// assert_eq!(polygons[1][0], vec![[4., 0.], [4., 4.], [0., 4.], [0., 0.]]);
+/// assert_eq!(polygons[1][1], vec![[1., 3.], [3., 3.], [3., 1.], [1., 1.]]);
+/// assert!(polygons.iter().all(|polygon| polygon.trapezoidize().is_ok()));
+/// ```
+pub fn group_rings<'p, P: PolygonList<'p> + ?Sized>(polygons: &'p P) -> Vec<Contours<<P::Vertex as Vertex>::Coordinate>>
+where <P::Vertex as Vertex>::Coordinate: Real {
+    group_contours(PolygonListExt::new(polygons).contours())
+}
+
+fn group_contours<C: Real>(mut contours: Contours<C>) -> Vec<Contours<C>> {
+    let tree = ContainmentTree::from_contours(&contours);
+    for (ci, contour) in contours.iter_mut().enumerate() {
+        if (math::signed_area_2(contour) < C::zero()) != tree.is_hole(ci) {
+            contour.reverse();
+        }
+    }
+    tree.groups().into_iter().map(|group| group.into_iter().map(|ci| core::mem::take(&mut contours[ci])).collect()).collect()
+}
+
+/// An order to paint the contours of a [PolygonList] in, each as a separate filled shape, created by [paint_order]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaintOrder {
+    /// The contours from back to front
+    pub order: Vec<usize>,
+    /// The pairs of contours whose edges cross or touch, each with the smaller contour index first
+    pub crossing: Vec<[usize; 2]>,
+    /// The nesting of the contours
+    pub tree: ContainmentTree,
+}
+
+/// Order the contours of `polygons` back to front, so that painting them in turn draws each shape over the shapes it
+/// lies inside.
+///
+/// A contour comes after every contour containing it. Contours whose edges cross overlap only partly, and are painted
+/// in their order in `polygons`, as SVG does; where that conflicts with containment, as can happen when three or
+/// more contours cross, the earlier contour is painted first. Otherwise contours keep their order in `polygons`.
+///
+/// ```
+/// # use triangulate::containment;
+/// let polygons = vec![
+///     vec![[3f64, 3.], [6., 3.], [6., 6.], [3., 6.]],
+///     vec![[0., 0.], [9., 0.], [9., 9.], [0., 9.]],
+///     vec![[5., 5.], [8., 5.], [8., 8.], [5., 8.]],
+/// ];
+/// let paint = containment::paint_order(&polygons);
+/// assert_eq!(paint.order, vec![1, 0, 2]);
+/// assert_eq!(paint.crossing, vec![[0, 2]]);
+/// ```
+pub fn paint_order<'p, P: PolygonList<'p> + ?Sized>(polygons: &'p P) -> PaintOrder
+where <P::Vertex as Vertex>::Coordinate: Real + fmt::Debug {
+    let contours = PolygonListExt::new(polygons).contours();
+    let tree = ContainmentTree::from_contours(&contours);
+
+    let mut edges = Vec::new();
+    let mut owners = Vec::new();
+    for (ci, contour) in contours.iter().enumerate() {
+        for (k, c0) in contour.iter().enumerate() {
+            edges.push([*c0, contour[(k + 1) % contour.len()]]);
+            owners.push(ci);
+        }
+    }
+    let mut crossing = BTreeSet::new();
+    for found in intersection::find_intersections(&edges) {
+        let segments = found.segments();
+        for (k, &si) in segments.iter().enumerate() {
+            for &sj in &segments[k + 1..] {
+                let (ci, cj) = (owners[si], owners[sj]);
+                if ci != cj {
+                    crossing.insert([ci.min(cj), ci.max(cj)]);
+                }
+            }
+        }
+    }
+
+    // Kahn's algorithm, taking the earliest contour which has nothing left to be painted after
+    // A contour is inside its innermost container which it does not cross
+    let mut after: Vec<Vec<usize>> = vec![Vec::new(); contours.len()];
+    for ci in 0..contours.len() {
+        if let Some(container) = tree.ancestors(ci).find(|&cj| !crossing.contains(&[cj.min(ci), cj.max(ci)])) {
+            after[container].push(ci);
+        }
+    }
+    for [ci, cj] in crossing.iter() {
+        after[*ci].push(*cj);
+    }
+    let mut before_count = vec![0; contours.len()];
+    for cj in after.iter().flatten() {
+        before_count[*cj] += 1;
+    }
+    let mut ready: BinaryHeap<Reverse<usize>> = (0..contours.len()).filter(|&ci| before_count[ci] == 0).map(Reverse).collect();
+    let mut painted = vec![false; contours.len()];
+    let mut order = Vec::with_capacity(contours.len());
+    while order.len() < contours.len() {
+        // A cycle is broken at its earliest contour
+        let ci = match ready.pop() {
+            Some(Reverse(ci)) if painted[ci] => continue,
+            Some(Reverse(ci)) => ci,
+            None => (0..contours.len()).find(|&ci| !painted[ci]).unwrap_or(0),
+        };
+        painted[ci] = true;
+        order.push(ci);
+        for &cj in &after[ci] {
+            before_count[cj] -= 1;
+            if before_count[cj] == 0 && !painted[cj] {
+                ready.push(Reverse(cj));
+            }
+        }
+    }
+
+    PaintOrder { order, crossing: crossing.into_iter().collect(), tree }
+}
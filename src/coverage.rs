@@ -0,0 +1,204 @@
+//! Cleaning of polygon coverages, such as cadastral parcels or administrative boundaries, where adjacent features are
+//! meant to share their borders exactly.
+//!
+//! Borders digitized separately rarely match: vertices on either side are a little apart, one side has vertices the
+//! other lacks, and the result is a scatter of slivers and gaps between features. [snap] merges vertices closer than a
+//! tolerance and inserts them into nearby borders, so that shared borders consist of the same edges on both sides.
+//! [clean] also gives the overlaps left after snapping to the first feature which covers them, and fills small gaps
+//! from the neighbor which shares the longest border with them.
+//!
+//! ```
+//! # use triangulate::coverage;
+//! // Two parcels, with a border which almost matches
+//! let parcels = vec![
+//!     vec![vec![[0f64, 0.], [1., 0.], [1., 1.], [0., 1.]]],
+//!     vec![vec![[1.001, 0.], [2., 0.], [2., 1.], [0.999, 1.], [1.005, 0.5]]],
+//! ];
+//! let cleaned = coverage::snap(&parcels, 0.01);
+//! // Both sides of the border now run through the same vertices
+//! assert_eq!(cleaned[0], vec![vec![[0., 0.], [1., 0.], [1.005, 0.5], [1., 1.], [0., 1.]]]);
+//! assert_eq!(cleaned[1], vec![vec![[1., 0.], [2., 0.], [2., 1.], [1., 1.], [1.005, 0.5]]]);
+//! ```
+
+use core::{cmp, fmt, slice};
+use alloc::vec::Vec;
+
+use num_traits::real::Real;
+
+use crate::{boolean::{self, BooleanOp, Contours}, compat::HashMap, math, PolygonList, PolygonListExt, Vertex};
+
+/// Snap the borders of `features` together, so that vertices closer than `tolerance` are merged and vertices closer
+/// than `tolerance` to an edge are inserted into it.
+///
+/// Vertices are merged into the first vertex within `tolerance` of them, in the order of the features. Rings are then
+/// rid of the repeated vertices and spikes that snapping leaves, and dropped when they have no area left. The result
+/// has the same number of features as the input, some of which may be empty.
+pub fn snap<'p, P, C>(features: &'p [P], tolerance: C) -> Vec<Contours<C>>
+where P: PolygonList<'p>,
+      P::Vertex: Vertex<Coordinate=C>,
+      C: Real + fmt::Debug {
+    let features: Vec<Contours<C>> = features.iter().map(|feature| PolygonListExt::new(feature).contours()).collect();
+    snap_contours(&features, tolerance)
+}
+
+/// [snap] the borders of `features` together and resolve what is left of their overlaps and gaps.
+///
+/// Where snapped features still overlap, the overlap is kept only by the first of them. Gaps enclosed by the features
+/// with an area of at most `max_gap_area` are merged into the feature which shares the longest border with them.
+/// Features are returned with counter-clockwise outer boundaries and clockwise holes, like the results of the
+/// [boolean] operations.
+pub fn clean<'p, P, C>(features: &'p [P], tolerance: C, max_gap_area: C) -> Vec<Contours<C>>
+where P: PolygonList<'p>,
+      P::Vertex: Vertex<Coordinate=C>,
+      C: Real + fmt::Debug {
+    let snapped = snap(features, tolerance);
+
+    let mut features: Vec<Contours<C>> = Vec::with_capacity(snapped.len());
+    for feature in snapped {
+        let Some([min, max]) = math::bounding_box(feature.iter().flatten()) else {
+            features.push(feature);
+            continue;
+        };
+        let earlier: Vec<Contours<C>> = features.iter()
+            .filter(|other| match math::bounding_box(other.iter().flatten()) {
+                Some([other_min, other_max]) => min[0] < other_max[0] && other_min[0] < max[0] && min[1] < other_max[1] && other_min[1] < max[1],
+                None => false,
+            })
+            .cloned()
+            .collect();
+        let covered = boolean::union_range(&earlier);
+        features.push(boolean::overlay(&feature, &covered, BooleanOp::Difference));
+    }
+
+    if max_gap_area > C::zero() {
+        let two = C::one() + C::one();
+        let union = boolean::union_range(&features);
+        let scale = math::bounding_box(union.iter().flatten()).map_or(C::zero(), |[min, max]| (max[0] - min[0]).max(max[1] - min[1]));
+        let epsilon = tolerance.max(scale * C::from(1e-9).unwrap_or_else(C::epsilon));
+        for gap in union.iter().filter(|contour| -math::signed_area_2(contour) / two <= max_gap_area && math::signed_area_2(contour) < C::zero()) {
+            let shared = features.iter().map(|feature| shared_length(gap, feature, epsilon));
+            let Some((fi, length)) = shared.enumerate().max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(cmp::Ordering::Equal)) else { continue };
+            if length > C::zero() {
+                features[fi] = boolean::overlay(&features[fi], slice::from_ref(gap), BooleanOp::Union);
+            }
+        }
+    }
+    features
+}
+
+fn snap_contours<C: Real + fmt::Debug>(features: &[Contours<C>], tolerance: C) -> Vec<Contours<C>> {
+    // Merge vertices into the first one within the tolerance, found through a grid of cells as wide as the tolerance
+    let mut points: Vec<[C; 2]> = Vec::new();
+    let mut grid: HashMap<[i64; 2], Vec<usize>> = HashMap::new();
+    let cell = |c: [C; 2]| -> Option<[i64; 2]> {
+        if tolerance > C::zero() {
+            Some([(c[0] / tolerance).floor().to_i64()?, (c[1] / tolerance).floor().to_i64()?])
+        } else {
+            None
+        }
+    };
+    let mut snap_point = |c: [C; 2]| -> [C; 2] {
+        let Some([x, y]) = cell(c) else {
+            points.push(c);
+            return c;
+        };
+        let nearest = (-1..=1).flat_map(|dx| (-1..=1).map(move |dy| [x + dx, y + dy]))
+            .filter_map(|key| grid.get(&key))
+            .flatten()
+            .map(|pi| points[*pi])
+            .find(|p| distance(*p, c) <= tolerance);
+        match nearest {
+            Some(p) => p,
+            None => {
+                grid.entry([x, y]).or_default().push(points.len());
+                points.push(c);
+                c
+            }
+        }
+    };
+    let snapped: Vec<Contours<C>> = features.iter().map(|feature| {
+        feature.iter().map(|ring| ring.iter().map(|c| snap_point(*c)).collect()).collect()
+    }).collect();
+
+    // Insert the vertices near each edge into it, found among the vertices sorted by x
+    points.sort_by(|a, b| a[0].partial_cmp(&b[0]).unwrap_or(cmp::Ordering::Equal));
+    points.dedup();
+    snapped.iter().map(|feature| {
+        feature.iter().filter_map(|ring| {
+            let mut snapped_ring = Vec::with_capacity(ring.len());
+            for (k, a) in ring.iter().enumerate() {
+                let b = ring[(k + 1) % ring.len()];
+                snapped_ring.push(*a);
+                let start = points.partition_point(|p| p[0] < a[0].min(b[0]) - tolerance);
+                let end = points.partition_point(|p| p[0] <= a[0].max(b[0]) + tolerance);
+                let mut inserted: Vec<(C, [C; 2])> = points[start..end].iter()
+                    .filter(|p| **p != *a && **p != b)
+                    .filter_map(|p| {
+                        let (t, nearest) = project(*p, *a, b);
+                        (t > C::zero() && t < C::one() && distance(nearest, *p) <= tolerance).then_some((t, *p))
+                    })
+                    .collect();
+                inserted.sort_by(|(t0, _), (t1, _)| t0.partial_cmp(t1).unwrap_or(cmp::Ordering::Equal));
+                snapped_ring.extend(inserted.into_iter().map(|(_, p)| p));
+            }
+            let ring = remove_spikes(snapped_ring);
+            (ring.len() >= 3 && math::signed_area_2(&ring) != C::zero()).then_some(ring)
+        }).collect()
+    }).collect()
+}
+
+// Remove repeated vertices, and the spikes which go out along an edge and come back along it
+fn remove_spikes<C: Real>(ring: Vec<[C; 2]>) -> Vec<[C; 2]> {
+    let mut result: Vec<[C; 2]> = Vec::with_capacity(ring.len());
+    for c in ring {
+        if result.last() == Some(&c) {
+            continue;
+        }
+        if result.len() >= 2 && result[result.len() - 2] == c {
+            result.pop();
+            continue;
+        }
+        result.push(c);
+    }
+    // The same again around the start of the ring
+    loop {
+        let len = result.len();
+        if len >= 2 && result[0] == result[len - 1] || len >= 3 && result[len - 2] == result[0] {
+            result.pop();
+        } else if len >= 3 && result[len - 1] == result[1] {
+            result.remove(0);
+        } else {
+            return result;
+        }
+    }
+}
+
+// The total length of the edges of `gap` which lie along the borders of `feature`
+fn shared_length<C: Real>(gap: &[[C; 2]], feature: &Contours<C>, epsilon: C) -> C {
+    let two = C::one() + C::one();
+    gap.iter().enumerate().map(|(k, a)| {
+        let b = gap[(k + 1) % gap.len()];
+        let midpoint = [(a[0] + b[0]) / two, (a[1] + b[1]) / two];
+        let on_border = feature.iter().any(|ring| ring.iter().enumerate().any(|(j, c)| {
+            let (_, nearest) = project(midpoint, *c, ring[(j + 1) % ring.len()]);
+            distance(nearest, midpoint) <= epsilon
+        }));
+        if on_border { distance(*a, b) } else { C::zero() }
+    }).fold(C::zero(), |sum, length| sum + length)
+}
+
+// The parameter along `a`-`b` of the closest point to `p`, and that closest point on the segment
+fn project<C: Real>(p: [C; 2], a: [C; 2], b: [C; 2]) -> (C, [C; 2]) {
+    let d = [b[0] - a[0], b[1] - a[1]];
+    let length_squared = d[0] * d[0] + d[1] * d[1];
+    if length_squared == C::zero() {
+        return (C::zero(), a);
+    }
+    let t = ((p[0] - a[0]) * d[0] + (p[1] - a[1]) * d[1]) / length_squared;
+    let clamped = t.max(C::zero()).min(C::one());
+    (t, [a[0] + d[0] * clamped, a[1] + d[1] * clamped])
+}
+
+fn distance<C: Real>(a: [C; 2], b: [C; 2]) -> C {
+    (b[0] - a[0]).hypot(b[1] - a[1])
+}
@@ -169,5 +169,18 @@ pub(crate) fn circle(x: f32, y: f32, r: f32) -> svg_fmt::Circle {
         y,
         radius: r,
         style: svg_fmt::Style::default(),
+        comment: None,
     }
 }
+
+// svg_fmt's Polygon writes its points outside of the quotes of the path data, so build the path instead
+pub(crate) fn polygon(points: &[[f32; 2]], closed: bool) -> svg_fmt::Path {
+    let mut path = svg_fmt::path();
+    for (i, &[x, y]) in points.iter().enumerate() {
+        path = if i == 0 { path.move_to(x, y) } else { path.line_to(x, y) };
+    }
+    if closed {
+        path = path.close();
+    }
+    path
+}
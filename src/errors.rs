@@ -1,6 +1,11 @@
-use std::{error, fmt};
+use core::{convert, error, fmt, fmt::Write};
+use alloc::{boxed::Box, format, string::String, vec::Vec};
 
+#[cfg(feature = "std")]
 use backtrace::Backtrace;
+use crate::Scalar;
+
+use crate::float_format::{Float, FloatFormat, FormatFloats};
 
 /// Describes an error which occurred during trapezoidation
 #[derive(Debug)]
@@ -14,35 +19,228 @@ pub enum TrapezoidationError {
 
 impl error::Error for TrapezoidationError { }
 
+impl TrapezoidationError {
+    /// An SVG image of the geometry involved in the error, if known. See [InternalError::to_svg]
+    pub fn to_svg(&self) -> Option<String> {
+        match self {
+            Self::InternalError(error) => error.to_svg(),
+            _ => None,
+        }
+    }
+
+    /// The step of the algorithm which failed, if known. See [InternalError::stage]
+    pub fn stage(&self) -> Option<ErrorStage> {
+        match self {
+            Self::InternalError(error) => error.stage,
+            _ => None,
+        }
+    }
+}
+
 impl fmt::Display for TrapezoidationError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_floats(f, &FloatFormat::for_formatter(f))
+    }
+}
+
+impl FormatFloats for TrapezoidationError {
+    fn fmt_floats(&self, f: &mut fmt::Formatter<'_>, format: &FloatFormat) -> fmt::Result {
         match self {
             Self::NotEnoughVertices(vertices) => write!(f, "Polygon only contains {} vertices", vertices),
-            Self::InternalError(error) => fmt::Display::fmt(error, f),
+            Self::InternalError(error) => error.fmt_floats(f, format),
         }
     }
 }
 
+/// A precondition violated by the input (such as intersecting polygons), or a bug
 #[derive(Debug)]
 pub struct InternalError {
+    /// A description of the error
     pub msg: String,
+    /// Where the error occurred. Requires the `std` feature.
+    #[cfg(feature = "std")]
     pub backtrace: Backtrace,
+    /// The step of the algorithm which failed, if known
+    pub stage: Option<ErrorStage>,
+    /// The input geometry being processed when the error occurred, if known. Boxed, so errors stay small on the
+    /// paths that don't fail.
+    pub geometry: Option<Box<ErrorGeometry>>,
 }
 
+/// The step of triangulation during which an [InternalError] occurred
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorStage {
+    /// Inserting the vertices of a polygon into the trapezoidation, and threading its segments through the
+    /// trapezoids they cross. Failures here usually mean the polygons intersect.
+    SegmentThreading,
+    /// Walking the finished trapezoidation to find which trapezoids are inside the polygons, or which are adjacent
+    TrapezoidTraversal,
+    /// Splitting the inside of the polygons into monotone polygons
+    MonotoneSplit,
+    /// Splitting a monotone polygon into triangle fans
+    FanTriangulation,
+}
+
+impl fmt::Display for ErrorStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::SegmentThreading => "segment threading",
+            Self::TrapezoidTraversal => "trapezoid traversal",
+            Self::MonotoneSplit => "monotone splitting",
+            Self::FanTriangulation => "fan triangulation",
+        })
+    }
+}
+
+/// The input geometry involved in an [InternalError], converted to `f64`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ErrorGeometry {
+    /// The vertices of the polygon being inserted
+    pub polygon: Vec<[f64; 2]>,
+    /// The position of the polygon being inserted among the polygons of the [PolygonList](crate::PolygonList), not
+    /// counting polygons without vertices
+    pub polygon_index: Option<usize>,
+    /// The segment of the polygon being inserted
+    pub segment: Option<[[f64; 2]; 2]>,
+    /// The positions of the segment's vertices within [ErrorGeometry::polygon]
+    pub segment_vertices: Option<[usize; 2]>,
+}
+
+// The most polygon vertices listed by `Display`
+const DISPLAY_VERTICES: usize = 32;
+
 impl InternalError {
     #[cold]
     #[inline(always)]
     pub(crate) fn new(msg: impl Into<String>) -> Self {
         Self {
             msg: msg.into(),
+            #[cfg(feature = "std")]
             backtrace: Backtrace::new_unresolved(),
+            stage: None,
+            geometry: None,
+        }
+    }
+
+    /// Record the step of the algorithm which failed, unless a step is already known
+    #[cold]
+    pub(crate) fn with_stage(mut self, stage: ErrorStage) -> Self {
+        self.stage.get_or_insert(stage);
+        self
+    }
+
+    /// Record the segment being inserted, between the vertices at `vertices` in its polygon, unless a segment is
+    /// already known
+    #[cold]
+    pub(crate) fn with_segment<C: Scalar>(mut self, segment: [[C; 2]; 2], vertices: [usize; 2]) -> Self {
+        let geometry = self.geometry.get_or_insert_with(Box::default);
+        if geometry.segment.is_none() {
+            geometry.segment = Some(segment.map(to_f64));
+            geometry.segment_vertices = Some(vertices);
+        }
+        self
+    }
+
+    /// Record the polygon being inserted, at `index` among the polygons, unless a polygon is already known
+    #[cold]
+    pub(crate) fn with_polygon<C: Scalar>(mut self, polygon: impl IntoIterator<Item=[C; 2]>, index: usize) -> Self {
+        let geometry = self.geometry.get_or_insert_with(Box::default);
+        if geometry.polygon.is_empty() {
+            geometry.polygon = polygon.into_iter().map(to_f64).collect();
+            geometry.polygon_index = Some(index);
+        }
+        self
+    }
+
+    /// An SVG image of the [geometry](InternalError::geometry) involved in the error, if known, to attach to bug
+    /// reports: the polygon being inserted with its vertices, and the segment being inserted in red.
+    pub fn to_svg(&self) -> Option<String> {
+        let geometry = self.geometry.as_ref()?;
+        let points: Vec<[f64; 2]> = geometry.polygon.iter().copied().chain(geometry.segment.into_iter().flatten()).collect();
+        let [min_x, min_y, max_x, max_y] = points.iter().fold([f64::MAX, f64::MAX, f64::MIN, f64::MIN], |[x0, y0, x1, y1], [x, y]| {
+            [x0.min(*x), y0.min(*y), x1.max(*x), y1.max(*y)]
+        });
+        if points.is_empty() {
+            return None;
+        }
+        let margin = (max_x - min_x).max(max_y - min_y).max(f64::EPSILON) * 0.05;
+        let radius = margin / 4.;
+
+        // Flip the y axis so the image has y pointing up, like the input
+        let mut svg = String::new();
+        let _ = writeln!(svg, r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}">"#, min_x - margin, -max_y - margin, max_x - min_x + 2. * margin, max_y - min_y + 2. * margin);
+        let _ = writeln!(svg, r#"<g transform="scale(1 -1)">"#);
+        if !geometry.polygon.is_empty() {
+            let points: Vec<String> = geometry.polygon.iter().map(|[x, y]| format!("{x},{y}")).collect();
+            let _ = writeln!(svg, r#"<polygon points="{}" fill="lightgray" stroke="black" vector-effect="non-scaling-stroke"/>"#, points.join(" "));
+            for [x, y] in geometry.polygon.iter() {
+                let _ = writeln!(svg, r#"<circle cx="{x}" cy="{y}" r="{radius}" fill="black"/>"#);
+            }
+        }
+        if let Some([[x0, y0], [x1, y1]]) = geometry.segment {
+            let _ = writeln!(svg, r#"<line x1="{x0}" y1="{y0}" x2="{x1}" y2="{y1}" stroke="red" stroke-width="2" vector-effect="non-scaling-stroke"/>"#);
         }
+        svg.push_str("</g>\n</svg>\n");
+        Some(svg)
+    }
+}
+
+fn to_f64<C: Scalar>(c: [C; 2]) -> [f64; 2] {
+    c.map(|x| x.to_f64().unwrap_or(f64::NAN))
+}
+
+impl fmt::Display for ErrorGeometry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_floats(f, &FloatFormat::for_formatter(f))
+    }
+}
+
+impl FormatFloats for ErrorGeometry {
+    fn fmt_floats(&self, f: &mut fmt::Formatter<'_>, format: &FloatFormat) -> fmt::Result {
+        let float = |x: f64| Float(x, *format);
+        if let Some([[x0, y0], [x1, y1]]) = self.segment {
+            write!(f, "while inserting the segment ({}, {}) -> ({}, {})", float(x0), float(y0), float(x1), float(y1))?;
+            match self.segment_vertices {
+                Some([v0, v1]) => writeln!(f, " between vertices {} and {}", v0, v1)?,
+                None => writeln!(f)?,
+            }
+        }
+        if !self.polygon.is_empty() {
+            match self.polygon_index {
+                Some(index) => write!(f, "of polygon {} with {} vertices [", index, self.polygon.len())?,
+                None => write!(f, "of the polygon with {} vertices [", self.polygon.len())?,
+            }
+            for (i, [x, y]) in self.polygon.iter().take(DISPLAY_VERTICES).enumerate() {
+                write!(f, "{}({}, {})", if i == 0 { "" } else { ", " }, float(*x), float(*y))?;
+            }
+            if self.polygon.len() > DISPLAY_VERTICES {
+                write!(f, ", ...")?;
+            }
+            writeln!(f, "]")?;
+        }
+        Ok(())
     }
 }
 
 impl fmt::Display for InternalError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}\n{:?}", self.msg, self.backtrace)
+        self.fmt_floats(f, &FloatFormat::for_formatter(f))
+    }
+}
+
+impl FormatFloats for InternalError {
+    fn fmt_floats(&self, f: &mut fmt::Formatter<'_>, format: &FloatFormat) -> fmt::Result {
+        writeln!(f, "{}", self.msg)?;
+        if let Some(stage) = self.stage {
+            writeln!(f, "during {}", stage)?;
+        }
+        if let Some(geometry) = &self.geometry {
+            geometry.fmt_floats(f, format)?;
+        }
+        #[cfg(feature = "std")]
+        write!(f, "{:?}", self.backtrace)?;
+        Ok(())
     }
 }
 
@@ -59,8 +257,12 @@ pub enum TriangulationError<FBError: error::Error> {
     /// A triangulation precondition was violated in the provided [PolygonList](crate::PolygonList), 
     /// or a triangulation bug was encountered.
     InternalError(InternalError),
-    /// The [FanBuilder](crate::FanBuilder) returned an error.
+    /// The [FanBuilder](crate::FanBuilder), or the [MonotoneBuilder](crate::MonotoneBuilder) when decomposing into monotone polygons, returned an error.
     FanBuilder(FBError),
+    /// The interior point at this index lies outside of every polygon, on a polygon's boundary, or on another vertex
+    InvalidInteriorPoint(usize),
+    /// No key was given for the polygon at this position, counting only polygons with vertices
+    MissingKey(usize),
     #[cfg(feature = "_debugging")]
     SvgOutput(std::io::Error),
 }
@@ -68,10 +270,51 @@ pub enum TriangulationError<FBError: error::Error> {
 impl<FBError: error::Error> TriangulationError<FBError> {
     #[inline(always)]
     pub(crate) fn internal(msg: impl Into<String>) -> Self {
-        TriangulationError::InternalError(InternalError {
-            msg: msg.into(),
-            backtrace: Backtrace::new_unresolved(),
-        })
+        TriangulationError::InternalError(InternalError::new(msg))
+    }
+
+    /// Record the step of the algorithm which failed on an [InternalError], unless a step is already known
+    #[cold]
+    pub(crate) fn with_stage(self, stage: ErrorStage) -> Self {
+        match self {
+            Self::InternalError(error) => Self::InternalError(error.with_stage(stage)),
+            Self::TrapezoidationError(TrapezoidationError::InternalError(error)) => Self::TrapezoidationError(TrapezoidationError::InternalError(error.with_stage(stage))),
+            error => error,
+        }
+    }
+
+    /// An SVG image of the geometry involved in the error, if known. See [InternalError::to_svg]
+    pub fn to_svg(&self) -> Option<String> {
+        match self {
+            Self::TrapezoidationError(error) => error.to_svg(),
+            Self::InternalError(error) => error.to_svg(),
+            _ => None,
+        }
+    }
+
+    /// The step of the algorithm which failed, if known. See [InternalError::stage]
+    pub fn stage(&self) -> Option<ErrorStage> {
+        match self {
+            Self::TrapezoidationError(error) => error.stage(),
+            Self::InternalError(error) => error.stage,
+            _ => None,
+        }
+    }
+}
+
+impl TriangulationError<convert::Infallible> {
+    // Used when triangulating into an internal format before forwarding to the user's `FanFormat`
+    pub(crate) fn with_fan_builder_error<FBError: error::Error>(self) -> TriangulationError<FBError> {
+        match self {
+            Self::TrapezoidationError(error) => TriangulationError::TrapezoidationError(error),
+            Self::NoVertices => TriangulationError::NoVertices,
+            Self::InternalError(error) => TriangulationError::InternalError(error),
+            Self::FanBuilder(error) => match error {},
+            Self::InvalidInteriorPoint(index) => TriangulationError::InvalidInteriorPoint(index),
+            Self::MissingKey(index) => TriangulationError::MissingKey(index),
+            #[cfg(feature = "_debugging")]
+            Self::SvgOutput(error) => TriangulationError::SvgOutput(error),
+        }
     }
 }
 
@@ -83,19 +326,27 @@ impl<FBError: error::Error> From<FBError> for TriangulationError<FBError> {
 
 impl<FBError: error::Error> fmt::Display for TriangulationError<FBError> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_floats(f, &FloatFormat::for_formatter(f))
+    }
+}
+
+impl<FBError: error::Error> FormatFloats for TriangulationError<FBError> {
+    fn fmt_floats(&self, f: &mut fmt::Formatter<'_>, format: &FloatFormat) -> fmt::Result {
         match self {
-            Self::TrapezoidationError(error) => fmt::Display::fmt(error, f),
+            Self::TrapezoidationError(error) => error.fmt_floats(f, format),
             Self::NoVertices => write!(f, "Polygon set contains no vertices"),
-            Self::InternalError(error) => fmt::Display::fmt(error, f),
+            Self::InternalError(error) => error.fmt_floats(f, format),
             Self::FanBuilder(error) => fmt::Display::fmt(error, f),
+            Self::InvalidInteriorPoint(index) => write!(f, "Interior point {} does not lie strictly inside the polygons", index),
+            Self::MissingKey(index) => write!(f, "No key was given for polygon {}", index),
             #[cfg(feature = "_debugging")]
             Self::SvgOutput(error) => fmt::Display::fmt(error, f),
         }
     }
 }
 
-impl<FBError: error::Error> std::error::Error for TriangulationError<FBError> {
-    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+impl<FBError: error::Error> error::Error for TriangulationError<FBError> {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match self {
             Self::InternalError(error) => Some(error),
             Self::FanBuilder(error) => error.source(), // This should be Some(error), but that forces restricting FBError to 'static.
@@ -0,0 +1,213 @@
+//! A pool of worker threads which triangulates in the background, so an interactive application can keep its UI
+//! thread free. Requires the `executor` feature.
+//!
+//! Jobs are queued with a priority, and the highest priority job is started whenever a worker is free; jobs of equal
+//! priority start in the order they were submitted. Each job's result is delivered through its own channel, read
+//! from the [JobHandle] returned on submission. Jobs run to completion once started, but a job which is cancelled
+//! before it starts is skipped.
+//!
+//! ```
+//! # use triangulate::executor::Executor;
+//! let executor = Executor::new(2);
+//! let square = vec![vec![[0f64, 0.], [1., 0.], [1., 1.], [0., 1.]]];
+//! let low = executor.submit_polygons(square.clone(), 0);
+//! let high = executor.submit_polygons(square, 10);
+//! let buffers = high.recv().expect("Job was cancelled").expect("Triangulation failed");
+//! assert_eq!(buffers.indices.len(), 6);
+//! assert!(low.recv().is_some());
+//! ```
+
+use std::{cmp, collections::BinaryHeap, panic::{self, AssertUnwindSafe}, sync::{atomic::{AtomicBool, Ordering}, mpsc, Arc, Condvar, Mutex}, thread};
+
+use num_traits::real::Real;
+
+use crate::{buffers::IndexBuffers, formats::IndexBufferError, mesh::Mesh, TriangulationError, Vertex};
+
+/// A pool of worker threads running queued jobs in order of priority
+#[derive(Debug)]
+pub struct Executor {
+    shared: Arc<Shared>,
+    workers: Vec<thread::JoinHandle<()>>,
+}
+
+impl Executor {
+    /// Start `threads` worker threads, or one per available core if `threads` is 0
+    pub fn new(threads: usize) -> Self {
+        let threads = match threads {
+            0 => thread::available_parallelism().map_or(1, usize::from),
+            n => n,
+        };
+        let shared = Arc::new(Shared { state: Mutex::new(State::default()), available: Condvar::new() });
+        let workers = (0..threads).map(|_| {
+            let shared = shared.clone();
+            thread::spawn(move || shared.work())
+        }).collect();
+        Self { shared, workers }
+    }
+
+    /// The number of worker threads
+    pub fn threads(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// The number of jobs waiting for a worker, including cancelled jobs which have not been skipped yet
+    pub fn queued(&self) -> usize {
+        self.shared.lock().queue.len()
+    }
+
+    /// Queue `job` to run on a worker thread. Higher `priority` jobs start first.
+    ///
+    /// If `job` panics, the panic is caught and the [JobHandle] receives nothing, as if it was cancelled.
+    pub fn submit<T: Send + 'static>(&self, priority: i32, job: impl FnOnce() -> T + Send + 'static) -> JobHandle<T> {
+        let (sender, receiver) = mpsc::sync_channel(1);
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let flag = cancelled.clone();
+        let run = Box::new(move || {
+            if !flag.load(Ordering::Acquire) {
+                if let Ok(result) = panic::catch_unwind(AssertUnwindSafe(job)) {
+                    // The handle may have been dropped, in which case no one wants the result
+                    let _ = sender.send(result);
+                }
+            }
+        });
+
+        let mut state = self.shared.lock();
+        let sequence = state.submitted;
+        state.submitted += 1;
+        state.queue.push(Job { priority, sequence, run });
+        drop(state);
+        self.shared.available.notify_one();
+        JobHandle { receiver, cancelled }
+    }
+
+    /// Queue the triangulation of `polygons` into [IndexBuffers], as with [Mesh::from_polygons] and
+    /// [IndexBuffers::from_mesh]. Higher `priority` jobs start first.
+    pub fn submit_polygons<V: Vertex + Clone + Send + 'static>(&self, polygons: Vec<Vec<V>>, priority: i32) -> JobHandle<Result<IndexBuffers<V::Payload>, TriangulationError<IndexBufferError>>>
+    where V::Coordinate: Real, V::Payload: Clone + Send + 'static {
+        self.submit(priority, move || {
+            let mesh = Mesh::from_polygons(&polygons).map_err(TriangulationError::with_fan_builder_error)?;
+            IndexBuffers::from_mesh(&mesh).map_err(TriangulationError::FanBuilder)
+        })
+    }
+}
+
+impl Default for Executor {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl Drop for Executor {
+    /// Discard the jobs which have not started, and wait for the running jobs to finish
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.shutdown = true;
+        state.queue.clear();
+        drop(state);
+        self.shared.available.notify_all();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+/// The receiving end of a job submitted to an [Executor]
+#[derive(Debug)]
+pub struct JobHandle<T> {
+    receiver: mpsc::Receiver<T>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl<T> JobHandle<T> {
+    /// Skip the job if it has not started yet. The result of a job which has already started is discarded
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release);
+    }
+
+    /// Whether [JobHandle::cancel] was called
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+    }
+
+    /// Wait for the result of the job, or `None` if it was cancelled, panicked, or discarded by dropping the
+    /// [Executor]
+    pub fn recv(&self) -> Option<T> {
+        self.receiver.recv().ok().filter(|_| !self.is_cancelled())
+    }
+
+    /// The result of the job if it has finished, without waiting
+    pub fn try_recv(&self) -> Option<T> {
+        self.receiver.try_recv().ok().filter(|_| !self.is_cancelled())
+    }
+}
+
+#[derive(Debug)]
+struct Shared {
+    state: Mutex<State>,
+    available: Condvar,
+}
+
+impl Shared {
+    // Jobs catch their own panics, so the lock is never poisoned while a job runs
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn work(&self) {
+        loop {
+            let job = {
+                let mut state = self.lock();
+                loop {
+                    if state.shutdown {
+                        return;
+                    }
+                    if let Some(job) = state.queue.pop() {
+                        break job;
+                    }
+                    state = self.available.wait(state).unwrap_or_else(|poisoned| poisoned.into_inner());
+                }
+            };
+            (job.run)();
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    queue: BinaryHeap<Job>,
+    submitted: u64,
+    shutdown: bool,
+}
+
+struct Job {
+    priority: i32,
+    sequence: u64,
+    run: Box<dyn FnOnce() + Send>,
+}
+
+impl std::fmt::Debug for Job {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Job").field("priority", &self.priority).field("sequence", &self.sequence).finish_non_exhaustive()
+    }
+}
+
+// The highest priority first, then the earliest submitted
+impl Ord for Job {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.priority.cmp(&other.priority).then_with(|| other.sequence.cmp(&self.sequence))
+    }
+}
+
+impl PartialOrd for Job {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Job {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == cmp::Ordering::Equal
+    }
+}
+
+impl Eq for Job { }
@@ -1,4 +1,4 @@
-use std::{fmt, mem};
+use core::{fmt, mem};
 
 use crate::{FanFormat, PolygonList, TriangulationError, FanBuilder};
 
@@ -16,7 +16,7 @@ impl<'p, P: PolygonList<'p> + ?Sized, FB: FanFormat<'p, P>> FanBuilderState<'p,
             if let FanBuilderState::Initialized(fb) = s {
                 Ok(fb)
             } else {
-                Err(TriangulationError::internal("msg"))
+                Err(TriangulationError::internal("Fan builder missing after initialization"))
             }
         }
 
@@ -34,7 +34,7 @@ impl<'p, P: PolygonList<'p> + ?Sized, FB: FanFormat<'p, P>> FanBuilderState<'p,
                 let fb = fb.initialize(polygon_list, vi0, vi1, vi2).map_err(TriangulationError::from)?;
                 set_initialized(self, fb)
             }
-            Self::Error(_) => Err(TriangulationError::internal("msg")),
+            Self::Error(_) => Err(TriangulationError::internal("Fan builder missing after initialization")),
         }
     }
 
@@ -53,9 +53,7 @@ impl<'p, P: PolygonList<'p> + ?Sized, FB: FanFormat<'p, P>> FanBuilderState<'p,
             }
             // Something went wrong (result should always be Err for FBS::Error)
             (FanBuilderState::Error(fb), Ok(())) => {
-                debug_assert!(false);
-
-                let err = TriangulationError::internal("Unknown error");
+                let err = TriangulationError::internal("Fan builder failed without reporting an error");
                 if let Some(fb) = fb {
                     fb.fail(&err);
                 }
@@ -0,0 +1,86 @@
+//! The triangulation pipeline for `f32` coordinates and `u32` vertex indices, compiled once into this crate.
+//!
+//! The rest of the crate is generic, so it is compiled in each crate which uses it, once for every combination of
+//! polygon, vertex, index and output types. The functions and types here have no type parameters: they are
+//! instantiated for the most common combination when this crate is built, so a dependent crate which only uses them
+//! compiles none of the generic machinery itself, and links a single copy of it. Requires the `fixed-f32-u32`
+//! feature.
+//!
+//! The polygons are a [FlatPolygonList] of [Point]s, and the output is a triangle list of `u32` indices into its
+//! [vertices](FlatPolygonList::vertices), as for an index buffer.
+//!
+//! ```
+//! # use triangulate::fixed::{self, Polygons};
+//! let mut polygons = Polygons::new();
+//! polygons.push_polygon([[0., 0.], [3., 0.], [3., 3.], [0., 3.]]);
+//! polygons.push_polygon([[1., 1.], [1., 2.], [2., 2.], [2., 1.]]);
+//!
+//! let indices = fixed::triangulate(&polygons).expect("Triangulation failed");
+//! assert_eq!(indices.len(), 8 * 3);
+//! ```
+
+use alloc::vec::Vec;
+
+use crate::{formats::{IndexBufferError, IndexBufferFormat}, FlatPolygonList, ListFormat, PolygonList, TriangulationError, TriangulationOptions};
+
+/// The vertex type of the polygons, as `[x, y]`
+pub type Point = [f32; 2];
+
+/// The polygons to triangulate
+pub type Polygons = FlatPolygonList<Point>;
+
+/// The error of a triangulation, which fails with [IndexBufferError::IndexOverflow] if there are more than
+/// `u32::MAX` vertices
+pub type Error = TriangulationError<IndexBufferError>;
+
+/// Triangulate `polygons`, like [PolygonList::triangulate], into a list of three indices per triangle
+pub fn triangulate(polygons: &Polygons) -> Result<Vec<u32>, Error> {
+    let mut indices = Vec::new();
+    polygons.triangulate(IndexBufferFormat::new(&mut indices, 0).into_fan_format())?;
+    Ok(indices)
+}
+
+/// Triangulate `polygons` with the algorithm chosen in `options`, like [PolygonList::triangulate_with_options], into
+/// a list of three indices per triangle
+pub fn triangulate_with_options(polygons: &Polygons, options: &TriangulationOptions) -> Result<Vec<u32>, Error> {
+    let mut indices = Vec::new();
+    polygons.triangulate_with_options(IndexBufferFormat::new(&mut indices, 0).into_fan_format(), options)?;
+    Ok(indices)
+}
+
+/// Triangulates polygons one after another, reusing the memory of each trapezoidation for the next, like
+/// [crate::Triangulator].
+///
+/// ```
+/// # use triangulate::fixed::{Polygons, Triangulator};
+/// let glyphs = [
+///     Polygons::from_iter([[[0., 0.], [1., 0.], [1., 1.], [0., 1.]]]),
+///     Polygons::from_iter([[[0., 0.], [2., 0.], [1., 1.]]]),
+/// ];
+/// let mut triangulator = Triangulator::new();
+/// let mut indices = Vec::new();
+/// let mut base_vertex = 0;
+/// for glyph in &glyphs {
+///     triangulator.triangulate(glyph, &mut indices, base_vertex).expect("Triangulation failed");
+///     base_vertex += glyph.vertices().len() as u32;
+/// }
+/// assert_eq!(indices.len(), 3 * 3);
+/// assert!(indices[6..].iter().all(|&i| (4..7).contains(&i)));
+/// ```
+#[derive(Default)]
+pub struct Triangulator {
+    triangulator: crate::Triangulator<Point, usize>,
+}
+
+impl Triangulator {
+    /// A triangulator which has not allocated anything yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Triangulate `polygons`, appending three indices per triangle to `indices`, with the first vertex numbered
+    /// `base_vertex`. Returns the number of indices added; on failure, none are.
+    pub fn triangulate(&mut self, polygons: &Polygons, indices: &mut Vec<u32>, base_vertex: u32) -> Result<usize, Error> {
+        self.triangulator.triangulate(polygons, IndexBufferFormat::new(indices, base_vertex).into_fan_format())
+    }
+}
@@ -0,0 +1,101 @@
+//! Formatting of coordinates in error messages and debug output.
+//!
+//! By default coordinates are written in their shortest representation which reads back as the same number. For
+//! large coordinates, or those with rounding noise, this is hard to read and to compare between runs, so a value
+//! which writes coordinates can be displayed in a fixed precision and scientific notation with
+//! [FormatFloats::display_with]. Otherwise, a precision given in the format string, as in `{:.3}`, applies to its
+//! coordinates.
+//!
+//! ```
+//! # use triangulate::float_format::FloatFormat;
+//! let format = FloatFormat { precision: Some(2), scientific: true };
+//! assert_eq!(format.format(123456.789), "1.23e5");
+//! assert_eq!(FloatFormat::default().format(-0.0), "0");
+//! ```
+
+use core::fmt;
+use alloc::string::String;
+
+/// How to write floating point numbers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FloatFormat {
+    /// The number of digits after the decimal point, or `None` for the shortest representation which reads back as
+    /// the same number
+    pub precision: Option<usize>,
+    /// Whether to write numbers in scientific notation, as in `1.5e3`
+    pub scientific: bool,
+}
+
+impl FloatFormat {
+    /// The default format, with the precision of the formatter if it has one
+    pub(crate) fn for_formatter(f: &fmt::Formatter<'_>) -> Self {
+        Self { precision: f.precision(), ..Self::default() }
+    }
+
+    /// Write `x` in this format. Negative zero is written as zero.
+    pub fn write(&self, w: &mut impl fmt::Write, x: f64) -> fmt::Result {
+        let x = if x == 0.0 { 0.0 } else { x };
+        match (self.precision, self.scientific) {
+            (None, false) => write!(w, "{}", x),
+            (None, true) => write!(w, "{:e}", x),
+            (Some(precision), false) => write!(w, "{:.*}", precision, x),
+            (Some(precision), true) => write!(w, "{:.*e}", precision, x),
+        }
+    }
+
+    /// `x` written in this format
+    pub fn format(&self, x: f64) -> String {
+        let mut s = String::new();
+        // Writing to a `String` cannot fail
+        let _ = self.write(&mut s, x);
+        s
+    }
+}
+
+/// A value which writes coordinates, such as an [InternalError](crate::InternalError), and can be displayed with them
+/// in a given [FloatFormat].
+///
+/// ```
+/// # use triangulate::{float_format::{FloatFormat, FormatFloats}, validity::InvalidReason};
+/// let reason = InvalidReason::DisconnectedInterior { point: [1. / 3., 2.5e6] };
+/// let format = FloatFormat { precision: Some(2), scientific: true };
+/// assert_eq!(reason.display_with(&format).to_string(), "Interior is disconnected[3.33e-1 2.50e6]");
+/// ```
+pub trait FormatFloats {
+    /// Write this value as its [Display](fmt::Display) implementation does, with its coordinates in `format`
+    fn fmt_floats(&self, f: &mut fmt::Formatter<'_>, format: &FloatFormat) -> fmt::Result;
+
+    /// Display this value with its coordinates in `format`
+    fn display_with<'a>(&'a self, format: &'a FloatFormat) -> DisplayWith<'a, Self> {
+        DisplayWith { value: self, format }
+    }
+}
+
+/// Displays a value with its coordinates in a [FloatFormat]. Created by [FormatFloats::display_with].
+#[derive(Debug, Clone, Copy)]
+pub struct DisplayWith<'a, T: FormatFloats + ?Sized> {
+    value: &'a T,
+    format: &'a FloatFormat,
+}
+
+impl<T: FormatFloats + ?Sized> fmt::Display for DisplayWith<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt_floats(f, self.format)
+    }
+}
+
+/// Displays a number in a [FloatFormat]
+#[derive(Clone, Copy)]
+pub(crate) struct Float(pub f64, pub FloatFormat);
+
+impl fmt::Display for Float {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.1.write(f, self.0)
+    }
+}
+
+impl fmt::Debug for Float {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
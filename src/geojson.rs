@@ -0,0 +1,161 @@
+//! Loading the polygons of [GeoJSON](https://www.rfc-editor.org/rfc/rfc7946) documents to triangulate. Requires the
+//! `geojson` feature.
+//!
+//! Each `Polygon` or `MultiPolygon` geometry becomes a [FlatPolygonList] of its rings, without the closing position
+//! which repeats the first. The rings are rewound as the specification asks of writers, with the exterior of each
+//! polygon counter-clockwise and its interiors clockwise, since readers can't rely on it. Triangulation uses the
+//! even-odd rule, so it does not depend on the winding, but consumers of the rings, such as
+//! [stroke](crate::stroke) offsets, do. Only the first two coordinates of each position are kept.
+//!
+//! ```
+//! # use triangulate::{formats, geojson, ListFormat, PolygonList};
+//! let json = r#"{
+//!     "type": "Feature",
+//!     "properties": { "name": "Courtyard" },
+//!     "geometry": {
+//!         "type": "Polygon",
+//!         "coordinates": [
+//!             [[0, 0], [3, 0], [3, 3], [0, 3], [0, 0]],
+//!             [[1, 1], [2, 1], [2, 2], [1, 2], [1, 1]]
+//!         ]
+//!     }
+//! }"#;
+//! let polygons = geojson::read(json).expect("Loading failed");
+//! assert_eq!(polygons.len(), 1);
+//! assert_eq!(polygons[0].polygon(0), [[0., 0.], [3., 0.], [3., 3.], [0., 3.]]);
+//! // The hole is rewound clockwise
+//! assert_eq!(polygons[0].polygon(1), [[1., 2.], [2., 2.], [2., 1.], [1., 1.]]);
+//!
+//! let mut triangles = Vec::<usize>::new();
+//! polygons[0].triangulate(formats::IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+//! assert_eq!(triangles.len(), 8 * 3);
+//! ```
+
+use core::{error, fmt};
+use alloc::{boxed::Box, vec::Vec};
+
+use ::geojson::{GeoJson, Geometry, PolygonType, Value};
+
+use crate::{math, FlatPolygonList};
+
+/// An error loading the polygons of a GeoJSON document
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum GeoJsonError {
+    /// The document is not valid GeoJSON
+    Parse(Box<::geojson::Error>),
+    /// The geometry passed to [polygons] is not a `Polygon` or `MultiPolygon`, but the contained type
+    NotAPolygon(&'static str),
+    /// The ring at index `ring` of the polygon at index `polygon` (`0` for a `Polygon` geometry) has a position with
+    /// fewer than two coordinates, or fewer than three positions besides the closing one
+    InvalidRing {
+        /// The index of the polygon in a `MultiPolygon`
+        polygon: usize,
+        /// The index of the ring in the polygon, where the exterior is `0`
+        ring: usize,
+    },
+}
+
+impl fmt::Display for GeoJsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GeoJsonError::Parse(error) => write!(f, "Invalid GeoJSON: {}", error),
+            GeoJsonError::NotAPolygon(type_name) => write!(f, "Expected a Polygon or MultiPolygon geometry, not {}", type_name),
+            GeoJsonError::InvalidRing { polygon, ring } => write!(f, "Ring {} of polygon {} is invalid", ring, polygon),
+        }
+    }
+}
+
+impl error::Error for GeoJsonError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            GeoJsonError::Parse(error) => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<::geojson::Error> for GeoJsonError {
+    fn from(error: ::geojson::Error) -> Self {
+        GeoJsonError::Parse(Box::new(error))
+    }
+}
+
+/// The polygons of each `Polygon` or `MultiPolygon` geometry in the GeoJSON document `json`, in order.
+///
+/// The document may be a geometry, a feature or a feature collection, and geometry collections are searched for
+/// polygons too. Other geometries, and features without one, are skipped. Separate geometries are separate lists, as
+/// neighbouring polygons, such as the countries of a map, often share vertices and can't be triangulated together.
+pub fn read(json: &str) -> Result<Vec<FlatPolygonList<[f64; 2]>>, GeoJsonError> {
+    let mut lists = Vec::new();
+    match json.parse::<GeoJson>()? {
+        GeoJson::Geometry(geometry) => add_polygons(&mut lists, &geometry)?,
+        GeoJson::Feature(feature) => {
+            if let Some(geometry) = &feature.geometry {
+                add_polygons(&mut lists, geometry)?;
+            }
+        }
+        GeoJson::FeatureCollection(collection) => {
+            for geometry in collection.features.iter().filter_map(|feature| feature.geometry.as_ref()) {
+                add_polygons(&mut lists, geometry)?;
+            }
+        }
+    }
+    Ok(lists)
+}
+
+/// The polygons of a `Polygon` or `MultiPolygon` geometry
+pub fn polygons(geometry: &Geometry) -> Result<FlatPolygonList<[f64; 2]>, GeoJsonError> {
+    let mut list = FlatPolygonList::new();
+    match &geometry.value {
+        Value::Polygon(polygon) => push_polygon(&mut list, polygon, 0)?,
+        Value::MultiPolygon(polygons) => {
+            for (i, polygon) in polygons.iter().enumerate() {
+                push_polygon(&mut list, polygon, i)?;
+            }
+        }
+        value => return Err(GeoJsonError::NotAPolygon(value.type_name())),
+    }
+    Ok(list)
+}
+
+fn add_polygons(lists: &mut Vec<FlatPolygonList<[f64; 2]>>, geometry: &Geometry) -> Result<(), GeoJsonError> {
+    match &geometry.value {
+        Value::Polygon(_) | Value::MultiPolygon(_) => lists.push(polygons(geometry)?),
+        Value::GeometryCollection(geometries) => {
+            for geometry in geometries {
+                add_polygons(lists, geometry)?;
+            }
+        }
+        _ => { }
+    }
+    Ok(())
+}
+
+fn push_polygon(list: &mut FlatPolygonList<[f64; 2]>, polygon: &PolygonType, index: usize) -> Result<(), GeoJsonError> {
+    for (ring_index, positions) in polygon.iter().enumerate() {
+        let invalid = || GeoJsonError::InvalidRing { polygon: index, ring: ring_index };
+
+        let mut ring = positions.iter()
+            .map(|position| match position.as_slice() {
+                [x, y, ..] => Some([*x, *y]),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(invalid)?;
+        if ring.len() > 1 && ring.first() == ring.last() {
+            ring.pop();
+        }
+        if ring.len() < 3 {
+            return Err(invalid());
+        }
+
+        // The exterior is counter-clockwise, and the interiors clockwise
+        let counterclockwise = math::signed_area_2(&ring) > 0.;
+        if counterclockwise != (ring_index == 0) {
+            ring.reverse();
+        }
+        list.push_polygon(ring);
+    }
+    Ok(())
+}
@@ -0,0 +1,79 @@
+//! Convex hulls, and the concave 'pockets' between a shape and its hull.
+//!
+//! ```
+//! # use triangulate::hull;
+//! // A square with a notch cut into its top edge
+//! let polygons = vec![vec![[0f64, 0.], [4., 0.], [4., 4.], [3., 4.], [2., 1.], [1., 4.], [0., 4.]]];
+//! assert_eq!(hull::convex_hull(&polygons[0]), vec![[0., 0.], [4., 0.], [4., 4.], [0., 4.]]);
+//!
+//! let pockets = hull::pockets(&polygons).expect("Triangulation failed");
+//! assert_eq!(pockets.triangles.len(), 1);
+//! ```
+
+use core::{convert::Infallible, fmt};
+use alloc::vec::Vec;
+
+use num_traits::real::Real;
+
+use crate::{boolean::{self, BooleanOp}, mesh::Mesh, GeometryKernel, DefaultKernel, Orientation, PolygonList, Scalar, PolygonListExt, TriangulationError, Vertex};
+
+/// The convex hull of `points`, in counter-clockwise order starting from the lowest point (with the lowest x coordinate on ties).
+///
+/// Collinear points on the hull's edges are omitted. Fewer than 3 points are returned if all points are collinear.
+pub fn convex_hull<V: Vertex>(points: &[V]) -> Vec<[V::Coordinate; 2]>
+where V::Coordinate: fmt::Debug {
+    convex_hull_with_kernel::<DefaultKernel, V>(points)
+}
+
+/// [convex_hull], using the [GeometryKernel] `K` for orientation tests
+pub fn convex_hull_with_kernel<K: GeometryKernel<V::Coordinate>, V: Vertex>(points: &[V]) -> Vec<[V::Coordinate; 2]>
+where V::Coordinate: fmt::Debug {
+    let mut points: Vec<[V::Coordinate; 2]> = points.iter().map(|v| [v.x(), v.y()]).collect();
+    points.sort_by(K::compare_y);
+    points.dedup();
+    if points.len() < 3 {
+        return points;
+    }
+
+    // Andrew's monotone chain: sweep upward for the right chain, then back down for the left chain
+    fn chain<'a, K: GeometryKernel<C>, C: Scalar + 'a>(points: impl Iterator<Item=&'a [C; 2]>) -> Vec<[C; 2]> {
+        let mut chain: Vec<[C; 2]> = Vec::new();
+        for c in points {
+            while chain.len() >= 2 && K::orientation(&chain[chain.len() - 2], &chain[chain.len() - 1], c) != Orientation::Left {
+                chain.pop();
+            }
+            chain.push(c.clone());
+        }
+        // The last point begins the other chain
+        chain.pop();
+        chain
+    }
+    let mut hull = chain::<K, _>(points.iter());
+    hull.extend(chain::<K, _>(points.iter().rev()));
+    hull
+}
+
+// The coordinate type of the vertices of `P`
+type Coordinate<'p, P> = <<P as PolygonList<'p>>::Vertex as Vertex>::Coordinate;
+
+/// Triangulate the difference between `polygons` and their convex hull (the 'pockets' of the shape), including any holes.
+///
+/// Each connected pocket is triangulated separately, since neighboring pockets may touch at a single vertex of the shape.
+pub fn pockets<'p, P: PolygonList<'p> + ?Sized>(polygons: &'p P) -> Result<Mesh<[Coordinate<'p, P>; 2]>, TriangulationError<Infallible>>
+where <P::Vertex as Vertex>::Coordinate: Real + fmt::Debug {
+    let contours = PolygonListExt::new(polygons).contours();
+    let hull = convex_hull(&contours.concat());
+    if hull.len() < 3 {
+        return Ok(Mesh::default());
+    }
+
+    let difference = boolean::overlay(&[hull], &contours, BooleanOp::Difference);
+    let mut mesh = Mesh::default();
+    for group in boolean::group_rings(difference) {
+        let part = Mesh::from_polygons(&group)?;
+        let offset = mesh.vertices.len();
+        mesh.vertices.extend(part.vertices);
+        mesh.triangles.extend(part.triangles.into_iter().map(|t| t.map(|vi| vi + offset)));
+    }
+    Ok(mesh)
+}
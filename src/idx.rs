@@ -1,4 +1,5 @@
-use std::{fmt, marker::PhantomData, num::NonZeroUsize, ops, cmp, hash};
+use core::{fmt, marker::PhantomData, num::NonZeroUsize, ops, cmp, hash};
+use alloc::{sync::Arc, vec::Vec};
 
 #[repr(transparent)]
 pub struct Idx<T>(NonZeroUsize, PhantomData<T>);
@@ -152,6 +153,22 @@ impl<T> hash::Hash for Idx<T> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<T> serde::Serialize for Idx<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.usize() as u64)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for Idx<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let index = u64::deserialize(deserializer)?;
+        usize::try_from(index).ok().filter(|&index| index < usize::MAX).map(Idx::new)
+            .ok_or_else(|| serde::de::Error::custom("index out of range"))
+    }
+}
+
 impl<T> cmp::PartialEq<Idx<T>> for usize {
     fn eq(&self, other: &Idx<T>) -> bool {
         self == &other.usize()
@@ -225,15 +242,21 @@ impl<T> VecExt<T> for Vec<T> {
 }
 
 pub struct SliceIndexIter<'a, T> {
-    slice: &'a [T],
+    len: usize,
     index: usize,
+    _slice: PhantomData<&'a [T]>,
 }
 
 impl<'a, T> SliceIndexIter<'a, T> {
     fn new(slice: &'a [T]) -> Self {
+        Self::with_len(slice.len())
+    }
+
+    fn with_len(len: usize) -> Self {
         Self {
-            slice,
+            len,
             index: 0,
+            _slice: PhantomData,
         }
     }
 }
@@ -242,7 +265,7 @@ impl<'a, T> Iterator for SliceIndexIter<'a, T> {
     type Item = Idx<T>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.index < self.slice.len() {
+        if self.index < self.len {
             let result = Some(Idx::new(self.index));
             self.index += 1;
             result
@@ -251,3 +274,229 @@ impl<'a, T> Iterator for SliceIndexIter<'a, T> {
         }
     }
 }
+
+const PAGE_BITS: u32 = 7;
+const PAGE_SIZE: usize = 1 << PAGE_BITS;
+
+/// An append-only vector stored in shared pages, which are copied on write. Used by [ForkableVec] once it is shared.
+/// 
+/// Cloning only clones the page pointers, so a clone can be modified while sharing the untouched pages with the original.
+pub struct PagedVec<T> {
+    pages: Vec<Arc<Vec<T>>>,
+    len: usize,
+}
+
+impl<T> PagedVec<T> {
+    pub fn new() -> Self {
+        Self { pages: Vec::new(), len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[cfg(any(feature = "trace", feature = "_debugging", feature = "serde"))]
+    pub fn iter(&self) -> impl Iterator<Item=&T> + '_ {
+        self.pages.iter().flat_map(|page| page.iter())
+    }
+}
+
+impl<T: Clone> PagedVec<T> {
+    pub fn push(&mut self, value: T) {
+        let page = self.len >> PAGE_BITS;
+        if page == self.pages.len() {
+            self.pages.push(Arc::new(Vec::with_capacity(PAGE_SIZE)));
+        }
+        Arc::make_mut(&mut self.pages[page]).push(value);
+        self.len += 1;
+    }
+
+    /// Reserve the page pointers for at least `additional` more values, as each page is allocated when it is reached
+    pub fn reserve(&mut self, additional: usize) {
+        let pages = (self.len + additional).div_ceil(PAGE_SIZE);
+        self.pages.reserve(pages.saturating_sub(self.pages.len()));
+    }
+}
+
+impl<T> Default for PagedVec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// #[derive] would require `T: Clone`, although only the pointers are cloned
+impl<T> Clone for PagedVec<T> {
+    fn clone(&self) -> Self {
+        Self { pages: self.pages.clone(), len: self.len }
+    }
+}
+
+impl<T> ops::Index<Idx<T>> for PagedVec<T> {
+    type Output = T;
+
+    fn index(&self, index: Idx<T>) -> &Self::Output {
+        let index = index.usize();
+        &self.pages[index >> PAGE_BITS][index & (PAGE_SIZE - 1)]
+    }
+}
+
+impl<T: Clone> ops::IndexMut<Idx<T>> for PagedVec<T> {
+    fn index_mut(&mut self, index: Idx<T>) -> &mut Self::Output {
+        let index = index.usize();
+        &mut Arc::make_mut(&mut self.pages[index >> PAGE_BITS])[index & (PAGE_SIZE - 1)]
+    }
+}
+
+/// An append-only vector, which is a plain [Vec] until it is [shared](ForkableVec::share), and is then stored in a
+/// [PagedVec], sharing its pages with its clones.
+///
+/// Sharing adds a reference count check and an indirection to every access, so only trapezoidations which are forked
+/// pay for it.
+pub struct ForkableVec<T>(Forkable<T>);
+
+enum Forkable<T> {
+    Owned(Vec<T>),
+    Shared(PagedVec<T>),
+}
+
+impl<T> ForkableVec<T> {
+    pub fn new() -> Self {
+        Self(Forkable::Owned(Vec::new()))
+    }
+
+    pub fn len(&self) -> usize {
+        match &self.0 {
+            Forkable::Owned(values) => values.len(),
+            Forkable::Shared(values) => values.len(),
+        }
+    }
+
+    #[cfg(any(feature = "trace", feature = "_debugging", feature = "serde"))]
+    pub fn iter(&self) -> impl Iterator<Item=&T> + '_ {
+        let (owned, shared) = match &self.0 {
+            Forkable::Owned(values) => (Some(values.iter()), None),
+            Forkable::Shared(values) => (None, Some(values.iter())),
+        };
+        owned.into_iter().flatten().chain(shared.into_iter().flatten())
+    }
+
+    /// Get the value at `index` without checking bounds
+    ///
+    /// # Safety
+    /// `index` must be less than [ForkableVec::len].
+    #[inline]
+    pub unsafe fn get_unchecked(&self, index: Idx<T>) -> &T {
+        match &self.0 {
+            Forkable::Owned(values) => unsafe { values.get_unchecked(index.usize()) },
+            Forkable::Shared(values) => &values[index],
+        }
+    }
+}
+
+impl<T: Clone> ForkableVec<T> {
+    pub fn push(&mut self, value: T) {
+        match &mut self.0 {
+            Forkable::Owned(values) => values.push(value),
+            Forkable::Shared(values) => values.push(value),
+        }
+    }
+
+    /// Reserve memory for at least `additional` more values
+    pub fn reserve(&mut self, additional: usize) {
+        match &mut self.0 {
+            Forkable::Owned(values) => values.reserve(additional),
+            Forkable::Shared(values) => values.reserve(additional),
+        }
+    }
+
+    /// Remove all values. Shared pages are released, so the vector is plain again and does not pay for sharing with
+    /// the clones it was shared with.
+    pub fn clear(&mut self) {
+        match &mut self.0 {
+            Forkable::Owned(values) => values.clear(),
+            Forkable::Shared(values) => self.0 = Forkable::Owned(Vec::with_capacity(values.len())),
+        }
+    }
+
+    /// Move the values into shared pages, so clones share them until they are modified
+    pub fn share(&mut self) {
+        if let Forkable::Owned(values) = &mut self.0 {
+            let mut paged = PagedVec::new();
+            for value in core::mem::take(values) {
+                paged.push(value);
+            }
+            self.0 = Forkable::Shared(paged);
+        }
+    }
+}
+
+impl<T> Default for ForkableVec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Owned values are copied, and shared pages only have their pointers cloned
+impl<T: Clone> Clone for ForkableVec<T> {
+    fn clone(&self) -> Self {
+        Self(match &self.0 {
+            Forkable::Owned(values) => Forkable::Owned(values.clone()),
+            Forkable::Shared(values) => Forkable::Shared(values.clone()),
+        })
+    }
+}
+
+// Serialized as a sequence, without the page structure
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for ForkableVec<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for ForkableVec<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Self(Forkable::Owned(Vec::deserialize(deserializer)?)))
+    }
+}
+
+impl<T> ops::Index<Idx<T>> for ForkableVec<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: Idx<T>) -> &Self::Output {
+        match &self.0 {
+            Forkable::Owned(values) => &values[index.usize()],
+            Forkable::Shared(values) => &values[index],
+        }
+    }
+}
+
+impl<T: Clone> ops::IndexMut<Idx<T>> for ForkableVec<T> {
+    #[inline]
+    fn index_mut(&mut self, index: Idx<T>) -> &mut Self::Output {
+        match &mut self.0 {
+            Forkable::Owned(values) => &mut values[index.usize()],
+            Forkable::Shared(values) => &mut values[index],
+        }
+    }
+}
+
+impl<T> SliceExt<T> for ForkableVec<T> {
+    fn iter_index(&self) -> SliceIndexIter<'_, T> {
+        SliceIndexIter::with_len(self.len())
+    }
+}
+
+impl<T: Clone> VecExt<T> for ForkableVec<T> {
+    fn push_get_index(&mut self, value: T) -> Idx<T> {
+        let index = Idx::new(self.len());
+        self.push(value);
+        index
+    }
+
+    fn next_index(&self) -> Idx<T> {
+        Idx::new(self.len())
+    }
+}
@@ -0,0 +1,204 @@
+use core::{iter, slice};
+use alloc::vec::Vec;
+
+use crate::{PolygonElement, PolygonList, VertexIndex};
+
+/// Identifies a vertex of a [Chain] list
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum ChainIndex<A: VertexIndex, B: VertexIndex> {
+    /// A vertex of the first list
+    First(A),
+    /// A vertex of the second list
+    Second(B),
+}
+
+/// A [PolygonList] with the polygons of one list followed by those of another with the same vertex type. Created by
+/// [PolygonList::chain].
+///
+/// Nothing is copied: vertices are indexed by [ChainIndex], which refers to the vertex of the list it came from, so
+/// chains can be nested to compose any number of lists.
+///
+/// ```
+/// # use triangulate::{formats, ChainIndex, ListFormat, Polygon, PolygonList};
+/// let building = vec![[0f32, 0.], [2., 0.], [2., 2.], [0., 2.]];
+/// let courtyard = vec![vec![[0.5f32, 0.5], [0.5, 1.5], [1.5, 1.5], [1.5, 0.5]]];
+/// let polygons = building.as_polygon_list().chain(&courtyard);
+///
+/// let mut triangles = Vec::<ChainIndex<usize, [usize; 2]>>::new();
+/// polygons.triangulate(formats::IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+/// assert_eq!(triangles.len(), 8 * 3);
+/// assert_eq!(polygons.get_vertex(ChainIndex::Second([0, 2])), &[1.5, 1.5]);
+/// ```
+#[derive(Debug)]
+pub struct Chain<'p, A: PolygonList<'p> + ?Sized, B: PolygonList<'p, Vertex=A::Vertex> + ?Sized> {
+    first: &'p A,
+    second: &'p B,
+}
+
+impl<'p, A: PolygonList<'p> + ?Sized, B: PolygonList<'p, Vertex=A::Vertex> + ?Sized> Clone for Chain<'p, A, B> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'p, A: PolygonList<'p> + ?Sized, B: PolygonList<'p, Vertex=A::Vertex> + ?Sized> Copy for Chain<'p, A, B> { }
+
+impl<'p, A: PolygonList<'p> + ?Sized, B: PolygonList<'p, Vertex=A::Vertex> + ?Sized> Chain<'p, A, B> {
+    /// The polygons of `first`, followed by those of `second`
+    pub fn new(first: &'p A, second: &'p B) -> Self {
+        Self { first, second }
+    }
+
+    /// The first [PolygonList], indexed by [ChainIndex::First]
+    pub fn first(&self) -> &'p A {
+        self.first
+    }
+
+    /// The second [PolygonList], indexed by [ChainIndex::Second]
+    pub fn second(&self) -> &'p B {
+        self.second
+    }
+}
+
+impl<'p, A: PolygonList<'p> + ?Sized, B: PolygonList<'p, Vertex=A::Vertex> + ?Sized> PolygonList<'p> for Chain<'p, A, B> {
+    type Vertex = A::Vertex;
+    type Index = ChainIndex<A::Index, B::Index>;
+    type IntoItem = PolygonElement<Self::Index>;
+    type Iter<'i> = iter::Chain<iter::Chain<iter::Map<A::Iter<'i>, fn(A::IntoItem) -> PolygonElement<Self::Index>>, iter::Once<PolygonElement<Self::Index>>>, iter::Map<B::Iter<'i>, fn(B::IntoItem) -> PolygonElement<Self::Index>>>
+    where Self: 'i, Self::Vertex: 'i, 'p: 'i;
+
+    fn vertex_count(&self) -> usize {
+        self.first.vertex_count() + self.second.vertex_count()
+    }
+
+    fn contour_count_hint(&self) -> Option<usize> {
+        Some(self.first.contour_count_hint()? + self.second.contour_count_hint()?)
+    }
+
+    fn contour_len_hint(&self, contour: usize) -> Option<usize> {
+        let first = self.first.contour_count_hint()?;
+        if contour < first {
+            self.first.contour_len_hint(contour)
+        } else {
+            self.second.contour_len_hint(contour - first)
+        }
+    }
+
+    fn iter_indices<'i>(&'i self) -> Self::Iter<'i>
+    where Self: 'i, Self::Vertex: 'i, 'p: 'i {
+        fn map_element<Index: VertexIndex, Chained: VertexIndex>(element: impl Into<PolygonElement<Index>>, f: impl FnOnce(Index) -> Chained) -> PolygonElement<Chained> {
+            match element.into() {
+                PolygonElement::ContinuePolygon(index) => PolygonElement::ContinuePolygon(f(index)),
+                PolygonElement::NewPolygon => PolygonElement::NewPolygon,
+            }
+        }
+        fn first<'p, A: PolygonList<'p> + ?Sized, B: PolygonList<'p> + ?Sized>(element: A::IntoItem) -> PolygonElement<ChainIndex<A::Index, B::Index>> {
+            map_element(element, ChainIndex::First)
+        }
+        fn second<'p, A: PolygonList<'p> + ?Sized, B: PolygonList<'p> + ?Sized>(element: B::IntoItem) -> PolygonElement<ChainIndex<A::Index, B::Index>> {
+            map_element(element, ChainIndex::Second)
+        }
+
+        // The last polygon of the first list must not continue into the second
+        self.first.iter_indices().map(first::<A, B> as fn(_) -> _)
+            .chain(iter::once(PolygonElement::NewPolygon))
+            .chain(self.second.iter_indices().map(second::<A, B> as fn(_) -> _))
+    }
+
+    fn get_vertex<'a>(&'a self, index: Self::Index) -> &'a Self::Vertex
+    where 'p: 'a {
+        match index {
+            ChainIndex::First(index) => self.first.get_vertex(index),
+            ChainIndex::Second(index) => self.second.get_vertex(index),
+        }
+    }
+}
+
+/// A [PolygonList] with some of the polygons of another, selected by their position in the order of
+/// [PolygonList::iter_indices]. Created by [PolygonList::subset].
+///
+/// Positions count only the polygons with vertices, as in [SourcePolygonsFormat](crate::formats::SourcePolygonsFormat).
+/// The polygons are in the order of the selection, and a polygon selected twice appears twice, which makes the list
+/// overlap itself. The selected polygons are found once on creation, storing the indices of their vertices, which
+/// index the underlying [PolygonList].
+///
+/// ```
+/// # use triangulate::PolygonList;
+/// let polygons = vec![
+///     vec![[0f32, 0.], [1., 0.], [0., 1.]],
+///     vec![[5., 0.], [6., 0.], [5., 1.]],
+///     vec![[9., 0.], [9., 1.], [8., 0.]],
+/// ];
+/// let subset = polygons.subset([2, 0]).expect("Position out of bounds");
+/// assert_eq!(subset.vertex_count(), 6);
+/// assert_eq!(subset.iter_indices().flatten().next(), Some([2, 0]));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Subset<'p, P: PolygonList<'p> + ?Sized> {
+    polygons: &'p P,
+    elements: Vec<Option<P::Index>>,
+    contour_lens: Vec<usize>,
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized> Subset<'p, P> {
+    /// The polygons of `polygons` at the positions `contours`, or [None] if a position is out of bounds.
+    pub fn new(polygons: &'p P, contours: impl IntoIterator<Item=usize>) -> Option<Self> {
+        let mut all = Vec::with_capacity(polygons.contour_count_hint().unwrap_or(0));
+        let mut contour = Vec::new();
+        for element in polygons.iter_indices().map(Into::into).chain(iter::once(PolygonElement::NewPolygon)) {
+            match element {
+                PolygonElement::ContinuePolygon(index) => contour.push(index),
+                PolygonElement::NewPolygon => {
+                    if !contour.is_empty() {
+                        all.push(core::mem::take(&mut contour));
+                    }
+                }
+            }
+        }
+
+        let mut elements = Vec::new();
+        let mut contour_lens = Vec::new();
+        for c in contours {
+            let contour = all.get(c)?;
+            elements.extend(contour.iter().cloned().map(Some));
+            elements.push(None);
+            contour_lens.push(contour.len());
+        }
+        Some(Self { polygons, elements, contour_lens })
+    }
+
+    /// The underlying [PolygonList]
+    pub fn polygons(&self) -> &'p P {
+        self.polygons
+    }
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized> PolygonList<'p> for Subset<'p, P> {
+    type Vertex = P::Vertex;
+    type Index = P::Index;
+    type IntoItem = Option<P::Index>;
+    type Iter<'i> = iter::Cloned<slice::Iter<'i, Option<P::Index>>>
+    where Self: 'i, Self::Vertex: 'i, 'p: 'i;
+
+    fn vertex_count(&self) -> usize {
+        self.contour_lens.iter().sum()
+    }
+
+    fn contour_count_hint(&self) -> Option<usize> {
+        Some(self.contour_lens.len())
+    }
+
+    fn contour_len_hint(&self, contour: usize) -> Option<usize> {
+        self.contour_lens.get(contour).copied()
+    }
+
+    fn iter_indices<'i>(&'i self) -> Self::Iter<'i>
+    where Self: 'i, Self::Vertex: 'i, 'p: 'i {
+        self.elements.iter().cloned()
+    }
+
+    fn get_vertex<'a>(&'a self, index: Self::Index) -> &'a Self::Vertex
+    where 'p: 'a {
+        self.polygons.get_vertex(index)
+    }
+}
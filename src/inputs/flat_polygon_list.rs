@@ -0,0 +1,152 @@
+use core::{iter::FromIterator, ops::Range};
+use alloc::vec::Vec;
+
+use crate::{PolygonElement, PolygonList, Vertex};
+
+/// A [PolygonList] stored as one [Vec] of all vertices and the end of each polygon, which can be built in a single
+/// pass over nested iterators, such as the output of a streaming parser.
+///
+/// Triangulation needs random access to the vertices, so they must be stored, but unlike a [Vec] of [Vec]s there is
+/// no allocation per polygon, and the vertices of all polygons are contiguous. Vertices are indexed by their position
+/// in [FlatPolygonList::vertices].
+///
+/// ```
+/// # use triangulate::{formats, FlatPolygonList, ListFormat, PolygonList};
+/// // Polygons as they might arrive from a parser
+/// let parsed = (0..3).map(|i| {
+///     let x = 2. * i as f32;
+///     [[x, 0.], [x + 1., 0.], [x + 1., 1.], [x, 1.]].into_iter()
+/// });
+/// let polygons = parsed.collect::<FlatPolygonList<_>>();
+/// assert_eq!(polygons.polygon_count(), 3);
+///
+/// let mut triangles = Vec::<[usize; 3]>::new();
+/// polygons.triangulate(formats::IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+/// assert_eq!(triangles.len(), 6);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlatPolygonList<V: Vertex> {
+    vertices: Vec<V>,
+    ends: Vec<usize>,
+}
+
+impl<V: Vertex> FlatPolygonList<V> {
+    /// An empty list
+    pub fn new() -> Self {
+        Self { vertices: Vec::new(), ends: Vec::new() }
+    }
+
+    /// An empty list with room for `vertices` vertices in `polygons` polygons
+    pub fn with_capacity(vertices: usize, polygons: usize) -> Self {
+        Self { vertices: Vec::with_capacity(vertices), ends: Vec::with_capacity(polygons) }
+    }
+
+    /// Add a polygon with the given vertices
+    pub fn push_polygon(&mut self, polygon: impl IntoIterator<Item=V>) {
+        self.vertices.extend(polygon);
+        self.ends.push(self.vertices.len());
+    }
+
+    /// The vertices of all polygons, in order
+    pub fn vertices(&self) -> &[V] {
+        &self.vertices
+    }
+
+    /// The number of polygons
+    pub fn polygon_count(&self) -> usize {
+        self.ends.len()
+    }
+
+    /// The vertices of the polygon at `index`
+    ///
+    /// # Panics
+    /// If `index` is not less than [FlatPolygonList::polygon_count]
+    pub fn polygon(&self, index: usize) -> &[V] {
+        &self.vertices[self.range(index)]
+    }
+
+    /// The polygons, as slices of their vertices
+    pub fn polygons(&self) -> impl Iterator<Item=&[V]> + '_ {
+        (0..self.ends.len()).map(|i| self.polygon(i))
+    }
+
+    fn range(&self, index: usize) -> Range<usize> {
+        let start = if index == 0 { 0 } else { self.ends[index - 1] };
+        start..self.ends[index]
+    }
+}
+
+impl<V: Vertex> Default for FlatPolygonList<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: Vertex, P: IntoIterator<Item=V>> Extend<P> for FlatPolygonList<V> {
+    fn extend<T: IntoIterator<Item=P>>(&mut self, polygons: T) {
+        for polygon in polygons {
+            self.push_polygon(polygon);
+        }
+    }
+}
+
+impl<V: Vertex, P: IntoIterator<Item=V>> FromIterator<P> for FlatPolygonList<V> {
+    fn from_iter<T: IntoIterator<Item=P>>(polygons: T) -> Self {
+        let mut list = Self::new();
+        list.extend(polygons);
+        list
+    }
+}
+
+/// [Iterator] for a [FlatPolygonList]
+#[derive(Debug, Clone)]
+pub struct FlatPolygonIter<'a> {
+    ends: &'a [usize],
+    index: usize,
+    polygon: usize,
+}
+
+impl<'a> Iterator for FlatPolygonIter<'a> {
+    type Item = PolygonElement<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let end = *self.ends.get(self.polygon)?;
+        Some(if self.index < end {
+            self.index += 1;
+            PolygonElement::ContinuePolygon(self.index - 1)
+        } else {
+            self.polygon += 1;
+            PolygonElement::NewPolygon
+        })
+    }
+}
+
+impl<'p, V: Vertex + 'p> PolygonList<'p> for FlatPolygonList<V> {
+    type Vertex = V;
+    type Index = usize;
+    type IntoItem = PolygonElement<usize>;
+    type Iter<'i> = FlatPolygonIter<'i>
+    where Self: 'i, Self::Vertex: 'i, 'p: 'i;
+
+    fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    fn contour_count_hint(&self) -> Option<usize> {
+        Some(self.ends.len())
+    }
+
+    fn contour_len_hint(&self, contour: usize) -> Option<usize> {
+        (contour < self.ends.len()).then(|| self.range(contour).len())
+    }
+
+    fn iter_indices<'i>(&'i self) -> Self::Iter<'i>
+    where Self: 'i, Self::Vertex: 'i, 'p: 'i {
+        FlatPolygonIter { ends: &self.ends, index: 0, polygon: 0 }
+    }
+
+    fn get_vertex<'a>(&'a self, index: Self::Index) -> &'a Self::Vertex
+    where 'p: 'a {
+        &self.vertices[index]
+    }
+}
@@ -0,0 +1,63 @@
+use core::fmt::Debug;
+
+use super::vertex::{Scalar, Vertex};
+
+/// A [Vertex] which can be created from a position alone, for the vertices that clipping, refinement and the
+/// conversion of generated meshes (such as strokes and text) add.
+///
+/// With this implemented, [LinearConstructor](crate::LinearConstructor) interpolates the vertex type, and
+/// [Mesh::convert](crate::mesh::Mesh::convert) and [Refinement::vertex](crate::refinement::Refinement::vertex) produce
+/// it, without a callback for each of them.
+/// ```
+/// # use triangulate::{mesh::Mesh, stroke::{self, StrokeOptions}, FromCoords, Vertex};
+/// #[derive(Debug, Clone, PartialEq)]
+/// struct Point {
+///     x: f32,
+///     y: f32,
+/// }
+///
+/// impl Vertex for Point {
+///     type Coordinate = f32;
+///     type Payload = ();
+///
+///     fn x(&self) -> f32 { self.x }
+///     fn y(&self) -> f32 { self.y }
+///     fn payload(&self) -> &() { &() }
+/// }
+///
+/// impl FromCoords for Point {
+///     fn from_coords([x, y]: [f32; 2]) -> Self { Point { x, y } }
+/// }
+///
+/// let line = [Point { x: 0., y: 0. }, Point { x: 4., y: 0. }];
+/// let mesh: Mesh<Point> = stroke::stroke(&line, false, &StrokeOptions::default()).convert();
+/// assert!(mesh.vertices.contains(&Point { x: 0., y: 0.5 }));
+/// ```
+pub trait FromCoords: Vertex {
+    /// The vertex at `coords`, as `[x, y]`. Any [Vertex::Payload] is up to the implementation, such as a default.
+    fn from_coords(coords: [Self::Coordinate; 2]) -> Self;
+}
+
+impl<C: Debug + Scalar> FromCoords for [C; 2] {
+    fn from_coords(coords: [C; 2]) -> Self {
+        coords
+    }
+}
+
+impl<C: Debug + Scalar> FromCoords for (C, C) {
+    fn from_coords([x, y]: [C; 2]) -> Self {
+        (x, y)
+    }
+}
+
+/// The position of a [Vertex] as `[x, y]`, the inverse of [FromCoords]. Implemented for every [Vertex].
+pub trait IntoCoords: Vertex {
+    /// The position of the vertex
+    fn into_coords(self) -> [Self::Coordinate; 2];
+}
+
+impl<V: Vertex> IntoCoords for V {
+    fn into_coords(self) -> [V::Coordinate; 2] {
+        [self.x(), self.y()]
+    }
+}
@@ -0,0 +1,221 @@
+use core::iter;
+
+use geo_types::{Coord, CoordNum, LineString, MultiPolygon, Polygon};
+
+use crate::{FromCoords, PolygonElement, PolygonList, Vertex};
+
+impl<T: CoordNum> Vertex for Coord<T> {
+    type Coordinate = T;
+    type Payload = ();
+
+    #[inline(always)]
+    fn x(&self) -> Self::Coordinate {
+        self.x
+    }
+
+    #[inline(always)]
+    fn y(&self) -> Self::Coordinate {
+        self.y
+    }
+
+    #[inline(always)]
+    fn payload(&self) -> &() {
+        &()
+    }
+}
+
+impl<T: CoordNum> FromCoords for Coord<T> {
+    fn from_coords([x, y]: [T; 2]) -> Self {
+        Coord { x, y }
+    }
+}
+
+// The exterior is ring 0, followed by the interiors
+fn ring<T: CoordNum>(polygon: &Polygon<T>, ring: usize) -> Option<&LineString<T>> {
+    match ring {
+        0 => Some(polygon.exterior()),
+        _ => polygon.interiors().get(ring - 1),
+    }
+}
+
+// Out of bounds indices panic as with slice indexing
+fn ring_vertex<T: CoordNum>(polygon: &Polygon<T>, ring: usize, vertex: usize) -> &Coord<T> {
+    match ring {
+        0 => &polygon.exterior().0[vertex],
+        _ => &polygon.interiors()[ring - 1].0[vertex],
+    }
+}
+
+// Rings are closed by repeating the first coordinate, which is not a separate vertex
+fn ring_len<T: CoordNum>(ring: &LineString<T>) -> usize {
+    match ring.0.as_slice() {
+        [first, .., last] if first == last => ring.0.len() - 1,
+        coords => coords.len(),
+    }
+}
+
+fn polygon_vertex_count<T: CoordNum>(polygon: &Polygon<T>) -> usize {
+    ring_len(polygon.exterior()) + polygon.interiors().iter().map(ring_len).sum::<usize>()
+}
+
+/// [Iterator] for [GeoPolygon] and [GeoMultiPolygon], over the rings of each polygon: the exterior, then the
+/// interiors
+#[derive(Debug, Clone)]
+pub struct GeoPolygonsIter<'i, T: CoordNum> {
+    polygons: &'i [Polygon<T>],
+    polygon: usize,
+    ring: usize,
+    vertex: usize,
+}
+
+impl<'i, T: CoordNum> GeoPolygonsIter<'i, T> {
+    fn new(polygons: &'i [Polygon<T>]) -> Self {
+        Self { polygons, polygon: 0, ring: 0, vertex: 0 }
+    }
+}
+
+impl<'i, T: CoordNum> Iterator for GeoPolygonsIter<'i, T> {
+    type Item = PolygonElement<[usize; 3]>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let polygon = self.polygons.get(self.polygon)?;
+            match ring(polygon, self.ring) {
+                Some(ring) if self.vertex < ring_len(ring) => {
+                    self.vertex += 1;
+                    return Some(PolygonElement::ContinuePolygon([self.polygon, self.ring, self.vertex - 1]));
+                }
+                Some(_) => {
+                    self.ring += 1;
+                    self.vertex = 0;
+                    return Some(PolygonElement::NewPolygon);
+                }
+                None => {
+                    self.polygon += 1;
+                    self.ring = 0;
+                }
+            }
+        }
+    }
+}
+
+/// A `geo` [Polygon] as a [PolygonList] of its exterior and interiors, indexed by `[ring, vertex]`, where the exterior
+/// is ring 0. The closing coordinate of each ring is not a separate vertex. Requires the `geo` feature.
+///
+/// [PolygonList] is implemented for anything which dereferences to a slice of polygons, so it can't be implemented
+/// for the `geo` types themselves.
+///
+/// ```
+/// # use triangulate::{formats, GeoPolygon, ListFormat, PolygonList};
+/// use geo_types::{coord, LineString, Polygon};
+///
+/// let square = LineString::from(vec![(0., 0.), (3., 0.), (3., 3.), (0., 3.)]);
+/// let hole = LineString::from(vec![(1., 1.), (1., 2.), (2., 2.), (2., 1.)]);
+/// let polygon = Polygon::new(square, vec![hole]);
+///
+/// let polygons = GeoPolygon::new(&polygon);
+/// let mut triangles = Vec::<[usize; 2]>::new();
+/// polygons.triangulate(formats::IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+/// assert_eq!(triangles.len(), 8 * 3);
+/// assert_eq!(*polygons.get_vertex([1, 2]), coord! { x: 2., y: 2. });
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct GeoPolygon<'a, T: CoordNum>(&'a Polygon<T>);
+
+impl<'a, T: CoordNum> GeoPolygon<'a, T> {
+    /// Wrap `polygon`
+    pub fn new(polygon: &'a Polygon<T>) -> Self {
+        Self(polygon)
+    }
+}
+
+impl<'a, T: CoordNum> From<&'a Polygon<T>> for GeoPolygon<'a, T> {
+    fn from(polygon: &'a Polygon<T>) -> Self {
+        Self::new(polygon)
+    }
+}
+
+impl<'p, 'a: 'p, T: CoordNum + 'p> PolygonList<'p> for GeoPolygon<'a, T> {
+    type Vertex = Coord<T>;
+    type Index = [usize; 2];
+    type IntoItem = PolygonElement<[usize; 2]>;
+    type Iter<'i> = iter::Map<GeoPolygonsIter<'i, T>, fn(PolygonElement<[usize; 3]>) -> PolygonElement<[usize; 2]>>
+    where Self: 'i, Self::Vertex: 'i, 'p: 'i;
+
+    fn vertex_count(&self) -> usize {
+        polygon_vertex_count(self.0)
+    }
+
+    fn contour_count_hint(&self) -> Option<usize> {
+        Some(1 + self.0.interiors().len())
+    }
+
+    fn contour_len_hint(&self, contour: usize) -> Option<usize> {
+        ring(self.0, contour).map(ring_len)
+    }
+
+    fn iter_indices<'i>(&'i self) -> Self::Iter<'i>
+    where Self: 'i, Self::Vertex: 'i, 'p: 'i {
+        fn ring_vertex(element: PolygonElement<[usize; 3]>) -> PolygonElement<[usize; 2]> {
+            match element {
+                PolygonElement::ContinuePolygon([_, ring, vertex]) => PolygonElement::ContinuePolygon([ring, vertex]),
+                PolygonElement::NewPolygon => PolygonElement::NewPolygon,
+            }
+        }
+        GeoPolygonsIter::new(core::slice::from_ref(self.0)).map(ring_vertex)
+    }
+
+    fn get_vertex<'b>(&'b self, index: Self::Index) -> &'b Self::Vertex
+    where 'p: 'b {
+        let [r, v] = index;
+        ring_vertex(self.0, r, v)
+    }
+}
+
+/// A `geo` [MultiPolygon] as a [PolygonList] of the rings of all its polygons, indexed by `[polygon, ring, vertex]`,
+/// where the exterior of each polygon is ring 0. The closing coordinate of each ring is not a separate vertex.
+/// Requires the `geo` feature.
+///
+/// See [GeoPolygon] for why the `geo` types don't implement [PolygonList] themselves.
+#[derive(Debug, Clone, Copy)]
+pub struct GeoMultiPolygon<'a, T: CoordNum>(&'a MultiPolygon<T>);
+
+impl<'a, T: CoordNum> GeoMultiPolygon<'a, T> {
+    /// Wrap `polygons`
+    pub fn new(polygons: &'a MultiPolygon<T>) -> Self {
+        Self(polygons)
+    }
+}
+
+impl<'a, T: CoordNum> From<&'a MultiPolygon<T>> for GeoMultiPolygon<'a, T> {
+    fn from(polygons: &'a MultiPolygon<T>) -> Self {
+        Self::new(polygons)
+    }
+}
+
+impl<'p, 'a: 'p, T: CoordNum + 'p> PolygonList<'p> for GeoMultiPolygon<'a, T> {
+    type Vertex = Coord<T>;
+    type Index = [usize; 3];
+    type IntoItem = PolygonElement<[usize; 3]>;
+    type Iter<'i> = GeoPolygonsIter<'i, T>
+    where Self: 'i, Self::Vertex: 'i, 'p: 'i;
+
+    fn vertex_count(&self) -> usize {
+        self.0.0.iter().map(polygon_vertex_count).sum()
+    }
+
+    fn contour_count_hint(&self) -> Option<usize> {
+        Some(self.0.0.iter().map(|polygon| 1 + polygon.interiors().len()).sum())
+    }
+
+    fn iter_indices<'i>(&'i self) -> Self::Iter<'i>
+    where Self: 'i, Self::Vertex: 'i, 'p: 'i {
+        GeoPolygonsIter::new(&self.0.0)
+    }
+
+    fn get_vertex<'b>(&'b self, index: Self::Index) -> &'b Self::Vertex
+    where 'p: 'b {
+        let [p, r, v] = index;
+        ring_vertex(&self.0.0[p], r, v)
+    }
+}
@@ -0,0 +1,169 @@
+use core::iter;
+use alloc::vec::Vec;
+
+use crate::{FanBuilder, FanFormat, FanBuilderState, GeometryKernel, DefaultKernel, ListFormat, Orientation, PolygonElement, PolygonList, TriangleWinding, TriangulationError, Vertex, VertexIndex, formats};
+
+/// Identifies a vertex of an [InteriorPoints] list
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum InteriorPointIndex<Index: VertexIndex> {
+    /// A vertex of one of the polygons
+    Boundary(Index),
+    /// The interior point at this position in the slice passed to [PolygonList::with_interior_points]
+    Interior(usize),
+}
+
+/// A [PolygonList] with isolated interior points that must appear as vertices of the triangulation.
+/// Created by [PolygonList::with_interior_points].
+///
+/// The polygons are triangulated as usual, then each interior point is inserted by splitting the triangle
+/// (or the pair of triangles sharing the edge) it lies in. Every point must lie strictly inside the polygons and
+/// must not coincide with another vertex, otherwise triangulation fails with [TriangulationError::InvalidInteriorPoint].
+///
+/// Interior points are only considered by [PolygonList::triangulate] and [PolygonList::triangulate_with_kernel];
+/// a [Trapezoidation](crate::Trapezoidation) of this list contains only the polygons.
+#[derive(Debug)]
+pub struct InteriorPoints<'p, P: PolygonList<'p> + ?Sized> {
+    polygons: &'p P,
+    points: &'p [P::Vertex],
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized> Clone for InteriorPoints<'p, P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized> Copy for InteriorPoints<'p, P> { }
+
+impl<'p, P: PolygonList<'p> + ?Sized> InteriorPoints<'p, P> {
+    pub(crate) fn new(polygons: &'p P, points: &'p [P::Vertex]) -> Self {
+        Self { polygons, points }
+    }
+
+    /// The underlying [PolygonList]
+    pub fn polygons(&self) -> &'p P {
+        self.polygons
+    }
+
+    /// The interior points, indexed by [InteriorPointIndex::Interior]
+    pub fn points(&self) -> &'p [P::Vertex] {
+        self.points
+    }
+
+    fn coords(&self, index: &InteriorPointIndex<P::Index>) -> [<P::Vertex as Vertex>::Coordinate; 2] {
+        let v = self.get_vertex(index.clone());
+        [v.x(), v.y()]
+    }
+
+    fn triangulate_inner<K: GeometryKernel<<P::Vertex as Vertex>::Coordinate>, FB: FanFormat<'p, Self>>(&'p self, fbs: &mut FanBuilderState<'p, Self, FB>) -> Result<(), TriangulationError<<FB::Builder as FanBuilder<'p, Self>>::Error>> {
+        let mut indices = Vec::new();
+        self.trapezoidize_with_kernel::<K>()
+            .map_err(TriangulationError::TrapezoidationError)?
+            .triangulate(formats::IndexedListFormat::new(&mut indices).into_fan_format())
+            .map_err(TriangulationError::with_fan_builder_error)?;
+
+        // Normalize every triangle to counter-clockwise, independent of the intermediate format
+        let mut triangles: Vec<[InteriorPointIndex<P::Index>; 3]> = indices.chunks_exact(3).map(|t| {
+            let [c0, c1, c2] = [&t[0], &t[1], &t[2]].map(|vi| self.coords(vi));
+            if K::orientation(&c0, &c1, &c2) == Orientation::Right {
+                [t[0].clone(), t[2].clone(), t[1].clone()]
+            } else {
+                [t[0].clone(), t[1].clone(), t[2].clone()]
+            }
+        }).collect();
+
+        for (pi, point) in self.points.iter().enumerate() {
+            let c = [point.x(), point.y()];
+            let vp = InteriorPointIndex::Interior(pi);
+
+            let (ti, orientations) = triangles.iter().enumerate().find_map(|(ti, t)| {
+                let [c0, c1, c2] = t.each_ref().map(|vi| self.coords(vi));
+                let orientations = [K::orientation(&c0, &c1, &c), K::orientation(&c1, &c2, &c), K::orientation(&c2, &c0, &c)];
+                (!orientations.contains(&Orientation::Right)).then_some((ti, orientations))
+            }).ok_or(TriangulationError::InvalidInteriorPoint(pi))?;
+
+            let mut collinear = orientations.iter().enumerate().filter(|(_, o)| **o == Orientation::Collinear).map(|(k, _)| k);
+            match (collinear.next(), collinear.next()) {
+                (None, _) => {
+                    let [v0, v1, v2] = triangles[ti].clone();
+                    triangles[ti] = [v0.clone(), v1.clone(), vp.clone()];
+                    triangles.push([v1, v2.clone(), vp.clone()]);
+                    triangles.push([v2, v0, vp]);
+                }
+                (Some(k), None) => {
+                    // The point lies on an edge, which must be shared with a neighboring triangle
+                    let t = &triangles[ti];
+                    let (va, vb, vc) = (t[k].clone(), t[(k + 1) % 3].clone(), t[(k + 2) % 3].clone());
+                    let (tj, vd) = triangles.iter().enumerate().find_map(|(tj, t)| {
+                        (0..3).find(|&l| t[l] == vb && t[(l + 1) % 3] == va).map(|l| (tj, t[(l + 2) % 3].clone()))
+                    }).ok_or(TriangulationError::InvalidInteriorPoint(pi))?;
+
+                    triangles[ti] = [va.clone(), vp.clone(), vc.clone()];
+                    triangles.push([vp.clone(), vb.clone(), vc]);
+                    triangles[tj] = [vb, vp.clone(), vd.clone()];
+                    triangles.push([vp, va, vd]);
+                }
+                // The point coincides with a vertex
+                (Some(_), Some(_)) => return Err(TriangulationError::InvalidInteriorPoint(pi)),
+            }
+        }
+
+        for [v0, v1, v2] in triangles {
+            if FB::Builder::WINDING == TriangleWinding::Clockwise {
+                fbs.new_fan(self, v0, v2, v1)?;
+            } else {
+                fbs.new_fan(self, v0, v1, v2)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized> PolygonList<'p> for InteriorPoints<'p, P> {
+    type Vertex = P::Vertex;
+    type Index = InteriorPointIndex<P::Index>;
+    type IntoItem = PolygonElement<Self::Index>;
+    type Iter<'i> = iter::Map<P::Iter<'i>, fn(P::IntoItem) -> PolygonElement<Self::Index>>
+    where Self: 'i, Self::Vertex: 'i, 'p: 'i;
+
+    fn vertex_count(&self) -> usize {
+        self.polygons.vertex_count() + self.points.len()
+    }
+
+    fn contour_count_hint(&self) -> Option<usize> {
+        self.polygons.contour_count_hint()
+    }
+
+    fn contour_len_hint(&self, contour: usize) -> Option<usize> {
+        self.polygons.contour_len_hint(contour)
+    }
+
+    fn iter_indices<'i>(&'i self) -> Self::Iter<'i>
+    where Self: 'i, Self::Vertex: 'i, 'p: 'i {
+        fn map_element<Index: VertexIndex>(element: impl Into<PolygonElement<Index>>) -> PolygonElement<InteriorPointIndex<Index>> {
+            match element.into() {
+                PolygonElement::ContinuePolygon(index) => PolygonElement::ContinuePolygon(InteriorPointIndex::Boundary(index)),
+                PolygonElement::NewPolygon => PolygonElement::NewPolygon,
+            }
+        }
+        self.polygons.iter_indices().map(map_element::<P::Index>)
+    }
+
+    fn get_vertex<'a>(&'a self, index: Self::Index) -> &'a Self::Vertex
+    where 'p: 'a {
+        match index {
+            InteriorPointIndex::Boundary(index) => self.polygons.get_vertex(index),
+            InteriorPointIndex::Interior(index) => &self.points[index],
+        }
+    }
+
+    fn triangulate<FB: FanFormat<'p, Self>>(&'p self, format: FB) -> Result<<FB::Builder as FanBuilder<'p, Self>>::Output, TriangulationError<<FB::Builder as FanBuilder<'p, Self>>::Error>> {
+        self.triangulate_with_kernel::<DefaultKernel, FB>(format)
+    }
+
+    fn triangulate_with_kernel<K: GeometryKernel<<Self::Vertex as Vertex>::Coordinate>, FB: FanFormat<'p, Self>>(&'p self, format: FB) -> Result<<FB::Builder as FanBuilder<'p, Self>>::Output, TriangulationError<<FB::Builder as FanBuilder<'p, Self>>::Error>> {
+        let mut fbs = FanBuilderState::<'p, Self, FB>::Uninitialized(format);
+        let result = self.triangulate_inner::<K, FB>(&mut fbs);
+        fbs.complete(result)
+    }
+}
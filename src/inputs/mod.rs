@@ -1,8 +1,26 @@
 mod polygon_list;
 pub use polygon_list::{Polygon, PolygonList, PolygonElement, IndexWith, IndexWithIter};
 pub(crate) use polygon_list::PolygonListExt;
+mod flat_polygon_list;
+pub use flat_polygon_list::{FlatPolygonList, FlatPolygonIter};
+mod polygon_adapters;
+pub use polygon_adapters::{Reversed, Dedup, Closed, Unbridged, Sanitized, Region};
+mod combinators;
+pub use combinators::{Chain, ChainIndex, Subset};
+mod interior_points;
+pub use interior_points::{InteriorPoints, InteriorPointIndex};
+mod weakly_simple;
+pub use weakly_simple::WeaklySimple;
 mod vertex;
-pub use vertex::Vertex;
+pub use vertex::{Vertex, Scalar};
 pub(crate) use vertex::{VertexExt, Coords};
+mod vertex_constructor;
+pub use vertex_constructor::{VertexConstructor, LinearConstructor};
+mod from_coords;
+pub use from_coords::{FromCoords, IntoCoords};
+#[cfg(feature = "geo")]
+mod geo;
+#[cfg(feature = "geo")]
+pub use geo::{GeoPolygon, GeoMultiPolygon, GeoPolygonsIter};
 mod vertex_index;
 pub use vertex_index::VertexIndex;
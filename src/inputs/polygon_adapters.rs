@@ -0,0 +1,496 @@
+use core::{cmp, iter, ops::Range, slice};
+use alloc::{vec, vec::Vec};
+
+use num_traits::real::Real;
+
+use crate::{components::UnionFind, Polygon, PolygonElement, PolygonList, Vertex};
+
+// The adapters index vertices by position, like lists of polygons do, so they can be nested in each other and
+// collected into a [Vec] of polygons. The wrapped polygon's vertices are taken in the order 0..vertex_count.
+
+fn same_position<V: Vertex>(a: &V, b: &V) -> bool {
+    a.x() == b.x() && a.y() == b.y()
+}
+
+/// A [Polygon] with the vertices of another in reverse order, which flips it between clockwise and
+/// counter-clockwise.
+///
+/// ```
+/// # use triangulate::{Polygon, Reversed};
+/// let clockwise = [[0f32, 0.], [0., 1.], [1., 1.], [1., 0.]];
+/// let polygon = Reversed::new(&clockwise[..]);
+/// assert_eq!(polygon.get_vertex(0), &[1., 0.]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Reversed<P>(P);
+
+impl<P> Reversed<P> {
+    /// Reverse the vertices of `polygon`
+    pub fn new(polygon: P) -> Self {
+        Self(polygon)
+    }
+
+    /// The wrapped polygon
+    pub fn into_inner(self) -> P {
+        self.0
+    }
+}
+
+impl<'p, P: Polygon<'p, Index=usize>> Polygon<'p> for Reversed<P> {
+    type Vertex = P::Vertex;
+    type Index = usize;
+    type Iter<'i> = Range<usize>
+    where Self: 'i, Self::Vertex: 'i, 'p: 'i;
+
+    fn vertex_count(&self) -> usize {
+        self.0.vertex_count()
+    }
+
+    fn iter_indices<'i>(&'i self) -> Self::Iter<'i>
+    where Self: 'i, Self::Vertex: 'i, 'p: 'i {
+        0..self.vertex_count()
+    }
+
+    fn get_vertex(&self, index: Self::Index) -> &Self::Vertex {
+        self.0.get_vertex(self.0.vertex_count() - 1 - index)
+    }
+}
+
+/// A [Polygon] without the vertices of another which are at the same position as the one before them, including
+/// the last if it is at the position of the first.
+///
+/// Triangulation fails on repeated vertices. The duplicates are found once on creation, storing the positions of the
+/// remaining vertices.
+///
+/// ```
+/// # use triangulate::{Dedup, Polygon};
+/// let polygon = [[0f32, 0.], [1., 0.], [1., 0.], [1., 1.], [0., 0.]];
+/// assert_eq!(Dedup::new(&polygon[..]).vertex_count(), 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Dedup<P> {
+    polygon: P,
+    kept: Vec<usize>,
+}
+
+impl<P> Dedup<P> {
+    /// Skip the repeated vertices of `polygon`
+    pub fn new<'p>(polygon: P) -> Self
+    where P: Polygon<'p, Index=usize> {
+        let n = polygon.vertex_count();
+        let mut kept: Vec<usize> = Vec::with_capacity(n);
+        for i in 0..n {
+            let repeated = kept.last().is_some_and(|&last| same_position(polygon.get_vertex(last), polygon.get_vertex(i)));
+            if !repeated {
+                kept.push(i);
+            }
+        }
+        while kept.len() > 1 && same_position(polygon.get_vertex(kept[kept.len() - 1]), polygon.get_vertex(kept[0])) {
+            kept.pop();
+        }
+        Self { polygon, kept }
+    }
+
+    /// The wrapped polygon
+    pub fn into_inner(self) -> P {
+        self.polygon
+    }
+}
+
+impl<'p, P: Polygon<'p, Index=usize>> Polygon<'p> for Dedup<P> {
+    type Vertex = P::Vertex;
+    type Index = usize;
+    type Iter<'i> = Range<usize>
+    where Self: 'i, Self::Vertex: 'i, 'p: 'i;
+
+    fn vertex_count(&self) -> usize {
+        self.kept.len()
+    }
+
+    fn iter_indices<'i>(&'i self) -> Self::Iter<'i>
+    where Self: 'i, Self::Vertex: 'i, 'p: 'i {
+        0..self.vertex_count()
+    }
+
+    fn get_vertex(&self, index: Self::Index) -> &Self::Vertex {
+        self.polygon.get_vertex(self.kept[index])
+    }
+}
+
+/// A [Polygon] for a closed ring of vertices, as in GeoJSON or WKT, whose last vertex repeats the first. The last
+/// vertex is skipped if it is at the position of the first, and kept otherwise.
+///
+/// ```
+/// # use triangulate::{Closed, Polygon};
+/// let ring = [[0f32, 0.], [1., 0.], [1., 1.], [0., 0.]];
+/// assert_eq!(Closed::new(&ring[..]).vertex_count(), 3);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Closed<P>(P);
+
+impl<P> Closed<P> {
+    /// Open the ring `polygon`
+    pub fn new(polygon: P) -> Self {
+        Self(polygon)
+    }
+
+    /// The wrapped polygon
+    pub fn into_inner(self) -> P {
+        self.0
+    }
+}
+
+impl<'p, P: Polygon<'p, Index=usize>> Polygon<'p> for Closed<P> {
+    type Vertex = P::Vertex;
+    type Index = usize;
+    type Iter<'i> = Range<usize>
+    where Self: 'i, Self::Vertex: 'i, 'p: 'i;
+
+    fn vertex_count(&self) -> usize {
+        let n = self.0.vertex_count();
+        if n > 1 && same_position(self.0.get_vertex(0), self.0.get_vertex(n - 1)) {
+            n - 1
+        } else {
+            n
+        }
+    }
+
+    fn iter_indices<'i>(&'i self) -> Self::Iter<'i>
+    where Self: 'i, Self::Vertex: 'i, 'p: 'i {
+        0..self.vertex_count()
+    }
+
+    fn get_vertex(&self, index: Self::Index) -> &Self::Vertex {
+        self.0.get_vertex(index)
+    }
+}
+
+// An edge between two vertex positions of `P`
+type Edge<'p, P> = [[<<P as PolygonList<'p>>::Vertex as Vertex>::Coordinate; 2]; 2];
+
+/// A [PolygonList] with the polygons of another, where holes joined to their outer boundary by zero-width bridges
+/// (the workaround for triangulators without hole support) are split off again.
+///
+/// A bridge is a pair of edges of one polygon between the same two positions, in opposite directions. Both edges and
+/// the repeated vertices at their ends are removed, splitting the polygon in two, and the pieces are searched for
+/// more bridges. Pieces with fewer than 3 vertices are dropped. The bridges are found once on creation, storing the
+/// indices of the remaining vertices.
+///
+/// ```
+/// # use triangulate::{formats, ListFormat, PolygonList, Unbridged};
+/// // A square with a square hole, joined by a bridge between (0, 0) and (1, 1)
+/// let bridged = vec![vec![
+///     [0f32, 0.], [1., 1.], [1., 2.], [2., 2.], [2., 1.], [1., 1.],
+///     [0., 0.], [3., 0.], [3., 3.], [0., 3.],
+/// ]];
+/// let polygons = Unbridged::new(&bridged);
+/// assert_eq!(polygons.vertex_count(), 8);
+/// let mut triangles = Vec::<[usize; 2]>::new();
+/// polygons.triangulate(formats::IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+/// assert_eq!(triangles.len(), 8 * 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Unbridged<'p, P: PolygonList<'p> + ?Sized> {
+    polygons: &'p P,
+    elements: Vec<Option<P::Index>>,
+    vertex_count: usize,
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized> Unbridged<'p, P> {
+    /// Split the bridged polygons of `polygons`
+    pub fn new(polygons: &'p P) -> Self {
+        let mut contours = Vec::new();
+        let mut contour = Vec::new();
+        for element in polygons.iter_indices().map(Into::into).chain(iter::once(PolygonElement::NewPolygon)) {
+            match element {
+                PolygonElement::ContinuePolygon(index) => contour.push(index),
+                PolygonElement::NewPolygon => {
+                    if !contour.is_empty() {
+                        contours.push(core::mem::take(&mut contour));
+                    }
+                }
+            }
+        }
+
+        // Taken from the end, so the polygons keep their order, and the rest of a split polygon comes before its hole
+        contours.reverse();
+        let mut elements = Vec::with_capacity(polygons.vertex_count() + contours.len());
+        let mut vertex_count = 0;
+        while let Some(contour) = contours.pop() {
+            if contour.len() < 3 {
+                continue;
+            }
+            match Self::find_bridge(polygons, &contour) {
+                Some((k, m)) => {
+                    contours.push(contour[k + 1..m].to_vec());
+                    contours.push(contour[m + 1..].iter().chain(&contour[..k]).cloned().collect());
+                }
+                None => {
+                    vertex_count += contour.len();
+                    elements.extend(contour.into_iter().map(Some));
+                    elements.push(None);
+                }
+            }
+        }
+        Self { polygons, elements, vertex_count }
+    }
+
+    /// The underlying [PolygonList]
+    pub fn polygons(&self) -> &'p P {
+        self.polygons
+    }
+
+    // The first edges `k < m` of `contour` which run between the same positions in opposite directions
+    fn find_bridge(polygons: &'p P, contour: &[P::Index]) -> Option<(usize, usize)> {
+        let position = |k: usize| {
+            let v = polygons.get_vertex(contour[k % contour.len()].clone());
+            [v.x(), v.y()]
+        };
+        let compare = |a: &Edge<'p, P>, b: &Edge<'p, P>| {
+            a.iter().flatten().zip(b.iter().flatten())
+                .map(|(a, b)| a.partial_cmp(b).unwrap_or(cmp::Ordering::Equal))
+                .find(|o| *o != cmp::Ordering::Equal)
+                .unwrap_or(cmp::Ordering::Equal)
+        };
+
+        let mut edges: Vec<(Edge<'p, P>, usize)> = (0..contour.len())
+            .map(|k| ([position(k), position(k + 1)], k))
+            .filter(|([c0, c1], _)| c0 != c1)
+            .collect();
+        edges.sort_by(|(a, _), (b, _)| compare(a, b));
+        edges.iter().filter_map(|([c0, c1], k)| {
+            let reverse = [c1.clone(), c0.clone()];
+            let start = edges.partition_point(|(e, _)| compare(e, &reverse) == cmp::Ordering::Less);
+            edges.get(start).filter(|(e, _)| compare(e, &reverse) == cmp::Ordering::Equal).map(|(_, m)| ((*k).min(*m), (*k).max(*m)))
+        }).min()
+    }
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized> PolygonList<'p> for Unbridged<'p, P> {
+    type Vertex = P::Vertex;
+    type Index = P::Index;
+    type IntoItem = Option<P::Index>;
+    type Iter<'i> = iter::Cloned<slice::Iter<'i, Option<P::Index>>>
+    where Self: 'i, Self::Vertex: 'i, 'p: 'i;
+
+    fn vertex_count(&self) -> usize {
+        self.vertex_count
+    }
+
+    fn iter_indices<'i>(&'i self) -> Self::Iter<'i>
+    where Self: 'i, Self::Vertex: 'i, 'p: 'i {
+        self.elements.iter().cloned()
+    }
+
+    fn get_vertex<'a>(&'a self, index: Self::Index) -> &'a Self::Vertex
+    where 'p: 'a {
+        self.polygons.get_vertex(index)
+    }
+}
+
+/// A [PolygonList] with the polygons of another, cleaned of the small defects common in real-world vector data which
+/// break the precondition of simple polygons. Constructed with [PolygonList::sanitized].
+///
+/// - Vertices closer than `epsilon` to each other are welded, even across polygons, each cluster taking the index
+///   (and position) of its first vertex in iteration order. Welding is transitive, so a chain of close vertices is
+///   welded into one
+/// - Consecutive vertices at the same position are removed
+/// - Vertices within `epsilon` of the line through their neighbours are removed, which also removes spikes doubling
+///   back on themselves
+/// - Polygons left with fewer than 3 vertices are dropped
+///
+/// The remaining vertices index the underlying [PolygonList], so nothing is copied. The cleaning is done once on
+/// creation.
+///
+/// ```
+/// # use triangulate::PolygonList;
+/// let polygons = vec![
+///     vec![[0f64, 0.], [1., 0.], [2., 0.], [2., 0.], [2., 2.], [1e-9, 2.], [0., 2.]],
+///     vec![[5., 5.], [6., 5.]],
+/// ];
+/// let sanitized = polygons.sanitized(1e-6);
+/// assert_eq!(sanitized.vertex_count(), 4);
+/// assert_eq!(sanitized.iter_indices().flatten().collect::<Vec<_>>(), vec![[0, 0], [0, 2], [0, 4], [0, 5]]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Sanitized<'p, P: PolygonList<'p> + ?Sized> {
+    polygons: &'p P,
+    elements: Vec<Option<P::Index>>,
+    vertex_count: usize,
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized> Sanitized<'p, P>
+where <P::Vertex as Vertex>::Coordinate: Real {
+    pub(crate) fn new(polygons: &'p P, epsilon: <P::Vertex as Vertex>::Coordinate) -> Self {
+        let mut indices = Vec::with_capacity(polygons.vertex_count());
+        let mut contours = Vec::new();
+        let mut contour = Vec::new();
+        for element in polygons.iter_indices().map(Into::into).chain(iter::once(PolygonElement::NewPolygon)) {
+            match element {
+                PolygonElement::ContinuePolygon(index) => {
+                    contour.push(indices.len());
+                    indices.push(index);
+                }
+                PolygonElement::NewPolygon => {
+                    if !contour.is_empty() {
+                        contours.push(core::mem::take(&mut contour));
+                    }
+                }
+            }
+        }
+        let position = |i: usize| {
+            let v = polygons.get_vertex(indices[i].clone());
+            [v.x(), v.y()]
+        };
+
+        // Weld close vertices by sweeping over them in order of x
+        let welded = {
+            let mut order: Vec<usize> = (0..indices.len()).collect();
+            order.sort_by(|&a, &b| position(a)[0].partial_cmp(&position(b)[0]).unwrap_or(cmp::Ordering::Equal));
+            let mut sets = UnionFind::new(indices.len());
+            for (k, &a) in order.iter().enumerate() {
+                let [ax, ay] = position(a);
+                for &b in order[k + 1..].iter() {
+                    let [bx, by] = position(b);
+                    if bx - ax >= epsilon {
+                        break;
+                    }
+                    if ((bx - ax).powi(2) + (by - ay).powi(2)).sqrt() < epsilon {
+                        sets.union(a, b);
+                    }
+                }
+            }
+            let mut first = vec![None; indices.len()];
+            (0..indices.len()).map(|i| {
+                let root = sets.find(i);
+                *first[root].get_or_insert(i)
+            }).collect::<Vec<_>>()
+        };
+
+        let same = |a: usize, b: usize| position(a) == position(b);
+        let collinear = |a: usize, b: usize, c: usize| {
+            let ([ax, ay], [bx, by], [cx, cy]) = (position(a), position(b), position(c));
+            let (dx, dy) = (cx - ax, cy - ay);
+            (dx * (by - ay) - dy * (bx - ax)).abs() <= epsilon * (dx * dx + dy * dy).sqrt()
+        };
+
+        let mut elements = Vec::with_capacity(indices.len() + contours.len());
+        let mut vertex_count = 0;
+        for contour in contours {
+            let mut kept: Vec<usize> = Vec::with_capacity(contour.len());
+            for i in contour {
+                kept.push(welded[i]);
+                loop {
+                    let n = kept.len();
+                    if n >= 2 && same(kept[n - 2], kept[n - 1]) {
+                        kept.pop();
+                    } else if n >= 3 && collinear(kept[n - 3], kept[n - 2], kept[n - 1]) {
+                        kept.remove(n - 2);
+                    } else {
+                        break;
+                    }
+                }
+            }
+            // Clean up around the join between the last vertex and the first
+            while kept.len() >= 3 {
+                let n = kept.len();
+                if same(kept[n - 1], kept[0]) || collinear(kept[n - 2], kept[n - 1], kept[0]) {
+                    kept.pop();
+                } else if collinear(kept[n - 1], kept[0], kept[1]) {
+                    kept.remove(0);
+                } else {
+                    break;
+                }
+            }
+            if kept.len() < 3 {
+                continue;
+            }
+            vertex_count += kept.len();
+            elements.extend(kept.into_iter().map(|i| Some(indices[i].clone())));
+            elements.push(None);
+        }
+        Self { polygons, elements, vertex_count }
+    }
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized> Sanitized<'p, P> {
+    /// The underlying [PolygonList]
+    pub fn polygons(&self) -> &'p P {
+        self.polygons
+    }
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized> PolygonList<'p> for Sanitized<'p, P> {
+    type Vertex = P::Vertex;
+    type Index = P::Index;
+    type IntoItem = Option<P::Index>;
+    type Iter<'i> = iter::Cloned<slice::Iter<'i, Option<P::Index>>>
+    where Self: 'i, Self::Vertex: 'i, 'p: 'i;
+
+    fn vertex_count(&self) -> usize {
+        self.vertex_count
+    }
+
+    fn iter_indices<'i>(&'i self) -> Self::Iter<'i>
+    where Self: 'i, Self::Vertex: 'i, 'p: 'i {
+        self.elements.iter().cloned()
+    }
+
+    fn get_vertex<'a>(&'a self, index: Self::Index) -> &'a Self::Vertex
+    where 'p: 'a {
+        self.polygons.get_vertex(index)
+    }
+}
+
+/// A [PolygonList] of the contours bounding one connected region of another, built by
+/// [Trapezoidation::extract_region](crate::Trapezoidation::extract_region).
+///
+/// The contours are walked with the region on their left, so outer boundaries are counter-clockwise and holes are
+/// clockwise, and index the vertices of the underlying [PolygonList], so triangles of the region can be matched to
+/// those of the whole.
+#[derive(Debug, Clone)]
+pub struct Region<'p, P: PolygonList<'p> + ?Sized> {
+    polygons: &'p P,
+    elements: Vec<Option<P::Index>>,
+    vertex_count: usize,
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized> Region<'p, P> {
+    pub(crate) fn new(polygons: &'p P, contours: Vec<Vec<P::Index>>) -> Self {
+        let vertex_count = contours.iter().map(Vec::len).sum();
+        let elements = contours.into_iter().flat_map(|contour| contour.into_iter().map(Some).chain(iter::once(None))).collect();
+        Self { polygons, elements, vertex_count }
+    }
+
+    /// The underlying [PolygonList]
+    pub fn polygons(&self) -> &'p P {
+        self.polygons
+    }
+
+    /// Whether the region is empty, as when the point it was extracted around is outside the polygons
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized> PolygonList<'p> for Region<'p, P> {
+    type Vertex = P::Vertex;
+    type Index = P::Index;
+    type IntoItem = Option<P::Index>;
+    type Iter<'i> = iter::Cloned<slice::Iter<'i, Option<P::Index>>>
+    where Self: 'i, Self::Vertex: 'i, 'p: 'i;
+
+    fn vertex_count(&self) -> usize {
+        self.vertex_count
+    }
+
+    fn iter_indices<'i>(&'i self) -> Self::Iter<'i>
+    where Self: 'i, Self::Vertex: 'i, 'p: 'i {
+        self.elements.iter().cloned()
+    }
+
+    fn get_vertex<'a>(&'a self, index: Self::Index) -> &'a Self::Vertex
+    where 'p: 'a {
+        self.polygons.get_vertex(index)
+    }
+}
@@ -1,8 +1,11 @@
-use std::{convert::TryInto, marker::PhantomData, ops};
+use core::{convert::TryInto, marker::PhantomData, ops};
+use alloc::vec::Vec;
 
-use crate::{FanFormat, TriangulationError, VertexExt, VertexIndex, trapezoidation::{Trapezoidation, TrapezoidationState}, errors::TrapezoidationError, FanBuilder};
+use crate::{FanFormat, FanResult, TriangulationError, VertexExt, VertexIndex, trapezoidation::{Trapezoidation, TrapezoidationState}, errors::TrapezoidationError, FanBuilder, GeometryKernel, MonotoneFormat, MonotoneResult, TriangulationOptions, options, validity::{self, SegmentIntersection}};
 
-use super::vertex::Vertex;
+use num_traits::real::Real;
+
+use super::{vertex::Vertex, Chain, InteriorPoints, Sanitized, Subset, WeaklySimple};
 
 /// Used to destinguish multiple polygons while iterating with 
 /// [PolygonList::iter_indices].
@@ -53,6 +56,14 @@ impl<'p, P: Polygon<'p>> PolygonList<'p> for SinglePolygon<'p, P> {
         self.0.vertex_count()
     }
 
+    fn contour_count_hint(&self) -> Option<usize> {
+        Some(1)
+    }
+
+    fn contour_len_hint(&self, contour: usize) -> Option<usize> {
+        (contour == 0).then(|| self.0.vertex_count())
+    }
+
     fn iter_indices<'i>(&'i self) -> Self::Iter<'i>
     where Self: 'i, Self::Vertex: 'i, 'p: 'i {
         self.0.iter_indices()
@@ -121,6 +132,28 @@ pub trait Polygon<'p>: 'p + Sized {
     fn triangulate<FB: FanFormat<'p, SinglePolygon<'p, Self>>>(&'p self, format: FB) -> Result<<FB::Builder as FanBuilder<'p, SinglePolygon<'p, Self>>>::Output, TriangulationError<<FB::Builder as FanBuilder<'p, SinglePolygon<'p, Self>>>::Error>> {
         self.as_polygon_list().triangulate(format)
     }
+
+    /// Triangulate the polygon into the layout specified by `format`, with the algorithm chosen in `options`
+    fn triangulate_with_options<FB: FanFormat<'p, SinglePolygon<'p, Self>>>(&'p self, format: FB, options: &TriangulationOptions) -> FanResult<'p, SinglePolygon<'p, Self>, FB> {
+        self.as_polygon_list().triangulate_with_options(format, options)
+    }
+
+    /// Split the polygon into monotone polygons in the layout specified by `format`
+    /// 
+    /// See [PolygonList::monotones].
+    fn monotones<MF: MonotoneFormat<'p, SinglePolygon<'p, Self>>>(&'p self, format: MF) -> MonotoneResult<'p, SinglePolygon<'p, Self>, MF> {
+        self.as_polygon_list().monotones(format)
+    }
+
+    /// Generate a [Trapezoidation] using the [GeometryKernel] `K` for all geometric predicates.
+    fn trapezoidize_with_kernel<K: GeometryKernel<<Self::Vertex as Vertex>::Coordinate>>(&'p self) -> Result<Trapezoidation<'p, SinglePolygon<'p, Self>, K>, TrapezoidationError> {
+        self.as_polygon_list().trapezoidize_with_kernel()
+    }
+
+    /// Triangulate the polygon into the layout specified by `format`, using the [GeometryKernel] `K` for all geometric predicates.
+    fn triangulate_with_kernel<K: GeometryKernel<<Self::Vertex as Vertex>::Coordinate>, FB: FanFormat<'p, SinglePolygon<'p, Self>>>(&'p self, format: FB) -> FanResult<'p, SinglePolygon<'p, Self>, FB> {
+        self.as_polygon_list().triangulate_with_kernel::<K, FB>(format)
+    }
 }
 
 /// An indexable list of polygons and their vertices
@@ -138,6 +171,23 @@ pub trait PolygonList<'p>: 'p {
     /// Provides the total number of vertices among all polygons.
     fn vertex_count(&self) -> usize;
 
+    /// The number of polygons, if it is known without iterating, used to pre-allocate memory.
+    ///
+    /// Polygons are counted in the order of [PolygonList::iter_indices], including empty ones. An incorrect count
+    /// only affects performance. The default is `None`.
+    fn contour_count_hint(&self) -> Option<usize> {
+        None
+    }
+
+    /// The number of vertices of the polygon at position `contour` in the order of [PolygonList::iter_indices], if it
+    /// is known without iterating, used to pre-allocate memory.
+    ///
+    /// An incorrect length only affects performance. The default is `None`.
+    fn contour_len_hint(&self, contour: usize) -> Option<usize> {
+        let _ = contour;
+        None
+    }
+
     /// Iterate through all `Index`es of all polygons.
     /// Indices must be returned in either clockwise or counter-clockwise order, 
     /// without repeating the initial index. Between each polygon, implementers
@@ -166,6 +216,61 @@ pub trait PolygonList<'p>: 'p {
         IndexWith::new(self)
     }
     
+    /// Add isolated points inside the polygons which must appear as vertices of the triangulation 
+    /// (e.g. sensor locations or label anchors).
+    ///
+    /// See [InteriorPoints] for the requirements on the points.
+    fn with_interior_points(&'p self, points: &'p [Self::Vertex]) -> InteriorPoints<'p, Self> {
+        InteriorPoints::new(self, points)
+    }
+
+    /// Follow the polygons of this list with those of `other`, without copying either.
+    ///
+    /// See [Chain] for the details.
+    fn chain<B: PolygonList<'p, Vertex=Self::Vertex> + ?Sized>(&'p self, other: &'p B) -> Chain<'p, Self, B> {
+        Chain::new(self, other)
+    }
+
+    /// Select the polygons at the positions `contours` in the order of [PolygonList::iter_indices], such as a range.
+    ///
+    /// Returns [None] if a position is out of bounds. See [Subset] for the details.
+    fn subset(&'p self, contours: impl IntoIterator<Item=usize>) -> Option<Subset<'p, Self>> {
+        Subset::new(self, contours)
+    }
+
+    /// Clean up the polygons before triangulation: weld vertices closer than `epsilon`, remove repeated and collinear
+    /// vertices, and drop polygons left with fewer than 3 vertices.
+    ///
+    /// See [Sanitized] for the details.
+    fn sanitized(&'p self, epsilon: <Self::Vertex as Vertex>::Coordinate) -> Sanitized<'p, Self>
+    where <Self::Vertex as Vertex>::Coordinate: Real {
+        Sanitized::new(self, epsilon)
+    }
+
+    /// Allow the polygons to touch at vertices: holes touching their outer boundary, polygons sharing a vertex, and
+    /// polygons touching themselves, as produced by clipping.
+    ///
+    /// See [WeaklySimple] for the details.
+    fn weakly_simple(&'p self) -> WeaklySimple<'p, Self>
+    where <Self::Vertex as Vertex>::Coordinate: Real {
+        WeaklySimple::new(self)
+    }
+
+    /// Check that no two edges of the polygons cross, touch or overlap, other than consecutive edges at their shared
+    /// vertex, which is required for triangulation.
+    ///
+    /// On failure, every offending pair of edges is listed; see [validity::segment_intersections]. The
+    /// [validity] module also has PostGIS-style checks, which allow touching polygons.
+    fn validate(&'p self) -> Result<(), Vec<SegmentIntersection<<Self::Vertex as Vertex>::Coordinate>>>
+    where <Self::Vertex as Vertex>::Coordinate: Real + core::fmt::Debug {
+        let intersections = validity::segment_intersections(self);
+        if intersections.is_empty() {
+            Ok(())
+        } else {
+            Err(intersections)
+        }
+    }
+
     /// Generate a [Trapezoidation], which can later be triangulated. 
     /// 
     /// Unless the [Trapezoidation] is needed for other reasons, this can be done in a single step with [PolygonList::triangulate].
@@ -177,6 +282,34 @@ pub trait PolygonList<'p>: 'p {
     fn triangulate<FB: FanFormat<'p, Self>>(&'p self, format: FB) -> Result<<FB::Builder as FanBuilder<'p, Self>>::Output, TriangulationError<<FB::Builder as FanBuilder<'p, Self>>::Error>> {
         self.trapezoidize().map_err(TriangulationError::TrapezoidationError)?.triangulate(format)
     }
+
+    /// Triangulate the polygons into the layout specified by `format`, with the algorithm chosen in `options`.
+    /// 
+    /// [PolygonList::triangulate] uses [TriangulationMethod::Seidel](crate::TriangulationMethod::Seidel).
+    fn triangulate_with_options<FB: FanFormat<'p, Self>>(&'p self, format: FB, options: &TriangulationOptions) -> FanResult<'p, Self, FB> {
+        options::triangulate(self, format, options)
+    }
+
+    /// Split the polygons into monotone polygons, the step of triangulation before each is split into triangles, in
+    /// the layout specified by `format`.
+    /// 
+    /// A polygon is monotone if every horizontal line crosses it at most once; the polygons produced by triangulation
+    /// also have one side which is a single edge, and are often called monotone mountains.
+    fn monotones<MF: MonotoneFormat<'p, Self>>(&'p self, format: MF) -> MonotoneResult<'p, Self, MF> {
+        self.trapezoidize().map_err(TriangulationError::TrapezoidationError)?.monotones(format)
+    }
+
+    /// Generate a [Trapezoidation] using the [GeometryKernel] `K` for all geometric predicates.
+    /// 
+    /// [PolygonList::trapezoidize] uses [DefaultKernel](crate::DefaultKernel).
+    fn trapezoidize_with_kernel<K: GeometryKernel<<Self::Vertex as Vertex>::Coordinate>>(&'p self) -> Result<Trapezoidation<'p, Self, K>, TrapezoidationError> {
+        TrapezoidationState::<Self, K>::new(self).build()
+    }
+
+    /// Triangulate the polygons into the layout specified by `format`, using the [GeometryKernel] `K` for all geometric predicates.
+    fn triangulate_with_kernel<K: GeometryKernel<<Self::Vertex as Vertex>::Coordinate>, FB: FanFormat<'p, Self>>(&'p self, format: FB) -> FanResult<'p, Self, FB> {
+        self.trapezoidize_with_kernel::<K>().map_err(TriangulationError::TrapezoidationError)?.triangulate(format)
+    }
 }
 
 // Allows indexing to directly return `VertexExt`s internally for convenience to add display and math functionality 
@@ -204,9 +337,42 @@ impl<'p, P: PolygonList<'p> + ?Sized> PolygonListExt<'p, P> {
         self.0.vertex_count()
     }
 
+    /// The number of vertices in all polygons, from the contour hints if they are known, which excludes vertices
+    /// that no polygon uses. Falls back to [PolygonList::vertex_count].
+    pub fn contour_vertex_count(&self) -> usize {
+        self.0.contour_count_hint()
+            .and_then(|count| (0..count).map(|contour| self.0.contour_len_hint(contour)).sum())
+            .unwrap_or_else(|| self.vertex_count())
+    }
+
     pub fn polygon_list(&self) -> &'p P {
         self.0
     }
+
+    /// Collect the coordinates of each polygon, skipping empty polygons
+    pub fn contours(&self) -> Vec<Vec<[<P::Vertex as Vertex>::Coordinate; 2]>> {
+        let mut contours = Vec::with_capacity(self.0.contour_count_hint().unwrap_or(0));
+        let mut polygon = 0;
+        let mut current = Vec::with_capacity(self.0.contour_len_hint(polygon).unwrap_or(0));
+        for element in self.0.iter_indices().map(Into::into).chain(core::iter::once(PolygonElement::NewPolygon)) {
+            match element {
+                PolygonElement::ContinuePolygon(index) => {
+                    let v = &self[index];
+                    current.push([v.x(), v.y()]);
+                }
+                PolygonElement::NewPolygon => {
+                    polygon += 1;
+                    let len = self.0.contour_len_hint(polygon).unwrap_or(0);
+                    if current.is_empty() {
+                        current.reserve(len);
+                    } else {
+                        contours.push(core::mem::replace(&mut current, Vec::with_capacity(len)));
+                    }
+                }
+            }
+        }
+        contours
+    }
 }
 
 impl<'p, P: PolygonList<'p> + ?Sized> ops::Index<P::Index> for PolygonListExt<'p, P> {
@@ -287,6 +453,14 @@ impl<'p, V: Vertex + 'p, P: 'p + Polygon<'p, Vertex=V, Index=usize>, D: 'p + ops
         (*self).iter().map(|p| (*p).vertex_count()).sum()
     }
 
+    fn contour_count_hint(&self) -> Option<usize> {
+        Some((*self).len())
+    }
+
+    fn contour_len_hint(&self, contour: usize) -> Option<usize> {
+        (*self).get(contour).map(|p| p.vertex_count())
+    }
+
     fn iter_indices<'i>(&'i self) -> Self::Iter<'i>
     where Self: 'i, Self::Vertex: 'i, 'p: 'i {
         VecVecIter::new(self)
@@ -303,38 +477,90 @@ fn conversion_panic<T, U>(_: T) -> U {
     panic!("Conversion of index failed")
 }
 
-/// [Iterator] for the [IndexWith] wrapper
-pub struct IndexWithIter<'i, Iter: Iterator + 'i, OldIndex: VertexIndex + crate::Mappable<Old>, New: TryInto<Old>, Old: TryInto<New>> 
-where Iter::Item: Into<PolygonElement<OldIndex>>,
-      OldIndex::Output<New>: VertexIndex + crate::Mappable<New, Output<Old> = OldIndex> {
+fn convert_element<Item: Into<PolygonElement<OldIndex>>, OldIndex: VertexIndex + crate::Mappable<Old>, New: TryInto<Old>, Old: TryInto<New>>(item: Item) -> PolygonElement<OldIndex::Output<New>>
+where OldIndex::Output<New>: VertexIndex + crate::Mappable<New, Output<Old> = OldIndex> {
+    match item.into() {
+        PolygonElement::ContinuePolygon(index) => PolygonElement::ContinuePolygon(index.map(|i| i.try_into().unwrap_or_else(conversion_panic))),
+        PolygonElement::NewPolygon => PolygonElement::NewPolygon,
+    }
+}
+
+/// [Iterator] for the [IndexWith] wrapper.
+///
+/// It is an [ExactSizeIterator] and [DoubleEndedIterator] if the wrapped iterator is, and with the `rayon` feature, a
+/// [ParallelIterator](rayon::iter::ParallelIterator) if it wraps one, such as from [IndexWith::par_iter_indices].
+pub struct IndexWithIter<'i, Iter: 'i, OldIndex: VertexIndex + crate::Mappable<Old>, New: TryInto<Old>, Old: TryInto<New>> 
+where OldIndex::Output<New>: VertexIndex + crate::Mappable<New, Output<Old> = OldIndex> {
     iter: Iter,
     _phantom: PhantomData<&'i (OldIndex, New, Old)>,
 }
 
-impl<'i, Iter: Iterator + 'i, OldIndex: VertexIndex + crate::Mappable<Old>, New: TryInto<Old>, Old: TryInto<New>> IndexWithIter<'i, Iter, OldIndex, New, Old>
-where Iter::Item: Into<PolygonElement<OldIndex>>,
-      OldIndex::Output<New>: VertexIndex + crate::Mappable<New, Output<Old> = OldIndex> {
+impl<'i, Iter: 'i, OldIndex: VertexIndex + crate::Mappable<Old>, New: TryInto<Old>, Old: TryInto<New>> IndexWithIter<'i, Iter, OldIndex, New, Old>
+where OldIndex::Output<New>: VertexIndex + crate::Mappable<New, Output<Old> = OldIndex> {
     pub(crate) fn new(iter: Iter) -> Self {
         Self { iter, _phantom: PhantomData }
     }
 }
 
-impl<'i, Iter: Iterator + 'i, OldIndex: VertexIndex + crate::Mappable<New>, In: TryInto<New>, New: TryInto<In>> Iterator for IndexWithIter<'i, Iter, OldIndex, In, New> 
+impl<'i, Iter: Iterator + 'i, OldIndex: VertexIndex + crate::Mappable<Old>, New: TryInto<Old>, Old: TryInto<New>> Iterator for IndexWithIter<'i, Iter, OldIndex, New, Old> 
 where Iter::Item: Into<PolygonElement<OldIndex>>,
-      OldIndex::Output<In>: VertexIndex + crate::Mappable<In, Output<New> = OldIndex> {
-    type Item = PolygonElement<OldIndex::Output<In>>;
+      OldIndex::Output<New>: VertexIndex + crate::Mappable<New, Output<Old> = OldIndex> {
+    type Item = PolygonElement<OldIndex::Output<New>>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.iter.next() {
-            Some(into_polygon_vertex) => {
-                let polygon_vertex: PolygonElement<OldIndex> = into_polygon_vertex.into();
-                Some(match polygon_vertex {
-                    PolygonElement::ContinuePolygon(index) => PolygonElement::ContinuePolygon(index.map(|i| i.try_into().unwrap_or_else(conversion_panic))),
-                    PolygonElement::NewPolygon => PolygonElement::NewPolygon,
-                })
-            }
-            None => None,
-        }
+        self.iter.next().map(convert_element)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'i, Iter: ExactSizeIterator + 'i, OldIndex: VertexIndex + crate::Mappable<Old>, New: TryInto<Old>, Old: TryInto<New>> ExactSizeIterator for IndexWithIter<'i, Iter, OldIndex, New, Old> 
+where Iter::Item: Into<PolygonElement<OldIndex>>,
+      OldIndex::Output<New>: VertexIndex + crate::Mappable<New, Output<Old> = OldIndex> {
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+impl<'i, Iter: DoubleEndedIterator + 'i, OldIndex: VertexIndex + crate::Mappable<Old>, New: TryInto<Old>, Old: TryInto<New>> DoubleEndedIterator for IndexWithIter<'i, Iter, OldIndex, New, Old> 
+where Iter::Item: Into<PolygonElement<OldIndex>>,
+      OldIndex::Output<New>: VertexIndex + crate::Mappable<New, Output<Old> = OldIndex> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(convert_element)
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<'i, Iter: rayon::iter::ParallelIterator + 'i, OldIndex: VertexIndex + crate::Mappable<Old> + Sync, New: TryInto<Old> + Sync, Old: TryInto<New> + Sync> rayon::iter::ParallelIterator for IndexWithIter<'i, Iter, OldIndex, New, Old> 
+where Iter::Item: Into<PolygonElement<OldIndex>>,
+      OldIndex::Output<New>: VertexIndex + crate::Mappable<New, Output<Old> = OldIndex> + Send {
+    type Item = PolygonElement<OldIndex::Output<New>>;
+
+    fn drive_unindexed<C: rayon::iter::plumbing::UnindexedConsumer<Self::Item>>(self, consumer: C) -> C::Result {
+        self.iter.map(convert_element).drive_unindexed(consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        self.iter.opt_len()
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<'i, Iter: rayon::iter::IndexedParallelIterator + 'i, OldIndex: VertexIndex + crate::Mappable<Old> + Sync, New: TryInto<Old> + Sync, Old: TryInto<New> + Sync> rayon::iter::IndexedParallelIterator for IndexWithIter<'i, Iter, OldIndex, New, Old> 
+where Iter::Item: Into<PolygonElement<OldIndex>>,
+      OldIndex::Output<New>: VertexIndex + crate::Mappable<New, Output<Old> = OldIndex> + Send {
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+
+    fn drive<C: rayon::iter::plumbing::Consumer<Self::Item>>(self, consumer: C) -> C::Result {
+        self.iter.map(convert_element).drive(consumer)
+    }
+
+    fn with_producer<CB: rayon::iter::plumbing::ProducerCallback<Self::Item>>(self, callback: CB) -> CB::Output {
+        self.iter.map(convert_element).with_producer(callback)
     }
 }
 
@@ -348,6 +574,31 @@ where OldIndex::Output<New>: VertexIndex + crate::Mappable<New, Output<Old> = Ol
     fn new(polygon_list: P) -> Self {
         Self(polygon_list, PhantomData)
     }
+
+    /// [PolygonList::iter_indices] as a [ParallelIterator](rayon::iter::ParallelIterator), which converts the indices
+    /// on all threads of the [rayon](https://crates.io/crates/rayon) thread pool and keeps their order when
+    /// collected. Requires the `rayon` feature.
+    ///
+    /// The indices of the wrapped list are gathered first, since [PolygonList::iter_indices] is sequential.
+    /// ```
+    /// # use triangulate::{PolygonElement, PolygonList};
+    /// use rayon::prelude::*;
+    ///
+    /// let polygons = vec![vec![[0f32, 0.], [1., 0.], [1., 1.]], vec![[2., 0.], [3., 0.], [3., 1.]]];
+    /// let polygons = polygons.index_with::<usize, u16>();
+    /// let indices: Vec<_> = polygons.par_iter_indices().filter_map(|element| match element {
+    ///     PolygonElement::ContinuePolygon([i0, i1]) => Some([i0, i1]),
+    ///     PolygonElement::NewPolygon => None,
+    /// }).collect();
+    /// assert_eq!(indices, [[0u16, 0], [0, 1], [0, 2], [1, 0], [1, 1], [1, 2]]);
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn par_iter_indices(&self) -> IndexWithIter<'_, rayon::vec::IntoIter<P::IntoItem>, OldIndex, New, Old>
+    where P::IntoItem: Send {
+        use rayon::prelude::*;
+
+        IndexWithIter::new(self.0.iter_indices().collect::<Vec<_>>().into_par_iter())
+    }
 }
 
 impl<'p, P: PolygonList<'p, Index=OldIndex>, OldIndex: VertexIndex + crate::Mappable<Old>, New: TryInto<Old>, Old: TryInto<New>> PolygonList<'p> for IndexWith<'p, P, OldIndex, Old, New> 
@@ -362,6 +613,14 @@ where OldIndex::Output<New>: VertexIndex + crate::Mappable<New, Output<Old> = Ol
         self.0.vertex_count()
     }
 
+    fn contour_count_hint(&self) -> Option<usize> {
+        self.0.contour_count_hint()
+    }
+
+    fn contour_len_hint(&self, contour: usize) -> Option<usize> {
+        self.0.contour_len_hint(contour)
+    }
+
     fn iter_indices<'i>(&'i self) -> Self::Iter<'i>
     where Self: 'i, Self::Vertex: 'i, 'p: 'i {
         IndexWithIter::new(self.0.iter_indices())
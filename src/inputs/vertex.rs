@@ -1,61 +1,118 @@
 use core::fmt;
-use std::{fmt::Debug, cmp};
+use core::{fmt::Debug, cmp};
 
-use num_traits::real::Real;
+use num_traits::{Num, ToPrimitive};
 
-use crate::idx::IdxDisplay;
+use crate::{float_format::{Float, FloatFormat}, idx::IdxDisplay};
+
+/// The coordinate type of a [Vertex]: any number which can be compared and converted, including the integers,
+/// fixed-point types, arbitrary-precision types and all [Real](num_traits::real::Real) types.
+///
+/// Triangulation only compares coordinates and multiplies their differences, so integer coordinates are triangulated
+/// exactly, as long as products of differences fit the type (so coordinates up to 2^15 in magnitude for `i32`, or up
+/// to 2^31 for `i64`). Coordinates are not required to be [Copy], and are cloned only where they are stored, so
+/// arbitrary-precision rationals (such as `BigRational`, reexported with the `num-rational` feature) triangulate
+/// exactly at any magnitude. Most other modules of this crate need [Real](num_traits::real::Real) coordinates.
+pub trait Scalar: Num + Clone + PartialOrd + ToPrimitive { }
+
+impl<T: Num + Clone + PartialOrd + ToPrimitive> Scalar for T { }
 
 /// A two-dimensional point. 
 /// 
-/// The coordinate type must implement [num_traits::real::Real], reexported as [crate::Real].
+/// The coordinate type must implement [Scalar]. Most modules other than triangulation need [num_traits::real::Real], reexported as [crate::Real].
+///
+/// A vertex may carry a [Vertex::Payload] of other data, such as a color or an id, which triangulation ignores and
+/// output vertex buffers (such as [IndexBuffers](crate::buffers::IndexBuffers)) copy alongside the position.
+/// ```
+/// # use triangulate::{buffers::IndexBuffers, mesh::Mesh, Vertex};
+/// #[derive(Debug, Clone)]
+/// struct ColoredVertex {
+///     position: [f32; 2],
+///     color: [u8; 4],
+/// }
+///
+/// impl Vertex for ColoredVertex {
+///     type Coordinate = f32;
+///     type Payload = [u8; 4];
+///
+///     fn x(&self) -> f32 { self.position[0] }
+///     fn y(&self) -> f32 { self.position[1] }
+///     fn payload(&self) -> &[u8; 4] { &self.color }
+/// }
+///
+/// let red = [255, 0, 0, 255];
+/// let polygons = vec![vec![
+///     ColoredVertex { position: [0., 0.], color: red },
+///     ColoredVertex { position: [1., 0.], color: red },
+///     ColoredVertex { position: [0., 1.], color: [0, 0, 255, 255] },
+/// ]];
+/// let buffers = IndexBuffers::from_mesh(&Mesh::from_polygons(&polygons).expect("Triangulation failed")).expect("Too many vertices");
+/// assert_eq!(buffers.payloads, vec![red, red, [0, 0, 255, 255]]);
+/// ```
 pub trait Vertex {
     /// The type of the individual `x` and `y` coordinates
-    type Coordinate: Real;
+    type Coordinate: Scalar;
+    /// Data carried by the vertex which is not part of its position, or `()` for none
+    type Payload;
 
     /// The x [Vertex::Coordinate] value
     fn x(&self) -> Self::Coordinate;
     /// The y [Vertex::Coordinate] value
     fn y(&self) -> Self::Coordinate;
+    /// The [Vertex::Payload] of the vertex
+    fn payload(&self) -> &Self::Payload;
 }
 
 #[derive(Clone, Copy, PartialEq)]
-pub(crate) struct Coords<C: Real>([C; 2]);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct Coords<C: Scalar>([C; 2]);
+
+impl<C: Scalar> Coords<C> {
+    pub fn new(c: [C; 2]) -> Self { Self(c) }
+
+    pub fn x(&self) -> &C { &self.0[0] }
+    pub fn y(&self) -> &C { &self.0[1] }
 
-impl<C: Real> Coords<C> {
-    pub fn x(&self) -> C { self.0[0] }
-    pub fn y(&self) -> C { self.0[1] }
+    pub fn array(&self) -> [C; 2] { self.0.clone() }
+    pub fn as_array(&self) -> &[C; 2] { &self.0 }
 
     pub fn zero() -> Self { Self([C::zero(), C::zero()]) }
 }
 
-impl<C: Real> fmt::Debug for Coords<C> {
+impl<C: Scalar> fmt::Debug for Coords<C> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let format = FloatFormat::for_formatter(f);
         let mut tuple = f.debug_tuple("Coords");
         if let Some(x) = self.x().to_f64() {
-            tuple.field(&x);
+            tuple.field(&Float(x, format));
         }
         if let Some(y) = self.y().to_f64() {
-            tuple.field(&y);
+            tuple.field(&Float(y, format));
         }
         tuple.finish()
     }
 }
 
-impl<C: Real> fmt::Display for Coords<C> {
+impl<C: Scalar> fmt::Display for Coords<C> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if let (Some(x), Some(y)) = (self.x().to_f64(), self.y().to_f64()) {
-            write!(f, "({}, {})", x, y)
+            let format = FloatFormat::for_formatter(f);
+            write!(f, "(")?;
+            format.write(f, x)?;
+            write!(f, ", ")?;
+            format.write(f, y)?;
+            write!(f, ")")
         } else {
-            write!(f, "Coords<{}>", std::any::type_name::<C>())
+            write!(f, "Coords<{}>", core::any::type_name::<C>())
         }
     }
 }
 
-impl<C: Real> PartialOrd for Coords<C> {
+impl<C: Scalar> PartialOrd for Coords<C> {
     fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
-        self.y().partial_cmp(&other.y()).and_then(|y_ord| 
+        self.y().partial_cmp(other.y()).and_then(|y_ord| 
             if y_ord == cmp::Ordering::Equal {
-                self.x().partial_cmp(&other.x())
+                self.x().partial_cmp(other.x())
             } else {
                 Some(y_ord)
             }
@@ -79,15 +136,14 @@ impl<V: Vertex> VertexExt<V> {
     }
 }
 
-impl<V: Vertex> std::fmt::Display for VertexExt<V>
-where V::Coordinate: std::fmt::Display {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "({}, {})", self.x(), self.y())
+impl<V: Vertex> core::fmt::Display for VertexExt<V> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt::Display::fmt(&self.coords(), f)
     }
 }
 
 impl<V: Vertex> IdxDisplay for VertexExt<V> {
-    fn fmt(f: &mut std::fmt::Formatter<'_>, idx: usize) -> std::fmt::Result {
+    fn fmt(f: &mut core::fmt::Formatter<'_>, idx: usize) -> core::fmt::Result {
         write!(f, "v{}", idx)
     }
 }
@@ -112,7 +168,7 @@ impl<V: Vertex> From<V> for VertexExt<V> {
 }
 
 impl<V: Vertex> PartialOrd for VertexExt<V> {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         self.y().partial_cmp(&other.y()).and_then(|y_ord| 
             if y_ord == cmp::Ordering::Equal {
                 self.x().partial_cmp(&other.x())
@@ -129,30 +185,64 @@ impl<V: Vertex> PartialEq for VertexExt<V> {
     }
 }
 
-impl<C: Debug + Real> Vertex for [C; 2] {
+impl<C: Debug + Scalar> Vertex for [C; 2] {
+    type Coordinate = C;
+    type Payload = ();
+
+    #[inline(always)]
+    fn x(&self) -> Self::Coordinate {
+        self[0].clone()
+    }
+
+    #[inline(always)]
+    fn y(&self) -> Self::Coordinate {
+        self[1].clone()
+    }
+
+    #[inline(always)]
+    fn payload(&self) -> &() {
+        &()
+    }
+}
+
+impl<C: Debug + Scalar> Vertex for (C, C) {
     type Coordinate = C;
+    type Payload = ();
 
     #[inline(always)]
     fn x(&self) -> Self::Coordinate {
-        self[0]
+        self.0.clone()
     }
 
     #[inline(always)]
     fn y(&self) -> Self::Coordinate {
-        self[1]
+        self.1.clone()
+    }
+
+    #[inline(always)]
+    fn payload(&self) -> &() {
+        &()
     }
 }
 
-impl<C: Debug + Real> Vertex for (C, C) {
+/// A point with an elevation, such as for terraced terrain or stacked layers. Only `x` and `y` are used for
+/// triangulation, and the `z` coordinate is carried through to the output, see [crate::mesh::Mesh::elevate].
+impl<C: Debug + Scalar> Vertex for [C; 3] {
     type Coordinate = C;
+    type Payload = ();
 
     #[inline(always)]
     fn x(&self) -> Self::Coordinate {
-        self.0
+        self[0].clone()
     }
 
     #[inline(always)]
     fn y(&self) -> Self::Coordinate {
-        self.1
+        self[1].clone()
+    }
+
+    #[inline(always)]
+    fn payload(&self) -> &() {
+        &()
     }
 }
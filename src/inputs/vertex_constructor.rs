@@ -0,0 +1,38 @@
+use core::fmt::Debug;
+
+use num_traits::real::Real;
+
+use super::{from_coords::FromCoords, vertex::Vertex};
+
+/// Creates the new vertices that post-processing steps (such as refinement) add to a triangulation.
+///
+/// Implemented for closures with the signature of [VertexConstructor::interpolate], so any additional vertex attributes
+/// (colors, texture coordinates, ...) can be interpolated alongside the position.
+pub trait VertexConstructor<V: Vertex> {
+    /// Construct the vertex at `t` along the edge from `a` to `b`, where `0` is `a` and `1` is `b`
+    fn interpolate(&mut self, a: &V, b: &V, t: V::Coordinate) -> V;
+}
+
+impl<V: Vertex, F: FnMut(&V, &V, V::Coordinate) -> V> VertexConstructor<V> for F {
+    fn interpolate(&mut self, a: &V, b: &V, t: V::Coordinate) -> V {
+        self(a, b, t)
+    }
+}
+
+/// A [VertexConstructor] which linearly interpolates the position of any [FromCoords] vertex, and the elevation of
+/// `[x, y, z]` vertices
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LinearConstructor;
+
+impl<V: FromCoords> VertexConstructor<V> for LinearConstructor
+where V::Coordinate: Real {
+    fn interpolate(&mut self, a: &V, b: &V, t: V::Coordinate) -> V {
+        V::from_coords([a.x() + (b.x() - a.x()) * t, a.y() + (b.y() - a.y()) * t])
+    }
+}
+
+impl<C: Debug + Real> VertexConstructor<[C; 3]> for LinearConstructor {
+    fn interpolate(&mut self, a: &[C; 3], b: &[C; 3], t: C) -> [C; 3] {
+        [a[0] + (b[0] - a[0]) * t, a[1] + (b[1] - a[1]) * t, a[2] + (b[2] - a[2]) * t]
+    }
+}
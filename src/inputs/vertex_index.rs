@@ -3,7 +3,7 @@
 #[cfg(not(feature = "_debugging"))]
 pub trait VertexIndex: Eq + Clone { }
 #[cfg(feature = "_debugging")]
-pub trait VertexIndex: Eq + Clone + std::fmt::Debug { }
+pub trait VertexIndex: Eq + Clone + core::fmt::Debug { }
 
 #[cfg(not(feature = "_debugging"))]
 impl<T> VertexIndex for T 
@@ -12,5 +12,5 @@ where T: Eq + Clone
 
 #[cfg(feature = "_debugging")]
 impl<T> VertexIndex for T 
-where T: Eq + Clone + std::fmt::Debug
+where T: Eq + Clone + core::fmt::Debug
 { }
\ No newline at end of file
@@ -0,0 +1,289 @@
+use core::{f64::consts::TAU, fmt, iter};
+use alloc::{vec, vec::Vec};
+
+use num_traits::{One, Zero, real::Real};
+
+use crate::{cmp_vertices, formats, math, sdf::segment_distance, DefaultKernel, FanBuilder, FanBuilderState, FanFormat, GeometryKernel, ListFormat, Orientation, PolygonList, PolygonElement, TriangleWinding, TriangulationError, Vertex};
+
+// The position of a vertex of `P`
+type Position<'p, P> = [<<P as PolygonList<'p>>::Vertex as Vertex>::Coordinate; 2];
+// The positions of the vertices of each polygon, and their indices
+type Rings<'p, P> = (Vec<Vec<Position<'p, P>>>, Vec<Vec<<P as PolygonList<'p>>::Index>>);
+
+/// A [PolygonList] whose polygons may be weakly simple: a hole may touch its outer boundary at a vertex, two polygons
+/// may share a vertex, and a polygon may touch itself at a vertex, as is common in the output of clipping. Created by
+/// [PolygonList::weakly_simple].
+///
+/// Triangulation requires every vertex to be at a distinct position, so each group of vertices at the same position
+/// is first separated: the area around the position is split into the filled wedges between its edges, and each
+/// wedge gets its own copy of the vertex, moved into the wedge by a fraction of the distance to the nearest other
+/// feature. The triangles are built on the separated polygons and returned with the indices of the original vertices,
+/// so they fit together at the shared positions. Repeated consecutive vertices, and vertices touching the inside of an
+/// edge, are not supported; [PolygonList::sanitized] removes the former.
+///
+/// Weakly simple input is only supported through this adapter. The trapezoidation itself still requires distinct
+/// vertex positions, so [PolygonList::trapezoidize] and [PolygonList::triangulate] on the polygons themselves fail on
+/// vertices which share a position, and a [Trapezoidation](crate::Trapezoidation) of this list contains the polygons as
+/// they are. Only [PolygonList::triangulate] and [PolygonList::triangulate_with_kernel] of this list separate the
+/// vertices.
+///
+/// ```
+/// # use triangulate::{formats, ListFormat, PolygonList};
+/// // A triangular hole touching the corner of its outer square
+/// let polygons = vec![
+///     vec![[0f64, 0.], [4., 0.], [4., 4.], [0., 4.]],
+///     vec![[0., 0.], [1., 2.], [2., 1.]],
+/// ];
+/// let mut triangles = Vec::<[usize; 2]>::new();
+/// polygons.weakly_simple().triangulate(formats::IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+/// assert_eq!(triangles.len(), 5 * 3);
+/// ```
+#[derive(Debug)]
+pub struct WeaklySimple<'p, P: PolygonList<'p> + ?Sized> {
+    polygons: &'p P,
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized> Clone for WeaklySimple<'p, P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized> Copy for WeaklySimple<'p, P> { }
+
+impl<'p, P: PolygonList<'p> + ?Sized> WeaklySimple<'p, P> {
+    pub(crate) fn new(polygons: &'p P) -> Self {
+        Self { polygons }
+    }
+
+    /// The underlying [PolygonList]
+    pub fn polygons(&self) -> &'p P {
+        self.polygons
+    }
+}
+
+// The end of an edge at a shared position
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum End {
+    // The edge to the previous vertex of the polygon
+    Previous,
+    // The edge to the next vertex of the polygon
+    Next,
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized> WeaklySimple<'p, P>
+where <P::Vertex as Vertex>::Coordinate: Real + fmt::Debug {
+    /// The polygons with the vertices at each shared position moved apart, and the index of the original vertex of each
+    fn separated(&self) -> Rings<'p, P> {
+        let mut rings: Vec<Vec<P::Index>> = Vec::new();
+        let mut ring = Vec::new();
+        for element in self.polygons.iter_indices().map(Into::into).chain(iter::once(PolygonElement::NewPolygon)) {
+            match element {
+                PolygonElement::ContinuePolygon(index) => ring.push(index),
+                PolygonElement::NewPolygon => if !ring.is_empty() {
+                    rings.push(core::mem::take(&mut ring));
+                },
+            }
+        }
+        let positions: Vec<Vec<Position<'p, P>>> = rings.iter().map(|ring| ring.iter().map(|index| {
+            let v = self.polygons.get_vertex(index.clone());
+            [v.x(), v.y()]
+        }).collect()).collect();
+
+        let mut occurrences: Vec<[usize; 2]> = positions.iter().enumerate().flat_map(|(r, ring)| (0..ring.len()).map(move |k| [r, k])).collect();
+        let position = |[r, k]: [usize; 2]| positions[r][k];
+        occurrences.sort_by(|a, b| cmp_vertices(&position(*a), &position(*b)));
+        let mut clusters: Vec<Vec<[usize; 2]>> = Vec::new();
+        for (i, &occurrence) in occurrences.iter().enumerate() {
+            if i > 0 && position(occurrences[i - 1]) == position(occurrence) {
+                match clusters.last_mut() {
+                    Some(cluster) if position(cluster[0]) == position(occurrence) => cluster.push(occurrence),
+                    _ => clusters.push(vec![occurrences[i - 1], occurrence]),
+                }
+            }
+        }
+        if clusters.is_empty() {
+            return (positions, rings);
+        }
+
+        let mut shared: Vec<Vec<bool>> = positions.iter().map(|ring| vec![false; ring.len()]).collect();
+        for &[r, k] in clusters.iter().flatten() {
+            shared[r][k] = true;
+        }
+        // Which side of each polygon is filled, judged from a vertex which is not shared where possible, as a shared
+        // vertex lies on the boundary of the other polygons there
+        let rotated: Vec<Vec<_>> = positions.iter().zip(&shared).map(|(ring, shared)| {
+            let start = shared.iter().position(|s| !s).unwrap_or(0);
+            ring[start..].iter().chain(&ring[..start]).copied().collect()
+        }).collect();
+        let filled_left = math::filled_left(&rotated);
+
+        // The copy of the shared vertex which each end of an edge at a shared position now joins
+        let mut joined: Vec<Vec<[usize; 2]>> = positions.iter().map(|ring| vec![[usize::MAX; 2]; ring.len()]).collect();
+        let mut copies: Vec<(Position<'p, P>, P::Index)> = Vec::new();
+        let tau = <<P::Vertex as Vertex>::Coordinate as num_traits::NumCast>::from(TAU).unwrap_or_else(<P::Vertex as Vertex>::Coordinate::max_value);
+        let two = <P::Vertex as Vertex>::Coordinate::one() + <P::Vertex as Vertex>::Coordinate::one();
+        for cluster in clusters {
+            let c = position(cluster[0]);
+            let mut rays: Vec<(<P::Vertex as Vertex>::Coordinate, [usize; 2], End)> = cluster.iter().flat_map(|&[r, k]| {
+                let n = positions[r].len();
+                [(End::Previous, (k + n - 1) % n), (End::Next, (k + 1) % n)].map(|(end, neighbor)| {
+                    let q = positions[r][neighbor];
+                    ((q[1] - c[1]).atan2(q[0] - c[0]), [r, k], end)
+                })
+            }).collect();
+            rays.sort_by(|(a, ..), (b, ..)| a.partial_cmp(b).unwrap_or(core::cmp::Ordering::Equal));
+
+            // Copies are moved a quarter of the way to the nearest feature, so no edge moves across another
+            let far_ends: Vec<Position<'p, P>> = rays.iter().map(|&(_, [r, k], end)| {
+                let n = positions[r].len();
+                positions[r][if end == End::Next { (k + 1) % n } else { (k + n - 1) % n }]
+            }).collect();
+            let mut limit = far_ends.iter().map(|q| (q[0] - c[0]).hypot(q[1] - c[1])).fold(<P::Vertex as Vertex>::Coordinate::max_value(), |a, b| a.min(b));
+            for ring in &positions {
+                for (k, &a) in ring.iter().enumerate() {
+                    let b = ring[(k + 1) % ring.len()];
+                    if a != c && b != c {
+                        limit = limit.min(segment_distance(a, b, c));
+                    }
+                    if a != c && !far_ends.contains(&a) {
+                        for q in &far_ends {
+                            limit = limit.min(segment_distance(c, *q, a));
+                        }
+                    }
+                }
+            }
+            let distance = limit / (two * two);
+
+            // The sectors between consecutive rays alternate between filled and empty. The sector counter-clockwise
+            // from a ray is filled if the filled area is on the left of its edge and the edge leaves the shared
+            // position, or on the right and the edge arrives there
+            let filled_after = |&(_, [r, _], end): &(_, [usize; 2], End)| filled_left[r] == (end == End::Next);
+            let first = rays.iter().position(filled_after).unwrap_or(0);
+            for j in 0..rays.len() / 2 {
+                let (from, [r0, k0], end0) = rays[(first + 2 * j) % rays.len()];
+                let (to, [r1, k1], end1) = rays[(first + 2 * j + 1) % rays.len()];
+                let mut sweep = to - from;
+                if sweep <= <P::Vertex as Vertex>::Coordinate::zero() {
+                    sweep = sweep + tau;
+                }
+                let angle = from + sweep / two;
+                let copy = copies.len();
+                copies.push(([c[0] + distance * angle.cos(), c[1] + distance * angle.sin()], rings[r0][k0].clone()));
+                joined[r0][k0][end0 as usize] = copy;
+                joined[r1][k1][end1 as usize] = copy;
+            }
+        }
+
+        // Relink the polygons through the copies. Each vertex which is not shared, and each copy, joins two edges
+        let offsets: Vec<usize> = positions.iter().scan(0, |offset, ring| {
+            let start = *offset;
+            *offset += ring.len();
+            Some(start)
+        }).collect();
+        let vertex_count: usize = positions.iter().map(Vec::len).sum();
+        let node = |[r, k]: [usize; 2], end: End| if shared[r][k] { vertex_count + joined[r][k][end as usize] } else { offsets[r] + k };
+        let mut adjacent: Vec<Vec<(usize, usize)>> = vec![Vec::new(); vertex_count + copies.len()];
+        let mut edge_count = 0;
+        for (r, ring) in positions.iter().enumerate() {
+            for k in 0..ring.len() {
+                let (a, b) = (node([r, k], End::Next), node([r, (k + 1) % ring.len()], End::Previous));
+                adjacent[a].push((b, edge_count));
+                adjacent[b].push((a, edge_count));
+                edge_count += 1;
+            }
+        }
+
+        let vertex = |n: usize| if n < vertex_count {
+            let r = offsets.partition_point(|offset| *offset <= n) - 1;
+            (positions[r][n - offsets[r]], rings[r][n - offsets[r]].clone())
+        } else {
+            copies[n - vertex_count].clone()
+        };
+        let mut used = vec![false; edge_count];
+        let (mut separated, mut indices) = (Vec::new(), Vec::new());
+        for start in 0..adjacent.len() {
+            let Some(&(_, first_edge)) = adjacent[start].first() else { continue };
+            if used[first_edge] {
+                continue;
+            }
+            let (mut ring_positions, mut ring_indices) = (Vec::new(), Vec::new());
+            let mut n = start;
+            loop {
+                let (c, index) = vertex(n);
+                ring_positions.push(c);
+                ring_indices.push(index);
+                let Some(&(next, edge)) = adjacent[n].iter().find(|(_, edge)| !used[*edge]) else { break };
+                used[edge] = true;
+                if next == start {
+                    break;
+                }
+                n = next;
+            }
+            separated.push(ring_positions);
+            indices.push(ring_indices);
+        }
+        (separated, indices)
+    }
+
+    fn triangulate_inner<K: GeometryKernel<<P::Vertex as Vertex>::Coordinate>, FB: FanFormat<'p, Self>>(&'p self, fbs: &mut FanBuilderState<'p, Self, FB>) -> Result<(), TriangulationError<<FB::Builder as FanBuilder<'p, Self>>::Error>> {
+        let (separated, indices) = self.separated();
+        let mut triangles = Vec::<[usize; 2]>::new();
+        separated.triangulate_with_kernel::<K, _>(formats::IndexedListFormat::new(&mut triangles).into_fan_format())
+            .map_err(TriangulationError::with_fan_builder_error)?;
+
+        for t in triangles.chunks_exact(3) {
+            // Normalize every triangle to counter-clockwise, judged by the separated positions, which are never collinear
+            let [c0, c1, c2] = [t[0], t[1], t[2]].map(|[r, k]| separated[r][k]);
+            let [v0, v1, v2] = [t[0], t[1], t[2]].map(|[r, k]| indices[r][k].clone());
+            let counterclockwise = K::orientation(&c0, &c1, &c2) != Orientation::Right;
+            if counterclockwise == (FB::Builder::WINDING == TriangleWinding::Counterclockwise) {
+                fbs.new_fan(self, v0, v1, v2)?;
+            } else {
+                fbs.new_fan(self, v0, v2, v1)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized> PolygonList<'p> for WeaklySimple<'p, P>
+where <P::Vertex as Vertex>::Coordinate: Real + fmt::Debug {
+    type Vertex = P::Vertex;
+    type Index = P::Index;
+    type IntoItem = P::IntoItem;
+    type Iter<'i> = P::Iter<'i>
+    where Self: 'i, Self::Vertex: 'i, 'p: 'i;
+
+    fn vertex_count(&self) -> usize {
+        self.polygons.vertex_count()
+    }
+
+    fn contour_count_hint(&self) -> Option<usize> {
+        self.polygons.contour_count_hint()
+    }
+
+    fn contour_len_hint(&self, contour: usize) -> Option<usize> {
+        self.polygons.contour_len_hint(contour)
+    }
+
+    fn iter_indices<'i>(&'i self) -> Self::Iter<'i>
+    where Self: 'i, Self::Vertex: 'i, 'p: 'i {
+        self.polygons.iter_indices()
+    }
+
+    fn get_vertex<'a>(&'a self, index: Self::Index) -> &'a Self::Vertex
+    where 'p: 'a {
+        self.polygons.get_vertex(index)
+    }
+
+    fn triangulate<FB: FanFormat<'p, Self>>(&'p self, format: FB) -> Result<<FB::Builder as FanBuilder<'p, Self>>::Output, TriangulationError<<FB::Builder as FanBuilder<'p, Self>>::Error>> {
+        self.triangulate_with_kernel::<DefaultKernel, FB>(format)
+    }
+
+    fn triangulate_with_kernel<K: GeometryKernel<<Self::Vertex as Vertex>::Coordinate>, FB: FanFormat<'p, Self>>(&'p self, format: FB) -> Result<<FB::Builder as FanBuilder<'p, Self>>::Output, TriangulationError<<FB::Builder as FanBuilder<'p, Self>>::Error>> {
+        let mut fbs = FanBuilderState::<'p, Self, FB>::Uninitialized(format);
+        let result = self.triangulate_inner::<K, FB>(&mut fbs);
+        fbs.complete(result)
+    }
+}
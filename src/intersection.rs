@@ -0,0 +1,316 @@
+//! Sweep-line (Bentley–Ottmann) segment intersection.
+//!
+//! Triangulation requires that no two edges cross. These utilities find the points where a set of segments
+//! meet and split the segments at those points, e.g. to planarize a soup of polylines before filling it.
+//!
+//! ```
+//! # use triangulate::intersection;
+//! let segments = [
+//!     [[0f32, 0.], [2., 2.]],
+//!     [[0., 2.], [2., 0.]],
+//! ];
+//! let intersections = intersection::find_intersections(&segments);
+//! assert_eq!(intersections.len(), 1);
+//! assert_eq!(intersections[0].point, [1., 1.]);
+//! assert_eq!(intersection::split_segments(&segments).len(), 4);
+//! ```
+
+use core::{cmp, marker::PhantomData};
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+
+use num_traits::real::Real;
+
+use crate::{Vertex, GeometryKernel, DefaultKernel, Orientation};
+
+/// A point where two or more segments meet
+#[derive(Debug, Clone, PartialEq)]
+pub struct Intersection<C: Real> {
+    /// The location of the intersection
+    pub point: [C; 2],
+    /// The indices of the segments which pass through [Intersection::point]
+    pub interior: Vec<usize>,
+    /// The indices of the segments which begin or end at [Intersection::point]
+    pub endpoint: Vec<usize>,
+}
+
+impl<C: Real> Intersection<C> {
+    /// All segments meeting at this intersection, in ascending order
+    pub fn segments(&self) -> Vec<usize> {
+        let mut segments: Vec<_> = self.interior.iter().chain(self.endpoint.iter()).copied().collect();
+        segments.sort_unstable();
+        segments
+    }
+}
+
+/// A piece of an input segment produced by [split_segments]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SplitSegment<C: Real> {
+    /// The index of the input segment this piece belongs to
+    pub source: usize,
+    /// The endpoints of the piece, in the same direction as the input segment
+    pub segment: [[C; 2]; 2],
+}
+
+/// Find every point where two or more of `segments` meet.
+///
+/// Two segments that only share an endpoint (such as consecutive edges of a polyline) are not reported;
+/// any other contact (crossings, an endpoint touching another segment's interior, three or more segments
+/// meeting at an endpoint, and the ends of collinear overlaps) is. Zero-length segments are ignored.
+///
+/// Results are ordered from the bottom to the top (by y, then by x). Crossing points are rounded to the
+/// nearest representable coordinate.
+pub fn find_intersections<V: Vertex>(segments: &[[V; 2]]) -> Vec<Intersection<V::Coordinate>>
+where V::Coordinate: Real {
+    find_intersections_with_kernel::<DefaultKernel, V>(segments)
+}
+
+/// [find_intersections] using the [GeometryKernel] `K` for all geometric predicates.
+pub fn find_intersections_with_kernel<K: GeometryKernel<V::Coordinate>, V: Vertex>(segments: &[[V; 2]]) -> Vec<Intersection<V::Coordinate>>
+where V::Coordinate: Real {
+    Sweep::<K, V::Coordinate>::new(segments).run()
+}
+
+/// Split `segments` at every point where they meet another segment, so that the resulting pieces only touch at their endpoints.
+///
+/// Pieces of the same segment are returned consecutively, in the segment's direction.
+pub fn split_segments<V: Vertex>(segments: &[[V; 2]]) -> Vec<SplitSegment<V::Coordinate>>
+where V::Coordinate: Real {
+    split_segments_with_kernel::<DefaultKernel, V>(segments)
+}
+
+/// [split_segments] using the [GeometryKernel] `K` for all geometric predicates.
+pub fn split_segments_with_kernel<K: GeometryKernel<V::Coordinate>, V: Vertex>(segments: &[[V; 2]]) -> Vec<SplitSegment<V::Coordinate>>
+where V::Coordinate: Real {
+    let mut splits: Vec<Vec<[V::Coordinate; 2]>> = vec![Vec::new(); segments.len()];
+    for intersection in find_intersections_with_kernel::<K, V>(segments) {
+        for si in intersection.interior {
+            splits[si].push(intersection.point);
+        }
+    }
+
+    let mut output = Vec::with_capacity(segments.len());
+    for (source, (segment, mut points)) in segments.iter().zip(splits).enumerate() {
+        let start = [segment[0].x(), segment[0].y()];
+        let end = [segment[1].x(), segment[1].y()];
+        // Points along a segment are monotone in the sweep order, so sorting by it orders them along the segment
+        let forward = K::compare_y(&start, &end) != cmp::Ordering::Greater;
+        points.sort_by(|a, b| {
+            let ord = K::compare_y(a, b);
+            if forward { ord } else { ord.reverse() }
+        });
+        points.dedup();
+
+        let mut from = start;
+        for point in points.into_iter().chain(core::iter::once(end)) {
+            if point != from {
+                output.push(SplitSegment { source, segment: [from, point] });
+                from = point;
+            }
+        }
+    }
+    output
+}
+
+/// The intersection of two non-parallel segments, if they meet. Both segments are given as `[lower, upper]`.
+pub(crate) fn segment_intersection<K: GeometryKernel<C>, C: Real>(a: [[C; 2]; 2], b: [[C; 2]; 2]) -> Option<[C; 2]> {
+    let [a0, a1] = a;
+    let [b0, b1] = b;
+
+    let oa0 = K::orientation(&b0, &b1, &a0);
+    let oa1 = K::orientation(&b0, &b1, &a1);
+    let ob0 = K::orientation(&a0, &a1, &b0);
+    let ob1 = K::orientation(&a0, &a1, &b1);
+
+    if oa0 == Orientation::Collinear && oa1 == Orientation::Collinear {
+        // Collinear overlaps meet at the segments' endpoints, which are found separately
+        return None;
+    }
+    if (oa0 == oa1 && oa0 != Orientation::Collinear) || (ob0 == ob1 && ob0 != Orientation::Collinear) {
+        return None;
+    }
+
+    // Touching at an endpoint can be answered exactly
+    if oa0 == Orientation::Collinear { return Some(a0); }
+    if oa1 == Orientation::Collinear { return Some(a1); }
+    if ob0 == Orientation::Collinear { return Some(b0); }
+    if ob1 == Orientation::Collinear { return Some(b1); }
+
+    let da = [a1[0] - a0[0], a1[1] - a0[1]];
+    let db = [b1[0] - b0[0], b1[1] - b0[1]];
+    let denominator = da[0] * db[1] - da[1] * db[0];
+    if denominator == C::zero() {
+        return None;
+    }
+    let t = ((b0[0] - a0[0]) * db[1] - (b0[1] - a0[1]) * db[0]) / denominator;
+    let t = t.max(C::zero()).min(C::one());
+    let x = a0[0] + da[0] * t;
+    let y = a0[1] + da[1] * t;
+
+    // Keep the rounded point within both segments' bounds so it can't move backwards past the sweep line
+    let clamp = |v: C, p: C, q: C| v.max(p.min(q)).min(p.max(q));
+    let x = clamp(clamp(x, a0[0], a1[0]), b0[0], b1[0]);
+    let y = clamp(clamp(y, a0[1], a1[1]), b0[1], b1[1]);
+    Some([x, y])
+}
+
+// Orders points by the sweep order of the kernel
+struct SweepPoint<K, C: Real>([C; 2], PhantomData<K>);
+
+impl<K: GeometryKernel<C>, C: Real> PartialEq for SweepPoint<K, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == cmp::Ordering::Equal
+    }
+}
+
+impl<K: GeometryKernel<C>, C: Real> Eq for SweepPoint<K, C> { }
+
+impl<K: GeometryKernel<C>, C: Real> PartialOrd for SweepPoint<K, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: GeometryKernel<C>, C: Real> Ord for SweepPoint<K, C> {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        K::compare_y(&self.0, &other.0)
+    }
+}
+
+#[derive(Default)]
+struct Event {
+    starting: Vec<usize>,
+    ending: Vec<usize>,
+    crossing: Vec<usize>,
+}
+
+struct Sweep<K: GeometryKernel<C>, C: Real> {
+    // Each segment as [lower, upper] in sweep order
+    segments: Vec<[[C; 2]; 2]>,
+    events: BTreeMap<SweepPoint<K, C>, Event>,
+    // Segments crossing the sweep line, from left to right
+    status: Vec<usize>,
+}
+
+impl<K: GeometryKernel<C>, C: Real> Sweep<K, C> {
+    fn new<V: Vertex<Coordinate=C>>(input: &[[V; 2]]) -> Self {
+        let mut segments = Vec::with_capacity(input.len());
+        let mut events = BTreeMap::<SweepPoint<K, C>, Event>::new();
+        for (si, [v0, v1]) in input.iter().enumerate() {
+            let c0 = [v0.x(), v0.y()];
+            let c1 = [v1.x(), v1.y()];
+            let (lower, upper) = match K::compare_y(&c0, &c1) {
+                cmp::Ordering::Less => (c0, c1),
+                cmp::Ordering::Greater => (c1, c0),
+                // Zero-length segments can't cross anything, keep the index valid but don't sweep them
+                cmp::Ordering::Equal => {
+                    segments.push([c0, c1]);
+                    continue;
+                }
+            };
+            segments.push([lower, upper]);
+            events.entry(SweepPoint(lower, PhantomData)).or_default().starting.push(si);
+            events.entry(SweepPoint(upper, PhantomData)).or_default().ending.push(si);
+        }
+
+        Self {
+            segments,
+            events,
+            status: Vec::new(),
+        }
+    }
+
+    // The x coordinate of segment `si` where it crosses the sweep line at `p`
+    fn x_at(&self, si: usize, p: [C; 2]) -> C {
+        let [lower, upper] = self.segments[si];
+        if lower[1] == upper[1] {
+            // The sweep line is treated as if it were rotated slightly, so a horizontal segment 'crosses' at the event point
+            p[0].max(lower[0]).min(upper[0])
+        } else if p[1] == lower[1] {
+            lower[0]
+        } else if p[1] == upper[1] {
+            upper[0]
+        } else {
+            lower[0] + (upper[0] - lower[0]) * (p[1] - lower[1]) / (upper[1] - lower[1])
+        }
+    }
+
+    fn contains(&self, si: usize, p: [C; 2]) -> bool {
+        let [lower, upper] = self.segments[si];
+        K::point_on_segment(&lower, &upper, &p)
+    }
+
+    fn add_crossing(&mut self, si0: usize, si1: usize, p: [C; 2]) {
+        if let Some(c) = segment_intersection::<K, C>(self.segments[si0], self.segments[si1]) {
+            if K::compare_y(&p, &c) == cmp::Ordering::Less {
+                let event = self.events.entry(SweepPoint(c, PhantomData)).or_default();
+                for si in [si0, si1] {
+                    if !event.crossing.contains(&si) {
+                        event.crossing.push(si);
+                    }
+                }
+            }
+        }
+    }
+
+    fn run(mut self) -> Vec<Intersection<C>> {
+        let mut output = Vec::new();
+
+        while let Some((SweepPoint(p, _), event)) = self.events.pop_first() {
+            // Find every segment on the sweep line passing through `p`
+            let position = self.status.partition_point(|&si| self.x_at(si, p) < p[0]);
+            let mut containing = event.crossing;
+            for &si in self.status[..position].iter().rev() {
+                if !self.contains(si, p) { break; }
+                containing.push(si);
+            }
+            for &si in self.status[position..].iter() {
+                if !self.contains(si, p) { break; }
+                containing.push(si);
+            }
+            containing.extend(event.ending.iter().copied());
+            containing.sort_unstable();
+            containing.dedup();
+
+            let interior: Vec<usize> = containing.iter().copied().filter(|si| !event.ending.contains(si)).collect();
+            let mut endpoint: Vec<usize> = event.starting.iter().chain(event.ending.iter()).copied().collect();
+            endpoint.sort_unstable();
+            endpoint.dedup();
+
+            let total = interior.len() + endpoint.len();
+            if total > 2 || (total == 2 && !interior.is_empty()) {
+                output.push(Intersection { point: p, interior: interior.clone(), endpoint });
+            }
+
+            // Remove segments ending at or passing through `p`, then reinsert the ones continuing above it
+            self.status.retain(|si| !containing.contains(si));
+
+            let mut continuing: Vec<usize> = event.starting.iter().copied().chain(interior).collect();
+            continuing.sort_by(|&si0, &si1| {
+                match K::orientation(&p, &self.segments[si0][1], &self.segments[si1][1]) {
+                    Orientation::Right => cmp::Ordering::Less,
+                    Orientation::Left => cmp::Ordering::Greater,
+                    Orientation::Collinear => si0.cmp(&si1),
+                }
+            });
+
+            let position = self.status.partition_point(|&si| self.x_at(si, p) < p[0]);
+            let left = position.checked_sub(1).map(|i| self.status[i]);
+            let right = self.status.get(position).copied();
+
+            if let (Some(&first), Some(&last)) = (continuing.first(), continuing.last()) {
+                if let Some(left) = left {
+                    self.add_crossing(left, first, p);
+                }
+                if let Some(right) = right {
+                    self.add_crossing(last, right, p);
+                }
+            } else if let (Some(left), Some(right)) = (left, right) {
+                self.add_crossing(left, right, p);
+            }
+
+            self.status.splice(position..position, continuing);
+        }
+
+        output
+    }
+}
@@ -0,0 +1,170 @@
+use core::cmp;
+
+use crate::{Coords, Scalar};
+
+/// The side of a directed line a point lies on
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Orientation {
+    /// The point lies to the left of the line
+    Left,
+    /// The point lies exactly on the line
+    Collinear,
+    /// The point lies to the right of the line
+    Right,
+}
+
+/// The geometric predicates used by trapezoidation and triangulation.
+///
+/// Every numeric decision the algorithm makes goes through a [GeometryKernel], so implementing this trait
+/// (e.g. with interval arithmetic or hardware-accelerated predicates) changes the numeric policy of the
+/// whole pipeline. Points are passed as references to `[x, y]` arrays, so coordinates which are expensive to copy
+/// (such as arbitrary-precision numbers) are only cloned where the kernel needs to.
+///
+/// [DefaultKernel] is used unless another kernel is requested with [PolygonList::trapezoidize_with_kernel](crate::PolygonList::trapezoidize_with_kernel)
+/// or [PolygonList::triangulate_with_kernel](crate::PolygonList::triangulate_with_kernel).
+pub trait GeometryKernel<C: Scalar> {
+    /// The [Orientation] of `c` relative to the directed line from `a` to `b`
+    fn orientation(a: &[C; 2], b: &[C; 2], c: &[C; 2]) -> Orientation;
+
+    /// Compares two points by their y coordinates, using the x coordinates to break ties.
+    ///
+    /// This is the 'below'/'above' order of the sweep, used both when inserting vertices and when descending the query
+    /// structure; it must be a total order, consistent for the lifetime of a trapezoidation. The default is
+    /// [cmp_vertices].
+    fn compare_y(a: &[C; 2], b: &[C; 2]) -> cmp::Ordering {
+        cmp_vertices(a, b)
+    }
+
+    /// Whether `c` lies to the left of the segment between `c_min` and `c_max`, where `c_min` is below `c_max`.
+    ///
+    /// A point level with one of the endpoints is compared against that endpoint's x coordinate.
+    fn is_left_of_segment(c_min: &[C; 2], c_max: &[C; 2], c: &[C; 2]) -> bool {
+        if c[1] == c_max[1] {
+            c[0] < c_max[0]
+        } else if c[1] == c_min[1] {
+            c[0] < c_min[0]
+        } else {
+            Self::orientation(c_max, c_min, c) == Orientation::Right
+        }
+    }
+
+    /// Whether `c` lies on the closed segment between `a` and `b`
+    fn point_on_segment(a: &[C; 2], b: &[C; 2], c: &[C; 2]) -> bool {
+        let between = |a: &C, b: &C, c: &C| if a <= b { a <= c && c <= b } else { b <= c && c <= a };
+        Self::orientation(a, b, c) == Orientation::Collinear && between(&a[0], &b[0], &c[0]) && between(&a[1], &b[1], &c[1])
+    }
+}
+
+/// The total order of points used by the sweep: by y coordinate, then by x coordinate to break ties.
+///
+/// Every comparison of points during trapezoidation and point location goes through this order (by way of
+/// [GeometryKernel::compare_y]), so vertices are placed in the query structure the same way they are later looked up.
+/// Unlike comparing coordinates with [PartialOrd], it is total:
+/// - `-0.0` and `0.0` are equal, as they are the same position
+/// - Coordinates which are unordered even with themselves, such as NaN, are greater than all others and equal to each
+///   other, so points with such a coordinate come after all others instead of breaking the order
+///
+/// ```
+/// # use std::cmp::Ordering;
+/// # use triangulate::cmp_vertices;
+/// assert_eq!(cmp_vertices(&[5f64, 0.], &[0., 1.]), Ordering::Less);
+/// assert_eq!(cmp_vertices(&[0f64, 1.], &[1., 1.]), Ordering::Less);
+/// assert_eq!(cmp_vertices(&[-0f64, 0.], &[0., -0.]), Ordering::Equal);
+/// assert_eq!(cmp_vertices(&[0f64, f64::NAN], &[0., f64::INFINITY]), Ordering::Greater);
+/// assert_eq!(cmp_vertices(&[f64::NAN, 0.], &[f64::NAN, 0.]), Ordering::Equal);
+/// ```
+pub fn cmp_vertices<C: Scalar>(a: &[C; 2], b: &[C; 2]) -> cmp::Ordering {
+    cmp_coordinates(&a[1], &b[1]).then_with(|| cmp_coordinates(&a[0], &b[0]))
+}
+
+fn cmp_coordinates<C: Scalar>(a: &C, b: &C) -> cmp::Ordering {
+    let unordered = |c: &C| c.partial_cmp(c).is_none();
+    match (unordered(a), unordered(b)) {
+        (false, false) => a.partial_cmp(b).unwrap_or(cmp::Ordering::Equal),
+        (true, true) => cmp::Ordering::Equal,
+        (true, false) => cmp::Ordering::Greater,
+        (false, true) => cmp::Ordering::Less,
+    }
+}
+
+/// The [GeometryKernel] used unless another is specified. Predicates are evaluated directly in the coordinate type.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DefaultKernel;
+
+impl<C: Scalar> GeometryKernel<C> for DefaultKernel {
+    fn orientation(a: &[C; 2], b: &[C; 2], c: &[C; 2]) -> Orientation {
+        direct_orientation(a, b, c)
+    }
+}
+
+/// A [GeometryKernel] for `f32` and `f64` coordinates which decides orientation exactly by adaptive precision
+/// arithmetic. Requires the `robust` feature.
+///
+/// Nearly collinear vertices are classified consistently and the result is the same on every platform, at little cost
+/// except for such vertices, where [DefaultKernel] can misclassify points and corrupt the trapezoidation.
+/// ```
+/// # use triangulate::{formats, ListFormat, Polygon, RobustKernel};
+/// let polygon = vec![[0f32, 0.1], [0.5, 0.25], [1., 0.4], [0.5, 1.]];
+/// let mut triangles = Vec::<usize>::new();
+/// polygon.triangulate_with_kernel::<RobustKernel, _>(formats::IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+/// assert_eq!(triangles.len(), 3 * 2);
+/// ```
+#[cfg(feature = "robust")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RobustKernel;
+
+#[cfg(feature = "robust")]
+impl GeometryKernel<f64> for RobustKernel {
+    fn orientation(a: &[f64; 2], b: &[f64; 2], c: &[f64; 2]) -> Orientation {
+        exact_orientation(*a, *b, *c)
+    }
+}
+
+#[cfg(feature = "robust")]
+impl GeometryKernel<f32> for RobustKernel {
+    fn orientation(a: &[f32; 2], b: &[f32; 2], c: &[f32; 2]) -> Orientation {
+        // Every f32 converts to f64 without rounding
+        exact_orientation(a.map(f64::from), b.map(f64::from), c.map(f64::from))
+    }
+}
+
+#[cfg(feature = "robust")]
+fn exact_orientation(a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> Orientation {
+    let det = crate::robust::orient2d(a, b, c);
+    if det > 0. {
+        Orientation::Left
+    } else if det < 0. {
+        Orientation::Right
+    } else {
+        Orientation::Collinear
+    }
+}
+
+fn direct_orientation<C: Scalar>(a: &[C; 2], b: &[C; 2], c: &[C; 2]) -> Orientation {
+    let lhs = (b[0].clone() - a[0].clone()) * (c[1].clone() - a[1].clone());
+    let rhs = (b[1].clone() - a[1].clone()) * (c[0].clone() - a[0].clone());
+    if lhs > rhs {
+        Orientation::Left
+    } else if lhs < rhs {
+        Orientation::Right
+    } else {
+        Orientation::Collinear
+    }
+}
+
+// Convenience wrappers so internal code can stay in terms of `Coords`
+
+#[inline(always)]
+pub(crate) fn is_left_of_line<K: GeometryKernel<C>, C: Scalar>(c_min: &Coords<C>, c_max: &Coords<C>, c: &Coords<C>) -> bool {
+    K::is_left_of_segment(c_min.as_array(), c_max.as_array(), c.as_array())
+}
+
+#[inline(always)]
+pub(crate) fn is_below<K: GeometryKernel<C>, C: Scalar>(a: &Coords<C>, b: &Coords<C>) -> bool {
+    K::compare_y(a.as_array(), b.as_array()) == cmp::Ordering::Less
+}
+
+#[inline(always)]
+pub(crate) fn is_below_or_equal<K: GeometryKernel<C>, C: Scalar>(a: &Coords<C>, b: &Coords<C>) -> bool {
+    K::compare_y(a.as_array(), b.as_array()) != cmp::Ordering::Greater
+}
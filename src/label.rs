@@ -0,0 +1,257 @@
+//! Label placement inside polygons.
+//!
+//! The centroid of a polygon is a poor place for its label, as it may lie outside a concave polygon, or so close to an
+//! edge that the label overlaps it. [pole_of_inaccessibility] finds the point inside the polygons farthest from any
+//! edge, the center of the largest circle which fits inside them, with the
+//! [polylabel](https://github.com/mapbox/polylabel) algorithm: the bounding box is split into square cells, and the
+//! cells which may still hold a point farther from the edges than the best found so far are split again, until none
+//! could improve on it by more than the precision. Its distance from the edges is the radius of that circle, the
+//! largest inscribed circle.
+//!
+//! [largest_inscribed_rectangle] finds a large axis-aligned rectangle inside the polygons instead, such as for badges
+//! and decals: the cells of a grid which lie entirely inside are found with a point location in the [Trapezoidation]
+//! of the polygons, the largest rectangle of such cells is taken, and its sides are then pushed outwards as far as
+//! they can go.
+//!
+//! ```
+//! # use triangulate::label;
+//! // An L shape with arms 2 wide, whose centroid lies outside it
+//! let polygons = vec![vec![[0f64, 0.], [10., 0.], [10., 2.], [2., 2.], [2., 10.], [0., 10.]]];
+//! let pole = label::pole_of_inaccessibility(&polygons, 0.001).expect("Trapezoidation failed").expect("Polygons have no area");
+//! // The largest circle fits in the corner, touching both outer sides and the inner corner
+//! let radius = 2. * 2f64.sqrt() / (1. + 2f64.sqrt());
+//! assert!((pole.distance - radius).abs() <= 0.001);
+//! assert!((pole.position[0] - radius).abs() <= 0.01 && (pole.position[1] - radius).abs() <= 0.01);
+//! ```
+
+use core::{cmp, fmt};
+use alloc::{collections::BinaryHeap, vec, vec::Vec};
+
+use num_traits::real::Real;
+
+use crate::{area, math, sdf, PolygonList, PolygonListExt, Trapezoidation, TrapezoidationError, Vertex};
+
+/// A point inside polygons, with its distance from their edges
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct LabelPoint<C: Real> {
+    /// The position of the point
+    pub position: [C; 2],
+    /// The distance from the point to the nearest edge of the polygons
+    pub distance: C,
+}
+
+/// The point inside `polygons` (interpreted with the even-odd rule, as in triangulation) farthest from their edges, to
+/// within `precision`: no point inside the polygons is farther from the edges than `precision` plus the distance of
+/// the point found.
+///
+/// Each step costs a point location in a [Trapezoidation] of the polygons and a distance to every edge. `None` if the
+/// polygons have no area.
+pub fn pole_of_inaccessibility<'p, P, C>(polygons: &'p P, precision: C) -> Result<Option<LabelPoint<C>>, TrapezoidationError>
+where P: PolygonList<'p> + ?Sized,
+      P::Vertex: Vertex<Coordinate=C>,
+      C: Real + fmt::Debug {
+    let contours = PolygonListExt::new(polygons).contours();
+    let Some([min, max]) = math::bounding_box(contours.iter().flatten()) else { return Ok(None) };
+    let size = (max[0] - min[0]).min(max[1] - min[1]);
+    if size <= C::zero() || contours.iter().all(|contour| math::signed_area_2(contour) == C::zero()) {
+        return Ok(None);
+    }
+    let precision = precision.max(size * C::epsilon());
+
+    let trapezoidation: Trapezoidation<'p, P> = polygons.trapezoidize()?;
+    // Distances to the edges, negative outside the polygons
+    let signed_distance = |c: [C; 2]| -> Result<C, TrapezoidationError> {
+        let distance = contours.iter()
+            .flat_map(|contour| contour.iter().zip(contour.iter().cycle().skip(1)))
+            .map(|(a, b)| sdf::segment_distance(*a, *b, c))
+            .fold(C::max_value(), |nearest, d| nearest.min(d));
+        Ok(if trapezoidation.contains_point(c)? { distance } else { -distance })
+    };
+    let cell = |center: [C; 2], half: C| -> Result<Cell<C>, TrapezoidationError> {
+        let distance = signed_distance(center)?;
+        Ok(Cell { center, half, distance, bound: distance + half * (C::one() + C::one()).sqrt() })
+    };
+
+    // Cover the bounding box with square cells, and start from the better of the centroid and the center of the box
+    let two = C::one() + C::one();
+    let half = size / two;
+    let mut cells = BinaryHeap::new();
+    let mut x = min[0];
+    while x < max[0] {
+        let mut y = min[1];
+        while y < max[1] {
+            cells.push(cell([x + half, y + half], half)?);
+            y = y + size;
+        }
+        x = x + size;
+    }
+    let mut best = cell([(min[0] + max[0]) / two, (min[1] + max[1]) / two], C::zero())?;
+    if let Some(centroid) = area::centroid(&contours) {
+        let centroid = cell(centroid, C::zero())?;
+        if centroid.distance > best.distance {
+            best = centroid;
+        }
+    }
+
+    while let Some(c) = cells.pop() {
+        if c.distance > best.distance {
+            best = c;
+        }
+        // The heap is ordered by bound, so no remaining cell can improve on the best by more than the precision either
+        if c.bound - best.distance <= precision {
+            break;
+        }
+        let half = c.half / two;
+        for [dx, dy] in [[-half, -half], [half, -half], [-half, half], [half, half]] {
+            cells.push(cell([c.center[0] + dx, c.center[1] + dy], half)?);
+        }
+    }
+
+    Ok((best.distance > C::zero()).then_some(LabelPoint { position: best.center, distance: best.distance }))
+}
+
+/// A large axis-aligned rectangle inside `polygons` (interpreted with the even-odd rule), as its `[min, max]` corners.
+///
+/// The bounding box of the polygons is divided into square cells, `resolution` along its longer side, and the
+/// largest rectangle of cells which lie entirely inside the polygons is grown until its sides touch their edges. So the
+/// rectangle is always inside, but it may be smaller than the largest one by up to a cell on each side, or be found in
+/// another part of the polygons if their largest rectangles are close in area. `None` if no cell lies entirely inside.
+pub fn largest_inscribed_rectangle<'p, P, C>(polygons: &'p P, resolution: usize) -> Result<Option<[[C; 2]; 2]>, TrapezoidationError>
+where P: PolygonList<'p> + ?Sized,
+      P::Vertex: Vertex<Coordinate=C>,
+      C: Real + fmt::Debug {
+    let contours = PolygonListExt::new(polygons).contours();
+    let Some([min, max]) = math::bounding_box(contours.iter().flatten()) else { return Ok(None) };
+    if resolution == 0 || contours.iter().all(|contour| math::signed_area_2(contour) == C::zero()) {
+        return Ok(None);
+    }
+    let size = (max[0] - min[0]).max(max[1] - min[1]) / C::from(resolution).unwrap_or_else(C::one);
+    let columns = ((max[0] - min[0]) / size).ceil().to_usize().unwrap_or(0).clamp(1, resolution);
+    let rows = ((max[1] - min[1]) / size).ceil().to_usize().unwrap_or(0).clamp(1, resolution);
+    let cell_of = |c: C, min: C, count: usize| ((c - min) / size).floor().to_usize().unwrap_or(0).min(count - 1);
+
+    // Cells crossed by an edge, found column by column along each edge
+    let mut crossed = vec![false; columns * rows];
+    for contour in contours.iter() {
+        for (a, b) in contour.iter().zip(contour.iter().cycle().skip(1)) {
+            let (a, b) = if a[0] <= b[0] { (a, b) } else { (b, a) };
+            for column in cell_of(a[0], min[0], columns)..=cell_of(b[0], min[0], columns) {
+                let x0 = min[0] + size * C::from(column).unwrap_or_else(C::zero);
+                let y_at = |x: C| if b[0] > a[0] { a[1] + (b[1] - a[1]) * (x.max(a[0]).min(b[0]) - a[0]) / (b[0] - a[0]) } else { a[1] };
+                let (y0, y1) = if b[0] > a[0] { (y_at(x0), y_at(x0 + size)) } else { (a[1], b[1]) };
+                for row in cell_of(y0.min(y1), min[1], rows)..=cell_of(y0.max(y1), min[1], rows) {
+                    crossed[row * columns + column] = true;
+                }
+            }
+        }
+    }
+
+    // The remaining cells are entirely inside or entirely outside, as their centers are
+    let trapezoidation: Trapezoidation<'p, P> = polygons.trapezoidize()?;
+    let two = C::one() + C::one();
+    let corner = |column: usize, row: usize| [min[0] + size * C::from(column).unwrap_or_else(C::zero), min[1] + size * C::from(row).unwrap_or_else(C::zero)];
+    let centers: Vec<[C; 2]> = (0..rows).flat_map(|row| (0..columns).map(move |column| (column, row)))
+        .map(|(column, row)| corner(column, row).map(|c| c + size / two)).collect();
+    let located = trapezoidation.locate_batch(&centers)?;
+    let inside: Vec<bool> = located.iter().zip(&crossed).map(|((_, inside), crossed)| *inside && !crossed).collect();
+
+    // The largest rectangle of inside cells, from the histogram of inside cells ending at each row
+    let mut heights = vec![0; columns];
+    let mut best: Option<(usize, [usize; 2], [usize; 2])> = None;
+    for row in 0..rows {
+        for column in 0..columns {
+            heights[column] = if inside[row * columns + column] { heights[column] + 1 } else { 0 };
+        }
+        let mut stack: Vec<(usize, usize)> = Vec::new();
+        for column in 0..=columns {
+            let height = heights.get(column).copied().unwrap_or(0);
+            let mut start = column;
+            while let Some(&(s, h)) = stack.last() {
+                if h < height {
+                    break;
+                }
+                stack.pop();
+                if h > 0 && best.is_none_or(|(area, _, _)| h * (column - s) > area) {
+                    best = Some((h * (column - s), [s, row + 1 - h], [column, row + 1]));
+                }
+                start = s;
+            }
+            stack.push((start, height));
+        }
+    }
+    let Some((_, [column0, row0], [column1, row1])) = best else { return Ok(None) };
+    let mut rectangle = [corner(column0, row0), corner(column1, row1)];
+
+    // Push each side outwards, by bisection within the next cell
+    for (corner, k, sign) in [(0, 0, -C::one()), (1, 0, C::one()), (0, 1, -C::one()), (1, 1, C::one())] {
+        let (mut lo, mut hi) = (C::zero(), size);
+        for _ in 0..32 {
+            let mid = (lo + hi) / two;
+            let mut grown = rectangle;
+            grown[corner][k] = rectangle[corner][k] + sign * mid;
+            if contours.iter().any(|contour| contour.iter().zip(contour.iter().cycle().skip(1)).any(|(a, b)| crosses_interior(*a, *b, grown))) {
+                hi = mid;
+            } else {
+                lo = mid;
+            }
+        }
+        rectangle[corner][k] = rectangle[corner][k] + sign * lo;
+    }
+    Ok(Some(rectangle))
+}
+
+// Whether the segment from `a` to `b` passes through the interior of `rectangle`, by clipping it to the rectangle
+fn crosses_interior<C: Real>(a: [C; 2], b: [C; 2], [min, max]: [[C; 2]; 2]) -> bool {
+    let (mut t0, mut t1) = (C::zero(), C::one());
+    for k in 0..2 {
+        let d = b[k] - a[k];
+        if d == C::zero() {
+            if a[k] <= min[k] || a[k] >= max[k] {
+                return false;
+            }
+            continue;
+        }
+        let (near, far) = ((min[k] - a[k]) / d, (max[k] - a[k]) / d);
+        let (near, far) = if near <= far { (near, far) } else { (far, near) };
+        t0 = t0.max(near);
+        t1 = t1.min(far);
+    }
+    if t0 >= t1 {
+        return false;
+    }
+    // The middle of the clipped segment is inside unless the segment only runs along a side
+    let t = (t0 + t1) / (C::one() + C::one());
+    (0..2).all(|k| {
+        let c = a[k] + (b[k] - a[k]) * t;
+        min[k] < c && c < max[k]
+    })
+}
+
+// A square cell of the search, ordered by the greatest distance any point in it could have
+#[derive(Clone, Copy)]
+struct Cell<C: Real> {
+    center: [C; 2],
+    half: C,
+    distance: C,
+    bound: C,
+}
+
+impl<C: Real> PartialEq for Cell<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == cmp::Ordering::Equal
+    }
+}
+
+impl<C: Real> Eq for Cell<C> { }
+
+impl<C: Real> PartialOrd for Cell<C> {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<C: Real> Ord for Cell<C> {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.bound.partial_cmp(&other.bound).unwrap_or(cmp::Ordering::Equal)
+    }
+}
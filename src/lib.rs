@@ -1,3 +1,4 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![warn(missing_docs)]
 
 //! # triangulate
@@ -19,13 +20,16 @@
 //!     vec![[0.05, 0.05], [0.05, 0.95], [0.95, 0.95], [0.95, 0.05]]
 //! ];
 //! let mut triangulated_indices = Vec::<[usize; 2]>::new();
-//! polygons.triangulate(formats::IndexedListFormat::new(&mut triangulated_indices).into_fan_builder()).expect("Triangulation failed");
+//! polygons.triangulate(formats::IndexedListFormat::new(&mut triangulated_indices).into_fan_format()).expect("Triangulation failed");
 //! println!("First triangle: {:?}, {:?}, {:?}", 
 //!     polygons.get_vertex(triangulated_indices[0]), 
 //!     polygons.get_vertex(triangulated_indices[1]), 
 //!     polygons.get_vertex(triangulated_indices[2]));
 //! ```
 //! 
+//! For the most common case, [triangulate_simple] takes a list of rings with `f32` coordinates and returns the vertices
+//! and `u32` triangle indices, without any of the traits below.
+//! 
 //! Any type that implements [Polygon] or [PolygonList] can be triangulated. Most commonly that would be [Vec<_>] or [Vec<Vec<_>>] (where `_`: [Vertex], such as `[f32; 2]`), 
 //! but you can implement the trait on your own types.
 //! 
@@ -47,21 +51,46 @@
 //! * [FanBuilder]
 //! * [ListFormat]
 //! * [ListBuilder]
+//! * [MonotoneFormat]
+//! * [MonotoneBuilder]
 //! 
 //! ## Preconditions  
 //! * No edge can cross any other edge, whether it is on the same polygon or not.
 //! * Each vertex must be part of exactly two edges. Polygons cannot 'share' vertices with each other.
 //! * Each vertex must be distinct - no vertex can have x and y coordinates that both compare equal to another vertex's.
+//!   Polygons which only touch at vertices can be triangulated through [PolygonList::weakly_simple], which separates
+//!   such vertices first.
 //! 
 //! These preconditions are not explicitly checked, but an invalid polygon set will likely yield `TriangulationError::InternalError`.
 //! 
 //! ## Results
 //! Because the algorithm involves random ordering, the exact triangulation is not guaranteed to be same between invocations.
+//! Set [TriangulationOptions::seed] to make it reproducible.
+//! 
+//! ## Features
+//! The `std` feature is enabled by default. Without it the crate is `no_std`, and only needs `alloc`: enable the `libm`
+//! feature instead for the floating point functions of `f32` and `f64`. Without `std`, [InternalError] has no
+//! backtrace, the `tuning` module is not available, and triangulations without a
+//! [seed](TriangulationOptions::seed) always use the same random order. The `executor`, `rayon`, `petgraph`, `geo`,
+//! `geojson` and `meshopt` features require `std`.
+//! 
+//! The `fixed-f32-u32` feature adds the `fixed` module, with the triangulation of `f32` polygons into `u32` indices
+//! compiled into this crate, for dependents which don't need the generic interface.
+//! 
+//! The `geo` feature implements [Vertex] for the `Coord` of [geo-types](https://crates.io/crates/geo-types), and
+//! triangulates its `Polygon` and `MultiPolygon` with `GeoPolygon` and `GeoMultiPolygon`. The `geojson` feature adds
+//! the `geojson` module, which loads the polygons of GeoJSON documents.
 //! 
 //! ## Algorithm
 //! This library is based on [Raimund Seidel's randomized algorithm for triangulating polygons](https://www.cs.princeton.edu/courses/archive/fall05/cos528/handouts/A%20Simple%20and%20fast.pdf). 
 //! The expected runtime for each polygon or hole with *n* vertices is O(*n* [log\*](https://en.wikipedia.org/wiki/Iterated_logarithm) *n*), a near-linear runtime.
 
+extern crate alloc;
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("triangulate requires either the `std` or the `libm` feature for floating point math");
+
+mod compat;
 mod idx;
 mod segment;
 mod trapezoid;
@@ -71,12 +100,57 @@ mod nexus;
 mod monotone;
 mod mappable;
 mod math;
+mod kernel;
+#[cfg(feature = "robust")]
+mod robust;
+#[cfg(feature = "approx")]
+mod approx_eq;
 mod fan_builder_state;
+mod options;
+mod triangulator;
 mod inputs;
 mod outputs;
 #[macro_use]
 mod errors;
 
+pub mod intersection;
+pub mod boolean;
+pub mod mesh;
+pub mod refinement;
+pub mod hull;
+pub mod validity;
+pub mod repair;
+pub mod winding;
+pub mod area;
+pub mod containment;
+pub mod components;
+pub mod shapes;
+pub mod stroke;
+pub mod minkowski;
+pub mod sdf;
+pub mod label;
+pub mod solid;
+pub mod skeleton;
+pub mod coverage;
+pub mod preview;
+pub mod buffers;
+pub mod bvh;
+pub mod float_format;
+#[cfg(feature = "std")]
+pub mod tuning;
+#[cfg(feature = "text")]
+pub mod text;
+#[cfg(feature = "trace")]
+pub mod trace;
+#[cfg(feature = "random")]
+pub mod random;
+#[cfg(feature = "executor")]
+pub mod executor;
+#[cfg(feature = "fixed-f32-u32")]
+pub mod fixed;
+#[cfg(feature = "geojson")]
+pub mod geojson;
+
 #[cfg(feature = "_debugging")]
 pub mod debug;
 
@@ -84,8 +158,19 @@ pub mod debug;
 #[doc(hidden)]
 pub mod tests;
 
-pub use trapezoidation::Trapezoidation;
-pub use errors::{TrapezoidationError, TriangulationError};
+pub use trapezoidation::{Trapezoidation, TrapezoidGeometry};
+#[cfg(feature = "serde")]
+pub use trapezoidation::TrapezoidationSeed;
+pub use errors::{TrapezoidationError, TriangulationError, InternalError, ErrorGeometry, ErrorStage};
+pub use kernel::{GeometryKernel, DefaultKernel, Orientation, cmp_vertices};
+#[cfg(feature = "robust")]
+pub use kernel::RobustKernel;
+pub use options::{TriangulationOptions, TriangulationMethod, MergePolicy};
+pub use triangulator::Triangulator;
+pub use mesh::triangulate_simple;
+/// Arbitrary-precision rational numbers, which are exact [Scalar] coordinates
+#[cfg(feature = "num-rational")]
+pub use num_rational::BigRational;
 
 pub(crate) use fan_builder_state::FanBuilderState;
 
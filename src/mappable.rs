@@ -1,3 +1,5 @@
+use alloc::vec::Vec;
+
 /// A type which can map its members of type `T` to other types
 /// (e.g. `[usize; 2]` is [Mappable] over [usize] to produce `[u16; 2]`, `[u32; 2]`, etc.)
 /// 
@@ -1,16 +1,7 @@
-use num_traits::real::Real;
-
-use crate::Coords;
+use core::{cmp, iter};
+use alloc::{vec, vec::Vec};
 
-pub(crate) fn is_left_of_line<C: Real>(c_min: Coords<C>, c_max: Coords<C>, c: Coords<C>) -> bool {
-    if c.y() == c_max.y() {
-        c.x() < c_max.x()
-    } else if c.y() == c_min.y() {
-        c.x() < c_min.x()
-    } else {
-        (c_min.x() - c_max.x()) * (c.y() - c_max.y()) < (c_min.y() - c_max.y()) * (c.x() - c_max.x())
-    }
-}
+use num_traits::real::Real;
 
 pub(crate) fn math_n(n: usize, h: usize) -> usize {
     let mut nf = n as f64;
@@ -19,3 +10,150 @@ pub(crate) fn math_n(n: usize, h: usize) -> usize {
     }
     ((n as f64) / nf).ceil() as usize
 }
+
+/// A sum with Neumaier's compensation, which carries the rounding error of each addition separately
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct CompensatedSum<C: Real> {
+    sum: C,
+    compensation: C,
+}
+
+impl<C: Real> CompensatedSum<C> {
+    pub fn new() -> Self {
+        Self { sum: C::zero(), compensation: C::zero() }
+    }
+
+    pub fn add(&mut self, x: C) {
+        let sum = self.sum + x;
+        self.compensation = self.compensation + if self.sum.abs() >= x.abs() {
+            (self.sum - sum) + x
+        } else {
+            (x - sum) + self.sum
+        };
+        self.sum = sum;
+    }
+
+    /// Add `a * b`, with the rounding error of the product
+    pub fn add_product(&mut self, a: C, b: C) {
+        let product = a * b;
+        self.add(product);
+        self.add(a.mul_add(b, -product));
+    }
+
+    pub fn value(&self) -> C {
+        self.sum + self.compensation
+    }
+}
+
+/// Twice the signed area of a contour (positive when counter-clockwise).
+///
+/// The vertices are taken relative to the first, so coordinates far from the origin do not cancel, and the products are
+/// summed with their rounding errors by [CompensatedSum].
+pub(crate) fn signed_area_2<C: Real>(contour: &[[C; 2]]) -> C {
+    let Some(&origin) = contour.first() else { return C::zero() };
+    let mut area = CompensatedSum::new();
+    for edge in contour[1..].windows(2) {
+        let [p, q] = [edge[0], edge[1]].map(|c| [c[0] - origin[0], c[1] - origin[1]]);
+        area.add_product(p[0], q[1]);
+        area.add_product(-q[0], p[1]);
+    }
+    area.value()
+}
+
+/// Even-odd containment of `c` within a single contour
+pub(crate) fn contour_contains<C: Real>(contour: &[[C; 2]], c: [C; 2]) -> bool {
+    let mut inside = false;
+    for (i, c0) in contour.iter().enumerate() {
+        let c1 = contour[(i + 1) % contour.len()];
+        if (c0[1] > c[1]) != (c1[1] > c[1]) && c[0] < c0[0] + (c[1] - c0[1]) * (c1[0] - c0[0]) / (c1[1] - c0[1]) {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// Even-odd containment of `c` within a set of contours
+pub(crate) fn contours_contain<C: Real>(contours: &[Vec<[C; 2]>], c: [C; 2]) -> bool {
+    contours.iter().filter(|contour| contour_contains(contour, c)).count() % 2 == 1
+}
+
+/// The `[min, max]` corners of the bounding box of `points`, or `None` if there are no points
+pub(crate) fn bounding_box<'a, C: Real + 'a>(points: impl IntoIterator<Item=&'a [C; 2]>) -> Option<[[C; 2]; 2]> {
+    points.into_iter().fold(None, |bb, c| Some(match bb {
+        None => [*c, *c],
+        Some([min, max]) => [[min[0].min(c[0]), min[1].min(c[1])], [max[0].max(c[0]), max[1].max(c[1])]],
+    }))
+}
+
+/// The innermost other contour containing each contour, by the smallest area among those containing its first point
+pub(crate) fn contour_parents<C: Real>(contours: &[Vec<[C; 2]>]) -> Vec<Option<usize>> {
+    let areas: Vec<C> = contours.iter().map(|contour| signed_area_2(contour)).collect();
+    let boxes: Vec<Option<[[C; 2]; 2]>> = contours.iter().map(|contour| bounding_box(contour)).collect();
+    let grid = BoxGrid::new(&boxes);
+    (0..contours.len()).map(|ci| {
+        let c = *contours[ci].first()?;
+        grid.candidates(c).into_iter()
+            .filter(|&cj| cj != ci && boxes[cj].is_some_and(|[min, max]| min[0] <= c[0] && c[0] <= max[0] && min[1] <= c[1] && c[1] <= max[1]))
+            .filter(|&cj| contour_contains(&contours[cj], c))
+            .min_by(|&cj, &ck| areas[cj].abs().partial_cmp(&areas[ck].abs()).unwrap_or(cmp::Ordering::Equal))
+    }).collect()
+}
+
+// Bounding boxes in a uniform grid, to find the boxes which may contain a point without testing all of them. Boxes
+// spanning many cells are kept in a separate list, which is always searched
+struct BoxGrid<C: Real> {
+    min: [C; 2],
+    cell: [C; 2],
+    side: usize,
+    cells: Vec<Vec<usize>>,
+    large: Vec<usize>,
+}
+
+impl<C: Real> BoxGrid<C> {
+    fn new(boxes: &[Option<[[C; 2]; 2]>]) -> Self {
+        let side = (boxes.len() as f64).sqrt().ceil().max(1.) as usize;
+        let [min, max] = bounding_box(boxes.iter().flatten().flatten()).unwrap_or([[C::zero(); 2]; 2]);
+        let count = C::from(side).unwrap_or_else(C::one);
+        let mut grid = Self { min, cell: [(max[0] - min[0]) / count, (max[1] - min[1]) / count], side, cells: vec![Vec::new(); side * side], large: Vec::new() };
+        for (bi, bb) in boxes.iter().enumerate() {
+            let Some([min, max]) = bb else { continue };
+            let ([x0, y0], [x1, y1]) = (grid.cell_of(*min), grid.cell_of(*max));
+            if (x1 - x0 + 1) * (y1 - y0 + 1) > side {
+                grid.large.push(bi);
+                continue;
+            }
+            for y in y0..=y1 {
+                for x in x0..=x1 {
+                    grid.cells[y * side + x].push(bi);
+                }
+            }
+        }
+        grid
+    }
+
+    fn cell_of(&self, c: [C; 2]) -> [usize; 2] {
+        [0, 1].map(|k| {
+            let i = if self.cell[k] > C::zero() { ((c[k] - self.min[k]) / self.cell[k]).floor().to_usize().unwrap_or(0) } else { 0 };
+            i.min(self.side - 1)
+        })
+    }
+
+    // The boxes which may contain `c`, in increasing order
+    fn candidates(&self, c: [C; 2]) -> Vec<usize> {
+        let [x, y] = self.cell_of(c);
+        let mut candidates: Vec<usize> = self.cells[y * self.side + x].iter().chain(&self.large).copied().collect();
+        candidates.sort_unstable();
+        candidates
+    }
+}
+
+/// Whether the area filled by the even-odd rule lies to the left of each contour
+pub(crate) fn filled_left<C: Real>(contours: &[Vec<[C; 2]>]) -> Vec<bool> {
+    let areas: Vec<C> = contours.iter().map(|contour| signed_area_2(contour)).collect();
+    let parents = contour_parents(contours);
+    // The filled area is inside a contour at an even depth and outside one at an odd depth
+    (0..contours.len()).map(|ci| {
+        let depth_even = iter::successors(parents[ci], |&cj| parents[cj]).count() % 2 == 0;
+        (areas[ci] > C::zero()) == depth_even
+    }).collect()
+}
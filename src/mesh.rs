@@ -0,0 +1,1503 @@
+//! Indexed triangle meshes and post-processing of triangulation output.
+//!
+//! A [Mesh] owns its vertices, so post-processing steps can add vertices which are not part of the original
+//! [PolygonList]. New vertices are created with a [VertexConstructor].
+//!
+//! ```
+//! # use triangulate::{mesh::Mesh, LinearConstructor};
+//! let polygons = vec![vec![[0f64, 0.], [1., 0.], [1., 1.], [0., 1.]]];
+//! let mut mesh = Mesh::from_polygons(&polygons).expect("Triangulation failed");
+//! assert_eq!(mesh.triangles.len(), 2);
+//!
+//! // Subdivide until no triangle is larger than 0.01
+//! mesh.refine(&mut LinearConstructor, |t| Mesh::<[f64; 2]>::area_of(t), 0.01, 16);
+//! assert!(mesh.iter_triangles().all(|t| Mesh::<[f64; 2]>::area_of(t) <= 0.01));
+//! ```
+
+use core::{cmp, convert::Infallible, fmt, iter, ops::Range, slice};
+use alloc::{collections::BTreeSet, vec, vec::Vec};
+
+use num_traits::{One, Zero, real::Real};
+
+use crate::{boolean::{self, BooleanOp, Contours}, bvh::Bvh, compat::{Entry, HashMap, HashSet}, components::{Components, UnionFind}, formats, intersection, math, solid::Solid, DefaultKernel, FromCoords, GeometryKernel, IntoCoords, ListFormat, Orientation, PolygonElement, PolygonList, TriangulationError, Scalar, Vertex, VertexConstructor};
+
+// A segment between two points
+type Segment<C> = [[C; 2]; 2];
+
+/// A triangle mesh with counter-clockwise triangles indexing into a shared vertex list
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mesh<V: Vertex> {
+    /// The vertices of the mesh
+    pub vertices: Vec<V>,
+    /// The triangles of the mesh, as indices into [Mesh::vertices]
+    pub triangles: Vec<[usize; 3]>,
+}
+
+impl<V: Vertex> Default for Mesh<V> {
+    fn default() -> Self {
+        Self { vertices: Vec::new(), triangles: Vec::new() }
+    }
+}
+
+impl<V: Vertex> Mesh<V>
+where V::Coordinate: Real {
+    /// Create a mesh from its parts
+    pub fn new(vertices: Vec<V>, triangles: Vec<[usize; 3]>) -> Self {
+        Self { vertices, triangles }
+    }
+
+    /// Triangulate `polygons` into a new mesh. Every vertex of `polygons` is copied into the mesh exactly once, in iteration order.
+    pub fn from_polygons<'p, P: PolygonList<'p, Vertex=V> + ?Sized>(polygons: &'p P) -> Result<Self, TriangulationError<Infallible>>
+    where V: Clone {
+        Self::triangulate_flattened(&Flattened::new(polygons))
+    }
+
+    /// [Mesh::from_polygons], also returning the range of vertices belonging to each non-empty polygon
+    pub(crate) fn from_polygons_with_contours<'p, P: PolygonList<'p, Vertex=V> + ?Sized>(polygons: &'p P) -> Result<(Self, Vec<Range<usize>>), TriangulationError<Infallible>>
+    where V: Clone {
+        let flattened = Flattened::new(polygons);
+        Ok((Self::triangulate_flattened(&flattened)?, flattened.contours))
+    }
+
+    /// [Mesh::from_polygons] on all threads of the [rayon](https://crates.io/crates/rayon) thread pool, for inputs
+    /// with many separate polygons, such as the countries and lakes of a map. Requires the `rayon` feature.
+    ///
+    /// Each polygon which is not a hole is triangulated on its own, with the holes directly inside it, and the triangles
+    /// are concatenated in the order of those polygons, so the result does not depend on the number of threads. If
+    /// several fail, the error is that of the first.
+    #[cfg(feature = "rayon")]
+    pub fn from_polygons_par<'p, P: PolygonList<'p, Vertex=V> + ?Sized + Sync>(polygons: &'p P) -> Result<Self, TriangulationError<Infallible>>
+    where V: Clone + Send,
+          P::Index: Send + Sync {
+        use rayon::prelude::*;
+
+        let flattened = Flattened::new(polygons);
+        let contours = &flattened.contours;
+        let rings: Vec<Vec<[V::Coordinate; 2]>> = contours.iter().map(|r| r.clone().map(|vi| {
+            let v = flattened.get_vertex(vi);
+            [v.x(), v.y()]
+        }).collect()).collect();
+        let parents = math::contour_parents(&rings);
+
+        // By the even-odd rule, a polygon nested at an odd depth is a hole of its parent
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+        let mut group_of = vec![usize::MAX; contours.len()];
+        for ci in 0..contours.len() {
+            let depth = iter::successors(parents[ci], |&cj| parents[cj]).count();
+            if depth % 2 == 0 {
+                group_of[ci] = groups.len();
+                groups.push(vec![ci]);
+            }
+        }
+        for ci in 0..contours.len() {
+            if let Some(gi) = parents[ci].map(|cj| group_of[cj]).filter(|_| group_of[ci] == usize::MAX) {
+                groups[gi].push(ci);
+            }
+        }
+
+        let triangles = groups.par_iter().map(|group| {
+            let mut elements = Vec::new();
+            for &ci in group {
+                elements.extend(contours[ci].clone().map(Some));
+                elements.push(None);
+            }
+            let mut indices = Vec::<usize>::new();
+            Group { flattened: &flattened, elements }.triangulate(formats::IndexedListFormat::new(&mut indices).into_fan_format())?;
+            Ok(indices)
+        }).collect::<Vec<Result<Vec<usize>, TriangulationError<Infallible>>>>();
+
+        let mut mesh = Self::new(flattened.indices.iter().map(|vi| polygons.get_vertex(vi.clone()).clone()).collect(), Vec::new());
+        for indices in triangles {
+            mesh.triangles.extend(indices?.chunks_exact(3).map(|t| [t[0], t[1], t[2]]));
+        }
+        Ok(mesh)
+    }
+
+    /// Triangulate `polygons` into a new mesh, with the triangles grouped into contiguous [Batch]es by the key of the area they fill.
+    ///
+    /// `keys` has one entry for each non-empty polygon, in iteration order. The area between an outer boundary and its holes
+    /// takes the key of the outer boundary, so holes' keys are not used (but must still be present).
+    ///
+    /// Fails with [TriangulationError::MissingKey] if `keys` has fewer entries than `polygons` has non-empty polygons.
+    pub fn from_polygons_batched<'p, P: PolygonList<'p, Vertex=V> + ?Sized>(polygons: &'p P, keys: &[u32]) -> Result<BatchedMesh<V>, TriangulationError<Infallible>>
+    where V: Clone {
+        let flattened = Flattened::new(polygons);
+        let contours = &flattened.contours;
+        if keys.len() < contours.len() {
+            return Err(TriangulationError::MissingKey(keys.len()));
+        }
+        let mut mesh = Self::triangulate_flattened(&flattened)?;
+
+        let coords = |vi: usize| [mesh.vertices[vi].x(), mesh.vertices[vi].y()];
+        let rings: Vec<Vec<[V::Coordinate; 2]>> = contours.iter().map(|r| r.clone().map(coords).collect()).collect();
+        let areas: Vec<V::Coordinate> = rings.iter().map(|ring| math::signed_area_2(ring)).collect();
+        let parents = math::contour_parents(&rings);
+        // By the even-odd rule, a polygon nested at an odd depth is a hole, so the area inside it belongs to its parent
+        let owner_inside: Vec<usize> = (0..rings.len()).map(|ci| {
+            let depth = iter::successors(parents[ci], |&cj| parents[cj]).count();
+            if depth % 2 == 0 { ci } else { parents[ci].unwrap_or(ci) }
+        }).collect();
+
+        let mut contour_of = vec![0; mesh.vertices.len()];
+        for (ci, range) in contours.iter().enumerate() {
+            contour_of[range.clone()].fill(ci);
+        }
+
+        let mut keyed: Vec<(u32, [usize; 3])> = mesh.triangles.iter().map(|t| {
+            // The triangle lies either just inside or just outside the polygon of its first vertex
+            let vi = t[0];
+            let ci = contour_of[vi];
+            let range = &contours[ci];
+            let prev = if vi == range.start { range.end - 1 } else { vi - 1 };
+            let next = if vi + 1 == range.end { range.start } else { vi + 1 };
+            let [c1, c2] = [coords(t[1]), coords(t[2])];
+            let two = V::Coordinate::one() + V::Coordinate::one();
+            let toward = [(c1[0] + c2[0]) / two, (c1[1] + c2[1]) / two];
+
+            let interior = if areas[ci] > V::Coordinate::zero() { Orientation::Left } else { Orientation::Right };
+            let o = |a, b, c| <DefaultKernel as GeometryKernel<V::Coordinate>>::orientation(&a, &b, &c) == interior;
+            let (c_prev, c, c_next) = (coords(prev), coords(vi), coords(next));
+            let inside = if o(c_prev, c, c_next) {
+                o(c_prev, c, toward) && o(c, c_next, toward)
+            } else {
+                o(c_prev, c, toward) || o(c, c_next, toward)
+            };
+            let owner = if inside { owner_inside[ci] } else { parents[ci].map_or(ci, |cj| owner_inside[cj]) };
+            (keys[owner], *t)
+        }).collect();
+        keyed.sort_by_key(|(key, _)| *key);
+
+        let mut batches: Vec<Batch> = Vec::new();
+        for (i, (key, _)) in keyed.iter().enumerate() {
+            match batches.last_mut() {
+                Some(batch) if batch.key == *key => batch.triangles.end = i + 1,
+                _ => batches.push(Batch { key: *key, triangles: i..i + 1 }),
+            }
+        }
+        mesh.triangles = keyed.into_iter().map(|(_, t)| t).collect();
+        Ok(BatchedMesh { mesh, batches })
+    }
+
+    fn triangulate_flattened<'p, P: PolygonList<'p, Vertex=V> + ?Sized>(flattened: &Flattened<'p, P>) -> Result<Self, TriangulationError<Infallible>>
+    where V: Clone {
+        // An empty mesh, such as a boolean intersection of disjoint meshes, is not an error
+        if flattened.indices.is_empty() {
+            return Ok(Self::new(Vec::new(), Vec::new()));
+        }
+        let mut indices = Vec::<usize>::new();
+        flattened.triangulate(formats::IndexedListFormat::new(&mut indices).into_fan_format())?;
+
+        let vertices = flattened.indices.iter().map(|vi| flattened.polygons.get_vertex(vi.clone()).clone()).collect();
+        let triangles = indices.chunks_exact(3).map(|t| [t[0], t[1], t[2]]).collect();
+        Ok(Self::new(vertices, triangles))
+    }
+
+    /// Iterate through the vertices of each triangle
+    pub fn iter_triangles(&self) -> impl Iterator<Item=[&V; 3]> + '_ {
+        self.triangles.iter().map(|t| t.map(|vi| &self.vertices[vi]))
+    }
+
+    /// The signed area of a triangle, positive when it is counter-clockwise
+    pub fn area_of(t: [&V; 3]) -> V::Coordinate {
+        let [v0, v1, v2] = t;
+        ((v1.x() - v0.x()) * (v2.y() - v0.y()) - (v2.x() - v0.x()) * (v1.y() - v0.y())) / (V::Coordinate::one() + V::Coordinate::one())
+    }
+
+    fn edge_length_squared(&self, vi0: usize, vi1: usize) -> V::Coordinate {
+        let (v0, v1) = (&self.vertices[vi0], &self.vertices[vi1]);
+        (v1.x() - v0.x()).powi(2) + (v1.y() - v0.y()).powi(2)
+    }
+
+    /// Subdivide triangles until `error` is at most `threshold` for every triangle, or `max_rounds` rounds have been performed.
+    ///
+    /// In each round, the longest edge of every triangle exceeding `threshold` is bisected, along with any other triangle sharing
+    /// that edge, so the mesh stays conforming (no vertex lies on another triangle's edge). Midpoints are created with `constructor`,
+    /// so `error` can measure variation of interpolated attributes, such as the deviation of a color gradient.
+    ///
+    /// Returns whether every triangle is within `threshold`.
+    pub fn refine<VC, E>(&mut self, constructor: &mut VC, mut error: E, threshold: V::Coordinate, max_rounds: usize) -> bool
+    where VC: VertexConstructor<V>,
+          E: FnMut([&V; 3]) -> V::Coordinate {
+        for _ in 0..max_rounds {
+            let mut marked = Vec::new();
+            for ti in 0..self.triangles.len() {
+                let t = self.triangles[ti];
+                if error(t.map(|vi| &self.vertices[vi])) <= threshold {
+                    continue;
+                }
+                let k = (0..3).map(|k| (k, self.edge_length_squared(t[k], t[(k + 1) % 3])))
+                    .fold((0, V::Coordinate::zero()), |longest, edge| if edge.1 > longest.1 { edge } else { longest }).0;
+                marked.push((t[k], t[(k + 1) % 3]));
+            }
+            if !self.bisect_edges(constructor, marked) {
+                return true;
+            }
+        }
+        self.iter_triangles().all(|t| error(t) <= threshold)
+    }
+
+    /// Subdivide edges until none is longer than `max_length`, including the boundary edges.
+    ///
+    /// Edges are bisected with new vertices from `constructor`, and the triangles around them are split so the mesh stays conforming.
+    /// This is useful for per-vertex lighting or displacement, where long edges cause artifacts. Nothing is subdivided
+    /// unless `max_length` is positive, since no edge could ever be short enough.
+    pub fn subdivide_edges<VC: VertexConstructor<V>>(&mut self, constructor: &mut VC, max_length: V::Coordinate) {
+        if max_length.partial_cmp(&V::Coordinate::zero()) != Some(cmp::Ordering::Greater) {
+            return;
+        }
+        let max_length_squared = max_length * max_length;
+        loop {
+            let marked: Vec<(usize, usize)> = self.triangles.iter()
+                .flat_map(|t| (0..3).map(move |k| (t[k], t[(k + 1) % 3])))
+                .filter(|(vi0, vi1)| self.edge_length_squared(*vi0, *vi1) > max_length_squared)
+                .collect();
+            if !self.bisect_edges(constructor, marked) {
+                return;
+            }
+        }
+    }
+
+    /// Remesh the interior of the mesh into near-equilateral triangles with edges close to `target_length`, such as to
+    /// seed cloth or soft body simulations from an outline.
+    ///
+    /// Each of the `iterations` splits edges longer than 4/3 of the target, collapses interior edges shorter than 4/5
+    /// of it, flips edges to make the mesh Delaunay and moves interior vertices towards the centroid of their neighbors.
+    /// Boundary vertices never move, and boundary edges are only split, so the outline is kept exactly. New vertices are
+    /// created with `constructor`, and the vertices which are no longer used are removed at the end.
+    pub fn remesh<VC: VertexConstructor<V>>(&mut self, constructor: &mut VC, target_length: V::Coordinate, iterations: usize) {
+        let three = V::Coordinate::one() + V::Coordinate::one() + V::Coordinate::one();
+        let four = three + V::Coordinate::one();
+        let five = four + V::Coordinate::one();
+        let (low, high) = (target_length * four / five, target_length * four / three);
+        let half = V::Coordinate::one() / (V::Coordinate::one() + V::Coordinate::one());
+        for _ in 0..iterations {
+            self.subdivide_edges(constructor, high);
+            self.collapse_short_edges(constructor, low, high);
+            self.flip_to_delaunay();
+            self.smooth(constructor, half, 1);
+        }
+        self.remove_unused_vertices();
+    }
+
+    /// Improve the shape of the triangles with `iterations` rounds of Laplacian smoothing, such as after [Mesh::refine].
+    ///
+    /// Each round moves every interior vertex by `factor` (between 0 and 1) of the way to the centroid of its neighbors,
+    /// with the new vertex created by `constructor`. Boundary vertices are pinned, and a vertex is left in place if
+    /// moving it would invert one of its triangles, so the outline and the area of the mesh are unchanged.
+    pub fn smooth<VC: VertexConstructor<V>>(&mut self, constructor: &mut VC, factor: V::Coordinate, iterations: usize) {
+        let boundary = self.boundary_vertices();
+        let incident = self.incident_triangles();
+        let neighbors: Vec<Vec<usize>> = incident.iter().enumerate().map(|(vi, triangles)| {
+            let mut neighbors: Vec<usize> = triangles.iter().flat_map(|ti| self.triangles[*ti]).filter(|vj| *vj != vi).collect();
+            neighbors.sort_unstable();
+            neighbors.dedup();
+            neighbors
+        }).collect();
+        for _ in 0..iterations {
+            for vi in 0..self.vertices.len() {
+                let neighbors = &neighbors[vi];
+                if boundary[vi] || neighbors.len() < 2 {
+                    continue;
+                }
+                let mut centroid = constructor.interpolate(&self.vertices[neighbors[0]], &self.vertices[neighbors[1]], V::Coordinate::one() / (V::Coordinate::one() + V::Coordinate::one()));
+                for (k, &vj) in neighbors.iter().enumerate().skip(2) {
+                    let t = V::Coordinate::one() / <V::Coordinate as num_traits::NumCast>::from(k + 1).unwrap_or_else(V::Coordinate::one);
+                    centroid = constructor.interpolate(&centroid, &self.vertices[vj], t);
+                }
+                let moved = constructor.interpolate(&self.vertices[vi], &centroid, factor);
+                let position = [moved.x(), moved.y()];
+                let inverts = incident[vi].iter().any(|ti| {
+                    let [c0, c1, c2] = self.triangles[*ti].map(|vj| if vj == vi { position } else { self.coords(vj) });
+                    (c1[0] - c0[0]) * (c2[1] - c0[1]) - (c2[0] - c0[0]) * (c1[1] - c0[1]) <= V::Coordinate::zero()
+                });
+                if !inverts {
+                    self.vertices[vi] = moved;
+                }
+            }
+        }
+    }
+
+    /// Reduce the mesh towards `target_triangles` triangles by collapsing interior edges, such as to generate levels of
+    /// detail for large filled regions.
+    ///
+    /// Each vertex is taken as a point at `height(vertex)` over the plane, and edges are collapsed in order of their
+    /// quadric error: the sum of squared distances from the kept vertex to the planes of the original triangles around
+    /// both ends, weighted by their areas. Collapses with an error above `max_error` are skipped. An interior vertex is
+    /// merged into one of its neighbors, which stays in place, so no vertex is created or moved: the boundary edges are
+    /// preserved exactly, and so is the area. The edges around a vertex which cannot be collapsed into any neighbor are
+    /// flipped until it can. With a constant `height`, every collapse is free and the shortest edges go first. The
+    /// vertices which are no longer used are removed at the end.
+    pub fn decimate<H: Fn(&V) -> V::Coordinate>(&mut self, target_triangles: usize, max_error: V::Coordinate, height: H) {
+        let boundary = self.boundary_vertices();
+        let mut incident = self.incident_triangles();
+        let mut alive = vec![true; self.triangles.len()];
+        let mut remaining = self.triangles.len();
+        let points: Vec<[V::Coordinate; 3]> = self.vertices.iter().map(|v| [v.x(), v.y(), height(v)]).collect();
+        let mut quadrics = vec![[V::Coordinate::zero(); 10]; self.vertices.len()];
+        for t in &self.triangles {
+            let quadric = plane_quadric(t.map(|vi| points[vi]));
+            for &vi in t {
+                quadrics[vi] = add_quadrics(&quadrics[vi], &quadric);
+            }
+        }
+
+        while remaining > target_triangles {
+            let mut candidates: Vec<(V::Coordinate, V::Coordinate, usize, usize)> = (0..self.vertices.len())
+                .filter(|vi| !boundary[*vi])
+                .flat_map(|removed| self.live_neighbors(&incident, &alive, removed).into_iter().map(move |kept| (removed, kept)))
+                .map(|(removed, kept)| {
+                    let error = quadric_error(&add_quadrics(&quadrics[removed], &quadrics[kept]), points[kept]);
+                    (error, self.edge_length_squared(removed, kept), removed, kept)
+                })
+                .filter(|(error, _, _, _)| *error <= max_error)
+                .collect();
+            candidates.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(cmp::Ordering::Equal).then(a.1.partial_cmp(&b.1).unwrap_or(cmp::Ordering::Equal)));
+
+            // Collapses only touch the triangles around the removed vertex, so those around its neighbors wait for the next round
+            let mut locked = vec![false; self.vertices.len()];
+            let mut collapsed = false;
+            let stuck: BTreeSet<usize> = candidates.iter().map(|(_, _, removed, _)| *removed).collect();
+            for (_, _, removed, kept) in candidates {
+                if remaining <= target_triangles {
+                    break;
+                }
+                if locked[removed] || locked[kept] {
+                    continue;
+                }
+                let (kept_neighbors, removed_neighbors) = (self.live_neighbors(&incident, &alive, kept), self.live_neighbors(&incident, &alive, removed));
+                if kept_neighbors.intersection(&removed_neighbors).count() != 2 {
+                    continue;
+                }
+                let inverts = incident[removed].iter()
+                    .filter(|ti| alive[**ti])
+                    .map(|ti| self.triangles[*ti])
+                    .filter(|t| !t.contains(&kept))
+                    .any(|t| {
+                        let [c0, c1, c2] = t.map(|vi| self.coords(if vi == removed { kept } else { vi }));
+                        (c1[0] - c0[0]) * (c2[1] - c0[1]) - (c2[0] - c0[0]) * (c1[1] - c0[1]) <= V::Coordinate::zero()
+                    });
+                if inverts {
+                    continue;
+                }
+
+                for ti in core::mem::take(&mut incident[removed]) {
+                    if !alive[ti] {
+                        continue;
+                    }
+                    if self.triangles[ti].contains(&kept) {
+                        alive[ti] = false;
+                        remaining -= 1;
+                    } else {
+                        self.triangles[ti] = self.triangles[ti].map(|vi| if vi == removed { kept } else { vi });
+                        incident[kept].push(ti);
+                    }
+                }
+                quadrics[kept] = add_quadrics(&quadrics[kept], &quadrics[removed]);
+                for vi in kept_neighbors.into_iter().chain(removed_neighbors).chain([kept, removed]) {
+                    locked[vi] = true;
+                }
+                collapsed = true;
+            }
+            // No fan from a neighbor may cover the triangles around a vertex, but one does after flipping its edges
+            if !collapsed && !self.flip_around(&mut incident, &alive, &stuck) {
+                break;
+            }
+        }
+
+        let mut ti = 0;
+        self.triangles.retain(|_| {
+            ti += 1;
+            alive[ti - 1]
+        });
+        self.remove_unused_vertices();
+    }
+
+    // Flip the edges around each of `vertices` until it is in only three triangles, or none of its edges can be flipped
+    fn flip_around(&mut self, incident: &mut [Vec<usize>], alive: &[bool], vertices: &BTreeSet<usize>) -> bool {
+        let area = |[p, q, r]: [[V::Coordinate; 2]; 3]| (q[0] - p[0]) * (r[1] - p[1]) - (r[0] - p[0]) * (q[1] - p[1]);
+        let mut flipped = false;
+        for &vi in vertices {
+            loop {
+                let around: Vec<usize> = incident[vi].iter().copied().filter(|ti| alive[*ti]).collect();
+                if around.len() <= 3 {
+                    break;
+                }
+                // The triangles `vi`, `k`, `a` and `k`, `vi`, `b` on either side of an edge become `a`, `vi`, `b` and `b`, `k`, `a`
+                let flip = around.iter().find_map(|&t0| {
+                    let t = self.triangles[t0];
+                    let i = t.iter().position(|vj| *vj == vi)?;
+                    let (k, a) = (t[(i + 1) % 3], t[(i + 2) % 3]);
+                    let (t1, b) = around.iter().find_map(|&t1| {
+                        let t = self.triangles[t1];
+                        (0..3).find(|j| t[*j] == k && t[(j + 1) % 3] == vi).map(|j| (t1, t[(j + 2) % 3]))
+                    })?;
+                    let convex = area([a, vi, b].map(|vj| self.coords(vj))) > V::Coordinate::zero()
+                        && area([b, k, a].map(|vj| self.coords(vj))) > V::Coordinate::zero();
+                    (convex && !self.live_neighbors(incident, alive, a).contains(&b)).then_some((t0, t1, k, a, b))
+                });
+                let Some((t0, t1, k, a, b)) = flip else { break };
+                self.triangles[t0] = [a, vi, b];
+                self.triangles[t1] = [b, k, a];
+                incident[k].retain(|ti| *ti != t0);
+                incident[vi].retain(|ti| *ti != t1);
+                incident[a].push(t1);
+                incident[b].push(t0);
+                flipped = true;
+            }
+        }
+        flipped
+    }
+
+    // Whether each vertex is on a boundary edge, used by only one triangle
+    fn boundary_vertices(&self) -> Vec<bool> {
+        let edges: HashSet<[usize; 2]> = self.triangles.iter().flat_map(|t| (0..3).map(move |k| [t[k], t[(k + 1) % 3]])).collect();
+        let mut boundary = vec![false; self.vertices.len()];
+        for &[vi0, vi1] in edges.iter().filter(|[vi0, vi1]| !edges.contains(&[*vi1, *vi0])) {
+            boundary[vi0] = true;
+            boundary[vi1] = true;
+        }
+        boundary
+    }
+
+    // The triangles around each vertex
+    fn incident_triangles(&self) -> Vec<Vec<usize>> {
+        let mut incident = vec![Vec::new(); self.vertices.len()];
+        for (ti, t) in self.triangles.iter().enumerate() {
+            for &vi in t {
+                incident[vi].push(ti);
+            }
+        }
+        incident
+    }
+
+    // The neighbors of a vertex in the triangles which are still `alive`
+    fn live_neighbors(&self, incident: &[Vec<usize>], alive: &[bool], vi: usize) -> BTreeSet<usize> {
+        incident[vi].iter().filter(|ti| alive[**ti]).flat_map(|ti| self.triangles[*ti]).filter(|vj| *vj != vi).collect()
+    }
+
+    fn coords(&self, vi: usize) -> [V::Coordinate; 2] {
+        [self.vertices[vi].x(), self.vertices[vi].y()]
+    }
+
+    // Collapse interior edges shorter than `low`, unless that would invert a triangle or create an edge longer than `high`
+    fn collapse_short_edges<VC: VertexConstructor<V>>(&mut self, constructor: &mut VC, low: V::Coordinate, high: V::Coordinate) {
+        let boundary = self.boundary_vertices();
+        let mut incident = self.incident_triangles();
+        let mut alive = vec![true; self.triangles.len()];
+        let mut locked = vec![false; self.vertices.len()];
+        let (low_squared, high_squared) = (low * low, high * high);
+
+        let mut short: Vec<(V::Coordinate, usize, usize)> = self.triangles.iter()
+            .flat_map(|t| (0..3).map(move |k| (t[k], t[(k + 1) % 3])))
+            .filter(|(vi0, vi1)| vi0 < vi1)
+            .map(|(vi0, vi1)| (self.edge_length_squared(vi0, vi1), vi0, vi1))
+            .filter(|(length_squared, _, _)| *length_squared < low_squared)
+            .collect();
+        short.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(cmp::Ordering::Equal));
+
+        for (_, a, b) in short {
+            if locked[a] || locked[b] || (boundary[a] && boundary[b]) {
+                continue;
+            }
+            // The removed vertex is interior, and the kept vertex moves to the midpoint unless it is on the boundary
+            let (kept, removed) = if boundary[b] { (b, a) } else { (a, b) };
+            let neighbors = |vi: usize| -> HashSet<usize> {
+                incident[vi].iter().filter(|ti| alive[**ti]).flat_map(|ti| self.triangles[*ti]).filter(|vj| *vj != vi).collect()
+            };
+            let (kept_neighbors, removed_neighbors) = (neighbors(kept), neighbors(removed));
+            // Collapsing an edge whose ends share more than the two opposite vertices would make the mesh non-manifold
+            if kept_neighbors.intersection(&removed_neighbors).count() != 2 {
+                continue;
+            }
+            let (ck, cr) = (self.coords(kept), self.coords(removed));
+            let two = V::Coordinate::one() + V::Coordinate::one();
+            let position = if boundary[kept] { ck } else { [(ck[0] + cr[0]) / two, (ck[1] + cr[1]) / two] };
+            let too_long = kept_neighbors.union(&removed_neighbors)
+                .filter(|vj| **vj != kept && **vj != removed)
+                .any(|&vj| {
+                    let c = self.coords(vj);
+                    (c[0] - position[0]).powi(2) + (c[1] - position[1]).powi(2) > high_squared
+                });
+            let inverts = incident[kept].iter().chain(&incident[removed])
+                .filter(|ti| alive[**ti])
+                .map(|ti| self.triangles[*ti])
+                .filter(|t| !(t.contains(&kept) && t.contains(&removed)))
+                .any(|t| {
+                    let [c0, c1, c2] = t.map(|vi| if vi == kept || vi == removed { position } else { self.coords(vi) });
+                    (c1[0] - c0[0]) * (c2[1] - c0[1]) - (c2[0] - c0[0]) * (c1[1] - c0[1]) <= V::Coordinate::zero()
+                });
+            if too_long || inverts {
+                continue;
+            }
+
+            if !boundary[kept] {
+                self.vertices[kept] = constructor.interpolate(&self.vertices[kept], &self.vertices[removed], V::Coordinate::one() / two);
+            }
+            for ti in core::mem::take(&mut incident[removed]) {
+                if !alive[ti] {
+                    continue;
+                }
+                if self.triangles[ti].contains(&kept) {
+                    alive[ti] = false;
+                } else {
+                    self.triangles[ti] = self.triangles[ti].map(|vi| if vi == removed { kept } else { vi });
+                    incident[kept].push(ti);
+                }
+            }
+            for vi in kept_neighbors.into_iter().chain(removed_neighbors).chain([kept, removed]) {
+                locked[vi] = true;
+            }
+        }
+
+        let mut ti = 0;
+        self.triangles.retain(|_| {
+            ti += 1;
+            alive[ti - 1]
+        });
+    }
+
+    /// Flip the edges shared by two triangles until every such pair is locally Delaunay, so no vertex is inside the
+    /// circumcircle of a neighboring triangle. This improves the shape of the triangles without moving or adding
+    /// vertices, and for a triangulation of polygons gives their constrained Delaunay triangulation.
+    ///
+    /// Returns `false` if flipping was stopped early, after one flip for each pair of vertices, which is only possible
+    /// when rounding of floating point coordinates makes the in-circle test inconsistent. The triangles are then valid,
+    /// but only approximately Delaunay.
+    pub fn flip_to_delaunay(&mut self) -> bool {
+        let positions = self.vertices.iter().map(|v| [v.x(), v.y()]).collect::<Vec<_>>();
+        flip_to_delaunay(&positions, &mut self.triangles)
+    }
+
+    // Bisect each edge in `edges` (in either direction), returning whether any were bisected
+    fn bisect_edges<VC: VertexConstructor<V>>(&mut self, constructor: &mut VC, edges: Vec<(usize, usize)>) -> bool {
+        let mut midpoints = HashMap::new();
+        for (vi0, vi1) in edges {
+            let key = (vi0.min(vi1), vi0.max(vi1));
+            if let Entry::Vacant(entry) = midpoints.entry(key) {
+                let half = V::Coordinate::one() / (V::Coordinate::one() + V::Coordinate::one());
+                let v = constructor.interpolate(&self.vertices[key.0], &self.vertices[key.1], half);
+                self.vertices.push(v);
+                entry.insert(self.vertices.len() - 1);
+            }
+        }
+        if midpoints.is_empty() {
+            return false;
+        }
+        self.split_edges(&midpoints);
+        true
+    }
+
+    /// Clip the mesh to the half-plane left of the directed line from `from` to `to` (counter-clockwise from the line's direction).
+    ///
+    /// Triangles crossing the line are cut, with new vertices created by `constructor` at the crossing points. Vertices created on
+    /// the same edge are shared between its triangles. Vertices which are no longer used are kept; see [Mesh::remove_unused_vertices].
+    pub fn clip_half_plane<VC: VertexConstructor<V>>(&mut self, constructor: &mut VC, from: [V::Coordinate; 2], to: [V::Coordinate; 2]) {
+        let sides: Vec<V::Coordinate> = self.vertices.iter()
+            .map(|v| (to[0] - from[0]) * (v.y() - from[1]) - (to[1] - from[1]) * (v.x() - from[0]))
+            .collect();
+        let kept = |vi: usize| sides[vi] >= V::Coordinate::zero();
+
+        let mut crossings = HashMap::new();
+        let triangles = core::mem::take(&mut self.triangles);
+        for t in triangles {
+            let inside = t.map(kept);
+            let count = inside.iter().filter(|inside| **inside).count();
+            if count == 3 {
+                self.triangles.push(t);
+                continue;
+            } else if count == 0 {
+                continue;
+            }
+
+            // Rotate the triangle so the lone vertex (the only kept one, or the only removed one) comes first
+            let k = (0..3).find(|&k| inside[k] == (count == 1)).unwrap();
+            let (a, b, c) = (t[k], t[(k + 1) % 3], t[(k + 2) % 3]);
+            let mut crossing = |vi0: usize, vi1: usize| {
+                // A vertex exactly on the line is the crossing point itself
+                if sides[vi0] == V::Coordinate::zero() {
+                    return vi0;
+                }
+                *crossings.entry((vi0.min(vi1), vi0.max(vi1))).or_insert_with(|| {
+                    let (vi0, vi1) = (vi0.min(vi1), vi0.max(vi1));
+                    let t = sides[vi0] / (sides[vi0] - sides[vi1]);
+                    let v = constructor.interpolate(&self.vertices[vi0], &self.vertices[vi1], t);
+                    self.vertices.push(v);
+                    self.vertices.len() - 1
+                })
+            };
+            let (mab, mca) = if count == 1 { (crossing(a, b), crossing(a, c)) } else { (crossing(b, a), crossing(c, a)) };
+            let pieces = if count == 1 { vec![[a, mab, mca]] } else { vec![[mab, b, c], [mab, c, mca]] };
+            // Pieces are degenerate where a kept vertex lies on the line
+            self.triangles.extend(pieces.into_iter().filter(|[v0, v1, v2]| v0 != v1 && v1 != v2 && v2 != v0));
+        }
+    }
+
+    /// Clip the mesh to the inside of a convex polygon with counter-clockwise vertices, by clipping to the half-plane of each edge.
+    ///
+    /// See [Mesh::clip_half_plane].
+    pub fn clip_convex<VC: VertexConstructor<V>>(&mut self, constructor: &mut VC, polygon: &[[V::Coordinate; 2]]) {
+        for (k, from) in polygon.iter().enumerate() {
+            self.clip_half_plane(constructor, *from, polygon[(k + 1) % polygon.len()]);
+        }
+    }
+
+    /// Lift the mesh into three dimensions for 2.5D scenes, with the z coordinate of each vertex given by `elevation`,
+    /// such as `|v| v[2]` for `[x, y, z]` vertices. Vertices created by post-processing get their elevation from the
+    /// [VertexConstructor], so [LinearConstructor](crate::LinearConstructor) interpolates it along edges.
+    pub fn elevate<F: Fn(&V) -> V::Coordinate>(&self, elevation: F) -> Solid<V::Coordinate> {
+        Solid {
+            positions: self.vertices.iter().map(|v| [v.x(), v.y(), elevation(v)]).collect(),
+            triangles: self.triangles.clone(),
+        }
+    }
+
+    /// Translate the mesh into the local space of a shape, with the origin at `anchor`, so that an engine can place
+    /// instances of it with a transform while the vertex data keeps small magnitudes.
+    ///
+    /// Only the positions are kept. For vertices with other attributes, [Anchor::locate] finds the anchor to subtract.
+    pub fn to_local(&self, anchor: Anchor<V::Coordinate>) -> LocalMesh<V::Coordinate>
+    where V::Coordinate: fmt::Debug {
+        let [ax, ay] = anchor.locate(self);
+        LocalMesh {
+            mesh: Mesh::new(self.vertices.iter().map(|v| [v.x() - ax, v.y() - ay]).collect(), self.triangles.clone()),
+            anchor: [ax, ay],
+        }
+    }
+
+    /// The outline of the area covered by the triangles, with counter-clockwise outer boundaries and clockwise holes.
+    ///
+    /// The triangles do not need to share vertices or edges and may overlap, so this also accepts a 'triangle soup'
+    /// from another tool, built with [Mesh::new]. Vertices in the middle of straight boundary edges are removed.
+    pub fn outline(&self) -> Contours<V::Coordinate>
+    where V::Coordinate: fmt::Debug {
+        let corners: Vec<[[V::Coordinate; 2]; 3]> = self.iter_triangles().filter_map(|t| {
+            let [c0, c1, c2] = t.map(|v| [v.x(), v.y()]);
+            let area = Self::area_of(t);
+            (area != V::Coordinate::zero()).then(|| if area > V::Coordinate::zero() { [c0, c1, c2] } else { [c0, c2, c1] })
+        }).collect();
+        let edges: Vec<[[V::Coordinate; 2]; 2]> = corners.iter().flat_map(|[c0, c1, c2]| [[*c0, *c1], [*c1, *c2], [*c2, *c0]]).collect();
+
+        // Each piece of an edge, with whether it runs in the direction of its key
+        let mut pieces: Vec<([[V::Coordinate; 2]; 2], bool)> = intersection::split_segments(&edges).into_iter().map(|piece| {
+            let key = boolean::segment_key(piece.segment);
+            (key, key == piece.segment)
+        }).collect();
+        pieces.sort_by(|(a, _), (b, _)| boolean::cmp_key(a, b));
+
+        let bvh = Bvh::new(self);
+        let two = V::Coordinate::one() + V::Coordinate::one();
+        let left = |a, b, c| <DefaultKernel as GeometryKernel<V::Coordinate>>::orientation(&a, &b, &c) == Orientation::Left;
+        let mut kept = Vec::new();
+        let mut start = 0;
+        while start < pieces.len() {
+            let key = pieces[start].0;
+            let end = start + pieces[start..].iter().take_while(|(k, _)| boolean::cmp_key(k, &key) == cmp::Ordering::Equal).count();
+            let forward = pieces[start..end].iter().filter(|(_, forward)| *forward).count();
+            let backward = end - start - forward;
+            start = end;
+
+            // Triangles with the piece on an edge cover one side of it, and triangles containing it cover both
+            if (forward == 0) == (backward == 0) {
+                continue;
+            }
+            let [c0, c1] = key;
+            let midpoint = [(c0[0] + c1[0]) / two, (c0[1] + c1[1]) / two];
+            let covered = bvh.query_aabb(self, midpoint, midpoint).into_iter().any(|ti| {
+                let [t0, t1, t2] = self.triangles[ti].map(|vi| self.coords(vi));
+                let t = if left(t0, t1, t2) { [t0, t1, t2] } else { [t0, t2, t1] };
+                (0..3).all(|k| left(t[k], t[(k + 1) % 3], midpoint))
+            });
+            if !covered {
+                kept.push(if forward > 0 { [c0, c1] } else { [c1, c0] });
+            }
+        }
+        boolean::assemble_rings(kept)
+    }
+
+    /// Triangulate the [outline](Mesh::outline) of the mesh again, such as to clean up a triangle soup from another
+    /// tool before refining it with [Mesh::refine] or [Mesh::remesh]. Only the positions of the vertices are kept.
+    pub fn retriangulate(&self) -> Result<Mesh<[V::Coordinate; 2]>, TriangulationError<Infallible>>
+    where V::Coordinate: fmt::Debug {
+        Mesh::from_polygons(&self.outline())
+    }
+
+    /// Perform the [BooleanOp] `op` on the areas covered by two meshes. Only the positions of the vertices are kept.
+    ///
+    /// The triangles of each operand are clipped against the other directly. The boundary of each operand is taken from
+    /// the adjacency of its triangles (the edges without a neighbouring triangle), and the triangles touched by the
+    /// boundary of the other operand are found with a [Bvh]. Every other triangle lies entirely inside or outside the
+    /// other operand, and is kept as it is or dropped. Only the area of the touched triangles, with any triangles they
+    /// enclose at a single vertex, is triangulated again, keeping the vertices of the triangles around it. The result
+    /// is as [watertight](Mesh::check_watertight) as the operands, without T-junctions, and can be used as an operand
+    /// again. This needs meshes which share vertices between adjacent triangles, like those built by this crate; a
+    /// triangle soup should be [retriangulated](Mesh::retriangulate) first.
+    ///
+    /// The vertices of the result are sorted, and vertices at the same position are merged.
+    pub fn boolean<W: Vertex<Coordinate=V::Coordinate>>(&self, other: &Mesh<W>, op: BooleanOp) -> Result<Mesh<[V::Coordinate; 2]>, TriangulationError<Infallible>>
+    where V::Coordinate: fmt::Debug {
+        let (bvh_a, bvh_b) = (Bvh::new(self), Bvh::new(other));
+        let (mut crossed_a, crossing_b) = self.touched_triangles(&bvh_a, other.boundary_edges(|_| true));
+        let (mut crossed_b, crossing_a) = other.touched_triangles(&bvh_b, self.boundary_edges(|_| true));
+        self.grow_region(&mut crossed_a);
+        other.grow_region(&mut crossed_b);
+        let inside_a = |point| bvh_a.query_point(self, point).is_some();
+        let inside_b = |point| bvh_b.query_point(other, point).is_some();
+
+        // The triangles not touched by the other boundary, by whether their centroid is inside the other operand
+        let mut triangles: Vec<[[V::Coordinate; 2]; 3]> = Vec::new();
+        let three = V::Coordinate::one() + V::Coordinate::one() + V::Coordinate::one();
+        let centroid = |[c0, c1, c2]: [[V::Coordinate; 2]; 3]| [(c0[0] + c1[0] + c2[0]) / three, (c0[1] + c1[1] + c2[1]) / three];
+        for (ti, t) in self.triangles.iter().enumerate() {
+            let corners = t.map(|vi| self.coords(vi));
+            if crossed_a[ti] || Self::area_of(t.map(|vi| &self.vertices[vi])) == V::Coordinate::zero() {
+                continue;
+            }
+            let inside = inside_b(centroid(corners));
+            if match op { BooleanOp::Union => true, BooleanOp::Intersection => inside, BooleanOp::Difference | BooleanOp::Xor => !inside } {
+                triangles.push(corners);
+            }
+        }
+        if matches!(op, BooleanOp::Union | BooleanOp::Xor) {
+            for (ti, t) in other.triangles.iter().enumerate() {
+                let corners = t.map(|vi| other.coords(vi));
+                if !crossed_b[ti] && Mesh::<W>::area_of(t.map(|vi| &other.vertices[vi])) != V::Coordinate::zero() && !inside_a(centroid(corners)) {
+                    triangles.push(corners);
+                }
+            }
+        }
+
+        // The touched triangles of each operand form a region, which is overlaid with the boundary of the other operand
+        // passing through it. A union keeps both sides of that boundary, so its crossings with the boundary of the
+        // region are vertices of the result
+        let region_a = self.boundary_edges(|ti| crossed_a[ti]);
+        let in_region_a = |point| bvh_a.query_point(self, point).is_some_and(|ti| crossed_a[ti]);
+        let ops_a: &[BooleanOp] = match op {
+            BooleanOp::Union => &[BooleanOp::Intersection, BooleanOp::Difference],
+            BooleanOp::Intersection => &[BooleanOp::Intersection],
+            BooleanOp::Difference | BooleanOp::Xor => &[BooleanOp::Difference],
+        };
+        let mut pieces: Vec<Vec<Segment<V::Coordinate>>> = ops_a.iter().map(|op_a| {
+            boolean::overlay_pieces(region_a.clone(), crossing_b.clone(), |shape, point| if shape == 0 { in_region_a(point) } else { inside_b(point) }, *op_a)
+        }).collect();
+        if matches!(op, BooleanOp::Union | BooleanOp::Xor) {
+            let region_b = other.boundary_edges(|ti| crossed_b[ti]);
+            let in_region_b = |point| bvh_b.query_point(other, point).is_some_and(|ti| crossed_b[ti]);
+            pieces.push(boolean::overlay_pieces(region_b, crossing_a, |shape, point| if shape == 0 { in_region_b(point) } else { inside_a(point) }, BooleanOp::Difference));
+        }
+        for pieces in pieces {
+            // Each part is triangulated on its own, as parts of the result may touch at single vertices
+            for part in boolean::group_rings(boolean::link_rings(pieces)) {
+                // Vertices in the middle of straight edges are kept for the triangles around the part, and flipping
+                // removes the triangles without area which the triangulation makes along them
+                let mut mesh = Mesh::<[V::Coordinate; 2]>::from_polygons(&part)?;
+                mesh.flip_to_delaunay();
+                triangles.extend(mesh.iter_triangles().map(|t| t.map(|c| *c)));
+            }
+        }
+
+        let compare = |a: &[V::Coordinate; 2], b: &[V::Coordinate; 2]| <DefaultKernel as GeometryKernel<V::Coordinate>>::compare_y(a, b);
+        let mut vertices: Vec<[V::Coordinate; 2]> = triangles.iter().flatten().copied().collect();
+        vertices.sort_by(compare);
+        vertices.dedup();
+        let triangles = triangles.into_iter()
+            .map(|t| t.map(|c| vertices.binary_search_by(|v| compare(v, &c)).unwrap()))
+            .collect();
+        Ok(Mesh::new(vertices, triangles))
+    }
+
+    // The edges of the triangles selected by `cells` used by only one of them, directed with that triangle on their left
+    fn boundary_edges(&self, cells: impl Fn(usize) -> bool) -> Vec<[[V::Coordinate; 2]; 2]> {
+        self.boundary_indices(cells).into_iter().map(|e| e.map(|vi| self.coords(vi))).collect()
+    }
+
+    // [Mesh::boundary_edges] as pairs of vertex indices
+    fn boundary_indices(&self, cells: impl Fn(usize) -> bool) -> Vec<[usize; 2]> {
+        let edges: HashSet<[usize; 2]> = self.triangles.iter().enumerate().filter(|(ti, _)| cells(*ti)).filter_map(|(_, t)| {
+            let area = Self::area_of(t.map(|vi| &self.vertices[vi]));
+            (area != V::Coordinate::zero()).then(|| if area > V::Coordinate::zero() { *t } else { [t[0], t[2], t[1]] })
+        }).flat_map(|t| [[t[0], t[1]], [t[1], t[2]], [t[2], t[0]]]).collect();
+        let mut boundary: Vec<[usize; 2]> = edges.iter().filter(|[vi0, vi1]| !edges.contains(&[*vi1, *vi0])).copied().collect();
+        // Sorted for a result which does not depend on the hash order
+        boundary.sort_unstable();
+        boundary
+    }
+
+    // Add the triangles around each vertex where the boundary of the triangles selected by `cells` touches itself,
+    // until it does not, so that the area they cover has no holes touching its outer boundary and can be triangulated
+    fn grow_region(&self, cells: &mut [bool]) {
+        loop {
+            let mut outgoing = vec![0usize; self.vertices.len()];
+            for [vi0, _] in self.boundary_indices(|ti| cells[ti]) {
+                outgoing[vi0] += 1;
+            }
+            let mut grown = false;
+            for (ti, t) in self.triangles.iter().enumerate() {
+                if !cells[ti] && t.iter().any(|vi| outgoing[*vi] > 1) {
+                    cells[ti] = true;
+                    grown = true;
+                }
+            }
+            if !grown {
+                return;
+            }
+        }
+    }
+
+    // Which triangles are touched by any of `edges`, including at a single point, and which of `edges` touch any triangle
+    fn touched_triangles(&self, bvh: &Bvh<V::Coordinate>, edges: Vec<Segment<V::Coordinate>>) -> (Vec<bool>, Vec<Segment<V::Coordinate>>) {
+        let right = |a, b, c| <DefaultKernel as GeometryKernel<V::Coordinate>>::orientation(&a, &b, &c) == Orientation::Right;
+        let mut touched = vec![false; self.triangles.len()];
+        let mut touching = Vec::new();
+        for edge @ [c0, c1] in edges {
+            let min = [c0[0].min(c1[0]), c0[1].min(c1[1])];
+            let max = [c0[0].max(c1[0]), c0[1].max(c1[1])];
+            let mut any = false;
+            for ti in bvh.query_aabb(self, min, max) {
+                let [t0, t1, t2] = self.triangles[ti].map(|vi| self.coords(vi));
+                let t = if right(t0, t1, t2) { [t0, t2, t1] } else { [t0, t1, t2] };
+                // The edge misses the triangle if the line through one of their edges separates them
+                let separated = (0..3).any(|k| right(t[k], t[(k + 1) % 3], c0) && right(t[k], t[(k + 1) % 3], c1))
+                    || t.iter().all(|c| right(c0, c1, *c))
+                    || t.iter().all(|c| right(c1, c0, *c));
+                if !separated {
+                    touched[ti] = true;
+                    any = true;
+                }
+            }
+            if any {
+                touching.push(edge);
+            }
+        }
+        (touched, touching)
+    }
+
+    /// The dual graph of the mesh, with a node for each triangle and an edge between triangles which share an edge,
+    /// weighted by the length of the shared edge.
+    pub fn dual_graph(&self) -> DualGraph<V::Coordinate> {
+        let mut sides: HashMap<[usize; 2], Vec<usize>> = HashMap::new();
+        for (ti, t) in self.triangles.iter().enumerate() {
+            for k in 0..3 {
+                let (vi0, vi1) = (t[k], t[(k + 1) % 3]);
+                sides.entry([vi0.min(vi1), vi0.max(vi1)]).or_default().push(ti);
+            }
+        }
+        let mut adjacent: Vec<Vec<(usize, V::Coordinate)>> = vec![Vec::new(); self.triangles.len()];
+        for ([vi0, vi1], triangles) in sides {
+            let length = self.edge_length_squared(vi0, vi1).sqrt();
+            for (i, &ti) in triangles.iter().enumerate() {
+                for &tj in &triangles[i + 1..] {
+                    adjacent[ti].push((tj, length));
+                    adjacent[tj].push((ti, length));
+                }
+            }
+        }
+
+        let mut graph = DualGraph { offsets: vec![0], neighbors: Vec::new(), weights: Vec::new() };
+        for mut row in adjacent {
+            row.sort_by_key(|(tj, _)| *tj);
+            graph.neighbors.extend(row.iter().map(|(tj, _)| *tj));
+            graph.weights.extend(row.iter().map(|(_, length)| *length));
+            graph.offsets.push(graph.neighbors.len());
+        }
+        graph
+    }
+
+    /// The connected components of the mesh, with the component of each triangle, found by joining the triangles which
+    /// share an edge in O(*n* α(*n*)) time.
+    ///
+    /// See the [components](crate::components) module.
+    pub fn components(&self) -> Components {
+        let mut sides: HashMap<[usize; 2], usize> = HashMap::new();
+        let mut sets = UnionFind::new(self.triangles.len());
+        for (ti, t) in self.triangles.iter().enumerate() {
+            for k in 0..3 {
+                let (vi0, vi1) = (t[k], t[(k + 1) % 3]);
+                match sides.entry([vi0.min(vi1), vi0.max(vi1)]) {
+                    Entry::Occupied(entry) => sets.union(*entry.get(), ti),
+                    Entry::Vacant(entry) => {
+                        entry.insert(ti);
+                    }
+                }
+            }
+        }
+        Components::from_union_find(&mut sets, |_| true)
+    }
+
+    /// Split the triangles into `chunks` contiguous chunks of similar size, such as to distribute a large mesh across
+    /// render tiles or worker threads, and return the chunk of each triangle.
+    ///
+    /// Chunks are grown one at a time over the [dual graph](Mesh::dual_graph) from the leftmost triangle which is not yet
+    /// in a chunk, always adding the leftmost triangle next to the chunk, until the chunk has its share of what is left by
+    /// `weight`. A chunk only continues in another part of the mesh when the part it started in runs out.
+    pub fn partition(&self, chunks: usize, weight: PartitionWeight) -> Vec<usize> {
+        let graph = self.dual_graph();
+        let weights: Vec<V::Coordinate> = match weight {
+            PartitionWeight::Area => self.iter_triangles().map(|t| Self::area_of(t).abs()).collect(),
+            PartitionWeight::Count => vec![V::Coordinate::one(); self.triangles.len()],
+        };
+        // Triangles ranked by their leftmost corner, then their lowest
+        let corners: Vec<[V::Coordinate; 2]> = self.triangles.iter().map(|t| {
+            t.iter().map(|vi| self.coords(*vi)).fold([V::Coordinate::max_value(); 2], |min, c| [min[0].min(c[0]), min[1].min(c[1])])
+        }).collect();
+        let mut order: Vec<usize> = (0..self.triangles.len()).collect();
+        order.sort_by(|a, b| corners[*a].partial_cmp(&corners[*b]).unwrap_or(cmp::Ordering::Equal));
+        let mut rank = vec![0; order.len()];
+        for (r, ti) in order.iter().enumerate() {
+            rank[*ti] = r;
+        }
+
+        let chunks = chunks.max(1);
+        let mut chunk_of = vec![usize::MAX; self.triangles.len()];
+        let mut remaining = weights.iter().fold(V::Coordinate::zero(), |sum, w| sum + *w);
+        let mut next_seed = 0;
+        for chunk in 0..chunks {
+            let share = <V::Coordinate as num_traits::NumCast>::from(chunks - chunk).unwrap_or_else(V::Coordinate::one);
+            let target = remaining / share;
+            let mut filled = V::Coordinate::zero();
+            let mut frontier: BTreeSet<usize> = BTreeSet::new();
+            while chunk + 1 == chunks || filled < target {
+                let ti = match frontier.pop_first() {
+                    Some(r) => order[r],
+                    None => {
+                        while next_seed < order.len() && chunk_of[order[next_seed]] != usize::MAX {
+                            next_seed += 1;
+                        }
+                        match order.get(next_seed) {
+                            Some(ti) => *ti,
+                            None => break,
+                        }
+                    }
+                };
+                chunk_of[ti] = chunk;
+                filled = filled + weights[ti];
+                frontier.extend(graph.neighbors(ti).filter(|(tj, _)| chunk_of[*tj] == usize::MAX).map(|(tj, _)| rank[tj]));
+            }
+            remaining = remaining - filled;
+        }
+        chunk_of
+    }
+
+    /// This mesh with its vertices converted to another [FromCoords] vertex type by their positions, such as to get the
+    /// output of [boolean](Mesh::boolean) or [stroke](crate::stroke::stroke) in the vertex type of the caller
+    pub fn convert<W: FromCoords<Coordinate=V::Coordinate>>(self) -> Mesh<W> {
+        let vertices = self.vertices.into_iter().map(|v| W::from_coords(v.into_coords())).collect();
+        Mesh { vertices, triangles: self.triangles }
+    }
+
+    /// Move the vertices and triangles of `other` to the end of this mesh, such as to draw strokes and fills with one buffer
+    pub fn append(&mut self, other: Mesh<V>) {
+        let offset = self.vertices.len();
+        self.vertices.extend(other.vertices);
+        self.triangles.extend(other.triangles.into_iter().map(|t| t.map(|vi| vi + offset)));
+    }
+
+    /// Remove the vertices which are not used by any triangle, preserving the order of the remaining vertices
+    pub fn remove_unused_vertices(&mut self) {
+        let mut used = vec![false; self.vertices.len()];
+        for t in self.triangles.iter() {
+            for vi in t {
+                used[*vi] = true;
+            }
+        }
+        let mut remap = vec![0; self.vertices.len()];
+        let mut next = 0;
+        for (vi, used) in used.iter().enumerate() {
+            if *used {
+                remap[vi] = next;
+                next += 1;
+            }
+        }
+        let mut vi = 0;
+        self.vertices.retain(|_| {
+            vi += 1;
+            used[vi - 1]
+        });
+        for t in self.triangles.iter_mut() {
+            *t = t.map(|vi| remap[vi]);
+        }
+    }
+
+    /// Check that the mesh is watertight: every edge inside the mesh is shared by exactly two consistently oriented triangles,
+    /// and the boundary edges (used by only one triangle) form closed loops.
+    ///
+    /// Every violation is reported, with the indices of the vertices and triangles involved. An edge used by more than two
+    /// triangles is only reported as non-manifold, whatever their directions.
+    pub fn check_watertight(&self) -> Result<(), Vec<WatertightViolation>> {
+        let mut violations = Vec::new();
+        let mut edges: HashMap<[usize; 2], Vec<usize>> = HashMap::new();
+        for (ti, t) in self.triangles.iter().enumerate() {
+            if t.iter().any(|vi| *vi >= self.vertices.len()) {
+                violations.push(WatertightViolation::InvalidIndex { triangle: ti });
+            } else if t[0] == t[1] || t[1] == t[2] || t[2] == t[0] {
+                violations.push(WatertightViolation::DegenerateTriangle { triangle: ti });
+            } else {
+                for k in 0..3 {
+                    edges.entry([t[k], t[(k + 1) % 3]]).or_default().push(ti);
+                }
+            }
+        }
+
+        let mut boundary_balance: HashMap<usize, isize> = HashMap::new();
+        for (edge, triangles) in edges.iter() {
+            let [vi0, vi1] = *edge;
+            let reverse = edges.get(&[vi1, vi0]);
+            // Each undirected edge is classified once, from its lower vertex if it is used in both directions
+            match (triangles.len(), reverse) {
+                (1, None) => {
+                    *boundary_balance.entry(vi0).or_default() += 1;
+                    *boundary_balance.entry(vi1).or_default() -= 1;
+                }
+                (2, None) => violations.push(WatertightViolation::InconsistentOrientation { edge: *edge, triangles: triangles.clone() }),
+                (len, None) if len > 2 => violations.push(WatertightViolation::NonManifoldEdge { edge: *edge, triangles: triangles.clone() }),
+                (len, Some(reverse)) if vi0 < vi1 && len + reverse.len() > 2 => {
+                    let mut triangles = triangles.clone();
+                    triangles.extend(reverse);
+                    triangles.sort_unstable();
+                    violations.push(WatertightViolation::NonManifoldEdge { edge: *edge, triangles });
+                }
+                _ => (),
+            }
+        }
+        violations.extend(boundary_balance.into_iter()
+            .filter(|(_, balance)| *balance != 0)
+            .map(|(vertex, _)| WatertightViolation::OpenBoundary { vertex }));
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            violations.sort_by_key(WatertightViolation::sort_key);
+            Err(violations)
+        }
+    }
+
+    // Replace every triangle with an edge in `midpoints` by the triangles formed with those midpoints
+    fn split_edges(&mut self, midpoints: &HashMap<(usize, usize), usize>) {
+        let triangles = core::mem::take(&mut self.triangles);
+        for t in triangles {
+            let m = [0, 1, 2].map(|k| {
+                let (vi0, vi1) = (t[k], t[(k + 1) % 3]);
+                midpoints.get(&(vi0.min(vi1), vi0.max(vi1))).copied()
+            });
+            match m {
+                [None, None, None] => self.triangles.push(t),
+                [Some(m0), Some(m1), Some(m2)] => {
+                    self.triangles.extend([[t[0], m0, m2], [m0, t[1], m1], [m2, m1, t[2]], [m0, m1, m2]]);
+                }
+                _ => {
+                    let marked = m.iter().filter(|m| m.is_some()).count();
+                    if marked == 1 {
+                        let k = m.iter().position(Option::is_some).unwrap();
+                        let (a, b, c, mab) = (t[k], t[(k + 1) % 3], t[(k + 2) % 3], m[k].unwrap());
+                        self.triangles.extend([[a, mab, c], [mab, b, c]]);
+                    } else {
+                        let k = m.iter().position(Option::is_none).unwrap();
+                        let (a, b, c) = (t[k], t[(k + 1) % 3], t[(k + 2) % 3]);
+                        let (mbc, mca) = (m[(k + 1) % 3].unwrap(), m[(k + 2) % 3].unwrap());
+                        self.triangles.extend([[a, b, mbc], [a, mbc, mca], [mca, mbc, c]]);
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Flip edges shared by two counter-clockwise triangles until every pair is locally Delaunay.
+//
+// Each flip queues the four edges around the new diagonal, which are the only ones it can make illegal. With exact
+// arithmetic every flip raises the triangulation towards the Delaunay one, so there are at most one flip for each pair
+// of vertices and the result is the (constrained) Delaunay triangulation. With floating point coordinates, rounding in
+// the in-circle test near four cocircular vertices can undo a flip, so the number of flips is capped at that bound;
+// returns `false` if the cap was hit, when the triangles are only approximately Delaunay.
+pub(crate) fn flip_to_delaunay<C: Scalar>(positions: &[[C; 2]], triangles: &mut [[usize; 3]]) -> bool {
+    // The triangle on the left of each directed edge
+    let mut edges: HashMap<[usize; 2], usize> = HashMap::new();
+    for (ti, t) in triangles.iter().enumerate() {
+        for k in 0..3 {
+            edges.insert([t[k], t[(k + 1) % 3]], ti);
+        }
+    }
+    // Queued in the order of the triangles, so the result does not depend on the order of the map
+    let mut queue: Vec<[usize; 2]> = triangles.iter().rev()
+        .flat_map(|t| [[t[2], t[0]], [t[1], t[2]], [t[0], t[1]]])
+        .filter(|[a, b]| a < b && edges.contains_key(&[*b, *a]))
+        .collect();
+
+    let area = |p: &[C; 2], q: &[C; 2], r: &[C; 2]| {
+        let [[px, py], [qx, qy], [rx, ry]] = [p, q, r].map(|c| c.clone());
+        (qx - px.clone()) * (ry - py.clone()) - (rx - px) * (qy - py)
+    };
+    let third = |t: &[usize; 3], a: usize, b: usize| t.iter().copied().find(|vi| *vi != a && *vi != b);
+    let mut flips_left = positions.len().saturating_mul(positions.len().saturating_sub(1)) / 2;
+    while let Some([a, b]) = queue.pop() {
+        // The edge may have been flipped away since it was queued
+        let (Some(&t0), Some(&t1)) = (edges.get(&[a, b]), edges.get(&[b, a])) else { continue };
+        let (Some(c), Some(d)) = (third(&triangles[t0], a, b), third(&triangles[t1], a, b)) else { continue };
+        let [ca, cb, cc, cd] = [a, b, c, d].map(|vi| &positions[vi]);
+        // The flipped triangles must both be counter-clockwise, which holds when the quadrilateral is convex
+        if !(in_circle(ca, cb, cc, cd) > C::zero() && area(ca, cd, cc) > C::zero() && area(cd, cb, cc) > C::zero()) {
+            continue;
+        }
+        if flips_left == 0 {
+            return false;
+        }
+        flips_left -= 1;
+
+        triangles[t0] = [a, d, c];
+        triangles[t1] = [d, b, c];
+        edges.remove(&[a, b]);
+        edges.remove(&[b, a]);
+        for (edge, ti) in [([a, d], t0), ([d, c], t0), ([c, a], t0), ([d, b], t1), ([b, c], t1), ([c, d], t1)] {
+            edges.insert(edge, ti);
+        }
+        for [p, q] in [[a, d], [d, b], [b, c], [c, a]] {
+            if edges.contains_key(&[q, p]) {
+                queue.push([p.min(q), p.max(q)]);
+            }
+        }
+    }
+    true
+}
+
+// The vertices and `u32` triangle indices of [triangulate_simple]
+type SimpleMesh = (Vec<[f32; 2]>, Vec<u32>);
+
+/// Triangulate `rings` in one call, returning the vertices and the indices of the corners of each counter-clockwise
+/// triangle, three at a time, ready to draw.
+///
+/// This covers the most common case without any of the crate's traits: typically the first ring is an outer boundary
+/// and the rest are holes in it, though any rings may be given, filled by the even-odd rule. The vertices are those of
+/// the rings, in order.
+///
+/// ```
+/// let rings = vec![
+///     vec![[0f32, 0.], [3., 0.], [3., 3.], [0., 3.]],
+///     vec![[1., 1.], [1., 2.], [2., 2.], [2., 1.]],
+/// ];
+/// let (vertices, indices) = triangulate::triangulate_simple(&rings).expect("Triangulation failed");
+/// assert_eq!(vertices.len(), 8);
+/// assert_eq!(indices.len(), 8 * 3);
+/// ```
+///
+/// Fails with [IndexBufferError::IndexOverflow](formats::IndexBufferError::IndexOverflow) if there are more than
+/// `u32::MAX` vertices.
+pub fn triangulate_simple(rings: &[Vec<[f32; 2]>]) -> Result<SimpleMesh, TriangulationError<formats::IndexBufferError>> {
+    let mesh = Mesh::from_polygons(&rings).map_err(TriangulationError::with_fan_builder_error)?;
+    if u32::try_from(mesh.vertices.len()).is_err() {
+        return Err(TriangulationError::FanBuilder(formats::IndexBufferError::IndexOverflow));
+    }
+    let indices = mesh.triangles.iter().flatten().map(|vi| *vi as u32).collect();
+    Ok((mesh.vertices, indices))
+}
+
+// Positive when `d` is inside the circle through the counter-clockwise triangle `a`, `b`, `c`
+fn in_circle<C: Scalar>(a: &[C; 2], b: &[C; 2], c: &[C; 2], d: &[C; 2]) -> C {
+    let [[ax, ay], [bx, by], [cx, cy]] = [a, b, c].map(|p| [p[0].clone() - d[0].clone(), p[1].clone() - d[1].clone()]);
+    let [la, lb, lc] = [[&ax, &ay], [&bx, &by], [&cx, &cy]].map(|[x, y]| x.clone() * x.clone() + y.clone() * y.clone());
+    let det = |p: &C, q: &C, r: &C, s: &C| p.clone() * q.clone() - r.clone() * s.clone();
+    ax * det(&by, &lc, &lb, &cy) - ay * det(&bx, &lc, &lb, &cx) + la * det(&bx, &cy, &by, &cx)
+}
+
+// The quadric of the plane through a triangle, as the upper half of a symmetric 4x4 matrix, weighted by its area
+fn plane_quadric<C: Real>([p0, p1, p2]: [[C; 3]; 3]) -> [C; 10] {
+    let (u, v) = ([p1[0] - p0[0], p1[1] - p0[1], p1[2] - p0[2]], [p2[0] - p0[0], p2[1] - p0[1], p2[2] - p0[2]]);
+    let n = [u[1] * v[2] - u[2] * v[1], u[2] * v[0] - u[0] * v[2], u[0] * v[1] - u[1] * v[0]];
+    let length = (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt();
+    if length <= C::zero() {
+        return [C::zero(); 10];
+    }
+    let [a, b, c] = n.map(|x| x / length);
+    let d = -(a * p0[0] + b * p0[1] + c * p0[2]);
+    let weight = length / (C::one() + C::one());
+    [a * a, a * b, a * c, a * d, b * b, b * c, b * d, c * c, c * d, d * d].map(|x| x * weight)
+}
+
+fn add_quadrics<C: Real>(q: &[C; 10], r: &[C; 10]) -> [C; 10] {
+    let mut sum = *q;
+    for (s, x) in sum.iter_mut().zip(r) {
+        *s = *s + *x;
+    }
+    sum
+}
+
+// The sum of squared distances from `p` to the planes of a quadric
+fn quadric_error<C: Real>(q: &[C; 10], [x, y, z]: [C; 3]) -> C {
+    let two = C::one() + C::one();
+    q[0] * x * x + q[4] * y * y + q[7] * z * z + q[9]
+        + two * (q[1] * x * y + q[2] * x * z + q[3] * x + q[5] * y * z + q[6] * y + q[8] * z)
+}
+
+/// A violation found by [Mesh::check_watertight]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum WatertightViolation {
+    /// A triangle refers to a vertex which does not exist
+    InvalidIndex {
+        /// The index of the triangle
+        triangle: usize,
+    },
+    /// A triangle uses the same vertex more than once
+    DegenerateTriangle {
+        /// The index of the triangle
+        triangle: usize,
+    },
+    /// More than two triangles share an edge
+    NonManifoldEdge {
+        /// The vertices of the edge
+        edge: [usize; 2],
+        /// The triangles sharing the edge
+        triangles: Vec<usize>,
+    },
+    /// Triangles sharing an edge traverse it in the same direction, so their windings disagree
+    InconsistentOrientation {
+        /// The vertices of the edge, in the direction the triangles traverse it
+        edge: [usize; 2],
+        /// The triangles traversing the edge in that direction
+        triangles: Vec<usize>,
+    },
+    /// The boundary edges at a vertex do not continue a closed loop
+    OpenBoundary {
+        /// The index of the vertex
+        vertex: usize,
+    },
+}
+
+impl WatertightViolation {
+    fn sort_key(&self) -> (usize, usize, usize) {
+        match self {
+            Self::InvalidIndex { triangle } => (0, *triangle, 0),
+            Self::DegenerateTriangle { triangle } => (1, *triangle, 0),
+            Self::NonManifoldEdge { edge, .. } => (2, edge[0], edge[1]),
+            Self::InconsistentOrientation { edge, .. } => (3, edge[0], edge[1]),
+            Self::OpenBoundary { vertex } => (4, *vertex, 0),
+        }
+    }
+}
+
+impl fmt::Display for WatertightViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidIndex { triangle } => write!(f, "Triangle {} refers to a vertex which does not exist", triangle),
+            Self::DegenerateTriangle { triangle } => write!(f, "Triangle {} uses a vertex more than once", triangle),
+            Self::NonManifoldEdge { edge, triangles } => write!(f, "Edge {}-{} is shared by triangles {:?}", edge[0], edge[1], triangles),
+            Self::InconsistentOrientation { edge, triangles } => write!(f, "Edge {}-{} is traversed in the same direction by triangles {:?}", edge[0], edge[1], triangles),
+            Self::OpenBoundary { vertex } => write!(f, "The boundary is not closed at vertex {}", vertex),
+        }
+    }
+}
+
+/// The point which becomes the origin of a shape's local space, see [Mesh::to_local]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Anchor<C: Real> {
+    /// The given point
+    Point([C; 2]),
+    /// The minimum corner of the bounding box of the vertices
+    Min,
+    /// The center of the bounding box of the vertices
+    Center,
+    /// The centroid of the area covered by the triangles
+    Centroid,
+}
+
+impl<C: Real> Anchor<C> {
+    /// The position of the anchor in `mesh`. An empty mesh is anchored at the origin
+    pub fn locate<V: Vertex<Coordinate=C>>(&self, mesh: &Mesh<V>) -> [C; 2] {
+        let two = C::one() + C::one();
+        let positions: Vec<[C; 2]> = mesh.vertices.iter().map(|v| [v.x(), v.y()]).collect();
+        let bounds = math::bounding_box(&positions).unwrap_or([[C::zero(); 2]; 2]);
+        match self {
+            Anchor::Point(point) => *point,
+            Anchor::Min => bounds[0],
+            Anchor::Center => [(bounds[0][0] + bounds[1][0]) / two, (bounds[0][1] + bounds[1][1]) / two],
+            Anchor::Centroid => {
+                let three = two + C::one();
+                let (mut area, mut sum) = (C::zero(), [C::zero(); 2]);
+                for t in mesh.iter_triangles() {
+                    let a = Mesh::area_of(t);
+                    area = area + a;
+                    for k in 0..2 {
+                        let [c0, c1, c2] = t.map(|v| [v.x(), v.y()][k]);
+                        sum[k] = sum[k] + a * (c0 + c1 + c2) / three;
+                    }
+                }
+                if area == C::zero() { Anchor::Center.locate(mesh) } else { sum.map(|c| c / area) }
+            }
+        }
+    }
+}
+
+/// A [Mesh] of positions relative to an anchor, created by [Mesh::to_local]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalMesh<C: Real + fmt::Debug> {
+    /// The mesh, in local space
+    pub mesh: Mesh<[C; 2]>,
+    /// The position of the local origin
+    pub anchor: [C; 2],
+}
+
+/// What [Mesh::partition] balances between chunks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PartitionWeight {
+    /// The area of the triangles
+    #[default]
+    Area,
+    /// The number of triangles
+    Count,
+}
+
+/// The adjacency of the triangles of a [Mesh] in compressed sparse row form, created by [Mesh::dual_graph]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DualGraph<C: Real> {
+    /// The start of the neighbors of each triangle in [DualGraph::neighbors], followed by the total number of neighbors
+    pub offsets: Vec<usize>,
+    /// The triangles adjacent to each triangle, in increasing order
+    pub neighbors: Vec<usize>,
+    /// The length of the edge shared with each neighbor
+    pub weights: Vec<C>,
+}
+
+impl<C: Real> DualGraph<C> {
+    /// The number of triangles
+    pub fn node_count(&self) -> usize {
+        self.offsets.len().saturating_sub(1)
+    }
+
+    /// The number of pairs of adjacent triangles
+    pub fn edge_count(&self) -> usize {
+        self.neighbors.len() / 2
+    }
+
+    /// The triangles adjacent to triangle `ti`, with the lengths of the shared edges
+    pub fn neighbors(&self, ti: usize) -> impl Iterator<Item=(usize, C)> + '_ {
+        let range = self.offsets[ti]..self.offsets[ti + 1];
+        self.neighbors[range.clone()].iter().copied().zip(self.weights[range].iter().copied())
+    }
+
+    /// The graph as a [petgraph](https://crates.io/crates/petgraph) graph, with the node indices matching the triangles
+    #[cfg(feature = "petgraph")]
+    pub fn to_petgraph(&self) -> petgraph::graph::UnGraph<(), C> {
+        let mut graph = petgraph::graph::UnGraph::with_capacity(self.node_count(), self.edge_count());
+        for _ in 0..self.node_count() {
+            graph.add_node(());
+        }
+        for ti in 0..self.node_count() {
+            for (tj, length) in self.neighbors(ti).filter(|(tj, _)| ti < *tj) {
+                graph.add_edge(petgraph::graph::NodeIndex::new(ti), petgraph::graph::NodeIndex::new(tj), length);
+            }
+        }
+        graph
+    }
+}
+
+/// A contiguous range of a [BatchedMesh]'s triangles which share a key
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Batch {
+    /// The key of the polygons the triangles fill
+    pub key: u32,
+    /// The range of [Mesh::triangles] in this batch
+    pub triangles: Range<usize>,
+}
+
+/// A [Mesh] with its triangles grouped by key, created by [Mesh::from_polygons_batched]
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchedMesh<V: Vertex> {
+    /// The mesh, with the triangles of each batch stored contiguously
+    pub mesh: Mesh<V>,
+    /// The batches, sorted by key
+    pub batches: Vec<Batch>,
+}
+
+impl<V: Vertex> BatchedMesh<V> {
+    /// The triangles with the given key
+    pub fn batch(&self, key: u32) -> &[[usize; 3]] {
+        match self.batches.binary_search_by_key(&key, |batch| batch.key) {
+            Ok(i) => &self.mesh.triangles[self.batches[i].triangles.clone()],
+            Err(_) => &[],
+        }
+    }
+}
+
+// Re-indexes a `PolygonList` with sequential `usize`s, so its triangulation can index directly into a `Mesh`
+struct Flattened<'p, P: PolygonList<'p> + ?Sized> {
+    polygons: &'p P,
+    indices: Vec<P::Index>,
+    elements: Vec<Option<usize>>,
+    // The range of flattened indices of each non-empty polygon
+    contours: Vec<Range<usize>>,
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized> Flattened<'p, P> {
+    fn new(polygons: &'p P) -> Self {
+        let mut indices = Vec::with_capacity(polygons.vertex_count());
+        let mut elements = Vec::with_capacity(polygons.vertex_count() + polygons.contour_count_hint().unwrap_or(0));
+        let mut contours = Vec::with_capacity(polygons.contour_count_hint().unwrap_or(0));
+        let mut start = 0;
+        for element in polygons.iter_indices().map(Into::into).chain(iter::once(PolygonElement::NewPolygon)) {
+            match element {
+                PolygonElement::ContinuePolygon(index) => {
+                    elements.push(Some(indices.len()));
+                    indices.push(index);
+                }
+                PolygonElement::NewPolygon => {
+                    elements.push(None);
+                    if indices.len() > start {
+                        contours.push(start..indices.len());
+                        start = indices.len();
+                    }
+                }
+            }
+        }
+        Self { polygons, indices, elements, contours }
+    }
+}
+
+impl<'f, 'p: 'f, P: PolygonList<'p> + ?Sized> PolygonList<'f> for Flattened<'p, P> {
+    type Vertex = P::Vertex;
+    type Index = usize;
+    type IntoItem = Option<usize>;
+    type Iter<'i> = iter::Copied<slice::Iter<'i, Option<usize>>>
+    where Self: 'i, Self::Vertex: 'i, 'f: 'i;
+
+    fn vertex_count(&self) -> usize {
+        self.indices.len()
+    }
+
+    fn iter_indices<'i>(&'i self) -> Self::Iter<'i>
+    where Self: 'i, Self::Vertex: 'i, 'f: 'i {
+        self.elements.iter().copied()
+    }
+
+    fn get_vertex<'a>(&'a self, index: Self::Index) -> &'a Self::Vertex
+    where 'f: 'a {
+        self.polygons.get_vertex(self.indices[index].clone())
+    }
+}
+
+// Some of the polygons of a [Flattened] list, by their indices in it
+#[cfg(feature = "rayon")]
+struct Group<'g, 'p, P: PolygonList<'p> + ?Sized> {
+    flattened: &'g Flattened<'p, P>,
+    elements: Vec<Option<usize>>,
+}
+
+#[cfg(feature = "rayon")]
+impl<'g, 'p: 'g, P: PolygonList<'p> + ?Sized> PolygonList<'g> for Group<'g, 'p, P> {
+    type Vertex = P::Vertex;
+    type Index = usize;
+    type IntoItem = Option<usize>;
+    type Iter<'i> = iter::Copied<slice::Iter<'i, Option<usize>>>
+    where Self: 'i, Self::Vertex: 'i, 'g: 'i;
+
+    fn vertex_count(&self) -> usize {
+        self.elements.iter().flatten().count()
+    }
+
+    fn iter_indices<'i>(&'i self) -> Self::Iter<'i>
+    where Self: 'i, Self::Vertex: 'i, 'g: 'i {
+        self.elements.iter().copied()
+    }
+
+    fn get_vertex<'a>(&'a self, index: Self::Index) -> &'a Self::Vertex
+    where 'g: 'a {
+        self.flattened.get_vertex(index)
+    }
+}
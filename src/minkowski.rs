@@ -0,0 +1,52 @@
+//! Minkowski sums of polygons with convex shapes.
+//!
+//! The Minkowski sum of a shape with a convex kernel is the area covered by the kernel as its origin moves over the
+//! shape: the shape dilated by a brush, or, with the kernel of a robot reflected through its origin, the configuration
+//! space obstacle of the shape, where the robot's origin cannot go without touching it. [minkowski_sum] computes it as
+//! the union of the shape moved by one point of the kernel and of the convex hull of the kernel placed at both ends of
+//! each edge, with [boolean::union_all](crate::boolean::union_all)'s cascaded union. The result is returned as contours
+//! like the [boolean](crate::boolean) operations, ready to triangulate.
+//!
+//! ```
+//! # use triangulate::minkowski;
+//! // A square dilated by a diamond gains a triangle on each side
+//! let square = vec![vec![[0f64, 0.], [2., 0.], [2., 2.], [0., 2.]]];
+//! let diamond = [[1f64, 0.], [0., 1.], [-1., 0.], [0., -1.]];
+//! let sum = minkowski::minkowski_sum(&square, &diamond);
+//! assert_eq!(sum.len(), 1);
+//! assert_eq!(sum[0].len(), 8);
+//! ```
+
+use core::fmt;
+use alloc::{vec, vec::Vec};
+
+use num_traits::real::Real;
+
+use crate::{boolean::{self, Contours}, hull, PolygonList, PolygonListExt, Vertex};
+
+/// The Minkowski sum of `polygons` (interpreted with the even-odd rule) and the convex hull of `kernel`.
+///
+/// A kernel which is not convex is replaced by its convex hull. The sum is empty if the kernel is empty; a kernel of a
+/// single point moves the polygons by that point, and a collinear kernel sweeps them along a segment.
+pub fn minkowski_sum<'p, P, V, C>(polygons: &'p P, kernel: &[V]) -> Contours<C>
+where P: PolygonList<'p> + ?Sized,
+      P::Vertex: Vertex<Coordinate=C>,
+      V: Vertex<Coordinate=C>,
+      C: Real + fmt::Debug {
+    let kernel = hull::convex_hull(kernel);
+    let Some(&origin) = kernel.first() else { return Vec::new() };
+    let translate = |c: [C; 2], by: [C; 2]| [c[0] + by[0], c[1] + by[1]];
+
+    let contours = PolygonListExt::new(polygons).contours();
+    let mut pieces: Vec<Contours<C>> = vec![contours.iter().map(|contour| contour.iter().map(|c| translate(*c, origin)).collect()).collect()];
+    for contour in &contours {
+        for (a, b) in contour.iter().zip(contour.iter().cycle().skip(1)) {
+            let swept: Vec<[C; 2]> = kernel.iter().flat_map(|k| [translate(*a, *k), translate(*b, *k)]).collect();
+            let piece = hull::convex_hull(&swept);
+            if piece.len() >= 3 {
+                pieces.push(vec![piece]);
+            }
+        }
+    }
+    boolean::union_range(&pieces)
+}
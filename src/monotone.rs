@@ -1,26 +1,39 @@
-use std::marker::PhantomData;
+use core::marker::PhantomData;
+use alloc::format;
 
-use num_traits::real::Real;
+use crate::Scalar;
 use smallvec::{SmallVec, smallvec};
 
-use crate::{FanFormat, FanBuilderState, PolygonList, PolygonListExt, TriangleWinding, VertexIndex, errors::{TriangulationError, InternalError}, math::is_left_of_line, FanBuilder, Coords};
+use crate::{FanFormat, FanBuilderState, PolygonList, PolygonListExt, TriangleWinding, VertexIndex, errors::{TriangulationError, InternalError}, kernel::{GeometryKernel, Orientation, is_left_of_line}, FanBuilder, Coords, MergePolicy};
 
-pub(crate) struct MonotoneBuilder<Index: VertexIndex, C: Real> {
-    vec: SmallVec<[(Index, Coords<C>); 16]>,
+type MonotoneVertex<Index, C> = (Index, Coords<C>);
+
+/// The side of a monotone polygon a vertex between its highest and lowest vertices is on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Chain {
+    Left,
+    Right,
+}
+
+pub(crate) struct MonotoneBuilder<Index: VertexIndex, C: Scalar> {
+    vec: SmallVec<[MonotoneVertex<Index, C>; 16]>,
+    chains: SmallVec<[Chain; 16]>,
     diff_x: bool,
     diff_y: bool,
 }
 
-impl<Index: VertexIndex, C: Real> MonotoneBuilder<Index, C> {
+impl<Index: VertexIndex, C: Scalar> MonotoneBuilder<Index, C> {
     pub fn new(vi: Index, c: Coords<C>) -> Self {
         Self {
             vec: smallvec![(vi, c)],
+            // The highest vertex is on both chains
+            chains: smallvec![Chain::Left],
             diff_x: false,
             diff_y: false,
         }
     }
 
-    pub fn add_vertex(&mut self, vi: Index, c: Coords<C>) {
+    pub fn add_vertex(&mut self, vi: Index, c: Coords<C>, chain: Chain) {
         if !self.diff_x && c.x() != self.vec[0].1.x() {
             self.diff_x = true;
         }
@@ -29,34 +42,42 @@ impl<Index: VertexIndex, C: Real> MonotoneBuilder<Index, C> {
         }
 
         self.vec.push((vi, c));
+        self.chains.push(chain);
     }
 
-    pub fn build(self) -> Result<Option<Monotone<Index, C>>, InternalError> {
+    pub fn build<K: GeometryKernel<C>>(self, merge: MergePolicy) -> Result<Option<Monotone<Index, C>>, InternalError> {
         if self.vec.len() < 3 {
             return Err(InternalError::new(format!("Monotone needs at least 3 vertices, has {}", self.vec.len())));
         }
 
         if self.diff_x && self.diff_y {
-            let is_left_chain = is_left_of_line(self.vec[self.vec.len() - 1].1, self.vec[0].1, self.vec[1].1);
-            Ok(Some(Monotone::new(self.vec, is_left_chain)))
+            let inner = &self.chains[1..self.chains.len() - 1];
+            if merge == MergePolicy::Monotone && inner.iter().any(|&chain| chain != inner[0]) {
+                Ok(Some(Monotone::with_chains(self.vec, self.chains)))
+            } else {
+                let is_left_chain = is_left_of_line::<K, _>(&self.vec[self.vec.len() - 1].1, &self.vec[0].1, &self.vec[1].1);
+                Ok(Some(Monotone::new(self.vec, is_left_chain)))
+            }
         } else {
             Ok(None)
         }
     }
 }
 
-pub struct Monotone<Index: VertexIndex, C: Real> {
+pub struct Monotone<Index: VertexIndex, C: Scalar> {
     // Skipped stack from [0, skipped_top), pending stack from [pending_top, len), expended/deferred values remain in [skipped_top, pending_top)
-    pub(crate) skipped_and_pending: SmallVec<[(Index, Coords<C>); 16]>,
+    pub(crate) skipped_and_pending: SmallVec<[MonotoneVertex<Index, C>; 16]>,
     skipped_top: usize,
     pending_top: usize,
     // Is the chain on the left of the polygon (and the single edge on the right)?
     is_left_chain: bool,
+    // The side of each vertex, if there are vertices on both sides
+    chains: Option<SmallVec<[Chain; 16]>>,
 }
 
 #[cfg(feature = "_debugging")]
-impl<Index: VertexIndex, C: Real> std::fmt::Display for Monotone<Index, C> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl<Index: VertexIndex, C: Scalar> core::fmt::Display for Monotone<Index, C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         writeln!(f, "is_left_chain: {}", self.is_left_chain)?;
         write!(f, "[ ")?;
         for i in 0..self.skipped_top {
@@ -74,17 +95,51 @@ impl<Index: VertexIndex, C: Real> std::fmt::Display for Monotone<Index, C> {
     }
 }
 
-impl<Index: VertexIndex, C: Real> Monotone<Index, C> {
-    fn new(vertices: SmallVec<[(Index, Coords<C>); 16]>, is_left_chain: bool) -> Self {
+impl<Index: VertexIndex, C: Scalar> Monotone<Index, C> {
+    fn new(vertices: SmallVec<[MonotoneVertex<Index, C>; 16]>, is_left_chain: bool) -> Self {
         Self {
             skipped_and_pending: vertices,
             skipped_top: 2,
             pending_top: 2,
             is_left_chain,
+            chains: None,
+        }
+    }
+
+    fn with_chains(vertices: SmallVec<[MonotoneVertex<Index, C>; 16]>, chains: SmallVec<[Chain; 16]>) -> Self {
+        Self {
+            chains: Some(chains),
+            ..Self::new(vertices, true)
+        }
+    }
+
+    /// The indices of the polygon from its highest vertex, in the order of `winding`
+    pub(crate) fn into_indices(self, winding: TriangleWinding) -> SmallVec<[Index; 16]> {
+        let (mut indices, is_left_chain) = match self.chains {
+            Some(chains) => {
+                // Down the left chain to the lowest vertex, then up the right chain
+                let len = chains.len();
+                let (mut left, mut right) = (SmallVec::<[Index; 16]>::new(), SmallVec::<[Index; 16]>::new());
+                for (i, ((vi, _), chain)) in self.skipped_and_pending.into_iter().zip(chains).enumerate() {
+                    if i == 0 || i == len - 1 || chain == Chain::Left {
+                        left.push(vi);
+                    } else {
+                        right.push(vi);
+                    }
+                }
+                left.extend(right.into_iter().rev());
+                (left, true)
+            }
+            None => (self.skipped_and_pending.into_iter().map(|(vi, _)| vi).collect::<SmallVec<[Index; 16]>>(), self.is_left_chain),
+        };
+        // The chain runs down the left side of a counterclockwise polygon
+        if is_left_chain != (winding == TriangleWinding::Counterclockwise) {
+            indices[1..].reverse();
         }
+        indices
     }
 
-    pub(crate) fn build_fans<'z, 'p, P: PolygonList<'p, Index=Index> + ?Sized, FB: FanFormat<'p, P>>(mut self, ps: PolygonListExt<'p, P>, fbs: &'z mut FanBuilderState<'p, P, FB>) -> Result<(), TriangulationError<<FB::Builder as FanBuilder<'p, P>>::Error>> {
+    pub(crate) fn build_fans<'z, 'p, K: GeometryKernel<C>, P: PolygonList<'p, Index=Index> + ?Sized, FB: FanFormat<'p, P>>(mut self, ps: PolygonListExt<'p, P>, fbs: &'z mut FanBuilderState<'p, P, FB>) -> Result<(), TriangulationError<<FB::Builder as FanBuilder<'p, P>>::Error>> {
         enum BuilderOrDeferredTris<'z, 'p, P: PolygonList<'p> + ?Sized, FB: FanFormat<'p, P>> {
             Builder(&'z mut FB::Builder),
             DeferredTris(&'z mut FanBuilderState<'p, P, FB>, usize, PhantomData<&'p ()>),
@@ -99,20 +154,25 @@ impl<Index: VertexIndex, C: Real> Monotone<Index, C> {
             }
         }
 
+        if let Some(chains) = self.chains.take() {
+            return self.build_chain_fans::<K, P, FB>(chains, ps, fbs);
+        }
+
         while self.remaining_vertices() >= 3 {
-            if self.can_triangulate() {
+            if self.can_triangulate::<K>() {
                 // The base triangle, with all 3 points specified
-                let vi1 = self.skipped_pop().0;
-                let mut vi0 = self.skipped_peek().0;
-                let mut vi2 = self.pending_peek().0;
+                let vi1 = self.skipped_pop();
+                let mut vi0 = self.skipped_peek().0.clone();
+                let mut vi2 = self.pending_peek().0.clone();
 
                 // Advancing fan/backtracking fan and left chain/right chain both invert the winding.
                 // If we need to de-invert the winding, defer add_triangle calls until we have processed the fan 
                 // and can make the calls in a reversed order
-                let is_backtracking = self.can_triangulate();
+                let is_backtracking = self.can_triangulate::<K>();
                 let mut bodt: BuilderOrDeferredTris<'_, '_, P, FB> = if is_backtracking ^ self.is_left_chain ^ (FB::Builder::WINDING == TriangleWinding::Clockwise) {
-                    if !self.is_left_chain {
-                        std::mem::swap(&mut vi0, &mut vi2);
+                    // The base triangle is reversed on one chain, which depends on the winding
+                    if self.is_left_chain == (FB::Builder::WINDING == TriangleWinding::Clockwise) {
+                        core::mem::swap(&mut vi0, &mut vi2);
                     }
                     BuilderOrDeferredTris::Builder(fbs.new_fan(ps.polygon_list(), vi0, vi1, vi2)?)
                 } else {
@@ -124,18 +184,18 @@ impl<Index: VertexIndex, C: Real> Monotone<Index, C> {
                 if is_backtracking {
                     // We already confirmed we can extend at least one more triangle
                     self.skipped_pop();
-                    bodt.add_triangle(self.skipped_peek().0).map_err(TriangulationError::from)?;
+                    bodt.add_triangle(self.skipped_peek().0.clone()).map_err(TriangulationError::from)?;
 
                     // Then continue adding triangles as much as possible
-                    while self.can_triangulate() {
+                    while self.can_triangulate::<K>() {
                         self.skipped_pop();
-                        bodt.add_triangle(self.skipped_peek().0).map_err(TriangulationError::from)?;
+                        bodt.add_triangle(self.skipped_peek().0.clone()).map_err(TriangulationError::from)?;
                     }
                 } else {
                     self.transfer_pending();
-                    while self.can_triangulate() {
+                    while self.can_triangulate::<K>() {
                         self.skipped_pop();
-                        bodt.add_triangle(self.pending_peek().0).map_err(TriangulationError::from)?;
+                        bodt.add_triangle(self.pending_peek().0.clone()).map_err(TriangulationError::from)?;
 
                         self.transfer_pending();
                     }
@@ -144,10 +204,10 @@ impl<Index: VertexIndex, C: Real> Monotone<Index, C> {
                 // If we had to defer extend_fan calls, execute them now in reverse order
                 if let BuilderOrDeferredTris::DeferredTris(fbs, dt, _) = bodt {
                     let (vi0, vi1, vi2) = if is_backtracking {
-                        (self.pending_peek().0, self.skipped_peek().0, self.deferred_index(is_backtracking, 0))
+                        (self.pending_peek().0.clone(), self.skipped_peek().0.clone(), self.deferred_index(is_backtracking, 0))
                     } else {
                         let ((vi0,  _), (vi1, _)) = self.skipped_peek2();
-                        (vi0, vi1, self.deferred_index(is_backtracking, 0))
+                        (vi0.clone(), vi1.clone(), self.deferred_index(is_backtracking, 0))
                     };
                     let fb = fbs.new_fan(ps.polygon_list(), vi0, vi1, vi2)?;
                     for i in 1..dt {
@@ -175,25 +235,84 @@ impl<Index: VertexIndex, C: Real> Monotone<Index, C> {
         }
     }
 
+    /// Triangulate a monotone polygon with vertices on both chains, in a sweep from the highest vertex which keeps the
+    /// vertices not yet triangulated on a stack. Each vertex ends a fan around it, either across the whole stack if it is
+    /// on the other chain, or back along its own chain for as long as the diagonals are inside the polygon
+    fn build_chain_fans<'p, K: GeometryKernel<C>, P: PolygonList<'p, Index=Index> + ?Sized, FB: FanFormat<'p, P>>(self, chains: SmallVec<[Chain; 16]>, ps: PolygonListExt<'p, P>, fbs: &mut FanBuilderState<'p, P, FB>) -> Result<(), TriangulationError<<FB::Builder as FanBuilder<'p, P>>::Error>> {
+        let vertices = self.skipped_and_pending;
+        let last = vertices.len() - 1;
+
+        // Add the fan around `root` through `rim`, in the winding of the builder
+        let mut add_fan = |root: usize, rim: &[usize]| -> Result<(), TriangulationError<<FB::Builder as FanBuilder<'p, P>>::Error>> {
+            let counterclockwise = rim.windows(2)
+                .map(|edge| K::orientation(vertices[root].1.as_array(), vertices[edge[0]].1.as_array(), vertices[edge[1]].1.as_array()))
+                .find(|&orientation| orientation != Orientation::Collinear) != Some(Orientation::Right);
+            let mut rim = rim.iter().map(|&i| vertices[i].0.clone()).collect::<SmallVec<[Index; 16]>>();
+            if counterclockwise != (FB::Builder::WINDING == TriangleWinding::Counterclockwise) {
+                rim.reverse();
+            }
+            let mut rim = rim.into_iter();
+            let (Some(vi1), Some(vi2)) = (rim.next(), rim.next()) else {
+                return Err(TriangulationError::internal("A fan needs at least one triangle"));
+            };
+            let fb = fbs.new_fan(ps.polygon_list(), vertices[root].0.clone(), vi1, vi2)?;
+            for vi in rim {
+                fb.extend_fan(vi)?;
+            }
+            Ok(())
+        };
+
+        let mut stack: SmallVec<[usize; 16]> = smallvec![0, 1];
+        for i in 2..=last {
+            let top = stack[stack.len() - 1];
+            if i == last || chains[i] != chains[top] {
+                add_fan(i, &stack)?;
+                stack.clear();
+                stack.extend([top, i]);
+            } else {
+                let mut rim: SmallVec<[usize; 16]> = smallvec![top];
+                stack.pop();
+                while let Some(&next) = stack.last() {
+                    let orientation = K::orientation(vertices[i].1.as_array(), vertices[next].1.as_array(), vertices[rim[rim.len() - 1]].1.as_array());
+                    let inside = match chains[i] {
+                        Chain::Left => orientation == Orientation::Left,
+                        Chain::Right => orientation == Orientation::Right,
+                    };
+                    if !inside {
+                        break;
+                    }
+                    rim.push(next);
+                    stack.pop();
+                }
+                if rim.len() >= 2 {
+                    add_fan(i, &rim)?;
+                }
+                stack.push(rim[rim.len() - 1]);
+                stack.push(i);
+            }
+        }
+        Ok(())
+    }
+
     #[inline(always)]
-    fn skipped_peek(&self) -> (Index, Coords<C>) {
-        self.skipped_and_pending[self.skipped_top - 1].clone()
+    fn skipped_peek(&self) -> &MonotoneVertex<Index, C> {
+        &self.skipped_and_pending[self.skipped_top - 1]
     }
 
     #[inline(always)]
-    fn skipped_peek2(&self) -> ((Index, Coords<C>), (Index, Coords<C>)) {
-        (self.skipped_and_pending[self.skipped_top - 2].clone(), self.skipped_and_pending[self.skipped_top - 1].clone())
+    fn skipped_peek2(&self) -> (&MonotoneVertex<Index, C>, &MonotoneVertex<Index, C>) {
+        (&self.skipped_and_pending[self.skipped_top - 2], &self.skipped_and_pending[self.skipped_top - 1])
     }
 
     #[inline(always)]
-    fn skipped_pop(&mut self) -> (Index, Coords<C>) {
+    fn skipped_pop(&mut self) -> Index {
         self.skipped_top -= 1;
-        self.skipped_and_pending[self.skipped_top].clone()
+        self.skipped_and_pending[self.skipped_top].0.clone()
     }
 
     #[inline(always)]
-    fn pending_peek(&self) -> (Index, Coords<C>) {
-        self.skipped_and_pending[self.pending_top].clone()
+    fn pending_peek(&self) -> &MonotoneVertex<Index, C> {
+        &self.skipped_and_pending[self.pending_top]
     }
 
     fn remaining_vertices(&self) -> usize {
@@ -225,12 +344,12 @@ impl<Index: VertexIndex, C: Real> Monotone<Index, C> {
         self.skipped_and_pending[index].0.clone()
     }
 
-    fn can_triangulate(&self) -> bool {
+    fn can_triangulate<K: GeometryKernel<C>>(&self) -> bool {
         self.skipped_top >= 2 && self.has_pending() && {
-            let c_min = self.pending_peek().1;
+            let c_min = &self.pending_peek().1;
             let ((_, c_max), (_, c)) = self.skipped_peek2();
             
-            self.is_left_chain == is_left_of_line(c_min, c_max, c)
+            self.is_left_chain == is_left_of_line::<K, _>(c_min, c_max, c)
         }
     }
 }
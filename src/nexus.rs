@@ -1,15 +1,18 @@
-use std::{marker::PhantomData, mem, fmt};
+use core::{marker::PhantomData, mem, fmt};
+use alloc::format;
 
 use zot::{Ot, Zot};
 
-use crate::{Vertex, VertexIndex, errors::InternalError, idx::{Idx, IdxDisplay}, segment::Segment, trapezoid::Trapezoid, Coords, math::is_left_of_line};
+use crate::{Vertex, VertexIndex, errors::InternalError, idx::{Idx, IdxDisplay, ForkableVec}, segment::Segment, trapezoid::Trapezoid, Coords, kernel::{GeometryKernel, is_left_of_line}};
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DividerDirection {
     Ascending,
     Descending,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(bound = ""))]
 struct Divider<V: Vertex, Index: VertexIndex> {
     si: Idx<Segment<V, Index>>,
     ti_right: Idx<Trapezoid<V, Index>>,
@@ -22,6 +25,12 @@ impl<V: Vertex, Index: VertexIndex> fmt::Debug for Divider<V, Index> {
     }
 }
 
+impl<V: Vertex, Index: VertexIndex> Clone for Divider<V, Index> {
+    fn clone(&self) -> Self {
+        Self::new(self.si, self.ti_right, self.direction)
+    }
+}
+
 impl<V: Vertex, Index: VertexIndex> Divider<V, Index> {
     pub fn new(si: Idx<Segment<V, Index>>, ti_right: Idx<Trapezoid<V, Index>>, direction: DividerDirection) -> Self {
         Self {
@@ -32,8 +41,8 @@ impl<V: Vertex, Index: VertexIndex> Divider<V, Index> {
     }
 }
 
-impl<V: Vertex, Index: VertexIndex> std::fmt::Display for Divider<V, Index> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl<V: Vertex, Index: VertexIndex> core::fmt::Display for Divider<V, Index> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{} | {}", self.si, self.ti_right)
     }
 }
@@ -41,20 +50,67 @@ impl<V: Vertex, Index: VertexIndex> std::fmt::Display for Divider<V, Index> {
 pub(crate) enum FinalNexusType<V: Vertex, Index: VertexIndex> {
     V { ti_upleft: Idx<Trapezoid<V, Index>>, ti_upcenter: Idx<Trapezoid<V, Index>>, ti_upright: Idx<Trapezoid<V, Index>>, ti_down: Idx<Trapezoid<V, Index>> },
     I { ti_upleft: Idx<Trapezoid<V, Index>>, ti_upright: Idx<Trapezoid<V, Index>>, ti_downleft: Idx<Trapezoid<V, Index>>, ti_downright: Idx<Trapezoid<V, Index>> },
-    A { _ti_up: Idx<Trapezoid<V, Index>>, ti_downleft: Idx<Trapezoid<V, Index>>, ti_downcenter: Idx<Trapezoid<V, Index>>, ti_downright: Idx<Trapezoid<V, Index>> },
+    A { ti_up: Idx<Trapezoid<V, Index>>, ti_downleft: Idx<Trapezoid<V, Index>>, ti_downcenter: Idx<Trapezoid<V, Index>>, ti_downright: Idx<Trapezoid<V, Index>> },
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(bound(serialize = "V::Coordinate: serde::Serialize, Index: serde::Serialize", deserialize = "V::Coordinate: serde::Deserialize<'de>, Index: serde::Deserialize<'de>")))]
 pub(crate) struct Nexus<V: Vertex, Index: VertexIndex> {
     vi: Index,
     c: Coords<V::Coordinate>,
     ti_upleft: Idx<Trapezoid<V, Index>>,
     ti_downleft: Idx<Trapezoid<V, Index>>,
+    #[cfg_attr(feature = "serde", serde(with = "serde_dividers"))]
     dividers: Zot<Divider<V, Index>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     _v: PhantomData<V>,
 }
 
-impl<V: Vertex, Index:VertexIndex> std::fmt::Debug for Nexus<V, Index> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+// `Zot` is not serializable, so the dividers are stored as a sequence of up to two
+#[cfg(feature = "serde")]
+mod serde_dividers {
+    use core::iter;
+    use alloc::vec::Vec;
+
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+    use zot::Zot;
+
+    use super::Divider;
+    use crate::{Vertex, VertexIndex};
+
+    pub fn serialize<V: Vertex, Index: VertexIndex, S: Serializer>(dividers: &Zot<Divider<V, Index>>, serializer: S) -> Result<S::Ok, S::Error> {
+        match dividers {
+            Zot::Zero => serializer.collect_seq(iter::empty::<&Divider<V, Index>>()),
+            Zot::One(div0) => serializer.collect_seq([div0]),
+            Zot::Two(div0, div1) => serializer.collect_seq([div0, div1]),
+        }
+    }
+
+    pub fn deserialize<'de, V: Vertex, Index: VertexIndex, D: Deserializer<'de>>(deserializer: D) -> Result<Zot<Divider<V, Index>>, D::Error> {
+        let mut dividers = Vec::<Divider<V, Index>>::deserialize(deserializer)?.into_iter();
+        match (dividers.next(), dividers.next(), dividers.next()) {
+            (None, _, _) => Ok(Zot::Zero),
+            (Some(div0), None, _) => Ok(Zot::One(div0)),
+            (Some(div0), Some(div1), None) => Ok(Zot::Two(div0, div1)),
+            _ => Err(D::Error::invalid_length(3, &"at most 2 dividers")),
+        }
+    }
+}
+
+impl<V: Vertex, Index: VertexIndex> Clone for Nexus<V, Index> {
+    fn clone(&self) -> Self {
+        Self {
+            vi: self.vi.clone(),
+            c: self.c.clone(),
+            ti_upleft: self.ti_upleft,
+            ti_downleft: self.ti_downleft,
+            dividers: self.dividers.clone(),
+            _v: PhantomData,
+        }
+    }
+}
+
+impl<V: Vertex, Index:VertexIndex> core::fmt::Debug for Nexus<V, Index> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let mut s = f.debug_struct("Nexus");
         #[cfg(feature = "_debugging")]
         s.field("vi", &self.vi);
@@ -64,8 +120,8 @@ impl<V: Vertex, Index:VertexIndex> std::fmt::Debug for Nexus<V, Index> {
     }
 }
 
-impl<V: Vertex + std::fmt::Display, Index: VertexIndex + std::fmt::Display> std::fmt::Display for Nexus<V, Index> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl<V: Vertex + core::fmt::Display, Index: VertexIndex + core::fmt::Display> core::fmt::Display for Nexus<V, Index> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", self.ti_upleft)?;
         for div in self.filter_dividers(DividerDirection::Ascending).iter() {
             write!(f, "[{}]{}", div.si, div.ti_right)?;
@@ -81,7 +137,7 @@ impl<V: Vertex + std::fmt::Display, Index: VertexIndex + std::fmt::Display> std:
 }
 
 impl<V: Vertex, Index: VertexIndex> IdxDisplay for Nexus<V, Index> {
-    fn fmt(f: &mut std::fmt::Formatter<'_>, idx: usize) -> std::fmt::Result {
+    fn fmt(f: &mut core::fmt::Formatter<'_>, idx: usize) -> core::fmt::Result {
         write!(f, "n{}", idx)
     }
 }
@@ -103,7 +159,7 @@ impl<V: Vertex, Index: VertexIndex> Nexus<V, Index> {
              Ok(
                  if div0.direction == DividerDirection::Descending {
                     FinalNexusType::A {
-                        _ti_up: self.ti_upleft,
+                        ti_up: self.ti_upleft,
                         ti_downleft: self.ti_downleft,
                         ti_downcenter: div0.ti_right,
                         ti_downright: div1.ti_right,
@@ -131,7 +187,7 @@ impl<V: Vertex, Index: VertexIndex> Nexus<V, Index> {
 
     pub fn vertex(&self) -> Index { self.vi.clone() }
 
-    pub fn coords(&self) -> Coords<V::Coordinate> { self.c }
+    pub fn coords(&self) -> &Coords<V::Coordinate> { &self.c }
 
     pub fn replace_trapezoid(&mut self, ti_old: Idx<Trapezoid<V, Index>>, ti_new: Idx<Trapezoid<V, Index>>) -> Result<(), InternalError> {
         *self.find_trapezoid(ti_old).ok_or_else(|| InternalError::new(format!("Trapezoid {} is not connected to replace with {}", ti_old, ti_new)))? = ti_new;
@@ -153,11 +209,11 @@ impl<V: Vertex, Index: VertexIndex> Nexus<V, Index> {
         }
     }
 
-    pub fn add_segment(ns: &mut [Nexus<V, Index>], ss: &[Segment<V, Index>], ni: Idx<Nexus<V, Index>>, si: Idx<Segment<V, Index>>, ti_right: Idx<Trapezoid<V, Index>>) -> Result<(), InternalError> {
+    pub fn add_segment<K: GeometryKernel<V::Coordinate>>(ns: &mut ForkableVec<Nexus<V, Index>>, ss: &ForkableVec<Segment<V, Index>>, ni: Idx<Nexus<V, Index>>, si: Idx<Segment<V, Index>>, ti_right: Idx<Trapezoid<V, Index>>) -> Result<(), InternalError> {
         let dir = Self::get_segment_direction(ss, ni, si)?;
         let div = Divider::new(si, ti_right, dir);
         let mut divs = Zot::Zero;
-        std::mem::swap(&mut divs, &mut ns[ni].dividers);
+        core::mem::swap(&mut divs, &mut ns[ni].dividers);
         ns[ni].dividers = match divs {
             Zot::Zero => Zot::One(div),
             Zot::One(div0) => {
@@ -168,7 +224,7 @@ impl<V: Vertex, Index: VertexIndex> Nexus<V, Index> {
                         DividerDirection::Ascending => s1.ni_max(),
                         DividerDirection::Descending => s1.ni_min(),
                     };
-                    s0.is_on_left(ns[ni1].coords())
+                    s0.is_on_left::<K>(ns[ni1].coords())
                 } else {
                     div.direction == DividerDirection::Ascending
                 };
@@ -183,7 +239,7 @@ impl<V: Vertex, Index: VertexIndex> Nexus<V, Index> {
         Ok(())
     }
 
-    fn get_segment_direction(ss: &[Segment<V, Index>], ni: Idx<Nexus<V, Index>>, si: Idx<Segment<V, Index>>) -> Result<DividerDirection, InternalError> {
+    fn get_segment_direction(ss: &ForkableVec<Segment<V, Index>>, ni: Idx<Nexus<V, Index>>, si: Idx<Segment<V, Index>>) -> Result<DividerDirection, InternalError> {
         let s = &ss[si];
         if ni == s.ni_max() {
             Ok(DividerDirection::Descending)
@@ -220,7 +276,12 @@ impl<V: Vertex, Index: VertexIndex> Nexus<V, Index> {
         NexusTrapezoidIter::new(self, DividerDirection::Descending)
     }
 
-    pub fn get_down_trapezoid_in_direction(&self, ns: &[Nexus<V, Index>], ss: &[Segment<V, Index>], s: &Segment<V, Index>) -> Result<Idx<Trapezoid<V, Index>>, InternalError> {
+    #[cfg(feature = "serde")]
+    pub fn iter_segments(&self) -> impl Iterator<Item=Idx<Segment<V, Index>>> + '_ {
+        self.dividers.first().into_iter().chain(self.dividers.second()).map(|div| div.si)
+    }
+
+    pub fn get_down_trapezoid_in_direction<K: GeometryKernel<V::Coordinate>>(&self, ns: &ForkableVec<Nexus<V, Index>>, ss: &ForkableVec<Segment<V, Index>>, s: &Segment<V, Index>) -> Result<Idx<Trapezoid<V, Index>>, InternalError> {
         match self.filter_dividers(DividerDirection::Descending) {
             Zot::Zero => Ok(self.ti_downleft),
             Zot::One(div_r) |
@@ -232,7 +293,7 @@ impl<V: Vertex, Index: VertexIndex> Nexus<V, Index> {
                 } else {
                     self.coords()
                 };
-                if s.is_on_left(c) {
+                if s.is_on_left::<K>(c) {
                     Ok(div_r.ti_right)
                 } else {
                     Ok(self.ti_downleft)
@@ -241,7 +302,7 @@ impl<V: Vertex, Index: VertexIndex> Nexus<V, Index> {
         }
     }
 
-    pub fn get_trapezoid_between_coords(&self, direction: DividerDirection, mut c_from: Coords<V::Coordinate>, mut c_to: Coords<V::Coordinate>) -> Result<Idx<Trapezoid<V, Index>>, InternalError> {
+    pub fn get_trapezoid_between_coords<'c, K: GeometryKernel<V::Coordinate>>(&self, direction: DividerDirection, mut c_from: &'c Coords<V::Coordinate>, mut c_to: &'c Coords<V::Coordinate>) -> Result<Idx<Trapezoid<V, Index>>, InternalError> {
         match self.filter_dividers(direction) {
             Zot::Zero => Ok(if direction == DividerDirection::Ascending { self.ti_upleft } else { self.ti_downleft }),
             Zot::One(div_r)  |
@@ -253,7 +314,7 @@ impl<V: Vertex, Index: VertexIndex> Nexus<V, Index> {
                     mem::swap(&mut c_from, &mut c_to);
                 }
 
-                let ti = if is_left_of_line(c_from, c_to, self.c) {
+                let ti = if is_left_of_line::<K, _>(c_from, c_to, &self.c) {
                     div_r.ti_right
                 } else {
                     if direction == DividerDirection::Ascending { 
@@ -267,13 +328,13 @@ impl<V: Vertex, Index: VertexIndex> Nexus<V, Index> {
         }
     }
 
-    pub fn get_trapezoid_toward_coords(&self, ss: &[Segment<V, Index>], ns: &[Nexus<V, Index>], direction: DividerDirection, c_to: Coords<V::Coordinate>) -> Result<Idx<Trapezoid<V, Index>>, InternalError> {
+    pub fn get_trapezoid_toward_coords<K: GeometryKernel<V::Coordinate>>(&self, ss: &ForkableVec<Segment<V, Index>>, ns: &ForkableVec<Nexus<V, Index>>, direction: DividerDirection, c_to: &Coords<V::Coordinate>) -> Result<Idx<Trapezoid<V, Index>>, InternalError> {
         match self.filter_dividers(direction) {
             Zot::Zero => Ok(if direction == DividerDirection::Ascending { self.ti_upleft } else { self.ti_downleft }),
             Zot::One(div) => {
                 let s = &ss[div.si];
                 
-                let ti = if is_left_of_line(ns[s.ni_min()].coords(), ns[s.ni_max()].coords(), c_to) {
+                let ti = if is_left_of_line::<K, _>(ns[s.ni_min()].coords(), ns[s.ni_max()].coords(), c_to) {
                     match direction {
                         DividerDirection::Ascending => self.ti_upleft,
                         DividerDirection::Descending => self.ti_downleft,
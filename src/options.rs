@@ -0,0 +1,122 @@
+use alloc::vec::Vec;
+
+use crate::{formats, trapezoidation::{Trapezoidation, TrapezoidationState}, FanBuilder, FanBuilderState, FanFormat, FanResult, ListFormat, PolygonList, TrapezoidationError, TriangleWinding, TriangulationError};
+
+/// The algorithm used to triangulate polygons
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TriangulationMethod {
+    /// Seidel's randomized algorithm, in expected O(*n* log\* *n*) time, which tends to produce long, thin triangles
+    #[default]
+    Seidel,
+    /// The constrained Delaunay triangulation: of all triangulations which keep the edges of the polygons, the one
+    /// which maximizes the smallest angle. It is found by flipping the diagonals of Seidel's triangulation, which is
+    /// slower, but suits uses where the shape of the triangles matters, such as finite element meshes.
+    ///
+    /// The in-circle test is exact for integer and rational coordinates. With floating point coordinates it is
+    /// rounded, so where four vertices are nearly cocircular either diagonal may be kept, and the result is only
+    /// approximately Delaunay; see [Mesh::flip_to_delaunay](crate::mesh::Mesh::flip_to_delaunay).
+    ConstrainedDelaunay,
+}
+
+/// How the trapezoids of a [Trapezoidation] are merged into the monotone polygons which are split into triangles.
+///
+/// The number of triangles is the same either way, but the monotone polygons, such as from
+/// [Trapezoidation::monotones], are fewer and larger when merged. Each monotone polygon is split into its own fans.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MergePolicy {
+    /// Merge trapezoids only while the polygon has a single edge on one side, so it can be split into triangles in a
+    /// single pass along the other side. Outlines which alternate between sides from vertex to vertex, such as convex
+    /// polygons, split into many small pieces
+    #[default]
+    Unimonotone,
+    /// Merge trapezoids into the largest polygons which are monotone in y, which are only split at the vertices where
+    /// the outline turns back vertically, such as the highest and lowest vertices of a hole
+    Monotone,
+}
+
+/// Options for [PolygonList::triangulate_with_options]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct TriangulationOptions {
+    /// The algorithm used to triangulate the polygons
+    pub method: TriangulationMethod,
+    /// The seed for the random order in which segments are inserted, or `None` for a different order every time (or the
+    /// same order every time, without the `std` feature).
+    ///
+    /// With a seed, every run on the same input does the same work, across runs and platforms (for the same version of
+    /// this crate). The insertion order decides where rounding in the geometric predicates can go wrong, so a failure
+    /// reported from the field can be reproduced by triangulating with the same seed
+    pub seed: Option<u64>,
+    /// The winding of every output triangle, or `None` for the format's own [FanBuilder::WINDING].
+    ///
+    /// The triangles are oriented by their position, not by the winding of the polygons they come from, so this holds
+    /// for polygons of any winding, such as to match the front faces of a renderer with backface culling
+    pub winding: Option<TriangleWinding>,
+    /// How trapezoids are merged into the monotone polygons which are split into triangles
+    pub merge: MergePolicy,
+}
+
+impl TriangulationOptions {
+    /// Set [TriangulationOptions::method]
+    pub fn with_method(mut self, method: TriangulationMethod) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Set [TriangulationOptions::seed]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Set [TriangulationOptions::winding]
+    pub fn with_winding(mut self, winding: TriangleWinding) -> Self {
+        self.winding = Some(winding);
+        self
+    }
+
+    /// Set [TriangulationOptions::merge]
+    pub fn with_merge(mut self, merge: MergePolicy) -> Self {
+        self.merge = merge;
+        self
+    }
+}
+
+pub(crate) fn triangulate<'p, P: PolygonList<'p> + ?Sized, FB: FanFormat<'p, P>>(polygons: &'p P, format: FB, options: &TriangulationOptions) -> FanResult<'p, P, FB> {
+    match options.winding {
+        // The reversed format is told the opposite winding, but passes the triangles on as they are
+        Some(winding) if winding != FB::Builder::WINDING => triangulate_wound(polygons, format.reverse_winding(), options),
+        _ => triangulate_wound(polygons, format, options),
+    }
+}
+
+fn triangulate_wound<'p, P: PolygonList<'p> + ?Sized, FB: FanFormat<'p, P>>(polygons: &'p P, format: FB, options: &TriangulationOptions) -> FanResult<'p, P, FB> {
+    match options.method {
+        TriangulationMethod::Seidel => trapezoidize(polygons, options).map_err(TriangulationError::TrapezoidationError)?.triangulate(format),
+        TriangulationMethod::ConstrainedDelaunay => {
+            let mut fbs = FanBuilderState::<'p, P, FB>::Uninitialized(format);
+            let result = constrained_delaunay(polygons, &mut fbs, options);
+            fbs.complete(result)
+        }
+    }
+}
+
+fn trapezoidize<'p, P: PolygonList<'p> + ?Sized>(polygons: &'p P, options: &TriangulationOptions) -> Result<Trapezoidation<'p, P>, TrapezoidationError> {
+    TrapezoidationState::new(polygons).with_seed(options.seed).build().map(|trapezoidation| trapezoidation.with_merge_policy(options.merge))
+}
+
+fn constrained_delaunay<'p, P: PolygonList<'p> + ?Sized, FB: FanFormat<'p, P>>(polygons: &'p P, fbs: &mut FanBuilderState<'p, P, FB>, options: &TriangulationOptions) -> Result<(), TriangulationError<<FB::Builder as FanBuilder<'p, P>>::Error>> {
+    // Collected counterclockwise, as `FB` is a fan format and cannot be wrapped in the Delaunay list format itself
+    let mut triangles = Vec::<[P::Index; 3]>::new();
+    trapezoidize(polygons, options).map_err(TriangulationError::TrapezoidationError)?
+        .triangulate(formats::IndexedListFormat::new(&mut triangles).delaunay().into_fan_format())
+        .map_err(TriangulationError::with_fan_builder_error)?;
+
+    for [v0, v1, v2] in triangles {
+        if FB::Builder::WINDING == TriangleWinding::Clockwise {
+            fbs.new_fan(polygons, v0, v2, v1)?;
+        } else {
+            fbs.new_fan(polygons, v0, v1, v2)?;
+        }
+    }
+    Ok(())
+}
@@ -1,3 +1,5 @@
+use alloc::{vec, vec::Vec};
+
 /// A triangle fan with vertices of type `V`
 pub trait Fan<V> {
     /// Initialize the fan with a single triangle.
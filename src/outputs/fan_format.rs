@@ -1,7 +1,10 @@
-use std::error;
+use core::error;
 
 use crate::{PolygonList, TriangleWinding, TriangulationError, formats::ReverseFanFormat};
 
+/// The output of triangulating into the [FanFormat] `FB`, or the error
+pub(crate) type FanResult<'p, P, FB> = Result<<<FB as FanFormat<'p, P>>::Builder as FanBuilder<'p, P>>::Output, TriangulationError<<<FB as FanFormat<'p, P>>::Builder as FanBuilder<'p, P>>::Error>>;
+
 /// Describes the construction and layout of a triangle fans
 pub trait FanFormat<'p, P: PolygonList<'p> + ?Sized> {
     /// The type responsible for constructing triangle fans.
@@ -0,0 +1,43 @@
+use core::{error, marker::PhantomData};
+
+use crate::{ListBuilder, ListFormat, PolygonList, TriangulationError};
+
+/// A [ListFormat] which passes each triangle to a closure instead of storing it, such as to write straight to a
+/// mapped GPU buffer or a file.
+///
+/// The closure can stop the triangulation early by returning an error, which is returned as
+/// [TriangulationError::FanBuilder]. Triangles already passed to it are not taken back.
+pub struct CallbackFormat<'p, P: PolygonList<'p> + ?Sized, F: FnMut(P::Index, P::Index, P::Index) -> Result<(), E>, E: error::Error> {
+    callback: F,
+    _phantom: PhantomData<(&'p P, E)>,
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, F: FnMut(P::Index, P::Index, P::Index) -> Result<(), E>, E: error::Error> CallbackFormat<'p, P, F, E> {
+    /// Create a format which calls `callback` with the indices of each triangle
+    pub fn new(callback: F) -> Self {
+        Self { callback, _phantom: PhantomData, }
+    }
+}
+
+impl <'p, P: PolygonList<'p> + ?Sized, F: FnMut(P::Index, P::Index, P::Index) -> Result<(), E>, E: error::Error> ListFormat<'p, P> for CallbackFormat<'p, P, F, E> {
+    type Builder = Self;
+
+    fn initialize(self, _polygon_list: &'p P) -> Result<Self::Builder, <Self::Builder as ListBuilder<'p, P>>::Error> {
+        Ok(self)
+    }
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, F: FnMut(P::Index, P::Index, P::Index) -> Result<(), E>, E: error::Error> ListBuilder<'p, P> for CallbackFormat<'p, P, F, E> {
+    type Output = ();
+    type Error = E;
+
+    fn add_triangle(&mut self, vi0: P::Index, vi1: P::Index, vi2: P::Index) -> Result<(), Self::Error> {
+        (self.callback)(vi0, vi1, vi2)
+    }
+
+    fn build(self) -> Result<Self::Output, Self::Error> {
+        Ok(())
+    }
+
+    fn fail(self, _error: &TriangulationError<Self::Error>) { }
+}
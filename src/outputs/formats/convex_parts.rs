@@ -0,0 +1,111 @@
+use core::marker::PhantomData;
+use alloc::vec::Vec;
+
+use crate::{compat::HashMap, DefaultKernel, GeometryKernel, ListFormat, ListBuilder, Orientation, PolygonList, TriangulationError};
+
+use super::NumberedTriangles;
+
+/// A [ListFormat] which merges the triangles into convex polygons by their indices, using the Hertel-Mehlhorn
+/// heuristic: each diagonal between two triangles is removed, in the order the triangles were generated, unless
+/// that would make a corner reflex. This produces at most four times as many polygons as the fewest possible.
+///
+/// Each polygon is in counterclockwise order. Triangles are only merged across edges whose end vertices have the
+/// same indices, so vertices must be distinct, as for triangulation.
+#[derive(Debug)]
+pub struct ConvexPartsFormat<'p, P: PolygonList<'p> + ?Sized, M: AsMut<Vec<Vec<P::Index>>>> {
+    parts: M,
+    _phantom: PhantomData<&'p P>,
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, M: AsMut<Vec<Vec<P::Index>>>> ConvexPartsFormat<'p, P, M> {
+    /// Create a convex parts format which stores its output in the given [Vec] of polygons
+    pub fn new(parts: M) -> Self {
+        Self { parts, _phantom: PhantomData, }
+    }
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, M: AsMut<Vec<Vec<P::Index>>>> ListFormat<'p, P> for ConvexPartsFormat<'p, P, M> {
+    type Builder = ConvexPartsBuilder<'p, P, M>;
+
+    fn initialize(self, polygon_list: &'p P) -> Result<Self::Builder, <Self::Builder as ListBuilder<'p, P>>::Error> {
+        ConvexPartsBuilder::new(self.parts, polygon_list)
+    }
+}
+
+pub struct ConvexPartsBuilder<'p, P: PolygonList<'p> + ?Sized, M: AsMut<Vec<Vec<P::Index>>>> {
+    parts: M,
+    polygon_list: &'p P,
+    triangles: Vec<[P::Index; 3]>,
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, M: AsMut<Vec<Vec<P::Index>>>> ConvexPartsBuilder<'p, P, M> {
+    fn new(parts: M, polygon_list: &'p P) -> Result<Self, <Self as ListBuilder<'p, P>>::Error> {
+        Ok(Self { parts, polygon_list, triangles: Vec::new() })
+    }
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, M: AsMut<Vec<Vec<P::Index>>>> ListBuilder<'p, P> for ConvexPartsBuilder<'p, P, M> {
+    type Output = M;
+    type Error = core::convert::Infallible;
+
+    fn add_triangle(&mut self, vi0: P::Index, vi1: P::Index, vi2: P::Index) -> Result<(), Self::Error> {
+        self.triangles.push([vi0, vi1, vi2]);
+        Ok(())
+    }
+
+    fn build(mut self) -> Result<Self::Output, Self::Error> {
+        let NumberedTriangles { triangles, positions, indices } = NumberedTriangles::new(self.polygon_list, core::mem::take(&mut self.triangles));
+
+        let mut parts = triangles.iter().map(|t| Some(t.to_vec())).collect::<Vec<_>>();
+        let mut owners = HashMap::<[usize; 2], usize>::new();
+        for (t, part) in parts.iter().enumerate() {
+            let part = part.as_ref().expect("All parts start as triangles");
+            for k in 0..3 {
+                owners.insert([part[k], part[(k + 1) % 3]], t);
+            }
+        }
+
+        let is_convex = |a: usize, b: usize, c: usize| {
+            DefaultKernel::orientation(&positions[a], &positions[b], &positions[c]) != Orientation::Right
+        };
+        for t in &triangles {
+            for k in 0..3 {
+                let (a, b) = (t[k], t[(k + 1) % 3]);
+                let (Some(&p), Some(&q)) = (owners.get(&[a, b]), owners.get(&[b, a])) else { continue };
+                if p == q {
+                    continue;
+                }
+
+                // Walk each part from the far end of the diagonal back to its near end
+                let (part_p, part_q) = (parts[p].as_ref().expect("Owned parts exist"), parts[q].as_ref().expect("Owned parts exist"));
+                let start_p = part_p.iter().position(|&id| id == b).expect("Owned edges are in their part");
+                let path_p = (0..part_p.len()).map(|i| part_p[(start_p + i) % part_p.len()]).collect::<Vec<_>>();
+                let start_q = part_q.iter().position(|&id| id == a).expect("Owned edges are in their part");
+                let path_q = (0..part_q.len()).map(|i| part_q[(start_q + i) % part_q.len()]).collect::<Vec<_>>();
+
+                if !is_convex(path_p[path_p.len() - 2], a, path_q[1]) || !is_convex(path_q[path_q.len() - 2], b, path_p[1]) {
+                    continue;
+                }
+
+                owners.remove(&[a, b]);
+                owners.remove(&[b, a]);
+                for i in 1..path_q.len() - 1 {
+                    owners.insert([path_q[i], path_q[i + 1]], p);
+                }
+                owners.insert([a, path_q[1]], p);
+                let mut merged = path_p;
+                merged.extend_from_slice(&path_q[1..path_q.len() - 1]);
+                parts[p] = Some(merged);
+                parts[q] = None;
+            }
+        }
+
+        let output = self.parts.as_mut();
+        for part in parts.into_iter().flatten() {
+            output.push(part.into_iter().map(|id| indices[id].clone()).collect());
+        }
+        Ok(self.parts)
+    }
+
+    fn fail(self, _error: &TriangulationError<Self::Error>) { }
+}
@@ -1,4 +1,4 @@
-use std::marker::PhantomData;
+use core::marker::PhantomData;
 
 use crate::{Fan, PolygonList, Fans, FanFormat, FanBuilder, TriangulationError};
 
@@ -58,7 +58,7 @@ impl<'p, P: PolygonList<'p> + ?Sized, FS: Fans> FanBuilder<'p, P> for DeindexedF
 where FS::Fan: Fan<P::Vertex>,
       P::Vertex: Clone {
     type Output = FS;
-    type Error = std::convert::Infallible;
+    type Error = core::convert::Infallible;
 
     fn new_fan(&mut self, vi0: P::Index, vi1: P::Index, vi2: P::Index) -> Result<(), Self::Error> {
         let (v0, v1, v2) = (self.polygon_list.get_vertex(vi0).clone(), self.polygon_list.get_vertex(vi1).clone(), self.polygon_list.get_vertex(vi2).clone());
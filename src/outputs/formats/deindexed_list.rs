@@ -1,4 +1,4 @@
-use std::marker::PhantomData;
+use core::marker::PhantomData;
 
 use crate::{ListFormat, PolygonList, TriangulationError, List, ListBuilder};
 
@@ -51,7 +51,7 @@ where P::Vertex: Clone {
 impl<'p, P: PolygonList<'p> + ?Sized, L: List<P::Vertex>> ListBuilder<'p, P> for DeindexedListBuilder<'p, P, L> 
 where P::Vertex: Clone {
     type Output = L;
-    type Error = std::convert::Infallible;
+    type Error = core::convert::Infallible;
 
     fn add_triangle(&mut self, vi0: P::Index, vi1: P::Index, vi2: P::Index) -> Result<(), Self::Error> {
         let (v0, v1, v2) = (self.polygon_list.get_vertex(vi0).clone(), self.polygon_list.get_vertex(vi1).clone(), self.polygon_list.get_vertex(vi2).clone());
@@ -0,0 +1,68 @@
+use core::marker::PhantomData;
+use alloc::vec::Vec;
+
+use crate::{mesh, ListFormat, ListBuilder, PolygonList, TriangleWinding, TriangulationError};
+
+use super::NumberedTriangles;
+
+/// A wrapper which flips the diagonals of a [ListFormat]'s triangles until they form the constrained Delaunay
+/// triangulation of the polygons, avoiding the long slivers triangulation otherwise tends to produce.
+///
+/// The triangles are collected, and only passed on to the wrapped format once triangulation completes. Edges of the
+/// polygons are never flipped. Constructed with [ListFormat::delaunay]; see [Mesh::flip_to_delaunay](crate::mesh::Mesh::flip_to_delaunay).
+pub struct DelaunayListFormat<'p, P: PolygonList<'p> + ?Sized, LF: ListFormat<'p, P>>(LF, PhantomData<&'p P>);
+
+impl<'p, P: PolygonList<'p> + ?Sized, LF: ListFormat<'p, P>> DelaunayListFormat<'p, P, LF> {
+    pub(crate) fn new(list_format: LF) -> Self {
+        Self(list_format, PhantomData)
+    }
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, LF: ListFormat<'p, P>> ListFormat<'p, P> for DelaunayListFormat<'p, P, LF> {
+    type Builder = DelaunayListBuilder<'p, P, LF::Builder>;
+
+    fn initialize(self, polygon_list: &'p P) -> Result<Self::Builder, <Self::Builder as ListBuilder<'p, P>>::Error> {
+        let list_builder = self.0.initialize(polygon_list)?;
+        Ok(DelaunayListBuilder { list_builder, polygon_list, triangles: Vec::new() })
+    }
+}
+
+pub struct DelaunayListBuilder<'p, P: PolygonList<'p> + ?Sized, LB: ListBuilder<'p, P>> {
+    list_builder: LB,
+    polygon_list: &'p P,
+    triangles: Vec<[P::Index; 3]>,
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, LB: ListBuilder<'p, P>> ListBuilder<'p, P> for DelaunayListBuilder<'p, P, LB> {
+    type Output = LB::Output;
+    type Error = LB::Error;
+
+    fn add_triangle(&mut self, vi0: P::Index, vi1: P::Index, vi2: P::Index) -> Result<(), Self::Error> {
+        self.triangles.push([vi0, vi1, vi2]);
+        Ok(())
+    }
+
+    fn build(mut self) -> Result<Self::Output, Self::Error> {
+        let NumberedTriangles { mut triangles, positions, indices } = NumberedTriangles::new(self.polygon_list, self.triangles);
+        mesh::flip_to_delaunay(&positions, &mut triangles);
+
+        for [vi0, vi1, vi2] in triangles {
+            let [vi0, vi1, vi2] = [vi0, vi1, vi2].map(|vi| indices[vi].clone());
+            let result = match LB::WINDING {
+                TriangleWinding::Counterclockwise => self.list_builder.add_triangle(vi0, vi1, vi2),
+                TriangleWinding::Clockwise => self.list_builder.add_triangle(vi0, vi2, vi1),
+            };
+            if let Err(error) = result {
+                let error = TriangulationError::FanBuilder(error);
+                self.list_builder.fail(&error);
+                let TriangulationError::FanBuilder(error) = error else { unreachable!() };
+                return Err(error);
+            }
+        }
+        self.list_builder.build()
+    }
+
+    fn fail(self, error: &TriangulationError<Self::Error>) {
+        self.list_builder.fail(error);
+    }
+}
@@ -1,5 +1,5 @@
 use core::fmt;
-use std::{error, marker::PhantomData};
+use core::{error, marker::PhantomData};
 
 use crate::{FanFormat, PolygonList, TriangulationError, outputs::fan_format::FanBuilder, Fans};
 
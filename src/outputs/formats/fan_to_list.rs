@@ -1,4 +1,4 @@
-use std::marker::PhantomData;
+use core::marker::PhantomData;
 
 use crate::{PolygonList, ListFormat, FanFormat, FanBuilder, ListBuilder, TriangleWinding};
 
@@ -56,7 +56,7 @@ impl<'p, P: PolygonList<'p> + ?Sized, LB: ListBuilder<'p, P>> FanBuilder<'p, P>
     }
 
     fn extend_fan(&mut self, vi: <P as PolygonList<'p>>::Index) -> Result<(), Self::Error> {
-        let vi1 = std::mem::replace(&mut self.vi1, vi.clone());
+        let vi1 = core::mem::replace(&mut self.vi1, vi.clone());
         self.list_builder.add_triangle(self.vi0.clone(), vi1, vi)
     }
 
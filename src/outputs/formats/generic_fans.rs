@@ -1,4 +1,4 @@
-use std::marker::PhantomData;
+use core::marker::PhantomData;
 
 use crate::{Fans, Fan};
 
@@ -24,7 +24,7 @@ where FS::Fan: Fan<V> {
     }
 
     pub fn new_fan(&mut self, v0: V, v1: V, v2: V) {
-        let prev_fan = std::mem::replace(&mut self.current_fan, FS::Fan::new(v0, v1, v2));
+        let prev_fan = core::mem::replace(&mut self.current_fan, FS::Fan::new(v0, v1, v2));
         self.fans.push(prev_fan);
     }
 
@@ -0,0 +1,124 @@
+use core::{error, fmt, marker::PhantomData};
+use alloc::{vec, vec::Vec};
+
+use crate::{ListBuilder, ListFormat, PolygonList, TriangulationError};
+
+use super::VertexNumbers;
+
+/// An error building [GpuBuffers]
+#[non_exhaustive]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum GpuBuffersError {
+    /// There are more vertices than the index type can number
+    IndexOverflow,
+    /// A triangle vertex is not an index of the polygons
+    UnknownVertex,
+}
+
+impl fmt::Display for GpuBuffersError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GpuBuffersError::IndexOverflow => write!(f, "A vertex index does not fit in the index type"),
+            GpuBuffersError::UnknownVertex => write!(f, "A triangle vertex is not an index of the polygons"),
+        }
+    }
+}
+
+impl error::Error for GpuBuffersError { }
+
+/// A vertex buffer of only the vertices used by the triangles, and an index buffer into it, built by [GpuBuffersFormat]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GpuBuffers<V, I> {
+    /// The vertices, in the order the triangles first use them
+    pub vertices: Vec<V>,
+    /// The indices into [GpuBuffers::vertices] of the corners of each triangle, three at a time
+    pub indices: Vec<I>,
+}
+
+impl<V, I> Default for GpuBuffers<V, I> {
+    fn default() -> Self {
+        Self { vertices: Vec::new(), indices: Vec::new() }
+    }
+}
+
+/// A [ListFormat] which outputs [GpuBuffers]: a compact vertex buffer, with each vertex of the [PolygonList] used by
+/// a triangle converted to `V` once, and indices of type `I` (such as `u16` or `u32`) into it.
+///
+/// Vertices which are not part of any triangle, such as those of polygons left out of the triangulation, are not
+/// copied. Triangulation fails with [GpuBuffersError::IndexOverflow] if an index does not fit in `I`.
+#[derive(Debug)]
+pub struct GpuBuffersFormat<'p, P: PolygonList<'p> + ?Sized, V: From<P::Vertex>, I: TryFrom<usize> + Copy>
+where P::Vertex: Clone {
+    _phantom: PhantomData<(&'p P, V, I)>,
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, V: From<P::Vertex>, I: TryFrom<usize> + Copy> GpuBuffersFormat<'p, P, V, I>
+where P::Vertex: Clone {
+    /// Create a format which builds new [GpuBuffers]
+    pub fn new() -> Self {
+        Self { _phantom: PhantomData, }
+    }
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, V: From<P::Vertex>, I: TryFrom<usize> + Copy> Default for GpuBuffersFormat<'p, P, V, I>
+where P::Vertex: Clone {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl <'p, P: PolygonList<'p> + ?Sized, V: From<P::Vertex>, I: TryFrom<usize> + Copy> ListFormat<'p, P> for GpuBuffersFormat<'p, P, V, I>
+where P::Vertex: Clone {
+    type Builder = GpuBuffersBuilder<'p, P, V, I>;
+
+    fn initialize(self, polygon_list: &'p P) -> Result<Self::Builder, <Self::Builder as ListBuilder<'p, P>>::Error> {
+        let numbers = VertexNumbers::new(polygon_list);
+        let remap = vec![None; numbers.vertex_count()];
+        Ok(GpuBuffersBuilder { polygon_list, numbers, remap, buffers: GpuBuffers::default() })
+    }
+}
+
+pub struct GpuBuffersBuilder<'p, P: PolygonList<'p> + ?Sized, V: From<P::Vertex>, I: TryFrom<usize> + Copy>
+where P::Vertex: Clone {
+    polygon_list: &'p P,
+    numbers: VertexNumbers<'p, P>,
+    // The index in the vertex buffer of each vertex of the polygon list, once used
+    remap: Vec<Option<I>>,
+    buffers: GpuBuffers<V, I>,
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, V: From<P::Vertex>, I: TryFrom<usize> + Copy> GpuBuffersBuilder<'p, P, V, I>
+where P::Vertex: Clone {
+    fn push_index(&mut self, vi: P::Index) -> Result<(), GpuBuffersError> {
+        let number = self.numbers.number(&vi).ok_or(GpuBuffersError::UnknownVertex)?;
+        let index = match self.remap[number] {
+            Some(index) => index,
+            None => {
+                let index = I::try_from(self.buffers.vertices.len()).map_err(|_| GpuBuffersError::IndexOverflow)?;
+                self.buffers.vertices.push(V::from(self.polygon_list.get_vertex(vi).clone()));
+                self.remap[number] = Some(index);
+                index
+            }
+        };
+        self.buffers.indices.push(index);
+        Ok(())
+    }
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, V: From<P::Vertex>, I: TryFrom<usize> + Copy> ListBuilder<'p, P> for GpuBuffersBuilder<'p, P, V, I>
+where P::Vertex: Clone {
+    type Output = GpuBuffers<V, I>;
+    type Error = GpuBuffersError;
+
+    fn add_triangle(&mut self, vi0: P::Index, vi1: P::Index, vi2: P::Index) -> Result<(), Self::Error> {
+        self.push_index(vi0)?;
+        self.push_index(vi1)?;
+        self.push_index(vi2)
+    }
+
+    fn build(self) -> Result<Self::Output, Self::Error> {
+        Ok(self.buffers)
+    }
+
+    fn fail(self, _error: &TriangulationError<Self::Error>) { }
+}
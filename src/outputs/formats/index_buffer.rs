@@ -0,0 +1,161 @@
+use core::{error, fmt, marker::PhantomData, mem::MaybeUninit};
+use alloc::vec::Vec;
+
+use crate::{ListBuilder, ListFormat, PolygonList, TriangulationError};
+
+use super::VertexNumbers;
+
+/// An error writing to an [IndexBuffer]
+#[non_exhaustive]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum IndexBufferError {
+    /// The buffer has no room for more indices
+    BufferFull,
+    /// A vertex number, after adding the base vertex, does not fit in a `u32`
+    IndexOverflow,
+    /// A triangle vertex is not an index of the polygons
+    UnknownVertex,
+}
+
+impl fmt::Display for IndexBufferError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IndexBufferError::BufferFull => write!(f, "The index buffer is full"),
+            IndexBufferError::IndexOverflow => write!(f, "A vertex index does not fit in u32"),
+            IndexBufferError::UnknownVertex => write!(f, "A triangle vertex is not an index of the polygons"),
+        }
+    }
+}
+
+impl error::Error for IndexBufferError { }
+
+/// A caller-provided buffer which [IndexBufferFormat] writes `u32` indices into
+pub trait IndexBuffer {
+    /// The number of indices already in the buffer, which are kept
+    fn initial_len(&self) -> usize;
+
+    /// Write `index` at `position`, which is one past the previously written position
+    fn write(&mut self, position: usize, index: u32) -> Result<(), IndexBufferError>;
+
+    /// Remove the indices written at and after `len`
+    fn truncate(&mut self, len: usize);
+}
+
+impl IndexBuffer for Vec<u32> {
+    fn initial_len(&self) -> usize {
+        self.len()
+    }
+
+    fn write(&mut self, _position: usize, index: u32) -> Result<(), IndexBufferError> {
+        self.push(index);
+        Ok(())
+    }
+
+    fn truncate(&mut self, len: usize) {
+        self.truncate(len)
+    }
+}
+
+impl IndexBuffer for [MaybeUninit<u32>] {
+    fn initial_len(&self) -> usize {
+        0
+    }
+
+    fn write(&mut self, position: usize, index: u32) -> Result<(), IndexBufferError> {
+        self.get_mut(position).ok_or(IndexBufferError::BufferFull)?.write(index);
+        Ok(())
+    }
+
+    fn truncate(&mut self, _len: usize) { }
+}
+
+impl<B: IndexBuffer + ?Sized> IndexBuffer for &mut B {
+    fn initial_len(&self) -> usize {
+        (**self).initial_len()
+    }
+
+    fn write(&mut self, position: usize, index: u32) -> Result<(), IndexBufferError> {
+        (**self).write(position, index)
+    }
+
+    fn truncate(&mut self, len: usize) {
+        (**self).truncate(len)
+    }
+}
+
+/// A [ListFormat] which writes the triangles as flat `u32` vertex numbers into a caller-provided [IndexBuffer], such
+/// as a `&mut Vec<u32>` shared by many polygons, or a `&mut [MaybeUninit<u32>]` mapped from a GPU buffer.
+///
+/// Vertices are numbered in the iteration order of the [PolygonList], starting at the base vertex, the same order
+/// [Mesh::from_polygons](crate::mesh::Mesh::from_polygons) copies them in. Indices are appended after those already
+/// in a [Vec], or written from the start of a slice; the output is the number of indices written. On failure, the
+/// indices added to a [Vec] are removed again.
+#[derive(Debug)]
+pub struct IndexBufferFormat<'p, P: PolygonList<'p> + ?Sized, B: IndexBuffer> {
+    buffer: B,
+    base_vertex: u32,
+    _phantom: PhantomData<&'p P>,
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, B: IndexBuffer> IndexBufferFormat<'p, P, B> {
+    /// Create a format which writes to `buffer`, numbering the first vertex `base_vertex`
+    pub fn new(buffer: B, base_vertex: u32) -> Self {
+        Self { buffer, base_vertex, _phantom: PhantomData, }
+    }
+}
+
+impl <'p, P: PolygonList<'p> + ?Sized, B: IndexBuffer> ListFormat<'p, P> for IndexBufferFormat<'p, P, B> {
+    type Builder = IndexBufferBuilder<'p, P, B>;
+
+    fn initialize(self, polygon_list: &'p P) -> Result<Self::Builder, <Self::Builder as ListBuilder<'p, P>>::Error> {
+        IndexBufferBuilder::new(self.buffer, self.base_vertex, polygon_list)
+    }
+}
+
+pub struct IndexBufferBuilder<'p, P: PolygonList<'p> + ?Sized, B: IndexBuffer> {
+    buffer: B,
+    base_vertex: u32,
+    numbers: VertexNumbers<'p, P>,
+    initial_len: usize,
+    len: usize,
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, B: IndexBuffer> IndexBufferBuilder<'p, P, B> {
+    fn new(buffer: B, base_vertex: u32, polygon_list: &'p P) -> Result<Self, <Self as ListBuilder<'p, P>>::Error> {
+        let numbers = VertexNumbers::new(polygon_list);
+        let last = numbers.vertex_count().checked_sub(1).map(|last| u32::try_from(last).ok().and_then(|last| base_vertex.checked_add(last)));
+        if let Some(None) = last {
+            return Err(IndexBufferError::IndexOverflow);
+        }
+        let initial_len = buffer.initial_len();
+        Ok(Self { buffer, base_vertex, numbers, initial_len, len: initial_len })
+    }
+
+    fn push_index(&mut self, vi: &P::Index) -> Result<(), IndexBufferError> {
+        let number = self.numbers.number(vi).ok_or(IndexBufferError::UnknownVertex)?;
+        let index = self.base_vertex + number as u32;
+        self.buffer.write(self.len, index)?;
+        self.len += 1;
+        Ok(())
+    }
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, B: IndexBuffer> ListBuilder<'p, P> for IndexBufferBuilder<'p, P, B> {
+    type Output = usize;
+    type Error = IndexBufferError;
+
+    fn add_triangle(&mut self, vi0: P::Index, vi1: P::Index, vi2: P::Index) -> Result<(), Self::Error> {
+        self.push_index(&vi0)?;
+        self.push_index(&vi1)?;
+        self.push_index(&vi2)?;
+        Ok(())
+    }
+
+    fn build(self) -> Result<Self::Output, Self::Error> {
+        Ok(self.len - self.initial_len)
+    }
+
+    fn fail(mut self, _error: &TriangulationError<Self::Error>) {
+        self.buffer.truncate(self.initial_len);
+    }
+}
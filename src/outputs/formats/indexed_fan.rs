@@ -1,4 +1,4 @@
-use std::marker::PhantomData;
+use core::marker::PhantomData;
 
 use crate::{FanFormat, PolygonList, TriangulationError, outputs::fan_format::FanBuilder, Fan, Fans};
 
@@ -49,7 +49,7 @@ where FS::Fan: Fan<P::Index> {
 impl<'p, P: PolygonList<'p> + ?Sized, FS: Fans> FanBuilder<'p, P> for IndexedFanBuilder<'p, P, FS>
 where FS::Fan: Fan<P::Index> {
     type Output = FS;
-    type Error = std::convert::Infallible;
+    type Error = core::convert::Infallible;
 
     fn new_fan(&mut self, vi0: P::Index, vi1: P::Index, vi2: P::Index) -> Result<(), Self::Error> {
         self.fans.new_fan(vi0, vi1, vi2);
@@ -1,4 +1,4 @@
-use std::marker::PhantomData;
+use core::marker::PhantomData;
 
 use crate::{ListFormat, PolygonList, TriangulationError, List, ListBuilder};
 
@@ -43,7 +43,7 @@ impl<'p, P: PolygonList<'p> + ?Sized, L: List<P::Index>> IndexedListBuilder<'p,
 
 impl<'p, P: PolygonList<'p> + ?Sized, L: List<P::Index>> ListBuilder<'p, P> for IndexedListBuilder<'p, P, L> {
     type Output = L;
-    type Error = std::convert::Infallible;
+    type Error = core::convert::Infallible;
 
     fn add_triangle(&mut self, vi0: P::Index, vi1: P::Index, vi2: P::Index) -> Result<(), Self::Error> {
         self.list.new_triangle(vi0, vi1, vi2);
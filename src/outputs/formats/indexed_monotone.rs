@@ -0,0 +1,58 @@
+use core::marker::PhantomData;
+use alloc::vec::Vec;
+
+use crate::{MonotoneFormat, MonotoneBuilder, PolygonList, TriangulationError};
+
+/// A [MonotoneFormat] which outputs each monotone polygon as a [Vec] of its indices
+#[derive(Debug)]
+pub struct IndexedMonotoneFormat<'p, P: PolygonList<'p> + ?Sized, M: AsMut<Vec<Vec<P::Index>>>> {
+    monotones: M,
+    _phantom: PhantomData<&'p P>,
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, M: AsMut<Vec<Vec<P::Index>>>> IndexedMonotoneFormat<'p, P, M> {
+    /// Create an indexed format which stores its output in the given [Vec] of polygons
+    pub fn new(monotones: M) -> Self {
+        Self { monotones, _phantom: PhantomData, }
+    }
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, M: AsMut<Vec<Vec<P::Index>>>> MonotoneFormat<'p, P> for IndexedMonotoneFormat<'p, P, M> {
+    type Builder = IndexedMonotoneBuilder<'p, P, M>;
+
+    fn initialize(self, polygon_list: &'p P) -> Result<Self::Builder, <Self::Builder as MonotoneBuilder<'p, P>>::Error> {
+        IndexedMonotoneBuilder::new(self.monotones, polygon_list)
+    }
+}
+
+pub struct IndexedMonotoneBuilder<'p, P: PolygonList<'p> + ?Sized, M: AsMut<Vec<Vec<P::Index>>>> {
+    monotones: M,
+    initial_count: usize,
+    _phantom: PhantomData<&'p P>,
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, M: AsMut<Vec<Vec<P::Index>>>> IndexedMonotoneBuilder<'p, P, M> {
+    fn new(mut monotones: M, _polygon_list: &'p P) -> Result<Self, <Self as MonotoneBuilder<'p, P>>::Error> {
+        let initial_count = monotones.as_mut().len();
+        Ok(Self { monotones, initial_count, _phantom: PhantomData })
+    }
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, M: AsMut<Vec<Vec<P::Index>>>> MonotoneBuilder<'p, P> for IndexedMonotoneBuilder<'p, P, M> {
+    type Output = M;
+    type Error = core::convert::Infallible;
+
+    fn add_monotone(&mut self, vertices: &[P::Index]) -> Result<(), Self::Error> {
+        self.monotones.as_mut().push(vertices.to_vec());
+        Ok(())
+    }
+
+    fn build(self) -> Result<Self::Output, Self::Error> {
+        Ok(self.monotones)
+    }
+
+    fn fail(mut self, _error: &TriangulationError<Self::Error>) {
+        let initial_count = self.initial_count;
+        self.monotones.as_mut().truncate(initial_count);
+    }
+}
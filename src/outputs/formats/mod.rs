@@ -1,5 +1,6 @@
 //! Predefined implementations of [FanFormat](crate::FanFormat), [FanBuilder](crate::FanBuilder), 
-//! [ListFormat](crate::ListFormat), and [ListBuilder](crate::ListBuilder)
+//! [ListFormat](crate::ListFormat), [ListBuilder](crate::ListBuilder), [MonotoneFormat](crate::MonotoneFormat), and
+//! [MonotoneBuilder](crate::MonotoneBuilder)
 
 mod generic_fans;
 pub(crate) use generic_fans::GenericFans;
@@ -18,4 +19,26 @@ pub use indexed_list::IndexedListFormat;
 mod deindexed_list;
 pub use deindexed_list::DeindexedListFormat;
 mod reverse_fan;
-pub use reverse_fan::ReverseFanFormat;
\ No newline at end of file
+pub use reverse_fan::ReverseFanFormat;
+mod numbered_triangles;
+pub(crate) use numbered_triangles::NumberedTriangles;
+mod vertex_numbers;
+pub(crate) use vertex_numbers::VertexNumbers;
+mod convex_parts;
+pub use convex_parts::ConvexPartsFormat;
+mod delaunay_list;
+pub use delaunay_list::DelaunayListFormat;
+mod indexed_monotone;
+pub use indexed_monotone::IndexedMonotoneFormat;
+mod index_buffer;
+pub use index_buffer::{IndexBufferFormat, IndexBuffer, IndexBufferError};
+mod callback;
+pub use callback::CallbackFormat;
+mod strip;
+pub use strip::{StripFormat, StripJoin, StripError};
+mod gpu_buffers;
+pub use gpu_buffers::{GpuBuffersFormat, GpuBuffers, GpuBuffersError};
+mod numbered_list;
+pub use numbered_list::{NumberedListFormat, NumberedListError};
+mod source_polygons;
+pub use source_polygons::SourcePolygonsFormat;
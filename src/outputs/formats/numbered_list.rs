@@ -0,0 +1,99 @@
+use core::{any, error, fmt, marker::PhantomData};
+
+use crate::{List, ListBuilder, ListFormat, PolygonList, TriangulationError};
+
+use super::{GenericList, VertexNumbers};
+
+/// An error building a [NumberedListFormat]
+#[non_exhaustive]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum NumberedListError {
+    /// The polygons have more vertices than the index type can number
+    IndexOverflow {
+        /// The number of vertices of the polygons
+        vertex_count: usize,
+        /// The name of the index type
+        index_type: &'static str,
+    },
+    /// A triangle vertex is not an index of the polygons
+    UnknownVertex,
+}
+
+impl fmt::Display for NumberedListError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NumberedListError::IndexOverflow { vertex_count, index_type } => write!(f, "The polygons have {} vertices, more than {} can number", vertex_count, index_type),
+            NumberedListError::UnknownVertex => write!(f, "A triangle vertex is not an index of the polygons"),
+        }
+    }
+}
+
+impl error::Error for NumberedListError { }
+
+/// A [ListFormat] which outputs the triangle list by vertex numbers of type `I` (such as `u16`, `u32` or `usize`),
+/// independent of the [PolygonList]'s own index type.
+///
+/// Vertices are numbered from zero in the iteration order of the [PolygonList], the same order
+/// [Mesh::from_polygons](crate::mesh::Mesh::from_polygons) copies them in. Triangulation fails up front with
+/// [NumberedListError::IndexOverflow] if the last vertex number does not fit in `I`.
+#[derive(Debug)]
+pub struct NumberedListFormat<'p, P: PolygonList<'p> + ?Sized, I: TryFrom<usize>, L: List<I>> {
+    list: L,
+    _phantom: PhantomData<(&'p P, I)>,
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, I: TryFrom<usize>, L: List<I>> NumberedListFormat<'p, P, I, L> {
+    /// Create a numbered format which stores its output in the given [List]
+    pub fn new(list: L) -> Self {
+        Self { list, _phantom: PhantomData, }
+    }
+}
+
+impl <'p, P: PolygonList<'p> + ?Sized, I: TryFrom<usize>, L: List<I>> ListFormat<'p, P> for NumberedListFormat<'p, P, I, L> {
+    type Builder = NumberedListBuilder<'p, P, I, L>;
+
+    fn initialize(self, polygon_list: &'p P) -> Result<Self::Builder, <Self::Builder as ListBuilder<'p, P>>::Error> {
+        NumberedListBuilder::new(self.list, polygon_list)
+    }
+}
+
+pub struct NumberedListBuilder<'p, P: PolygonList<'p> + ?Sized, I: TryFrom<usize>, L: List<I>> {
+    list: GenericList<L, I>,
+    numbers: VertexNumbers<'p, P>,
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, I: TryFrom<usize>, L: List<I>> NumberedListBuilder<'p, P, I, L> {
+    fn new(list: L, polygon_list: &'p P) -> Result<Self, <Self as ListBuilder<'p, P>>::Error> {
+        let numbers = VertexNumbers::new(polygon_list);
+        let vertex_count = numbers.vertex_count();
+        // Checking the last number once means every conversion afterwards succeeds
+        if vertex_count > 0 && I::try_from(vertex_count - 1).is_err() {
+            return Err(NumberedListError::IndexOverflow { vertex_count, index_type: any::type_name::<I>() });
+        }
+        Ok(Self { list: GenericList::new(list), numbers })
+    }
+
+    fn number(&self, vi: &P::Index) -> Result<I, NumberedListError> {
+        let number = self.numbers.number(vi).ok_or(NumberedListError::UnknownVertex)?;
+        I::try_from(number).map_err(|_| NumberedListError::IndexOverflow { vertex_count: self.numbers.vertex_count(), index_type: any::type_name::<I>() })
+    }
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, I: TryFrom<usize>, L: List<I>> ListBuilder<'p, P> for NumberedListBuilder<'p, P, I, L> {
+    type Output = L;
+    type Error = NumberedListError;
+
+    fn add_triangle(&mut self, vi0: P::Index, vi1: P::Index, vi2: P::Index) -> Result<(), Self::Error> {
+        let (n0, n1, n2) = (self.number(&vi0)?, self.number(&vi1)?, self.number(&vi2)?);
+        self.list.new_triangle(n0, n1, n2);
+        Ok(())
+    }
+
+    fn build(self) -> Result<Self::Output, Self::Error> {
+        Ok(self.list.build())
+    }
+
+    fn fail(self, _error: &TriangulationError<Self::Error>) {
+        self.list.fail();
+    }
+}
@@ -0,0 +1,51 @@
+use core::cmp;
+use alloc::{vec, vec::Vec};
+
+use crate::{PolygonList, Vertex};
+
+/// Triangles with their vertices numbered from zero, for formats which need the adjacency of the triangles, as
+/// indices can only be compared for equality
+pub(crate) struct NumberedTriangles<'p, P: PolygonList<'p> + ?Sized> {
+    pub triangles: Vec<[usize; 3]>,
+    pub positions: Vec<[<P::Vertex as Vertex>::Coordinate; 2]>,
+    pub indices: Vec<P::Index>,
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized> NumberedTriangles<'p, P> {
+    pub fn new(polygon_list: &'p P, triangles: Vec<[P::Index; 3]>) -> Self {
+        // Sorting the corners by position puts equal positions next to each other. Distinct indices can share a
+        // position, such as the touching vertices of weakly simple polygons, and their corners may be interleaved.
+        let corners = triangles.into_iter().flatten().collect::<Vec<_>>();
+        let corner_positions = corners.iter().map(|vi| {
+            let v = polygon_list.get_vertex(vi.clone());
+            [v.x(), v.y()]
+        }).collect::<Vec<_>>();
+        let compare = |i: usize, j: usize| {
+            let ([xi, yi], [xj, yj]) = (&corner_positions[i], &corner_positions[j]);
+            xi.partial_cmp(xj).unwrap_or(cmp::Ordering::Equal).then(yi.partial_cmp(yj).unwrap_or(cmp::Ordering::Equal))
+        };
+        let mut order = (0..corners.len()).collect::<Vec<_>>();
+        order.sort_by(|&i, &j| compare(i, j));
+
+        let mut ids = vec![0; corners.len()];
+        let mut positions = Vec::new();
+        let mut indices: Vec<P::Index> = Vec::new();
+        // The first id of the indices at the current position
+        let mut id_start = 0;
+        for (k, &i) in order.iter().enumerate() {
+            if k == 0 || compare(order[k - 1], i) != cmp::Ordering::Equal {
+                id_start = indices.len();
+            }
+            ids[i] = match indices[id_start..].iter().position(|vi| *vi == corners[i]) {
+                Some(offset) => id_start + offset,
+                None => {
+                    positions.push(corner_positions[i].clone());
+                    indices.push(corners[i].clone());
+                    indices.len() - 1
+                }
+            };
+        }
+        let triangles = ids.chunks_exact(3).map(|t| [t[0], t[1], t[2]]).collect();
+        Self { triangles, positions, indices }
+    }
+}
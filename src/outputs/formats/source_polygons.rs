@@ -0,0 +1,90 @@
+use core::marker::PhantomData;
+use alloc::vec::Vec;
+
+use num_traits::real::Real;
+
+use crate::{containment::ContainmentTree, ListBuilder, ListFormat, PolygonElement, PolygonList, TriangleWinding, TriangulationError, Vertex};
+
+use super::VertexNumbers;
+
+/// A wrapper which records the source polygon of each triangle added to a [ListFormat], such as to give each feature of
+/// a multipolygon its own color after triangulating them together.
+///
+/// Polygons are numbered by the non-empty polygons of the [PolygonList] in iteration order, as in the
+/// [containment](crate::containment) module. A triangle's source is the polygon whose inside it is in: the outer
+/// boundary of the holes around it, or an island inside a hole. Sources are appended to the [Vec] in the order the
+/// triangles are added, and removed again on failure. A triangle whose corners are not indices of the [PolygonList]
+/// has the source [usize::MAX]. Constructed with [ListFormat::with_source_polygons].
+pub struct SourcePolygonsFormat<'p, P: PolygonList<'p> + ?Sized, LF: ListFormat<'p, P>, S: AsMut<Vec<usize>>> {
+    list_format: LF,
+    sources: S,
+    _phantom: PhantomData<&'p P>,
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, LF: ListFormat<'p, P>, S: AsMut<Vec<usize>>> SourcePolygonsFormat<'p, P, LF, S> {
+    pub(crate) fn new(list_format: LF, sources: S) -> Self {
+        Self { list_format, sources, _phantom: PhantomData }
+    }
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, LF: ListFormat<'p, P>, S: AsMut<Vec<usize>>> ListFormat<'p, P> for SourcePolygonsFormat<'p, P, LF, S>
+where <P::Vertex as Vertex>::Coordinate: Real {
+    type Builder = SourcePolygonsBuilder<'p, P, LF::Builder, S>;
+
+    fn initialize(self, polygon_list: &'p P) -> Result<Self::Builder, <Self::Builder as ListBuilder<'p, P>>::Error> {
+        let list_builder = self.list_format.initialize(polygon_list)?;
+
+        // The polygon of each vertex by number, skipping empty polygons
+        let mut polygon_of = Vec::with_capacity(polygon_list.vertex_count());
+        let mut polygon = 0;
+        for element in polygon_list.iter_indices().map(Into::into) {
+            match element {
+                PolygonElement::ContinuePolygon(_) => polygon_of.push(polygon),
+                PolygonElement::NewPolygon => if polygon_of.last() == Some(&polygon) {
+                    polygon += 1;
+                },
+            }
+        }
+        // Holes are filled by their parent
+        let tree = ContainmentTree::new(polygon_list);
+        let filled = (0..tree.len()).map(|ci| if tree.is_hole(ci) { tree.parent(ci).unwrap_or(ci) } else { ci }).collect();
+
+        let mut sources = self.sources;
+        let initial_len = sources.as_mut().len();
+        Ok(SourcePolygonsBuilder { list_builder, numbers: VertexNumbers::new(polygon_list), polygon_of, filled, sources, initial_len })
+    }
+}
+
+pub struct SourcePolygonsBuilder<'p, P: PolygonList<'p> + ?Sized, LB: ListBuilder<'p, P>, S: AsMut<Vec<usize>>> {
+    list_builder: LB,
+    numbers: VertexNumbers<'p, P>,
+    polygon_of: Vec<usize>,
+    filled: Vec<usize>,
+    sources: S,
+    initial_len: usize,
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, LB: ListBuilder<'p, P>, S: AsMut<Vec<usize>>> ListBuilder<'p, P> for SourcePolygonsBuilder<'p, P, LB, S> {
+    type Output = LB::Output;
+    type Error = LB::Error;
+
+    const WINDING: TriangleWinding = LB::WINDING;
+
+    fn add_triangle(&mut self, vi0: P::Index, vi1: P::Index, vi2: P::Index) -> Result<(), Self::Error> {
+        // The corners of a triangle are on the boundary of the same region, so any one of them finds its polygon
+        let source = [&vi0, &vi1, &vi2].into_iter().find_map(|vi| self.numbers.number(vi))
+            .map_or(usize::MAX, |number| self.filled[self.polygon_of[number]]);
+        self.list_builder.add_triangle(vi0, vi1, vi2)?;
+        self.sources.as_mut().push(source);
+        Ok(())
+    }
+
+    fn build(self) -> Result<Self::Output, Self::Error> {
+        self.list_builder.build()
+    }
+
+    fn fail(mut self, error: &TriangulationError<Self::Error>) {
+        self.sources.as_mut().truncate(self.initial_len);
+        self.list_builder.fail(error);
+    }
+}
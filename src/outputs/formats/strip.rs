@@ -0,0 +1,170 @@
+use core::{error, fmt, marker::PhantomData};
+use alloc::vec::Vec;
+
+use crate::{FanFormat, PolygonList, TriangulationError, outputs::fan_format::FanBuilder};
+
+/// How [StripFormat] joins one triangle strip to the next
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum StripJoin<Index> {
+    /// Separate the strips with this primitive restart index, such as `u32::MAX`
+    Restart(Index),
+    /// Join the strips into one with degenerate (zero-area) triangles, by repeating vertices
+    Degenerate,
+}
+
+/// An error building a triangle strip
+#[non_exhaustive]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum StripError {
+    /// A vertex index equal to the [StripJoin::Restart] index was encountered
+    IndexMatchesRestart,
+}
+
+impl fmt::Display for StripError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StripError::IndexMatchesRestart => write!(f, "A vertex index equal to the restart index was encountered"),
+        }
+    }
+}
+
+impl error::Error for StripError { }
+
+/// A [FanFormat] which outputs the triangles as triangle strips, built while the fans are constructed.
+///
+/// In a strip, every index after the first two adds a triangle with the two before it, with the order of those two
+/// swapped for every second triangle so that all triangles have the same winding. A triangle which continues the
+/// current strip costs one index, otherwise a new strip is started and joined to the previous one as set by
+/// [StripJoin].
+///
+/// Indices are appended to those already in the [Vec]. With [StripJoin::Restart], a restart index separates them from
+/// the new strips; with [StripJoin::Degenerate], the existing indices are assumed to be a single strip, such as the
+/// output of an earlier triangulation with the same [Vec], and the new strips are joined to it.
+#[derive(Debug)]
+pub struct StripFormat<'p, P: PolygonList<'p> + ?Sized, S: AsMut<Vec<P::Index>>> {
+    strip: S,
+    join: StripJoin<P::Index>,
+    _phantom: PhantomData<&'p P>,
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, S: AsMut<Vec<P::Index>>> StripFormat<'p, P, S> {
+    /// Create a strip format which stores its output in the given [Vec]
+    pub fn new(strip: S, join: StripJoin<P::Index>) -> Self {
+        Self { strip, join, _phantom: PhantomData, }
+    }
+}
+
+impl <'p, P: PolygonList<'p> + ?Sized, S: AsMut<Vec<P::Index>>> FanFormat<'p, P> for StripFormat<'p, P, S> {
+    type Builder = StripBuilder<'p, P, S>;
+
+    fn initialize(self, polygon_list: &'p P, vi0: P::Index, vi1: P::Index, vi2: P::Index) -> Result<Self::Builder, <Self::Builder as FanBuilder<'p, P>>::Error> {
+        StripBuilder::new(self.strip, self.join, polygon_list, vi0, vi1, vi2)
+    }
+}
+
+#[derive(Debug)]
+pub struct StripBuilder<'p, P: PolygonList<'p> + ?Sized, S: AsMut<Vec<P::Index>>> {
+    strip: S,
+    join: StripJoin<P::Index>,
+    initial_len: usize,
+    // Where the current strip starts, which decides the winding of its next triangle
+    strip_start: usize,
+    // The center and last vertex of the current fan
+    center: P::Index,
+    last: P::Index,
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, S: AsMut<Vec<P::Index>>> StripBuilder<'p, P, S> {
+    fn new(mut strip: S, join: StripJoin<P::Index>, _polygon_list: &'p P, vi0: P::Index, vi1: P::Index, vi2: P::Index) -> Result<Self, <Self as FanBuilder<'p, P>>::Error> {
+        let initial_len = strip.as_mut().len();
+        let strip_start = match join {
+            StripJoin::Restart(_) => initial_len,
+            StripJoin::Degenerate => 0,
+        };
+        let mut sb = Self {
+            strip,
+            join,
+            initial_len,
+            strip_start,
+            center: vi0.clone(),
+            last: vi2.clone(),
+        };
+        match sb.add_triangle([vi0, vi1, vi2]) {
+            Ok(()) => Ok(sb),
+            Err(e) => {
+                // There is no builder to fail, so undo the first triangle here
+                sb.strip.as_mut().truncate(initial_len);
+                Err(e)
+            }
+        }
+    }
+
+    fn add_triangle(&mut self, t: [P::Index; 3]) -> Result<(), StripError> {
+        let len = self.strip.as_mut().len();
+        if len >= self.strip_start + 3 {
+            // The next triangle is formed with the last two indices, swapped after an odd number of triangles
+            let strip = self.strip.as_mut();
+            let (p, q) = if (len - self.strip_start) % 2 == 1 { (&strip[len - 1], &strip[len - 2]) } else { (&strip[len - 2], &strip[len - 1]) };
+            if let Some(k) = (0..3).find(|&k| t[k] == *p && t[(k + 1) % 3] == *q) {
+                let vi = t[(k + 2) % 3].clone();
+                return self.push_index(vi);
+            }
+        }
+
+        if len > 0 {
+            match self.join.clone() {
+                StripJoin::Restart(restart) => {
+                    self.strip.as_mut().push(restart);
+                    self.strip_start = len + 1;
+                }
+                StripJoin::Degenerate => {
+                    // Repeating the last and first vertices makes every triangle between the strips degenerate, and a
+                    // third repeat puts the new strip's first triangle at an even position
+                    let strip = self.strip.as_mut();
+                    strip.push(strip[len - 1].clone());
+                    strip.push(t[0].clone());
+                    if (strip.len() - self.strip_start) % 2 == 1 {
+                        strip.push(t[0].clone());
+                    }
+                }
+            }
+        }
+        let [vi0, vi1, vi2] = t;
+        self.push_index(vi0)?;
+        self.push_index(vi1)?;
+        self.push_index(vi2)
+    }
+
+    fn push_index(&mut self, vi: P::Index) -> Result<(), StripError> {
+        if matches!(&self.join, StripJoin::Restart(restart) if *restart == vi) {
+            Err(StripError::IndexMatchesRestart)
+        } else {
+            self.strip.as_mut().push(vi);
+            Ok(())
+        }
+    }
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, S: AsMut<Vec<P::Index>>> FanBuilder<'p, P> for StripBuilder<'p, P, S> {
+    type Output = S;
+    type Error = StripError;
+
+    fn new_fan(&mut self, vi0: P::Index, vi1: P::Index, vi2: P::Index) -> Result<(), Self::Error> {
+        self.center = vi0.clone();
+        self.last = vi2.clone();
+        self.add_triangle([vi0, vi1, vi2])
+    }
+
+    fn extend_fan(&mut self, vi: P::Index) -> Result<(), Self::Error> {
+        let last = core::mem::replace(&mut self.last, vi.clone());
+        self.add_triangle([self.center.clone(), last, vi])
+    }
+
+    fn build(self) -> Result<Self::Output, Self::Error> {
+        Ok(self.strip)
+    }
+
+    fn fail(mut self, _error: &TriangulationError<Self::Error>) {
+        self.strip.as_mut().truncate(self.initial_len);
+    }
+}
@@ -0,0 +1,44 @@
+use core::cmp;
+use alloc::vec::Vec;
+
+use crate::{PolygonElement, PolygonList, Vertex};
+
+/// The position of each vertex of a [PolygonList] in its iteration order, for formats which number the vertices
+/// from zero. Indices can only be compared for equality, so they are found among the vertices at their position.
+pub(crate) struct VertexNumbers<'p, P: PolygonList<'p> + ?Sized> {
+    polygon_list: &'p P,
+    // The vertex indices in iteration order, and their numbers sorted by position
+    indices: Vec<P::Index>,
+    by_position: Vec<usize>,
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized> VertexNumbers<'p, P> {
+    pub fn new(polygon_list: &'p P) -> Self {
+        let indices: Vec<P::Index> = polygon_list.iter_indices().filter_map(|element| match element.into() {
+            PolygonElement::ContinuePolygon(index) => Some(index),
+            PolygonElement::NewPolygon => None,
+        }).collect();
+        let mut by_position = (0..indices.len()).collect::<Vec<_>>();
+        by_position.sort_by(|&i, &j| Self::compare(polygon_list.get_vertex(indices[i].clone()), polygon_list.get_vertex(indices[j].clone())));
+        Self { polygon_list, indices, by_position }
+    }
+
+    /// The number of vertices
+    pub fn vertex_count(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// The number of the vertex `vi`, or [None] if `vi` is not an index of the [PolygonList]
+    pub fn number(&self, vi: &P::Index) -> Option<usize> {
+        let v = self.polygon_list.get_vertex(vi.clone());
+        let vertex = |n: usize| self.polygon_list.get_vertex(self.indices[n].clone());
+        let start = self.by_position.partition_point(|&n| Self::compare(vertex(n), v) == cmp::Ordering::Less);
+        self.by_position[start..].iter().copied()
+            .take_while(|&n| Self::compare(vertex(n), v) == cmp::Ordering::Equal)
+            .find(|&n| self.indices[n] == *vi)
+    }
+
+    fn compare(a: &P::Vertex, b: &P::Vertex) -> cmp::Ordering {
+        a.x().partial_cmp(&b.x()).unwrap_or(cmp::Ordering::Equal).then(a.y().partial_cmp(&b.y()).unwrap_or(cmp::Ordering::Equal))
+    }
+}
@@ -1,3 +1,5 @@
+use alloc::vec::Vec;
+
 /// A list of triangles represented as triplets of vertices of type `V`
 pub trait List<V> {
     /// Add a new triangle to the list
@@ -1,6 +1,9 @@
-use std::error;
+use core::error;
+use alloc::vec::Vec;
 
-use crate::{PolygonList, TriangleWinding, TriangulationError, formats};
+use num_traits::real::Real;
+
+use crate::{PolygonList, TriangleWinding, TriangulationError, Vertex, formats};
 
 /// Describes the construction and layout of a triangle list
 pub trait ListFormat<'p, P: PolygonList<'p> + ?Sized> {
@@ -17,6 +20,36 @@ pub trait ListFormat<'p, P: PolygonList<'p> + ?Sized> {
     where Self: Sized {
         formats::FanToListFormat::new(self)
     }
+
+    /// Flips the diagonals of the triangles to form the constrained Delaunay triangulation before they are added to
+    /// this [ListFormat], improving the shape of the triangles.
+    fn delaunay(self) -> formats::DelaunayListFormat<'p, P, Self>
+    where Self: Sized {
+        formats::DelaunayListFormat::new(self)
+    }
+
+    /// Appends the number of the source polygon of each triangle added to this [ListFormat] to `sources`, such as to
+    /// tell which feature of a multipolygon a triangle belongs to.
+    ///
+    /// ```
+    /// # use triangulate::{formats, ListFormat, PolygonList};
+    /// // Two squares, the first with a hole
+    /// let polygons = vec![
+    ///     vec![[0f64, 0.], [3., 0.], [3., 3.], [0., 3.]],
+    ///     vec![[1., 1.], [1., 2.], [2., 2.], [2., 1.]],
+    ///     vec![[4., 0.], [5., 0.], [5., 1.], [4., 1.]],
+    /// ];
+    /// let mut sources = Vec::new();
+    /// let triangles: Vec<[[usize; 2]; 3]> = polygons.triangulate(formats::IndexedListFormat::new(Vec::new()).with_source_polygons(&mut sources).into_fan_format()).expect("Triangulation failed");
+    /// assert_eq!(sources.len(), triangles.len());
+    /// assert_eq!(sources.iter().filter(|p| **p == 0).count(), 8);
+    /// assert_eq!(sources.iter().filter(|p| **p == 2).count(), 2);
+    /// ```
+    fn with_source_polygons<S: AsMut<Vec<usize>>>(self, sources: S) -> formats::SourcePolygonsFormat<'p, P, Self, S>
+    where Self: Sized,
+          <P::Vertex as Vertex>::Coordinate: Real {
+        formats::SourcePolygonsFormat::new(self, sources)
+    }
 }
 
 /// Performs the construction of a triangle list
@@ -2,8 +2,12 @@ pub mod formats;
 
 mod fan_format;
 pub use fan_format::{FanFormat, FanBuilder};
+pub(crate) use fan_format::FanResult;
 mod list_format;
 pub use list_format::{ListFormat, ListBuilder};
+mod monotone_format;
+pub use monotone_format::{MonotoneFormat, MonotoneBuilder};
+pub(crate) use monotone_format::MonotoneResult;
 mod triangle_winding;
 pub use triangle_winding::TriangleWinding;
 mod fan;
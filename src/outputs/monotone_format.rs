@@ -0,0 +1,40 @@
+use core::error;
+
+use crate::{PolygonList, TriangleWinding, TriangulationError};
+
+/// The output of decomposing into the [MonotoneFormat] `MF`, or the error
+pub(crate) type MonotoneResult<'p, P, MF> = Result<<<MF as MonotoneFormat<'p, P>>::Builder as MonotoneBuilder<'p, P>>::Output, TriangulationError<<<MF as MonotoneFormat<'p, P>>::Builder as MonotoneBuilder<'p, P>>::Error>>;
+
+/// Describes the construction and layout of the monotone polygons which triangulation splits the polygons into
+pub trait MonotoneFormat<'p, P: PolygonList<'p> + ?Sized> {
+    /// The type responsible for constructing the monotone polygons.
+    /// 
+    /// This type can be `Self`, if you choose to implement both [MonotoneFormat] and [MonotoneBuilder] on the same type.
+    type Builder: MonotoneBuilder<'p, P> + Sized;
+
+    /// Constructs a [MonotoneFormat::Builder], optionally using a reference to the [PolygonList] being decomposed.
+    fn initialize(self, polygon_list: &'p P) -> Result<Self::Builder, <Self::Builder as MonotoneBuilder<'p, P>>::Error>;
+}
+
+/// Performs the construction of monotone polygons
+pub trait MonotoneBuilder<'p, P: PolygonList<'p> + ?Sized> {
+    /// The monotone polygons output type
+    type Output;
+    /// The error type when the builder fails
+    type Error: error::Error;
+
+    /// The winding direction this builder expects for polygons
+    const WINDING: TriangleWinding = TriangleWinding::Counterclockwise;
+
+    /// Adds a polygon with the given indices, which is monotone with respect to the y axis: one side is a single
+    /// edge from its lowest to its highest vertex. It starts at its highest vertex
+    fn add_monotone(&mut self, vertices: &[P::Index]) -> Result<(), Self::Error>;
+
+    /// Called when decomposition has completed to get the resulting output
+    fn build(self) -> Result<Self::Output, Self::Error>;
+
+    /// Called when decomposition encounters an error.
+    /// 
+    /// Any required cleanup (e.g. removing the partial output added to an existing [Vec]) should be done here
+    fn fail(self, error: &TriangulationError<Self::Error>);
+}
@@ -0,0 +1,122 @@
+//! Approximate triangulation of huge inputs, for previews.
+//!
+//! [preview] snaps the vertices to a grid and decimates the contours before triangulating them, which is much faster
+//! for detailed inputs such as imported maps and vector art, where many vertices are closer together than a pixel.
+//! Alongside the mesh, it returns a bound on how far its area may be from the input's, so an editor can draw the
+//! preview while the exact triangulation runs in the background, and know when the preview is good enough to keep.
+//!
+//! ```
+//! # use triangulate::preview;
+//! // A circle with a vertex every tenth of a degree
+//! let circle: Vec<[f64; 2]> = (0..3600).map(|i| {
+//!     let (sin, cos) = (i as f64 / 3600. * std::f64::consts::TAU).sin_cos();
+//!     [100. * cos, 100. * sin]
+//! }).collect();
+//! let preview = preview::preview(&vec![circle], 0.5, 0.5).expect("Triangulation failed");
+//! assert!(preview.mesh.vertices.len() < 100);
+//! assert!(preview.error <= 0.5 * 2f64.sqrt() / 2. + 0.5);
+//! ```
+
+use core::{convert::Infallible, fmt};
+use alloc::{vec, vec::Vec};
+
+use num_traits::real::Real;
+
+use crate::{boolean::{self, BooleanOp, Contours}, mesh::Mesh, PolygonList, PolygonListExt, TriangulationError, Vertex};
+
+/// An approximate triangulation made by [preview]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Preview<C: Real + fmt::Debug> {
+    /// The triangulation of the simplified contours
+    pub mesh: Mesh<[C; 2]>,
+    /// Every point covered by the input but not by [Preview::mesh], or by the mesh but not by the input, lies within
+    /// this distance of the input's contours (up to rounding)
+    pub error: C,
+}
+
+/// Triangulate a simplified version of `polygons`, for a quick preview of the result of triangulating them.
+///
+/// Vertices are first rounded to the nearest multiple of `grid` in each coordinate, so that vertices in the same cell
+/// are merged, and then contours are decimated by the Douglas-Peucker algorithm, dropping vertices closer than
+/// `tolerance` to the line between their neighbors. Contours crossing each other after simplification are resolved by
+/// the even-odd rule, like the input. A `grid` of zero or less disables snapping, and a `tolerance` of zero drops only
+/// vertices in the middle of straight edges.
+///
+/// [Preview::error] is measured from the moves actually made, so it is at most half the diagonal of a grid cell plus
+/// `tolerance`, and often much less.
+pub fn preview<'p, P, C>(polygons: &'p P, grid: C, tolerance: C) -> Result<Preview<C>, TriangulationError<Infallible>>
+where P: PolygonList<'p> + ?Sized,
+      P::Vertex: Vertex<Coordinate=C>,
+      C: Real + fmt::Debug {
+    let mut snap_error = C::zero();
+    let mut decimation_error = C::zero();
+    let simplified: Contours<C> = PolygonListExt::new(polygons).contours().into_iter().filter_map(|contour| {
+        let mut snapped: Vec<[C; 2]> = Vec::with_capacity(contour.len());
+        for c in contour {
+            let s = if grid > C::zero() { c.map(|x| (x / grid).round() * grid) } else { c };
+            snap_error = snap_error.max(distance(c, s));
+            if snapped.last() != Some(&s) {
+                snapped.push(s);
+            }
+        }
+        while snapped.len() > 1 && snapped.first() == snapped.last() {
+            snapped.pop();
+        }
+        let (decimated, error) = decimate(&snapped, tolerance);
+        decimation_error = decimation_error.max(error);
+        (decimated.len() >= 3).then_some(decimated)
+    }).collect();
+
+    // Resolve the crossings that moving the vertices may have introduced
+    let resolved = boolean::overlay(&simplified, &Vec::new(), BooleanOp::Union);
+    Ok(Preview { mesh: Mesh::from_polygons(&resolved)?, error: snap_error + decimation_error })
+}
+
+// Douglas-Peucker decimation of a closed ring, also returning the greatest distance of a dropped vertex from the edge
+// replacing it
+fn decimate<C: Real>(ring: &[[C; 2]], tolerance: C) -> (Vec<[C; 2]>, C) {
+    if ring.len() < 3 {
+        return (ring.to_vec(), C::zero());
+    }
+    // Split the ring into two chains at the vertex farthest from the first
+    let far = (1..ring.len()).fold(1, |far, i| if distance(ring[0], ring[i]) > distance(ring[0], ring[far]) { i } else { far });
+    let mut keep = vec![false; ring.len()];
+    keep[0] = true;
+    keep[far] = true;
+    let mut error = C::zero();
+    let mut stack = vec![(0, far), (far, ring.len())];
+    while let Some((start, end)) = stack.pop() {
+        let (a, b) = (ring[start], ring[end % ring.len()]);
+        let farthest = (start + 1..end).map(|i| (i, segment_distance(ring[i], a, b)))
+            .fold(None, |farthest: Option<(usize, C)>, (i, d)| match farthest {
+                Some((_, fd)) if fd >= d => farthest,
+                _ => Some((i, d)),
+            });
+        match farthest {
+            Some((i, d)) if d > tolerance => {
+                keep[i] = true;
+                stack.push((start, i));
+                stack.push((i, end));
+            }
+            Some((_, d)) => error = error.max(d),
+            None => {}
+        }
+    }
+    (ring.iter().zip(keep).filter_map(|(c, keep)| keep.then_some(*c)).collect(), error)
+}
+
+// The distance from `p` to the segment from `a` to `b`
+fn segment_distance<C: Real>(p: [C; 2], a: [C; 2], b: [C; 2]) -> C {
+    let d = [b[0] - a[0], b[1] - a[1]];
+    let length_squared = d[0] * d[0] + d[1] * d[1];
+    if length_squared == C::zero() {
+        return distance(p, a);
+    }
+    let t = ((p[0] - a[0]) * d[0] + (p[1] - a[1]) * d[1]) / length_squared;
+    let t = t.max(C::zero()).min(C::one());
+    distance(p, [a[0] + d[0] * t, a[1] + d[1] * t])
+}
+
+fn distance<C: Real>(a: [C; 2], b: [C; 2]) -> C {
+    (b[0] - a[0]).hypot(b[1] - a[1])
+}
@@ -1,11 +1,12 @@
 use core::fmt;
-use std::clone;
+use core::clone;
 
-use num_traits::real::Real;
+use crate::Scalar;
 
 use crate::{Vertex, VertexIndex, idx::{Idx, IdxDisplay}, trapezoid::Trapezoid, Coords};
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(bound(serialize = "V::Coordinate: serde::Serialize", deserialize = "V::Coordinate: serde::Deserialize<'de>")))]
 pub(crate) enum QueryNode<V: Vertex, Index: VertexIndex> {
     Branch(Idx<QueryNode<V, Index>>, Idx<QueryNode<V, Index>>, QueryNodeBranch<V::Coordinate>),
     Sink(Idx<Trapezoid<V, Index>>),
@@ -29,12 +30,13 @@ impl<V: Vertex, Index: VertexIndex> fmt::Display for QueryNode<V, Index> {
     }
 }
 
-pub(crate) enum QueryNodeBranch<C: Real> {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) enum QueryNodeBranch<C: Scalar> {
     X(Coords<C>, Coords<C>),
     Y(Coords<C>),
 }
 
-impl<C: Real> fmt::Debug for QueryNodeBranch<C> {
+impl<C: Scalar> fmt::Debug for QueryNodeBranch<C> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::X(c_min_x, c_max_x) => f.debug_tuple("X").field(c_min_x).field(c_max_x).finish(),
@@ -43,16 +45,16 @@ impl<C: Real> fmt::Debug for QueryNodeBranch<C> {
     }
 }
 
-impl<C: Real> clone::Clone for QueryNodeBranch<C> {
+impl<C: Scalar> clone::Clone for QueryNodeBranch<C> {
     fn clone(&self) -> Self {
         match self {
-            Self::X(c_min, c_max) => Self::X(*c_min, *c_max),
-            Self::Y(c) => Self::Y(*c),
+            Self::X(c_min, c_max) => Self::X(c_min.clone(), c_max.clone()),
+            Self::Y(c) => Self::Y(c.clone()),
         }
     }
 }
 
-impl<C: Real> fmt::Display for QueryNodeBranch<C> {
+impl<C: Scalar> fmt::Display for QueryNodeBranch<C> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::X(c_min_x, c_max_x) => write!(f, "X({}, {})", c_min_x, c_max_x),
@@ -65,7 +67,7 @@ pub struct IndexedQueryNode<'a, V: Vertex, Index: VertexIndex>(Idx<QueryNode<V,
 
 impl<V: Vertex, Index: VertexIndex> QueryNode<V, Index> {
     #[cfg(feature = "_debugging")]
-    pub fn as_text_tree<'a>(&'a self, qi: Idx<Self>, qs: &'a [Self]) -> text_trees::TreeNode<IndexedQueryNode<'a, V, Index>> {
+    pub fn as_text_tree<'a>(&'a self, qi: Idx<Self>, qs: &'a crate::idx::ForkableVec<Self>) -> text_trees::TreeNode<IndexedQueryNode<'a, V, Index>> {
         let node = IndexedQueryNode(qi, self.into());
         match self {
             QueryNode::Branch(left, right, _) => text_trees::TreeNode::with_child_nodes(node, vec![qs[*left].as_text_tree(*left, qs), qs[*right].as_text_tree(*right, qs)].into_iter()),
@@ -74,14 +76,14 @@ impl<V: Vertex, Index: VertexIndex> QueryNode<V, Index> {
     }
 }
 
-impl<'a, V: Vertex, Index: VertexIndex> std::fmt::Display for IndexedQueryNode<'a, V, Index> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl<'a, V: Vertex, Index: VertexIndex> core::fmt::Display for IndexedQueryNode<'a, V, Index> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "[{}] {}", self.0, self.1)
     }
 }
 
 impl<V: Vertex, Index: VertexIndex> IdxDisplay for QueryNode<V, Index> {
-    fn fmt(f: &mut std::fmt::Formatter<'_>, idx: usize) -> std::fmt::Result {
+    fn fmt(f: &mut core::fmt::Formatter<'_>, idx: usize) -> core::fmt::Result {
         write!(f, "q{}", idx)
     }
 }
@@ -92,24 +94,24 @@ impl<V: Vertex, Index: VertexIndex> QueryNode<V, Index> {
     }
 
     #[must_use]
-    pub fn branch_x(&mut self, qi_left: Idx<Self>, qi_right: Idx<Self>, c_min_x: Coords<V::Coordinate>, c_max_x: Coords<V::Coordinate>, ti_right: Idx<Trapezoid<V, Index>>) -> (Self, Self) {
-        (self.branch(qi_left, qi_right, QueryNodeBranch::X(c_min_x, c_max_x)), QueryNode::Sink(ti_right))
+    pub fn branch_x(&mut self, qi_left: Idx<Self>, qi_right: Idx<Self>, c_min_x: &Coords<V::Coordinate>, c_max_x: &Coords<V::Coordinate>, ti_right: Idx<Trapezoid<V, Index>>) -> (Self, Self) {
+        (self.branch(qi_left, qi_right, QueryNodeBranch::X(c_min_x.clone(), c_max_x.clone())), QueryNode::Sink(ti_right))
     }
 
     #[must_use]
-    pub fn merge_x(&mut self, qi_left: Idx<Self>, qi_right: Idx<Self>, c_min_x: Coords<V::Coordinate>, c_max_x: Coords<V::Coordinate>) -> Self {
-        self.branch(qi_left, qi_right, QueryNodeBranch::X(c_min_x, c_max_x))
+    pub fn merge_x(&mut self, qi_left: Idx<Self>, qi_right: Idx<Self>, c_min_x: &Coords<V::Coordinate>, c_max_x: &Coords<V::Coordinate>) -> Self {
+        self.branch(qi_left, qi_right, QueryNodeBranch::X(c_min_x.clone(), c_max_x.clone()))
     }
 
     #[must_use]
-    pub fn branch_y(&mut self, qi_left: Idx<Self>, qi_right: Idx<Self>, c_y: Coords<V::Coordinate>, ti_up: Idx<Trapezoid<V, Index>>) -> (Self, Self) {
-        (self.branch(qi_left, qi_right, QueryNodeBranch::Y(c_y)), QueryNode::Sink(ti_up))
+    pub fn branch_y(&mut self, qi_left: Idx<Self>, qi_right: Idx<Self>, c_y: &Coords<V::Coordinate>, ti_up: Idx<Trapezoid<V, Index>>) -> (Self, Self) {
+        (self.branch(qi_left, qi_right, QueryNodeBranch::Y(c_y.clone())), QueryNode::Sink(ti_up))
     }
 
     #[must_use]
     fn branch(&mut self, qi_left: Idx<Self>, qi_right: Idx<Self>, branch: QueryNodeBranch<V::Coordinate>) -> Self {
         let mut new = QueryNode::Branch(qi_left, qi_right, branch);
-        std::mem::swap(self, &mut new);
+        core::mem::swap(self, &mut new);
         new
     }
 }
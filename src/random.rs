@@ -0,0 +1,50 @@
+//! Random simple polygons of any size, for fuzzing, benchmarks and demos. Requires the `random` feature.
+//!
+//! Polygons are generated by walking once around the origin in random angular steps, placing each vertex at a random
+//! distance from it. The result is star-shaped around the origin, so it never crosses itself, and generating *n*
+//! vertices takes O(*n*) time. Seeding the random number generator makes the output reproducible.
+//!
+//! ```
+//! # use rand::{SeedableRng, rngs::StdRng};
+//! # use triangulate::{random, formats, ListFormat, Polygon};
+//! let mut rng = StdRng::seed_from_u64(7);
+//! let polygon = random::generate_random_simple_polygon::<f64, _>(&mut rng, 1000, 0.5, 0.5).expect("Not a polygon");
+//! assert_eq!(polygon.len(), 1000);
+//!
+//! let mut triangles = Vec::<[usize; 3]>::new();
+//! polygon.triangulate(formats::IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+//! assert_eq!(triangles.len(), 998);
+//! ```
+
+use core::f64::consts::TAU;
+use alloc::vec::Vec;
+
+use num_traits::real::Real;
+use rand::Rng;
+
+/// A counter-clockwise simple polygon with `n` vertices, inside the unit circle around the origin.
+///
+/// `spikiness` from 0 to 1 varies the distance of vertices from the origin, from all on the unit circle to anywhere
+/// inside it. `irregularity` from 0 to 1 varies the angle between consecutive vertices, from evenly spaced to
+/// anywhere between zero and twice the average. Values outside that range are clamped.
+///
+/// Returns [None] if `n` is less than 3, or the coordinate type cannot represent the vertices.
+pub fn generate_random_simple_polygon<C: Real, R: Rng + ?Sized>(rng: &mut R, n: usize, spikiness: f64, irregularity: f64) -> Option<Vec<[C; 2]>> {
+    if n < 3 {
+        return None;
+    }
+    let spikiness = spikiness.clamp(0., 1.);
+    let irregularity = irregularity.clamp(0., 1.);
+
+    // Positive steps keep the angles, and so the vertices, distinct
+    let steps = (0..n).map(|_| (1. + irregularity * (2. * rng.gen::<f64>() - 1.)).max(f64::EPSILON)).collect::<Vec<_>>();
+    let scale = TAU / steps.iter().sum::<f64>();
+
+    let mut angle = rng.gen::<f64>() * TAU;
+    steps.into_iter().map(|step| {
+        let radius = 1. - spikiness * rng.gen::<f64>();
+        let c = [C::from(radius * angle.cos())?, C::from(radius * angle.sin())?];
+        angle += step * scale;
+        Some(c)
+    }).collect()
+}
@@ -0,0 +1,241 @@
+//! Quality refinement of triangulations with Steiner points, for finite element meshes and other uses which need
+//! small, well-shaped triangles.
+//!
+//! Triangles which are larger than the [maximum area](RefinementOptions::max_area) or have an angle smaller than the
+//! [minimum angle](RefinementOptions::min_angle) are split Ruppert-style, by inserting a Steiner point at their
+//! circumcenter and flipping the triangles back to the constrained Delaunay triangulation. A circumcenter close to
+//! the boundary (inside the circle which has a boundary edge as its diameter) would make slivers, so that edge is split
+//! at its midpoint instead. The outline of the polygons is kept, though its edges may be split.
+//!
+//! Each insertion takes time linear in the size of the mesh, so this suits meshes of up to some thousands of
+//! triangles.
+//!
+//! ```
+//! # use triangulate::refinement::{self, RefinedIndex, RefinementOptions};
+//! let square = vec![vec![[0f64, 0.], [4., 0.], [4., 4.], [0., 4.]]];
+//! let options = RefinementOptions { max_area: Some(1.), min_angle: Some(0.4), max_steiner_points: 1000 };
+//! let refinement = refinement::refine(&square, &options).expect("Triangulation failed");
+//! assert!(refinement.complete);
+//! assert!(refinement.triangles.len() >= 16);
+//!
+//! // Original vertices keep their indices, and Steiner points index into `steiner_points`
+//! let area = |t: &[RefinedIndex<[usize; 2]>; 3]| {
+//!     let [a, b, c] = t.map(|vi| refinement.vertex(&square, vi));
+//!     ((b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1])) / 2.
+//! };
+//! assert!(refinement.triangles.iter().all(|t| area(t) > 0. && area(t) <= 1.));
+//! ```
+
+use core::convert::Infallible;
+use alloc::vec::Vec;
+
+use num_traits::real::Real;
+
+use crate::{compat::HashSet, formats::{self, NumberedTriangles}, mesh, FromCoords, ListFormat, PolygonList, TriangulationError, Vertex};
+
+/// Limits on the triangles produced by [refine]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct RefinementOptions<C: Real> {
+    /// The largest area of a triangle, or `None` for no limit
+    pub max_area: Option<C>,
+    /// The smallest angle of a triangle (in radians), or `None` for no limit. Angles up to about 0.36 (20 degrees) can
+    /// be reached, except near corners of the polygons which are sharper than that
+    pub min_angle: Option<C>,
+    /// The most Steiner points to insert, which bounds the time taken if the limits cannot be reached
+    pub max_steiner_points: usize,
+}
+
+/// A vertex of a [Refinement]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum RefinedIndex<I> {
+    /// A vertex of the polygons, by its index
+    Original(I),
+    /// A Steiner point inserted by refinement, by its position in [Refinement::steiner_points]
+    Steiner(usize),
+}
+
+/// The triangles of a refined triangulation, created by [refine]
+#[derive(Debug, PartialEq, Clone)]
+pub struct Refinement<I, C: Real> {
+    /// The positions of the vertices which are not part of the polygons
+    pub steiner_points: Vec<[C; 2]>,
+    /// The counter-clockwise triangles
+    pub triangles: Vec<[RefinedIndex<I>; 3]>,
+    /// Whether every triangle is within the limits. This is `false` if [RefinementOptions::max_steiner_points] was
+    /// reached first
+    pub complete: bool,
+}
+
+impl<I, C: Real> Refinement<I, C> {
+    /// The vertex at `index`: a vertex of `polygons`, which were refined, or a Steiner point created with [FromCoords]
+    pub fn vertex<'p, P: PolygonList<'p, Index=I> + ?Sized>(&self, polygons: &'p P, index: RefinedIndex<I>) -> P::Vertex
+    where P::Vertex: FromCoords<Coordinate=C> + Clone {
+        match index {
+            RefinedIndex::Original(index) => polygons.get_vertex(index).clone(),
+            RefinedIndex::Steiner(i) => P::Vertex::from_coords(self.steiner_points[i]),
+        }
+    }
+}
+
+// The refinement of the polygons of `P`
+type RefinementOf<'p, P> = Refinement<<P as PolygonList<'p>>::Index, <<P as PolygonList<'p>>::Vertex as Vertex>::Coordinate>;
+
+/// Triangulate `polygons`, then insert Steiner points until every triangle is within the limits of `options`
+pub fn refine<'p, P: PolygonList<'p> + ?Sized>(polygons: &'p P, options: &RefinementOptions<<P::Vertex as Vertex>::Coordinate>) -> Result<RefinementOf<'p, P>, TriangulationError<Infallible>>
+where <P::Vertex as Vertex>::Coordinate: Real {
+    let mut triangles = Vec::<[P::Index; 3]>::new();
+    polygons.triangulate(formats::IndexedListFormat::new(&mut triangles).into_fan_format())?;
+
+    let NumberedTriangles { mut triangles, mut positions, indices } = NumberedTriangles::new(polygons, triangles);
+    let original = positions.len();
+    let complete = refine_positions(&mut positions, &mut triangles, options);
+
+    let steiner_points = positions.split_off(original);
+    let triangles = triangles.into_iter().map(|t| t.map(|vi| match indices.get(vi) {
+        Some(index) => RefinedIndex::Original(index.clone()),
+        None => RefinedIndex::Steiner(vi - original),
+    })).collect();
+    Ok(Refinement { steiner_points, triangles, complete })
+}
+
+// Insert Steiner points into the counter-clockwise `triangles`, returning whether they are all within the limits
+fn refine_positions<C: Real>(positions: &mut Vec<[C; 2]>, triangles: &mut Vec<[usize; 3]>, options: &RefinementOptions<C>) -> bool {
+    let two = C::one() + C::one();
+    let min_angle_cos = options.min_angle.map(|angle| angle.cos());
+    let is_bad = |positions: &[[C; 2]], t: &[usize; 3]| {
+        let [a, b, c] = t.map(|vi| positions[vi]);
+        options.max_area.is_some_and(|max_area| cross(a, b, c) / two > max_area)
+            || min_angle_cos.is_some_and(|cos| largest_cos([a, b, c]) > cos)
+    };
+
+    mesh::flip_to_delaunay(positions, triangles);
+    for _ in 0..options.max_steiner_points {
+        // Splitting the largest triangles first spreads the Steiner points evenly
+        let worst = triangles.iter()
+            .filter(|t| is_bad(positions, t))
+            .max_by(|t0, t1| {
+                let [a0, b0, c0] = t0.map(|vi| positions[vi]);
+                let [a1, b1, c1] = t1.map(|vi| positions[vi]);
+                cross(a0, b0, c0).partial_cmp(&cross(a1, b1, c1)).unwrap_or(core::cmp::Ordering::Equal)
+            });
+        let Some(&t) = worst else { return true };
+
+        let edges: HashSet<[usize; 2]> = triangles.iter().flat_map(|t| (0..3).map(move |k| [t[k], t[(k + 1) % 3]])).collect();
+        let [a, b, c] = t.map(|vi| positions[vi]);
+        let center = circumcenter(a, b, c);
+        let encroached = edges.iter()
+            .filter(|[v0, v1]| !edges.contains(&[*v1, *v0]))
+            .find(|[v0, v1]| center.is_some_and(|center| encroaches(center, positions[*v0], positions[*v1])));
+
+        if let Some(&[v0, v1]) = encroached {
+            let midpoint = midpoint(positions[v0], positions[v1]);
+            split_edge(positions, triangles, v0, v1, midpoint);
+        } else {
+            match center.and_then(|center| locate(positions, triangles, center).map(|location| (center, location))) {
+                Some((center, Location::Inside(ti))) => split_triangle(positions, triangles, ti, center),
+                Some((center, Location::OnEdge(v0, v1))) => split_edge(positions, triangles, v0, v1, center),
+                // The circumcenter is outside the polygons or on a vertex, so split the longest edge instead
+                None => {
+                    let k = (0..3).max_by(|&i, &j| {
+                        let (li, lj) = (length_squared(t[i], t[(i + 1) % 3], positions), length_squared(t[j], t[(j + 1) % 3], positions));
+                        li.partial_cmp(&lj).unwrap_or(core::cmp::Ordering::Equal)
+                    }).unwrap_or(0);
+                    let (v0, v1) = (t[k], t[(k + 1) % 3]);
+                    let midpoint = midpoint(positions[v0], positions[v1]);
+                    split_edge(positions, triangles, v0, v1, midpoint);
+                }
+            }
+        }
+        mesh::flip_to_delaunay(positions, triangles);
+    }
+    !triangles.iter().any(|t| is_bad(positions, t))
+}
+
+enum Location {
+    Inside(usize),
+    OnEdge(usize, usize),
+}
+
+// The triangle containing `p`, or the edge it is on. `None` if it is outside every triangle or on a vertex
+fn locate<C: Real>(positions: &[[C; 2]], triangles: &[[usize; 3]], p: [C; 2]) -> Option<Location> {
+    for (ti, t) in triangles.iter().enumerate() {
+        let [a, b, c] = t.map(|vi| positions[vi]);
+        let sides = [cross(a, b, p), cross(b, c, p), cross(c, a, p)];
+        if sides.iter().any(|side| *side < C::zero()) {
+            continue;
+        }
+        let on_edges = (0..3).filter(|&k| sides[k] == C::zero()).collect::<Vec<_>>();
+        return match on_edges[..] {
+            [] => Some(Location::Inside(ti)),
+            [k] => Some(Location::OnEdge(t[k], t[(k + 1) % 3])),
+            _ => None,
+        };
+    }
+    None
+}
+
+// Replace the triangles on either side of the edge from `v0` to `v1` by two triangles each, meeting at `p`
+fn split_edge<C: Real>(positions: &mut Vec<[C; 2]>, triangles: &mut Vec<[usize; 3]>, v0: usize, v1: usize, p: [C; 2]) {
+    positions.push(p);
+    let vp = positions.len() - 1;
+    for ti in 0..triangles.len() {
+        let t = triangles[ti];
+        let Some(k) = (0..3).find(|&k| {
+            let (a, b) = (t[k], t[(k + 1) % 3]);
+            (a, b) == (v0, v1) || (a, b) == (v1, v0)
+        }) else { continue };
+        let (a, b, c) = (t[k], t[(k + 1) % 3], t[(k + 2) % 3]);
+        triangles[ti] = [a, vp, c];
+        triangles.push([vp, b, c]);
+    }
+}
+
+// Replace the triangle `ti` by three triangles meeting at `p`
+fn split_triangle<C: Real>(positions: &mut Vec<[C; 2]>, triangles: &mut Vec<[usize; 3]>, ti: usize, p: [C; 2]) {
+    positions.push(p);
+    let vp = positions.len() - 1;
+    let [a, b, c] = triangles[ti];
+    triangles[ti] = [a, b, vp];
+    triangles.push([b, c, vp]);
+    triangles.push([c, a, vp]);
+}
+
+// Twice the signed area of the triangle, positive when it is counter-clockwise
+fn cross<C: Real>(a: [C; 2], b: [C; 2], c: [C; 2]) -> C {
+    (b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1])
+}
+
+// The cosine of the smallest angle of the triangle
+fn largest_cos<C: Real>(corners: [[C; 2]; 3]) -> C {
+    (0..3).map(|k| {
+        let (p, q, r) = (corners[k], corners[(k + 1) % 3], corners[(k + 2) % 3]);
+        let (u, v) = ([q[0] - p[0], q[1] - p[1]], [r[0] - p[0], r[1] - p[1]]);
+        (u[0] * v[0] + u[1] * v[1]) / ((u[0] * u[0] + u[1] * u[1]) * (v[0] * v[0] + v[1] * v[1])).sqrt()
+    }).fold(-C::one(), C::max)
+}
+
+fn circumcenter<C: Real>(a: [C; 2], b: [C; 2], c: [C; 2]) -> Option<[C; 2]> {
+    let area = cross(a, b, c);
+    if area == C::zero() {
+        return None;
+    }
+    let d = (area + area).recip();
+    let (b, c) = ([b[0] - a[0], b[1] - a[1]], [c[0] - a[0], c[1] - a[1]]);
+    let (lb, lc) = (b[0] * b[0] + b[1] * b[1], c[0] * c[0] + c[1] * c[1]);
+    Some([a[0] + (c[1] * lb - b[1] * lc) * d, a[1] + (b[0] * lc - c[0] * lb) * d])
+}
+
+// Whether `p` is strictly inside the circle which has the edge from `a` to `b` as its diameter
+fn encroaches<C: Real>(p: [C; 2], a: [C; 2], b: [C; 2]) -> bool {
+    (a[0] - p[0]) * (b[0] - p[0]) + (a[1] - p[1]) * (b[1] - p[1]) < C::zero()
+}
+
+fn midpoint<C: Real>(a: [C; 2], b: [C; 2]) -> [C; 2] {
+    let two = C::one() + C::one();
+    [(a[0] + b[0]) / two, (a[1] + b[1]) / two]
+}
+
+fn length_squared<C: Real>(v0: usize, v1: usize, positions: &[[C; 2]]) -> C {
+    let (a, b) = (positions[v0], positions[v1]);
+    (b[0] - a[0]).powi(2) + (b[1] - a[1]).powi(2)
+}
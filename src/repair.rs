@@ -0,0 +1,125 @@
+//! Repair of self-intersecting polygons, such as font outlines and shapes drawn by hand, into simple polygons which
+//! can be triangulated.
+//!
+//! The edges are split where they cross or overlap, and the winding number on either side of each piece decides
+//! whether it is filled by the chosen [FillRule]. The pieces between filled and unfilled areas are linked into
+//! contours with counter-clockwise outer boundaries and clockwise holes, as returned by the
+//! [boolean](crate::boolean) operations. Filled areas which only meet at a point are left touching there.
+//!
+//! ```
+//! # use triangulate::{formats, repair::{self, FillRule}, ListFormat, PolygonList};
+//! // Two overlapping squares, drawn in the same direction
+//! let polygons = vec![
+//!     vec![[0f64, 0.], [2., 0.], [2., 2.], [0., 2.]],
+//!     vec![[1., 1.], [3., 1.], [3., 3.], [1., 3.]],
+//! ];
+//! let repaired = repair::repair(&polygons, FillRule::NonZero);
+//! assert_eq!(repaired.len(), 1);
+//! assert_eq!(repaired[0].len(), 8);
+//!
+//! let mut triangles = Vec::<[usize; 2]>::new();
+//! repaired.triangulate(formats::IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+//! assert_eq!(triangles.len(), 6 * 3);
+//! ```
+
+use core::fmt;
+use alloc::vec::Vec;
+
+use num_traits::real::Real;
+
+use crate::{boolean::{self, Contours}, intersection, DefaultKernel, GeometryKernel, Orientation, PolygonList, PolygonListExt, Vertex};
+
+/// The rule deciding which areas of overlapping or self-intersecting polygons are filled, by their winding number:
+/// the number of times the polygons wind counter-clockwise around a point, less the number of times clockwise
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub enum FillRule {
+    /// Areas with an odd winding number are filled, as in triangulation and SVG's `evenodd`
+    #[default]
+    EvenOdd,
+    /// Areas with a winding number other than zero are filled, as in TrueType fonts and SVG's `nonzero`
+    NonZero,
+    /// Areas with a positive winding number are filled
+    Positive,
+    /// Areas with a negative winding number are filled
+    Negative,
+}
+
+impl FillRule {
+    /// Whether an area with the winding number `winding` is filled
+    pub fn is_filled(self, winding: i32) -> bool {
+        match self {
+            FillRule::EvenOdd => winding % 2 != 0,
+            FillRule::NonZero => winding != 0,
+            FillRule::Positive => winding > 0,
+            FillRule::Negative => winding < 0,
+        }
+    }
+}
+
+/// The boundary of the area of `polygons` filled by `rule`, as simple polygons
+pub fn repair<'p, P: PolygonList<'p> + ?Sized>(polygons: &'p P, rule: FillRule) -> Contours<<P::Vertex as Vertex>::Coordinate>
+where <P::Vertex as Vertex>::Coordinate: Real + fmt::Debug {
+    repair_contours(&PolygonListExt::new(polygons).contours(), rule)
+}
+
+pub(crate) fn repair_contours<C: Real + fmt::Debug>(contours: &[Vec<[C; 2]>], rule: FillRule) -> Contours<C> {
+    let edges: Vec<[[C; 2]; 2]> = contours.iter()
+        .flat_map(|contour| (0..contour.len()).map(move |k| [contour[k], contour[(k + 1) % contour.len()]]))
+        .filter(|[c0, c1]| c0 != c1)
+        .collect();
+
+    // Pieces which only meet at their endpoints, directed from their lower end, with the number of times they are
+    // traversed in that direction less the number of times in the other
+    let mut pieces: Vec<([[C; 2]; 2], i32)> = intersection::split_segments(&edges).into_iter().map(|piece| {
+        let key = boolean::segment_key(piece.segment);
+        (key, if key == piece.segment { 1 } else { -1 })
+    }).collect();
+    pieces.sort_by(|(a, _), (b, _)| boolean::cmp_key(a, b));
+    let mut groups: Vec<([[C; 2]; 2], i32)> = Vec::with_capacity(pieces.len());
+    for (key, count) in pieces {
+        match groups.last_mut() {
+            Some((last, total)) if *last == key => *total += count,
+            _ => groups.push((key, count)),
+        }
+    }
+    // Pieces traversed equally often in both directions don't change the winding number
+    groups.retain(|(_, count)| *count != 0);
+
+    let two = C::one() + C::one();
+    let boundary = groups.iter().enumerate().filter_map(|(i, &([c0, c1], count))| {
+        let midpoint = [(c0[0] + c1[0]) / two, (c0[1] + c1[1]) / two];
+        // A ray to the right of an upward piece starts on its right side, and a ray upwards from a rightward piece
+        // starts on its left side
+        let (left, right) = if c0[1] == c1[1] {
+            let left = -winding(&groups, i, midpoint, true);
+            (left, left - count)
+        } else {
+            let right = winding(&groups, i, midpoint, false);
+            (right + count, right)
+        };
+        match (rule.is_filled(left), rule.is_filled(right)) {
+            (true, false) => Some([c0, c1]),
+            (false, true) => Some([c1, c0]),
+            _ => None,
+        }
+    }).collect();
+    boolean::assemble_rings(boundary)
+}
+
+// The winding number next to `c` along a ray to the right of it, or upwards if `transpose` is set (where the winding
+// number is negated), counting every piece but `skip`
+fn winding<C: Real + fmt::Debug>(groups: &[([[C; 2]; 2], i32)], skip: usize, c: [C; 2], transpose: bool) -> i32 {
+    let transposed = |c: [C; 2]| if transpose { [c[1], c[0]] } else { c };
+    let c = transposed(c);
+    groups.iter().enumerate().filter(|(j, _)| *j != skip).map(|(_, (piece, count))| {
+        let [a, b] = piece.map(transposed);
+        let orientation = <DefaultKernel as GeometryKernel<C>>::orientation(&a, &b, &c);
+        if a[1] <= c[1] && c[1] < b[1] && orientation == Orientation::Left {
+            *count
+        } else if b[1] <= c[1] && c[1] < a[1] && orientation == Orientation::Right {
+            -*count
+        } else {
+            0
+        }
+    }).sum()
+}
@@ -0,0 +1,90 @@
+use alloc::vec::Vec;
+
+use num_traits::Float;
+
+// Adaptive exact orientation, after Shewchuk's "Adaptive Precision Floating-Point Arithmetic and Fast Robust
+// Geometric Predicates". The determinant is first evaluated in floating point with an error bound, and only when
+// that cannot decide the sign is it evaluated exactly, as an expansion: a sum of non-overlapping floats.
+
+const EPSILON: f64 = f64::EPSILON / 2.;
+const CCW_ERROR_BOUND: f64 = (3. + 16. * EPSILON) * EPSILON;
+
+// A value with the sign of the orientation of `c` relative to the directed line from `a` to `b`, positive when `c` is
+// to the left. The sign is exact unless the computation overflows or underflows.
+pub(crate) fn orient2d(a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> f64 {
+    let left = (b[0] - a[0]) * (c[1] - a[1]);
+    let right = (b[1] - a[1]) * (c[0] - a[0]);
+    let det = left - right;
+    if left == 0. || (left > 0.) != (right > 0.) {
+        // The terms cannot cancel, and rounding keeps the sign of each
+        return det;
+    }
+    if det.abs() >= CCW_ERROR_BOUND * (left.abs() + right.abs()) {
+        return det;
+    }
+    orient2d_exact(a, b, c)
+}
+
+fn orient2d_exact(a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> f64 {
+    let left = expansion_product(two_diff(b[0], a[0]), two_diff(c[1], a[1]));
+    let right = expansion_product(two_diff(b[1], a[1]), two_diff(c[0], a[0]));
+    let det = expansion_sum(&left, &right.iter().map(|x| -x).collect::<Vec<_>>());
+    // The components grow in magnitude, so the last one has the sign of the sum
+    det.last().copied().unwrap_or(0.)
+}
+
+// `a + b` as `[error, sum]`
+fn two_sum(a: f64, b: f64) -> [f64; 2] {
+    let x = a + b;
+    let b_virtual = x - a;
+    let a_virtual = x - b_virtual;
+    [(a - a_virtual) + (b - b_virtual), x]
+}
+
+// `a - b` as `[error, difference]`
+fn two_diff(a: f64, b: f64) -> [f64; 2] {
+    two_sum(a, -b)
+}
+
+// `a * b` as `[error, product]`
+fn two_product(a: f64, b: f64) -> [f64; 2] {
+    let x = a * b;
+    [Float::mul_add(a, b, -x), x]
+}
+
+// The exact product of two expansions of two components each, without zero components
+fn expansion_product(a: [f64; 2], b: [f64; 2]) -> Vec<f64> {
+    let mut product = Vec::new();
+    for x in a {
+        for y in b {
+            product = expansion_sum(&product, &two_product(x, y));
+        }
+    }
+    product
+}
+
+// The exact sum of two expansions, without zero components
+fn expansion_sum(e: &[f64], f: &[f64]) -> Vec<f64> {
+    let mut sum = e.to_vec();
+    for &x in f {
+        sum = grow_expansion(&sum, x);
+    }
+    sum
+}
+
+// The exact sum of an expansion and a float, without zero components
+fn grow_expansion(e: &[f64], b: f64) -> Vec<f64> {
+    let mut sum = Vec::with_capacity(e.len() + 1);
+    let mut q = b;
+    for &x in e {
+        let [error, total] = two_sum(q, x);
+        q = total;
+        if error != 0. {
+            sum.push(error);
+        }
+    }
+    if q != 0. {
+        sum.push(q);
+    }
+    sum
+}
@@ -0,0 +1,151 @@
+//! Signed distance fields of polygons, for rendering text and icons that stay sharp under scaling.
+//!
+//! Each texel holds the distance from its center to the nearest polygon edge, positive inside the polygons and negative
+//! outside, clamped to the [spread](SdfOptions::spread). Inside and outside are decided with the same
+//! [Trapezoidation] used for triangulation, which doubles as a point-location structure.
+//!
+//! ```
+//! # use triangulate::sdf::{self, SdfOptions};
+//! let square = vec![vec![[0f64, 0.], [4., 0.], [4., 4.], [0., 4.]]];
+//! let options = SdfOptions { width: 8, height: 8, bounds: Some([[-2., -2.], [6., 6.]]), spread: 1. };
+//! let field = sdf::distance_field(&square, &options).expect("Trapezoidation failed");
+//! // The texel centered on (2.5, 2.5) is 1.5 inside the square, clamped to the spread
+//! assert_eq!(field.get(4, 4), 1.);
+//! // The texel centered on (-1.5, 2.5) is 1.5 outside
+//! assert_eq!(field.get(0, 4), -1.);
+//! assert_eq!(field.get(1, 4), -0.5);
+//! ```
+
+use core::fmt;
+use alloc::{vec, vec::Vec};
+
+use num_traits::{One, Zero, real::Real};
+
+use crate::{math, PolygonList, PolygonListExt, Trapezoidation, TrapezoidationError, Vertex};
+
+/// Resolution and extent of a [DistanceField]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct SdfOptions<C: Real> {
+    /// The number of texels in each row
+    pub width: usize,
+    /// The number of rows
+    pub height: usize,
+    /// The `[min, max]` corners of the area covered by the field. If `None`, the bounding box of the polygons grown by
+    /// [SdfOptions::spread] on every side is used
+    pub bounds: Option<[[C; 2]; 2]>,
+    /// The largest distance stored. Distances further from an edge are clamped to this
+    pub spread: C,
+}
+
+/// A grid of signed distances to the edges of a set of polygons
+#[derive(Debug, PartialEq, Clone)]
+pub struct DistanceField<C: Real> {
+    /// The number of texels in each row
+    pub width: usize,
+    /// The number of rows
+    pub height: usize,
+    /// The `[min, max]` corners of the area covered by the field
+    pub bounds: [[C; 2]; 2],
+    /// The largest distance stored
+    pub spread: C,
+    /// The distances, row by row, starting from the row with the lowest y coordinate
+    pub distances: Vec<C>,
+}
+
+impl<C: Real> DistanceField<C> {
+    /// The distance at column `x` of row `y`
+    pub fn get(&self, x: usize, y: usize) -> C {
+        self.distances[y * self.width + x]
+    }
+
+    /// The center of the texel at column `x` of row `y`
+    pub fn texel_center(&self, x: usize, y: usize) -> [C; 2] {
+        texel_center(self.bounds, self.width, self.height, x, y)
+    }
+
+    /// The field as an 8-bit texture in the usual encoding, with the edge at 128 and the spread mapped to the full range
+    /// (0 at `-spread`, 255 at `spread`)
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let spread = self.spread.to_f64().unwrap_or(1.);
+        self.distances.iter().map(|d| {
+            let normalized = 0.5 + d.to_f64().unwrap_or(0.) / (2. * spread);
+            (normalized * 256.).floor().clamp(0., 255.) as u8
+        }).collect()
+    }
+}
+
+fn texel_center<C: Real>(bounds: [[C; 2]; 2], width: usize, height: usize, x: usize, y: usize) -> [C; 2] {
+    let half = C::one() / (C::one() + C::one());
+    let fraction = |i: usize, count: usize| (C::from(i).unwrap_or_else(C::zero) + half) / C::from(count).unwrap_or_else(C::one);
+    let [min, max] = bounds;
+    [min[0] + (max[0] - min[0]) * fraction(x, width), min[1] + (max[1] - min[1]) * fraction(y, height)]
+}
+
+// The index of the texel containing the coordinate `c` along one axis, clamped to the grid, or the index after the one containing it
+fn texel_index<C: Real>(c: C, min: C, max: C, count: usize, round_up: bool) -> usize {
+    let position = (c - min) / (max - min) * C::from(count).unwrap_or_else(C::one);
+    let position = if round_up { position.ceil() } else { position.floor() };
+    position.max(C::zero()).to_usize().unwrap_or(0).min(count)
+}
+
+/// Compute the signed distance field of `polygons` (interpreted with the even-odd rule, as in triangulation)
+pub fn distance_field<'p, P: PolygonList<'p> + ?Sized>(polygons: &'p P, options: &SdfOptions<<P::Vertex as Vertex>::Coordinate>) -> Result<DistanceField<<P::Vertex as Vertex>::Coordinate>, TrapezoidationError>
+where <P::Vertex as Vertex>::Coordinate: Real + fmt::Debug {
+    let contours = PolygonListExt::new(polygons).contours();
+    let spread = options.spread.abs();
+    let bounds = options.bounds.or_else(|| math::bounding_box(contours.iter().flatten()).map(|[min, max]| {
+        [[min[0] - spread, min[1] - spread], [max[0] + spread, max[1] + spread]]
+    })).unwrap_or([[<P::Vertex as Vertex>::Coordinate::zero(); 2], [<P::Vertex as Vertex>::Coordinate::one(); 2]]);
+    let (width, height) = (options.width, options.height);
+
+    // Unsigned distances, only computed for texels within the spread of each segment
+    let mut distances = vec![spread; width * height];
+    for contour in contours.iter() {
+        for (k, a) in contour.iter().enumerate() {
+            let b = contour[(k + 1) % contour.len()];
+            let [x0, x1] = [
+                texel_index(a[0].min(b[0]) - spread, bounds[0][0], bounds[1][0], width, false),
+                texel_index(a[0].max(b[0]) + spread, bounds[0][0], bounds[1][0], width, true),
+            ];
+            let [y0, y1] = [
+                texel_index(a[1].min(b[1]) - spread, bounds[0][1], bounds[1][1], height, false),
+                texel_index(a[1].max(b[1]) + spread, bounds[0][1], bounds[1][1], height, true),
+            ];
+            for y in y0..y1 {
+                for x in x0..x1 {
+                    let distance = segment_distance(*a, b, texel_center(bounds, width, height, x, y));
+                    let texel = &mut distances[y * width + x];
+                    *texel = texel.min(distance);
+                }
+            }
+        }
+    }
+
+    // Negate the distances outside the polygons
+    if contours.iter().any(|contour| contour.len() >= 3) {
+        let trapezoidation: Trapezoidation<'p, P> = polygons.trapezoidize()?;
+        for y in 0..height {
+            for x in 0..width {
+                if !trapezoidation.contains_point(texel_center(bounds, width, height, x, y))? {
+                    distances[y * width + x] = -distances[y * width + x];
+                }
+            }
+        }
+    } else {
+        distances.iter_mut().for_each(|d| *d = -*d);
+    }
+
+    Ok(DistanceField { width, height, bounds, spread, distances })
+}
+
+pub(crate) fn segment_distance<C: Real>(a: [C; 2], b: [C; 2], c: [C; 2]) -> C {
+    let ab = [b[0] - a[0], b[1] - a[1]];
+    let ac = [c[0] - a[0], c[1] - a[1]];
+    let length_2 = ab[0] * ab[0] + ab[1] * ab[1];
+    let t = if length_2 > C::zero() {
+        ((ac[0] * ab[0] + ac[1] * ab[1]) / length_2).max(C::zero()).min(C::one())
+    } else {
+        C::zero()
+    };
+    (ac[0] - ab[0] * t).hypot(ac[1] - ab[1] * t)
+}
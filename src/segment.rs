@@ -1,8 +1,8 @@
-use std::fmt;
+use core::fmt;
 
-use crate::{Vertex, VertexIndex, idx::{Idx, IdxDisplay}, math::is_left_of_line, nexus::Nexus, Coords};
+use crate::{Vertex, VertexIndex, idx::{Idx, IdxDisplay}, kernel::{GeometryKernel, is_left_of_line}, nexus::Nexus, Coords};
 
-#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(bound(serialize = "V::Coordinate: serde::Serialize", deserialize = "V::Coordinate: serde::Deserialize<'de>")))]
 pub(crate) struct Segment<V: Vertex, Index: VertexIndex> {
     ni_min: Idx<Nexus<V, Index>>,
     ni_max: Idx<Nexus<V, Index>>,
@@ -10,6 +10,13 @@ pub(crate) struct Segment<V: Vertex, Index: VertexIndex> {
     c_max: Coords<V::Coordinate>,
 }
 
+// #[derive] would require `V: Clone`
+impl<V: Vertex, Index: VertexIndex> Clone for Segment<V, Index> {
+    fn clone(&self) -> Self {
+        Self { ni_min: self.ni_min, ni_max: self.ni_max, c_min: self.c_min.clone(), c_max: self.c_max.clone() }
+    }
+}
+
 impl<V: Vertex, Index: VertexIndex> fmt::Debug for Segment<V, Index> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Segment").field("ni_min", &self.ni_min).field("ni_max", &self.ni_max).field("c_min", &self.c_min).field("c_max", &self.c_max).finish()
@@ -17,14 +24,14 @@ impl<V: Vertex, Index: VertexIndex> fmt::Debug for Segment<V, Index> {
 }
 
 impl<V: Vertex, Index: VertexIndex> IdxDisplay for Segment<V, Index> {
-    fn fmt(f: &mut std::fmt::Formatter<'_>, idx: usize) -> std::fmt::Result {
+    fn fmt(f: &mut core::fmt::Formatter<'_>, idx: usize) -> core::fmt::Result {
         write!(f, "s{}", idx)
     }
 }
 
-impl<V: Vertex, Index: VertexIndex> std::fmt::Display for Segment<V, Index>
-where V::Coordinate: std::fmt::Display {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl<V: Vertex, Index: VertexIndex> core::fmt::Display for Segment<V, Index>
+where V::Coordinate: core::fmt::Display {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{} -> {}", self.ni_min, self.ni_max)
     }
 }
@@ -42,7 +49,7 @@ impl<V: Vertex, Index: VertexIndex> Segment<V, Index> {
     pub fn ni_min(&self) -> Idx<Nexus<V, Index>> { self.ni_min }
     pub fn ni_max(&self) -> Idx<Nexus<V, Index>> { self.ni_max }
 
-    pub fn is_on_left(&self, c: Coords<V::Coordinate>) -> bool {
-        is_left_of_line(self.c_min, self.c_max, c)
+    pub fn is_on_left<K: GeometryKernel<V::Coordinate>>(&self, c: &Coords<V::Coordinate>) -> bool {
+        is_left_of_line::<K, _>(&self.c_min, &self.c_max, c)
     }
 }
@@ -0,0 +1,156 @@
+//! Polygons for common parametric shapes, ready to be triangulated.
+//!
+//! Every function returns a [PolygonList] of `[C; 2]` vertices, with counter-clockwise outer boundaries and clockwise
+//! holes. Curves are approximated by a given number of straight segments. Consecutive points which would coincide
+//! (such as where a rounded corner has a radius of zero) are merged, so the result always has distinct vertices.
+//! Points on a curve whose direction the coordinate type cannot represent are left out.
+//!
+//! ```
+//! # use triangulate::{shapes, formats, ListFormat, PolygonList};
+//! let button = shapes::rounded_rect([0f32, 0.], [4., 2.], [0.5; 4], 4);
+//! let mut triangles = Vec::<[f32; 2]>::new();
+//! button.triangulate(formats::DeindexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+//!
+//! // A ring with 32 segments on each side
+//! let ring = shapes::ring([0f64, 0.], 1., 2., 32);
+//! assert_eq!(ring.len(), 2);
+//! ```
+//!
+//! [PolygonList]: crate::PolygonList
+
+use core::f64::consts::{FRAC_PI_2, TAU};
+use alloc::{vec, vec::Vec};
+
+use num_traits::real::Real;
+
+// The unit vector at `angle`, if the coordinate type can represent it
+fn direction<C: Real>(angle: f64) -> Option<[C; 2]> {
+    Some([C::from(angle.cos())?, C::from(angle.sin())?])
+}
+
+// Append `c` unless it coincides with the previous point
+fn push_distinct<C: Real>(contour: &mut Vec<[C; 2]>, c: [C; 2]) {
+    if contour.last() != Some(&c) {
+        contour.push(c);
+    }
+}
+
+fn finish<C: Real>(mut contour: Vec<[C; 2]>) -> Vec<Vec<[C; 2]>> {
+    while contour.len() > 1 && contour.first() == contour.last() {
+        contour.pop();
+    }
+    if contour.len() < 3 {
+        return Vec::new();
+    }
+    vec![contour]
+}
+
+/// An axis-aligned rectangle between the corners `min` and `max`
+pub fn rect<C: Real>(min: [C; 2], max: [C; 2]) -> Vec<Vec<[C; 2]>> {
+    let mut contour = Vec::with_capacity(4);
+    for c in [min, [max[0], min[1]], max, [min[0], max[1]]] {
+        push_distinct(&mut contour, c);
+    }
+    finish(contour)
+}
+
+/// An axis-aligned rectangle between the corners `min` and `max`, with each corner rounded by a quarter circle of
+/// `segments` segments.
+///
+/// The radii are given counter-clockwise starting from the `min` corner: `[min x/min y, max x/min y, max x/max y, min x/max y]`.
+/// Negative radii are treated as zero. As in CSS, if the radii on any side add up to more than its length, all radii
+/// are scaled down so they fit.
+pub fn rounded_rect<C: Real>(min: [C; 2], max: [C; 2], radii: [C; 4], segments: usize) -> Vec<Vec<[C; 2]>> {
+    let size = [max[0] - min[0], max[1] - min[1]];
+    let radii = radii.map(|r| r.max(C::zero()));
+    // Each side, as its length and the radii at its ends
+    let sides = [(size[0], radii[0] + radii[1]), (size[1], radii[1] + radii[2]), (size[0], radii[2] + radii[3]), (size[1], radii[3] + radii[0])];
+    let scale = sides.iter()
+        .filter(|(_, sum)| *sum > C::zero())
+        .fold(C::one(), |scale, (length, sum)| scale.min(*length / *sum));
+    let radii = radii.map(|r| r * scale);
+
+    let centers = [
+        [max[0] - radii[1], min[1] + radii[1]],
+        [max[0] - radii[2], max[1] - radii[2]],
+        [min[0] + radii[3], max[1] - radii[3]],
+        [min[0] + radii[0], min[1] + radii[0]],
+    ];
+    let segments = segments.max(1);
+    let mut contour = Vec::with_capacity(4 * (segments + 1));
+    for (quadrant, (center, radius)) in centers.into_iter().zip([radii[1], radii[2], radii[3], radii[0]]).enumerate() {
+        // Start facing down from the max x/min y corner; the ends of each arc are axis-aligned, so compute them exactly
+        let directions = [[C::zero(), -C::one()], [C::one(), C::zero()], [C::zero(), C::one()], [-C::one(), C::zero()], [C::zero(), -C::one()]];
+        for k in 0..=segments {
+            let direction = if k == 0 {
+                directions[quadrant]
+            } else if k == segments {
+                directions[quadrant + 1]
+            } else {
+                let angle = -FRAC_PI_2 + FRAC_PI_2 * (quadrant as f64 + k as f64 / segments as f64);
+                match direction(angle) {
+                    Some(direction) => direction,
+                    None => continue,
+                }
+            };
+            push_distinct(&mut contour, [center[0] + radius * direction[0], center[1] + radius * direction[1]]);
+        }
+    }
+    finish(contour)
+}
+
+/// A circle approximated by `segments` segments (at least 3), starting from the point with the largest x coordinate
+pub fn circle<C: Real>(center: [C; 2], radius: C, segments: usize) -> Vec<Vec<[C; 2]>> {
+    ellipse(center, [radius, radius], segments)
+}
+
+/// An axis-aligned ellipse with the given `radii` along x and y, approximated by `segments` segments (at least 3)
+pub fn ellipse<C: Real>(center: [C; 2], radii: [C; 2], segments: usize) -> Vec<Vec<[C; 2]>> {
+    finish(ellipse_contour(center, radii, segments))
+}
+
+fn ellipse_contour<C: Real>(center: [C; 2], radii: [C; 2], segments: usize) -> Vec<[C; 2]> {
+    let segments = segments.max(3);
+    let mut contour = Vec::with_capacity(segments);
+    for k in 0..segments {
+        if let Some([x, y]) = direction::<C>(TAU * k as f64 / segments as f64) {
+            push_distinct(&mut contour, [center[0] + radii[0] * x, center[1] + radii[1] * y]);
+        }
+    }
+    contour
+}
+
+/// The area between two concentric circles (an annulus), each approximated by `segments` segments (at least 3).
+///
+/// If `inner_radius` is not positive, or not smaller than `outer_radius`, this is a [circle] of `outer_radius`.
+pub fn ring<C: Real>(center: [C; 2], inner_radius: C, outer_radius: C, segments: usize) -> Vec<Vec<[C; 2]>> {
+    let mut polygons = circle(center, outer_radius, segments);
+    if inner_radius > C::zero() && inner_radius < outer_radius {
+        let mut hole = ellipse_contour(center, [inner_radius, inner_radius], segments);
+        hole.reverse();
+        polygons.extend(finish(hole));
+    }
+    polygons
+}
+
+/// A slice of a circle, from the angle `start` (in radians, counter-clockwise from the x axis) through `sweep`,
+/// with the arc approximated by `segments` segments.
+///
+/// A negative `sweep` extends clockwise from `start`. A `sweep` of a full turn or more is a [circle].
+pub fn pie<C: Real>(center: [C; 2], radius: C, start: C, sweep: C, segments: usize) -> Vec<Vec<[C; 2]>> {
+    let (start, sweep) = (start.to_f64().unwrap_or(0.), sweep.to_f64().unwrap_or(0.));
+    if sweep.abs() >= TAU {
+        return circle(center, radius, segments);
+    }
+    let (start, sweep) = if sweep < 0. { (start + sweep, -sweep) } else { (start, sweep) };
+
+    let segments = segments.max(1);
+    let mut contour = Vec::with_capacity(segments + 2);
+    contour.push(center);
+    for k in 0..=segments {
+        if let Some([x, y]) = direction::<C>(start + sweep * k as f64 / segments as f64) {
+            push_distinct(&mut contour, [center[0] + radius * x, center[1] + radius * y]);
+        }
+    }
+    finish(contour)
+}
@@ -0,0 +1,416 @@
+//! Straight skeletons of polygons, and the roofs built from them.
+//!
+//! The straight skeleton is traced by the corners of the polygons as every edge moves inwards at the same speed. Each
+//! edge sweeps out one [face](Skeleton::faces) of the skeleton, and lifting every node by the time at which the
+//! wavefront reached it gives a roof of constant slope over the polygons.
+//!
+//! ```
+//! # use triangulate::skeleton::{self, RoofStyle};
+//! let footprint = vec![vec![[0f64, 0.], [4., 0.], [4., 2.], [0., 2.]]];
+//! let skeleton = skeleton::straight_skeleton(&footprint);
+//! // The four corners, and the two ends of the ridge
+//! assert_eq!(skeleton.nodes.len(), 6);
+//! assert!(skeleton.nodes.iter().any(|n| n.position == [1., 1.] && n.time == 1.));
+//!
+//! let roof = skeleton::generate_roof(&footprint, RoofStyle::Gabled, |t| t * 0.5).expect("Triangulation failed");
+//! assert!(roof.positions.contains(&[0., 1., 0.5]));
+//! ```
+//!
+//! The wavefront is simulated one event at a time, which takes `O(n³)` time for `n` vertices; this is meant for
+//! footprints and outlines with at most a few hundred vertices. Vertices where several events coincide exactly are
+//! handled one event at a time, which may leave short zero-length arcs in the skeleton.
+
+use core::{convert::Infallible, fmt, iter};
+use alloc::{vec, vec::Vec};
+
+use num_traits::{Zero, real::Real};
+
+use crate::{math, mesh::Mesh, solid::Solid, PolygonList, PolygonListExt, TriangulationError, Vertex};
+
+/// A vertex of a [Skeleton]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct SkeletonNode<C: Real> {
+    /// The position of the node
+    pub position: [C; 2],
+    /// The time at which the wavefront reached the node, which is its distance from the nearest edge
+    pub time: C,
+}
+
+/// The straight skeleton of a set of polygons
+#[derive(Debug, PartialEq, Clone)]
+pub struct Skeleton<C: Real> {
+    /// The distinct vertices of the polygons, followed by the nodes where the wavefront changed
+    pub nodes: Vec<SkeletonNode<C>>,
+    /// The paths traced by the corners of the wavefront, as pairs of indices into [Skeleton::nodes]
+    pub arcs: Vec<[usize; 2]>,
+    /// The area swept by each edge of the polygons, as a counter-clockwise polygon of indices into [Skeleton::nodes].
+    ///
+    /// The edges are in the iteration order of the polygons, the `k`th edge of a polygon running from its `k`th vertex
+    /// to the next, and each face starts with the two ends of its edge. Repeated vertices, and polygons with fewer than
+    /// three distinct vertices, are left out.
+    pub faces: Vec<Vec<usize>>,
+}
+
+/// How [generate_roof] treats the ends of a building
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum RoofStyle {
+    /// Every edge slopes up from the eaves
+    #[default]
+    Hipped,
+    /// Edges whose roof would be a single triangle rising to a peak of its own end in a vertical gable instead, with
+    /// the ridge extended to meet it
+    Gabled,
+}
+
+// An edge of the polygons, with the filled area on its left
+struct Edge<C: Real> {
+    origin: [C; 2],
+    direction: [C; 2],
+    normal: [C; 2],
+}
+
+// A corner of the wavefront, between two edges
+#[derive(Clone)]
+struct WaveVertex<C: Real> {
+    position: [C; 2],
+    velocity: [C; 2],
+    edges: [usize; 2],
+    prev: usize,
+    next: usize,
+    node: usize,
+    active: bool,
+}
+
+enum Event<C: Real> {
+    // The edge after a vertex shrinks to nothing
+    Edge(usize, C),
+    // A reflex vertex reaches the edge after another vertex
+    Split(usize, usize, C),
+}
+
+fn dot<C: Real>(a: [C; 2], b: [C; 2]) -> C {
+    a[0] * b[0] + a[1] * b[1]
+}
+
+fn along<C: Real>(c: [C; 2], v: [C; 2], t: C) -> [C; 2] {
+    [c[0] + v[0] * t, c[1] + v[1] * t]
+}
+
+fn sub<C: Real>(a: [C; 2], b: [C; 2]) -> [C; 2] {
+    [a[0] - b[0], a[1] - b[1]]
+}
+
+struct Wavefront<C: Real> {
+    edges: Vec<Edge<C>>,
+    vertices: Vec<WaveVertex<C>>,
+    skeleton: Skeleton<C>,
+    time: C,
+    epsilon: C,
+}
+
+impl<C: Real> Wavefront<C> {
+    // The velocity of the corner between two edges, which keeps it on both as they move
+    fn velocity(&self, edges: [usize; 2]) -> [C; 2] {
+        let [n0, n1] = edges.map(|e| self.edges[e].normal);
+        let scale = C::one() + dot(n0, n1);
+        // Antiparallel edges have met head-on, and the corner between them stays where it is
+        if scale <= self.epsilon {
+            [C::zero(); 2]
+        } else {
+            [(n0[0] + n1[0]) / scale, (n0[1] + n1[1]) / scale]
+        }
+    }
+
+    fn is_reflex(&self, vi: usize) -> bool {
+        let [d0, d1] = self.vertices[vi].edges.map(|e| self.edges[e].direction);
+        d0[0] * d1[1] - d0[1] * d1[0] < -self.epsilon
+    }
+
+    fn add_node(&mut self, position: [C; 2], edges: &[usize]) -> usize {
+        let time = self.time;
+        let node = self.skeleton.nodes.iter()
+            .position(|n| (n.time - time).abs() <= self.epsilon && (n.position[0] - position[0]).abs().max((n.position[1] - position[1]).abs()) <= self.epsilon)
+            .unwrap_or_else(|| {
+                self.skeleton.nodes.push(SkeletonNode { position, time });
+                self.skeleton.nodes.len() - 1
+            });
+        for &e in edges {
+            if !self.skeleton.faces[e].contains(&node) {
+                self.skeleton.faces[e].push(node);
+            }
+        }
+        node
+    }
+
+    fn add_arc(&mut self, from: usize, to: usize) {
+        if from != to && !self.skeleton.arcs.iter().any(|&arc| arc == [from, to] || arc == [to, from]) {
+            self.skeleton.arcs.push([from, to]);
+        }
+    }
+
+    fn add_vertex(&mut self, position: [C; 2], edges: [usize; 2], [prev, next]: [usize; 2], node: usize) -> usize {
+        let velocity = self.velocity(edges);
+        let vi = self.vertices.len();
+        self.vertices.push(WaveVertex { position, velocity, edges, prev, next, node, active: true });
+        self.vertices[prev].next = vi;
+        self.vertices[next].prev = vi;
+        vi
+    }
+
+    fn next_event(&self) -> Option<Event<C>> {
+        let mut earliest: Option<Event<C>> = None;
+        let time = |event: &Event<C>| match *event { Event::Edge(_, t) | Event::Split(_, _, t) => t };
+        let mut consider = |event: Event<C>| {
+            // Edge events come first among simultaneous events, as they simplify the wavefront
+            let better = match &earliest {
+                None => true,
+                Some(e) => time(&event) < time(e) - self.epsilon
+                    || (time(&event) <= time(e) + self.epsilon && matches!(event, Event::Edge(..)) && matches!(e, Event::Split(..))),
+            };
+            if better {
+                earliest = Some(event);
+            }
+        };
+
+        for (vi, v) in self.vertices.iter().enumerate().filter(|(_, v)| v.active) {
+            let w = &self.vertices[v.next];
+            let direction = self.edges[v.edges[1]].direction;
+            let length = dot(sub(w.position, v.position), direction);
+            let shrinking = dot(sub(w.velocity, v.velocity), direction);
+            if length <= self.epsilon {
+                consider(Event::Edge(vi, C::zero()));
+            } else if shrinking < C::zero() {
+                consider(Event::Edge(vi, -length / shrinking));
+            }
+        }
+
+        for (ri, r) in self.vertices.iter().enumerate().filter(|(ri, r)| r.active && self.is_reflex(*ri)) {
+            for (ai, a) in self.vertices.iter().enumerate().filter(|(_, a)| a.active) {
+                if ai == ri || a.next == ri {
+                    continue;
+                }
+                let b = &self.vertices[a.next];
+                let edge = &self.edges[a.edges[1]];
+                // The distance from the reflex vertex to the moving line of the edge, and how fast it closes
+                let distance = dot(sub(r.position, edge.origin), edge.normal) - self.time;
+                let closing = C::one() - dot(r.velocity, edge.normal);
+                if distance < -self.epsilon || closing <= self.epsilon {
+                    continue;
+                }
+                let dt = distance.max(C::zero()) / closing;
+                let hit = along(r.position, r.velocity, dt);
+                let (pa, pb) = (along(a.position, a.velocity, dt), along(b.position, b.velocity, dt));
+                if dot(sub(hit, pa), edge.direction) >= -self.epsilon && dot(sub(pb, hit), edge.direction) >= -self.epsilon {
+                    consider(Event::Split(ri, ai, dt));
+                }
+            }
+        }
+        earliest
+    }
+
+    fn advance(&mut self, dt: C) {
+        for v in self.vertices.iter_mut().filter(|v| v.active) {
+            v.position = along(v.position, v.velocity, dt);
+        }
+        self.time = self.time + dt;
+    }
+
+    // Close up the part of the wavefront containing a vertex if it has no area left, joining its vertices along the
+    // ridge they have collapsed onto
+    fn collapse_flat(&mut self, vi: usize) {
+        if !self.vertices[vi].active {
+            return;
+        }
+        let cycle: Vec<usize> = iter::successors(Some(vi), |&wi| Some(self.vertices[wi].next).filter(|&ui| ui != vi)).collect();
+        let ring: Vec<[C; 2]> = cycle.iter().map(|&wi| self.vertices[wi].position).collect();
+        let perimeter = (0..ring.len()).fold(C::zero(), |p, k| {
+            let d = sub(ring[(k + 1) % ring.len()], ring[k]);
+            p + d[0].hypot(d[1])
+        });
+        if math::signed_area_2(&ring) > self.epsilon * perimeter {
+            return;
+        }
+
+        let nodes: Vec<usize> = cycle.iter().map(|&wi| {
+            let w = self.vertices[wi].clone();
+            let node = self.add_node(w.position, &w.edges);
+            self.add_arc(w.node, node);
+            self.vertices[wi].active = false;
+            node
+        }).collect();
+        for (k, &node) in nodes.iter().enumerate() {
+            self.add_arc(node, nodes[(k + 1) % nodes.len()]);
+        }
+    }
+
+    fn edge_event(&mut self, vi: usize) {
+        let (v, w) = (self.vertices[vi].clone(), self.vertices[self.vertices[vi].next].clone());
+        let wi = v.next;
+        let two = C::one() + C::one();
+        let position = [(v.position[0] + w.position[0]) / two, (v.position[1] + w.position[1]) / two];
+        self.vertices[vi].active = false;
+        self.vertices[wi].active = false;
+
+        if self.vertices[w.next].next == vi {
+            // The last three vertices of a part of the wavefront meet
+            let ui = w.next;
+            let u = self.vertices[ui].clone();
+            let node = self.add_node(position, &[v.edges[0], v.edges[1], w.edges[1]]);
+            self.add_arc(v.node, node);
+            self.add_arc(w.node, node);
+            self.add_arc(u.node, node);
+            self.vertices[ui].active = false;
+            return;
+        }
+
+        let node = self.add_node(position, &[v.edges[0], v.edges[1], w.edges[1]]);
+        self.add_arc(v.node, node);
+        self.add_arc(w.node, node);
+        let new = self.add_vertex(position, [v.edges[0], w.edges[1]], [v.prev, w.next], node);
+        self.collapse_flat(new);
+    }
+
+    fn split_event(&mut self, ri: usize, ai: usize) {
+        let r = self.vertices[ri].clone();
+        let (bi, edge) = (self.vertices[ai].next, self.vertices[ai].edges[1]);
+        let position = r.position;
+        self.vertices[ri].active = false;
+
+        let node = self.add_node(position, &[r.edges[0], r.edges[1], edge]);
+        self.add_arc(r.node, node);
+        let first = self.add_vertex(position, [r.edges[0], edge], [r.prev, bi], node);
+        let second = self.add_vertex(position, [edge, r.edges[1]], [ai, r.next], node);
+        self.collapse_flat(first);
+        self.collapse_flat(second);
+    }
+}
+
+/// Compute the straight skeleton of `polygons` (interpreted with the even-odd rule, as in triangulation)
+pub fn straight_skeleton<'p, P: PolygonList<'p> + ?Sized>(polygons: &'p P) -> Skeleton<<P::Vertex as Vertex>::Coordinate>
+where <P::Vertex as Vertex>::Coordinate: Real {
+    skeleton_of(PolygonListExt::new(polygons).contours())
+}
+
+fn skeleton_of<C: Real>(mut contours: Vec<Vec<[C; 2]>>) -> Skeleton<C> {
+    for contour in contours.iter_mut() {
+        contour.dedup();
+        while contour.len() > 1 && contour.first() == contour.last() {
+            contour.pop();
+        }
+    }
+    contours.retain(|contour| contour.len() >= 3);
+    let filled_left = math::filled_left(&contours);
+    let scale = math::bounding_box(contours.iter().flatten())
+        .map_or(C::one(), |[min, max]| (max[0] - min[0]).max(max[1] - min[1]).max(C::one()));
+    let epsilon = scale * C::from(1e-9).unwrap_or_else(C::epsilon);
+
+    let mut wavefront = Wavefront {
+        edges: Vec::new(),
+        vertices: Vec::new(),
+        skeleton: Skeleton { nodes: Vec::new(), arcs: Vec::new(), faces: Vec::new() },
+        time: C::zero(),
+        epsilon,
+    };
+    for (contour, left) in contours.iter().zip(filled_left) {
+        let start = wavefront.edges.len();
+        for (k, &c) in contour.iter().enumerate() {
+            let next = contour[(k + 1) % contour.len()];
+            let (delta, length) = (sub(next, c), (next[0] - c[0]).hypot(next[1] - c[1]));
+            let direction = if length > C::zero() { [delta[0] / length, delta[1] / length] } else { [C::zero(); 2] };
+            let normal = if left { [-direction[1], direction[0]] } else { [direction[1], -direction[0]] };
+            // The wavefront is simulated with the filled area on the left, so edges of reversed contours run backwards
+            let direction = if left { direction } else { [-direction[0], -direction[1]] };
+            wavefront.edges.push(Edge { origin: c, direction, normal });
+            wavefront.skeleton.nodes.push(SkeletonNode { position: c, time: C::zero() });
+            wavefront.skeleton.faces.push(Vec::new());
+        }
+        let count = contour.len();
+        for (k, &position) in contour.iter().enumerate() {
+            let (e_prev, e_next) = (start + (k + count - 1) % count, start + k);
+            let (edges, [prev, next]) = if left {
+                ([e_prev, e_next], [start + (k + count - 1) % count, start + (k + 1) % count])
+            } else {
+                ([e_next, e_prev], [start + (k + 1) % count, start + (k + count - 1) % count])
+            };
+            let velocity = wavefront.velocity(edges);
+            wavefront.vertices.push(WaveVertex { position, velocity, edges, prev, next, node: start + k, active: true });
+        }
+        // Every face starts with the ends of its edge, in the order which keeps the face counter-clockwise
+        for k in 0..count {
+            let (e, node, other) = (start + k, start + k, start + (k + 1) % count);
+            let face = if left { [node, other] } else { [other, node] };
+            wavefront.skeleton.faces[e].extend(face);
+        }
+    }
+
+    // Every event removes at least one vertex, or splits the wavefront at a reflex vertex, which can only happen as
+    // often as there are pairs of vertices and edges
+    let limit = 4 * wavefront.vertices.len() * wavefront.vertices.len() + 16;
+    for _ in 0..limit {
+        match wavefront.next_event() {
+            Some(Event::Edge(vi, dt)) => {
+                wavefront.advance(dt);
+                wavefront.edge_event(vi);
+            }
+            Some(Event::Split(ri, ai, dt)) => {
+                wavefront.advance(dt);
+                wavefront.split_event(ri, ai);
+            }
+            None => break,
+        }
+    }
+
+    // Order the faces: each is monotone along its edge, so its nodes besides the edge's ends are sorted backwards along it
+    let Wavefront { edges, mut skeleton, .. } = wavefront;
+    for (face, edge) in skeleton.faces.iter_mut().zip(&edges) {
+        let nodes = &skeleton.nodes;
+        let projection = |ni: &usize| dot(nodes[*ni].position, edge.direction);
+        face[2..].sort_by(|a, b| projection(b).partial_cmp(&projection(a)).unwrap_or(core::cmp::Ordering::Equal));
+    }
+    skeleton
+}
+
+/// Build a roof over the footprints `polygons` from their [straight skeleton](straight_skeleton).
+///
+/// Each node of the skeleton is raised to `height(time)`, so `|t| t * slope` gives a roof with the same slope on every
+/// side. The eaves are at `height(0)`. The result is an open surface, with the roof facing up and gables facing out.
+pub fn generate_roof<'p, P: PolygonList<'p> + ?Sized>(polygons: &'p P, style: RoofStyle, height: impl Fn(<P::Vertex as Vertex>::Coordinate) -> <P::Vertex as Vertex>::Coordinate) -> Result<Solid<<P::Vertex as Vertex>::Coordinate>, TriangulationError<Infallible>>
+where <P::Vertex as Vertex>::Coordinate: Real + fmt::Debug {
+    let skeleton = straight_skeleton(polygons);
+    let mut positions: Vec<[_; 2]> = skeleton.nodes.iter().map(|n| n.position).collect();
+
+    // A gable replaces a triangular face, whose peak is not shared with another triangular face
+    let mut gables = vec![false; skeleton.faces.len()];
+    if style == RoofStyle::Gabled {
+        let peaks: Vec<Option<usize>> = skeleton.faces.iter().map(|face| (face.len() == 3).then(|| face[2])).collect();
+        for (fi, face) in skeleton.faces.iter().enumerate() {
+            let Some(peak) = peaks[fi] else { continue };
+            if peaks.iter().filter(|&&p| p == Some(peak)).count() == 1 {
+                // Move the peak back onto the edge, carrying the ridge with it
+                let [a, b] = [face[0], face[1]].map(|ni| skeleton.nodes[ni].position);
+                let [dx, dy] = sub(b, a);
+                let length_2 = dx * dx + dy * dy;
+                if length_2 > <P::Vertex as Vertex>::Coordinate::zero() {
+                    let c = skeleton.nodes[peak].position;
+                    let t = dot(sub(c, a), [dx, dy]) / length_2;
+                    positions[peak] = along(a, [dx, dy], t);
+                    gables[fi] = true;
+                }
+            }
+        }
+    }
+
+    let mut triangles = Vec::new();
+    for (face, gable) in skeleton.faces.iter().zip(gables) {
+        if gable {
+            triangles.push([face[0], face[1], face[2]]);
+            continue;
+        }
+        let ring: Vec<[_; 2]> = face.iter().map(|&ni| positions[ni]).collect();
+        let mesh = Mesh::from_polygons(&vec![ring])?;
+        triangles.extend(mesh.triangles.iter().map(|t| t.map(|vi| face[vi])));
+    }
+
+    let positions = positions.iter().zip(&skeleton.nodes).map(|(c, n)| [c[0], c[1], height(n.time)]).collect();
+    Ok(Solid { positions, triangles })
+}
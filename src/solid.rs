@@ -0,0 +1,387 @@
+//! Three-dimensional triangle meshes built from polygons by extrusion or revolution, with the normals needed to shade them.
+//!
+//! A [Solid] only stores positions and triangles. [Solid::face_normals] gives one normal per triangle for flat shading,
+//! and [Solid::vertex_normals] splits vertices along creases sharper than a threshold angle, so that curved surfaces
+//! shade smoothly while hard edges stay sharp.
+//!
+//! ```
+//! # use triangulate::solid;
+//! let square = vec![vec![[0f64, 0.], [1., 0.], [1., 1.], [0., 1.]]];
+//! let cube = solid::extrude(&square, 1.).expect("Triangulation failed");
+//! assert_eq!(cube.positions.len(), 8);
+//! assert_eq!(cube.triangles.len(), 12);
+//!
+//! // Every corner of a cube touches three faces at right angles, which are all split apart
+//! let shaded = cube.vertex_normals(std::f64::consts::FRAC_PI_4);
+//! assert_eq!(shaded.positions.len(), 24);
+//! ```
+
+use core::{convert::Infallible, fmt, ops::Range};
+use alloc::{vec, vec::Vec};
+
+use num_traits::{Zero, real::Real};
+
+use crate::{compat::HashMap, math, mesh::Mesh, PolygonList, TriangulationError, Vertex};
+
+/// A triangle mesh in three dimensions, with counter-clockwise triangles when seen from outside
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solid<C: Real> {
+    /// The positions of the vertices
+    pub positions: Vec<[C; 3]>,
+    /// The triangles, as indices into [Solid::positions]
+    pub triangles: Vec<[usize; 3]>,
+}
+
+/// A [Solid] with a normal for every vertex, as produced by [Solid::vertex_normals]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShadedSolid<C: Real> {
+    /// The positions of the vertices
+    pub positions: Vec<[C; 3]>,
+    /// The unit normal of each vertex
+    pub normals: Vec<[C; 3]>,
+    /// The triangles, as indices into [ShadedSolid::positions] and [ShadedSolid::normals]
+    pub triangles: Vec<[usize; 3]>,
+}
+
+impl<C: Real> Solid<C> {
+    /// The centroid of each triangle
+    pub fn centroids(&self) -> Vec<[C; 3]> {
+        let three = C::one() + C::one() + C::one();
+        self.triangles.iter().map(|t| {
+            let [a, b, c] = t.map(|vi| self.positions[vi]);
+            [(a[0] + b[0] + c[0]) / three, (a[1] + b[1] + c[1]) / three, (a[2] + b[2] + c[2]) / three]
+        }).collect()
+    }
+
+    /// The outward unit normal of each triangle. Degenerate triangles have a zero normal
+    pub fn face_normals(&self) -> Vec<[C; 3]> {
+        self.triangles.iter().map(|t| normalize(self.cross(t))).collect()
+    }
+
+    /// Compute a normal for every vertex by averaging the normals of the triangles around it, weighted by their angle at
+    /// the vertex (so that the result does not depend on how a face is split into triangles).
+    ///
+    /// Triangles whose normals differ by more than `crease_angle` (in radians) are not averaged together, so a vertex on
+    /// a crease is split into one vertex for each side of it. A `crease_angle` of zero gives flat shading, and one of
+    /// π smooths the whole surface.
+    pub fn vertex_normals(&self, crease_angle: C) -> ShadedSolid<C> {
+        let normals = self.face_normals();
+        let angles: Vec<[C; 3]> = self.triangles.iter().map(|t| {
+            let [a, b, c] = t.map(|vi| self.positions[vi]);
+            [angle(a, b, c), angle(b, c, a), angle(c, a, b)]
+        }).collect();
+        let threshold = crease_angle.min(C::from(core::f64::consts::PI).unwrap_or_else(C::one)).cos();
+
+        let mut incident = vec![Vec::new(); self.positions.len()];
+        for (ti, t) in self.triangles.iter().enumerate() {
+            for &vi in t {
+                incident[vi].push(ti);
+            }
+        }
+
+        let mut shaded = ShadedSolid { positions: Vec::new(), normals: Vec::new(), triangles: Vec::with_capacity(self.triangles.len()) };
+        // Corners are merged when they share a position and average the same triangles
+        let mut vertices: HashMap<(usize, Vec<usize>), usize> = HashMap::new();
+        for (ti, t) in self.triangles.iter().enumerate() {
+            shaded.triangles.push(t.map(|vi| {
+                let group: Vec<usize> = incident[vi].iter().copied()
+                    .filter(|&tj| tj == ti || dot(normals[ti], normals[tj]) >= threshold)
+                    .collect();
+                *vertices.entry((vi, group)).or_insert_with_key(|(_, group)| {
+                    let sum = group.iter().fold([C::zero(); 3], |s, &tj| {
+                        let (n, k) = (normals[tj], self.triangles[tj].iter().position(|&vj| vj == vi).unwrap_or(0));
+                        let w = angles[tj][k];
+                        [s[0] + n[0] * w, s[1] + n[1] * w, s[2] + n[2] * w]
+                    });
+                    shaded.positions.push(self.positions[vi]);
+                    shaded.normals.push(normalize(sum));
+                    shaded.positions.len() - 1
+                })
+            }));
+        }
+        shaded
+    }
+
+    fn cross(&self, t: &[usize; 3]) -> [C; 3] {
+        let [a, b, c] = t.map(|vi| self.positions[vi]);
+        let (u, v) = ([b[0] - a[0], b[1] - a[1], b[2] - a[2]], [c[0] - a[0], c[1] - a[1], c[2] - a[2]]);
+        [u[1] * v[2] - u[2] * v[1], u[2] * v[0] - u[0] * v[2], u[0] * v[1] - u[1] * v[0]]
+    }
+}
+
+fn dot<C: Real>(a: [C; 3], b: [C; 3]) -> C {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+// The angle at `a` of the triangle `a`, `b`, `c`
+fn angle<C: Real>(a: [C; 3], b: [C; 3], c: [C; 3]) -> C {
+    let u = normalize([b[0] - a[0], b[1] - a[1], b[2] - a[2]]);
+    let v = normalize([c[0] - a[0], c[1] - a[1], c[2] - a[2]]);
+    dot(u, v).max(-C::one()).min(C::one()).acos()
+}
+
+fn normalize<C: Real>(n: [C; 3]) -> [C; 3] {
+    let length = dot(n, n).sqrt();
+    if length > C::zero() {
+        n.map(|c| c / length)
+    } else {
+        [C::zero(); 3]
+    }
+}
+
+/// The shape of the rounded or cut edges added by [extrude_beveled]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum BevelProfile {
+    /// A single flat face cut across the edge
+    Chamfer,
+    /// A quarter of an ellipse, approximated by [BevelOptions::segments] faces
+    #[default]
+    Round,
+}
+
+/// Options for [extrude_beveled]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct BevelOptions<C: Real> {
+    /// How far the bevel reaches inwards from the outline of the polygons
+    pub width: C,
+    /// How far the bevel reaches along the extrusion from each cap. Clamped to half the extrusion depth
+    pub depth: C,
+    /// The shape of the bevel
+    pub profile: BevelProfile,
+    /// The number of faces of a [BevelProfile::Round] bevel
+    pub segments: usize,
+}
+
+impl<C: Real> Default for BevelOptions<C> {
+    /// A round bevel 1/10 wide and deep, with 3 segments
+    fn default() -> Self {
+        let tenth = C::from(0.1).unwrap_or_else(C::epsilon);
+        Self { width: tenth, depth: tenth, profile: BevelProfile::Round, segments: 3 }
+    }
+}
+
+/// Extrude `polygons` (interpreted with the even-odd rule, as in triangulation) from `z = 0` to `z = depth`, with both
+/// ends capped by their triangulation.
+///
+/// The bottom cap's vertices come first, in the iteration order of `polygons`, followed by the top cap's in the same
+/// order. The side walls reuse the caps' vertices, so use [Solid::vertex_normals] to separate the caps from the walls
+/// for shading.
+pub fn extrude<'p, P: PolygonList<'p> + ?Sized>(polygons: &'p P, depth: <P::Vertex as Vertex>::Coordinate) -> Result<Solid<<P::Vertex as Vertex>::Coordinate>, TriangulationError<Infallible>>
+where P::Vertex: Clone, <P::Vertex as Vertex>::Coordinate: Real + fmt::Debug {
+    let zero = <P::Vertex as Vertex>::Coordinate::zero();
+    extrude_layers(polygons, &[(zero, zero), (zero, depth)])
+}
+
+/// [extrude] `polygons`, replacing the edges around both caps with a bevel.
+///
+/// The caps are inset by the bevel's [width](BevelOptions::width), and the bevel joins them to the walls, which keep
+/// the outline of `polygons`. Every corner is inset along its miter, so the width should be small compared to the
+/// polygons' features: a bevel wider than a narrow part of a polygon folds over itself.
+///
+/// ```
+/// # use triangulate::solid::{self, BevelOptions, BevelProfile};
+/// let square = vec![vec![[0f64, 0.], [4., 0.], [4., 4.], [0., 4.]]];
+/// let options = BevelOptions { width: 1., depth: 1., profile: BevelProfile::Chamfer, ..Default::default() };
+/// let solid = solid::extrude_beveled(&square, 3., &options).expect("Triangulation failed");
+/// // The caps are 2x2 squares at z = 0 and z = 3, and the walls run from z = 1 to z = 2
+/// assert_eq!(solid.positions.len(), 4 * 4);
+/// assert!(solid.positions.contains(&[1., 1., 3.]) && solid.positions.contains(&[0., 0., 2.]));
+/// ```
+pub fn extrude_beveled<'p, P: PolygonList<'p> + ?Sized>(polygons: &'p P, depth: <P::Vertex as Vertex>::Coordinate, options: &BevelOptions<<P::Vertex as Vertex>::Coordinate>) -> Result<Solid<<P::Vertex as Vertex>::Coordinate>, TriangulationError<Infallible>>
+where P::Vertex: Clone, <P::Vertex as Vertex>::Coordinate: Real + fmt::Debug {
+    extrude_layers(polygons, &bevel_layers(depth, options))
+}
+
+// The (inset, z) layers of a beveled extrusion, from the bottom cap to the top cap
+fn bevel_layers<C: Real>(depth: C, options: &BevelOptions<C>) -> Vec<(C, C)> {
+    let bevel_depth = options.depth.abs().min(depth.abs() / (C::one() + C::one())) * depth.signum();
+    if bevel_depth == C::zero() || options.width == C::zero() {
+        return vec![(options.width, C::zero()), (options.width, depth)];
+    }
+    let segments = match options.profile {
+        BevelProfile::Chamfer => 1,
+        BevelProfile::Round => options.segments.max(1),
+    };
+
+    // The bevel from the bottom cap to the bottom of the walls
+    let bevel: Vec<(C, C)> = (0..=segments).map(|k| {
+        let t = C::from(k).unwrap_or_else(C::zero) / C::from(segments).unwrap_or_else(C::one);
+        let (inset, rise) = match options.profile {
+            BevelProfile::Chamfer => (C::one() - t, t),
+            BevelProfile::Round => {
+                let angle = t * C::from(core::f64::consts::FRAC_PI_2).unwrap_or_else(C::one);
+                (C::one() - angle.sin(), C::one() - angle.cos())
+            }
+        };
+        (options.width * inset, bevel_depth * rise)
+    }).collect();
+    let mut layers: Vec<(C, C)> = bevel.iter().copied()
+        .chain(bevel.iter().rev().map(|&(inset, z)| (inset, depth - z)))
+        .collect();
+    // Without walls between the bevels, the middle layer is shared
+    layers.dedup();
+    layers
+}
+
+// The coordinate type of the vertices of `P`
+type Coordinate<'p, P> = <<P as PolygonList<'p>>::Vertex as Vertex>::Coordinate;
+
+// Extrude through a sequence of (inset, z) layers, capping the first and last
+fn extrude_layers<'p, P: PolygonList<'p> + ?Sized>(polygons: &'p P, layers: &[(Coordinate<'p, P>, Coordinate<'p, P>)]) -> Result<Solid<Coordinate<'p, P>>, TriangulationError<Infallible>>
+where P::Vertex: Clone, <P::Vertex as Vertex>::Coordinate: Real + fmt::Debug {
+    let (mesh, contours) = Mesh::from_polygons_with_contours(polygons)?;
+    let n = mesh.vertices.len();
+    let zero = <P::Vertex as Vertex>::Coordinate::zero();
+
+    let rings = rings(&mesh, &contours);
+    let filled_left = math::filled_left(&rings);
+
+    let mut positions = Vec::with_capacity(layers.len() * n);
+    let mut insets: Vec<Vec<Vec<[_; 2]>>> = Vec::with_capacity(layers.len());
+    for &(inset, z) in layers {
+        let inset_rings: Vec<Vec<[_; 2]>> = rings.iter().zip(&filled_left).map(|(ring, &left)| inset_ring(ring, inset, left)).collect();
+        positions.extend(inset_rings.iter().flatten().map(|c| [c[0], c[1], z]));
+        insets.push(inset_rings);
+    }
+
+    // The caps are triangulated after insetting, since their vertices have moved
+    let (first, last) = (0, (layers.len() - 1) * n);
+    let caps = if layers[0].0 == zero {
+        mesh.triangles
+    } else {
+        Mesh::from_polygons(&insets[0])?.triangles
+    };
+    let mut triangles: Vec<[usize; 3]> = Vec::with_capacity(2 * caps.len() + 2 * (layers.len() - 1) * n);
+    for &[a, b, c] in caps.iter() {
+        triangles.extend([[a + first, c + first, b + first], [a + last, b + last, c + last]]);
+    }
+
+    for (below, above) in (0..layers.len()).zip(1..layers.len()) {
+        let (below, above) = (below * n, above * n);
+        for (range, &left) in contours.iter().zip(&filled_left) {
+            for a in range.clone() {
+                let b = if a + 1 == range.end { range.start } else { a + 1 };
+                // The wall faces away from the filled area
+                let (a, b) = if left { (a, b) } else { (b, a) };
+                triangles.push([a + below, b + below, b + above]);
+                triangles.push([a + below, b + above, a + above]);
+            }
+        }
+    }
+
+    // Extruding downwards turns the solid inside out
+    if layers[layers.len() - 1].1 < zero {
+        triangles.iter_mut().for_each(|t| t.swap(1, 2));
+    }
+
+    Ok(Solid { positions, triangles })
+}
+
+/// Revolve the polygons of `profile` by `angle` (in radians) around the z axis, in `segments` steps.
+///
+/// The x coordinates of `profile` become distances from the axis and its y coordinates become z coordinates, so the
+/// profile must lie in `x >= 0`. Vertices on the axis are shared by every step. A revolution by less than a full turn
+/// is closed with a triangulated copy of the profile at each end.
+///
+/// ```
+/// # use triangulate::solid;
+/// // A cone of height 1 over a unit disk
+/// let profile = vec![vec![[0f64, 0.], [1., 0.], [0., 1.]]];
+/// let cone = solid::revolve(&profile, 16, 2. * std::f64::consts::PI).expect("Triangulation failed");
+/// assert_eq!(cone.positions.len(), 2 + 16);
+/// assert_eq!(cone.triangles.len(), 2 * 16);
+/// ```
+pub fn revolve<'p, P: PolygonList<'p> + ?Sized>(profile: &'p P, segments: usize, angle: <P::Vertex as Vertex>::Coordinate) -> Result<Solid<<P::Vertex as Vertex>::Coordinate>, TriangulationError<Infallible>>
+where P::Vertex: Clone, <P::Vertex as Vertex>::Coordinate: Real {
+    let (mesh, contours) = Mesh::from_polygons_with_contours(profile)?;
+    let zero = <P::Vertex as Vertex>::Coordinate::zero();
+    let (segments, thetas) = revolution_steps(angle, segments);
+    let (steps, full) = (thetas.len(), thetas.len() == segments);
+
+    // The index of each profile vertex at each step
+    let mut positions = Vec::new();
+    let mut indices = vec![Vec::with_capacity(mesh.vertices.len()); steps];
+    for (s, (step, theta)) in indices.iter_mut().zip(thetas).enumerate() {
+        let (sin, cos) = theta.sin_cos();
+        for (vi, v) in mesh.vertices.iter().enumerate() {
+            if s > 0 && v.x() == zero {
+                step.push(vi);
+            } else {
+                step.push(positions.len());
+                positions.push([v.x() * cos, v.x() * sin, v.y()]);
+            }
+        }
+    }
+
+    let mut triangles = Vec::new();
+    if !full {
+        for &[a, b, c] in mesh.triangles.iter() {
+            triangles.push([a, b, c]);
+            triangles.push([indices[segments][a], indices[segments][c], indices[segments][b]]);
+        }
+    }
+    let rings = rings(&mesh, &contours);
+    for (range, left) in contours.iter().zip(math::filled_left(&rings)) {
+        for a in range.clone() {
+            let b = if a + 1 == range.end { range.start } else { a + 1 };
+            // The surface faces away from the filled area
+            let (a, b) = if left { (a, b) } else { (b, a) };
+            for s in 0..segments {
+                let (this, next) = (&indices[s], &indices[(s + 1) % steps]);
+                for t in [[this[a], next[a], next[b]], [this[a], next[b], this[b]]] {
+                    // Triangles touching the axis collapse
+                    if t[0] != t[1] && t[1] != t[2] && t[2] != t[0] {
+                        triangles.push(t);
+                    }
+                }
+            }
+        }
+    }
+
+    // Revolving clockwise turns the solid inside out
+    if angle < zero {
+        triangles.iter_mut().for_each(|t| t.swap(1, 2));
+    }
+
+    Ok(Solid { positions, triangles })
+}
+
+// The number of segments and the angle of each step of a revolution. The last step of a full turn is the first, so
+// it is left out
+fn revolution_steps<C: Real>(angle: C, segments: usize) -> (usize, Vec<C>) {
+    let turn = C::from(core::f64::consts::TAU).unwrap_or_else(C::max_value);
+    let full = angle.abs() >= turn;
+    let segments = segments.max(if full { 3 } else { 1 });
+    let angle = if full { turn * angle.signum() } else { angle };
+    let steps = if full { segments } else { segments + 1 };
+    (segments, (0..steps).map(|s| angle * C::from(s).unwrap_or_else(C::zero) / C::from(segments).unwrap_or_else(C::one)).collect())
+}
+
+// The coordinates of each polygon of a mesh built by `Mesh::from_polygons_with_contours`
+fn rings<V: Vertex>(mesh: &Mesh<V>, contours: &[Range<usize>]) -> Vec<Vec<[V::Coordinate; 2]>> {
+    contours.iter().map(|r| r.clone().map(|vi| [mesh.vertices[vi].x(), mesh.vertices[vi].y()]).collect()).collect()
+}
+
+// Move every vertex of a ring by `distance` towards its filled side, along the bisector of its corner
+fn inset_ring<C: Real>(ring: &[[C; 2]], distance: C, filled_left: bool) -> Vec<[C; 2]> {
+    if distance == C::zero() {
+        return ring.to_vec();
+    }
+    let distance = if filled_left { distance } else { -distance };
+    let normal = |a: [C; 2], b: [C; 2]| {
+        let (dx, dy) = (b[0] - a[0], b[1] - a[1]);
+        let length = dx.hypot(dy);
+        if length > C::zero() { [-dy / length, dx / length] } else { [C::zero(); 2] }
+    };
+    (0..ring.len()).map(|k| {
+        let (prev, c, next) = (ring[(k + ring.len() - 1) % ring.len()], ring[k], ring[(k + 1) % ring.len()]);
+        let (n0, n1) = (normal(prev, c), normal(c, next));
+        // The miter reaches 1 / cos(θ/2) times further than the distance, for an angle θ between the edges
+        let scale = C::one() + n0[0] * n1[0] + n0[1] * n1[1];
+        if scale > C::epsilon() {
+            [c[0] + (n0[0] + n1[0]) * distance / scale, c[1] + (n0[1] + n1[1]) * distance / scale]
+        } else {
+            [c[0] + n0[0] * distance, c[1] + n0[1] * distance]
+        }
+    }).collect()
+}
@@ -0,0 +1,447 @@
+//! Triangulated strokes along polylines, with optional dash patterns.
+//!
+//! Strokes are built as a [Mesh] of counter-clockwise triangles: one quad per segment, plus joins at the corners and
+//! caps at the ends, so they can be combined with filled areas using [Mesh::append]. Widths may be constant or vary
+//! along the line ([stroke_variable]). The style is described by [StrokeOptions], which follows the semantics (and
+//! defaults) of [lyon](https://docs.rs/lyon_tessellation)'s options of the same name, and of the HTML canvas.
+//!
+//! Triangles overlap on the inside of corners, and wherever the stroke crosses itself. This is harmless when the mesh is
+//! drawn with a single opaque color; for translucent strokes, draw with a stencil test or flatten the outline with
+//! [boolean::union_all](crate::boolean::union_all) first.
+//!
+//! Dash patterns follow SVG's `stroke-dasharray` and `stroke-dashoffset`: the pattern is measured along the whole
+//! polyline, so dashes continue around corners (and keep their joins), and on a closed contour the dash crossing the
+//! start point is a single dash. Each dash has caps at both ends.
+//!
+//! ```
+//! # use triangulate::stroke::{self, LineCap, LineJoin, StrokeOptions};
+//! let square = [[0f64, 0.], [4., 0.], [4., 4.], [0., 4.]];
+//! // 16 units of perimeter in dashes of 3 with gaps of 1, starting 1 unit before the first corner
+//! let dashes = stroke::dash(&square, true, &[3., 1.], 1.);
+//! assert_eq!(dashes.len(), 4);
+//! assert_eq!(dashes[1], vec![[3., 0.], [4., 0.], [4., 2.]]);
+//! // The last dash wraps around to the first
+//! assert_eq!(dashes[0], vec![[0., 1.], [0., 0.], [2., 0.]]);
+//!
+//! let options = StrokeOptions::default().with_line_width(0.5).with_line_join(LineJoin::Round).with_line_cap(LineCap::Round);
+//! let mesh = stroke::stroke_dashed(&square, true, &options, &[3., 1.], 1.);
+//! assert!(!mesh.triangles.is_empty());
+//! ```
+
+use core::{f64::consts::PI, fmt};
+use alloc::{vec, vec::Vec};
+
+use num_traits::{One, Zero, real::Real};
+
+use crate::{mesh::Mesh, Vertex};
+
+/// The shape of the outside of a corner between two segments of a stroke
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub enum LineJoin {
+    /// The outer edges of the segments are extended until they meet. If the tip would be further from the corner than
+    /// [StrokeOptions::miter_limit] half-widths, a [LineJoin::Bevel] is used instead
+    #[default]
+    Miter,
+    /// As [LineJoin::Miter], but a tip which is too long is cut off at [StrokeOptions::miter_limit] half-widths from the corner
+    MiterClip,
+    /// The outer corners of the segments are connected by a circular arc
+    Round,
+    /// The outer corners of the segments are connected directly
+    Bevel,
+}
+
+/// The shape of the ends of an open stroke
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub enum LineCap {
+    /// The stroke ends exactly at the end point
+    #[default]
+    Butt,
+    /// The stroke is extended past the end point by half its width
+    Square,
+    /// The stroke ends in a half circle around the end point
+    Round,
+}
+
+/// The style of a stroke
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct StrokeOptions<C: Real> {
+    /// The width of the stroke. Ignored by [stroke_variable], which has a width for each point
+    pub line_width: C,
+    /// The shape of corners
+    pub line_join: LineJoin,
+    /// The shape of the start of open strokes (and of each dash)
+    pub start_cap: LineCap,
+    /// The shape of the end of open strokes (and of each dash)
+    pub end_cap: LineCap,
+    /// The longest a miter may be, as the ratio between its length (from the inner to the outer corner) and the line width.
+    /// Values below 1 are treated as 1
+    pub miter_limit: C,
+    /// The greatest distance allowed between a round join or cap and the arc it approximates
+    pub tolerance: C,
+}
+
+impl<C: Real> StrokeOptions<C> {
+    /// The default [StrokeOptions::miter_limit]
+    pub const DEFAULT_MITER_LIMIT: f64 = 4.;
+    /// The default [StrokeOptions::tolerance]
+    pub const DEFAULT_TOLERANCE: f64 = 0.1;
+
+    /// Set [StrokeOptions::line_width]
+    pub fn with_line_width(mut self, line_width: C) -> Self {
+        self.line_width = line_width;
+        self
+    }
+
+    /// Set [StrokeOptions::line_join]
+    pub fn with_line_join(mut self, line_join: LineJoin) -> Self {
+        self.line_join = line_join;
+        self
+    }
+
+    /// Set both [StrokeOptions::start_cap] and [StrokeOptions::end_cap]
+    pub fn with_line_cap(mut self, line_cap: LineCap) -> Self {
+        self.start_cap = line_cap;
+        self.end_cap = line_cap;
+        self
+    }
+
+    /// Set [StrokeOptions::start_cap]
+    pub fn with_start_cap(mut self, start_cap: LineCap) -> Self {
+        self.start_cap = start_cap;
+        self
+    }
+
+    /// Set [StrokeOptions::end_cap]
+    pub fn with_end_cap(mut self, end_cap: LineCap) -> Self {
+        self.end_cap = end_cap;
+        self
+    }
+
+    /// Set [StrokeOptions::miter_limit]
+    pub fn with_miter_limit(mut self, miter_limit: C) -> Self {
+        self.miter_limit = miter_limit;
+        self
+    }
+
+    /// Set [StrokeOptions::tolerance]
+    pub fn with_tolerance(mut self, tolerance: C) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+}
+
+impl<C: Real> Default for StrokeOptions<C> {
+    /// A butt-capped, mitered line of width 1
+    fn default() -> Self {
+        Self {
+            line_width: C::one(),
+            line_join: LineJoin::default(),
+            start_cap: LineCap::default(),
+            end_cap: LineCap::default(),
+            miter_limit: C::from(Self::DEFAULT_MITER_LIMIT).unwrap_or_else(C::one),
+            tolerance: C::from(Self::DEFAULT_TOLERANCE).unwrap_or_else(C::epsilon),
+        }
+    }
+}
+
+fn sub<C: Real>(a: [C; 2], b: [C; 2]) -> [C; 2] {
+    [a[0] - b[0], a[1] - b[1]]
+}
+
+fn add<C: Real>(a: [C; 2], b: [C; 2]) -> [C; 2] {
+    [a[0] + b[0], a[1] + b[1]]
+}
+
+fn scale<C: Real>(a: [C; 2], s: C) -> [C; 2] {
+    [a[0] * s, a[1] * s]
+}
+
+fn dot<C: Real>(a: [C; 2], b: [C; 2]) -> C {
+    a[0] * b[0] + a[1] * b[1]
+}
+
+fn cross<C: Real>(a: [C; 2], b: [C; 2]) -> C {
+    a[0] * b[1] - a[1] * b[0]
+}
+
+fn rotate<C: Real>(a: [C; 2], angle: C) -> [C; 2] {
+    let (sin, cos) = angle.sin_cos();
+    [a[0] * cos - a[1] * sin, a[0] * sin + a[1] * cos]
+}
+
+/// Split the polyline through `points` into dashes, each returned as a polyline.
+///
+/// `pattern` alternates between the lengths of dashes and gaps, starting with a dash; if it has an odd number of entries
+/// it is repeated to make it even. `offset` is the distance into the pattern at the first point. If the pattern is empty,
+/// has a negative entry or has no length, the whole polyline is a single dash.
+///
+/// If `closed` is true, the polyline continues from the last point back to the first. A closed polyline which is not
+/// interrupted by any gap is returned as one dash ending at its first point.
+pub fn dash<V: Vertex>(points: &[V], closed: bool, pattern: &[V::Coordinate], offset: V::Coordinate) -> Vec<Vec<[V::Coordinate; 2]>>
+where V::Coordinate: Real {
+    let mut points: Vec<[V::Coordinate; 2]> = points.iter().map(|v| [v.x(), v.y()]).collect();
+    if closed {
+        if let Some(first) = points.first().copied() {
+            points.push(first);
+        }
+    }
+
+    let mut pattern = pattern.to_vec();
+    if pattern.len() % 2 == 1 {
+        pattern.extend_from_within(..);
+    }
+    let total = pattern.iter().fold(V::Coordinate::zero(), |total, length| total + *length);
+    if pattern.iter().any(|length| *length < V::Coordinate::zero()) || total <= V::Coordinate::zero() {
+        return finish_dashes(vec![points]);
+    }
+
+    // Find the position within the pattern at the first point
+    let mut phase = offset % total;
+    if phase < V::Coordinate::zero() {
+        phase = phase + total;
+    }
+    let mut index = 0;
+    while phase >= pattern[index] {
+        phase = phase - pattern[index];
+        index = (index + 1) % pattern.len();
+    }
+    let mut remaining = pattern[index] - phase;
+    let starts_on = index % 2 == 0;
+
+    let mut dashes = Vec::new();
+    let mut current = starts_on.then(|| points[..points.len().min(1)].to_vec());
+    for segment in points.windows(2) {
+        let [a, b] = [segment[0], segment[1]];
+        let d = sub(b, a);
+        let length = d[0].hypot(d[1]);
+        let mut position = V::Coordinate::zero();
+        while length - position > remaining {
+            position = position + remaining;
+            let c = add(a, scale(d, position / length));
+            match current.take() {
+                Some(mut dash) => {
+                    dash.push(c);
+                    dashes.push(dash);
+                }
+                None => current = Some(vec![c]),
+            }
+            index = (index + 1) % pattern.len();
+            remaining = pattern[index];
+        }
+        remaining = remaining - (length - position);
+        if let Some(dash) = current.as_mut() {
+            dash.push(b);
+        }
+    }
+
+    if let Some(last) = current {
+        // The dash crossing the start of a closed polyline continues into the first dash
+        if closed && starts_on && !dashes.is_empty() {
+            let first = dashes.remove(0);
+            let mut merged = last;
+            merged.extend_from_slice(&first[1..]);
+            dashes.insert(0, merged);
+        } else {
+            dashes.push(last);
+        }
+    }
+    finish_dashes(dashes)
+}
+
+// Remove repeated points, and dashes with no length
+fn finish_dashes<C: Real>(dashes: Vec<Vec<[C; 2]>>) -> Vec<Vec<[C; 2]>> {
+    dashes.into_iter().filter_map(|mut dash| {
+        dash.dedup();
+        (dash.len() >= 2).then_some(dash)
+    }).collect()
+}
+
+
+/// Triangulate a stroke centered on the polyline through `points`.
+///
+/// If `closed` is true, the polyline continues from the last point back to the first, and that corner is joined too;
+/// closed strokes have no caps. Repeated points are ignored.
+pub fn stroke<V: Vertex>(points: &[V], closed: bool, options: &StrokeOptions<V::Coordinate>) -> Mesh<[V::Coordinate; 2]>
+where V::Coordinate: Real + fmt::Debug {
+    stroke_variable(points, &vec![options.line_width; points.len()], closed, options)
+}
+
+/// Triangulate a stroke centered on the polyline through `points`, with the width at each point given by `widths`.
+///
+/// The width changes linearly along each segment, so a width of zero at the ends tapers the stroke to a point.
+/// Negative widths are treated as zero. Where a point is repeated, the width of its first occurrence is used.
+///
+/// # Panics
+/// Panics if `widths` and `points` have different lengths.
+pub fn stroke_variable<V: Vertex>(points: &[V], widths: &[V::Coordinate], closed: bool, options: &StrokeOptions<V::Coordinate>) -> Mesh<[V::Coordinate; 2]>
+where V::Coordinate: Real + fmt::Debug {
+    assert_eq!(points.len(), widths.len(), "Every point needs a width");
+    let two = V::Coordinate::one() + V::Coordinate::one();
+    let mut points: Vec<([V::Coordinate; 2], V::Coordinate)> = points.iter().zip(widths)
+        .map(|(v, width)| ([v.x(), v.y()], width.max(V::Coordinate::zero()) / two))
+        .collect();
+    points.dedup_by(|(c1, _), (c0, _)| c0 == c1);
+    while closed && points.len() > 1 && points.first().map(|(c, _)| c) == points.last().map(|(c, _)| c) {
+        points.pop();
+    }
+    let (points, half_widths): (Vec<_>, Vec<_>) = points.into_iter().unzip();
+    let mut mesh = Mesh::default();
+    stroke_into(&mut mesh, &points, &half_widths, closed, options);
+    mesh
+}
+
+/// Triangulate strokes along each [dash] of the polyline through `points`
+pub fn stroke_dashed<V: Vertex>(points: &[V], closed: bool, options: &StrokeOptions<V::Coordinate>, pattern: &[V::Coordinate], offset: V::Coordinate) -> Mesh<[V::Coordinate; 2]>
+where V::Coordinate: Real + fmt::Debug {
+    let half_width = options.line_width.max(V::Coordinate::zero()) / (V::Coordinate::one() + V::Coordinate::one());
+    let mut mesh = Mesh::default();
+    for mut dash in dash(points, closed, pattern, offset) {
+        // A closed polyline without gaps is still closed
+        let closed = dash.len() > 2 && dash.first() == dash.last();
+        if closed {
+            dash.pop();
+        }
+        stroke_into(&mut mesh, &dash, &vec![half_width; dash.len()], closed, options);
+    }
+    mesh
+}
+
+// The offsets of a point to the right and left of a segment, sharing a vertex where the width is zero
+fn push_sides<C: Real + fmt::Debug>(mesh: &mut Mesh<[C; 2]>, c: [C; 2], normal: [C; 2]) -> [usize; 2] {
+    let right = mesh.vertices.len();
+    if normal == [C::zero(), C::zero()] {
+        mesh.vertices.push(c);
+        [right, right]
+    } else {
+        mesh.vertices.extend([sub(c, normal), add(c, normal)]);
+        [right, right + 1]
+    }
+}
+
+// Fan triangles around `center` along an arc from the vertex `from` (at the offset `start` from the center) through `sweep` radians
+// counter-clockwise to the vertex `to`
+fn push_arc<C: Real + fmt::Debug>(mesh: &mut Mesh<[C; 2]>, center: usize, [from, to]: [usize; 2], start: [C; 2], sweep: C, tolerance: C) {
+    let radius = start[0].hypot(start[1]);
+    // Each segment may cut inside the arc by at most the tolerance
+    let ratio = (C::one() - tolerance.max(C::epsilon()) / radius).max(-C::one());
+    let step = ratio.acos() * (C::one() + C::one());
+    let segments = (sweep / step).ceil().to_usize().unwrap_or(1).clamp(1, 1024);
+    let c = mesh.vertices[center];
+    let mut previous = from;
+    for k in 1..=segments {
+        let next = if k == segments {
+            to
+        } else {
+            let angle = sweep * C::from(k).unwrap_or_else(C::zero) / C::from(segments).unwrap_or_else(C::one);
+            mesh.vertices.push(add(c, rotate(start, angle)));
+            mesh.vertices.len() - 1
+        };
+        mesh.triangles.push([center, previous, next]);
+        previous = next;
+    }
+}
+
+fn stroke_into<C: Real + fmt::Debug>(mesh: &mut Mesh<[C; 2]>, points: &[[C; 2]], half_widths: &[C], closed: bool, options: &StrokeOptions<C>) {
+    if points.len() < 2 {
+        return;
+    }
+    let closed = closed && points.len() > 2;
+    let segment_count = if closed { points.len() } else { points.len() - 1 };
+
+    // The left-hand unit normal of each segment, and the indices of its quad's corners
+    let mut segments: Vec<([C; 2], [usize; 4])> = Vec::with_capacity(segment_count);
+    for i in 0..segment_count {
+        let j = (i + 1) % points.len();
+        let (a, b) = (points[i], points[j]);
+        let d = sub(b, a);
+        let normal = scale([-d[1], d[0]], d[0].hypot(d[1]).recip());
+        let [a_right, a_left] = push_sides(mesh, a, scale(normal, half_widths[i]));
+        let [b_right, b_left] = push_sides(mesh, b, scale(normal, half_widths[j]));
+        let quad = [a_right, b_right, b_left, a_left];
+        // A side with no width leaves a triangle
+        for t in [[quad[0], quad[1], quad[2]], [quad[0], quad[2], quad[3]]] {
+            if t[0] != t[1] && t[1] != t[2] && t[2] != t[0] {
+                mesh.triangles.push(t);
+            }
+        }
+        segments.push((normal, quad));
+    }
+
+    let join_count = if closed { segment_count } else { segment_count - 1 };
+    for i in 0..join_count {
+        let ((n0, q0), (n1, q1)) = (segments[i], segments[(i + 1) % segment_count]);
+        let turn = cross(n0, n1);
+        let (c, half_width) = (points[(i + 1) % points.len()], half_widths[(i + 1) % points.len()]);
+        if turn == C::zero() || half_width == C::zero() {
+            continue;
+        }
+        // The outer side of the corner is on the right of a left turn, and the left of a right turn. Walking
+        // counter-clockwise around the corner, the join starts at the offset `o0` and ends at `o1`
+        let (from, to, o0, o1) = if turn > C::zero() {
+            (q0[1], q1[0], scale(n0, -half_width), scale(n1, -half_width))
+        } else {
+            (q1[3], q0[2], scale(n1, half_width), scale(n0, half_width))
+        };
+        let center = mesh.vertices.len();
+        mesh.vertices.push(c);
+
+        // The bisector of the offsets points at the miter's tip, which is 1 / cos(turn / 2) half-widths from the corner
+        let bisector = add(o0, o1);
+        let cos = bisector[0].hypot(bisector[1]) / (half_width + half_width);
+        let limit = options.miter_limit.max(C::one());
+        match options.line_join {
+            LineJoin::Miter | LineJoin::MiterClip if cos > C::zero() && C::one() <= limit * cos => {
+                mesh.vertices.push(add(c, scale(bisector, half_width / (cos * (half_width + half_width) * cos))));
+                let tip = mesh.vertices.len() - 1;
+                mesh.triangles.extend([[center, from, tip], [center, tip, to]]);
+            }
+            LineJoin::MiterClip => {
+                // Cut the miter along a line perpendicular to the bisector, at the limit. The outer edges of the segments
+                // reach it after the same distance, along directions perpendicular to their offsets
+                let u = if cos > C::zero() { scale(bisector, (cos * (half_width + half_width)).recip()) } else { scale([-o0[1], o0[0]], half_width.recip()) };
+                let along = (limit * half_width - dot(o0, u)) / dot(scale([-o0[1], o0[0]], half_width.recip()), u);
+                let p0 = add(add(c, o0), scale([-o0[1], o0[0]], along / half_width));
+                let p1 = add(add(c, o1), scale([o1[1], -o1[0]], along / half_width));
+                mesh.vertices.extend([p0, p1]);
+                let (i0, i1) = (mesh.vertices.len() - 2, mesh.vertices.len() - 1);
+                mesh.triangles.extend([[center, from, i0], [center, i0, i1], [center, i1, to]]);
+            }
+            LineJoin::Round => {
+                let sweep = cross(o0, o1).atan2(dot(o0, o1));
+                push_arc(mesh, center, [from, to], o0, sweep, options.tolerance);
+            }
+            LineJoin::Miter | LineJoin::Bevel => mesh.triangles.push([center, from, to]),
+        }
+    }
+
+    if !closed {
+        let (n, q) = segments[0];
+        push_cap(mesh, points[0], half_widths[0], scale(n, -C::one()), [q[3], q[0]], options.start_cap, options.tolerance);
+        let (n, q) = segments[segment_count - 1];
+        push_cap(mesh, points[points.len() - 1], half_widths[points.len() - 1], n, [q[1], q[2]], options.end_cap, options.tolerance);
+    }
+}
+
+// Add a cap at the point `c`, between the vertices `sides` at `c - normal * half_width` and `c + normal * half_width`, with `normal`
+// a unit vector rotated counter-clockwise from the outward direction of the line
+fn push_cap<C: Real + fmt::Debug>(mesh: &mut Mesh<[C; 2]>, c: [C; 2], half_width: C, normal: [C; 2], sides: [usize; 2], cap: LineCap, tolerance: C) {
+    if half_width == C::zero() {
+        return;
+    }
+    let outward = scale([normal[1], -normal[0]], half_width);
+    let offset = scale(normal, half_width);
+    match cap {
+        LineCap::Butt => {}
+        LineCap::Square => {
+            let first = mesh.vertices.len();
+            mesh.vertices.extend([add(sub(c, offset), outward), add(add(c, offset), outward)]);
+            mesh.triangles.extend([[sides[0], first, first + 1], [sides[0], first + 1, sides[1]]]);
+        }
+        LineCap::Round => {
+            let center = mesh.vertices.len();
+            mesh.vertices.push(c);
+            let pi = C::from(PI).unwrap_or_else(C::one);
+            push_arc(mesh, center, sides, scale(offset, -C::one()), pi, tolerance);
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use approx::{assert_abs_diff_eq, assert_relative_eq, assert_relative_ne};
+
+use crate::{mesh::Mesh, PolygonList};
+
+#[test]
+fn component_summaries() {
+    let square = vec![vec![[0f64, 0.], [3., 0.], [3., 3.], [0., 3.]]];
+    let shifted = vec![vec![[0.1f64 + 0.2 - 0.3, 0.], [3., 0.], [3., 3.], [0., 3.]]];
+    let summary = square.trapezoidize().expect("Trapezoidation failed").component_summaries().expect("Trapezoidation failed");
+    let shifted_summary = shifted.trapezoidize().expect("Trapezoidation failed").component_summaries().expect("Trapezoidation failed");
+    assert_eq!(summary.len(), 1);
+    assert_relative_eq!(summary[0], shifted_summary[0]);
+    assert_relative_eq!(summary[0].area, 9.);
+}
+
+#[test]
+fn mesh() {
+    let polygon = vec![vec![[0f64, 0.], [1., 0.], [1., 1.], [0., 1.]]];
+    let nudged = vec![vec![[0f64, 0.], [1., 1e-12], [1., 1.], [0., 1.]]];
+    let mesh = Mesh::from_polygons(&polygon).expect("Triangulation failed");
+    let nudged_mesh = Mesh::from_polygons(&nudged).expect("Triangulation failed");
+    assert_abs_diff_eq!(mesh, nudged_mesh, epsilon = 1e-9);
+    assert_relative_ne!(mesh, nudged_mesh);
+}
@@ -0,0 +1,42 @@
+use crate::area;
+
+// Far from the origin, like UTM coordinates in meters
+const X: f64 = 500_000.;
+const Y: f64 = 5_000_000.;
+
+#[test]
+fn slender() {
+    // A sliver whose apex is 2^-20 above its base, with twice its area exactly 1000 * 2^-20
+    let epsilon = 2f64.powi(-20);
+    let polygon = vec![vec![[X, Y], [X + 1000., Y + 1000.], [X + 500., Y + 500. + epsilon]]];
+    assert_eq!(area::signed_area(&polygon), 500. * epsilon);
+
+    let reversed = vec![polygon[0].iter().rev().copied().collect::<Vec<_>>()];
+    assert_eq!(area::signed_area(&reversed), -500. * epsilon);
+
+    let [x, y] = area::centroid(&polygon).expect("Polygon has no area");
+    assert!((x - (X + 500.)).abs() < 1e-6);
+    assert!((y - (Y + 500. + epsilon / 3.)).abs() < 1e-6);
+}
+
+#[test]
+fn many_vertices() {
+    let n = 10000;
+    let r = 100.;
+    let polygon = vec![(0..n).map(|i| {
+        let (sin, cos) = (std::f64::consts::TAU * i as f64 / n as f64).sin_cos();
+        [X + r * cos, Y + r * sin]
+    }).collect::<Vec<_>>()];
+    let expected = n as f64 / 2. * r * r * (std::f64::consts::TAU / n as f64).sin();
+    assert!((area::signed_area(&polygon) - expected).abs() < expected * 1e-12);
+
+    let [x, y] = area::centroid(&polygon).expect("Polygon has no area");
+    assert!((x - X).abs() < 1e-9 && (y - Y).abs() < 1e-9);
+}
+
+#[test]
+fn degenerate() {
+    assert_eq!(area::signed_area(&Vec::<Vec<[f64; 2]>>::new()), 0.);
+    assert_eq!(area::centroid(&Vec::<Vec<[f64; 2]>>::new()), None);
+    assert_eq!(area::centroid(&vec![vec![[0f64, 0.], [1., 1.], [2., 2.]]]), None);
+}
@@ -0,0 +1,75 @@
+use crate::{boolean::{self, BooleanOp}, formats, ListFormat, PolygonList};
+
+use super::util::square;
+
+fn area(contours: &[Vec<[f64; 2]>]) -> f64 {
+    contours.iter().map(|contour| {
+        (0..contour.len()).map(|i| {
+            let (c0, c1) = (contour[i], contour[(i + 1) % contour.len()]);
+            c0[0] * c1[1] - c1[0] * c0[1]
+        }).sum::<f64>() / 2.
+    }).sum()
+}
+
+fn assert_triangulates(contours: &Vec<Vec<[f64; 2]>>) {
+    let mut triangles = Vec::<[usize; 2]>::new();
+    contours.triangulate(formats::IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+}
+
+#[test]
+fn binary_ops() {
+    let a = vec![square(0., 0., 2.)];
+    let b = vec![square(1., 1., 2.)];
+    for (op, expected) in [(BooleanOp::Union, 7.), (BooleanOp::Intersection, 1.), (BooleanOp::Difference, 3.), (BooleanOp::Xor, 6.)] {
+        let result = boolean::boolean(&a, &b, op);
+        assert_eq!(area(&result), expected, "{:?}", op);
+        // The two halves of the xor touch at single vertices, which triangulation does not allow
+        if op != BooleanOp::Xor {
+            assert_triangulates(&result);
+        }
+    }
+}
+
+#[test]
+fn shared_edge() {
+    let a = vec![square(0., 0., 1.)];
+    let b = vec![square(1., 0., 1.)];
+    let union = boolean::boolean(&a, &b, BooleanOp::Union);
+    assert_eq!(union, vec![vec![[0., 0.], [2., 0.], [2., 1.], [0., 1.]]]);
+    assert!(boolean::boolean(&a, &b, BooleanOp::Intersection).is_empty());
+    assert_eq!(area(&boolean::boolean(&a, &b, BooleanOp::Difference)), 1.);
+}
+
+#[test]
+fn hole() {
+    let outer = vec![square(0., 0., 4.)];
+    let inner = vec![square(1., 1., 2.)];
+    let ring = boolean::boolean(&outer, &inner, BooleanOp::Difference);
+    assert_eq!(ring.len(), 2);
+    assert_eq!(area(&ring), 12.);
+    assert_triangulates(&ring);
+
+    // Filling the hole again restores the square
+    let filled = boolean::boolean(&ring, &inner, BooleanOp::Union);
+    assert_eq!(filled.len(), 1);
+    assert_eq!(area(&filled), 16.);
+}
+
+#[test]
+fn cascaded_union() {
+    // A grid of overlapping squares plus a few disjoint ones
+    let mut shapes = Vec::new();
+    for i in 0..8 {
+        for j in 0..8 {
+            shapes.push(vec![square(i as f64 * 0.75, j as f64 * 0.75, 1.)]);
+        }
+    }
+    shapes.push(vec![square(20., 20., 1.)]);
+    shapes.push(vec![square(-20., 20., 1.)]);
+    let union = boolean::union_all(&shapes);
+    assert_eq!(union.len(), 3);
+    assert!((area(&union) - (6.25 * 6.25 + 2.)).abs() < 1e-9);
+    assert_triangulates(&union);
+
+    assert!(boolean::union_all::<Vec<Vec<[f64; 2]>>, f64>(&[]).is_empty());
+}
@@ -0,0 +1,129 @@
+use crate::{buffers::IndexBuffers, formats::IndexBufferError, mesh::Mesh, solid, Vertex};
+
+#[test]
+fn from_mesh() {
+    let polygons = vec![
+        vec![[0f64, 0.], [4., 0.], [4., 4.], [0., 4.]],
+        vec![[1., 1.], [1., 3.], [3., 3.], [3., 1.]],
+    ];
+    let mesh = Mesh::from_polygons(&polygons).expect("Triangulation failed");
+    let buffers = IndexBuffers::from_mesh(&mesh).expect("Too many vertices");
+    assert_eq!(buffers.positions.len(), mesh.vertices.len());
+    for (p, v) in buffers.positions.iter().zip(&mesh.vertices) {
+        assert_eq!(*p, [v[0] as f32, v[1] as f32, 0.]);
+    }
+    assert_eq!(buffers.indices, mesh.triangles.iter().flatten().map(|vi| *vi as u32).collect::<Vec<_>>());
+
+    // Elevations are kept
+    let elevated = IndexBuffers::from_solid(&mesh.elevate(|v| v[0])).expect("Too many vertices");
+    assert!(elevated.positions.iter().all(|p| p[2] == p[0]));
+    assert_eq!(elevated.indices, buffers.indices);
+
+    let cube = solid::extrude(&vec![vec![[0f32, 0.], [1., 0.], [1., 1.], [0., 1.]]], 1.).expect("Triangulation failed");
+    let buffers = IndexBuffers::from_solid(&cube).expect("Too many vertices");
+    assert_eq!(buffers.positions, cube.positions);
+    assert_eq!(buffers.indices.len(), 3 * 12);
+}
+
+#[test]
+fn merge() {
+    let square = Mesh::from_polygons(&vec![vec![[0f64, 0.], [1., 0.], [1., 1.], [0., 1.]]]).expect("Triangulation failed");
+    let square = IndexBuffers::from_mesh(&square).expect("Too many vertices");
+
+    let mut merged = square.clone();
+    merged.append(&square).expect("Too many vertices");
+    merged.append_translated(&square, [2., 0., 1.]).expect("Too many vertices");
+    assert_eq!(merged.positions.len(), 12);
+    assert_eq!(merged.indices.len(), 18);
+    assert_eq!(merged.positions[8..], square.positions.iter().map(|p| [p[0] + 2., p[1], 1.]).collect::<Vec<_>>()[..]);
+    assert_eq!(merged.indices[12..], square.indices.iter().map(|vi| vi + 8).collect::<Vec<_>>()[..]);
+
+    // Extracting the triangles of an appended copy gives back the copy
+    assert_eq!(merged.extract(2..4), square);
+    let mut translated = merged.extract(4..6);
+    assert_eq!(translated.indices, square.indices);
+    translated.rebase(8).expect("Too many vertices");
+    assert_eq!(translated.indices[..], merged.indices[12..]);
+
+    // Reserved ranges follow the existing vertices and indices
+    let (vertices, indices) = merged.reserve(3, 1).expect("Too many vertices");
+    assert_eq!((vertices, indices.clone()), (12..15, 18..21));
+    merged.indices[indices].copy_from_slice(&[12, 13, 14]);
+    assert_eq!(merged.extract(6..7).positions, vec![[0.; 3]; 3]);
+
+    // Indices past u32 fail, and leave the buffers unchanged
+    assert_eq!(translated.rebase(u32::MAX), Err(IndexBufferError::IndexOverflow));
+    assert_eq!(translated.indices[..], merged.indices[12..18]);
+    let len = merged.positions.len();
+    assert_eq!(merged.reserve(u32::MAX as usize, 0), Err(IndexBufferError::IndexOverflow));
+    assert_eq!(merged.positions.len(), len);
+}
+
+#[derive(Debug, Clone)]
+struct TaggedVertex {
+    position: [f64; 2],
+    tag: char,
+}
+
+impl Vertex for TaggedVertex {
+    type Coordinate = f64;
+    type Payload = char;
+
+    fn x(&self) -> f64 { self.position[0] }
+    fn y(&self) -> f64 { self.position[1] }
+    fn payload(&self) -> &char { &self.tag }
+}
+
+#[test]
+fn payloads() {
+    let polygons = vec![
+        vec![[0f64, 0.], [1., 0.], [1., 1.], [0., 1.]].into_iter().zip(['a', 'b', 'c', 'd']).map(|(position, tag)| TaggedVertex { position, tag }).collect::<Vec<_>>(),
+    ];
+    let mesh = Mesh::from_polygons(&polygons).expect("Triangulation failed");
+    let buffers = IndexBuffers::from_mesh(&mesh).expect("Too many vertices");
+    assert_eq!(buffers.payloads, vec!['a', 'b', 'c', 'd']);
+
+    // Payloads stay with their positions
+    let mut merged = buffers.clone();
+    merged.append_translated(&buffers, [2., 0., 0.]).expect("Too many vertices");
+    assert_eq!(merged.payloads.len(), merged.positions.len());
+    let extracted = merged.extract(2..4);
+    assert_eq!(extracted.payloads, buffers.payloads);
+    for (position, payload) in extracted.positions.iter().zip(&extracted.payloads) {
+        let vertex = &polygons[0][(*payload as u8 - b'a') as usize];
+        assert_eq!(*position, [vertex.position[0] as f32 + 2., vertex.position[1] as f32, 0.]);
+    }
+
+    let (vertices, _) = merged.reserve(2, 0).expect("Too many vertices");
+    assert_eq!(merged.payloads[vertices.start as usize..], ['\0', '\0']);
+}
+
+#[cfg(feature = "meshopt")]
+#[test]
+fn optimize() {
+    let mut mesh = Mesh::from_polygons(&vec![vec![[0f64, 0.], [4., 0.], [4., 4.], [0., 4.]]]).expect("Triangulation failed");
+    mesh.subdivide_edges(&mut crate::LinearConstructor, 0.5);
+    mesh.vertices.push([9., 9.]);
+    let original = IndexBuffers::from_mesh(&mesh).expect("Too many vertices");
+    let mut optimized = original.clone();
+    let remap = optimized.optimize();
+
+    // The same triangles, with the unused vertex removed
+    assert_eq!(remap.len(), original.positions.len());
+    assert_eq!(remap[remap.len() - 1], u32::MAX);
+    assert_eq!(optimized.positions.len(), original.positions.len() - 1);
+    let triangles = |buffers: &IndexBuffers| {
+        let mut triangles: Vec<[[u32; 2]; 3]> = buffers.indices.chunks(3).map(|t| {
+            let corners = [0, 1, 2].map(|k| buffers.positions[t[k] as usize]).map(|p| [p[0].to_bits(), p[1].to_bits()]);
+            // Triangles may start at any corner, so start at the smallest
+            let first = (0..3).min_by_key(|k| corners[*k]).unwrap_or(0);
+            [0, 1, 2].map(|k| corners[(first + k) % 3])
+        }).collect();
+        triangles.sort();
+        triangles
+    };
+    assert_eq!(triangles(&optimized), triangles(&original));
+    for (vi, new) in remap.iter().enumerate().filter(|(_, new)| **new != u32::MAX) {
+        assert_eq!(optimized.positions[*new as usize], original.positions[vi]);
+    }
+}
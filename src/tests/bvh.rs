@@ -0,0 +1,75 @@
+use crate::{bvh::Bvh, mesh::Mesh, LinearConstructor};
+
+fn mesh() -> Mesh<[f64; 2]> {
+    let polygons = vec![
+        vec![[0f64, 0.], [6., 0.], [6., 4.], [0., 4.]],
+        vec![[2., 1.], [2., 3.], [4., 3.], [4., 1.]],
+    ];
+    let mut mesh = Mesh::from_polygons(&polygons).expect("Triangulation failed");
+    mesh.subdivide_edges(&mut LinearConstructor, 0.5);
+    mesh
+}
+
+fn contains(t: [&[f64; 2]; 3], p: [f64; 2]) -> bool {
+    (0..3).all(|k| {
+        let (a, b) = (t[k], t[(k + 1) % 3]);
+        (b[0] - a[0]) * (p[1] - a[1]) - (p[0] - a[0]) * (b[1] - a[1]) >= 0.
+    })
+}
+
+#[test]
+fn query_point() {
+    let mesh = mesh();
+    let bvh = Bvh::new(&mesh);
+    assert_eq!(bvh.bounds(), Some([[0., 0.], [6., 4.]]));
+    for i in 0..60 {
+        for j in 0..40 {
+            let p = [i as f64 * 0.1 + 0.03, j as f64 * 0.1 + 0.07];
+            let expected = mesh.iter_triangles().any(|t| contains(t, p));
+            match bvh.query_point(&mesh, p) {
+                Some(ti) => assert!(contains(mesh.triangles[ti].map(|vi| &mesh.vertices[vi]), p)),
+                None => assert!(!expected, "{p:?} not found"),
+            }
+        }
+    }
+    // In the hole, and outside
+    assert_eq!(bvh.query_point(&mesh, [3., 2.]), None);
+    assert_eq!(bvh.query_point(&mesh, [-1., 2.]), None);
+    // On a vertex
+    assert!(bvh.query_point(&mesh, [0., 0.]).is_some());
+}
+
+#[test]
+fn query_aabb() {
+    let mesh = mesh();
+    let bvh = Bvh::new(&mesh);
+
+    // A box in the hole only touches the triangles around it
+    assert!(bvh.query_aabb(&mesh, [2.5, 1.5], [3.5, 2.5]).is_empty());
+    let around = bvh.query_aabb(&mesh, [1.9, 0.9], [4.1, 3.1]);
+    assert!(!around.is_empty());
+    assert!(around.iter().all(|ti| mesh.triangles[*ti].iter().any(|vi| {
+        let [x, y] = mesh.vertices[*vi];
+        (1.4..=4.6).contains(&x) && (0.4..=3.6).contains(&y)
+    })));
+
+    // Everything within a box covering the whole mesh
+    assert_eq!(bvh.query_aabb(&mesh, [-1., -1.], [7., 5.]), (0..mesh.triangles.len()).collect::<Vec<_>>());
+
+    // Small boxes find every triangle containing their center
+    for i in 0..30 {
+        let p = [i as f64 * 0.2 + 0.05, 0.31];
+        let found = bvh.query_aabb(&mesh, [p[0] - 0.01, p[1] - 0.01], [p[0] + 0.01, p[1] + 0.01]);
+        for (ti, t) in mesh.iter_triangles().enumerate() {
+            if contains(t, p) {
+                assert!(found.contains(&ti));
+            }
+        }
+    }
+
+    let empty = Mesh::<[f64; 2]>::default();
+    let bvh = Bvh::new(&empty);
+    assert_eq!(bvh.bounds(), None);
+    assert!(bvh.query_aabb(&empty, [0., 0.], [1., 1.]).is_empty());
+    assert_eq!(bvh.query_point(&empty, [0., 0.]), None);
+}
@@ -0,0 +1,94 @@
+use crate::{mesh::Mesh, PolygonList};
+
+use super::util::square;
+
+#[test]
+fn trapezoid_components() {
+    // A frame, an island in its hole, and a separate triangle
+    let polygons = vec![
+        square(0., 0., 10.),
+        square(3., 3., 4.),
+        square(4., 4., 2.),
+        vec![[12., 4.], [14., 4.], [13., 6.]],
+    ];
+    let trapezoidation = polygons.trapezoidize().expect("Trapezoidation failed");
+    let components = trapezoidation.components().expect("Trapezoidation failed");
+    assert_eq!(components.count, 3);
+    assert!(!components.is_connected());
+
+    let label = |point: [f64; 2]| components.labels[trapezoidation.locate(point)];
+    assert_eq!(label([1., 5.]), label([9., 5.]));
+    assert_eq!(label([1., 5.]), label([5., 1.]));
+    assert_ne!(label([1., 5.]), label([5., 5.]));
+    assert_ne!(label([5., 5.]), label([13., 5.]));
+    assert!(label([1., 5.]).is_some() && label([5., 5.]).is_some() && label([13., 5.]).is_some());
+    assert_eq!(label([5., 3.5]), None);
+    assert_eq!(label([20., 5.]), None);
+
+    // The trapezoids of the frame cover its area
+    let frame = label([1., 5.]).unwrap();
+    let area = components.members(frame).map(|ti| trapezoidation.trapezoids().unwrap().nth(ti).unwrap()).map(|t| {
+        let c = t.corners().expect("Inside trapezoids are bounded");
+        (0..4).map(|i| c[i][0] * c[(i + 1) % 4][1] - c[(i + 1) % 4][0] * c[i][1]).sum::<f64>() / 2.
+    }).sum::<f64>();
+    assert!((area - 84.).abs() < 1e-9);
+
+    let single = vec![square(0., 0., 1.)];
+    assert!(single.trapezoidize().unwrap().components().unwrap().is_connected());
+}
+
+#[test]
+fn mesh_components() {
+    let polygons = vec![
+        square(0., 0., 10.),
+        square(3., 3., 4.),
+        square(4., 4., 2.),
+        vec![[12., 4.], [14., 4.], [13., 6.]],
+    ];
+    let mesh = Mesh::from_polygons(&polygons).expect("Triangulation failed");
+    let components = mesh.components();
+    assert_eq!(components.count, 3);
+    assert_eq!(components.labels.len(), mesh.triangles.len());
+    assert!(components.labels.iter().all(Option::is_some));
+    let mut sizes = (0..3).map(|c| components.members(c).count()).collect::<Vec<_>>();
+    sizes.sort();
+    assert_eq!(sizes, vec![1, 2, 8]);
+
+    // Triangles which only share a vertex are separate
+    let bowtie = Mesh::new(vec![[0f64, 0.], [1., 0.], [0., 1.], [-1., 0.], [0., -1.]], vec![[0, 1, 2], [0, 3, 4]]);
+    assert_eq!(bowtie.components().count, 2);
+    assert_eq!(Mesh::<[f64; 2]>::default().components().count, 0);
+}
+
+#[test]
+fn component_summaries() {
+    let polygons = vec![
+        square(0., 0., 10.),
+        square(3., 3., 4.),
+        square(4., 4., 2.),
+        vec![[12., 4.], [14., 4.], [13., 6.]],
+    ];
+    let trapezoidation = polygons.trapezoidize().expect("Trapezoidation failed");
+    let components = trapezoidation.components().expect("Trapezoidation failed");
+    let summaries = trapezoidation.component_summaries().expect("Trapezoidation failed");
+    assert_eq!(summaries.len(), 3);
+    let summary = |point: [f64; 2]| summaries[components.labels[trapezoidation.locate(point)].unwrap()];
+
+    let frame = summary([1., 5.]);
+    assert!((frame.area - 84.).abs() < 1e-9);
+    assert!((frame.perimeter - 56.).abs() < 1e-9);
+    assert_eq!(frame.bounds, [[0., 0.], [10., 10.]]);
+    assert_eq!(frame.holes, 1);
+
+    let island = summary([5., 5.]);
+    assert!((island.area - 4.).abs() < 1e-9);
+    assert!((island.perimeter - 8.).abs() < 1e-9);
+    assert_eq!(island.bounds, [[4., 4.], [6., 6.]]);
+    assert_eq!(island.holes, 0);
+
+    let triangle = summary([13., 5.]);
+    assert!((triangle.area - 2.).abs() < 1e-9);
+    assert!((triangle.perimeter - (2. + 2. * 5f64.sqrt())).abs() < 1e-9);
+    assert_eq!(triangle.bounds, [[12., 4.], [14., 6.]]);
+    assert_eq!(triangle.holes, 0);
+}
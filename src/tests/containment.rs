@@ -0,0 +1,99 @@
+use crate::containment::{self, ContainmentTree};
+
+use super::util::square;
+
+#[test]
+fn hierarchy() {
+    let polygons = vec![
+        square(0., 0., 10.),
+        square(1., 1., 3.),
+        vec![],
+        square(5., 5., 4.),
+        square(6., 6., 2.),
+        square(6.5, 6.5, 1.),
+        square(20., 20., 1.),
+    ];
+    let tree = ContainmentTree::new(&polygons);
+
+    // Empty polygons are skipped
+    assert_eq!(tree.len(), 6);
+    assert_eq!(tree.roots(), &[0, 5]);
+    assert_eq!(tree.children(0), &[1, 2]);
+    assert_eq!(tree.children(2), &[3]);
+    assert_eq!(tree.children(5), &[] as &[usize]);
+    assert_eq!(tree.parent(4), Some(3));
+    assert_eq!(tree.parent(5), None);
+
+    assert_eq!(tree.ancestors(4).collect::<Vec<_>>(), vec![3, 2, 0]);
+    assert_eq!((0..tree.len()).map(|ci| tree.depth(ci)).collect::<Vec<_>>(), vec![0, 1, 1, 2, 3, 0]);
+    assert_eq!((0..tree.len()).map(|ci| tree.is_hole(ci)).collect::<Vec<_>>(), vec![false, true, true, false, true, false]);
+
+    assert_eq!(tree.descendants(0).collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    assert_eq!(tree.descendants(4).count(), 0);
+    assert_eq!(tree.iter_depth_first().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn empty() {
+    let tree = ContainmentTree::new(&Vec::<Vec<[f64; 2]>>::new());
+    assert!(tree.is_empty());
+    assert_eq!(tree.roots(), &[] as &[usize]);
+    assert_eq!(tree.iter_depth_first().count(), 0);
+}
+
+#[test]
+fn paint_order() {
+    let polygons = vec![
+        square(2., 2., 1.),
+        square(0., 0., 10.),
+        square(20., 20., 1.),
+        vec![],
+        square(1., 1., 3.),
+        square(3.5, 3.5, 2.5),
+        square(15., 15., 15.),
+    ];
+    let paint = containment::paint_order(&polygons);
+
+    // Containers come first, crossing shapes keep their order, and the rest keep their order too
+    assert_eq!(paint.crossing, vec![[3, 4]]);
+    assert_eq!(paint.order, vec![1, 3, 0, 4, 5, 2]);
+    assert_eq!(paint.tree.parent(2), Some(5));
+
+    // 2 contains 0, which crosses 1, which crosses 2. The cycle is broken at the earliest contour
+    let cycle = vec![
+        square(1., 1., 2.),
+        vec![[2f64, 2.2], [12., 2.2], [12., 2.8], [2., 2.8]],
+        square(0., 0., 10.),
+    ];
+    let paint = containment::paint_order(&cycle);
+    assert_eq!(paint.crossing, vec![[0, 1], [1, 2]]);
+    let order = paint.order;
+    assert_eq!(order, vec![0, 1, 2]);
+}
+
+#[test]
+fn group_rings() {
+    let reversed = |mut contour: Vec<[f64; 2]>| {
+        contour.reverse();
+        contour
+    };
+    // Clockwise and counter-clockwise rings, in no particular order
+    let rings = vec![
+        square(6., 6., 2.),
+        reversed(square(0., 0., 10.)),
+        vec![],
+        square(20., 20., 1.),
+        square(1., 1., 3.),
+        reversed(square(6.5, 6.5, 1.)),
+        square(5., 5., 4.),
+    ];
+    let tree = ContainmentTree::new(&rings);
+    assert_eq!(tree.groups(), vec![vec![1, 3, 5], vec![0, 4], vec![2]]);
+
+    let polygons = containment::group_rings(&rings);
+    assert_eq!(polygons, vec![
+        vec![square(0., 0., 10.), reversed(square(1., 1., 3.)), reversed(square(5., 5., 4.))],
+        vec![square(6., 6., 2.), reversed(square(6.5, 6.5, 1.))],
+        vec![square(20., 20., 1.)],
+    ]);
+}
@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+
+use crate::{boolean, coverage};
+
+use super::util::assert_close;
+
+type Contours = Vec<Vec<[f64; 2]>>;
+
+fn area(contours: &Contours) -> f64 {
+    contours.iter().map(|ring| {
+        (0..ring.len()).map(|k| {
+            let (a, b) = (ring[k], ring[(k + 1) % ring.len()]);
+            a[0] * b[1] - b[0] * a[1]
+        }).sum::<f64>() / 2.
+    }).sum()
+}
+
+#[test]
+fn snap() {
+    // A 2x2 grid of parcels whose shared borders were each digitized a little differently
+    let jitter = |x: f64, y: f64, k: usize| [x + [0.002, -0.003, 0.001, 0.][k], y + [-0.001, 0.002, 0.003, -0.002][k]];
+    let parcels: Vec<Contours> = (0..4).map(|k| {
+        let (x, y) = ((k % 2) as f64, (k / 2) as f64);
+        let ring = [[x, y], [x + 0.5, y], [x + 1., y], [x + 1., y + 1.], [x, y + 1.]];
+        vec![ring.iter().map(|c| if c[0] % 2. == 0. && c[1] % 2. == 0. { *c } else { jitter(c[0], c[1], k) }).collect()]
+    }).collect();
+    let snapped = coverage::snap(&parcels, 0.01);
+    assert_eq!(snapped.len(), 4);
+
+    // Every interior edge is used once in each direction, by neighboring parcels. The middle vertices of the bottom edges
+    // are inserted into the top edges below them, so there are four edges across the middle and two up it
+    let mut edges: HashMap<[[u64; 2]; 2], usize> = HashMap::new();
+    for (fi, feature) in snapped.iter().enumerate() {
+        for ring in feature {
+            for k in 0..ring.len() {
+                let key = [ring[k], ring[(k + 1) % ring.len()]].map(|c| c.map(f64::to_bits));
+                assert!(edges.insert(key, fi).is_none());
+            }
+        }
+    }
+    let shared = edges.iter().filter(|([a, b], fi)| edges.get(&[*b, *a]).is_some_and(|fj| fj != *fi)).count();
+    assert_eq!(shared, 2 * 6);
+    assert!(edges.keys().all(|[a, b]| edges.contains_key(&[*b, *a]) || [a, b].iter().all(|c| {
+        let [x, y] = c.map(f64::from_bits);
+        x.abs() < 0.01 || y.abs() < 0.01 || (x - 2.).abs() < 0.01 || (y - 2.).abs() < 0.01
+    })));
+    // So the parcels tile their union without gaps or overlaps
+    let total: f64 = snapped.iter().map(area).sum();
+    assert_close(total, area(&boolean::union_all(&snapped)));
+
+    // Rings thinner than the tolerance vanish, but their feature is kept
+    let sliver = vec![vec![vec![[0f64, 0.], [1., 0.], [1., 0.001]]], parcels[0].clone()];
+    let snapped = coverage::snap(&sliver, 0.01);
+    assert!(snapped[0].is_empty());
+    assert_eq!(snapped[1].len(), 1);
+}
+
+#[test]
+fn clean_overlaps() {
+    let parcels = vec![
+        vec![vec![[0f64, 0.], [2., 0.], [2., 1.], [0., 1.]]],
+        vec![vec![[1.5, 0.], [3., 0.], [3., 1.], [1.5, 1.]]],
+        vec![vec![[5., 5.], [6., 5.], [6., 6.], [5., 6.]]],
+    ];
+    let cleaned = coverage::clean(&parcels, 0.01, 0.);
+    // The first parcel keeps the overlap
+    assert_close(area(&cleaned[0]), 2.);
+    assert_close(area(&cleaned[1]), 1.);
+    assert!(cleaned[1][0].iter().all(|c| c[0] >= 2.));
+    assert_close(area(&cleaned[2]), 1.);
+
+    // Borders which are shared exactly are left alone
+    let tiles = vec![parcels[0].clone(), vec![vec![[2., 0.], [3., 0.], [3., 1.], [2., 1.]]]];
+    let cleaned = coverage::clean(&tiles, 0.01, 0.);
+    assert_close(area(&cleaned[0]), 2.);
+    assert_close(area(&cleaned[1]), 1.);
+}
+
+#[test]
+fn clean_gaps() {
+    // A parcel wrapping around a unit gap, which it shares three sides with
+    let parcels = vec![
+        vec![vec![[0f64, 0.], [1., 0.], [1., 3.], [0., 3.]]],
+        vec![vec![[1., 0.], [3., 0.], [3., 3.], [1., 3.], [1., 2.], [2., 2.], [2., 1.], [1., 1.]]],
+    ];
+    let kept = coverage::clean(&parcels, 0.01, 0.5);
+    assert_close(area(&kept[1]), 5.);
+    assert_eq!(boolean::union_all(&kept).len(), 2);
+
+    let filled = coverage::clean(&parcels, 0.01, 1.5);
+    assert_close(area(&filled[0]), 3.);
+    assert_close(area(&filled[1]), 6.);
+    assert_eq!(boolean::union_all(&filled).len(), 1);
+}
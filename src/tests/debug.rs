@@ -0,0 +1,27 @@
+use crate::{formats, ListFormat, PolygonList};
+
+#[test]
+fn save_svg() {
+    let polygons = vec![
+        vec![[0f64, 0.], [10., 0.], [10., 10.], [0., 10.]],
+        vec![[3., 3.], [3., 7.], [7., 7.], [7., 3.]],
+    ];
+    let trapezoidation = polygons.trapezoidize().expect("Trapezoidation failed");
+    let mut triangles = Vec::<[usize; 2]>::new();
+    trapezoidation.triangulate(formats::IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+
+    let path = std::env::temp_dir().join(format!("triangulate-save-svg-{}.svg", std::process::id()));
+    trapezoidation.save_svg(&path).expect("Saving the SVG failed");
+    let svg = std::fs::read_to_string(&path).expect("Reading the SVG failed");
+    let _ = std::fs::remove_file(&path);
+
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.lines().all(|line| line.matches('"').count() % 2 == 0), "Unbalanced quotes");
+    // A closed path for each triangle, and an outline for each of the polygons
+    assert_eq!(svg.matches('Z').count(), triangles.len() / 3 + polygons.len());
+    if crate::debug::env::svg::show_labels() {
+        for label in ["t0 q", "s0", "s7", "n0", "n7"] {
+            assert!(svg.contains(label), "Missing label {}", label);
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use std::sync::{mpsc, Arc, Mutex};
+
+use crate::executor::Executor;
+
+#[test]
+fn priority() {
+    let executor = Executor::new(1);
+    // Hold the only worker until the other jobs are queued
+    let (release, blocked) = mpsc::channel::<()>();
+    let (start, started) = mpsc::channel::<()>();
+    let blocker = executor.submit(0, move || start.send(()).is_ok() && blocked.recv().is_ok());
+    started.recv().unwrap();
+
+    let order = Arc::new(Mutex::new(Vec::new()));
+    let handles: Vec<_> = [(1, 0), (2, 5), (3, 5), (4, -1)].into_iter().map(|(id, priority)| {
+        let order = order.clone();
+        executor.submit(priority, move || order.lock().unwrap().push(id))
+    }).collect();
+    assert_eq!(executor.queued(), 4);
+
+    release.send(()).unwrap();
+    assert_eq!(blocker.recv(), Some(true));
+    for handle in &handles {
+        assert_eq!(handle.recv(), Some(()));
+    }
+    assert_eq!(*order.lock().unwrap(), vec![2, 3, 1, 4]);
+}
+
+#[test]
+fn cancel() {
+    let executor = Executor::new(1);
+    let (release, blocked) = mpsc::channel::<()>();
+    let blocker = executor.submit(0, move || blocked.recv().is_ok());
+
+    let ran = Arc::new(Mutex::new(false));
+    let flag = ran.clone();
+    let cancelled = executor.submit(0, move || *flag.lock().unwrap() = true);
+    let kept = executor.submit_polygons(vec![vec![[0f64, 0.], [1., 0.], [0., 1.]]], 0);
+    cancelled.cancel();
+
+    release.send(()).unwrap();
+    assert_eq!(blocker.recv(), Some(true));
+    assert_eq!(cancelled.recv(), None);
+    assert_eq!(kept.recv().expect("Job was cancelled").expect("Triangulation failed").indices.len(), 3);
+    assert!(!*ran.lock().unwrap());
+}
+
+#[test]
+fn panic() {
+    let executor = Executor::new(1);
+    let failed = executor.submit(0, || -> u32 { panic!("Job failed") });
+    assert_eq!(failed.recv(), None);
+    // The worker survives the panic
+    assert_eq!(executor.submit(0, || 1).recv(), Some(1));
+}
@@ -0,0 +1,29 @@
+use crate::{fixed::{self, Polygons, Triangulator}, formats, ListFormat, PolygonList, TriangulationMethod, TriangulationOptions};
+
+use super::util;
+
+#[test]
+fn triangulate() {
+    let polygons = Polygons::from_iter([util::polygon::star(), vec![[-0.1, -0.1], [-0.1, 0.1], [0.1, 0.1], [0.1, -0.1]]]);
+    let indices = fixed::triangulate(&polygons).expect("Triangulation failed");
+
+    let mut expected = Vec::<usize>::new();
+    polygons.triangulate(formats::IndexedListFormat::new(&mut expected).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(indices.len(), expected.len());
+    assert!(indices.iter().all(|&i| (i as usize) < polygons.vertices().len()));
+
+    let options = TriangulationOptions::default().with_method(TriangulationMethod::ConstrainedDelaunay);
+    let indices = fixed::triangulate_with_options(&polygons, &options).expect("Triangulation failed");
+    assert_eq!(indices.len(), expected.len());
+}
+
+#[test]
+fn triangulator() {
+    let polygons = Polygons::from_iter([util::polygon::star()]);
+    let mut triangulator = Triangulator::new();
+    let mut indices = vec![7];
+    let added = triangulator.triangulate(&polygons, &mut indices, 10).expect("Triangulation failed");
+    assert_eq!(added, indices.len() - 1);
+    assert_eq!(indices[0], 7);
+    assert!(indices[1..].iter().all(|&i| (10..10 + polygons.vertices().len() as u32).contains(&i)));
+}
@@ -0,0 +1,29 @@
+use core::convert::Infallible;
+
+use crate::{float_format::{FloatFormat, FormatFloats}, Coords, InternalError, TriangulationError};
+
+#[test]
+fn float_format() {
+    let shortest = FloatFormat::default();
+    assert_eq!(shortest.format(0.1 + 0.2), "0.30000000000000004");
+    assert_eq!(shortest.format(-0.0), "0");
+
+    let fixed = FloatFormat { precision: Some(3), scientific: false };
+    assert_eq!(fixed.format(0.1 + 0.2), "0.300");
+    assert_eq!(fixed.format(-1e-9), "-0.000");
+
+    let scientific = FloatFormat { precision: None, scientific: true };
+    assert_eq!(scientific.format(1500.), "1.5e3");
+    let scientific = FloatFormat { precision: Some(2), scientific: true };
+    assert_eq!(scientific.format(-123456.789), "-1.23e5");
+    assert_eq!(scientific.format(f64::INFINITY), "inf");
+
+    // The precision of the format string takes priority
+    assert_eq!(format!("{:.1}", Coords::new([1f64, 2.25])), "(1.0, 2.2)");
+
+    // Errors are displayed in a given format, down to their geometry
+    let error = TriangulationError::<Infallible>::InternalError(InternalError::new("Segment crosses another").with_segment([[0.5f64, 1.], [1500., 0.]], [0, 1]));
+    let message = error.display_with(&FloatFormat { precision: Some(1), scientific: true }).to_string();
+    assert!(message.starts_with("Segment crosses another\nwhile inserting the segment (5.0e-1, 1.0e0) -> (1.5e3, 0.0e0) between vertices 0 and 1\n"));
+    assert!(format!("{:.2}", error).contains("(0.50, 1.00) -> (1500.00, 0.00)"));
+}
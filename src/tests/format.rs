@@ -1,6 +1,6 @@
 use std::{error, fmt};
 
-use crate::{FanFormat, Polygon, PolygonList, TriangulationError, formats, FanBuilder, ListFormat};
+use crate::{ChainIndex, FanFormat, MergePolicy, Polygon, PolygonElement, PolygonList, TriangleWinding, TriangulationError, TriangulationMethod, TriangulationOptions, Triangulator, Unbridged, formats, trapezoidation::TrapezoidationState, FanBuilder, ListFormat};
 
 use super::util;
 
@@ -138,6 +138,97 @@ fn index_wrapper_polygons() {
     require_u16(result[0][0][0]);
 }
 
+#[test]
+fn index_wrapper_iterators() {
+    let polygon = vec![[0f32, 0.], [1., 0.], [1., 1.], [0., 1.]];
+    let polygon = polygon.index_with::<u16>();
+
+    let indices = polygon.iter_indices();
+    assert_eq!(indices.len(), 4);
+    let reversed: Vec<_> = indices.rev().map(|element| match element {
+        PolygonElement::ContinuePolygon(i) => i,
+        PolygonElement::NewPolygon => panic!("Unexpected new polygon"),
+    }).collect();
+    assert_eq!(reversed, [3u16, 2, 1, 0]);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn index_wrapper_parallel() {
+    use rayon::prelude::*;
+
+    let polygons = vec![util::polygon::star(), vec![[0.05f32, 0.05], [0.05, 0.95], [0.95, 0.95], [0.95, 0.05]]];
+    let polygons = polygons.index_with::<usize, u16>();
+
+    let sequential: Vec<_> = polygons.iter_indices().map(|element| match element {
+        PolygonElement::ContinuePolygon(i) => Some(i),
+        PolygonElement::NewPolygon => None,
+    }).collect();
+    let indices = polygons.par_iter_indices();
+    assert_eq!(indices.len(), sequential.len());
+    let parallel: Vec<_> = indices.map(|element| match element {
+        PolygonElement::ContinuePolygon(i) => Some(i),
+        PolygonElement::NewPolygon => None,
+    }).collect();
+    assert_eq!(parallel, sequential);
+}
+
+#[test]
+fn chain() {
+    let outer = vec![vec![[0f32, 0.], [3., 0.], [3., 3.], [0., 3.]], vec![[5., 0.], [6., 0.], [6., 1.]]];
+    let holes = vec![vec![[1f32, 1.], [1., 2.], [2., 2.], [2., 1.]]];
+    let polygons = outer.chain(&holes);
+    assert_eq!(polygons.vertex_count(), 11);
+    assert_eq!(polygons.contour_count_hint(), Some(3));
+    assert_eq!(polygons.contour_len_hint(2), Some(4));
+
+    // The last polygon of the first list is closed before the second begins
+    let elements: Vec<_> = polygons.iter_indices().map(|element| match element {
+        PolygonElement::ContinuePolygon(i) => Some(i),
+        PolygonElement::NewPolygon => None,
+    }).collect();
+    let last = elements.iter().rposition(|i| matches!(i, Some(ChainIndex::First(_)))).unwrap();
+    assert!(elements[last + 1].is_none());
+
+    let mut triangles = Vec::<ChainIndex<[usize; 2], [usize; 2]>>::new();
+    polygons.triangulate(formats::IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(triangles.len(), (8 + 1) * 3);
+
+    // Chains nest
+    let triangle = vec![vec![[10f32, 0.], [11., 0.], [10., 1.]]];
+    let nested = polygons.chain(&triangle);
+    assert_eq!(*nested.get_vertex(ChainIndex::First(ChainIndex::Second([0, 2]))), [2., 2.]);
+    let mut triangles = Vec::<[f32; 2]>::new();
+    nested.triangulate(formats::DeindexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(triangles.len(), (8 + 1 + 1) * 3);
+}
+
+#[test]
+fn subset() {
+    let polygons = vec![
+        vec![[0f32, 0.], [3., 0.], [3., 3.], [0., 3.]],
+        vec![],
+        vec![[5., 0.], [6., 0.], [6., 1.]],
+        vec![[1., 1.], [1., 2.], [2., 2.], [2., 1.]],
+    ];
+    // Empty polygons are not counted, as in `SourcePolygonsFormat`
+    let subset = polygons.subset([1, 0]).expect("Position out of bounds");
+    assert_eq!(subset.vertex_count(), 7);
+    assert_eq!(subset.contour_len_hint(0), Some(3));
+
+    let mut triangles = Vec::<[usize; 2]>::new();
+    subset.triangulate(formats::IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(triangles.len(), (2 + 1) * 3);
+    assert!(!triangles.contains(&[3, 0]));
+    assert!(polygons.subset([0, 3]).is_none());
+
+    // A hole taken from another subset
+    let (outer, hole) = (polygons.subset(0..1).expect("Position out of bounds"), polygons.subset([2]).expect("Position out of bounds"));
+    let mut triangles = Vec::<[f32; 2]>::new();
+    outer.chain(&hole).triangulate(formats::DeindexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(triangles.len(), 8 * 3);
+}
+
 #[test]
 fn separate_trapezoidation() {
     let mut output = Vec::<Vec<_>>::new();
@@ -169,6 +260,65 @@ fn reverse_winding() {
 
     assert_ne!(result0, result1);
 }
+#[test]
+fn reverse_winding_chains() {
+    // Monotone polygons are triangulated from either chain, so this covers the base triangle of each
+    let polygons = vec![
+        util::polygon::star(),
+        util::polygon::star().into_iter().map(|[x, y]| [-x, y]).collect(),
+        vec![[0f32, 0.], [2., 0.], [2., 2.], [0., 2.]],
+        vec![[3., 0.], [3., 2.], [5., 2.], [5., 0.]],
+    ];
+    for polygon in &polygons {
+        let signed_areas = |fans: &[Vec<usize>]| fans.iter().flat_map(|fan| (1..fan.len() - 1).map(move |k| {
+            let [[x0, y0], [x1, y1], [x2, y2]] = [fan[0], fan[k], fan[k + 1]].map(|vi| polygon[vi]);
+            (x1 - x0) * (y2 - y0) - (x2 - x0) * (y1 - y0)
+        })).collect::<Vec<_>>();
+
+        let mut output = Vec::<Vec<usize>>::new();
+        let result = polygon.triangulate(formats::IndexedFanFormat::new(&mut output)).expect("Triangulation failed");
+        assert!(signed_areas(result).iter().all(|a| *a > 0.));
+        let mut output = Vec::<Vec<usize>>::new();
+        let result = polygon.triangulate(formats::IndexedFanFormat::new(&mut output).reverse_winding()).expect("Triangulation failed");
+        assert!(signed_areas(result).iter().all(|a| *a < 0.));
+    }
+}
+
+
+#[test]
+fn winding_option() {
+    // A counter-clockwise and a clockwise square
+    let polygons = vec![
+        vec![[0f64, 0.], [2., 0.], [2., 2.], [0., 2.]],
+        vec![[3., 0.], [3., 2.], [5., 2.], [5., 0.]],
+    ];
+    let signed_areas = |options: &TriangulationOptions| {
+        let mut output = Vec::<[[usize; 2]; 3]>::new();
+        polygons.triangulate_with_options(formats::IndexedListFormat::new(&mut output).into_fan_format(), options).expect("Triangulation failed");
+        output.iter().map(|t| {
+            let [[x0, y0], [x1, y1], [x2, y2]] = t.map(|vi| *polygons.get_vertex(vi));
+            (x1 - x0) * (y2 - y0) - (x2 - x0) * (y1 - y0)
+        }).collect::<Vec<_>>()
+    };
+
+    for method in [TriangulationMethod::Seidel, TriangulationMethod::ConstrainedDelaunay] {
+        let options = TriangulationOptions::default().with_method(method);
+        assert!(signed_areas(&options).iter().all(|a| *a > 0.));
+        let clockwise = signed_areas(&options.with_winding(TriangleWinding::Clockwise));
+        assert_eq!(clockwise.len(), 4);
+        assert!(clockwise.iter().all(|a| *a < 0.));
+        assert!(signed_areas(&options.with_winding(TriangleWinding::Counterclockwise)).iter().all(|a| *a > 0.));
+    }
+
+    // The option overrides a reversed format
+    let mut output = Vec::<Vec<[usize; 2]>>::new();
+    let options = TriangulationOptions::default().with_winding(TriangleWinding::Counterclockwise);
+    polygons.triangulate_with_options(formats::IndexedFanFormat::new(&mut output).reverse_winding(), &options).expect("Triangulation failed");
+    assert!(output.iter().all(|fan| {
+        let [[x0, y0], [x1, y1], [x2, y2]] = [fan[0], fan[1], fan[2]].map(|vi| *polygons.get_vertex(vi));
+        (x1 - x0) * (y2 - y0) - (x2 - x0) * (y1 - y0) > 0.
+    }));
+}
 
 #[test]
 fn deindexed_fan() {
@@ -208,3 +358,446 @@ fn delimited_fan() {
 
     assert!(output.into_iter().filter(|i| *i == delimiter).count() > 0);
 }
+
+#[test]
+fn index_buffer() {
+    let polygons = vec![
+        vec![[0f32, 0.], [0., 1.], [1., 1.], [1., 0.]],
+        vec![[0.25, 0.25], [0.25, 0.75], [0.75, 0.75], [0.75, 0.25]],
+    ];
+    let options = TriangulationOptions::default().with_seed(3);
+    let mut expected = Vec::<[usize; 2]>::new();
+    polygons.triangulate_with_options(formats::IndexedListFormat::new(&mut expected).into_fan_format(), &options).expect("Triangulation failed");
+    let expected = expected.iter().map(|[pi, vi]| 100 + (pi * 4 + vi) as u32).collect::<Vec<_>>();
+
+    // Indices are appended after those already in the buffer
+    let mut buffer = vec![7u32];
+    let written = polygons.triangulate_with_options(formats::IndexBufferFormat::new(&mut buffer, 100).into_fan_format(), &options).expect("Triangulation failed");
+    assert_eq!(written, 24);
+    assert_eq!(buffer[0], 7);
+    assert_eq!(&buffer[1..], &expected[..]);
+
+    let mut uninit = [std::mem::MaybeUninit::<u32>::uninit(); 30];
+    let written = polygons.triangulate_with_options(formats::IndexBufferFormat::new(&mut uninit[..], 100).into_fan_format(), &options).expect("Triangulation failed");
+    assert_eq!(written, 24);
+    // SAFETY: the first `written` indices have been written
+    assert!(uninit[..written].iter().map(|i| unsafe { i.assume_init() }).eq(expected.iter().copied()));
+
+    let mut small = [std::mem::MaybeUninit::<u32>::uninit(); 12];
+    assert!(polygons.triangulate(formats::IndexBufferFormat::new(&mut small[..], 0).into_fan_format()).is_err());
+    let mut buffer = vec![7u32];
+    assert!(polygons.triangulate(formats::IndexBufferFormat::new(&mut buffer, u32::MAX - 6).into_fan_format()).is_err());
+    assert_eq!(buffer, vec![7]);
+}
+
+#[test]
+fn callback() {
+    let polygon = util::polygon::half_frame();
+    let mut triangles = Vec::new();
+    polygon.triangulate(formats::CallbackFormat::new(|vi0, vi1, vi2| {
+        triangles.push([vi0, vi1, vi2]);
+        Ok::<_, std::convert::Infallible>(())
+    }).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(triangles.len(), 4);
+
+    // Returning an error stops the triangulation
+    let mut count = 0;
+    let result = polygon.triangulate(formats::CallbackFormat::new(|_, _, _| {
+        count += 1;
+        if count == 2 { Err(BuilderError::Build) } else { Ok(()) }
+    }).into_fan_format());
+    assert!(matches!(result, Err(TriangulationError::FanBuilder(BuilderError::Build))));
+    assert_eq!(count, 2);
+}
+
+#[test]
+fn strip() {
+    // The counter-clockwise triangles of a strip, without the degenerate ones, each starting at its smallest index
+    fn triangles(strip: &[usize], restart: usize) -> Vec<[usize; 3]> {
+        let mut triangles = Vec::new();
+        for part in strip.split(|vi| *vi == restart) {
+            for (k, w) in part.windows(3).enumerate() {
+                let mut t = if k % 2 == 0 { [w[0], w[1], w[2]] } else { [w[1], w[0], w[2]] };
+                if t[0] != t[1] && t[1] != t[2] && t[2] != t[0] {
+                    let first = (0..3).min_by_key(|k| t[*k]).unwrap_or(0);
+                    t.rotate_left(first);
+                    triangles.push(t);
+                }
+            }
+        }
+        triangles.sort();
+        triangles
+    }
+
+    let polygon = util::polygon::star();
+    let options = TriangulationOptions::default().with_seed(5);
+    let mut list = Vec::<[usize; 3]>::new();
+    polygon.triangulate_with_options(formats::IndexedListFormat::new(&mut list).into_fan_format(), &options).expect("Triangulation failed");
+    let expected = triangles(&list.iter().flat_map(|t| [t[0], t[1], t[2], usize::MAX]).collect::<Vec<_>>(), usize::MAX);
+
+    let mut restarted = vec![0, 1];
+    polygon.triangulate_with_options(formats::StripFormat::new(&mut restarted, formats::StripJoin::Restart(usize::MAX)), &options).expect("Triangulation failed");
+    assert_eq!(&restarted[..3], &[0, 1, usize::MAX]);
+    assert_eq!(triangles(&restarted[3..], usize::MAX), expected);
+    assert!(restarted.len() - 3 <= list.len() * 4);
+
+    let mut stitched = Vec::new();
+    polygon.triangulate_with_options(formats::StripFormat::new(&mut stitched, formats::StripJoin::Degenerate), &options).expect("Triangulation failed");
+    assert_eq!(triangles(&stitched, usize::MAX), expected);
+    // A second triangulation is joined to the first
+    polygon.triangulate_with_options(formats::StripFormat::new(&mut stitched, formats::StripJoin::Degenerate), &options).expect("Triangulation failed");
+    let mut twice = triangles(&stitched, usize::MAX);
+    twice.dedup();
+    assert_eq!(twice, expected);
+
+    let mut output = Vec::new();
+    let result = polygon.triangulate(formats::StripFormat::new(&mut output, formats::StripJoin::Restart(0)));
+    assert!(matches!(result, Err(TriangulationError::FanBuilder(formats::StripError::IndexMatchesRestart))));
+    assert!(output.is_empty());
+}
+
+#[test]
+fn gpu_buffers() {
+    // The vertices repeated at the ends of the bridge are not used by any triangle
+    let bridged = vec![vec![
+        [0f32, 0.], [1., 1.], [1., 2.], [2., 2.], [2., 1.], [1., 1.],
+        [0., 0.], [3., 0.], [3., 3.], [0., 3.],
+    ]];
+    let polygons = Unbridged::new(&bridged);
+    let buffers: formats::GpuBuffers<[f32; 2], u16> = polygons.triangulate(formats::GpuBuffersFormat::new().into_fan_format()).expect("Triangulation failed");
+    assert_eq!(buffers.vertices.len(), 8);
+    assert_eq!(buffers.indices.len(), 24);
+    assert!(buffers.indices.iter().all(|i| (*i as usize) < buffers.vertices.len()));
+
+    // The triangles are the same as those of the indices into the polygon list
+    let options = TriangulationOptions::default().with_seed(2);
+    let mut expected = Vec::<[usize; 2]>::new();
+    polygons.triangulate_with_options(formats::IndexedListFormat::new(&mut expected).into_fan_format(), &options).expect("Triangulation failed");
+    let buffers: formats::GpuBuffers<[f32; 2], u32> = polygons.triangulate_with_options(formats::GpuBuffersFormat::new().into_fan_format(), &options).expect("Triangulation failed");
+    assert!(buffers.indices.iter().map(|i| buffers.vertices[*i as usize]).eq(expected.iter().map(|vi| *polygons.get_vertex(*vi))));
+
+    let circle = (0..300).map(|i| {
+        let angle = i as f64 / 300. * std::f64::consts::TAU;
+        [angle.cos(), angle.sin()]
+    }).collect::<Vec<_>>();
+    let result = circle.triangulate(formats::GpuBuffersFormat::<_, [f64; 2], u8>::new().into_fan_format());
+    assert!(matches!(result, Err(TriangulationError::FanBuilder(formats::GpuBuffersError::IndexOverflow))));
+}
+
+#[test]
+fn numbered_list() {
+    let polygons = vec![
+        vec![[0f32, 0.], [0., 1.], [1., 1.], [1., 0.]],
+        vec![[0.25, 0.25], [0.25, 0.75], [0.75, 0.75], [0.75, 0.25]],
+    ];
+    let options = TriangulationOptions::default().with_seed(3);
+    let mut expected = Vec::<[usize; 2]>::new();
+    polygons.triangulate_with_options(formats::IndexedListFormat::new(&mut expected).into_fan_format(), &options).expect("Triangulation failed");
+    let expected = expected.iter().map(|[pi, vi]| (pi * 4 + vi) as u16).collect::<Vec<_>>();
+
+    let numbered: Vec<u16> = polygons.triangulate_with_options(formats::NumberedListFormat::<_, u16, _>::new(Vec::new()).into_fan_format(), &options).expect("Triangulation failed");
+    assert_eq!(numbered, expected);
+
+    let circle = (0..300).map(|i| {
+        let angle = i as f64 / 300. * std::f64::consts::TAU;
+        [angle.cos(), angle.sin()]
+    }).collect::<Vec<_>>();
+    let mut triangles = vec![[1u8, 2, 3]];
+    let result = circle.triangulate(formats::NumberedListFormat::<_, u8, _>::new(&mut triangles).into_fan_format());
+    assert!(matches!(result, Err(TriangulationError::FanBuilder(formats::NumberedListError::IndexOverflow { vertex_count: 300, .. }))));
+    assert_eq!(triangles, vec![[1, 2, 3]]);
+    let triangles: Vec<[u16; 3]> = circle.triangulate(formats::NumberedListFormat::<_, u16, _>::new(Vec::new()).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(triangles.len(), 298);
+}
+
+#[test]
+fn source_polygons() {
+    // A frame with an island in its hole, and a separate triangle
+    let polygons = vec![
+        vec![[0f64, 0.], [10., 0.], [10., 10.], [0., 10.]],
+        vec![[3., 3.], [3., 7.], [7., 7.], [7., 3.]],
+        vec![],
+        vec![[4., 4.], [6., 4.], [6., 6.], [4., 6.]],
+        vec![[12., 4.], [14., 4.], [13., 6.]],
+    ];
+    let mut sources = vec![99];
+    let triangles: Vec<[[usize; 2]; 3]> = polygons.triangulate(formats::IndexedListFormat::new(Vec::new()).with_source_polygons(&mut sources).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(sources[0], 99);
+    assert_eq!(sources.len(), triangles.len() + 1);
+    // Polygons are numbered skipping the empty one
+    let sourced = triangles.iter().zip(&sources[1..]).map(|(t, source)| (t.map(|[pi, _]| pi), *source)).collect::<Vec<_>>();
+    assert!(sourced.iter().all(|(t, source)| match source {
+        0 => t.iter().all(|pi| *pi < 2),
+        2 => t.iter().all(|pi| *pi == 3),
+        3 => t.iter().all(|pi| *pi == 4),
+        _ => false,
+    }));
+    assert_eq!(sourced.iter().filter(|(_, source)| *source == 0).count(), 8);
+
+    // The sources are removed again on failure
+    let mut count = 0;
+    let result = polygons.triangulate(formats::CallbackFormat::new(|_, _, _| {
+        count += 1;
+        if count == 3 { Err(BuilderError::Build) } else { Ok(()) }
+    }).with_source_polygons(&mut sources).into_fan_format());
+    assert!(result.is_err());
+    assert_eq!(sources.len(), triangles.len() + 1);
+}
+
+#[test]
+fn monotones() {
+    let polygon = util::polygon::star();
+    let area = |indices: &[usize]| (0..indices.len()).map(|i| {
+        let ([x0, y0], [x1, y1]) = (polygon[indices[i]], polygon[indices[(i + 1) % indices.len()]]);
+        x0 * y1 - x1 * y0
+    }).sum::<f32>() / 2.;
+
+    let mut output = vec![vec![usize::MAX]];
+    let result = polygon.monotones(formats::IndexedMonotoneFormat::new(&mut output)).expect("Decomposition failed");
+    let monotones = &result[1..];
+    // As many triangles as triangulation
+    assert_eq!(monotones.iter().map(|m| m.len() - 2).sum::<usize>(), 6);
+
+    let star = (0..polygon.len()).collect::<Vec<_>>();
+    let total = monotones.iter().map(|m| area(m)).sum::<f32>();
+    assert!((total - area(&star)).abs() < 1e-4);
+    for m in monotones {
+        assert!(area(m) > 0.);
+        // From the highest vertex, down one side and up the other
+        let y = m.iter().map(|&vi| polygon[vi][1]).collect::<Vec<_>>();
+        let lowest = (0..y.len()).min_by(|&i, &j| y[i].total_cmp(&y[j])).unwrap();
+        assert!(y[..=lowest].windows(2).all(|w| w[0] >= w[1]));
+        assert!(y[lowest..].windows(2).all(|w| w[0] <= w[1]));
+    }
+}
+
+#[test]
+fn merge_policy() {
+    let octagon = vec![vec![[2f64, 0.], [4., 0.], [6., 2.], [6., 4.], [4., 6.], [2., 6.], [0., 4.], [0., 2.]]];
+    // A zigzag outline with a diamond hole, so monotones split at the top and bottom of the hole
+    let comb = vec![
+        vec![[0f64, 0.], [4., 1.], [8., 0.], [7., 3.], [9., 6.], [7., 9.], [8., 12.], [4., 11.], [0., 12.], [1., 9.], [-1., 6.], [1., 3.]],
+        vec![[4., 4.], [3., 6.], [4., 8.], [5., 6.]],
+    ];
+
+    for polygons in [octagon, comb] {
+        let area = |triangles: &[[[usize; 2]; 3]]| triangles.iter().map(|t| {
+            let [[x0, y0], [x1, y1], [x2, y2]] = t.map(|vi| *polygons.get_vertex(vi));
+            ((x1 - x0) * (y2 - y0) - (x2 - x0) * (y1 - y0)) / 2.
+        }).collect::<Vec<_>>();
+        let triangulate = |merge: MergePolicy, winding: TriangleWinding| {
+            let mut fans = Vec::<Vec<[usize; 2]>>::new();
+            let options = TriangulationOptions::default().with_merge(merge).with_winding(winding);
+            polygons.triangulate_with_options(formats::IndexedFanFormat::new(&mut fans), &options).expect("Triangulation failed");
+            let triangles = fans.iter().flat_map(|fan| (2..fan.len()).map(|i| [fan[0], fan[i - 1], fan[i]])).collect::<Vec<_>>();
+            area(&triangles)
+        };
+
+        let split_areas = triangulate(MergePolicy::Unimonotone, TriangleWinding::Counterclockwise);
+        let merged_areas = triangulate(MergePolicy::Monotone, TriangleWinding::Counterclockwise);
+        assert_eq!(merged_areas.len(), split_areas.len());
+        assert!(merged_areas.iter().all(|a| *a > 0.));
+        assert!((merged_areas.iter().sum::<f64>() - split_areas.iter().sum::<f64>()).abs() < 1e-9);
+        let clockwise_areas = triangulate(MergePolicy::Monotone, TriangleWinding::Clockwise);
+        assert!(clockwise_areas.iter().all(|a| *a < 0.));
+
+        let monotones = |merge: MergePolicy| {
+            let trapezoidation = polygons.trapezoidize().expect("Trapezoidation failed").with_merge_policy(merge);
+            let mut monotones = Vec::<Vec<[usize; 2]>>::new();
+            trapezoidation.monotones(formats::IndexedMonotoneFormat::new(&mut monotones)).expect("Decomposition failed");
+            monotones
+        };
+        let merged = monotones(MergePolicy::Monotone);
+        assert!(merged.len() < monotones(MergePolicy::Unimonotone).len());
+        assert_eq!(merged.iter().map(|m| m.len() - 2).sum::<usize>(), split_areas.len());
+        for m in merged {
+            // Counterclockwise from the highest vertex, down one side and up the other
+            let positions = m.iter().map(|&vi| *polygons.get_vertex(vi)).collect::<Vec<_>>();
+            let area = (0..positions.len()).map(|i| {
+                let ([x0, y0], [x1, y1]) = (positions[i], positions[(i + 1) % positions.len()]);
+                x0 * y1 - x1 * y0
+            }).sum::<f64>();
+            assert!(area > 0.);
+            let lowest = (0..positions.len()).min_by(|&i, &j| positions[i][1].total_cmp(&positions[j][1])).unwrap();
+            assert!(positions[..=lowest].windows(2).all(|w| w[0][1] >= w[1][1]));
+            assert!(positions[lowest..].windows(2).all(|w| w[0][1] <= w[1][1]));
+        }
+    }
+}
+
+#[test]
+fn convex_parts() {
+    let signed_area = |polygon: &[[f32; 2]], indices: &[usize]| (0..indices.len()).map(|i| {
+        let ([x0, y0], [x1, y1]) = (polygon[indices[i]], polygon[indices[(i + 1) % indices.len()]]);
+        x0 * y1 - x1 * y0
+    }).sum::<f32>() / 2.;
+    let is_convex = |polygon: &[[f32; 2]], indices: &[usize]| (0..indices.len()).all(|i| {
+        let [a, b, c] = [0, 1, 2].map(|k| polygon[indices[(i + k) % indices.len()]]);
+        (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0]) >= 0.
+    });
+
+    // A convex polygon is a single part
+    let square = util::polygon::square();
+    let mut output = Vec::<Vec<usize>>::new();
+    let parts = square.triangulate(formats::ConvexPartsFormat::new(&mut output).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(parts.len(), 1);
+    assert_eq!(parts[0].len(), 4);
+
+    let star = util::polygon::star();
+    let mut output = vec![vec![usize::MAX]];
+    let result = star.triangulate(formats::ConvexPartsFormat::new(&mut output).into_fan_format()).expect("Triangulation failed");
+    let parts = &result[1..];
+    assert!(parts.len() < 6);
+    assert!(parts.iter().all(|part| is_convex(&star, part)));
+    let total = parts.iter().map(|part| signed_area(&star, part)).sum::<f32>();
+    let all = (0..star.len()).collect::<Vec<_>>();
+    assert!((total - signed_area(&star, &all)).abs() < 1e-4);
+}
+
+#[test]
+fn delaunay() {
+    // An ellipse, which triangulation tends to fill with slivers
+    let polygon = (0..40).map(|i| {
+        let angle = i as f64 / 40. * std::f64::consts::TAU;
+        [4. * angle.cos(), angle.sin()]
+    }).collect::<Vec<_>>();
+
+    let mut output = Vec::<[usize; 3]>::new();
+    let triangles = polygon.triangulate(formats::IndexedListFormat::new(&mut output).delaunay().into_fan_format()).expect("Triangulation failed");
+    assert_eq!(triangles.len(), 38);
+
+    // No vertex is inside the circumcircle of the triangle across an edge
+    for t0 in triangles.iter() {
+        for t1 in triangles.iter() {
+            for k in 0..3 {
+                let (a, b) = (t0[k], t0[(k + 1) % 3]);
+                let Some(j) = (0..3).find(|&j| t1[j] == b && t1[(j + 1) % 3] == a) else { continue };
+                let [pa, pb, pc] = [a, b, t0[(k + 2) % 3]].map(|vi| polygon[vi]);
+                let d = polygon[t1[(j + 2) % 3]];
+                let [pa, pb, pc] = [pa, pb, pc].map(|p| [p[0] - d[0], p[1] - d[1]]);
+                let [la, lb, lc] = [pa, pb, pc].map(|p| p[0] * p[0] + p[1] * p[1]);
+                let in_circle = pa[0] * (pb[1] * lc - lb * pc[1]) - pa[1] * (pb[0] * lc - lb * pc[0]) + la * (pb[0] * pc[1] - pb[1] * pc[0]);
+                assert!(in_circle <= 1e-9);
+            }
+        }
+    }
+}
+
+#[test]
+fn numbered_triangles_repeated_position() {
+    // Indices 0 and 3 share a position, and their corners are interleaved when sorted by position
+    let polygons = vec![vec![[0f64, 0.], [1., 0.], [1., 1.], [0., 0.], [0., 1.]]];
+    let triangles = [[0, 1, 2], [3, 2, 4], [0, 2, 4]].map(|t| t.map(|vi| [0, vi])).to_vec();
+    let numbered = formats::NumberedTriangles::new(&polygons, triangles);
+    assert_eq!(numbered.indices.len(), 5);
+    assert_eq!(numbered.triangles[0][0], numbered.triangles[2][0]);
+    assert_ne!(numbered.triangles[0][0], numbered.triangles[1][0]);
+    assert_eq!(numbered.triangles[1][1], numbered.triangles[2][1]);
+}
+
+#[test]
+fn constrained_delaunay() {
+    let polygon = (0..40).map(|i| {
+        let angle = i as f64 / 40. * std::f64::consts::TAU;
+        [4. * angle.cos(), angle.sin()]
+    }).collect::<Vec<_>>();
+
+    let options = TriangulationOptions::default().with_method(TriangulationMethod::ConstrainedDelaunay);
+    let mut output = Vec::<[usize; 3]>::new();
+    let triangles = polygon.triangulate_with_options(formats::IndexedListFormat::new(&mut output).into_fan_format(), &options).expect("Triangulation failed");
+    assert_eq!(triangles.len(), 38);
+
+    // Every triangle is counterclockwise, and no vertex is inside the circumcircle of the triangle across an edge
+    for t0 in triangles.iter() {
+        let [a, b, c] = t0.map(|vi| polygon[vi]);
+        assert!((b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0]) > 0.);
+        for t1 in triangles.iter() {
+            for k in 0..3 {
+                let (a, b) = (t0[k], t0[(k + 1) % 3]);
+                let Some(j) = (0..3).find(|&j| t1[j] == b && t1[(j + 1) % 3] == a) else { continue };
+                let [pa, pb, pc] = [a, b, t0[(k + 2) % 3]].map(|vi| polygon[vi]);
+                let d = polygon[t1[(j + 2) % 3]];
+                let [pa, pb, pc] = [pa, pb, pc].map(|p| [p[0] - d[0], p[1] - d[1]]);
+                let [la, lb, lc] = [pa, pb, pc].map(|p| p[0] * p[0] + p[1] * p[1]);
+                let in_circle = pa[0] * (pb[1] * lc - lb * pc[1]) - pa[1] * (pb[0] * lc - lb * pc[0]) + la * (pb[0] * pc[1] - pb[1] * pc[0]);
+                assert!(in_circle <= 1e-9);
+            }
+        }
+    }
+
+    // Seidel's method is the same as plain triangulation
+    let mut seidel = Vec::<[usize; 3]>::new();
+    let options = TriangulationOptions::default();
+    polygon.triangulate_with_options(formats::IndexedListFormat::new(&mut seidel).into_fan_format(), &options).expect("Triangulation failed");
+    assert_eq!(seidel.len(), 38);
+}
+
+#[test]
+fn seeded() {
+    // A spiky polygon with a hole, which has many triangulations
+    let polygons = vec![
+        (0..200).map(|i| {
+            let angle = i as f64 / 200. * std::f64::consts::TAU;
+            let radius = if i % 2 == 0 { 10. } else { 6. + (i % 7) as f64 / 4. };
+            [radius * angle.cos(), radius * angle.sin()]
+        }).collect::<Vec<_>>(),
+        vec![[-1., -1.], [-1., 1.], [1., 1.], [1., -1.]],
+    ];
+    let triangulate = |options: &TriangulationOptions| {
+        let mut output = Vec::<[[usize; 2]; 3]>::new();
+        polygons.triangulate_with_options(formats::IndexedListFormat::new(&mut output).into_fan_format(), options).expect("Triangulation failed");
+        output
+    };
+
+    // The same seed always gives the same triangles in the same order
+    for method in [TriangulationMethod::Seidel, TriangulationMethod::ConstrainedDelaunay] {
+        let options = TriangulationOptions::default().with_method(method).with_seed(7);
+        let expected = triangulate(&options);
+        assert_eq!(expected.len(), 204 - 2 + 2);
+        for _ in 0..4 {
+            assert_eq!(triangulate(&options), expected);
+        }
+    }
+
+    // The decomposition into trapezoids is the same for any insertion order, but they are numbered in the order they
+    // were created, which only a seed makes reproducible
+    let trapezoids = |seed: u64| {
+        let trapezoidation = TrapezoidationState::<_>::new(&polygons).with_seed(Some(seed)).build().expect("Trapezoidation failed");
+        trapezoidation.trapezoids().expect("Trapezoidation failed").collect::<Vec<_>>()
+    };
+    assert_eq!(trapezoids(7), trapezoids(7));
+    assert_ne!(trapezoids(7), trapezoids(8));
+}
+
+#[test]
+fn triangulator() {
+    // Shapes of different sizes, so that each reuses storage left over from a larger or smaller one
+    let spiky = |n: usize| vec![(0..n).map(|i| {
+        let angle = i as f64 / n as f64 * std::f64::consts::TAU;
+        let radius = if i % 2 == 0 { 10. } else { 6. + (i % 7) as f64 / 4. };
+        [radius * angle.cos(), radius * angle.sin()]
+    }).collect::<Vec<_>>(), vec![[-1., -1.], [-1., 1.], [1., 1.], [1., -1.]]];
+    let inputs = [spiky(400), spiky(10), spiky(1000), spiky(6), spiky(400)];
+
+    // The triangles are the same as those of a fresh trapezoidation, up to their order and first vertex
+    let normalized = |mut triangles: Vec<[[usize; 2]; 3]>| {
+        for t in triangles.iter_mut() {
+            let first = (0..3).min_by_key(|k| t[*k]).unwrap_or(0);
+            t.rotate_left(first);
+        }
+        triangles.sort();
+        triangles
+    };
+    let mut triangulator = Triangulator::new();
+    for polygons in &inputs {
+        let mut expected = Vec::<[[usize; 2]; 3]>::new();
+        polygons.triangulate(formats::IndexedListFormat::new(&mut expected).into_fan_format()).expect("Triangulation failed");
+        let mut output = Vec::<[[usize; 2]; 3]>::new();
+        triangulator.triangulate(polygons, formats::IndexedListFormat::new(&mut output).into_fan_format()).expect("Triangulation failed");
+        assert_eq!(normalized(output), normalized(expected));
+
+        // A failed triangulation leaves the triangulator usable
+        let mut failed = false;
+        assert!(triangulator.triangulate(polygons, ErrorFormat::new(BuilderError::NewFan, &mut failed)).is_err());
+    }
+}
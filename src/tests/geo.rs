@@ -0,0 +1,45 @@
+use geo_types::{coord, Coord, LineString, MultiPolygon, Polygon};
+
+use crate::{formats, FromCoords, GeoMultiPolygon, GeoPolygon, ListFormat, PolygonElement, PolygonList};
+
+use super::util::square;
+
+fn area(triangles: &[Coord<f64>]) -> f64 {
+    triangles.chunks(3).map(|t| ((t[1].x - t[0].x) * (t[2].y - t[0].y) - (t[2].x - t[0].x) * (t[1].y - t[0].y)).abs() / 2.).sum()
+}
+
+#[test]
+fn polygon() {
+    let polygon = Polygon::new(LineString::from(square(0., 0., 4.)), vec![LineString::from(square(1., 1., 1.)), LineString::from(square(2.5, 2.5, 1.))]);
+    let polygons = GeoPolygon::new(&polygon);
+    // The closing coordinates are not vertices
+    assert_eq!(polygons.vertex_count(), 12);
+    assert_eq!(polygons.contour_len_hint(2), Some(4));
+    assert_eq!(polygons.iter_indices().filter(|element| matches!(element, PolygonElement::NewPolygon)).count(), 3);
+
+    let mut triangles = Vec::<Coord<f64>>::new();
+    polygons.triangulate(formats::DeindexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(triangles.len(), (12 + 2 * 2 - 2) * 3);
+    assert_eq!(area(&triangles), 16. - 2.);
+}
+
+#[test]
+fn multi_polygon() {
+    let polygons = MultiPolygon::new(vec![
+        Polygon::new(LineString::from(square(0., 0., 3.)), vec![LineString::from(square(1., 1., 1.))]),
+        Polygon::new(LineString::from(square(5., 0., 1.)), vec![]),
+    ]);
+    let polygons = GeoMultiPolygon::from(&polygons);
+    assert_eq!(polygons.vertex_count(), 12);
+    assert_eq!(*polygons.get_vertex([1, 0, 2]), coord! { x: 6., y: 1. });
+
+    let mut triangles = Vec::<Coord<f64>>::new();
+    polygons.triangulate(formats::DeindexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(triangles.len(), (8 + 2 - 2 + 4 - 2) * 3);
+    assert_eq!(area(&triangles), 9. - 1. + 1.);
+}
+
+#[test]
+fn coord() {
+    assert_eq!(Coord::from_coords([1f32, 2.]), coord! { x: 1f32, y: 2. });
+}
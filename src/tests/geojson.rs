@@ -0,0 +1,77 @@
+use crate::{formats, geojson::{self, GeoJsonError}, ListFormat, PolygonList};
+
+fn triangle_count(polygons: &crate::FlatPolygonList<[f64; 2]>) -> usize {
+    let mut triangles = Vec::<usize>::new();
+    polygons.triangulate(formats::IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+    triangles.len() / 3
+}
+
+#[test]
+fn rewinds_rings() {
+    // A clockwise exterior with a counter-clockwise hole, the opposite of the specification
+    let polygons = geojson::read(r#"{
+        "type": "Polygon",
+        "coordinates": [
+            [[0, 0], [0, 4], [4, 4], [4, 0], [0, 0]],
+            [[1, 1], [3, 1], [3, 3], [1, 3], [1, 1]]
+        ]
+    }"#).expect("Loading failed");
+    assert_eq!(polygons.len(), 1);
+    assert_eq!(polygons[0].polygon(0), [[4., 0.], [4., 4.], [0., 4.], [0., 0.]]);
+    assert_eq!(polygons[0].polygon(1), [[1., 3.], [3., 3.], [3., 1.], [1., 1.]]);
+    assert_eq!(triangle_count(&polygons[0]), 8);
+}
+
+#[test]
+fn multi_polygon() {
+    // Altitudes are dropped
+    let polygons = geojson::read(r#"{
+        "type": "MultiPolygon",
+        "coordinates": [
+            [[[0, 0, 10], [1, 0, 10], [0, 1, 10], [0, 0, 10]]],
+            [[[5, 0], [6, 0], [6, 1], [5, 1], [5, 0]]]
+        ]
+    }"#).expect("Loading failed");
+    assert_eq!(polygons.len(), 1);
+    assert_eq!(polygons[0].polygon_count(), 2);
+    assert_eq!(polygons[0].vertices().len(), 7);
+    assert_eq!(triangle_count(&polygons[0]), 1 + 2);
+}
+
+#[test]
+fn feature_collection() {
+    let polygons = geojson::read(r#"{
+        "type": "FeatureCollection",
+        "features": [
+            { "type": "Feature", "properties": null, "geometry": { "type": "Point", "coordinates": [0, 0] } },
+            { "type": "Feature", "properties": null, "geometry": null },
+            { "type": "Feature", "properties": null, "geometry": {
+                "type": "Polygon", "coordinates": [[[0, 0], [1, 0], [0, 1], [0, 0]]]
+            } },
+            { "type": "Feature", "properties": null, "geometry": {
+                "type": "GeometryCollection", "geometries": [
+                    { "type": "LineString", "coordinates": [[0, 0], [1, 1]] },
+                    { "type": "Polygon", "coordinates": [[[0, 0], [1, 0], [1, 1], [0, 1], [0, 0]]] }
+                ]
+            } }
+        ]
+    }"#).expect("Loading failed");
+    assert_eq!(polygons.len(), 2);
+    assert_eq!(polygons[0].vertices().len(), 3);
+    assert_eq!(polygons[1].vertices().len(), 4);
+}
+
+#[test]
+fn errors() {
+    assert!(matches!(geojson::read("{"), Err(GeoJsonError::Parse(_))));
+    assert!(matches!(
+        geojson::read(r#"{ "type": "MultiPolygon", "coordinates": [
+            [[[0, 0], [1, 0], [0, 1], [0, 0]]],
+            [[[0, 0], [1, 0], [0, 1], [0, 0]], [[0, 0], [1, 0], [0, 0]]]
+        ] }"#),
+        Err(GeoJsonError::InvalidRing { polygon: 1, ring: 1 })
+    ));
+
+    let point = "{ \"type\": \"Point\", \"coordinates\": [0, 0] }".parse::<::geojson::Geometry>().unwrap();
+    assert!(matches!(geojson::polygons(&point), Err(GeoJsonError::NotAPolygon("Point"))));
+}
@@ -0,0 +1,39 @@
+use crate::{hull, mesh::Mesh};
+
+use super::util;
+
+#[test]
+fn convex_hull() {
+    let points = [[0f64, 0.], [1., 1.], [2., 0.], [2., 2.], [1., 0.], [0., 2.], [1., 2.], [0.5, 1.5], [2., 2.]];
+    assert_eq!(hull::convex_hull(&points), vec![[0., 0.], [2., 0.], [2., 2.], [0., 2.]]);
+
+    assert_eq!(hull::convex_hull(&[[0f64, 0.], [1., 1.], [2., 2.]]), vec![[0., 0.], [2., 2.]]);
+    assert_eq!(hull::convex_hull(&[[1f64, 1.]]), vec![[1., 1.]]);
+    assert!(hull::convex_hull::<[f64; 2]>(&[]).is_empty());
+
+    for polygon in util::polygon::all() {
+        let hull = hull::convex_hull(&polygon);
+        assert!(hull.len() >= 3);
+        for i in 0..hull.len() {
+            let (a, b, c) = (hull[i], hull[(i + 1) % hull.len()], hull[(i + 2) % hull.len()]);
+            assert!((b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0]) > 0.);
+        }
+    }
+}
+
+#[test]
+fn pockets() {
+    // A 'U' shape with an island in its pocket and a hole in its base
+    let polygons = vec![
+        vec![[0f64, 0.], [6., 0.], [6., 6.], [4., 6.], [4., 2.], [2., 2.], [2., 6.], [0., 6.]],
+        vec![[2.5, 4.], [3.5, 4.], [3.5, 5.], [2.5, 5.]],
+        vec![[1., 0.5], [1., 1.5], [5., 1.5], [5., 0.5]],
+    ];
+    let pockets = hull::pockets(&polygons).expect("Triangulation failed");
+    let area: f64 = pockets.iter_triangles().map(Mesh::<[f64; 2]>::area_of).sum();
+    assert_eq!(area, 8. - 1. + 4.);
+
+    // Convex shapes have no pockets
+    let square = vec![vec![[0f64, 0.], [1., 0.], [1., 1.], [0., 1.]]];
+    assert!(hull::pockets(&square).expect("Triangulation failed").triangles.is_empty());
+}
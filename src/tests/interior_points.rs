@@ -0,0 +1,51 @@
+use crate::{formats, InteriorPointIndex, ListFormat, PolygonList, TriangulationError};
+
+fn triangle_area(t: &[[f64; 2]]) -> f64 {
+    ((t[1][0] - t[0][0]) * (t[2][1] - t[0][1]) - (t[2][0] - t[0][0]) * (t[1][1] - t[0][1])) / 2.
+}
+
+#[test]
+fn points_become_vertices() {
+    let polygons = vec![vec![[0f64, 0.], [4., 0.], [4., 4.], [0., 4.]]];
+    let points = [[1f64, 1.], [3., 2.], [2., 3.5], [2., 2.]];
+    let with_points = polygons.with_interior_points(&points);
+
+    let mut indices = Vec::<InteriorPointIndex<[usize; 2]>>::new();
+    with_points.triangulate(formats::IndexedListFormat::new(&mut indices).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(indices.len(), 3 * (polygons[0].len() - 2 + 2 * points.len()));
+    for i in 0..points.len() {
+        assert!(indices.contains(&InteriorPointIndex::Interior(i)));
+    }
+
+    let mut vertices = Vec::<[f64; 2]>::new();
+    with_points.triangulate(formats::DeindexedListFormat::new(&mut vertices).into_fan_format()).expect("Triangulation failed");
+    for t in vertices.chunks_exact(3) {
+        assert!(triangle_area(t) > 0.);
+    }
+    assert_eq!(vertices.chunks_exact(3).map(triangle_area).sum::<f64>(), 16.);
+}
+
+#[test]
+fn point_on_diagonal() {
+    // Any triangulation of a square uses one of its diagonals, so the center lies on an interior edge
+    let polygons = vec![vec![[0f64, 0.], [2., 0.], [2., 2.], [0., 2.]]];
+    let points = [[1f64, 1.]];
+    let mut indices = Vec::<InteriorPointIndex<[usize; 2]>>::new();
+    polygons.with_interior_points(&points).triangulate(formats::IndexedListFormat::new(&mut indices).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(indices.len(), 3 * 4);
+}
+
+#[test]
+fn invalid_points() {
+    let polygons = vec![
+        vec![[0f64, 0.], [4., 0.], [4., 4.], [0., 4.]],
+        vec![[1., 1.], [1., 3.], [3., 3.], [3., 1.]],
+    ];
+    // Outside, inside the hole, on the boundary, and on a vertex
+    for (i, point) in [[5f64, 5.], [2., 2.], [4., 2.], [1., 1.]].into_iter().enumerate() {
+        let points = [[0.5, 0.5], point];
+        let mut indices = Vec::<InteriorPointIndex<[usize; 2]>>::new();
+        let result = polygons.with_interior_points(&points).triangulate(formats::IndexedListFormat::new(&mut indices).into_fan_format());
+        assert!(matches!(result, Err(TriangulationError::InvalidInteriorPoint(1))), "case {}", i);
+    }
+}
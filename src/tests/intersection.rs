@@ -0,0 +1,59 @@
+use crate::intersection;
+
+use super::util;
+
+fn polygon_edges(polygon: &[[f32; 2]]) -> Vec<[[f32; 2]; 2]> {
+    (0..polygon.len()).map(|i| [polygon[i], polygon[(i + 1) % polygon.len()]]).collect()
+}
+
+#[test]
+fn simple_polygons() {
+    // Consecutive edges only share endpoints, which are not reported
+    for polygon in util::polygon::all() {
+        assert!(intersection::find_intersections(&polygon_edges(&polygon)).is_empty());
+    }
+}
+
+#[test]
+fn bowtie() {
+    let polygon = vec![[0f32, 0.], [2., 2.], [2., 0.], [0., 2.]];
+    let intersections = intersection::find_intersections(&polygon_edges(&polygon));
+    assert_eq!(intersections.len(), 1);
+    assert_eq!(intersections[0].point, [1., 1.]);
+    assert_eq!(intersections[0].segments(), vec![0, 2]);
+    assert_eq!(intersection::split_segments(&polygon_edges(&polygon)).len(), 6);
+}
+
+#[test]
+fn t_junction() {
+    let segments = [[[0f32, 0.], [2., 0.]], [[1., 0.], [1., 1.]]];
+    let intersections = intersection::find_intersections(&segments);
+    assert_eq!(intersections.len(), 1);
+    assert_eq!(intersections[0].point, [1., 0.]);
+    assert_eq!(intersections[0].interior, vec![0]);
+    assert_eq!(intersections[0].endpoint, vec![1]);
+}
+
+#[test]
+fn collinear_overlap() {
+    let segments = [[[0f32, 0.], [2., 2.]], [[1., 1.], [3., 3.]]];
+    let intersections = intersection::find_intersections(&segments);
+    let points: Vec<_> = intersections.iter().map(|i| i.point).collect();
+    assert_eq!(points, vec![[1., 1.], [2., 2.]]);
+
+    let pieces = intersection::split_segments(&segments);
+    assert_eq!(pieces.len(), 4);
+}
+
+#[test]
+fn many_crossings() {
+    // A grid of horizontal and vertical lines, each pair crossing once
+    let n = 8;
+    let mut segments = Vec::new();
+    for i in 0..n {
+        let t = i as f64 + 0.5;
+        segments.push([[t, 0.], [t, n as f64]]);
+        segments.push([[0., t], [n as f64, t]]);
+    }
+    assert_eq!(intersection::find_intersections(&segments).len(), n * n);
+}
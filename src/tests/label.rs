@@ -0,0 +1,76 @@
+use crate::{label, shapes, PolygonList};
+
+#[test]
+fn pole_of_inaccessibility() {
+    // The largest circle in a rectangle touches its long sides
+    let rectangle = shapes::rect([0f64, 0.], [10., 4.]);
+    let pole = label::pole_of_inaccessibility(&rectangle, 0.001).expect("Trapezoidation failed").expect("Polygons have no area");
+    assert!((pole.distance - 2.).abs() <= 0.001);
+    assert!((pole.position[1] - 2.).abs() <= 0.01 && pole.position[0] > 2. - 0.01 && pole.position[0] < 8. + 0.01);
+
+    // The centroid of a ring is in its hole, so the pole is in the band around it
+    let ring = shapes::ring([5f64, 5.], 3., 5., 128);
+    let pole = label::pole_of_inaccessibility(&ring, 0.001).expect("Trapezoidation failed").expect("Polygons have no area");
+    let radius = (pole.position[0] - 5.).hypot(pole.position[1] - 5.);
+    assert!((pole.distance - 1.).abs() <= 0.01);
+    assert!((radius - 4.).abs() <= 0.05);
+
+    // A thin bar far from a big square: the pole is in the square, however the precision is chosen
+    let polygons = vec![
+        vec![[0f64, 0.], [100., 0.], [100., 1.], [0., 1.]],
+        vec![[200., 0.], [260., 0.], [260., 60.], [200., 60.]],
+    ];
+    for precision in [10., 1., 0.] {
+        let pole = label::pole_of_inaccessibility(&polygons, precision).expect("Trapezoidation failed").expect("Polygons have no area");
+        assert!(pole.distance >= 30. - precision - 1e-9);
+        assert!(pole.position[0] > 200.);
+    }
+}
+
+// Whether `rectangle` lies inside `polygons`, checked at a grid of points
+fn inside(polygons: &Vec<Vec<[f64; 2]>>, [min, max]: [[f64; 2]; 2]) -> bool {
+    let trapezoidation = polygons.trapezoidize().expect("Trapezoidation failed");
+    (1..20).flat_map(|i| (1..20).map(move |j| [
+        min[0] + (max[0] - min[0]) * i as f64 / 20.,
+        min[1] + (max[1] - min[1]) * j as f64 / 20.,
+    ])).all(|p| trapezoidation.contains_point(p).expect("Point location failed"))
+}
+
+#[test]
+fn largest_inscribed_rectangle() {
+    // An axis-aligned rectangle is found exactly, whatever the resolution
+    let rectangle = shapes::rect([1f64, 2.], [11., 6.]);
+    for resolution in [10, 37, 100] {
+        let found = label::largest_inscribed_rectangle(&rectangle, resolution).expect("Trapezoidation failed").expect("No cell inside");
+        for k in 0..2 {
+            assert!((found[0][k] - [1., 2.][k]).abs() <= 1e-6 && (found[1][k] - [11., 6.][k]).abs() <= 1e-6, "{found:?}");
+        }
+    }
+
+    // The L shape holds a 10 by 2 rectangle along either arm
+    let l = vec![vec![[0f64, 0.], [10., 0.], [10., 2.], [2., 2.], [2., 10.], [0., 10.]]];
+    let found = label::largest_inscribed_rectangle(&l, 50).expect("Trapezoidation failed").expect("No cell inside");
+    assert!(((found[1][0] - found[0][0]) * (found[1][1] - found[0][1]) - 20.).abs() <= 1e-6, "{found:?}");
+    assert!(inside(&l, found));
+
+    // In a disk the largest rectangle is a square with the diameter as its diagonal; a hole in the middle pushes it aside
+    let disk = shapes::circle([0f64, 0.], 10., 256);
+    let found = label::largest_inscribed_rectangle(&disk, 100).expect("Trapezoidation failed").expect("No cell inside");
+    assert!((found[1][0] - found[0][0]) * (found[1][1] - found[0][1]) >= 200. * 0.95, "{found:?}");
+    assert!(inside(&disk, found));
+    let ring = shapes::ring([0f64, 0.], 3., 10., 256);
+    let found = label::largest_inscribed_rectangle(&ring, 100).expect("Trapezoidation failed").expect("No cell inside");
+    assert!(inside(&ring, found));
+    assert!(found[0][0] >= 3. - 1e-6 || found[1][0] <= -3. + 1e-6 || found[0][1] >= 3. - 1e-6 || found[1][1] <= -3. + 1e-6, "{found:?}");
+}
+
+#[test]
+fn empty() {
+    assert!(matches!(label::pole_of_inaccessibility(&Vec::<Vec<[f64; 2]>>::new(), 0.1), Ok(None)));
+    let line = vec![vec![[0f64, 0.], [1., 1.], [2., 2.]]];
+    assert!(matches!(label::pole_of_inaccessibility(&line, 0.1), Ok(None)));
+    assert!(matches!(label::largest_inscribed_rectangle(&line, 10), Ok(None)));
+    // A bar thinner than the cells
+    let bar = vec![vec![[0f64, 0.], [100., 0.], [100., 1.], [0., 1.]]];
+    assert!(matches!(label::largest_inscribed_rectangle(&bar, 10), Ok(None)));
+}
@@ -0,0 +1,690 @@
+use std::collections::HashMap;
+
+use crate::{boolean::{self, BooleanOp}, math, mesh::{Anchor, Mesh, PartitionWeight, WatertightViolation}, FromCoords, IntoCoords, LinearConstructor, TriangulationError, Vertex, shapes, triangulate_simple};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ShadedVertex {
+    position: [f64; 2],
+    shade: f64,
+}
+
+impl Vertex for ShadedVertex {
+    type Coordinate = f64;
+    type Payload = f64;
+
+    fn x(&self) -> f64 { self.position[0] }
+    fn y(&self) -> f64 { self.position[1] }
+    fn payload(&self) -> &f64 { &self.shade }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Point {
+    x: f64,
+    y: f64,
+}
+
+impl Vertex for Point {
+    type Coordinate = f64;
+    type Payload = ();
+
+    fn x(&self) -> f64 { self.x }
+    fn y(&self) -> f64 { self.y }
+    fn payload(&self) -> &() { &() }
+}
+
+impl FromCoords for Point {
+    fn from_coords([x, y]: [f64; 2]) -> Self { Point { x, y } }
+}
+
+fn shade(x: f64, y: f64) -> f64 {
+    (x * 3.).sin() * (y * 2.).cos()
+}
+
+fn total_area<V: Vertex<Coordinate=f64>>(mesh: &Mesh<V>) -> f64 {
+    mesh.iter_triangles().map(Mesh::<V>::area_of).sum()
+}
+
+// Each edge must be shared by two oppositely-directed triangles, or be on the boundary
+fn assert_conforming<V: Vertex>(mesh: &Mesh<V>, boundary_edges: usize) {
+    let mut edges = HashMap::new();
+    for t in mesh.triangles.iter() {
+        for k in 0..3 {
+            *edges.entry((t[k], t[(k + 1) % 3])).or_insert(0) += 1;
+        }
+    }
+    assert!(edges.values().all(|count| *count == 1));
+    assert_eq!(edges.keys().filter(|(vi0, vi1)| !edges.contains_key(&(*vi1, *vi0))).count(), boundary_edges);
+}
+
+// No vertex may lie inside an edge of a triangle which does not use it
+fn assert_no_t_junctions(mesh: &Mesh<[f64; 2]>) {
+    for t in mesh.triangles.iter() {
+        for k in 0..3 {
+            let [a, b] = [mesh.vertices[t[k]], mesh.vertices[t[(k + 1) % 3]]];
+            for (vi, c) in mesh.vertices.iter().enumerate() {
+                let cross = (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0]);
+                let along = (c[0] - a[0]) * (b[0] - a[0]) + (c[1] - a[1]) * (b[1] - a[1]);
+                let length = (b[0] - a[0]).powi(2) + (b[1] - a[1]).powi(2);
+                assert!(t.contains(&vi) || cross != 0. || along <= 0. || along >= length, "vertex {vi} inside edge {:?}", [t[k], t[(k + 1) % 3]]);
+            }
+        }
+    }
+}
+
+#[test]
+fn from_polygons() {
+    let polygons = vec![
+        vec![[0f64, 0.], [4., 0.], [4., 4.], [0., 4.]],
+        vec![[1., 1.], [1., 3.], [3., 3.], [3., 1.]],
+    ];
+    let mesh = Mesh::from_polygons(&polygons).expect("Triangulation failed");
+    assert_eq!(mesh.vertices, polygons.concat());
+    assert_eq!(mesh.triangles.len(), 8);
+    assert_eq!(total_area(&mesh), 12.);
+    assert_conforming(&mesh, 8);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn from_polygons_par() {
+    // A grid of squares, each with a hole, and every third with an island in its hole
+    let mut polygons = Vec::new();
+    for k in 0..100 {
+        let [x, y] = [(k % 10) as f64 * 5., (k / 10) as f64 * 5.];
+        polygons.push(vec![[x, y], [x + 4., y], [x + 4., y + 4.], [x, y + 4.]]);
+        polygons.push(vec![[x + 1., y + 1.], [x + 1., y + 3.], [x + 3., y + 3.], [x + 3., y + 1.]]);
+        if k % 3 == 0 {
+            polygons.push(vec![[x + 1.5, y + 1.5], [x + 2.5, y + 1.5], [x + 2.5, y + 2.5], [x + 1.5, y + 2.5]]);
+        }
+    }
+    let mesh = Mesh::from_polygons_par(&polygons).expect("Triangulation failed");
+    let sequential = Mesh::from_polygons(&polygons).expect("Triangulation failed");
+    assert_eq!(mesh.vertices, sequential.vertices);
+    assert_eq!(mesh.triangles.len(), sequential.triangles.len());
+    assert!((total_area(&mesh) - total_area(&sequential)).abs() < 1e-9);
+    assert_conforming(&mesh, polygons.iter().map(Vec::len).sum());
+
+    // The triangles of each square come before those of the next, and the same every time
+    let first = mesh.triangles.iter().position(|t| t.iter().any(|vi| *vi >= 12)).expect("Only one square");
+    assert_eq!(first, 8 + 2);
+    assert_eq!(Mesh::from_polygons_par(&polygons).expect("Triangulation failed"), mesh);
+}
+
+#[test]
+fn refine_area() {
+    let polygons = vec![vec![[0f64, 0.], [3., 0.], [3., 1.], [1., 2.], [0., 1.]]];
+    let mut mesh = Mesh::from_polygons(&polygons).expect("Triangulation failed");
+    let area = total_area(&mesh);
+    assert!(mesh.refine(&mut LinearConstructor, Mesh::<[f64; 2]>::area_of, 0.05, 32));
+    assert!(mesh.iter_triangles().all(|t| Mesh::<[f64; 2]>::area_of(t) > 0. && Mesh::<[f64; 2]>::area_of(t) <= 0.05));
+    assert!((total_area(&mesh) - area).abs() < 1e-9);
+}
+
+#[test]
+fn refine_gradient() {
+    let polygons = vec![[[0f64, 0.], [2., 0.], [2., 2.], [0., 2.]].map(|[x, y]| ShadedVertex { position: [x, y], shade: shade(x, y) }).to_vec()];
+    let mut mesh = Mesh::from_polygons(&polygons).expect("Triangulation failed");
+
+    // The deviation between the interpolated and the true shade at the centroid
+    let error = |t: [&ShadedVertex; 3]| {
+        let (x, y) = ((t[0].x() + t[1].x() + t[2].x()) / 3., (t[0].y() + t[1].y() + t[2].y()) / 3.);
+        ((t[0].shade + t[1].shade + t[2].shade) / 3. - shade(x, y)).abs()
+    };
+    let mut constructor = |a: &ShadedVertex, b: &ShadedVertex, t: f64| {
+        let position = [a.x() + (b.x() - a.x()) * t, a.y() + (b.y() - a.y()) * t];
+        ShadedVertex { position, shade: shade(position[0], position[1]) }
+    };
+    assert!(mesh.refine(&mut constructor, error, 0.01, 32));
+    assert!(mesh.vertices.len() > 4);
+    assert!(mesh.iter_triangles().all(|t| error(t) <= 0.01));
+    assert_eq!(total_area(&mesh), 4.);
+
+    let boundary_edges = mesh.vertices.iter().filter(|v| v.x() == 0. || v.x() == 2. || v.y() == 0. || v.y() == 2.).count();
+    assert_conforming(&mesh, boundary_edges);
+
+    // Nothing is refined if the threshold is already met
+    let vertex_count = mesh.vertices.len();
+    assert!(mesh.refine(&mut constructor, error, 1., 32));
+    assert_eq!(mesh.vertices.len(), vertex_count);
+}
+
+#[test]
+fn subdivide_edges() {
+    let polygons = vec![
+        vec![[0f64, 0.], [5., 0.], [5., 3.], [0., 3.]],
+        vec![[1., 1.], [1., 2.], [4., 2.], [4., 1.]],
+    ];
+    let mut mesh = Mesh::from_polygons(&polygons).expect("Triangulation failed");
+    mesh.subdivide_edges(&mut LinearConstructor, 0.6);
+
+    for t in mesh.triangles.iter() {
+        for k in 0..3 {
+            let (v0, v1) = (mesh.vertices[t[k]], mesh.vertices[t[(k + 1) % 3]]);
+            assert!((v1[0] - v0[0]).hypot(v1[1] - v0[1]) <= 0.6);
+        }
+    }
+    assert!(mesh.iter_triangles().all(|t| Mesh::<[f64; 2]>::area_of(t) > 0.));
+    assert_eq!(total_area(&mesh), 12.);
+
+    // Each vertex on the outer boundary or the hole begins exactly one boundary edge
+    let boundary_edges = mesh.vertices.iter().filter(|[x, y]| *x == 0. || *x == 5. || *y == 0. || *y == 3.).count()
+        + mesh.vertices.iter().filter(|[x, y]| (*x == 1. || *x == 4.) && (1. ..=2.).contains(y) || (*y == 1. || *y == 2.) && (1. ..=4.).contains(x)).count();
+    assert_conforming(&mesh, boundary_edges);
+}
+
+#[test]
+fn subdivide_edges_invalid_length() {
+    let polygons = vec![vec![[0f64, 0.], [5., 0.], [5., 3.], [0., 3.]]];
+    let mesh = Mesh::from_polygons(&polygons).expect("Triangulation failed");
+    for max_length in [0., -1., f64::NAN] {
+        let mut subdivided = mesh.clone();
+        subdivided.subdivide_edges(&mut LinearConstructor, max_length);
+        assert_eq!(subdivided, mesh);
+    }
+}
+
+#[test]
+fn batched() {
+    let polygons = vec![
+        // Key 2: a square with a hole
+        vec![[0f64, 0.], [4., 0.], [4., 4.], [0., 4.]],
+        vec![[1., 1.], [1., 3.], [3., 3.], [3., 1.]],
+        // Key 1: an island inside the hole, wound clockwise
+        vec![[1.5, 1.5], [1.5, 2.5], [2.5, 2.5], [2.5, 1.5]],
+        // Key 0: a separate triangle
+        vec![[5., 0.], [6., 0.], [5., 1.]],
+    ];
+    let batched = Mesh::from_polygons_batched(&polygons, &[2, 7, 1, 0]).expect("Triangulation failed");
+    assert_eq!(batched.batches.iter().map(|batch| batch.key).collect::<Vec<_>>(), vec![0, 1, 2]);
+    assert_eq!(batched.batches.last().unwrap().triangles.end, batched.mesh.triangles.len());
+
+    let area = |key| batched.batch(key).iter().map(|t| Mesh::area_of(t.map(|vi| &batched.mesh.vertices[vi]))).sum::<f64>();
+    assert_eq!(area(0), 0.5);
+    assert_eq!(area(1), 1.);
+    assert_eq!(area(2), 12.);
+    assert!(batched.batch(7).is_empty());
+
+    assert!(matches!(Mesh::from_polygons_batched(&polygons, &[2, 7]), Err(TriangulationError::MissingKey(2))));
+}
+
+#[test]
+fn clip() {
+    let polygons = vec![[[0f64, 0.], [4., 0.], [4., 4.], [0., 4.]].map(|[x, y]| ShadedVertex { position: [x, y], shade: x + 2. * y }).to_vec()];
+    let mut mesh = Mesh::from_polygons(&polygons).expect("Triangulation failed");
+    let mut constructor = |a: &ShadedVertex, b: &ShadedVertex, t: f64| ShadedVertex {
+        position: [a.x() + (b.x() - a.x()) * t, a.y() + (b.y() - a.y()) * t],
+        shade: a.shade + (b.shade - a.shade) * t,
+    };
+
+    // A diamond inscribed in the square, rotated 45 degrees
+    mesh.clip_convex(&mut constructor, &[[2., 0.], [4., 2.], [2., 4.], [0., 2.]]);
+    assert_eq!(total_area(&mesh), 8.);
+    assert!(mesh.iter_triangles().all(|t| Mesh::area_of(t) > 0.));
+    // The shade is linear, so interpolated vertices have exact values
+    assert!(mesh.vertices.iter().all(|v| (v.shade - (v.x() + 2. * v.y())).abs() < 1e-12));
+
+    // Clipping away half the diamond
+    mesh.clip_half_plane(&mut constructor, [2., 4.], [2., 0.]);
+    assert_eq!(total_area(&mesh), 4.);
+    assert!(mesh.iter_triangles().flatten().all(|v| v.x() >= 2.));
+
+    mesh.remove_unused_vertices();
+    assert!(mesh.vertices.iter().all(|v| v.x() >= 2.));
+    assert_eq!(total_area(&mesh), 4.);
+    assert_conforming(&mesh, mesh.vertices.len());
+
+    // Everything is clipped by a half-plane beside the mesh
+    mesh.clip_half_plane(&mut constructor, [0., 0.], [0., 4.]);
+    assert!(mesh.triangles.is_empty());
+}
+
+#[test]
+fn watertight() {
+    let polygons = vec![
+        vec![[0f64, 0.], [4., 0.], [4., 4.], [0., 4.]],
+        vec![[1., 1.], [1., 3.], [3., 3.], [3., 1.]],
+    ];
+    let mut mesh = Mesh::from_polygons(&polygons).expect("Triangulation failed");
+    assert_eq!(mesh.check_watertight(), Ok(()));
+    mesh.subdivide_edges(&mut LinearConstructor, 0.5);
+    assert_eq!(mesh.check_watertight(), Ok(()));
+
+    let vertices = vec![[0f64, 0.], [1., 0.], [1., 1.], [0., 1.], [2., 0.]];
+    let mesh = Mesh::new(vertices.clone(), vec![[0, 1, 2], [0, 2, 3]]);
+    assert_eq!(mesh.check_watertight(), Ok(()));
+
+    // A flipped triangle, a repeated vertex, and an out of bounds index
+    let mesh = Mesh::new(vertices.clone(), vec![[0, 1, 2], [0, 3, 2], [0, 0, 1], [1, 4, 7]]);
+    assert_eq!(mesh.check_watertight(), Err(vec![
+        WatertightViolation::InvalidIndex { triangle: 3 },
+        WatertightViolation::DegenerateTriangle { triangle: 2 },
+        WatertightViolation::InconsistentOrientation { edge: [2, 0], triangles: vec![0, 1] },
+        WatertightViolation::OpenBoundary { vertex: 0 },
+        WatertightViolation::OpenBoundary { vertex: 2 },
+    ]));
+
+    let mesh = Mesh::new(vertices, vec![[0, 1, 2], [0, 2, 3], [2, 0, 4]]);
+    assert_eq!(mesh.check_watertight(), Err(vec![
+        WatertightViolation::NonManifoldEdge { edge: [0, 2], triangles: vec![0, 1, 2] },
+        WatertightViolation::OpenBoundary { vertex: 0 },
+        WatertightViolation::OpenBoundary { vertex: 2 },
+    ]));
+
+    // A reversed triangle between its neighbors is only inconsistent with them
+    let vertices = vec![[0f64, 0.], [2., 0.], [0., 2.], [1., 0.], [1., 1.], [0., 1.]];
+    let mesh = Mesh::new(vertices, vec![[0, 3, 5], [3, 1, 4], [5, 4, 2], [3, 5, 4]]);
+    assert_eq!(mesh.check_watertight(), Err(vec![
+        WatertightViolation::InconsistentOrientation { edge: [3, 5], triangles: vec![0, 3] },
+        WatertightViolation::InconsistentOrientation { edge: [4, 3], triangles: vec![1, 3] },
+        WatertightViolation::InconsistentOrientation { edge: [5, 4], triangles: vec![2, 3] },
+    ]));
+}
+
+// The smallest angle of any triangle, in degrees
+fn min_angle<V: Vertex<Coordinate=f64>>(mesh: &Mesh<V>) -> f64 {
+    mesh.iter_triangles().flat_map(|t| (0..3).map(move |k| {
+        let (a, b, c) = (t[k], t[(k + 1) % 3], t[(k + 2) % 3]);
+        let (u, v) = ([b.x() - a.x(), b.y() - a.y()], [c.x() - a.x(), c.y() - a.y()]);
+        ((u[0] * v[0] + u[1] * v[1]) / (u[0].hypot(u[1]) * v[0].hypot(v[1]))).acos().to_degrees()
+    })).fold(180., f64::min)
+}
+
+#[test]
+fn smooth() {
+    let polygons = vec![vec![[0f64, 0.], [3., 0.], [3., 1.], [1., 2.], [0., 1.]]];
+    let mut mesh = Mesh::from_polygons(&polygons).expect("Triangulation failed");
+    mesh.refine(&mut LinearConstructor, Mesh::<[f64; 2]>::area_of, 0.05, 32);
+    let (before, triangles) = (mesh.clone(), mesh.triangles.clone());
+
+    mesh.smooth(&mut LinearConstructor, 0.5, 10);
+    assert_eq!(mesh.triangles, triangles);
+    assert!(mesh.iter_triangles().all(|t| Mesh::<[f64; 2]>::area_of(t) > 0.));
+    assert!((total_area(&mesh) - total_area(&before)).abs() < 1e-9);
+    assert!(min_angle(&mesh) > min_angle(&before));
+    // Only interior vertices move
+    let edges: Vec<(usize, usize)> = triangles.iter().flat_map(|t| (0..3).map(move |k| (t[k], t[(k + 1) % 3]))).collect();
+    let boundary: Vec<usize> = edges.iter().filter(|(vi0, vi1)| !edges.contains(&(*vi1, *vi0))).map(|(vi0, _)| *vi0).collect();
+    assert!(boundary.iter().all(|vi| mesh.vertices[*vi] == before.vertices[*vi]));
+    assert!(mesh.vertices != before.vertices);
+
+    // A regular grid is already smooth
+    let mut grid = Mesh::new(
+        vec![[0f64, 0.], [1., 0.], [2., 0.], [0., 1.], [1., 1.], [2., 1.], [0., 2.], [1., 2.], [2., 2.]],
+        vec![[0, 1, 4], [0, 4, 3], [1, 2, 5], [1, 5, 4], [3, 4, 7], [3, 7, 6], [4, 5, 8], [4, 8, 7]],
+    );
+    let vertices = grid.vertices.clone();
+    grid.smooth(&mut LinearConstructor, 1., 3);
+    assert_eq!(grid.vertices, vertices);
+}
+
+#[test]
+fn flip_to_delaunay() {
+    // A fan over a convex chain on a parabola, which needs many rounds of flips
+    let vertices: Vec<[f64; 2]> = (-15..=15).map(|x| [x as f64, (x * x) as f64]).collect();
+    let triangles = (1..vertices.len() - 1).map(|i| [0, i, i + 1]).collect::<Vec<_>>();
+    let mut mesh = Mesh::new(vertices, triangles);
+    let area = total_area(&mesh);
+    assert!(mesh.flip_to_delaunay());
+    assert_eq!(mesh.triangles.len(), 29);
+    assert_eq!(total_area(&mesh), area);
+    assert_conforming(&mesh, 31);
+
+    // No vertex is inside the circumcircle of a triangle
+    for t in mesh.iter_triangles() {
+        let [[ax, ay], [bx, by], [cx, cy]] = t.map(|v| *v);
+        for &[dx, dy] in mesh.vertices.iter() {
+            let [(ax, ay), (bx, by), (cx, cy)] = [(ax - dx, ay - dy), (bx - dx, by - dy), (cx - dx, cy - dy)];
+            let det = (ax * ax + ay * ay) * (bx * cy - cx * by) - (bx * bx + by * by) * (ax * cy - cx * ay) + (cx * cx + cy * cy) * (ax * by - bx * ay);
+            assert!(det <= 0.);
+        }
+    }
+}
+
+#[test]
+fn remesh() {
+    let polygons = vec![
+        vec![[0f64, 0.], [6., 0.], [6., 4.], [0., 4.]],
+        vec![[2., 1.], [2., 3.], [3., 3.], [3., 1.]],
+    ].into_iter().map(|ring| ring.into_iter().map(|[x, y]| ShadedVertex { position: [x, y], shade: x + 2. * y }).collect()).collect::<Vec<Vec<_>>>();
+    let mut mesh = Mesh::from_polygons(&polygons).expect("Triangulation failed");
+    let mut constructor = |a: &ShadedVertex, b: &ShadedVertex, t: f64| ShadedVertex {
+        position: [a.x() + (b.x() - a.x()) * t, a.y() + (b.y() - a.y()) * t],
+        shade: a.shade + (b.shade - a.shade) * t,
+    };
+    mesh.remesh(&mut constructor, 0.5, 5);
+
+    assert_eq!(mesh.check_watertight(), Ok(()));
+    assert!((total_area(&mesh) - 22.).abs() < 1e-9);
+    assert!(polygons.iter().flatten().all(|v| mesh.vertices.contains(v)));
+    assert!(mesh.vertices.iter().all(|v| (v.shade - (v.x() + 2. * v.y())).abs() < 1e-9));
+
+    // Roughly equilateral triangles with sides of about 0.5
+    assert!((150..300).contains(&mesh.triangles.len()));
+    assert!(min_angle(&mesh) > 20.);
+    for t in mesh.iter_triangles() {
+        assert!(Mesh::area_of(t) > 0.);
+        for k in 0..3 {
+            let (a, b) = (t[k].position, t[(k + 1) % 3].position);
+            assert!((b[0] - a[0]).hypot(b[1] - a[1]) < 0.8);
+        }
+    }
+}
+
+#[test]
+fn decimate() {
+    let polygons = vec![
+        vec![[0f64, 0.], [6., 0.], [6., 4.], [0., 4.]],
+        vec![[2., 1.], [2., 3.], [3., 3.], [3., 1.]],
+    ];
+    let mut dense = Mesh::from_polygons(&polygons).expect("Triangulation failed");
+    dense.remesh(&mut LinearConstructor, 0.5, 3);
+    let boundary_edges = |mesh: &Mesh<[f64; 2]>| {
+        let edges: Vec<([f64; 2], [f64; 2])> = mesh.triangles.iter().flat_map(|t| (0..3).map(move |k| (t[k], t[(k + 1) % 3]))).map(|(vi0, vi1)| (mesh.vertices[vi0], mesh.vertices[vi1])).collect();
+        let mut boundary: Vec<_> = edges.iter().filter(|(a, b)| !edges.contains(&(*b, *a))).map(|(a, b)| format!("{a:?}{b:?}")).collect();
+        boundary.sort();
+        boundary
+    };
+    let outline = boundary_edges(&dense);
+
+    // A flat region loses all of its interior vertices, leaving one triangle per boundary vertex around the hole
+    let mut flat = dense.clone();
+    flat.decimate(0, f64::INFINITY, |_| 0.);
+    assert_eq!(flat.check_watertight(), Ok(()));
+    assert_eq!(boundary_edges(&flat), outline);
+    assert_eq!(flat.triangles.len(), outline.len());
+    assert_eq!(flat.vertices.len(), outline.len());
+    assert!((total_area(&flat) - 22.).abs() < 1e-9);
+    assert!(flat.iter_triangles().all(|t| Mesh::area_of(t) > 0.));
+
+    // Decimation stops at the target
+    let mut partial = dense.clone();
+    partial.decimate(100, f64::INFINITY, |_| 0.);
+    assert!((99..=100).contains(&partial.triangles.len()));
+    assert_eq!(boundary_edges(&partial), outline);
+
+    // A curved surface keeps the vertices which would move it too far
+    let mut curved = dense.clone();
+    curved.decimate(0, 1e-3, |v| v[0] * v[0] + v[1] * v[1]);
+    assert!(curved.triangles.len() > flat.triangles.len() && curved.triangles.len() < dense.triangles.len());
+    assert_eq!(boundary_edges(&curved), outline);
+    assert!((total_area(&curved) - 22.).abs() < 1e-9);
+    // But a planar one has no error
+    let mut planar = dense.clone();
+    planar.decimate(0, 1e-9, |v| v[0] + 2. * v[1]);
+    assert_eq!(planar.triangles.len(), flat.triangles.len());
+}
+
+#[test]
+fn elevate() {
+    // A terrace, with the hole raised above the outline
+    let polygons = vec![
+        vec![[0f64, 0., 0.], [4., 0., 0.], [4., 4., 0.], [0., 4., 0.]],
+        vec![[1., 1., 1.], [1., 3., 1.], [3., 3., 1.], [3., 1., 1.]],
+    ];
+    let mut mesh = Mesh::from_polygons(&polygons).expect("Triangulation failed");
+    let solid = mesh.elevate(|v| v[2]);
+    assert_eq!(solid.triangles, mesh.triangles);
+    assert_eq!(solid.positions, mesh.vertices);
+    // Every slope between the levels faces up and outwards
+    assert!(solid.face_normals().iter().all(|n| n[2] > 0.));
+
+    // New vertices are interpolated between the levels
+    mesh.subdivide_edges(&mut LinearConstructor, 0.5);
+    let solid = mesh.elevate(|v| v[2]);
+    assert!(solid.positions.len() > 8);
+    for [x, y, z] in solid.positions {
+        let ring = (x - 2.).abs().max((y - 2.).abs());
+        assert!((z - (2. - ring)).abs() < 1e-9, "{x} {y} {z}");
+    }
+
+    // Any attribute can become the elevation
+    let flat = Mesh::from_polygons(&vec![vec![[0f64, 0.], [1., 0.], [0., 1.]]]).expect("Triangulation failed");
+    assert_eq!(flat.elevate(|v| v[0] + v[1]).positions, vec![[0., 0., 0.], [1., 0., 1.], [0., 1., 1.]]);
+}
+
+#[test]
+fn to_local() {
+    // An L shape far from the origin
+    let polygons = vec![vec![[1000f64, 2000.], [1002., 2000.], [1002., 2001.], [1001., 2001.], [1001., 2002.], [1000., 2002.]]];
+    let mesh = Mesh::from_polygons(&polygons).expect("Triangulation failed");
+
+    let local = mesh.to_local(Anchor::Min);
+    assert_eq!(local.anchor, [1000., 2000.]);
+    assert_eq!(local.mesh.vertices[2], [2., 1.]);
+    assert_eq!(local.mesh.triangles, mesh.triangles);
+    assert_eq!(mesh.to_local(Anchor::Center).anchor, [1001., 2001.]);
+    assert_eq!(mesh.to_local(Anchor::Point([5., 5.])).mesh.vertices[0], [995., 1995.]);
+
+    // The centroid of the three unit squares
+    let local = mesh.to_local(Anchor::Centroid);
+    assert!((local.anchor[0] - (1000. + 5. / 6.)).abs() < 1e-9 && (local.anchor[1] - (2000. + 5. / 6.)).abs() < 1e-9);
+    assert!((total_area(&local.mesh) - 3.).abs() < 1e-9);
+    // Adding the anchor back restores the original positions
+    for (v, original) in local.mesh.vertices.iter().zip(&mesh.vertices) {
+        assert!((v[0] + local.anchor[0] - original[0]).abs() < 1e-9 && (v[1] + local.anchor[1] - original[1]).abs() < 1e-9);
+    }
+
+    assert_eq!(Mesh::<[f64; 2]>::default().to_local(Anchor::Centroid).anchor, [0., 0.]);
+}
+
+#[test]
+fn dual_graph() {
+    let polygons = vec![vec![[0f64, 0.], [2., 0.], [2., 1.], [0., 1.]]];
+    let mut mesh = Mesh::from_polygons(&polygons).expect("Triangulation failed");
+    let graph = mesh.dual_graph();
+    assert_eq!(graph.node_count(), 2);
+    assert_eq!(graph.edge_count(), 1);
+    // The two triangles share the diagonal
+    assert_eq!(graph.neighbors(0).collect::<Vec<_>>(), vec![(1, 5f64.sqrt())]);
+    assert_eq!(graph.neighbors(1).collect::<Vec<_>>(), vec![(0, 5f64.sqrt())]);
+
+    mesh.subdivide_edges(&mut LinearConstructor, 0.3);
+    let graph = mesh.dual_graph();
+    assert_eq!(graph.node_count(), mesh.triangles.len());
+    assert_eq!(graph.offsets.len(), mesh.triangles.len() + 1);
+    // Every edge is shared by two triangles except those on the boundary
+    let edges: Vec<(usize, usize)> = mesh.triangles.iter().flat_map(|t| (0..3).map(move |k| (t[k], t[(k + 1) % 3]))).collect();
+    let boundary_edges = edges.iter().filter(|(vi0, vi1)| !edges.contains(&(*vi1, *vi0))).count();
+    assert_eq!(boundary_edges, 2 * (8 + 4));
+    assert_eq!(2 * graph.edge_count() + boundary_edges, 3 * mesh.triangles.len());
+    for ti in 0..graph.node_count() {
+        assert!(graph.neighbors(ti).count() <= 3);
+        for (tj, length) in graph.neighbors(ti) {
+            assert!(graph.neighbors(tj).any(|(tk, l)| tk == ti && l == length));
+            let shared: Vec<usize> = mesh.triangles[ti].into_iter().filter(|vi| mesh.triangles[tj].contains(vi)).collect();
+            let [a, b] = [mesh.vertices[shared[0]], mesh.vertices[shared[1]]];
+            assert!(((b[0] - a[0]).hypot(b[1] - a[1]) - length).abs() < 1e-12);
+        }
+    }
+}
+
+#[cfg(feature = "petgraph")]
+#[test]
+fn dual_graph_petgraph() {
+    let mut mesh = Mesh::from_polygons(&vec![vec![[0f64, 0.], [2., 0.], [2., 1.], [0., 1.]]]).expect("Triangulation failed");
+    mesh.subdivide_edges(&mut LinearConstructor, 0.3);
+    let graph = mesh.dual_graph();
+    let petgraph = graph.to_petgraph();
+    assert_eq!(petgraph.node_count(), graph.node_count());
+    assert_eq!(petgraph.edge_count(), graph.edge_count());
+    assert_eq!(petgraph::algo::connected_components(&petgraph), 1);
+}
+
+#[test]
+fn partition() {
+    // A long strip with a hole, refined unevenly
+    let polygons = vec![
+        vec![[0f64, 0.], [8., 0.], [8., 2.], [0., 2.]],
+        vec![[5., 0.5], [5., 1.5], [6., 1.5], [6., 0.5]],
+    ];
+    let mut mesh = Mesh::from_polygons(&polygons).expect("Triangulation failed");
+    mesh.subdivide_edges(&mut LinearConstructor, 0.4);
+    mesh.refine(&mut LinearConstructor, |t| if t[0][0] < 2. { Mesh::<[f64; 2]>::area_of(t) } else { 0. }, 0.01, 8);
+    let graph = mesh.dual_graph();
+    let largest = mesh.iter_triangles().map(Mesh::<[f64; 2]>::area_of).fold(0., f64::max);
+
+    let chunks = mesh.partition(4, PartitionWeight::Area);
+    for chunk in 0..4 {
+        let triangles: Vec<usize> = (0..chunks.len()).filter(|ti| chunks[*ti] == chunk).collect();
+        let area: f64 = triangles.iter().map(|ti| Mesh::<[f64; 2]>::area_of(mesh.triangles[*ti].map(|vi| &mesh.vertices[vi]))).sum();
+        assert!((area - 15. / 4.).abs() <= 4. * largest, "chunk {chunk} has area {area}");
+
+        // Every chunk is connected
+        let mut reached = vec![triangles[0]];
+        let mut stack = vec![triangles[0]];
+        while let Some(ti) = stack.pop() {
+            for (tj, _) in graph.neighbors(ti) {
+                if chunks[tj] == chunk && !reached.contains(&tj) {
+                    reached.push(tj);
+                    stack.push(tj);
+                }
+            }
+        }
+        assert_eq!(reached.len(), triangles.len());
+    }
+
+    let chunks = mesh.partition(3, PartitionWeight::Count);
+    let counts: Vec<usize> = (0..3).map(|chunk| chunks.iter().filter(|c| **c == chunk).count()).collect();
+    assert_eq!(counts.iter().sum::<usize>(), mesh.triangles.len());
+    assert!(counts.iter().all(|count| count.abs_diff(mesh.triangles.len() / 3) <= 1), "{counts:?}");
+
+    assert!(mesh.partition(1, PartitionWeight::Area).iter().all(|c| *c == 0));
+    assert_eq!(Mesh::<[f64; 2]>::default().partition(4, PartitionWeight::Area), Vec::<usize>::new());
+}
+
+#[test]
+fn retriangulate_soup() {
+    // Separate vertices for each triangle, a T-junction at (1, 1), an overlapping and a clockwise triangle
+    let soup = [
+        [[0., 0.], [1., 0.], [1., 1.]],
+        [[0., 0.], [1., 1.], [0., 2.]],
+        [[0., 2.], [1., 2.], [1., 1.]],
+        [[1., 0.], [2., 0.], [2., 2.]],
+        [[1., 0.], [2., 2.], [1., 2.]],
+        [[0., 0.], [2., 0.], [1., 1.]],
+    ];
+    let mesh = Mesh::new(soup.iter().flatten().copied().collect::<Vec<[f64; 2]>>(), (0..soup.len()).map(|i| [3 * i, 3 * i + 1, 3 * i + 2]).collect());
+    let outline = mesh.outline();
+    assert_eq!(outline.len(), 1);
+    assert_eq!(outline[0].len(), 4);
+    let retriangulated = mesh.retriangulate().expect("Triangulation failed");
+    assert_eq!(retriangulated.triangles.len(), 2);
+    assert_eq!(retriangulated.check_watertight(), Ok(()));
+    let area: f64 = retriangulated.iter_triangles().map(Mesh::<[f64; 2]>::area_of).sum();
+    assert!((area - 4.).abs() < 1e-9);
+
+    // A frame around a hole keeps the hole
+    let polygons = vec![vec![[0f64, 0.], [3., 0.], [3., 3.], [0., 3.]], vec![[1., 1.], [1., 2.], [2., 2.], [2., 1.]]];
+    let mut frame = Mesh::from_polygons(&polygons).expect("Triangulation failed");
+    frame.subdivide_edges(&mut LinearConstructor, 0.5);
+    let outline = frame.outline();
+    assert_eq!(outline.len(), 2);
+    assert!(outline.iter().all(|ring| ring.len() == 4));
+    let retriangulated = frame.retriangulate().expect("Triangulation failed");
+    assert_eq!(retriangulated.triangles.len(), 8);
+}
+
+#[test]
+fn boolean() {
+    let square = |x: f64, y: f64, size: f64| vec![vec![[x, y], [x + size, y], [x + size, y + size], [x, y + size]]];
+    let mut a = Mesh::from_polygons(&square(0., 0., 2.)).expect("Triangulation failed");
+    a.subdivide_edges(&mut LinearConstructor, 0.5);
+    let b = Mesh::from_polygons(&square(1., 1., 2.)).expect("Triangulation failed");
+
+    for (op, area) in [(BooleanOp::Union, 7.), (BooleanOp::Intersection, 1.), (BooleanOp::Difference, 3.), (BooleanOp::Xor, 6.)] {
+        let result = a.boolean(&b, op).expect("Triangulation failed");
+        assert_eq!(result.check_watertight(), Ok(()));
+        assert_no_t_junctions(&result);
+        assert!((total_area(&result) - area).abs() < 1e-9, "{op:?}");
+    }
+
+    // Only the triangles touched by the boundary of the other operand are triangulated again
+    let corners = |mesh: &Mesh<[f64; 2]>, t: &[usize; 3]| {
+        let mut corners = t.map(|vi| mesh.vertices[vi]);
+        corners.sort_by(|c0, c1| c0.partial_cmp(c1).unwrap());
+        corners
+    };
+    let difference = a.boolean(&b, BooleanOp::Difference).expect("Triangulation failed");
+    let kept: Vec<_> = difference.triangles.iter().map(|t| corners(&difference, t)).collect();
+    let untouched: Vec<_> = a.triangles.iter().map(|t| corners(&a, t)).filter(|c| c.iter().any(|[x, y]| *x < 1. && *y < 1.) && c.iter().all(|[x, y]| *x < 1. || *y < 1.)).collect();
+    assert!(untouched.len() >= 8);
+    assert!(untouched.iter().all(|c| kept.contains(c)));
+
+    // Subtracting a square from the middle leaves a hole, and the result can be used as an operand again
+    let frame = a.boolean(&Mesh::from_polygons(&square(0.5, 0.5, 1.)).expect("Triangulation failed"), BooleanOp::Difference).expect("Triangulation failed");
+    assert!((total_area(&frame) - 3.).abs() < 1e-9);
+    assert_eq!(frame.outline().len(), 2);
+    let filled = frame.boolean(&b, BooleanOp::Union).expect("Triangulation failed");
+    assert!((total_area(&filled) - 6.25).abs() < 1e-9);
+    assert_eq!(filled.check_watertight(), Ok(()));
+    assert_no_t_junctions(&filled);
+
+    // Shared edges are kept once
+    let right = Mesh::from_polygons(&square(2., 0., 2.)).expect("Triangulation failed");
+    let union = a.boolean(&right, BooleanOp::Union).expect("Triangulation failed");
+    assert!((total_area(&union) - 8.).abs() < 1e-9);
+    assert_eq!(union.outline().len(), 1);
+    assert_no_t_junctions(&union);
+    assert!(a.boolean(&right, BooleanOp::Intersection).expect("Triangulation failed").triangles.is_empty());
+
+    // An empty result is an empty mesh, not an error
+    let apart = Mesh::from_polygons(&square(5., 5., 1.)).expect("Triangulation failed");
+    assert!(a.boolean(&apart, BooleanOp::Intersection).expect("Triangulation failed").triangles.is_empty());
+}
+
+#[test]
+fn boolean_curved() {
+    // Crossings at rounded points, and enclosed triangles which must be triangulated again with the ones around them
+    let circle = shapes::circle([0f64, 0.], 1., 24);
+    let mut a = Mesh::from_polygons(&circle).expect("Triangulation failed");
+    a.subdivide_edges(&mut LinearConstructor, 0.3);
+    for i in 0..20 {
+        let pentagon = vec![(0..5).map(|k| {
+            let angle = i as f64 * 0.37 + k as f64 * 1.2566;
+            [0.4 + 0.9 * angle.cos(), 0.2 * (i % 3) as f64 + 0.9 * angle.sin()]
+        }).collect::<Vec<_>>()];
+        let mut b = Mesh::from_polygons(&pentagon).expect("Triangulation failed");
+        b.subdivide_edges(&mut LinearConstructor, 0.25);
+        for op in [BooleanOp::Union, BooleanOp::Intersection, BooleanOp::Difference, BooleanOp::Xor] {
+            let result = a.boolean(&b, op).expect("Triangulation failed");
+            assert_eq!(result.check_watertight(), Ok(()));
+            assert_no_t_junctions(&result);
+            assert!(result.iter_triangles().all(|t| Mesh::<[f64; 2]>::area_of(t) > 0.));
+            let expected: f64 = boolean::boolean(&circle, &pentagon, op).iter().map(|ring| math::signed_area_2(ring) / 2.).sum();
+            assert!((total_area(&result) - expected).abs() < 1e-9, "{i} {op:?}");
+        }
+    }
+}
+
+#[test]
+fn simple() {
+    let rings = vec![
+        vec![[0f32, 0.], [4., 0.], [4., 4.], [0., 4.]],
+        vec![[1., 1.], [1., 3.], [3., 3.], [3., 1.]],
+    ];
+    let (vertices, indices) = triangulate_simple(&rings).expect("Triangulation failed");
+    assert_eq!(vertices, rings.concat());
+    assert_eq!(indices.len(), 8 * 3);
+    let area: f32 = indices.chunks_exact(3).map(|t| {
+        let [a, b, c] = [0, 1, 2].map(|k| vertices[t[k] as usize]);
+        ((b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])) / 2.
+    }).sum();
+    assert_eq!(area, 12.);
+
+    assert!(triangulate_simple(&[]).expect("Triangulation failed").1.is_empty());
+}
+
+#[test]
+fn from_coords() {
+    let square = vec![vec![Point { x: 0., y: 0. }, Point { x: 2., y: 0. }, Point { x: 2., y: 2. }, Point { x: 0., y: 2. }]];
+    let mut mesh = Mesh::from_polygons(&square).expect("Triangulation failed");
+    mesh.subdivide_edges(&mut LinearConstructor, 1.);
+    assert!(mesh.vertices.contains(&Point { x: 1., y: 0. }));
+    assert!((total_area(&mesh) - 4.).abs() < 1e-9);
+
+    // A generated mesh converts to the vertex type, and back to positions
+    let retriangulated: Mesh<Point> = mesh.retriangulate().expect("Triangulation failed").convert();
+    assert!((total_area(&retriangulated) - 4.).abs() < 1e-9);
+    let positions = retriangulated.clone().convert::<[f64; 2]>();
+    assert_eq!(positions.vertices, retriangulated.vertices.into_iter().map(IntoCoords::into_coords).collect::<Vec<_>>());
+}
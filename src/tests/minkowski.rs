@@ -0,0 +1,69 @@
+use crate::{area, boolean::{self, BooleanOp}, minkowski, PolygonList};
+
+use super::util::assert_close;
+
+#[test]
+fn convex() {
+    // A square and a diamond sum to an octagon, the square with its corners cut by half a unit
+    let square = vec![vec![[0f64, 0.], [2., 0.], [2., 2.], [0., 2.]]];
+    let diamond = [[1f64, 0.], [0., 1.], [-1., 0.], [0., -1.]];
+    let sum = minkowski::minkowski_sum(&square, &diamond);
+    assert_eq!(sum.len(), 1);
+    assert_close(area::signed_area(&sum), 16. - 4. * 0.5);
+
+    // The order and convexity of the kernel do not matter
+    let shuffled = [[0f64, -1.], [0., 1.], [0., 0.], [1., 0.], [-1., 0.]];
+    assert_close(area::signed_area(&minkowski::minkowski_sum(&square, &shuffled)), 14.);
+}
+
+#[test]
+fn holes() {
+    // A small square kernel shrinks the hole of a frame, and a large one fills it
+    let frame = vec![
+        vec![[0f64, 0.], [10., 0.], [10., 10.], [0., 10.]],
+        vec![[4., 4.], [4., 6.], [6., 6.], [6., 4.]],
+    ];
+    let kernel = |r: f64| [[-r, -r], [r, -r], [r, r], [-r, r]];
+    let sum = minkowski::minkowski_sum(&frame, &kernel(0.5));
+    assert_eq!(sum.len(), 2);
+    assert_close(area::signed_area(&sum), 121. - 1.);
+    let sum = minkowski::minkowski_sum(&frame, &kernel(1.5));
+    assert_eq!(sum.len(), 1);
+    assert_close(area::signed_area(&sum), 169.);
+}
+
+#[test]
+fn concave() {
+    // A point x is in the sum exactly when the kernel reflected and moved to x overlaps the shape
+    let shape = vec![
+        vec![[0f64, 0.], [8., 0.], [8., 8.], [5., 8.], [5., 2.], [3., 2.], [3., 8.], [0., 8.]],
+        vec![[1., 3.], [2., 3.], [2., 5.], [1., 5.]],
+    ];
+    let kernel = [[0f64, 0.], [1.5, 0.5], [0.5, 1.5]];
+    let sum = minkowski::minkowski_sum(&shape, &kernel);
+    let trapezoidation = sum.trapezoidize().expect("Trapezoidation failed");
+    for i in 0..24 {
+        for j in 0..24 {
+            let x = [-2. + i as f64 * 0.5 + 0.013, -2. + j as f64 * 0.5 + 0.017];
+            let reflected = vec![kernel.iter().map(|k| [x[0] - k[0], x[1] - k[1]]).collect::<Vec<_>>()];
+            let overlaps = !boolean::boolean(&shape, &reflected, BooleanOp::Intersection).is_empty();
+            assert_eq!(trapezoidation.contains_point(x).expect("Point location failed"), overlaps, "{x:?}");
+        }
+    }
+}
+
+#[test]
+fn degenerate() {
+    let square = vec![vec![[0f64, 0.], [2., 0.], [2., 2.], [0., 2.]]];
+    assert!(minkowski::minkowski_sum(&square, &Vec::<[f64; 2]>::new()).is_empty());
+
+    // A single point moves the shape
+    let moved = minkowski::minkowski_sum(&square, &[[1f64, 1.]]);
+    assert_close(area::signed_area(&moved), 4.);
+    assert!(moved[0].iter().all(|c| (1. ..=3.).contains(&c[0]) && (1. ..=3.).contains(&c[1])));
+
+    // A segment sweeps it
+    let swept = minkowski::minkowski_sum(&square, &[[0f64, 0.], [3., 0.], [1., 0.]]);
+    assert_eq!(swept.len(), 1);
+    assert_close(area::signed_area(&swept), 10.);
+}
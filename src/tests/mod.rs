@@ -9,4 +9,78 @@ mod regression;
 #[cfg(test)]
 mod triangulate;
 #[cfg(test)]
-mod format;
\ No newline at end of file
+mod format;
+#[cfg(test)]
+mod float_format;
+#[cfg(test)]
+mod intersection;
+#[cfg(test)]
+mod boolean;
+#[cfg(test)]
+mod interior_points;
+#[cfg(test)]
+mod mesh;
+#[cfg(test)]
+mod refinement;
+#[cfg(test)]
+mod hull;
+#[cfg(test)]
+mod validity;
+#[cfg(test)]
+mod repair;
+#[cfg(test)]
+mod winding;
+#[cfg(test)]
+mod area;
+#[cfg(test)]
+mod shapes;
+#[cfg(test)]
+mod stroke;
+#[cfg(test)]
+mod minkowski;
+#[cfg(all(test, feature = "text"))]
+mod text;
+#[cfg(test)]
+mod sdf;
+#[cfg(test)]
+mod label;
+#[cfg(test)]
+mod solid;
+#[cfg(test)]
+mod skeleton;
+#[cfg(test)]
+mod coverage;
+#[cfg(test)]
+mod preview;
+#[cfg(test)]
+mod buffers;
+#[cfg(all(test, feature = "std"))]
+mod tuning;
+#[cfg(all(test, feature = "trace"))]
+mod trace;
+#[cfg(all(test, feature = "random"))]
+mod random;
+#[cfg(all(test, feature = "robust"))]
+mod robust;
+#[cfg(all(test, feature = "num-rational"))]
+mod rational;
+#[cfg(all(test, feature = "approx"))]
+mod approx;
+#[cfg(all(test, feature = "serde"))]
+mod serde;
+#[cfg(all(test, feature = "_debugging"))]
+mod debug;
+#[cfg(all(test, feature = "fixed-f32-u32"))]
+mod fixed;
+#[cfg(all(test, feature = "geo"))]
+mod geo;
+#[cfg(all(test, feature = "geojson"))]
+mod geojson;
+#[cfg(all(test, feature = "executor"))]
+mod executor;
+#[cfg(test)]
+mod bvh;
+#[cfg(test)]
+mod containment;
+#[cfg(test)]
+mod components;
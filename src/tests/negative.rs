@@ -1,4 +1,6 @@
-use crate::{formats, Polygon, PolygonList};
+use std::convert::Infallible;
+
+use crate::{formats, ErrorStage, InternalError, Polygon, PolygonList, TrapezoidationError, TriangulationError};
 
 #[test]
 #[should_panic]
@@ -30,4 +32,33 @@ fn overlapping_polygons() {
     let polygon_a: Vec<[f32; 2]> = vec![[0., 0.], [0., 1.], [1., 1.], [1., 0.]];
     let polygon_b: Vec<[f32; 2]> = vec![[0.75, 0.25], [0.75, 0.75], [1.25, 0.75], [1.25, 0.25]];
     vec![polygon_a, polygon_b].triangulate(formats::IndexedFanFormat::new(&mut Vec::<Vec<_>>::new())).unwrap();
-}
\ No newline at end of file
+}
+
+#[test]
+fn error_geometry() {
+    let error = InternalError::new("Segment crosses another")
+        .with_segment([[1f64, 1.], [0., 0.]], [1, 2])
+        .with_polygon([[-1f64, 1.], [1., 1.], [0., 0.], [1., -1.]], 3)
+        .with_stage(ErrorStage::SegmentThreading)
+        // Only the innermost context is kept
+        .with_segment([[2f64, 2.], [3., 3.]], [0, 1])
+        .with_stage(ErrorStage::MonotoneSplit);
+    let geometry = error.geometry.as_ref().expect("No geometry");
+    assert_eq!(geometry.segment, Some([[1., 1.], [0., 0.]]));
+    assert_eq!(geometry.segment_vertices, Some([1, 2]));
+    assert_eq!(geometry.polygon.len(), 4);
+    assert_eq!(geometry.polygon_index, Some(3));
+    assert_eq!(error.stage, Some(ErrorStage::SegmentThreading));
+
+    let message = error.to_string();
+    assert!(message.starts_with("Segment crosses another\nduring segment threading\nwhile inserting the segment (1, 1) -> (0, 0) between vertices 1 and 2\nof polygon 3 with 4 vertices [(-1, 1), (1, 1), (0, 0), (1, -1)]\n"));
+
+    let error = TriangulationError::<Infallible>::TrapezoidationError(TrapezoidationError::InternalError(error));
+    let svg = error.to_svg().expect("No SVG");
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.contains(r#"<polygon points="-1,1 1,1 0,0 1,-1""#));
+    assert!(svg.contains(r#"<line x1="1" y1="1" x2="0" y2="0""#));
+
+    assert!(TriangulationError::<Infallible>::NoVertices.to_svg().is_none());
+    assert!(InternalError::new("No context").to_svg().is_none());
+}
@@ -0,0 +1,74 @@
+use std::f64::consts::TAU;
+
+use crate::{math, mesh::Mesh, preview};
+
+type Contours = Vec<Vec<[f64; 2]>>;
+
+fn circle(center: [f64; 2], radius: f64, n: usize) -> Vec<[f64; 2]> {
+    (0..n).map(|i| {
+        let (sin, cos) = (TAU * i as f64 / n as f64).sin_cos();
+        [center[0] + radius * cos, center[1] + radius * sin]
+    }).collect()
+}
+
+fn mesh_contains(mesh: &Mesh<[f64; 2]>, p: [f64; 2]) -> bool {
+    mesh.iter_triangles().any(|[a, b, c]| {
+        let side = |a: &[f64; 2], b: &[f64; 2]| (b[0] - a[0]) * (p[1] - a[1]) - (b[1] - a[1]) * (p[0] - a[0]);
+        side(a, b) > 0. && side(b, c) > 0. && side(c, a) > 0.
+    })
+}
+
+fn boundary_distance(contours: &Contours, p: [f64; 2]) -> f64 {
+    contours.iter().flat_map(|ring| (0..ring.len()).map(move |k| {
+        let (a, b) = (ring[k], ring[(k + 1) % ring.len()]);
+        let d = [b[0] - a[0], b[1] - a[1]];
+        let t = (((p[0] - a[0]) * d[0] + (p[1] - a[1]) * d[1]) / (d[0] * d[0] + d[1] * d[1])).clamp(0., 1.);
+        (p[0] - a[0] - d[0] * t).hypot(p[1] - a[1] - d[1] * t)
+    })).fold(f64::INFINITY, f64::min)
+}
+
+#[test]
+fn error_bound() {
+    // A wavy disk with a hole, far more detailed than the grid
+    let outer: Vec<[f64; 2]> = circle([0., 0.], 1., 4000).into_iter().enumerate().map(|(i, [x, y])| {
+        let r = 100. + 2. * (i as f64 / 40.).sin() + 0.05 * (i as f64 * 1.7).sin();
+        [x * r, y * r]
+    }).collect();
+    let polygons: Contours = vec![outer, circle([20., 10.], 30., 1000).into_iter().rev().collect()];
+    let (grid, tolerance) = (0.25, 0.5);
+    let preview = preview::preview(&polygons, grid, tolerance).expect("Triangulation failed");
+
+    assert!(preview.error > 0. && preview.error <= grid * 2f64.sqrt() / 2. + tolerance);
+    assert!(preview.mesh.vertices.len() * 10 < 5000, "{} vertices", preview.mesh.vertices.len());
+    // Points further from the contours than the error are covered by the preview exactly when they are by the input
+    for i in 0..60 {
+        for j in 0..60 {
+            let p = [-110. + 220. * (i as f64 + 0.5) / 60., -110. + 220. * (j as f64 + 0.5) / 60.];
+            if boundary_distance(&polygons, p) > preview.error {
+                assert_eq!(mesh_contains(&preview.mesh, p), math::contours_contain(&polygons, p), "at {p:?}");
+            }
+        }
+    }
+}
+
+#[test]
+fn exact() {
+    // Without snapping, and with no tolerance, only the vertices in the middle of straight edges are dropped
+    let polygons = vec![vec![[0f64, 0.], [1., 0.], [2., 0.], [2., 2.], [1., 2.], [0., 2.]]];
+    let preview = preview::preview(&polygons, 0., 0.).expect("Triangulation failed");
+    assert_eq!(preview.error, 0.);
+    assert_eq!(preview.mesh.vertices.len(), 4);
+    assert_eq!(preview.mesh.triangles.len(), 2);
+}
+
+#[test]
+fn collapse() {
+    // Features smaller than a grid cell vanish, and the error covers them
+    let polygons = vec![
+        vec![[0f64, 0.], [10., 0.], [10., 10.], [0., 10.]],
+        vec![[20.1, 20.1], [20.3, 20.1], [20.2, 20.3]],
+    ];
+    let preview = preview::preview(&polygons, 1., 0.).expect("Triangulation failed");
+    assert_eq!(preview.mesh.triangles.len(), 2);
+    assert!(preview.error >= 0.3f64.hypot(0.2) - 1e-12);
+}
@@ -0,0 +1,33 @@
+use rand::{SeedableRng, rngs::StdRng};
+
+use crate::{formats, random::generate_random_simple_polygon, ListFormat, Polygon, validity};
+
+#[test]
+fn random_polygons() {
+    let generate = |seed: u64, n: usize, spikiness: f64, irregularity: f64| {
+        generate_random_simple_polygon::<f64, _>(&mut StdRng::seed_from_u64(seed), n, spikiness, irregularity).expect("Not a polygon")
+    };
+    assert!(generate_random_simple_polygon::<f64, _>(&mut StdRng::seed_from_u64(0), 2, 0.5, 0.5).is_none());
+
+    // The same seed gives the same polygon
+    assert_eq!(generate(1, 50, 0.5, 0.5), generate(1, 50, 0.5, 0.5));
+    assert_ne!(generate(1, 50, 0.5, 0.5), generate(2, 50, 0.5, 0.5));
+
+    // Without spikiness, every vertex is on the unit circle
+    assert!(generate(3, 20, 0., 1.).iter().all(|[x, y]| (x.hypot(*y) - 1.).abs() < 1e-12));
+
+    for (seed, (spikiness, irregularity)) in [(0., 0.), (1., 0.), (0., 1.), (1., 1.), (0.3, 0.7)].into_iter().enumerate() {
+        let polygon = generate(seed as u64, 200, spikiness, irregularity);
+        assert_eq!(polygon.len(), 200);
+        assert!(validity::is_simple(&vec![polygon.clone()]));
+        let area = (0..polygon.len()).map(|i| {
+            let ([x0, y0], [x1, y1]) = (polygon[i], polygon[(i + 1) % polygon.len()]);
+            x0 * y1 - x1 * y0
+        }).sum::<f64>();
+        assert!(area > 0.);
+
+        let mut triangles = Vec::<[usize; 3]>::new();
+        polygon.triangulate(formats::IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+        assert_eq!(triangles.len(), 198);
+    }
+}
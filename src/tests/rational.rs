@@ -0,0 +1,28 @@
+use num_traits::One;
+
+use crate::{formats, BigRational, DefaultKernel, GeometryKernel, ListFormat, Orientation, Polygon};
+
+fn rational(numerator: i64, denominator: i64) -> BigRational {
+    BigRational::new(numerator.into(), denominator.into())
+}
+
+#[test]
+fn big_rational() {
+    // A sliver whose apex is a tiny fraction off the base, far beyond what a float can distinguish at this offset
+    let offset = BigRational::from_integer(1_000_000_000_000_000_000i64.into());
+    let tiny = rational(1, 1_000_000_000_000_000_000);
+    let polygon = vec![
+        [offset.clone(), offset.clone()],
+        [offset.clone() + BigRational::one(), offset.clone()],
+        [offset.clone() + BigRational::one(), offset.clone() + BigRational::one()],
+        [offset.clone() + rational(1, 2), offset.clone() + tiny],
+        [offset.clone(), offset.clone() + BigRational::one()],
+    ];
+
+    let mut triangles = Vec::<[usize; 3]>::new();
+    polygon.triangulate(formats::IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(triangles.len(), 3);
+    for [a, b, c] in triangles {
+        assert_eq!(<DefaultKernel as GeometryKernel<BigRational>>::orientation(&polygon[a], &polygon[b], &polygon[c]), Orientation::Left);
+    }
+}
@@ -0,0 +1,66 @@
+use crate::refinement::{self, RefinedIndex, RefinementOptions};
+
+fn positions(polygons: &[Vec<[f64; 2]>], refinement: &refinement::Refinement<[usize; 2], f64>) -> Vec<[[f64; 2]; 3]> {
+    refinement.triangles.iter().map(|t| t.map(|vi| match vi {
+        RefinedIndex::Original([p, v]) => polygons[p][v],
+        RefinedIndex::Steiner(i) => refinement.steiner_points[i],
+    })).collect()
+}
+
+fn area([a, b, c]: [[f64; 2]; 3]) -> f64 {
+    ((b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1])) / 2.
+}
+
+fn smallest_angle(t: [[f64; 2]; 3]) -> f64 {
+    (0..3).map(|k| {
+        let (p, q, r) = (t[k], t[(k + 1) % 3], t[(k + 2) % 3]);
+        let (u, v) = ([q[0] - p[0], q[1] - p[1]], [r[0] - p[0], r[1] - p[1]]);
+        (u[0] * v[1] - u[1] * v[0]).atan2(u[0] * v[0] + u[1] * v[1])
+    }).fold(f64::INFINITY, f64::min)
+}
+
+#[test]
+fn refine() {
+    // A square with a square hole
+    let polygons = vec![
+        vec![[0f64, 0.], [10., 0.], [10., 10.], [0., 10.]],
+        vec![[4., 4.], [4., 6.], [6., 6.], [6., 4.]],
+    ];
+    let options = RefinementOptions { max_area: Some(2.), min_angle: Some(0.35), max_steiner_points: 1000 };
+    let refinement = refinement::refine(&polygons, &options).expect("Triangulation failed");
+    assert!(refinement.complete);
+    assert!(!refinement.steiner_points.is_empty());
+
+    let triangles = positions(&polygons, &refinement);
+    for &t in &triangles {
+        assert!(area(t) > 0. && area(t) <= 2.);
+        assert!(smallest_angle(t) >= 0.35);
+    }
+    // The outline is kept, so the area is too
+    assert!((triangles.iter().map(|t| area(*t)).sum::<f64>() - 96.).abs() < 1e-9);
+
+    // Every original vertex is still used
+    for (p, polygon) in polygons.iter().enumerate() {
+        for v in 0..polygon.len() {
+            assert!(refinement.triangles.iter().flatten().any(|vi| *vi == RefinedIndex::Original([p, v])));
+        }
+    }
+}
+
+#[test]
+fn refine_limit() {
+    // The corner at the origin is sharper than the minimum angle, so refinement cannot complete
+    let polygons = vec![vec![[0f64, 0.], [10., 1.], [10., 2.]]];
+    let options = RefinementOptions { max_area: None, min_angle: Some(0.3), max_steiner_points: 50 };
+    let refinement = refinement::refine(&polygons, &options).expect("Triangulation failed");
+    assert!(!refinement.complete);
+    assert_eq!(refinement.steiner_points.len(), 50);
+    assert!((positions(&polygons, &refinement).into_iter().map(area).sum::<f64>() - 5.).abs() < 1e-9);
+
+    // Without limits, nothing is inserted
+    let options = RefinementOptions { max_area: None, min_angle: None, max_steiner_points: 50 };
+    let refinement = refinement::refine(&polygons, &options).expect("Triangulation failed");
+    assert!(refinement.complete);
+    assert!(refinement.steiner_points.is_empty());
+    assert_eq!(refinement.triangles.len(), 1);
+}
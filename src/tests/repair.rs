@@ -0,0 +1,69 @@
+use crate::{formats, repair::{self, FillRule}, ListFormat, PolygonList};
+
+fn area(contours: &[Vec<[f64; 2]>]) -> f64 {
+    contours.iter().map(|contour| {
+        (0..contour.len()).map(|i| {
+            let (c0, c1) = (contour[i], contour[(i + 1) % contour.len()]);
+            c0[0] * c1[1] - c1[0] * c0[1]
+        }).sum::<f64>() / 2.
+    }).sum()
+}
+
+#[test]
+fn bowtie() {
+    // The left half winds counter-clockwise and the right half clockwise
+    let polygons = vec![vec![[0f64, 0.], [2., 2.], [2., 0.], [0., 2.]]];
+
+    let positive = repair::repair(&polygons, FillRule::Positive);
+    assert_eq!(positive.len(), 1);
+    assert!(positive[0].iter().all(|c| c[0] <= 1.));
+    assert!((area(&positive) - 1.).abs() < 1e-9);
+
+    let negative = repair::repair(&polygons, FillRule::Negative);
+    assert_eq!(negative.len(), 1);
+    assert!(negative[0].iter().all(|c| c[0] >= 1.));
+    assert!((area(&negative) - 1.).abs() < 1e-9);
+
+    let nonzero = repair::repair(&polygons, FillRule::NonZero);
+    assert_eq!(nonzero.len(), 2);
+    assert!((area(&nonzero) - 2.).abs() < 1e-9);
+}
+
+#[test]
+fn overlapping() {
+    let polygons = vec![
+        vec![[0f64, 0.], [2., 0.], [2., 2.], [0., 2.]],
+        vec![[1., 1.], [3., 1.], [3., 3.], [1., 3.]],
+    ];
+    let union = repair::repair(&polygons, FillRule::NonZero);
+    assert_eq!(union.len(), 1);
+    assert!((area(&union) - 7.).abs() < 1e-9);
+    let mut triangles = Vec::<[usize; 2]>::new();
+    union.triangulate(formats::IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(triangles.len(), 6 * 3);
+
+    // The overlap is wound twice, so it is a hole by the even-odd rule, leaving two pieces which touch at the crossings
+    let even_odd = repair::repair(&polygons, FillRule::EvenOdd);
+    assert_eq!(even_odd.len(), 2);
+    assert!((area(&even_odd) - 6.).abs() < 1e-9);
+
+    // A square drawn twice over itself
+    let doubled = vec![vec![[0f64, 0.], [1., 0.], [1., 1.], [0., 1.]]; 2];
+    assert_eq!(repair::repair(&doubled, FillRule::NonZero), vec![vec![[0., 0.], [1., 0.], [1., 1.], [0., 1.]]]);
+    assert!(repair::repair(&doubled, FillRule::EvenOdd).is_empty());
+}
+
+#[test]
+fn touching_at_a_point() {
+    // Two squares touching at a corner, drawn as one ring passing through the corner twice
+    let polygons = vec![vec![[0f64, 0.], [1., 0.], [1., 1.], [2., 1.], [2., 2.], [1., 2.], [1., 1.], [0., 1.]]];
+    for fill_rule in [FillRule::NonZero, FillRule::EvenOdd] {
+        let repaired = repair::repair(&polygons, fill_rule);
+        assert_eq!(repaired.len(), 2);
+        assert!(repaired.iter().all(|ring| ring.len() == 4));
+        assert!((area(&repaired) - 2.).abs() < 1e-9);
+        let mut triangles = Vec::<[usize; 2]>::new();
+        repaired.triangulate(formats::IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+        assert_eq!(triangles.len(), 4 * 3);
+    }
+}
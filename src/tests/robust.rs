@@ -0,0 +1,64 @@
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+use crate::{formats, GeometryKernel, ListFormat, Orientation, Polygon, RobustKernel};
+
+// The orientation computed with integers, for coordinates which are multiples of 2^-60 below 1
+fn exact_orientation(a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> Orientation {
+    let scale = |p: [f64; 2]| p.map(|x| {
+        let scaled = x * 2f64.powi(60);
+        assert_eq!(scaled, scaled.trunc());
+        scaled as i128
+    });
+    let ([ax, ay], [bx, by], [cx, cy]) = (scale(a), scale(b), scale(c));
+    match ((bx - ax) * (cy - ay) - (by - ay) * (cx - ax)).signum() {
+        1 => Orientation::Left,
+        -1 => Orientation::Right,
+        _ => Orientation::Collinear,
+    }
+}
+
+fn naive_orientation(a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> Orientation {
+    let det = (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0]);
+    if det > 0. { Orientation::Left } else if det < 0. { Orientation::Right } else { Orientation::Collinear }
+}
+
+#[test]
+fn orientation() {
+    let mut rng = StdRng::seed_from_u64(5);
+    let mut naive_errors = 0;
+    for _ in 0..10000 {
+        let a = [rng.gen_range(0.5..1.), rng.gen_range(0.5..1.)];
+        let b = [rng.gen_range(0.5..1.), rng.gen_range(0.5..1.)];
+        // A point on the line through `a` and `b`, rounded, then moved by a few units in the last place
+        let t: f64 = rng.gen();
+        let c = [a[0] + t * (b[0] - a[0]), a[1] + t * (b[1] - a[1])]
+            .map(|x| f64::from_bits((x.to_bits() as i64 + rng.gen_range(-2..=2)) as u64));
+
+        let expected = exact_orientation(a, b, c);
+        assert_eq!(<RobustKernel as GeometryKernel<f64>>::orientation(&a, &b, &c), expected);
+        let [a32, b32, c32] = [a, b, c].map(|p| p.map(|x| x as f32));
+        assert_eq!(<RobustKernel as GeometryKernel<f32>>::orientation(&a32, &b32, &c32), exact_orientation(a32.map(f64::from), b32.map(f64::from), c32.map(f64::from)));
+        if naive_orientation(a, b, c) != expected {
+            naive_errors += 1;
+        }
+    }
+    // Without exact arithmetic, some of these would be misclassified
+    assert!(naive_errors > 0);
+}
+
+#[test]
+fn nearly_collinear() {
+    // Vertices rounded onto a shallow line, which zigzag by less than an f32 can resolve in a product
+    let mut polygon = (0..=50).map(|i| {
+        let x = i as f32 / 50.;
+        [x, x * 0.3 + 0.1]
+    }).collect::<Vec<_>>();
+    polygon.push([0.5, 1.]);
+
+    let mut triangles = Vec::<[usize; 3]>::new();
+    polygon.triangulate_with_kernel::<RobustKernel, _>(formats::IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(triangles.len(), polygon.len() - 2);
+    for [a, b, c] in triangles {
+        assert_ne!(RobustKernel::orientation(&polygon[a], &polygon[b], &polygon[c]), Orientation::Right);
+    }
+}
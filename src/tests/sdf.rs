@@ -0,0 +1,65 @@
+use crate::{sdf::{self, SdfOptions}, math};
+
+use super::util;
+
+// The distance from `c` to the boundary of the box between `min` and `max`
+fn box_distance(c: [f64; 2], min: f64, max: f64) -> f64 {
+    let dx = (min - c[0]).max(c[0] - max).max(0.);
+    let dy = (min - c[1]).max(c[1] - max).max(0.);
+    if dx == 0. && dy == 0. {
+        (c[0] - min).min(max - c[0]).min(c[1] - min).min(max - c[1])
+    } else {
+        dx.hypot(dy)
+    }
+}
+
+#[test]
+fn distances() {
+    // A square with a square hole
+    let polygons = vec![
+        vec![[0f64, 0.], [10., 0.], [10., 10.], [0., 10.]],
+        vec![[4., 4.], [4., 6.], [6., 6.], [6., 4.]],
+    ];
+    let options = SdfOptions { width: 24, height: 24, bounds: Some([[-1., -1.], [11., 11.]]), spread: 2. };
+    let field = sdf::distance_field(&polygons, &options).expect("Trapezoidation failed");
+    assert_eq!(field.distances.len(), 24 * 24);
+
+    for y in 0..24 {
+        for x in 0..24 {
+            let c = field.texel_center(x, y);
+            let inside = math::contours_contain(&polygons, c);
+            let distance = box_distance(c, 0., 10.).min(box_distance(c, 4., 6.));
+            let expected = distance.min(2.) * if inside { 1. } else { -1. };
+            assert!((field.get(x, y) - expected).abs() < 1e-9, "Texel {x}, {y} at {c:?} is {} instead of {expected}", field.get(x, y));
+        }
+    }
+
+    let bytes = field.to_bytes();
+    // -1.06 and 1.75 from the edge, with the spread of 2 mapped to 128
+    assert_eq!(bytes[0], 60);
+    assert_eq!(bytes[5 * 24 + 5], 240);
+    assert_eq!(sdf::DistanceField { distances: vec![-3., 0., 2.], ..field }.to_bytes(), vec![0, 128, 255]);
+}
+
+#[test]
+fn default_bounds() {
+    for polygon in util::polygon::all() {
+        let polygons = vec![polygon];
+        let field = sdf::distance_field(&polygons, &SdfOptions { width: 32, height: 32, bounds: None, spread: 0.1 }).expect("Trapezoidation failed");
+        let [min, max] = math::bounding_box(polygons[0].iter()).unwrap();
+        assert_eq!(field.bounds, [[min[0] - 0.1, min[1] - 0.1], [max[0] + 0.1, max[1] + 0.1]]);
+        // Every texel's sign agrees with a containment test
+        for y in 0..32 {
+            for x in 0..32 {
+                let c = field.texel_center(x, y);
+                let d = field.get(x, y);
+                if d.abs() > 1e-9 {
+                    assert_eq!(d > 0., math::contours_contain(&polygons, c), "Texel {x}, {y} at {c:?}");
+                }
+            }
+        }
+    }
+
+    let empty = sdf::distance_field(&Vec::<Vec<[f64; 2]>>::new(), &SdfOptions { width: 2, height: 2, bounds: None, spread: 1. }).expect("Trapezoidation failed");
+    assert_eq!(empty.distances, vec![-1.; 4]);
+}
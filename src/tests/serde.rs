@@ -0,0 +1,54 @@
+use serde::de::DeserializeSeed;
+
+use crate::{formats, ListFormat, PolygonList, TrapezoidationSeed};
+
+fn star(points: usize) -> Vec<[f64; 2]> {
+    (0..points * 2).map(|i| {
+        let angle = i as f64 * core::f64::consts::PI / points as f64;
+        let radius = if i % 2 == 0 { 10. } else { 4. };
+        [radius * angle.cos(), radius * angle.sin()]
+    }).collect()
+}
+
+#[test]
+fn round_trip() {
+    // Enough vertices to span several storage pages
+    let polygons = vec![star(150), vec![[-1., -1.], [-1., 1.], [1., 1.], [1., -1.]]];
+    let trapezoidation = polygons.trapezoidize().expect("Trapezoidation failed").with_seed(Some(3));
+    let json = serde_json::to_string(&trapezoidation).expect("Serialization failed");
+    assert!(json.ends_with(r#""seed":3}"#));
+
+    let loaded = TrapezoidationSeed::new(&polygons).deserialize(&mut serde_json::Deserializer::from_str(&json)).expect("Deserialization failed");
+    assert_eq!(serde_json::to_string(&loaded).expect("Serialization failed"), json);
+
+    let mut triangles = Vec::<[usize; 2]>::new();
+    trapezoidation.triangulate(formats::IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+    let mut loaded_triangles = Vec::<[usize; 2]>::new();
+    loaded.triangulate(formats::IndexedListFormat::new(&mut loaded_triangles).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(triangles, loaded_triangles);
+
+    for point in [[0., 0.], [5., 0.], [0., 9.], [20., 0.]] {
+        assert_eq!(loaded.contains_point(point).expect("Point location failed"), trapezoidation.contains_point(point).expect("Point location failed"));
+    }
+}
+
+#[test]
+fn different_polygons() {
+    let polygons = vec![star(5)];
+    let json = serde_json::to_string(&polygons.trapezoidize().expect("Trapezoidation failed")).expect("Serialization failed");
+
+    let moved = vec![star(5).into_iter().map(|[x, y]| [x + 1., y]).collect::<Vec<_>>()];
+    assert!(TrapezoidationSeed::new(&moved).deserialize(&mut serde_json::Deserializer::from_str(&json)).is_err());
+    let more = vec![star(6)];
+    assert!(TrapezoidationSeed::new(&more).deserialize(&mut serde_json::Deserializer::from_str(&json)).is_err());
+}
+
+#[test]
+fn broken_links() {
+    let polygons = vec![vec![[0f64, 0.], [1., 0.], [1., 1.], [0., 1.]]];
+    let trapezoidation = polygons.trapezoidize().expect("Trapezoidation failed");
+    let mut value = serde_json::to_value(&trapezoidation).expect("Serialization failed");
+    value["ss"][0]["ni_max"] = serde_json::json!(1000);
+
+    assert!(TrapezoidationSeed::new(&polygons).deserialize(value).is_err());
+}
@@ -0,0 +1,69 @@
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use crate::{mesh::Mesh, shapes, validity};
+
+use super::util::assert_close;
+
+fn area(polygons: &Vec<Vec<[f64; 2]>>) -> f64 {
+    assert!(validity::is_valid(polygons), "{:?}", validity::validate(polygons));
+    let mesh = Mesh::from_polygons(polygons).expect("Triangulation failed");
+    mesh.iter_triangles().map(Mesh::<[f64; 2]>::area_of).sum()
+}
+
+#[test]
+fn rect() {
+    assert_close(area(&shapes::rect([1., 1.], [4., 3.])), 6.);
+    assert!(shapes::rect([1., 1.], [1., 3.]).is_empty());
+}
+
+#[test]
+fn rounded_rect() {
+    // Square corners produce a plain rectangle
+    let square = shapes::rounded_rect([0., 0.], [4., 2.], [0.; 4], 8);
+    assert_eq!(square, vec![vec![[4., 0.], [4., 2.], [0., 2.], [0., 0.]]]);
+
+    let polygons = shapes::rounded_rect([0., 0.], [4., 2.], [0., 1., 0., 0.5], 16);
+    assert_eq!(polygons[0].len(), 17 + 17 + 1 + 1);
+    let expected = 8. - (1. - PI / 4.) - (0.25 - PI / 16.);
+    assert!((area(&polygons) - expected).abs() < 1e-2);
+
+    // Radii which do not fit are scaled down, so the arcs meet on the short sides
+    let stadium = shapes::rounded_rect([0., 0.], [4., 2.], [2.; 4], 16);
+    assert_eq!(stadium[0].len(), 4 * 17 - 2);
+    assert!((area(&stadium) - (4. + PI)).abs() < 1e-2);
+}
+
+#[test]
+fn circles() {
+    let circle = shapes::circle([1., 2.], 1., 4);
+    assert_eq!(circle[0].len(), 4);
+    assert_close(area(&circle), 2.);
+    assert_eq!(shapes::circle([0., 0.], 1., 1)[0].len(), 3);
+
+    let ellipse = shapes::ellipse([0., 0.], [2., 1.], 256);
+    assert!((area(&ellipse) - 2. * PI).abs() < 1e-2);
+}
+
+#[test]
+fn ring() {
+    let ring = shapes::ring([0., 0.], 1., 2., 64);
+    assert_eq!(ring.len(), 2);
+    let expected = shapes::circle([0., 0.], 2., 64);
+    assert_close(area(&ring), area(&expected) - area(&shapes::circle([0., 0.], 1., 64)));
+
+    assert_eq!(shapes::ring([0., 0.], 0., 2., 64), expected);
+    assert_eq!(shapes::ring([0., 0.], 3., 2., 64), expected);
+}
+
+#[test]
+fn pie() {
+    let quarter = shapes::pie([0., 0.], 1., 0., FRAC_PI_2, 1);
+    assert_eq!(quarter[0].len(), 3);
+    assert_close(area(&quarter), 0.5);
+
+    let clockwise = shapes::pie([0., 0.], 2., PI, -3. * FRAC_PI_2, 128);
+    assert!((area(&clockwise) - 3. * PI).abs() < 1e-2);
+
+    assert_eq!(shapes::pie([0., 0.], 1., 0., 2. * PI, 16), shapes::circle([0., 0.], 1., 16));
+    assert!(shapes::pie([0., 0.], 1., 0., 0., 16).is_empty());
+}
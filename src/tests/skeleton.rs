@@ -0,0 +1,107 @@
+use std::f64::consts::PI;
+
+use crate::{math, skeleton::{self, RoofStyle}, solid::Solid};
+
+use super::util::assert_close;
+
+// The area of the roof seen from above, checking that no triangle is upside down
+fn plan_area(roof: &Solid<f64>) -> f64 {
+    roof.triangles.iter().map(|t| {
+        let ring = t.map(|vi| [roof.positions[vi][0], roof.positions[vi][1]]);
+        let area = math::signed_area_2(&ring) / 2.;
+        assert!(area > -1e-9, "Triangle {ring:?} is upside down");
+        area
+    }).sum()
+}
+
+fn check(polygons: &Vec<Vec<[f64; 2]>>, area: f64, peak: f64) {
+    let skeleton = skeleton::straight_skeleton(polygons);
+    assert_eq!(skeleton.faces.len(), polygons.iter().map(Vec::len).sum::<usize>());
+    assert_close(skeleton.nodes.iter().map(|n| n.time).fold(0., f64::max), peak);
+    for face in &skeleton.faces {
+        let ring: Vec<[f64; 2]> = face.iter().map(|&ni| skeleton.nodes[ni].position).collect();
+        assert!(math::signed_area_2(&ring) > 0., "Face {ring:?} is not counter-clockwise");
+    }
+    let roof = skeleton::generate_roof(polygons, RoofStyle::Hipped, |t| t).expect("Triangulation failed");
+    assert_close(plan_area(&roof), area);
+}
+
+#[test]
+fn convex() {
+    let square = vec![vec![[0f64, 0.], [2., 0.], [2., 2.], [0., 2.]]];
+    let skeleton = skeleton::straight_skeleton(&square);
+    assert_eq!(skeleton.nodes.len(), 5);
+    assert_eq!(skeleton.arcs.len(), 4);
+    assert!(skeleton.faces.iter().all(|face| face.len() == 3 && face[2] == 4));
+    check(&square, 4., 1.);
+
+    let rectangle = vec![vec![[0f64, 0.], [0., 2.], [4., 2.], [4., 0.]]];
+    let skeleton = skeleton::straight_skeleton(&rectangle);
+    assert_eq!(skeleton.arcs.len(), 5);
+    check(&rectangle, 8., 1.);
+}
+
+#[test]
+fn reflex() {
+    let l = vec![vec![[0f64, 0.], [4., 0.], [4., 2.], [2., 2.], [2., 4.], [0., 4.]]];
+    check(&l, 12., 1.);
+    // The notch splits the wavefront in two. Each half peaks at the center of the circle touching its bottom, outer side
+    // and the side of the notch
+    let notched = vec![vec![[0f64, 0.], [6., 0.], [6., 4.], [4., 4.], [3., 2.], [2., 4.], [0., 4.]]];
+    check(&notched, 22., 6. - 2. * 5f64.sqrt());
+
+    // Every corner of a plus meets at the center at once
+    let plus = vec![vec![[2f64, 0.], [4., 0.], [4., 2.], [6., 2.], [6., 4.], [4., 4.], [4., 6.], [2., 6.], [2., 4.], [0., 4.], [0., 2.], [2., 2.]]];
+    check(&plus, 20., 1.);
+
+    // A star peaks at the distance from its center to its edges
+    let star: Vec<[f64; 2]> = (0..10).map(|k| {
+        let angle = PI * k as f64 / 5.;
+        let radius = if k % 2 == 0 { 5. } else { 2. };
+        [radius * angle.cos(), radius * angle.sin()]
+    }).collect();
+    let [a, b] = [star[0], star[1]];
+    let peak = (a[0] * b[1] - a[1] * b[0]).abs() / (b[0] - a[0]).hypot(b[1] - a[1]);
+    let area = math::signed_area_2(&star) / 2.;
+    check(&vec![star], area, peak);
+}
+
+#[test]
+fn holes() {
+    let courtyard = vec![
+        vec![[0f64, 0.], [10., 0.], [10., 6.], [0., 6.]],
+        vec![[2., 2.], [4., 2.], [4., 4.], [2., 4.]],
+    ];
+    // The widest part is the 6x6 square to the right of the courtyard
+    check(&courtyard, 56., 3.);
+
+    // Around a centered courtyard, the whole wavefront collapses onto a ring of ridges at once
+    let centered = vec![
+        vec![[0f64, 0.], [6., 0.], [6., 6.], [0., 6.]],
+        vec![[2., 2.], [4., 2.], [4., 4.], [2., 4.]],
+    ];
+    check(&centered, 32., 1.);
+}
+
+#[test]
+fn roofs() {
+    let rectangle = vec![vec![[0f64, 0.], [4., 0.], [4., 2.], [0., 2.]]];
+    let hipped = skeleton::generate_roof(&rectangle, RoofStyle::Hipped, |t| 3. + t * 0.5).expect("Triangulation failed");
+    assert_eq!(hipped.triangles.len(), 6);
+    assert!(hipped.positions.contains(&[1., 1., 3.5]) && hipped.positions.contains(&[0., 0., 3.]));
+
+    // The gables stand upright, facing out of the ends
+    let gabled = skeleton::generate_roof(&rectangle, RoofStyle::Gabled, |t| t).expect("Triangulation failed");
+    assert_eq!(gabled.triangles.len(), 6);
+    let normals = gabled.face_normals();
+    assert!(normals.contains(&[-1., 0., 0.]) && normals.contains(&[1., 0., 0.]));
+    assert_close(plan_area(&gabled), 8.);
+
+    // A square has no ridge to extend, so it keeps its hips
+    let square = vec![vec![[0f64, 0.], [2., 0.], [2., 2.], [0., 2.]]];
+    assert_eq!(
+        skeleton::generate_roof(&square, RoofStyle::Gabled, |t| t).expect("Triangulation failed"),
+        skeleton::generate_roof(&square, RoofStyle::Hipped, |t| t).expect("Triangulation failed"),
+    );
+}
+
@@ -0,0 +1,147 @@
+use std::f64::consts::PI;
+
+use crate::solid::{self, BevelOptions, BevelProfile, Solid};
+
+use super::util::assert_close;
+
+fn volume(solid: &Solid<f64>) -> f64 {
+    solid.triangles.iter().map(|t| {
+        let [a, b, c] = t.map(|vi| solid.positions[vi]);
+        (a[0] * (b[1] * c[2] - b[2] * c[1]) - a[1] * (b[0] * c[2] - b[2] * c[0]) + a[2] * (b[0] * c[1] - b[1] * c[0])) / 6.
+    }).sum()
+}
+
+#[test]
+fn extrude() {
+    // A square with a hole, given clockwise so that the holes are counter-clockwise
+    let polygons = vec![
+        vec![[0f64, 0.], [0., 4.], [4., 4.], [4., 0.]],
+        vec![[1., 1.], [3., 1.], [3., 3.], [1., 3.]],
+    ];
+    let solid = solid::extrude(&polygons, 2.).expect("Triangulation failed");
+    assert_eq!(solid.positions.len(), 16);
+    assert_eq!(solid.triangles.len(), 2 * 8 + 2 * 8);
+    assert_close(volume(&solid), 12. * 2.);
+
+    // Every triangle faces away from the middle of the wall it belongs to
+    let centroids = solid.centroids();
+    for (c, n) in centroids.iter().zip(solid.face_normals()) {
+        if n[2] != 0. {
+            assert_eq!(n[2], if c[2] == 0. { -1. } else { 1. });
+        } else {
+            let r = (c[0] - 2.).abs().max((c[1] - 2.).abs());
+            let outward = [(c[0] - 2.) / r, (c[1] - 2.) / r];
+            let facing = n[0] * outward[0].trunc() + n[1] * outward[1].trunc();
+            assert_eq!(facing, if r == 2. { 1. } else { -1. });
+        }
+    }
+
+    // Extruding downwards still faces outwards
+    let down = solid::extrude(&polygons, -2.).expect("Triangulation failed");
+    assert_close(volume(&down), volume(&solid));
+    assert!(down.face_normals().iter().zip(down.centroids()).all(|(n, c)| n[2] == 0. || n[2] == if c[2] == 0. { 1. } else { -1. }));
+}
+
+#[test]
+fn normals() {
+    let solid = Solid { positions: vec![[0f64, 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 0.]], triangles: vec![[0, 1, 2], [0, 3, 1]] };
+    assert_eq!(solid.face_normals(), vec![[0., 0., 1.], [0., 0., 0.]]);
+    assert_eq!(solid.centroids()[0], [1. / 3., 1. / 3., 0.]);
+
+    // A regular prism with enough sides is shaded smoothly around its walls, but not across the rim
+    let sides = 32;
+    let polygon: Vec<[f64; 2]> = (0..sides).map(|k| {
+        let angle = 2. * PI * k as f64 / sides as f64;
+        [angle.cos(), angle.sin()]
+    }).collect();
+    let prism = solid::extrude(&vec![polygon], 1.).expect("Triangulation failed");
+
+    let smooth = prism.vertex_normals(PI / 4.);
+    assert_eq!(smooth.positions.len(), 2 * sides * 2);
+    assert_eq!(smooth.triangles.len(), prism.triangles.len());
+    for (p, n) in smooth.positions.iter().zip(&smooth.normals) {
+        assert_close(n[0] * n[0] + n[1] * n[1] + n[2] * n[2], 1.);
+        if n[2] == 0. {
+            // The average of the walls on either side points straight out
+            assert_close(n[0], p[0]);
+            assert_close(n[1], p[1]);
+        }
+    }
+
+    // Without smoothing every vertex takes the normal of its triangle
+    let flat = prism.vertex_normals(0.);
+    for (t, n) in flat.triangles.iter().zip(prism.face_normals()) {
+        for vi in t {
+            assert!((0..3).all(|k| (flat.normals[*vi][k] - n[k]).abs() < 1e-9));
+        }
+    }
+    assert_eq!(prism.vertex_normals(PI).positions.len(), prism.positions.len());
+}
+
+#[test]
+fn extrude_beveled() {
+    let chamfer = BevelOptions { width: 1., depth: 1., profile: BevelProfile::Chamfer, segments: 8 };
+    let square = vec![vec![[0f64, 0.], [4., 0.], [4., 4.], [0., 4.]]];
+    let solid = solid::extrude_beveled(&square, 3., &chamfer).expect("Triangulation failed");
+    // Two frustums from 2x2 to 4x4 around a 4x4 slab
+    assert_close(volume(&solid), 16. + 2. * (4. + 16. + 8.) / 3.);
+
+    // Holes grow as the caps shrink, so that the area of the cross-section falls linearly over the bevel
+    let polygons = vec![
+        vec![[0f64, 0.], [6., 0.], [6., 6.], [0., 6.]],
+        vec![[2., 2.], [2., 4.], [4., 4.], [4., 2.]],
+    ];
+    let options = BevelOptions { width: 0.5, depth: 0.5, ..chamfer };
+    let solid = solid::extrude_beveled(&polygons, 2., &options).expect("Triangulation failed");
+    assert!(solid.positions.contains(&[0.5, 0.5, 2.]) && solid.positions.contains(&[1.5, 1.5, 2.]));
+    assert_close(volume(&solid), 32. + 2. * 0.5 * (32. + 16.) / 2.);
+    let down = solid::extrude_beveled(&polygons, -2., &options).expect("Triangulation failed");
+    assert_close(volume(&down), volume(&solid));
+
+    // A bevel deeper than half the extrusion leaves no walls
+    let thin = solid::extrude_beveled(&square, 1., &chamfer).expect("Triangulation failed");
+    assert_eq!(thin.positions.len(), 3 * 4);
+    assert!(thin.positions.contains(&[0., 0., 0.5]));
+
+    // A round bevel has a layer for each segment, and is shaded smoothly from the caps to the walls, except at corners
+    let round = BevelOptions { profile: BevelProfile::Round, segments: 4, ..chamfer };
+    let solid = solid::extrude_beveled(&square, 3., &round).expect("Triangulation failed");
+    assert_eq!(solid.positions.len(), 2 * 5 * 4);
+    let shaded = solid.vertex_normals(PI / 4.);
+    assert_eq!(shaded.positions.len(), 2 * solid.positions.len());
+
+    // Without a bevel, the caps are only inset
+    let flat = solid::extrude_beveled(&square, 3., &BevelOptions { depth: 0., ..chamfer }).expect("Triangulation failed");
+    assert_close(volume(&flat), 3. * 4.);
+    let plain = solid::extrude_beveled(&square, 3., &BevelOptions { width: 0., ..chamfer }).expect("Triangulation failed");
+    assert_eq!(plain, solid::extrude(&square, 3.).expect("Triangulation failed"));
+}
+
+#[test]
+fn revolve() {
+    // A square profile revolved into a washer, with the volume of prisms over regular polygons
+    let profile = vec![vec![[1f64, 0.], [2., 0.], [2., 1.], [1., 1.]]];
+    let polygon_area = |sides: f64, sweep: f64| sides / 2. * (sweep / sides).sin();
+    let washer = solid::revolve(&profile, 24, 2. * PI).expect("Triangulation failed");
+    assert_eq!(washer.positions.len(), 24 * 4);
+    assert_eq!(washer.triangles.len(), 24 * 4 * 2);
+    assert_close(volume(&washer), polygon_area(24., 2. * PI) * (4. - 1.));
+
+    // A clockwise profile, revolved clockwise, and further than a full turn
+    let reversed: Vec<Vec<[f64; 2]>> = profile.iter().map(|ring| ring.iter().rev().copied().collect()).collect();
+    assert_close(volume(&solid::revolve(&reversed, 24, -3. * PI).expect("Triangulation failed")), volume(&washer));
+
+    // A partial turn is capped at both ends
+    let quarter = solid::revolve(&profile, 6, PI / 2.).expect("Triangulation failed");
+    assert_eq!(quarter.positions.len(), 7 * 4);
+    assert_eq!(quarter.triangles.len(), 6 * 4 * 2 + 2 * 2);
+    assert_close(volume(&quarter), polygon_area(6., PI / 2.) * (4. - 1.));
+    let normals = quarter.face_normals();
+    assert!(normals.contains(&[0., -1., 0.]) && normals.iter().any(|n| (n[0] + 1.).abs() < 1e-9));
+
+    // Vertices on the axis are shared
+    let cone = solid::revolve(&vec![vec![[0f64, 0.], [1., 0.], [0., 1.]]], 16, 2. * PI).expect("Triangulation failed");
+    assert_close(volume(&cone), polygon_area(16., 2. * PI) / 3.);
+    // The base and the rim are creased, and so is the tip, where opposite sides meet at right angles
+    assert_eq!(cone.vertex_normals(PI / 3.).positions.len(), 1 + 16 * 3);
+}
@@ -0,0 +1,159 @@
+use std::f64::consts::PI;
+
+use crate::{mesh::Mesh, stroke::{self, LineCap, LineJoin, StrokeOptions}};
+
+use super::util::assert_close;
+
+fn options(join: LineJoin) -> StrokeOptions<f64> {
+    StrokeOptions::default().with_line_join(join).with_miter_limit(2.)
+}
+
+fn area(mesh: &Mesh<[f64; 2]>) -> f64 {
+    mesh.iter_triangles().map(|t| {
+        let area = Mesh::<[f64; 2]>::area_of(t);
+        assert!(area >= 0., "Triangle {t:?} is clockwise");
+        area
+    }).sum()
+}
+
+#[test]
+fn dash_open() {
+    let line = [[0f64, 0.], [10., 0.]];
+    assert_eq!(stroke::dash(&line, false, &[2., 3.], 0.), vec![
+        vec![[0., 0.], [2., 0.]],
+        vec![[5., 0.], [7., 0.]],
+    ]);
+
+    // An odd pattern is repeated, and a negative offset shifts the pattern forward
+    assert_eq!(stroke::dash(&line, false, &[3.], -1.), vec![
+        vec![[1., 0.], [4., 0.]],
+        vec![[7., 0.], [10., 0.]],
+    ]);
+
+    // Dashes continue around corners
+    let corner = [[0f64, 0.], [2., 0.], [2., 2.]];
+    assert_eq!(stroke::dash(&corner, false, &[3., 1.], 0.), vec![vec![[0., 0.], [2., 0.], [2., 1.]]]);
+
+    // Patterns without length leave the line whole
+    assert_eq!(stroke::dash(&line, false, &[], 0.), vec![line.to_vec()]);
+    assert_eq!(stroke::dash(&line, false, &[0., 0.], 0.), vec![line.to_vec()]);
+    assert_eq!(stroke::dash(&line, false, &[1., -1.], 0.), vec![line.to_vec()]);
+}
+
+#[test]
+fn dash_closed() {
+    let square = [[0f64, 0.], [4., 0.], [4., 4.], [0., 4.]];
+    // Dashes which line up with the start are not merged
+    let dashes = stroke::dash(&square, true, &[2., 2.], 0.);
+    assert_eq!(dashes.len(), 4);
+    assert_eq!(dashes[3], vec![[0., 4.], [0., 2.]]);
+
+    // A pattern longer than the perimeter leaves a single closed dash
+    assert_eq!(stroke::dash(&square, true, &[20., 1.], 0.), vec![vec![[0., 0.], [4., 0.], [4., 4.], [0., 4.], [0., 0.]]]);
+}
+
+#[test]
+fn stroke() {
+    let line = [[0f64, 0.], [4., 0.]];
+    let mesh = stroke::stroke(&line, false, &options(LineJoin::Bevel));
+    assert_eq!(mesh.triangles.len(), 2);
+    assert_close(area(&mesh), 4.);
+
+    // A right angle, turning left and then right
+    for corner in [[[0f64, 0.], [2., 0.], [2., 2.]], [[0f64, 0.], [2., 0.], [2., -2.]]] {
+        let bevel = stroke::stroke(&corner, false, &options(LineJoin::Bevel));
+        assert_close(area(&bevel), 4. + 0.125);
+        let miter = stroke::stroke(&corner, false, &options(LineJoin::Miter));
+        assert_close(area(&miter), 4. + 0.25);
+        // The miter would reach sqrt(2) half-widths from the corner
+        let limited = stroke::stroke(&corner, false, &options(LineJoin::Miter).with_miter_limit(1.4));
+        assert_eq!(limited, bevel);
+    }
+
+    // A closed square is joined at every corner, including the first
+    let square = [[0f64, 0.], [4., 0.], [4., 4.], [0., 4.], [0., 0.]];
+    let mesh = stroke::stroke(&square, true, &options(LineJoin::Miter));
+    assert_eq!(mesh.triangles.len(), 4 * 2 + 4 * 2);
+    assert_close(area(&mesh), 4. * 4. + 4. * 0.25);
+}
+
+#[test]
+fn stroke_dashed() {
+    let square = [[0f64, 0.], [4., 0.], [4., 4.], [0., 4.]];
+    let mesh = stroke::stroke_dashed(&square, true, &options(LineJoin::Miter), &[3., 1.], 1.);
+    // 12 units of dashes, each crossing one corner
+    assert_eq!(mesh.triangles.len(), 4 * (2 * 2 + 2));
+    assert_close(area(&mesh), 12. + 4. * 0.25);
+
+    // Without gaps the stroke is closed
+    let solid = stroke::stroke_dashed(&square, true, &options(LineJoin::Miter), &[20., 1.], 0.);
+    assert_eq!(solid, stroke::stroke(&square, true, &options(LineJoin::Miter)));
+}
+
+#[test]
+fn stroke_variable() {
+    // A taper from a width of 2 to a point
+    let line = [[0f64, 0.], [4., 0.]];
+    let taper = stroke::stroke_variable(&line, &[2., 0.], false, &options(LineJoin::Bevel));
+    assert_eq!(taper.triangles.len(), 1);
+    assert_eq!(taper.vertices.len(), 3);
+    assert_close(area(&taper), 4.);
+
+    // Repeated points and constant widths match a plain stroke
+    let corner = [[0f64, 0.], [2., 0.], [2., 0.], [2., 2.]];
+    let variable = stroke::stroke_variable(&corner, &[1., 1., 3., 1.], false, &options(LineJoin::Miter));
+    assert_eq!(variable, stroke::stroke(&corner, false, &options(LineJoin::Miter)));
+
+    // The miter at a corner uses the width there
+    let arrow = stroke::stroke_variable(&[[0f64, 0.], [2., 0.], [2., 2.]], &[0., 2., 0.], false, &options(LineJoin::Miter));
+    assert_close(area(&arrow), 2. * 2. + 1.);
+    assert_eq!(arrow.triangles.len(), 4);
+
+    let mut mesh = Mesh::from_polygons(&vec![vec![[0f64, 0.], [1., 0.], [1., 1.]]]).expect("Triangulation failed");
+    mesh.append(taper);
+    assert_eq!(mesh.vertices.len(), 6);
+    assert_eq!(mesh.triangles[1], [3, 5, 4]);
+    assert_close(area(&mesh), 4.5);
+}
+
+#[test]
+fn joins() {
+    let corner = [[0f64, 0.], [2., 0.], [2., 2.]];
+    // The miter is cut 1.2 half-widths from the corner, short of its tip at sqrt(2)
+    let clipped = stroke::stroke(&corner, false, &options(LineJoin::MiterClip).with_miter_limit(1.2));
+    let cut = 0.5 * 2f64.sqrt() - 0.6;
+    assert_close(area(&clipped), 4. + 0.25 - cut * cut);
+    // A miter within the limit is not cut
+    assert_eq!(stroke::stroke(&corner, false, &options(LineJoin::MiterClip)), stroke::stroke(&corner, false, &options(LineJoin::Miter)));
+
+    let round = stroke::stroke(&corner, false, &options(LineJoin::Round).with_tolerance(1e-4));
+    assert!((area(&round) - (4. + PI / 16.)).abs() < 1e-3);
+    let coarse = stroke::stroke(&corner, false, &options(LineJoin::Round).with_tolerance(0.1));
+    assert!(coarse.triangles.len() < round.triangles.len());
+}
+
+#[test]
+fn caps() {
+    let line = [[0f64, 0.], [4., 0.]];
+    let square = stroke::stroke(&line, false, &options(LineJoin::Miter).with_line_cap(LineCap::Square));
+    assert_close(area(&square), 5.);
+    let start = stroke::stroke(&line, false, &options(LineJoin::Miter).with_start_cap(LineCap::Square));
+    assert_close(area(&start), 4.5);
+    assert!(start.vertices.contains(&[-0.5, 0.5]) && !start.vertices.contains(&[4.5, 0.5]));
+
+    let round = stroke::stroke(&line, false, &options(LineJoin::Miter).with_line_cap(LineCap::Round).with_tolerance(1e-4));
+    assert!((area(&round) - (4. + PI / 4.)).abs() < 1e-3);
+
+    // Tapered ends and closed strokes have no caps
+    let taper = stroke::stroke_variable(&line, &[1., 0.], false, &options(LineJoin::Miter).with_line_cap(LineCap::Square));
+    assert_close(area(&taper), 2. + 0.5);
+    let closed = [[0f64, 0.], [4., 0.], [4., 4.]];
+    assert_eq!(
+        stroke::stroke(&closed, true, &options(LineJoin::Miter).with_line_cap(LineCap::Round)),
+        stroke::stroke(&closed, true, &options(LineJoin::Miter)),
+    );
+
+    // Each dash is capped
+    let dashed = stroke::stroke_dashed(&line, false, &options(LineJoin::Miter).with_line_cap(LineCap::Square), &[1., 1.], 0.);
+    assert_close(area(&dashed), 2. + 2. * 1.);
+}
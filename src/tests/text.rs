@@ -0,0 +1,95 @@
+use crate::{mesh::Mesh, text::{self, FontProvider, PathCommand, TextOptions}};
+
+use PathCommand::*;
+
+// 'I' is a bar, 'O' a square with a square hole, and 'D' a half disc of radius 0.5 made of two quarter arcs
+struct TestFont;
+
+impl FontProvider<f64> for TestFont {
+    fn outline(&self, c: char) -> Vec<PathCommand<f64>> {
+        match c {
+            'I' => vec![MoveTo([0., 0.]), LineTo([0.2, 0.]), LineTo([0.2, 1.]), LineTo([0., 1.]), Close],
+            'O' => vec![
+                MoveTo([0., 0.]), LineTo([1., 0.]), LineTo([1., 1.]), LineTo([0., 1.]), Close,
+                MoveTo([0.25, 0.25]), LineTo([0.25, 0.75]), LineTo([0.75, 0.75]), LineTo([0.75, 0.25]), Close,
+            ],
+            'D' => {
+                // The standard approximation of a quarter circle by a cubic
+                let k = 0.5 * 0.552_284_749_8;
+                vec![
+                    MoveTo([0., 0.]),
+                    CurveTo([k, 0.], [0.5, 0.5 - k], [0.5, 0.5]),
+                    CurveTo([0.5, 0.5 + k], [k, 1.], [0., 1.]),
+                    LineTo([0., 0.]),
+                ]
+            }
+            'V' => vec![MoveTo([0., 1.]), QuadTo([0.5, -1.], [1., 1.]), Close],
+            _ => Vec::new(),
+        }
+    }
+
+    fn advance(&self, c: char) -> f64 {
+        match c {
+            'I' => 0.5,
+            _ => 1.,
+        }
+    }
+
+    fn kerning(&self, left: char, right: char) -> f64 {
+        if (left, right) == ('O', 'I') { -0.25 } else { 0. }
+    }
+
+    fn line_height(&self) -> f64 {
+        2.
+    }
+}
+
+fn area(mesh: &Mesh<[f64; 2]>, triangles: &[[usize; 3]]) -> f64 {
+    triangles.iter().map(|t| Mesh::<[f64; 2]>::area_of(t.map(|vi| &mesh.vertices[vi]))).sum()
+}
+
+#[test]
+fn layout() {
+    let options = TextOptions { size: 2., origin: [10., 20.], ..Default::default() };
+    let result = text::triangulate(&TestFont, "OI I\r\nIO", &options).expect("Triangulation failed");
+
+    let layout: Vec<_> = result.glyphs.iter().map(|g| (g.character, g.byte_offset, g.origin)).collect();
+    assert_eq!(layout, vec![
+        ('O', 0, [10., 20.]),
+        ('I', 1, [11.5, 20.]),
+        (' ', 2, [12.5, 20.]),
+        ('I', 3, [14.5, 20.]),
+        ('I', 6, [10., 16.]),
+        ('O', 7, [11., 16.]),
+    ]);
+
+    // The glyph ranges cover the mesh in order
+    assert!(result.glyph_triangles(2).is_empty());
+    assert_eq!(result.glyphs.last().map(|g| g.triangles.end), Some(result.mesh.triangles.len()));
+    for (i, glyph) in result.glyphs.iter().enumerate() {
+        let expected = match glyph.character {
+            'O' => 0.75 * 4.,
+            'I' => 0.2 * 4.,
+            _ => 0.,
+        };
+        assert!((area(&result.mesh, result.glyph_triangles(i)) - expected).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn curves() {
+    let quarter_disc_area = std::f64::consts::PI / 8.;
+    for tolerance in [0.1, 0.001] {
+        let options = TextOptions { tolerance, ..Default::default() };
+        let result = text::triangulate(&TestFont, "D", &options).expect("Triangulation failed");
+        let error = (area(&result.mesh, &result.mesh.triangles) - quarter_disc_area).abs();
+        // The flattened arc may cut inside the curve by at most the tolerance along its length of pi / 2
+        assert!(error <= tolerance * std::f64::consts::FRAC_PI_2, "Error {error} exceeds tolerance {tolerance}");
+    }
+
+    let coarse = text::triangulate(&TestFont, "V", &TextOptions { tolerance: 1., ..Default::default() }).expect("Triangulation failed");
+    let fine = text::triangulate(&TestFont, "V", &TextOptions { tolerance: 0.001, ..Default::default() }).expect("Triangulation failed");
+    assert!(coarse.mesh.vertices.len() < fine.mesh.vertices.len());
+    // The area under the parabola from y = 1 down to y = 0 is 2/3 of the enclosing 1 x 1 rectangle
+    assert!((area(&fine.mesh, &fine.mesh.triangles) - 2. / 3.).abs() < 1e-3);
+}
@@ -0,0 +1,51 @@
+use crate::trace::{self, TraceEvent};
+
+#[test]
+fn trace() {
+    let polygons = vec![
+        vec![[0f64, 0.], [4., 0.], [4., 4.], [0., 4.]],
+        vec![[1., 1.], [1., 3.], [3., 3.], [3., 1.]],
+    ];
+    let trace = trace::trace(&polygons).expect("Triangulation failed");
+    assert_eq!(trace.steps.len(), 8 + 8);
+
+    let mut inserted = Vec::new();
+    let mut trapezoids = 1;
+    for step in &trace.steps {
+        match step.event {
+            TraceEvent::InsertVertex { position } => {
+                assert!(polygons.iter().flatten().any(|c| *c == position));
+                inserted.push(position);
+                // The trapezoid containing the vertex is split in two
+                assert_eq!(step.trapezoids.len(), trapezoids + 1);
+            }
+            TraceEvent::InsertSegment { from, to } => {
+                // Both ends are inserted before the segment, and it runs upwards
+                assert!(inserted.contains(&from) && inserted.contains(&to));
+                assert!(from[1] < to[1] || from[1] == to[1] && from[0] < to[0]);
+                // The trapezoids it crosses are split, and merged again on either side wherever they are no longer separated
+                assert_eq!(step.trapezoids.len(), trapezoids + 1);
+                assert!(step.trapezoids.iter().any(|t| t.left == Some([from, to])) && step.trapezoids.iter().any(|t| t.right == Some([from, to])));
+            }
+        }
+        trapezoids = step.trapezoids.len();
+    }
+    assert_eq!(trapezoids, 2 * 8 + 1);
+    assert_eq!(trace.interior.len(), trapezoids);
+    let last = &trace.steps[trace.steps.len() - 1].trapezoids;
+    assert!(trace.interior.iter().zip(last).all(|(inside, t)| !inside || t.left.is_some() && t.right.is_some()));
+    // Only the last step knows which trapezoids are inside
+    assert!(trace.interior.iter().zip(last).all(|(inside, t)| t.inside == *inside));
+    assert!(trace.steps[..trace.steps.len() - 1].iter().flat_map(|step| &step.trapezoids).all(|t| !t.inside));
+
+    assert_eq!(trace.triangles.len(), 8);
+    let area: f64 = trace.triangles.iter().map(|[a, b, c]| ((b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1])).abs() / 2.).sum();
+    assert!((area - 12.).abs() < 1e-9);
+
+    let json = trace.to_json();
+    assert_eq!(json.matches("\"insert_vertex\"").count(), 8);
+    assert_eq!(json.matches("\"insert_segment\"").count(), 8);
+    assert_eq!(json.matches('[').count(), json.matches(']').count());
+    assert_eq!(json.matches('{').count(), json.matches('}').count());
+    assert!(json.ends_with("]]]}"));
+}
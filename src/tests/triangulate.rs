@@ -1,6 +1,6 @@
-use std::fs;
+use std::{cmp::Ordering, fs};
 
-use crate::{formats, Polygon, PolygonList, ListFormat};
+use crate::{cmp_vertices, inputs::PolygonListExt, formats, MergePolicy, Polygon, PolygonList, ListFormat, TriangulationOptions, GeometryKernel, DefaultKernel, Orientation, FlatPolygonList, Reversed, Dedup, Closed, Unbridged};
 
 use super::util;
 
@@ -33,6 +33,30 @@ fn triangulate_geography() {
     }
 }
 
+#[test]
+fn merged_monotones_geography() {
+    let area = |polygon_list: &Vec<Vec<[f32; 2]>>, merge: MergePolicy| {
+        let mut triangles = Vec::<[[usize; 2]; 3]>::new();
+        let options = TriangulationOptions::default().with_merge(merge).with_seed(1);
+        polygon_list.triangulate_with_options(formats::IndexedListFormat::new(&mut triangles).into_fan_format(), &options).expect("Triangulation failed");
+        triangles.iter().map(|t| {
+            let [[x0, y0], [x1, y1], [x2, y2]] = t.map(|vi| polygon_list.get_vertex(vi).map(f64::from));
+            let area = ((x1 - x0) * (y2 - y0) - (x2 - x0) * (y1 - y0)) / 2.;
+            assert!(area >= 0.);
+            area
+        }).sum::<f64>()
+    };
+
+    for file in fs::read_dir(util::countries_path()).unwrap() {
+        let polygon_list = util::load_polygon_list(file.unwrap().path().to_str().unwrap()).unwrap();
+        if polygon_list.vertex_count() <= 1000 {
+            let merged = area(&polygon_list, MergePolicy::Monotone);
+            let split = area(&polygon_list, MergePolicy::Unimonotone);
+            assert!((merged - split).abs() <= 1e-6 * split);
+        }
+    }
+}
+
 #[test]
 fn regular_polygons() {
     for n in 3..=500 {
@@ -52,3 +76,502 @@ fn regular_polygons() {
         std::hint::black_box(output);
     }
 }
+
+#[test]
+fn custom_kernel() {
+    // A kernel which defers to the default kernel, but counts how often it is consulted
+    static ORIENTATION_TESTS: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+    struct CountingKernel;
+
+    impl GeometryKernel<f32> for CountingKernel {
+        fn orientation(a: &[f32; 2], b: &[f32; 2], c: &[f32; 2]) -> Orientation {
+            ORIENTATION_TESTS.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            <DefaultKernel as GeometryKernel<f32>>::orientation(a, b, c)
+        }
+    }
+
+    for polygon in util::polygon::all() {
+        let mut expected = Vec::<usize>::new();
+        polygon.triangulate(formats::IndexedListFormat::new(&mut expected).into_fan_format()).expect("Triangulation failed");
+        let mut output = Vec::<usize>::new();
+        polygon.triangulate_with_kernel::<CountingKernel, _>(formats::IndexedListFormat::new(&mut output).into_fan_format()).expect("Triangulation failed");
+        assert_eq!(expected.len(), output.len());
+    }
+    assert!(ORIENTATION_TESTS.load(std::sync::atomic::Ordering::Relaxed) > 0);
+}
+
+#[test]
+fn vertex_order() {
+    let values = [f64::NEG_INFINITY, -1., -0., 0., 1., f64::INFINITY, f64::NAN];
+    let points: Vec<[f64; 2]> = values.iter().flat_map(|&x| values.iter().map(move |&y| [x, y])).collect();
+    // A total order: antisymmetric and transitive, with every point equal to itself
+    for a in &points {
+        assert_eq!(cmp_vertices(a, a), Ordering::Equal);
+        for b in &points {
+            assert_eq!(cmp_vertices(a, b), cmp_vertices(b, a).reverse());
+            for c in &points {
+                if cmp_vertices(a, b) != Ordering::Greater && cmp_vertices(b, c) != Ordering::Greater {
+                    assert_ne!(cmp_vertices(a, c), Ordering::Greater);
+                }
+            }
+        }
+    }
+
+    // Signed zeros are the same position when inserting the vertices and when locating them
+    let polygon = vec![[-0f64, -0.], [1., 0.], [1., 1.], [0., 1.]];
+    let mut triangles = Vec::<usize>::new();
+    polygon.triangulate(formats::IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(triangles.len(), 6);
+    let trapezoidation = polygon.trapezoidize().expect("Trapezoidation failed");
+    let inside = |point| trapezoidation.contains_point(point).expect("Trapezoidation failed");
+    assert_eq!(inside([0.5, 0.]), inside([0.5, -0.]));
+    assert!(inside([0.5, 0.5]));
+}
+
+#[test]
+fn append_trapezoidation() {
+    let world = vec![
+        vec![[0f64, 0.], [10., 0.], [10., 10.], [0., 10.]],
+        vec![[1., 1.], [1., 2.], [2., 2.], [2., 1.]],
+    ];
+    let mut trapezoidation = world.trapezoidize().expect("Trapezoidation failed");
+
+    for offset in [3., 6.] {
+        let mut frame = world.clone();
+        frame.push(vec![[offset, offset], [offset, offset + 1.], [offset + 1., offset + 1.], [offset + 1., offset]]);
+        frame.push(vec![[offset + 2., 1.], [offset + 3., 1.], [offset + 2.5, 2.]]);
+        let appended = trapezoidation.fork().append(&frame).expect("Trapezoidation failed");
+
+        let mut triangles = Vec::<[usize; 2]>::new();
+        appended.triangulate(formats::IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+        let mut expected = Vec::<[usize; 2]>::new();
+        frame.triangulate(formats::IndexedListFormat::new(&mut expected).into_fan_format()).expect("Triangulation failed");
+        assert_eq!(triangles.len(), expected.len());
+
+        let area: f64 = triangles.chunks_exact(3).map(|t| {
+            let [a, b, c] = [t[0], t[1], t[2]].map(|[pi, vi]| frame[pi][vi]);
+            ((b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1])).abs() / 2.
+        }).sum();
+        assert!((area - (100. - 1. - 1. - 0.5)).abs() < 1e-9);
+    }
+
+    // The appended list must not continue a polygon that is already inserted
+    let mut extended = world.clone();
+    extended[1].push([1.5, 0.5]);
+    assert!(trapezoidation.append(&extended).is_err());
+}
+
+#[test]
+fn append_trapezoidation_with_seed() {
+    let world = vec![vec![[-20f64, -20.], [20., -20.], [20., 20.], [-20., 20.]]];
+    let mut trapezoidation = world.trapezoidize().expect("Trapezoidation failed").with_seed(Some(7));
+    let mut frame = world.clone();
+    frame.push((0..100).map(|i| {
+        let angle = i as f64 / 100. * std::f64::consts::TAU;
+        let radius = if i % 2 == 0 { 10. } else { 4. };
+        [radius * angle.cos(), radius * angle.sin()]
+    }).collect());
+
+    // Each fork inserts the new segments in the same order
+    let results = (0..2).map(|_| {
+        let mut triangles = Vec::<[usize; 2]>::new();
+        trapezoidation.fork().append(&frame).expect("Trapezoidation failed").triangulate(formats::IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+        triangles
+    }).collect::<Vec<_>>();
+    assert_eq!(results[0], results[1]);
+}
+
+#[test]
+fn fork_trapezoidation() {
+    // Enough vertices for the storage to span several pages
+    let n = 300;
+    let polygons = vec![(0..n).map(|i| {
+        let angle = i as f64 / n as f64 * std::f64::consts::TAU;
+        [angle.cos(), angle.sin()]
+    }).collect::<Vec<_>>()];
+    let mut trapezoidation = polygons.trapezoidize().expect("Trapezoidation failed");
+    let mut expected = Vec::<[usize; 2]>::new();
+    trapezoidation.triangulate(formats::IndexedListFormat::new(&mut expected).into_fan_format()).expect("Triangulation failed");
+
+    // Speculatively add a hole, then discard it
+    let mut edited = polygons.clone();
+    edited.push(vec![[-0.1, -0.1], [-0.1, 0.1], [0.1, 0.1], [0.1, -0.1]]);
+    let preview = trapezoidation.fork().append(&edited).expect("Trapezoidation failed");
+    let mut previewed = Vec::<[usize; 2]>::new();
+    preview.triangulate(formats::IndexedListFormat::new(&mut previewed).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(previewed.len(), expected.len() + 3 * 6);
+    drop(preview);
+
+    let mut triangles = Vec::<[usize; 2]>::new();
+    trapezoidation.triangulate(formats::IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(triangles, expected);
+}
+
+#[test]
+fn point_location() {
+    let polygons = vec![
+        vec![[0f64, 0.], [10., 0.], [10., 10.], [0., 10.]],
+        vec![[3., 3.], [3., 7.], [7., 7.], [7., 3.]],
+    ];
+    let mut trapezoidation = polygons.trapezoidize().expect("Trapezoidation failed");
+
+    assert!(trapezoidation.contains_point([1., 1.]).unwrap());
+    assert!(trapezoidation.contains_point([8., 5.]).unwrap());
+    assert!(!trapezoidation.contains_point([5., 5.]).unwrap());
+    assert!(!trapezoidation.contains_point([-1., 5.]).unwrap());
+    assert!(!trapezoidation.contains_point([5., 11.]).unwrap());
+
+    // Points between the same segments and vertices are in the same trapezoid
+    assert_eq!(trapezoidation.locate([1., 4.]), trapezoidation.locate([2., 6.]));
+    assert_ne!(trapezoidation.locate([1., 4.]), trapezoidation.locate([5., 4.]));
+
+    // Appending finds the interior again
+    let mut edited = polygons.clone();
+    edited.push(vec![[4., 4.], [6., 4.], [5., 6.]]);
+    let appended = trapezoidation.fork().append(&edited).expect("Trapezoidation failed");
+    assert!(appended.contains_point([5., 4.5]).unwrap());
+    assert!(!trapezoidation.contains_point([5., 4.5]).unwrap());
+}
+
+#[test]
+fn locate_batch() {
+    let polygons = vec![
+        vec![[0f64, 0.], [10., 0.], [10., 10.], [0., 10.]],
+        vec![[3., 3.], [3., 7.], [7., 7.], [7., 3.]],
+    ];
+    let trapezoidation = polygons.trapezoidize().expect("Trapezoidation failed");
+
+    let points = (0..400).map(|i| [(i % 20) as f64 * 0.6 - 0.5, (i / 20) as f64 * 0.6 - 0.5]).collect::<Vec<_>>();
+    let located = trapezoidation.locate_batch(&points).expect("Point location failed");
+    assert_eq!(located.len(), points.len());
+    for (point, (ti, inside)) in points.iter().zip(located) {
+        assert_eq!(ti, trapezoidation.locate(*point));
+        assert_eq!(inside, trapezoidation.contains_point(*point).unwrap());
+    }
+    assert!(trapezoidation.locate_batch(&[]).expect("Point location failed").is_empty());
+
+    #[cfg(feature = "rayon")]
+    assert_eq!(trapezoidation.locate_batch_par(&points).expect("Point location failed"), trapezoidation.locate_batch(&points).expect("Point location failed"));
+}
+
+#[test]
+fn trapezoid_geometry() {
+    let polygons = vec![
+        vec![[0f64, 0.], [10., 0.], [10., 10.], [0., 10.]],
+        vec![[3., 3.], [3., 7.], [7., 7.], [7., 3.]],
+    ];
+    let trapezoidation = polygons.trapezoidize().expect("Trapezoidation failed");
+    let trapezoids = trapezoidation.trapezoids().expect("Trapezoids failed").collect::<Vec<_>>();
+
+    let left_of_hole = trapezoids[trapezoidation.locate([1., 4.])];
+    assert!(left_of_hole.inside);
+    assert_eq!(left_of_hole.corners(), Some([[0., 3.], [3., 3.], [3., 7.], [0., 7.]]));
+    assert!(!trapezoids[trapezoidation.locate([5., 5.])].inside);
+    assert!(!trapezoids[trapezoidation.locate([-1., 5.])].is_bounded());
+
+    // The inside trapezoids cover the polygons
+    let area = trapezoids.iter().filter(|t| t.inside).map(|t| {
+        let c = t.corners().expect("Inside trapezoids are bounded");
+        (0..4).map(|i| c[i][0] * c[(i + 1) % 4][1] - c[(i + 1) % 4][0] * c[i][1]).sum::<f64>() / 2.
+    }).sum::<f64>();
+    assert!((area - 84.).abs() < 1e-9);
+
+    // Trapezoids at the corners of a triangle have coinciding corners
+    let triangle = vec![vec![[0f64, 0.], [2., 1.], [0., 2.]]];
+    let trapezoidation = triangle.trapezoidize().expect("Trapezoidation failed");
+    let trapezoids = trapezoidation.trapezoids().expect("Trapezoids failed").collect::<Vec<_>>();
+    assert_eq!(trapezoids.iter().filter(|t| t.inside).count(), 2);
+    assert_eq!(trapezoids[trapezoidation.locate([0.5, 0.5])].corners(), Some([[0., 0.], [0., 0.], [2., 1.], [0., 1.]]));
+    assert_eq!(trapezoids[trapezoidation.locate([0.5, 1.5])].corners(), Some([[0., 1.], [2., 1.], [0., 2.], [0., 2.]]));
+}
+
+#[test]
+fn free_intervals() {
+    let polygons = vec![
+        vec![[0f64, 0.], [10., 0.], [10., 10.], [0., 10.]],
+        vec![[3., 3.], [3., 7.], [7., 7.], [7., 3.]],
+        vec![[12., 4.], [14., 4.], [13., 6.]],
+    ];
+    let trapezoidation = polygons.trapezoidize().expect("Trapezoidation failed");
+    let free = |y: f64| trapezoidation.free_intervals_at(y).expect("Trapezoidation failed");
+
+    assert_eq!(free(5.), vec![[None, Some(0.)], [Some(3.), Some(7.)], [Some(10.), Some(12.5)], [Some(13.5), None]]);
+    assert_eq!(free(-1.), vec![[None, None]]);
+    assert_eq!(free(11.), vec![[None, None]]);
+
+    // A line through vertices is treated as just above them
+    assert_eq!(free(3.), vec![[None, Some(0.)], [Some(3.), Some(7.)], [Some(10.), None]]);
+    assert_eq!(free(7.), vec![[None, Some(0.)], [Some(10.), None]]);
+}
+
+#[test]
+fn extract_region() {
+    let polygons = vec![
+        vec![[0f64, 0.], [10., 0.], [10., 10.], [0., 10.]],
+        vec![[3., 3.], [3., 7.], [7., 7.], [7., 3.]],
+        vec![[12., 4.], [14., 4.], [13., 6.]],
+    ];
+    let trapezoidation = polygons.trapezoidize().expect("Trapezoidation failed");
+
+    // The frame is bounded by the outer square counter-clockwise, and the hole clockwise
+    let frame = trapezoidation.extract_region([1., 5.]).expect("Trapezoidation failed");
+    let elements = frame.iter_indices().collect::<Vec<_>>();
+    let contours = elements.split(Option::is_none).filter(|c| !c.is_empty()).map(|c| c.iter().flatten().copied().collect::<Vec<_>>()).collect::<Vec<_>>();
+    assert_eq!(contours.len(), 2);
+    let area = |contour: &Vec<[usize; 2]>| (0..contour.len()).map(|i| {
+        let ([x0, y0], [x1, y1]) = (polygons.get_vertex(contour[i]), polygons.get_vertex(contour[(i + 1) % contour.len()]));
+        x0 * y1 - x1 * y0
+    }).sum::<f64>() / 2.;
+    let mut areas = contours.iter().map(area).collect::<Vec<_>>();
+    areas.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert_eq!(areas, vec![-16., 100.]);
+
+    let mut triangles = Vec::<[usize; 2]>::new();
+    frame.triangulate(formats::IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(triangles.len(), 8 * 3);
+    assert!(triangles.iter().all(|[pi, _]| *pi < 2));
+
+    let triangle = trapezoidation.extract_region([13., 5.]).expect("Trapezoidation failed");
+    assert_eq!(triangle.vertex_count(), 3);
+    assert!(trapezoidation.extract_region([5., 5.]).expect("Trapezoidation failed").is_empty());
+    assert!(trapezoidation.extract_region([20., 5.]).expect("Trapezoidation failed").is_empty());
+}
+
+#[test]
+fn flat_polygon_list() {
+    let polygons = [
+        [[0f64, 0.], [10., 0.], [10., 10.], [0., 10.]],
+        [[3., 3.], [3., 7.], [7., 7.], [7., 3.]],
+    ];
+    let flat = polygons.iter().map(|p| p.iter().copied()).collect::<FlatPolygonList<_>>();
+    assert_eq!(flat.polygon_count(), 2);
+    assert_eq!(flat.vertex_count(), 8);
+    assert_eq!(flat.polygon(1), &polygons[1][..]);
+    assert!(flat.polygons().eq(polygons.iter().map(|p| &p[..])));
+
+    let mut triangles = Vec::<[usize; 3]>::new();
+    flat.triangulate(formats::IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(triangles.len(), 8);
+    // Indices are positions in the flat vertex list
+    assert!(triangles.iter().flatten().all(|&vi| vi < 8));
+    assert!(triangles.iter().any(|t| t.iter().any(|&vi| vi >= 4)));
+}
+
+#[test]
+fn contour_hints() {
+    let nested = vec![
+        vec![[0f64, 0.], [10., 0.], [10., 10.], [0., 10.]],
+        vec![],
+        vec![[3., 3.], [3., 7.], [5., 8.], [7., 7.], [7., 3.]],
+    ];
+    assert_eq!(nested.contour_count_hint(), Some(3));
+    assert_eq!([0, 1, 2, 3].map(|i| nested.contour_len_hint(i)), [Some(4), Some(0), Some(5), None]);
+
+    let flat = nested.iter().map(|p| p.iter().copied()).collect::<FlatPolygonList<_>>();
+    assert_eq!(flat.contour_count_hint(), Some(3));
+    assert_eq!([0, 1, 2, 3].map(|i| flat.contour_len_hint(i)), [Some(4), Some(0), Some(5), None]);
+
+    let single = nested[2].as_polygon_list();
+    assert_eq!(single.contour_count_hint(), Some(1));
+    assert_eq!([0, 1].map(|i| single.contour_len_hint(i)), [Some(5), None]);
+
+    // Interior points are not part of any polygon
+    let points = [[1., 1.]];
+    let with_points = nested.with_interior_points(&points);
+    assert_eq!(with_points.contour_count_hint(), Some(3));
+    assert_eq!(with_points.contour_len_hint(2), Some(5));
+    assert_eq!(PolygonListExt::new(&with_points).contour_vertex_count(), 9);
+    assert_eq!(with_points.vertex_count(), 10);
+}
+
+#[test]
+fn polygon_adapters() {
+    // Clockwise closed rings with a repeated vertex, as they might come from a file
+    let outer = [[0f64, 0.], [0., 10.], [10., 10.], [10., 10.], [10., 0.], [0., 0.]];
+    let hole = [[3., 3.], [7., 3.], [7., 7.], [3., 7.], [3., 3.]];
+
+    let closed = Closed::new(&outer[..]);
+    assert_eq!(closed.vertex_count(), 5);
+    let deduped = Dedup::new(closed);
+    assert_eq!(deduped.vertex_count(), 4);
+    let reversed = Reversed::new(deduped);
+    assert_eq!((0..4).map(|i| *reversed.get_vertex(i)).collect::<Vec<_>>(), vec![[10., 0.], [10., 10.], [0., 10.], [0., 0.]]);
+
+    // Dedup alone also drops the closing vertex, and a ring which is already open is unchanged by Closed
+    assert_eq!(Dedup::new(&outer[..]).vertex_count(), 4);
+    assert_eq!(Closed::new(&hole[..4]).vertex_count(), 4);
+
+    let polygons = vec![reversed, Reversed::new(Dedup::new(Closed::new(&hole[..])))];
+    let mut triangles = Vec::<[[usize; 2]; 3]>::new();
+    polygons.triangulate(formats::IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(triangles.len(), 8);
+    for t in triangles {
+        let [a, b, c] = t.map(|[p, v]| *polygons[p].get_vertex(v));
+        assert_eq!(DefaultKernel::orientation(&a, &b, &c), Orientation::Left);
+    }
+}
+
+#[test]
+fn unbridged() {
+    // Two holes, the second joined to the first rather than to the outer boundary, and a separate square
+    let polygons = vec![
+        vec![
+            [0f64, 0.], [2., 2.], [2., 4.], [4., 4.], [6., 4.], [6., 2.], [8., 2.], [8., 4.], [6., 4.], [4., 4.], [4., 2.], [2., 2.],
+            [0., 0.], [10., 0.], [10., 10.], [0., 10.],
+        ],
+        vec![[20., 0.], [21., 0.], [21., 1.], [20., 1.]],
+    ];
+    let unbridged = Unbridged::new(&polygons);
+    assert_eq!(unbridged.vertex_count(), 16);
+    let contours = unbridged.iter_indices().filter(Option::is_none).count();
+    assert_eq!(contours, 4);
+
+    let mut triangles = Vec::<[[usize; 2]; 3]>::new();
+    unbridged.triangulate(formats::IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(triangles.len(), 16);
+    let area: f64 = triangles.iter().map(|t| {
+        let [a, b, c] = t.map(|vi| *unbridged.get_vertex(vi));
+        ((b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])).abs() / 2.
+    }).sum();
+    assert!((area - (100. - 8. + 1.)).abs() < 1e-9);
+
+    // Polygons without bridges are unchanged
+    let plain = vec![vec![[0f64, 0.], [1., 0.], [1., 1.], [0., 1.]]];
+    assert_eq!(Unbridged::new(&plain).iter_indices().collect::<Vec<_>>(), vec![Some([0, 0]), Some([0, 1]), Some([0, 2]), Some([0, 3]), None]);
+}
+
+#[test]
+fn sanitized() {
+    // A repeated vertex, collinear vertices and a spike on the outer boundary, two welded vertices on the hole, and a
+    // polygon which is only a line
+    let polygons = vec![
+        vec![[0f64, 0.], [5., 0.], [10., 0.], [10., 10.], [10., 10.], [5., 10.], [5., 12.], [5., 10.], [0., 10.]],
+        vec![[2., 2.], [2., 8.], [8., 8.], [8., 2.0000001], [8., 2.]],
+        vec![[20., 0.], [21., 0.], [22., 0.]],
+    ];
+    let sanitized = polygons.sanitized(1e-3);
+    assert_eq!(sanitized.vertex_count(), 8);
+    assert_eq!(sanitized.iter_indices().take(5).collect::<Vec<_>>(), vec![Some([0, 0]), Some([0, 2]), Some([0, 3]), Some([0, 8]), None]);
+    assert_eq!(sanitized.iter_indices().filter(Option::is_none).count(), 2);
+
+    let mut triangles = Vec::<[[usize; 2]; 3]>::new();
+    sanitized.triangulate(formats::IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(triangles.len(), 8);
+    let area: f64 = triangles.iter().map(|t| {
+        let [a, b, c] = t.map(|vi| *sanitized.get_vertex(vi));
+        ((b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])).abs() / 2.
+    }).sum();
+    assert!((area - (100. - 36.)).abs() < 1e-5);
+
+    // Clean polygons are unchanged
+    let plain = vec![vec![[0f64, 0.], [1., 0.], [1., 1.], [0., 1.]]];
+    assert_eq!(plain.sanitized(1e-3).iter_indices().collect::<Vec<_>>(), vec![Some([0, 0]), Some([0, 1]), Some([0, 2]), Some([0, 3]), None]);
+}
+
+#[test]
+fn weakly_simple() {
+    fn area(polygons: &[Vec<[f64; 2]>], triangles: &[[[usize; 2]; 3]]) -> f64 {
+        triangles.iter().map(|t| {
+            let [a, b, c] = t.map(|vi| *polygons.get_vertex(vi));
+            ((b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])).abs() / 2.
+        }).sum()
+    }
+
+    // A hole touching the corner of its outer boundary
+    let polygons = vec![
+        vec![[0f64, 0.], [10., 0.], [10., 10.], [0., 10.]],
+        vec![[0., 0.], [3., 6.], [6., 3.]],
+    ];
+    let mut triangles = Vec::<[[usize; 2]; 3]>::new();
+    polygons.weakly_simple().triangulate(formats::IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(triangles.len(), 5);
+    assert!((area(&polygons, &triangles) - (100. - 13.5)).abs() < 1e-9);
+
+    // Two squares sharing a corner
+    let polygons = vec![
+        vec![[0f64, 0.], [1., 0.], [1., 1.], [0., 1.]],
+        vec![[1., 1.], [2., 1.], [2., 2.], [1., 2.]],
+    ];
+    let mut triangles = Vec::<[[usize; 2]; 3]>::new();
+    polygons.weakly_simple().triangulate(formats::IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(triangles.len(), 4);
+    assert!((area(&polygons, &triangles) - 2.).abs() < 1e-9);
+    // Every triangle lies within one of the squares
+    assert!(triangles.iter().all(|t| t.iter().all(|vi| vi[0] == t[0][0] || polygons.get_vertex(*vi) == &[1., 1.])));
+}
+
+#[test]
+fn integer_coordinates() {
+    let polygons = vec![
+        vec![[0i32, 0], [10, 0], [10, 10], [0, 10]],
+        vec![[3, 3], [3, 7], [7, 7], [7, 3]],
+    ];
+    let mut triangles = Vec::<[[usize; 2]; 3]>::new();
+    polygons.triangulate(formats::IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(triangles.len(), 8);
+    for t in triangles {
+        let [a, b, c] = t.map(|[p, v]| polygons[p][v]);
+        assert_eq!(DefaultKernel::orientation(&a, &b, &c), Orientation::Left);
+    }
+
+    // Far from the origin, where a float would round the differences of the coordinates
+    let offset = 1i64 << 53;
+    let polygon = vec![[offset, offset], [offset + 2, offset], [offset + 2, offset + 1], [offset + 1, offset + 2], [offset, offset + 1]];
+    let mut triangles = Vec::<[usize; 3]>::new();
+    polygon.triangulate(formats::IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(triangles.len(), 3);
+    for t in triangles {
+        let [a, b, c] = t.map(|v| polygon[v]);
+        assert_eq!(DefaultKernel::orientation(&a, &b, &c), Orientation::Left);
+    }
+}
+
+#[test]
+fn non_copy_coordinates() {
+    // An integer which can only be cloned, like an arbitrary-precision number
+    #[derive(Debug, Clone, PartialEq, PartialOrd)]
+    struct Exact(i64);
+
+    macro_rules! op {
+        ($trait:ident, $fn:ident) => {
+            impl std::ops::$trait for Exact {
+                type Output = Exact;
+                fn $fn(self, rhs: Exact) -> Exact { Exact(std::ops::$trait::$fn(self.0, rhs.0)) }
+            }
+        };
+    }
+    op!(Add, add);
+    op!(Sub, sub);
+    op!(Mul, mul);
+    op!(Div, div);
+    op!(Rem, rem);
+
+    impl num_traits::Zero for Exact {
+        fn zero() -> Self { Exact(0) }
+        fn is_zero(&self) -> bool { self.0 == 0 }
+    }
+    impl num_traits::One for Exact {
+        fn one() -> Self { Exact(1) }
+    }
+    impl num_traits::Num for Exact {
+        type FromStrRadixErr = std::num::ParseIntError;
+        fn from_str_radix(s: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> { i64::from_str_radix(s, radix).map(Exact) }
+    }
+    impl num_traits::ToPrimitive for Exact {
+        fn to_i64(&self) -> Option<i64> { Some(self.0) }
+        fn to_u64(&self) -> Option<u64> { self.0.to_u64() }
+    }
+
+    let polygons = vec![
+        [[0, 0], [10, 0], [10, 10], [0, 10]].map(|v| v.map(Exact)).to_vec(),
+        [[3, 3], [3, 7], [7, 7], [7, 3]].map(|v| v.map(Exact)).to_vec(),
+    ];
+    let mut triangles = Vec::<[[usize; 2]; 3]>::new();
+    polygons.triangulate(formats::IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(triangles.len(), 8);
+    for t in triangles {
+        let [a, b, c] = t.map(|[p, v]| &polygons[p][v]);
+        assert_eq!(DefaultKernel::orientation(a, b, c), Orientation::Left);
+    }
+}
@@ -0,0 +1,22 @@
+use crate::{tuning, TrapezoidationError, TriangulationError, TriangulationMethod, TriangulationOptions};
+
+use super::util;
+
+#[test]
+fn auto_tune() {
+    let samples = util::polygon::all().into_iter().map(|polygon| vec![polygon]).collect::<Vec<_>>();
+    let methods = [TriangulationMethod::Seidel, TriangulationMethod::ConstrainedDelaunay];
+    let base = TriangulationOptions::default();
+    let options = tuning::auto_tune(&samples, base, &methods).expect("Triangulation failed");
+    assert!(methods.contains(&options.method));
+
+    let delaunay = tuning::auto_tune(&samples, base, &[TriangulationMethod::ConstrainedDelaunay]).expect("Triangulation failed");
+    assert_eq!(delaunay.method, TriangulationMethod::ConstrainedDelaunay);
+    assert_eq!(tuning::auto_tune(&samples, delaunay, &[TriangulationMethod::Seidel]).expect("Triangulation failed"), base);
+    assert_eq!(tuning::auto_tune(&samples, delaunay, &[]).expect("Triangulation failed"), delaunay);
+    assert_eq!(tuning::auto_tune::<Vec<Vec<[f32; 2]>>>(&[], delaunay, &methods).expect("Triangulation failed"), delaunay);
+
+    // Every candidate fails on a degenerate polygon
+    let degenerate = vec![vec![vec![[0f32, 0.], [1., 0.]]]];
+    assert!(matches!(tuning::auto_tune(&degenerate, base, &methods), Err(TriangulationError::TrapezoidationError(TrapezoidationError::NotEnoughVertices(_)))));
+}
@@ -9,4 +9,15 @@ pub use load_polygon_list::load_polygon_list;
 /// Returns a directory containing sample polygon lists
 pub fn countries_path() -> path::PathBuf {
     path::Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("resources").join("geometry").join("countries")
-}
\ No newline at end of file
+}
+
+/// Asserts that `a` and `b` are equal up to rounding
+#[track_caller]
+pub fn assert_close(a: f64, b: f64) {
+    assert!((a - b).abs() < 1e-9, "{a} != {b}");
+}
+
+/// The counterclockwise square with sides of `size` and its lowest corner at (`x`, `y`)
+pub fn square(x: f64, y: f64, size: f64) -> Vec<[f64; 2]> {
+    vec![[x, y], [x + size, y], [x + size, y + size], [x, y + size]]
+}
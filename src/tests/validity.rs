@@ -0,0 +1,112 @@
+use crate::{validity::{self, InvalidReason, Neck, SegmentIntersection}, PolygonList};
+
+use super::util::{self, square};
+
+#[test]
+fn valid() {
+    for polygon in util::polygon::all() {
+        assert!(validity::is_valid(&vec![polygon]));
+    }
+
+    // A hole touching its shell at a single point, and an island inside the hole
+    let polygons = vec![square(0., 0., 6.), vec![[0., 3.], [3., 1.], [5., 3.], [3., 5.]], square(2.5, 2.5, 1.)];
+    assert_eq!(validity::validate(&polygons), vec![]);
+
+    // Two separate shells touching at two points
+    let polygons = vec![vec![[0., 0.], [2., 0.], [2., 2.], [0., 2.]], vec![[2., 0.], [4., 1.], [2., 2.], [3., 1.]]];
+    assert!(validity::is_valid(&polygons));
+}
+
+#[test]
+fn invalid_rings() {
+    let polygons = vec![
+        vec![[0f64, 0.], [1., 0.], [1., 0.]],
+        vec![[0., 0.], [f64::NAN, 1.], [1., 1.]],
+        // Touches itself at a vertex
+        vec![[10., 0.], [12., 0.], [11., 1.], [12., 2.], [10., 2.], [11., 1.]],
+        // A spike, which overlaps itself
+        vec![[20., 0.], [22., 0.], [22., 2.], [22., 3.], [22., 2.], [20., 2.]],
+    ];
+    let reasons = validity::validate(&polygons);
+    assert_eq!(reasons, vec![
+        InvalidReason::TooFewPoints { polygon: 0 },
+        InvalidReason::InvalidCoordinate { polygon: 1, vertex: 1 },
+        InvalidReason::RingSelfIntersection { polygon: 2, point: [11., 1.] },
+        InvalidReason::RingSelfIntersection { polygon: 3, point: [22., 2.] },
+    ]);
+    assert!(!validity::is_simple(&polygons));
+    assert_eq!(reasons[0].to_string(), "Too few points");
+    assert_eq!(reasons[2].to_string(), "Ring Self-intersection[11 1]");
+}
+
+#[test]
+fn invalid_between_rings() {
+    // Crossing rings are simple, but not valid
+    let polygons = vec![square(0., 0., 2.), square(1., 1., 2.)];
+    assert!(validity::is_simple(&polygons));
+    assert_eq!(validity::validate(&polygons), vec![
+        InvalidReason::SelfIntersection { polygons: [0, 1], point: [2., 1.] },
+        InvalidReason::SelfIntersection { polygons: [0, 1], point: [1., 2.] },
+    ]);
+
+    // Shared edges overlap
+    let polygons = vec![square(0., 0., 1.), square(1., 0., 1.)];
+    assert!(validity::validate(&polygons).iter().all(|reason| reason.code() == "Self-intersection"));
+
+    // A hole which cuts its shell in two
+    let polygons = vec![square(0., 0., 4.), vec![[0., 2.], [2., 1.], [4., 2.], [2., 3.]]];
+    assert_eq!(validity::validate(&polygons), vec![InvalidReason::DisconnectedInterior { point: [4., 2.] }]);
+
+    // Two holes which cut their shell in two
+    let polygons = vec![square(0., 0., 4.), vec![[0., 2.], [1., 1.], [2., 2.], [1., 3.]], vec![[2., 2.], [3., 1.], [4., 2.], [3., 3.]]];
+    assert_eq!(validity::validate(&polygons).len(), 1);
+}
+
+#[test]
+fn segment_intersections() {
+    assert_eq!(vec![square(0., 0., 2.), square(0.5, 0.5, 1.)].validate(), Ok(()));
+
+    let polygons = vec![square(0., 0., 2.), square(1., 1., 2.)];
+    assert_eq!(polygons.validate(), Err(vec![
+        SegmentIntersection { segments: [[0, 1], [1, 0]], point: [2., 1.], overlapping: false },
+        SegmentIntersection { segments: [[0, 2], [1, 3]], point: [1., 2.], overlapping: false },
+    ]));
+
+    // The shared edge is reported once, though it overlaps at both ends
+    let polygons = vec![square(0., 0., 2.), square(2., 0., 2.)];
+    let intersections = validity::segment_intersections(&polygons);
+    assert_eq!(intersections.iter().filter(|i| i.overlapping).map(|i| i.segments).collect::<Vec<_>>(), vec![[[0, 1], [1, 3]]]);
+
+    // A spike doubles back over itself
+    let polygons = vec![vec![[0f64, 0.], [4., 0.], [4., 4.], [2., 4.], [2., 6.], [2., 4.], [0., 4.]]];
+    let intersections = validity::segment_intersections(&polygons);
+    assert!(intersections.iter().any(|i| i.segments == [[0, 3], [0, 4]] && i.overlapping));
+}
+
+#[test]
+fn necks() {
+    assert_eq!(validity::necks(&vec![square(0., 0., 1.)], 0.1), vec![]);
+
+    // Two squares with a gap of 0.01 between them are not a neck, as the gap is not filled
+    assert_eq!(validity::necks(&vec![square(0., 0., 1.), square(1.01, 0., 1.)], 0.1), vec![]);
+
+    // A corridor 0.01 wide between two squares
+    let dumbbell = vec![vec![
+        [0f64, 0.], [2., 0.], [2., 0.995], [3., 0.995], [3., 0.], [5., 0.],
+        [5., 2.], [3., 2.], [3., 1.005], [2., 1.005], [2., 2.], [0., 2.],
+    ]];
+    let necks = validity::necks(&dumbbell, 0.1);
+    assert!(necks.iter().any(|neck| neck.segments == [[0, 2], [0, 8]]));
+    assert!(necks.iter().any(|neck| neck.segments == [[0, 3], [0, 7]]));
+    for neck in &necks {
+        assert!((neck.width - 0.01).abs() < 1e-9);
+        assert!(neck.points.iter().all(|p| (2. ..=3.).contains(&p[0]) && (0.99..=1.01).contains(&p[1])));
+    }
+
+    // A hole touching the edge of its shell pinches the filled area to a point
+    let polygons = vec![square(0., 0., 4.), vec![[0., 2.], [2., 1.], [2., 3.]]];
+    assert_eq!(validity::necks(&polygons, 0.1), vec![
+        Neck { segments: [[0, 3], [1, 0]], points: [[0., 2.], [0., 2.]], width: 0. },
+        Neck { segments: [[0, 3], [1, 2]], points: [[0., 2.], [0., 2.]], width: 0. },
+    ]);
+}
@@ -0,0 +1,37 @@
+use crate::winding::{self, WindingConvention};
+
+use super::util::square;
+
+fn reversed(mut contour: Vec<[f64; 2]>) -> Vec<[f64; 2]> {
+    contour.reverse();
+    contour
+}
+
+#[test]
+fn detect() {
+    let ccw = vec![square(0., 0., 10.), reversed(square(2., 2., 6.)), square(4., 4., 2.), square(20., 0., 1.)];
+    assert_eq!(winding::detect_convention(&ccw), WindingConvention::CounterclockwiseOuter);
+
+    let cw: Vec<_> = ccw.iter().cloned().map(reversed).collect();
+    assert_eq!(winding::detect_convention(&cw), WindingConvention::ClockwiseOuter);
+
+    // The majority decides when a few rings disagree
+    let mut mixed = cw.clone();
+    mixed[3] = square(20., 0., 1.);
+    assert_eq!(winding::detect_convention(&mixed), WindingConvention::ClockwiseOuter);
+
+    // No evidence either way
+    assert_eq!(winding::detect_convention(&Vec::<Vec<[f64; 2]>>::new()), WindingConvention::CounterclockwiseOuter);
+    assert_eq!(winding::detect_convention(&vec![vec![[0f64, 0.], [1., 1.], [2., 2.]]]), WindingConvention::CounterclockwiseOuter);
+}
+
+#[test]
+fn normalize() {
+    let ccw = vec![square(0., 0., 10.), reversed(square(2., 2., 6.))];
+    let cw: Vec<_> = ccw.iter().cloned().map(reversed).collect();
+
+    assert_eq!(winding::normalize(&ccw, None), ccw);
+    assert_eq!(winding::normalize(&cw, None), ccw);
+    assert_eq!(winding::normalize(&cw, Some(WindingConvention::CounterclockwiseOuter)), cw);
+    assert_eq!(winding::normalize(&ccw, Some(WindingConvention::ClockwiseOuter)), cw);
+}
@@ -0,0 +1,217 @@
+//! Triangulation of whole strings of text, using glyph outlines from a [FontProvider].
+//!
+//! Requires the `text` feature. The crate does not parse fonts itself: implement [FontProvider] over a font library
+//! (such as `ttf-parser` or `ab_glyph`) to supply outlines and advances. Outlines are flattened into polygons and
+//! triangulated with the even-odd rule, which matches the nonzero rule of font formats as long as a glyph's contours
+//! do not overlap each other.
+//!
+//! ```
+//! # use triangulate::text::{self, FontProvider, PathCommand, TextOptions};
+//! // A font where every glyph is a square
+//! struct Squares;
+//! impl FontProvider<f64> for Squares {
+//!     fn outline(&self, c: char) -> Vec<PathCommand<f64>> {
+//!         if c == ' ' {
+//!             return Vec::new();
+//!         }
+//!         vec![PathCommand::MoveTo([0., 0.]), PathCommand::LineTo([0.4, 0.]), PathCommand::LineTo([0.4, 0.4]), PathCommand::LineTo([0., 0.4]), PathCommand::Close]
+//!     }
+//!     fn advance(&self, _c: char) -> f64 { 0.5 }
+//!     fn line_height(&self) -> f64 { 1.2 }
+//! }
+//!
+//! let text = text::triangulate(&Squares, "ab c", &TextOptions { size: 10., ..Default::default() }).expect("Triangulation failed");
+//! assert_eq!(text.glyphs.len(), 4);
+//! assert_eq!(text.glyphs[3].origin, [15., 0.]);
+//! assert_eq!(text.glyph_triangles(3).len(), 2);
+//! ```
+
+use core::{convert::Infallible, fmt, ops::Range};
+use alloc::vec::Vec;
+
+use num_traits::real::Real;
+
+use crate::{mesh::Mesh, TriangulationError};
+
+/// One step of a glyph outline, in the style of SVG paths and font outline formats
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum PathCommand<C: Real> {
+    /// Begin a new contour at a point, closing the previous one
+    MoveTo([C; 2]),
+    /// A straight line to a point
+    LineTo([C; 2]),
+    /// A quadratic Bézier curve through a control point to a point, as in TrueType outlines
+    QuadTo([C; 2], [C; 2]),
+    /// A cubic Bézier curve through two control points to a point, as in CFF outlines
+    CurveTo([C; 2], [C; 2], [C; 2]),
+    /// Close the current contour
+    Close,
+}
+
+/// A source of glyph outlines and metrics for [triangulate].
+///
+/// All values are in units of the em size, with y pointing up and the glyph's origin on the baseline; [TextOptions::size]
+/// scales them to output units.
+pub trait FontProvider<C: Real> {
+    /// The outline of the glyph for `c`. Whitespace, and characters the font has no glyph for, have an empty outline
+    fn outline(&self, c: char) -> Vec<PathCommand<C>>;
+    /// The horizontal distance from the origin of the glyph for `c` to the origin of the next glyph
+    fn advance(&self, c: char) -> C;
+    /// An adjustment to the advance between the glyphs for `left` and `right`
+    fn kerning(&self, left: char, right: char) -> C {
+        let _ = (left, right);
+        C::zero()
+    }
+    /// The vertical distance between the baselines of consecutive lines
+    fn line_height(&self) -> C;
+}
+
+/// Layout and flattening options for [triangulate]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct TextOptions<C: Real> {
+    /// The em size, in output units
+    pub size: C,
+    /// The position of the first glyph's origin, on the first line's baseline
+    pub origin: [C; 2],
+    /// The greatest distance allowed between a flattened curve and the curve, in output units
+    pub tolerance: C,
+}
+
+impl<C: Real> Default for TextOptions<C> {
+    /// An em size of 1 at the origin, with curves flattened to within 1/100
+    fn default() -> Self {
+        Self {
+            size: C::one(),
+            origin: [C::zero(), C::zero()],
+            tolerance: C::from(0.01).unwrap_or_else(C::epsilon),
+        }
+    }
+}
+
+/// A character placed by [triangulate]
+#[derive(Debug, PartialEq, Clone)]
+pub struct PositionedGlyph<C: Real> {
+    /// The character
+    pub character: char,
+    /// The byte offset of the character in the string
+    pub byte_offset: usize,
+    /// The position of the glyph's origin
+    pub origin: [C; 2],
+    /// The glyph's triangles, as a range of [TextMesh::mesh]'s triangles
+    pub triangles: Range<usize>,
+}
+
+/// The result of [triangulate]: one mesh for the whole string, with the triangles of each glyph stored contiguously
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextMesh<C: Real + fmt::Debug> {
+    /// The triangles of every glyph
+    pub mesh: Mesh<[C; 2]>,
+    /// Every character of the string except line breaks, in order
+    pub glyphs: Vec<PositionedGlyph<C>>,
+}
+
+impl<C: Real + fmt::Debug> TextMesh<C> {
+    /// The triangles of the glyph at `index` in [TextMesh::glyphs]
+    pub fn glyph_triangles(&self, index: usize) -> &[[usize; 3]] {
+        &self.mesh.triangles[self.glyphs[index].triangles.clone()]
+    }
+}
+
+/// Lay out `text` on consecutive lines (split at `'\n'`) and triangulate every glyph
+pub fn triangulate<C: Real + fmt::Debug>(font: &dyn FontProvider<C>, text: &str, options: &TextOptions<C>) -> Result<TextMesh<C>, TriangulationError<Infallible>> {
+    let mut mesh = Mesh::default();
+    let mut glyphs = Vec::new();
+    let mut origin = options.origin;
+    let mut previous: Option<char> = None;
+
+    for (byte_offset, character) in text.char_indices() {
+        match character {
+            '\n' => {
+                origin = [options.origin[0], origin[1] - font.line_height() * options.size];
+                previous = None;
+                continue;
+            }
+            '\r' => continue,
+            _ => {}
+        }
+        if let Some(previous) = previous {
+            origin[0] = origin[0] + font.kerning(previous, character) * options.size;
+        }
+
+        let contours = flatten(&font.outline(character), |c| [origin[0] + c[0] * options.size, origin[1] + c[1] * options.size], options.tolerance);
+        let start = mesh.triangles.len();
+        if !contours.is_empty() {
+            mesh.append(Mesh::from_polygons(&contours)?);
+        }
+        glyphs.push(PositionedGlyph { character, byte_offset, origin, triangles: start..mesh.triangles.len() });
+
+        origin[0] = origin[0] + font.advance(character) * options.size;
+        previous = Some(character);
+    }
+
+    Ok(TextMesh { mesh, glyphs })
+}
+
+/// Flatten an outline into closed contours, after mapping its points with `transform`
+fn flatten<C: Real>(commands: &[PathCommand<C>], transform: impl Fn([C; 2]) -> [C; 2], tolerance: C) -> Vec<Vec<[C; 2]>> {
+    let mut contours = Vec::new();
+    let mut contour: Vec<[C; 2]> = Vec::new();
+    let tolerance = tolerance.max(C::epsilon());
+
+    fn finish<C: Real>(contours: &mut Vec<Vec<[C; 2]>>, mut contour: Vec<[C; 2]>) {
+        contour.dedup();
+        while contour.len() > 1 && contour.first() == contour.last() {
+            contour.pop();
+        }
+        if contour.len() >= 3 {
+            contours.push(contour);
+        }
+    }
+
+    for command in commands {
+        let last = contour.last().copied();
+        match *command {
+            PathCommand::MoveTo(c) => {
+                finish(&mut contours, core::mem::take(&mut contour));
+                contour.push(transform(c));
+            }
+            PathCommand::LineTo(c) => contour.push(transform(c)),
+            PathCommand::QuadTo(c1, c2) => {
+                let (p0, p1, p2) = (last.unwrap_or_else(|| transform(c1)), transform(c1), transform(c2));
+                // Uniform steps keep within the tolerance when |B''| h² / 8 <= tolerance
+                let dd = [p0[0] - p1[0] - p1[0] + p2[0], p0[1] - p1[1] - p1[1] + p2[1]];
+                let segments = segment_count(dd[0].hypot(dd[1]) / (tolerance + tolerance + tolerance + tolerance));
+                for k in 1..=segments {
+                    let t = C::from(k).unwrap_or_else(C::one) / C::from(segments).unwrap_or_else(C::one);
+                    let s = C::one() - t;
+                    let [w0, w1, w2] = [s * s, (s + s) * t, t * t];
+                    contour.push([w0 * p0[0] + w1 * p1[0] + w2 * p2[0], w0 * p0[1] + w1 * p1[1] + w2 * p2[1]]);
+                }
+            }
+            PathCommand::CurveTo(c1, c2, c3) => {
+                let (p0, p1, p2, p3) = (last.unwrap_or_else(|| transform(c1)), transform(c1), transform(c2), transform(c3));
+                let dd0 = [p0[0] - p1[0] - p1[0] + p2[0], p0[1] - p1[1] - p1[1] + p2[1]];
+                let dd1 = [p1[0] - p2[0] - p2[0] + p3[0], p1[1] - p2[1] - p2[1] + p3[1]];
+                let three = C::one() + C::one() + C::one();
+                let segments = segment_count(three * dd0[0].hypot(dd0[1]).max(dd1[0].hypot(dd1[1])) / (tolerance + tolerance + tolerance + tolerance));
+                for k in 1..=segments {
+                    let t = C::from(k).unwrap_or_else(C::one) / C::from(segments).unwrap_or_else(C::one);
+                    let s = C::one() - t;
+                    let [w0, w1, w2, w3] = [s * s * s, three * s * s * t, three * s * t * t, t * t * t];
+                    contour.push([
+                        w0 * p0[0] + w1 * p1[0] + w2 * p2[0] + w3 * p3[0],
+                        w0 * p0[1] + w1 * p1[1] + w2 * p2[1] + w3 * p3[1],
+                    ]);
+                }
+            }
+            PathCommand::Close => finish(&mut contours, core::mem::take(&mut contour)),
+        }
+    }
+    finish(&mut contours, contour);
+    contours
+}
+
+// The number of uniform segments with at most the tolerance of error, given the square of that number's lower bound
+fn segment_count<C: Real>(squared: C) -> usize {
+    squared.sqrt().ceil().to_usize().unwrap_or(1).clamp(1, 1024)
+}
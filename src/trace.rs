@@ -0,0 +1,185 @@
+//! Structured traces of the triangulation algorithm, for visualizing its execution in external tools or in teaching
+//! material.
+//!
+//! Triangulation first builds a trapezoidation (Seidel's algorithm): the segments of the polygons are inserted in a
+//! random order, and inserting each segment first inserts any of its end vertices which are not yet present. A vertex
+//! splits the trapezoid containing it horizontally, and a segment splits every trapezoid it crosses vertically. The
+//! trapezoids inside the polygons are then split into monotone polygons, which are triangulated.
+//!
+//! [trace] records a [TraceStep] for every insertion, with a snapshot of all trapezoids after it, and the triangles
+//! in the order they are emitted. [Trace::to_json] writes the trace as JSON. Since the insertion order is random, so
+//! are the steps of the trace.
+//!
+//! ```
+//! # use triangulate::trace::{self, TraceEvent};
+//! let square = vec![vec![[0f64, 0.], [1., 0.], [1., 1.], [0., 1.]]];
+//! let trace = trace::trace(&square).expect("Triangulation failed");
+//! // Four vertices and four segments
+//! assert_eq!(trace.steps.len(), 8);
+//! assert!(matches!(trace.steps[0].event, TraceEvent::InsertVertex { .. }));
+//! assert_eq!(trace.triangles.len(), 2);
+//! assert!(trace.to_json().starts_with("{\"steps\":[{\"event\":\"insert_vertex\""));
+//! ```
+
+use core::{convert::Infallible, fmt::{self, Write}};
+use alloc::{string::String, vec::Vec};
+
+use crate::{formats, ListFormat, PolygonList, Scalar, TrapezoidGeometry, TriangulationError, Vertex, trapezoidation::TrapezoidationState};
+
+/// A step of the trapezoidation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TraceEvent<C: Scalar> {
+    /// A vertex was inserted, splitting the trapezoid containing it into the trapezoids below and above it
+    InsertVertex {
+        /// The position of the vertex
+        position: [C; 2],
+    },
+    /// A segment was inserted between two inserted vertices, splitting the trapezoids it crosses into left and right
+    InsertSegment {
+        /// The lower end of the segment
+        from: [C; 2],
+        /// The upper end of the segment
+        to: [C; 2],
+    },
+}
+
+/// An event of the trapezoidation and the trapezoids after it
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceStep<C: Scalar> {
+    /// What happened in this step
+    pub event: TraceEvent<C>,
+    /// All trapezoids after the step. Trapezoids keep their index between steps, and new trapezoids are added at the end.
+    /// Which trapezoids are inside the polygons is only known once all segments are inserted, so
+    /// [inside](TrapezoidGeometry::inside) is `false` in all steps but the last
+    pub trapezoids: Vec<TrapezoidGeometry<C>>,
+}
+
+/// The execution of a triangulation, created by [trace]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trace<C: Scalar> {
+    /// The steps of the trapezoidation, in order
+    pub steps: Vec<TraceStep<C>>,
+    /// Whether each trapezoid of the final step is inside the polygons
+    pub interior: Vec<bool>,
+    /// The corners of the triangles, in the order they are emitted
+    pub triangles: Vec<[[C; 2]; 3]>,
+}
+
+/// Triangulate `polygons`, recording the steps of the algorithm
+pub fn trace<'p, P: PolygonList<'p> + ?Sized>(polygons: &'p P) -> Result<Trace<<P::Vertex as Vertex>::Coordinate>, TriangulationError<Infallible>> {
+    let (trapezoidation, mut steps) = TrapezoidationState::<P>::new(polygons).build_traced().map_err(TriangulationError::TrapezoidationError)?;
+    let trapezoids = trapezoidation.trapezoids().map_err(TriangulationError::TrapezoidationError)?.collect::<Vec<_>>();
+    let interior = trapezoids.iter().map(|t| t.inside).collect();
+    if let Some(last) = steps.last_mut() {
+        last.trapezoids = trapezoids;
+    }
+    let mut indices = Vec::<P::Index>::new();
+    trapezoidation.triangulate(formats::IndexedListFormat::new(&mut indices).into_fan_format())?;
+    let position = |index: &P::Index| {
+        let v = polygons.get_vertex(index.clone());
+        [v.x(), v.y()]
+    };
+    let triangles = indices.chunks_exact(3).map(|t| [position(&t[0]), position(&t[1]), position(&t[2])]).collect();
+    Ok(Trace { steps, interior, triangles })
+}
+
+impl<C: Scalar> Trace<C> {
+    /// Write the trace as JSON.
+    ///
+    /// The object has the fields `steps`, `interior` and `triangles`, as in [Trace]. Each step has an `event` of
+    /// `"insert_vertex"` (with a `position`) or `"insert_segment"` (with `from` and `to`), and its `trapezoids`, each
+    /// with `bottom`, `top`, `left` and `right` fields which are `null` when the trapezoid is unbounded. Points are
+    /// `[x, y]` arrays, and coordinates which cannot be represented in JSON are written as `null`.
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        // Writing to a `String` cannot fail
+        let _ = self.write_json(&mut json);
+        json
+    }
+
+    fn write_json(&self, w: &mut impl Write) -> fmt::Result {
+        write!(w, "{{\"steps\":[")?;
+        for (i, step) in self.steps.iter().enumerate() {
+            if i > 0 {
+                write!(w, ",")?;
+            }
+            match &step.event {
+                TraceEvent::InsertVertex { position } => {
+                    write!(w, "{{\"event\":\"insert_vertex\",\"position\":")?;
+                    write_point(w, Some(position))?;
+                }
+                TraceEvent::InsertSegment { from, to } => {
+                    write!(w, "{{\"event\":\"insert_segment\",\"from\":")?;
+                    write_point(w, Some(from))?;
+                    write!(w, ",\"to\":")?;
+                    write_point(w, Some(to))?;
+                }
+            }
+            write!(w, ",\"trapezoids\":[")?;
+            for (j, t) in step.trapezoids.iter().enumerate() {
+                if j > 0 {
+                    write!(w, ",")?;
+                }
+                write!(w, "{{\"bottom\":")?;
+                write_point(w, t.bottom.as_ref())?;
+                write!(w, ",\"top\":")?;
+                write_point(w, t.top.as_ref())?;
+                write!(w, ",\"left\":")?;
+                write_segment(w, t.left.as_ref())?;
+                write!(w, ",\"right\":")?;
+                write_segment(w, t.right.as_ref())?;
+                write!(w, "}}")?;
+            }
+            write!(w, "]}}")?;
+        }
+        write!(w, "],\"interior\":[")?;
+        for (i, inside) in self.interior.iter().enumerate() {
+            write!(w, "{}{}", if i > 0 { "," } else { "" }, inside)?;
+        }
+        write!(w, "],\"triangles\":[")?;
+        for (i, t) in self.triangles.iter().enumerate() {
+            write!(w, "{}[", if i > 0 { "," } else { "" })?;
+            for (k, c) in t.iter().enumerate() {
+                if k > 0 {
+                    write!(w, ",")?;
+                }
+                write_point(w, Some(c))?;
+            }
+            write!(w, "]")?;
+        }
+        write!(w, "]}}")
+    }
+}
+
+fn write_number<C: Scalar>(w: &mut impl Write, c: &C) -> fmt::Result {
+    match c.to_f64() {
+        Some(x) if x.is_finite() => write!(w, "{x}"),
+        _ => write!(w, "null"),
+    }
+}
+
+fn write_point<C: Scalar>(w: &mut impl Write, c: Option<&[C; 2]>) -> fmt::Result {
+    match c {
+        Some([x, y]) => {
+            write!(w, "[")?;
+            write_number(w, x)?;
+            write!(w, ",")?;
+            write_number(w, y)?;
+            write!(w, "]")
+        }
+        None => write!(w, "null"),
+    }
+}
+
+fn write_segment<C: Scalar>(w: &mut impl Write, segment: Option<&[[C; 2]; 2]>) -> fmt::Result {
+    match segment {
+        Some([c0, c1]) => {
+            write!(w, "[")?;
+            write_point(w, Some(c0))?;
+            write!(w, ",")?;
+            write_point(w, Some(c1))?;
+            write!(w, "]")
+        }
+        None => write!(w, "null"),
+    }
+}
@@ -1,8 +1,9 @@
-use std::fmt::Debug;
+use core::fmt::Debug;
 
 use crate::{Vertex, VertexIndex, idx::{Idx, IdxDisplay}, nexus::Nexus, querynode::QueryNode, segment::Segment};
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(bound = ""))]
 pub(crate) struct Trapezoid<V: Vertex, Index: VertexIndex> {
     left: Option<Idx<Segment<V, Index>>>,
     right: Option<Idx<Segment<V, Index>>>,
@@ -11,15 +12,21 @@ pub(crate) struct Trapezoid<V: Vertex, Index: VertexIndex> {
     sink: Idx<QueryNode<V, Index>>,
 }
 
-impl<V: Vertex, Index: VertexIndex> std::fmt::Display for Trapezoid<V, Index> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl<V: Vertex, Index: VertexIndex> Clone for Trapezoid<V, Index> {
+    fn clone(&self) -> Self {
+        Self { left: self.left, right: self.right, down: self.down, up: self.up, sink: self.sink }
+    }
+}
+
+impl<V: Vertex, Index: VertexIndex> core::fmt::Display for Trapezoid<V, Index> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         if let Some(up) = self.up {
             writeln!(f, "-{}-", up)?;
         }
         if let Some(left) = self.left {
             write!(f, "[{}]", left)?;
         }
-        std::fmt::Display::fmt(&self.sink, f)?;
+        core::fmt::Display::fmt(&self.sink, f)?;
         if let Some(right) = self.right {
             write!(f, "[{}]", right)?;
         }
@@ -32,7 +39,7 @@ impl<V: Vertex, Index: VertexIndex> std::fmt::Display for Trapezoid<V, Index> {
 }
 
 impl<V: Vertex, Index: VertexIndex> IdxDisplay for Trapezoid<V, Index> {
-    fn fmt(f: &mut std::fmt::Formatter<'_>, idx: usize) -> std::fmt::Result {
+    fn fmt(f: &mut core::fmt::Formatter<'_>, idx: usize) -> core::fmt::Result {
         write!(f, "t{}", idx)
     }
 }
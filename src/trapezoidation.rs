@@ -1,47 +1,61 @@
-use std::iter;
+use core::{error, iter, marker::PhantomData};
+use alloc::{format, string::ToString, sync::Arc, vec, vec::Vec};
 
-use rand::prelude::SliceRandom;
+use rand::{prelude::SliceRandom, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use num_traits::{One, Zero, real::Real};
+
+use crate::Scalar;
 use zot::Ot;
-use crate::{FanFormat, FanBuilderState, PolygonList, PolygonListExt, PolygonElement, Vertex, VertexIndex, errors::{TriangulationError, InternalError, TrapezoidationError}, idx::{Idx, VecExt, SliceExt}, math::{math_n, is_left_of_line}, monotone::MonotoneBuilder, nexus::{FinalNexusType, Nexus, DividerDirection}, querynode::{QueryNode, QueryNodeBranch}, segment::Segment, trapezoid::Trapezoid, Coords, FanBuilder};
+use crate::{compat::OnceLock, components::{ComponentSummary, Components, UnionFind}, FanFormat, FanBuilderState, PolygonList, PolygonListExt, PolygonElement, Region, Vertex, VertexIndex, errors::{TriangulationError, InternalError, TrapezoidationError, ErrorStage}, idx::{Idx, ForkableVec, VecExt, SliceExt}, math::{self, math_n}, kernel::{GeometryKernel, DefaultKernel, is_left_of_line, is_below, is_below_or_equal}, monotone::{Chain, Monotone, MonotoneBuilder}, nexus::{FinalNexusType, Nexus, DividerDirection}, querynode::{QueryNode, QueryNodeBranch}, segment::Segment, trapezoid::Trapezoid, Coords, FanBuilder, MonotoneFormat, outputs::{MonotoneBuilder as MonotoneOutputBuilder, MonotoneResult}, MergePolicy};
 
 #[cfg(feature = "_debugging")]
-use std::fmt;
+use core::fmt;
 #[cfg(feature = "_debugging")]
-use crate::{debug, monotone::Monotone, VertexExt};
+use crate::{debug, VertexExt};
 #[cfg(feature = "_debugging")]
 use num_traits::ToPrimitive;
+#[cfg(feature = "trace")]
+use crate::trace;
+
+// An interval of a horizontal line, where a `None` end extends to infinity
+type Interval<'p, P> = [Option<<<P as PolygonList<'p>>::Vertex as Vertex>::Coordinate>; 2];
 
-trait TrapezoidationStructure<'p, P: PolygonList<'p> + ?Sized + 'p> {
+#[cfg(feature = "trace")]
+type TraceSteps<'p, P> = Vec<trace::TraceStep<<<P as PolygonList<'p>>::Vertex as Vertex>::Coordinate>>;
+
+trait TrapezoidationStructure<'p, P: PolygonList<'p> + ?Sized + 'p, K: GeometryKernel<<P::Vertex as Vertex>::Coordinate>> {
     fn ps(&self) -> PolygonListExt<'p, P>;
-    fn ns(&self) -> &[Nexus<P::Vertex, P::Index>];
-    fn ss(&self) -> &[Segment<P::Vertex, P::Index>];
-    fn ts(&self) -> &[Trapezoid<P::Vertex, P::Index>];
-    fn qs(&self) -> &[QueryNode<P::Vertex, P::Index>];
+    fn ns(&self) -> &ForkableVec<Nexus<P::Vertex, P::Index>>;
+    fn ss(&self) -> &ForkableVec<Segment<P::Vertex, P::Index>>;
+    fn ts(&self) -> &ForkableVec<Trapezoid<P::Vertex, P::Index>>;
+    fn qs(&self) -> &ForkableVec<QueryNode<P::Vertex, P::Index>>;
+
+    #[cfg(feature = "_debugging")]
+    fn svg_structure<'t>(&'t self, triangles: &'t [[P::Index; 3]]) -> SvgStructure<'t, 'p, P> {
+        SvgStructure { ps: self.ps(), ns: self.ns(), ss: self.ss(), ts: self.ts(), triangles }
+    }
 
     fn query_node_root(&self) -> Idx<QueryNode<P::Vertex, P::Index>> {
         Idx::new(0)
     }
 
-    fn find_trapezoid(&self, c: Coords<<P::Vertex as Vertex>::Coordinate>) -> (Idx<QueryNode<P::Vertex, P::Index>>, Idx<Trapezoid<P::Vertex, P::Index>>) {
+    fn find_trapezoid(&self, c: &Coords<<P::Vertex as Vertex>::Coordinate>) -> (Idx<QueryNode<P::Vertex, P::Index>>, Idx<Trapezoid<P::Vertex, P::Index>>) {
         self.find_trapezoid_from_root(c, self.query_node_root())
     }
 
     #[inline(never)]
-    fn find_trapezoid_from_root(&self, c: Coords<<P::Vertex as Vertex>::Coordinate>, qi_root: Idx<QueryNode<P::Vertex, P::Index>>) -> (Idx<QueryNode<P::Vertex, P::Index>>, Idx<Trapezoid<P::Vertex, P::Index>>) {
+    fn find_trapezoid_from_root(&self, c: &Coords<<P::Vertex as Vertex>::Coordinate>, qi_root: Idx<QueryNode<P::Vertex, P::Index>>) -> (Idx<QueryNode<P::Vertex, P::Index>>, Idx<Trapezoid<P::Vertex, P::Index>>) {
         let mut qi = qi_root;
         loop {
             // unsafe: `qs` is append-only and `Idx`s are never modified, so they will always remain valid within the same trapezoidation
-            match unsafe { self.qs().get_unchecked(qi.usize()) } {
+            match unsafe { self.qs().get_unchecked(qi) } {
                 QueryNode::Branch(left, right, branch) => {
-                    let use_left = match *branch {
+                    let use_left = match branch {
                         // The right trapezoid will be chosen if the vertex is one of the edge's endpoints
-                        QueryNodeBranch::X(c_min_x, c_max_x) => is_left_of_line(c_min_x, c_max_x, c),
+                        QueryNodeBranch::X(c_min_x, c_max_x) => is_left_of_line::<K, _>(c_min_x, c_max_x, c),
                         // Choose the lower trapezoid if this corresponds to an existing vertex (to make horizontal splitting easier)
-                        QueryNodeBranch::Y(c_y) => { //self.ps()[vi.clone()] <= self.ps()[self.ns()[*ni_y].vertex()],
-                            let left = c;
-                            let right = c_y;
-                            left <= right
-                        }
+                        QueryNodeBranch::Y(c_y) => is_below_or_equal::<K, _>(c, c_y),
                     };
                     qi = if use_left { *left } else { *right };
                 },
@@ -52,7 +66,7 @@ trait TrapezoidationStructure<'p, P: PolygonList<'p> + ?Sized + 'p> {
 }
 
 #[cfg(feature = "_debugging")]
-fn trapezoidation_fmt<'p, P: PolygonList<'p> + ?Sized, T: TrapezoidationStructure<'p, P>>(w: &mut impl std::io::Write, trapezoidation: &T) -> std::io::Result<()> {
+fn trapezoidation_fmt<'p, P: PolygonList<'p> + ?Sized, K: GeometryKernel<<P::Vertex as Vertex>::Coordinate>, T: TrapezoidationStructure<'p, P, K>>(w: &mut impl std::io::Write, trapezoidation: &T) -> std::io::Result<()> {
     writeln!(w, "nexuses:")?;
     for (i, n) in trapezoidation.ns().iter().enumerate() {
         writeln!(w, "{}:", Idx::<Nexus<P::Vertex, P::Index>>::new(i))?;
@@ -62,7 +76,7 @@ fn trapezoidation_fmt<'p, P: PolygonList<'p> + ?Sized, T: TrapezoidationStructur
     writeln!(w, "segments:")?;
     write!(w, "[")?;
     if trapezoidation.ss().len() > 0 {
-        write!(w, "{:?}", trapezoidation.ss()[0])?;
+        write!(w, "{:?}", trapezoidation.ss()[Idx::new(0)])?;
     }
     for s in trapezoidation.ss().iter().skip(1) {
         write!(w, ", {:?}", s)?;
@@ -87,10 +101,10 @@ enum VertexLocation<V: Vertex, Index: VertexIndex> {
 }
 
 impl<V: Vertex, Index: VertexIndex> VertexLocation<V, Index> {
-    pub fn coords(&self) -> Coords<V::Coordinate> {
+    pub fn coords(&self) -> &Coords<V::Coordinate> {
         match self {
-            VertexLocation::Pending(c, _, _) => *c,
-            VertexLocation::Inserted(c, _) => *c,
+            VertexLocation::Pending(c, _, _) => c,
+            VertexLocation::Inserted(c, _) => c,
         }
     }
 }
@@ -98,8 +112,8 @@ impl<V: Vertex, Index: VertexIndex> VertexLocation<V, Index> {
 impl<V: Vertex, Index: VertexIndex> Clone for VertexLocation<V, Index> {
     fn clone(&self) -> Self {
         match self {
-            Self::Pending(arg0, arg1, arg2) => Self::Pending(*arg0, arg1.clone(), *arg2),
-            Self::Inserted(arg0, arg1) => Self::Inserted(*arg0, *arg1),
+            Self::Pending(arg0, arg1, arg2) => Self::Pending(arg0.clone(), arg1.clone(), *arg2),
+            Self::Inserted(arg0, arg1) => Self::Inserted(arg0.clone(), *arg1),
         }
     }
 }
@@ -134,6 +148,11 @@ impl DebugInfo {
             return None;
         }
 
+        Some(Self::svg_context_at(ps, output_path, output_level, show_labels))
+    }
+
+    /// A context viewing all of the polygons, with a margin around them
+    fn svg_context_at<'p, P: PolygonList<'p> + ?Sized>(ps: &PolygonListExt<'p, P>, output_path: std::path::PathBuf, output_level: debug::svg::SvgOutputLevel, show_labels: bool) -> debug::svg::SvgContext {
         let min_value = f32::MIN;
         let max_value = f32::MAX;
 
@@ -160,7 +179,7 @@ impl DebugInfo {
         view_y_min -= h * margin_scale;
         view_y_max += h * margin_scale;
 
-        Some(debug::svg::SvgContext {
+        debug::svg::SvgContext {
             output_path, 
             output_level,
             view_x_min, 
@@ -168,34 +187,68 @@ impl DebugInfo {
             view_y_min, 
             view_y_max,
             show_labels,
-        })
+        }
+    }
+}
+
+/// The nexuses, segments, trapezoids and query nodes of a trapezoidation, kept to build another one without allocating
+pub(crate) struct Storage<V: Vertex, Index: VertexIndex> {
+    ns: ForkableVec<Nexus<V, Index>>,
+    ss: ForkableVec<Segment<V, Index>>,
+    ts: ForkableVec<Trapezoid<V, Index>>,
+    qs: ForkableVec<QueryNode<V, Index>>,
+}
+
+impl<V: Vertex, Index: VertexIndex> Storage<V, Index> {
+    fn clear(&mut self) {
+        self.ns.clear();
+        self.ss.clear();
+        self.ts.clear();
+        self.qs.clear();
     }
 }
 
-pub(crate) struct TrapezoidationState<'p, P: PolygonList<'p> + ?Sized> {
+impl<V: Vertex, Index: VertexIndex> Default for Storage<V, Index> {
+    fn default() -> Self {
+        Self { ns: ForkableVec::new(), ss: ForkableVec::new(), ts: ForkableVec::new(), qs: ForkableVec::new() }
+    }
+}
+
+pub(crate) struct TrapezoidationState<'p, P: PolygonList<'p> + ?Sized, K: GeometryKernel<<P::Vertex as Vertex>::Coordinate> = DefaultKernel> {
     ps: PolygonListExt<'p, P>,
-    ns: Vec<Nexus<P::Vertex, P::Index>>,
-    ss: Vec<Segment<P::Vertex, P::Index>>,
-    ts: Vec<Trapezoid<P::Vertex, P::Index>>,
-    qs: Vec<QueryNode<P::Vertex, P::Index>>,
+    ns: ForkableVec<Nexus<P::Vertex, P::Index>>,
+    ss: ForkableVec<Segment<P::Vertex, P::Index>>,
+    ts: ForkableVec<Trapezoid<P::Vertex, P::Index>>,
+    qs: ForkableVec<QueryNode<P::Vertex, P::Index>>,
     #[cfg(feature = "_debugging")]
     debug_info: DebugInfo,
+    #[cfg(feature = "trace")]
+    trace: Option<TraceSteps<'p, P>>,
+    seed: Option<u64>,
+    _kernel: PhantomData<K>,
 }
 
-impl<'p, P: PolygonList<'p> + ?Sized> TrapezoidationState<'p, P> {
+impl<'p, P: PolygonList<'p> + ?Sized, K: GeometryKernel<<P::Vertex as Vertex>::Coordinate>> TrapezoidationState<'p, P, K> {
     pub fn new(ps: &'p P) -> Self {
+        Self::with_storage(ps, Storage::default())
+    }
+
+    /// Start a trapezoidation in the pages of `storage`, which is cleared first
+    pub fn with_storage(ps: &'p P, mut storage: Storage<P::Vertex, P::Index>) -> Self {
         let ps = PolygonListExt::new(ps);
-        let vertex_count = ps.vertex_count();
 
-        // TODO What is the upper bound for number of query nodes?
-        // Just allocate a large amount for now
-        let mut qs = Vec::with_capacity(vertex_count * 4);
+        // Storage which was forked is plain again after clearing
+        storage.clear();
+        let Storage { mut ns, mut ss, mut ts, mut qs } = storage;
+        let vertex_count = ps.contour_vertex_count();
+        qs.reserve(vertex_count * 4);
+        ts.reserve(vertex_count * 2 + 1);
+        ns.reserve(vertex_count);
+        ss.reserve(vertex_count);
         let ti = Idx::new(0);
         let q = QueryNode::root(ti);
         let qi = qs.push_get_index(q);
         let t = Trapezoid::all(qi);
-
-        let mut ts = Vec::with_capacity(vertex_count * 2 + 1);
         ts.push(t);
 
         #[cfg(feature = "_debugging")]
@@ -203,16 +256,24 @@ impl<'p, P: PolygonList<'p> + ?Sized> TrapezoidationState<'p, P> {
 
         Self {
             ps,
-            ns: Vec::with_capacity(vertex_count),
-            ss: Vec::with_capacity(vertex_count),
+            ns,
+            ss,
             ts,
             qs,
             #[cfg(feature = "_debugging")]
             debug_info,
+            #[cfg(feature = "trace")]
+            trace: None,
+            seed: None,
+            _kernel: PhantomData,
         }
     }
 
-    
+    /// Shuffle the insertion order with a generator seeded from `seed`, rather than a random one
+    pub fn with_seed(mut self, seed: Option<u64>) -> Self {
+        self.seed = seed;
+        self
+    }
 
     #[cfg(feature = "_debugging")]
     fn output_svg(&mut self, style: debug::svg::SvgTriangulationStyle<'p, P::Vertex, P::Index>, level: debug::svg::SvgOutputLevel) {
@@ -227,7 +288,7 @@ impl<'p, P: PolygonList<'p> + ?Sized> TrapezoidationState<'p, P> {
                 }
 
                 let mut svg = debug::svg::SvgOutput::new(&svg_context, style);
-                let _ = svg.append_element(self, &());
+                let _ = svg.append_element(&self.svg_structure(&[]), &());
                 
                 let path: std::path::PathBuf = format!("{:03}", self.debug_info.current_step).into();
                 let path = path.join(format!("{:03}.svg", self.debug_info.current_substep));
@@ -245,7 +306,7 @@ impl<'p, P: PolygonList<'p> + ?Sized> TrapezoidationState<'p, P> {
                 let path = svg_context.output_path.join(format!("{:03}", self.debug_info.current_step)).join("state.txt");
                 if let Ok(f) = std::fs::File::create(path) {
                     let mut w = std::io::BufWriter::new(&f);
-                    let _ = trapezoidation_fmt(&mut w, self);
+                    let _ = trapezoidation_fmt::<P, K, _>(&mut w, self);
                 }
 
                 self.debug_info.current_step += 1;
@@ -254,50 +315,105 @@ impl<'p, P: PolygonList<'p> + ?Sized> TrapezoidationState<'p, P> {
         }
     }
 
-    pub fn build(mut self) -> Result<Trapezoidation<'p, P>, TrapezoidationError> {
+    #[cfg(feature = "trace")]
+    fn record(&mut self, event: trace::TraceEvent<<P::Vertex as Vertex>::Coordinate>) {
+        if self.trace.is_none() {
+            return;
+        }
+        // Which trapezoids are inside is only known once the trapezoidation is complete
+        let trapezoids = self.ts.iter().map(|t| TrapezoidGeometry::new(&self.ns, &self.ss, t, false)).collect();
+        if let Some(steps) = &mut self.trace {
+            steps.push(trace::TraceStep { event, trapezoids });
+        }
+    }
+
+    /// Build the trapezoidation, recording each insertion with a snapshot of the trapezoids after it
+    #[cfg(feature = "trace")]
+    pub fn build_traced(mut self) -> Result<(Trapezoidation<'p, P, K>, TraceSteps<'p, P>), TrapezoidationError> {
+        self.trace = Some(Vec::new());
+        self.add_polygons(0)?;
+        let steps = self.trace.take().unwrap_or_default();
+        Ok((Trapezoidation::new(self), steps))
+    }
+
+    pub fn build(mut self) -> Result<Trapezoidation<'p, P, K>, TrapezoidationError> {
+        self.add_polygons(0)?;
+
+        #[cfg(feature = "_debugging")]
+        let svg_context = self.debug_info.svg_context.take();
+        let trapezoidation = Trapezoidation::new(self);
+        #[cfg(feature = "_debugging")]
+        if let Some(svg_context) = svg_context {
+            let _ = trapezoidation.write_svg(&svg_context, "result.svg");
+        }
+        Ok(trapezoidation)
+    }
+
+    /// Insert the polygons of `ps`, after the first `inserted` vertices, which must end a polygon
+    fn add_polygons(&mut self, inserted: usize) -> Result<(), TrapezoidationError> {
         // Track the best-known location of each vertex. Initially, all we have is the root QueryNode.
         // Periodically, for each uninserted vertex, we search for the trapezoid that contains the point and update the QueryNode.
         // Finally, once a vertex is inserted, we replace the QueryNode with the exact Nexus we created for the vertex
 
         // Allocate as if there is a single polygon (ensuring no reallocations)
-        let mut v_lookup: Vec<VertexLocation<P::Vertex, P::Index>> = Vec::with_capacity(self.ps.vertex_count());
+        let mut v_lookup: Vec<VertexLocation<P::Vertex, P::Index>> = Vec::with_capacity(self.ps.contour_vertex_count());
+
+        let mut skip = inserted;
+        // Whether the last skipped vertex's polygon has not ended yet
+        let mut in_skipped = false;
+        // The number of polygons with vertices so far, skipped or inserted
+        let mut polygon = 0;
 
         // Ensure the iteration ends with NewPolygon
         for polygon_vertex in self.ps.clone().iter_polygon_vertices().map(Into::into).chain(iter::once(PolygonElement::NewPolygon)) {
             match polygon_vertex {
+                PolygonElement::ContinuePolygon(_) if skip > 0 => {
+                    skip -= 1;
+                    in_skipped = true;
+                }
                 PolygonElement::ContinuePolygon(index) => {
+                    if in_skipped {
+                        return Err(TrapezoidationError::InternalError(InternalError::new("Appended polygons must not continue an inserted polygon").with_stage(ErrorStage::SegmentThreading)));
+                    }
                     let c = self.ps[index.clone()].coords();
                     v_lookup.push(VertexLocation::Pending(c, index, self.query_node_root()));
                 }
                 PolygonElement::NewPolygon => {
+                    if core::mem::replace(&mut in_skipped, false) {
+                        polygon += 1;
+                    }
                     let v_count = v_lookup.len();
                     if v_count > 0 {
                         if v_count < 3 {
                             return Err(TrapezoidationError::NotEnoughVertices(v_count));
                         } else {
-                            self.add_polygon(v_lookup.as_mut_slice()).map_err(TrapezoidationError::InternalError)?;
+                            self.add_polygon(v_lookup.as_mut_slice())
+                                .map_err(|error| TrapezoidationError::InternalError(error.with_polygon(v_lookup.iter().map(|vl| vl.coords().array()), polygon).with_stage(ErrorStage::SegmentThreading)))?;
                             v_lookup.clear();
+                            polygon += 1;
                         }
                     }
                 }
             }
         }
 
-        Ok(Trapezoidation::new(self))
+        Ok(())
     }
 
     #[inline(never)]
     fn add_polygon(&mut self, vls: &mut [VertexLocation<P::Vertex, P::Index>]) -> Result<(), InternalError> {
         #[inline(never)]
-        fn add_nth_segment<'p, P: PolygonList<'p> + ?Sized>(state: &mut TrapezoidationState<'p, P>, vls: &mut [VertexLocation<P::Vertex, P::Index>], si: usize) -> Result<usize, InternalError> {
+        fn add_nth_segment<'p, P: PolygonList<'p> + ?Sized, K: GeometryKernel<<P::Vertex as Vertex>::Coordinate>>(state: &mut TrapezoidationState<'p, P, K>, vls: &mut [VertexLocation<P::Vertex, P::Index>], si: usize) -> Result<usize, InternalError> {
             #[inline(never)]
-            fn add_vertex<'p, P: PolygonList<'p> + ?Sized>(state: &mut TrapezoidationState<'p, P>, vl: &mut VertexLocation<P::Vertex, P::Index>, index: P::Index, qi: Idx<QueryNode<P::Vertex, P::Index>>) -> Result<Idx<Nexus<P::Vertex, P::Index>>, InternalError> {
+            fn add_vertex<'p, P: PolygonList<'p> + ?Sized, K: GeometryKernel<<P::Vertex as Vertex>::Coordinate>>(state: &mut TrapezoidationState<'p, P, K>, vl: &mut VertexLocation<P::Vertex, P::Index>, index: P::Index, qi: Idx<QueryNode<P::Vertex, P::Index>>) -> Result<Idx<Nexus<P::Vertex, P::Index>>, InternalError> {
                 let ni = state.add_vertex(index, qi)?;
 
                 #[cfg(feature = "_debugging")]
                 state.output_svg(debug::svg::SvgTriangulationStyle::highlight_nexus(ni), debug::svg::SvgOutputLevel::AllSteps);
+                #[cfg(feature = "trace")]
+                state.record(trace::TraceEvent::InsertVertex { position: state.ns[ni].coords().array() });
                 
-                *vl = VertexLocation::Inserted(vl.coords(), ni);
+                *vl = VertexLocation::Inserted(vl.coords().clone(), ni);
                 Ok(ni)
             }
 
@@ -308,7 +424,7 @@ impl<'p, P: PolygonList<'p> + ?Sized> TrapezoidationState<'p, P> {
 
             let (ni0, ni1, added_vertices) = match (vl0, vl1) {
                 (VertexLocation::Pending(c0, index0, qi0), VertexLocation::Pending(c1, index1, qi1)) => {
-                    if c0 < c1 {
+                    if is_below::<K, _>(&c0, &c1) {
                         let ni0 = add_vertex(state, &mut vls[vli0], index0, qi0)?;
                         let ni1 = add_vertex(state, &mut vls[vli1], index1, qi1)?;
                         (ni0, ni1, 2)
@@ -329,16 +445,19 @@ impl<'p, P: PolygonList<'p> + ?Sized> TrapezoidationState<'p, P> {
                 (VertexLocation::Inserted(_, ni0), VertexLocation::Inserted(_, ni1)) => (ni0, ni1, 0),
             };
             
-            let c0 = state.ns[ni0].coords();
-            let c1 = state.ns[ni1].coords();
+            let c0 = state.ns[ni0].coords().clone();
+            let c1 = state.ns[ni1].coords().clone();
 
-            let (ni_min, ni_max, c_min, c_max) = if c0 < c1 {
+            let (ni_min, ni_max, c_min, c_max) = if is_below::<K, _>(&c0, &c1) {
                 (ni0, ni1, c0, c1)
             } else {
                 (ni1, ni0, c1, c0)
             };
 
-            state.add_segment(ni_min, ni_max, c_min, c_max)?;
+            state.add_segment(ni_min, ni_max, &c_min, &c_max)?;
+
+            #[cfg(feature = "trace")]
+            state.record(trace::TraceEvent::InsertSegment { from: c_min.array(), to: c_max.array() });
 
             #[cfg(feature = "_debugging")]
             state.advance_step();
@@ -352,7 +471,14 @@ impl<'p, P: PolygonList<'p> + ?Sized> TrapezoidationState<'p, P> {
 
         // Random insertion order of the segments avoids constant worst-case scenarios
         let mut segment_order: Vec<_> = (0..len).collect();
-        segment_order[..].shuffle(&mut rand::thread_rng());
+        match self.seed {
+            Some(seed) => segment_order[..].shuffle(&mut ChaCha8Rng::seed_from_u64(seed)),
+            #[cfg(feature = "std")]
+            None => segment_order[..].shuffle(&mut rand::thread_rng()),
+            // Without a source of entropy, every unseeded trapezoidation uses the same order
+            #[cfg(not(feature = "std"))]
+            None => segment_order[..].shuffle(&mut ChaCha8Rng::seed_from_u64(0)),
+        }
 
         // Periodically, at a decreasing rate, find the trapezoid each uninserted vertex is contained within, based on the current query structure
         // The next search can begin from that query node
@@ -360,7 +486,8 @@ impl<'p, P: PolygonList<'p> + ?Sized> TrapezoidationState<'p, P> {
         let mut next_update = math_n(len, update_count);
 
         for (i, vi0) in segment_order.into_iter().enumerate() {
-            pending_vertices -= add_nth_segment(self, &mut vls[..], vi0)?;
+            pending_vertices -= add_nth_segment(self, &mut vls[..], vi0)
+                .map_err(|error| error.with_segment([vls[vi0].coords().array(), vls[(vi0 + 1) % len].coords().array()], [vi0, (vi0 + 1) % len]))?;
 
             if i == next_update {
                 enum Location<N, T> {
@@ -368,15 +495,15 @@ impl<'p, P: PolygonList<'p> + ?Sized> TrapezoidationState<'p, P> {
                     Trapezoid(T),
                 }
 
-                fn reached_containing_trapezoid<V: Vertex, Index: VertexIndex>(ns: &[Nexus<V, Index>], t: &Trapezoid<V, Index>, direction: DividerDirection, c: Coords<V::Coordinate>) -> bool {
+                fn reached_containing_trapezoid<K: GeometryKernel<V::Coordinate>, V: Vertex, Index: VertexIndex>(ns: &ForkableVec<Nexus<V, Index>>, t: &Trapezoid<V, Index>, direction: DividerDirection, c: &Coords<V::Coordinate>) -> bool {
                     match direction {
                         DividerDirection::Ascending => t.up(),
                         DividerDirection::Descending => t.down(),
                     }.map(|ni| {
                         let c_far = ns[ni].coords();
                         match direction {
-                            DividerDirection::Ascending => c_far > c,
-                            DividerDirection::Descending => c_far < c,
+                            DividerDirection::Ascending => is_below::<K, _>(c, c_far),
+                            DividerDirection::Descending => is_below::<K, _>(c_far, c),
                         }
                     }).unwrap_or(true)
                 }
@@ -396,34 +523,35 @@ impl<'p, P: PolygonList<'p> + ?Sized> TrapezoidationState<'p, P> {
                 while unlocated_pending_vertices != 0 {
                     vli_target = (vli_target + 1) % len;
                     let vl_target = &vls[vli_target];
-                    match *vl_target {
+                    match vl_target {
                         // First iteration will always be Inserted
-                        VertexLocation::Inserted(_, ni) => {
+                        &VertexLocation::Inserted(_, ni) => {
                             // Inserted vertices already have a known location
                             // Just set location to the nexus
                             location = Location::Nexus(ni);
-                            c_origin = self.ns[ni].coords();
+                            c_origin = self.ns[ni].coords().clone();
                         }
-                        VertexLocation::Pending(c_target, ref index, _) => {
-                            let ascending = c_target > c_origin;
+                        VertexLocation::Pending(c_target, index, _) => {
+                            let c_target = c_target.clone();
+                            let ascending = is_below::<K, _>(&c_origin, &c_target);
                             let direction = if ascending { DividerDirection::Ascending } else { DividerDirection::Descending };
 
                             // If on an inserted vertex, move to an adjacent Trapezoid toward the target
                             let mut ti = match location {
-                                Location::Nexus(ni) => self.ns[ni].get_trapezoid_toward_coords(&self.ss, &self.ns, direction, c_target)?,
+                                Location::Nexus(ni) => self.ns[ni].get_trapezoid_toward_coords::<K>(&self.ss, &self.ns, direction, &c_target)?,
                                 Location::Trapezoid(ti) => ti,
                             };
 
-                            while !reached_containing_trapezoid(&self.ns, &self.ts[ti], direction, c_target) {
+                            while !reached_containing_trapezoid::<K, _, _>(&self.ns, &self.ts[ti], direction, &c_target) {
                                 let t = &self.ts[ti];
                                 let ni = if direction == DividerDirection::Ascending { t.up() } else { t.down() };
                                 let ni = ni.ok_or_else(|| InternalError::new(format!("Trapezoid containing {c_target} (from {c_origin}) not found")))?;
-                                ti = self.ns[ni].get_trapezoid_between_coords(direction, c_origin, c_target)?;
+                                ti = self.ns[ni].get_trapezoid_between_coords::<K>(direction, &c_origin, &c_target)?;
                             }
 
                             unlocated_pending_vertices -= 1;
                             let qi_target = self.ts[ti].sink();
-                            vls[vli_target] = VertexLocation::Pending(c_target, index.clone(), qi_target);
+                            vls[vli_target] = VertexLocation::Pending(c_target.clone(), index.clone(), qi_target);
                             location = Location::Trapezoid(ti);
                             c_origin = c_target;
                         }
@@ -441,16 +569,15 @@ impl<'p, P: PolygonList<'p> + ?Sized> TrapezoidationState<'p, P> {
     #[inline(never)]
     fn add_vertex(&mut self, vi: P::Index, qi_root: Idx<QueryNode<P::Vertex, P::Index>>) -> Result<Idx<Nexus<P::Vertex, P::Index>>, InternalError> {
         let c = self.ps[vi.clone()].coords();
-        let (qi_parent, ti) = self.find_trapezoid_from_root(c, qi_root);
+        let (qi_parent, ti) = self.find_trapezoid_from_root(&c, qi_root);
         let ti_new = self.ts.next_index();
 
         let qi_down = self.qs.next_index();
         let qi_up = qi_down + 1;
 
-        let c = self.ps[vi.clone()].coords();
-        let ni = self.ns.push_get_index(Nexus::new(vi, c, ti_new, ti));
+        let ni = self.ns.push_get_index(Nexus::new(vi, c.clone(), ti_new, ti));
 
-        let (q_left, q_right) = self.qs[qi_parent].branch_y(qi_down, qi_up, c, ti_new);
+        let (q_left, q_right) = self.qs[qi_parent].branch_y(qi_down, qi_up, &c, ti_new);
         self.ts[ti].set_sink(qi_down);
         self.qs.push(q_left);
         self.qs.push(q_right);
@@ -464,15 +591,15 @@ impl<'p, P: PolygonList<'p> + ?Sized> TrapezoidationState<'p, P> {
 
         self.ts.push(t_new);
 
-        self.check_consistency();
+        self.check_consistency()?;
 
         Ok(ni)
     }
 
-    pub fn add_segment(&mut self, ni_min: Idx<Nexus<P::Vertex, P::Index>>, ni_max: Idx<Nexus<P::Vertex, P::Index>>, c_min: Coords<<P::Vertex as Vertex>::Coordinate>, c_max: Coords<<P::Vertex as Vertex>::Coordinate>) -> Result<(), InternalError> {
-        let si = self.ss.push_get_index(Segment::new(ni_min, ni_max, c_min, c_max));
+    pub fn add_segment(&mut self, ni_min: Idx<Nexus<P::Vertex, P::Index>>, ni_max: Idx<Nexus<P::Vertex, P::Index>>, c_min: &Coords<<P::Vertex as Vertex>::Coordinate>, c_max: &Coords<<P::Vertex as Vertex>::Coordinate>) -> Result<(), InternalError> {
+        let si = self.ss.push_get_index(Segment::new(ni_min, ni_max, c_min.clone(), c_max.clone()));
 
-        let ti = self.ns[ni_max].get_down_trapezoid_in_direction( &self.ns, &self.ss, &self.ss[si])?;
+        let ti = self.ns[ni_max].get_down_trapezoid_in_direction::<K>(&self.ns, &self.ss, &self.ss[si])?;
 
         // Each segment adds one additional trapezoid
         let qi = self.ts[ti].sink();
@@ -487,7 +614,7 @@ impl<'p, P: PolygonList<'p> + ?Sized> TrapezoidationState<'p, P> {
         let t_new = self.ts[ti].split_vertical(qi_left, qi_right, si);
         self.ts.push(t_new);
 
-        Nexus::add_segment(&mut self.ns, &self.ss, ni_max, si, ti_new)?;
+        Nexus::add_segment::<K>(&mut self.ns, &self.ss, ni_max, si, ti_new)?;
 
         #[cfg(feature = "_debugging")]
         self.output_svg(debug::svg::SvgTriangulationStyle::highlight_segment(si), debug::svg::SvgOutputLevel::AllSteps);
@@ -495,7 +622,7 @@ impl<'p, P: PolygonList<'p> + ?Sized> TrapezoidationState<'p, P> {
         let t= &self.ts[ti];
         let mut ni = t.down().ok_or_else(|| InternalError::new(format!("Segment min nexus not found at {}", ti)))?;
         let n = &self.ns[ni];
-        if ni != ni_min && !self.ss[si].is_on_left(n.coords()) {
+        if ni != ni_min && !self.ss[si].is_on_left::<K>(n.coords()) {
             let n = &mut self.ns[ni];
             n.replace_trapezoid(ti, ti_new)?;
         }
@@ -504,7 +631,7 @@ impl<'p, P: PolygonList<'p> + ?Sized> TrapezoidationState<'p, P> {
         let mut ti_upright = ti_new;
 
         while ni != ni_min {
-            let ti = self.ns[ni].get_down_trapezoid_in_direction(&self.ns, &self.ss, &self.ss[si])?;
+            let ti = self.ns[ni].get_down_trapezoid_in_direction::<K>(&self.ns, &self.ss, &self.ss[si])?;
 
             ni = self.ts[ti].down().ok_or_else(|| InternalError::new(format!("Segment min nexus not found at {}", ti)))?;
             
@@ -521,7 +648,7 @@ impl<'p, P: PolygonList<'p> + ?Sized> TrapezoidationState<'p, P> {
                 self.ts[ti].set_right(si);
                 self.ts[ti_upright].set_down(ni);
                 
-                if ni != ni_min && !self.ss[si].is_on_left(self.ns[ni].coords()) {
+                if ni != ni_min && !self.ss[si].is_on_left::<K>(self.ns[ni].coords()) {
                     self.ns[ni].replace_trapezoid(ti, ti_upright)?;
                 }
                 ti_upleft = ti;
@@ -534,7 +661,7 @@ impl<'p, P: PolygonList<'p> + ?Sized> TrapezoidationState<'p, P> {
                 self.ts[ti].set_left(si);
                 self.ts[ti_upleft].set_down(ni);
                 
-                if ni == ni_min || self.ss[si].is_on_left(self.ns[ni].coords()) {
+                if ni == ni_min || self.ss[si].is_on_left::<K>(self.ns[ni].coords()) {
                     self.ns[ni].replace_trapezoid(ti, ti_upleft)?;
                 }
                 ti_upright = ti;
@@ -547,32 +674,35 @@ impl<'p, P: PolygonList<'p> + ?Sized> TrapezoidationState<'p, P> {
             self.output_svg(debug::svg::SvgTriangulationStyle::highlight_nexus(ni), debug::svg::SvgOutputLevel::AllSteps);
         }
 
-        Nexus::add_segment(&mut self.ns, &self.ss, ni_min, si, ti_upright)?;
+        Nexus::add_segment::<K>(&mut self.ns, &self.ss, ni_min, si, ti_upright)?;
 
         #[cfg(feature = "_debugging")]
         self.output_svg(debug::svg::SvgTriangulationStyle::highlight_segment(si), debug::svg::SvgOutputLevel::MajorSteps);
 
-        self.check_consistency();
+        self.check_consistency()?;
 
         Ok(())
     }
 
     #[cfg(debug_assertions)]
-    fn check_consistency(&self) {
+    fn check_consistency(&self) -> Result<(), InternalError> {
         // Trapezoid adjacency
         for ni in self.ns.iter_index() {
-            self.check_consistency_nexus(ni);
+            self.check_consistency_nexus(ni)?;
         }
 
         for ti in self.ts.iter_index() {
-            self.check_consistency_trapezoid(ti);
+            self.check_consistency_trapezoid(ti)?;
         }
 
         self.check_consistency_query_node(self.query_node_root());
+        Ok(())
     }
 
     #[cfg(not(debug_assertions))]
-    fn check_consistency(&self) { }
+    fn check_consistency(&self) -> Result<(), InternalError> {
+        Ok(())
+    }
 
     fn check_consistency_query_node(&self, qi: Idx<QueryNode<P::Vertex, P::Index>>) -> usize {
         let q = &self.qs[qi];
@@ -591,80 +721,190 @@ impl<'p, P: PolygonList<'p> + ?Sized> TrapezoidationState<'p, P> {
         }) + 1
     }
 
-    fn check_consistency_nexus(&self, ni: Idx<Nexus<P::Vertex, P::Index>>) {
+    fn check_consistency_nexus(&self, ni: Idx<Nexus<P::Vertex, P::Index>>) -> Result<(), InternalError> {
         let n = &self.ns[ni];
 
         for ti_up in n.up_trapezoids().iter() {
             let t_up = &self.ts[*ti_up];
             if t_up.down() != Some(ni) {
-                panic!("Inconsistent nexus-trapezoid connection: {}->{} (down: {})", ni, ti_up, t_up.down().map_or("None".to_string(), |ti| format!("{}", ti)));
+                return Err(InternalError::new(format!("Inconsistent nexus-trapezoid connection: {}->{} (down: {})", ni, ti_up, t_up.down().map_or("None".to_string(), |ti| format!("{}", ti)))));
             }
         }
 
         for ti_down in n.down_trapezoids().iter() {
             let t_down = &self.ts[*ti_down];
             if t_down.up() != Some(ni) {
-                panic!("Inconsistent nexus-trapezoid connection: {}->{} (up: {})", ni, ti_down, t_down.up().map_or("None".to_string(), |ti| format!("{}", ti)));
+                return Err(InternalError::new(format!("Inconsistent nexus-trapezoid connection: {}->{} (up: {})", ni, ti_down, t_down.up().map_or("None".to_string(), |ti| format!("{}", ti)))));
             }
         }
+        Ok(())
     }
 
-    fn check_consistency_trapezoid(&self, ti: Idx<Trapezoid<P::Vertex, P::Index>>) {
+    fn check_consistency_trapezoid(&self, ti: Idx<Trapezoid<P::Vertex, P::Index>>) -> Result<(), InternalError> {
         let t = &self.ts[ti];
 
         if let Some(ni_down) = t.down() {
             let n = &self.ns[ni_down];
             if !n.iter_up_trapezoids().any(|ti_up| ti == ti_up) {
-                panic!("Inconsistent trapezoid-nexus connection: {}->{}", ti, ni_down);
+                return Err(InternalError::new(format!("Inconsistent trapezoid-nexus connection: {}->{}", ti, ni_down)));
             }
         }
         if let Some(ni_up) = t.up() {
             let n = &self.ns[ni_up];
             if !n.iter_down_trapezoids().any(|ti_down| ti == ti_down) {
-                panic!("Inconsistent trapezoid-nexus connection: {}->{}", ti, ni_up);
+                return Err(InternalError::new(format!("Inconsistent trapezoid-nexus connection: {}->{}", ti, ni_up)));
             }
         }
         let qi = self.ts[ti].sink();
-        if let QueryNode::Sink(ti_other) = self.qs[qi] {
-            if ti != ti_other {
-                panic!("Inconsistent trapezoid-query node connection: {}->{}({})", ti, qi, ti_other);
-            }
-        } else {
-            panic!("Trapezoid points to a non-sink query node: {}->{}", ti, qi);
+        match self.qs[qi] {
+            QueryNode::Sink(ti_other) if ti != ti_other => Err(InternalError::new(format!("Inconsistent trapezoid-query node connection: {}->{}({})", ti, qi, ti_other))),
+            QueryNode::Sink(_) => Ok(()),
+            _ => Err(InternalError::new(format!("Trapezoid points to a non-sink query node: {}->{}", ti, qi))),
         }
     }
 }
 
-impl<'t, 'p: 't, P: PolygonList<'p> + ?Sized> TrapezoidationStructure<'p, P> for TrapezoidationState<'p, P> {
+impl<'t, 'p: 't, P: PolygonList<'p> + ?Sized, K: GeometryKernel<<P::Vertex as Vertex>::Coordinate>> TrapezoidationStructure<'p, P, K> for TrapezoidationState<'p, P, K> {
     fn ps(&self) -> PolygonListExt<'p, P> { self.ps }
 
-    fn ns(&self) -> &[Nexus<P::Vertex, P::Index>] { &self.ns }
+    fn ns(&self) -> &ForkableVec<Nexus<P::Vertex, P::Index>> { &self.ns }
+
+    fn ss(&self) -> &ForkableVec<Segment<P::Vertex, P::Index>> { &self.ss }
+
+    fn ts(&self) -> &ForkableVec<Trapezoid<P::Vertex, P::Index>> { &self.ts }
+
+    fn qs(&self) -> &ForkableVec<QueryNode<P::Vertex, P::Index>> { &self.qs }
+}
+
+/// A trapezoid of a [Trapezoidation], bounded below and above by horizontal lines through vertices and on either side
+/// by segments. Trapezoids without a bound extend to infinity on that side
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrapezoidGeometry<C: Scalar> {
+    /// The vertex whose horizontal line bounds the trapezoid below
+    pub bottom: Option<[C; 2]>,
+    /// The vertex whose horizontal line bounds the trapezoid above
+    pub top: Option<[C; 2]>,
+    /// The lower and upper end of the segment bounding the trapezoid on the left
+    pub left: Option<[[C; 2]; 2]>,
+    /// The lower and upper end of the segment bounding the trapezoid on the right
+    pub right: Option<[[C; 2]; 2]>,
+    /// Whether the trapezoid is inside the polygons
+    pub inside: bool,
+}
+
+impl<C: Scalar> TrapezoidGeometry<C> {
+    fn new<V: Vertex<Coordinate=C>, Index: VertexIndex>(ns: &ForkableVec<Nexus<V, Index>>, ss: &ForkableVec<Segment<V, Index>>, t: &Trapezoid<V, Index>, inside: bool) -> Self {
+        let position = |ni: Idx<Nexus<V, Index>>| ns[ni].coords().array();
+        let segment = |si: Idx<Segment<V, Index>>| [position(ss[si].ni_min()), position(ss[si].ni_max())];
+        Self {
+            bottom: t.down().map(position),
+            top: t.up().map(position),
+            left: t.left().map(segment),
+            right: t.right().map(segment),
+            inside,
+        }
+    }
 
-    fn ss(&self) -> &[Segment<P::Vertex, P::Index>] { &self.ss }
+    /// Whether the trapezoid is bounded on all sides
+    pub fn is_bounded(&self) -> bool {
+        self.bottom.is_some() && self.top.is_some() && self.left.is_some() && self.right.is_some()
+    }
+}
 
-    fn ts(&self) -> &[Trapezoid<P::Vertex, P::Index>] { &self.ts }
+impl<C: Real> TrapezoidGeometry<C> {
+    /// The corners of a bounded trapezoid in counterclockwise order, starting from the bottom left. Two corners
+    /// coincide where the trapezoid is a triangle
+    pub fn corners(&self) -> Option<[[C; 2]; 4]> {
+        let (y_bottom, y_top) = (self.bottom?[1], self.top?[1]);
+        let (left, right) = (self.left?, self.right?);
+        Some([
+            [x_intercept(left, y_bottom, 0), y_bottom],
+            [x_intercept(right, y_bottom, 0), y_bottom],
+            [x_intercept(right, y_top, 1), y_top],
+            [x_intercept(left, y_top, 1), y_top],
+        ])
+    }
+}
 
-    fn qs(&self) -> &[QueryNode<P::Vertex, P::Index>] { &self.qs }
+/// The x coordinate of `segment` at `y`, or of its `end` if it is horizontal
+fn x_intercept<C: Real>(segment: [[C; 2]; 2], y: C, end: usize) -> C {
+    let [[x0, y0], [x1, y1]] = segment;
+    if y0 == y1 {
+        segment[end][0]
+    } else {
+        x0 + (x1 - x0) * (y - y0) / (y1 - y0)
+    }
 }
 
 /// The trapezoidation of a [PolygonList] generated as the first step of triangulation.
-pub struct Trapezoidation<'p, P: PolygonList<'p> + ?Sized> {
+/// 
+/// All geometric predicates are evaluated with the [GeometryKernel] `K`.
+pub struct Trapezoidation<'p, P: PolygonList<'p> + ?Sized, K: GeometryKernel<<P::Vertex as Vertex>::Coordinate> = DefaultKernel> {
     ps: PolygonListExt<'p, P>,
-    ns: Box<[Nexus<P::Vertex, P::Index>]>,
-    ss: Box<[Segment<P::Vertex, P::Index>]>,
-    ts: Box<[Trapezoid<P::Vertex, P::Index>]>,
-    qs: Box<[QueryNode<P::Vertex, P::Index>]>,
+    ns: ForkableVec<Nexus<P::Vertex, P::Index>>,
+    ss: ForkableVec<Segment<P::Vertex, P::Index>>,
+    ts: ForkableVec<Trapezoid<P::Vertex, P::Index>>,
+    qs: ForkableVec<QueryNode<P::Vertex, P::Index>>,
+    // Found on the first point query
+    interior: OnceLock<Arc<[bool]>>,
+    merge: MergePolicy,
+    // Shuffles the segments of appended polygons, as it did those of the first ones
+    seed: Option<u64>,
+    _kernel: PhantomData<K>,
 }
 
-impl<'p, P: PolygonList<'p> + ?Sized> Trapezoidation<'p, P> {
-    fn new(state: TrapezoidationState<'p, P>) -> Self {
-        let TrapezoidationState { ps, ns, ss, ts, qs, .. } = state;
-        let ns = ns.into_boxed_slice();
-        let ss = ss.into_boxed_slice();
-        let ts = ts.into_boxed_slice();
-        let qs = qs.into_boxed_slice();
+impl<'p, P: PolygonList<'p> + ?Sized, K: GeometryKernel<<P::Vertex as Vertex>::Coordinate>> Trapezoidation<'p, P, K> {
+    fn new(state: TrapezoidationState<'p, P, K>) -> Self {
+        let TrapezoidationState { ps, ns, ss, ts, qs, seed, .. } = state;
+        Self { ps, ns, ss, ts, qs, interior: OnceLock::new(), merge: MergePolicy::default(), seed, _kernel: PhantomData }
+    }
 
-        Self { ps, ns, ss, ts, qs }
+    /// Give up the storage, to be reused by the next trapezoidation
+    pub(crate) fn into_storage(self) -> Storage<P::Vertex, P::Index> {
+        let Self { ns, ss, ts, qs, .. } = self;
+        Storage { ns, ss, ts, qs }
+    }
+
+    /// A copy of the trapezoidation for speculative edits, such as [appending](Trapezoidation::append) a shape to
+    /// preview it, which can be discarded without modifying this one.
+    ///
+    /// Forking moves the storage of this trapezoidation into pages shared with the fork, which are only copied when
+    /// an edit modifies them, so forking is cheap and an edit copies little more than it touches. Shared pages are
+    /// slightly slower to access, so trapezoidations which are never forked keep plain vectors, and
+    /// [cloning](Clone::clone) one copies it.
+    ///
+    /// This trapezoidation keeps its shared pages after the fork is dropped, so it stays slightly slower to query and
+    /// triangulate. Fork a trapezoidation which is queried often only when a speculative edit is needed.
+    pub fn fork(&mut self) -> Self {
+        self.ns.share();
+        self.ss.share();
+        self.ts.share();
+        self.qs.share();
+        self.clone()
+    }
+
+    /// Set how trapezoids are merged into the monotone polygons which are [triangulated](Trapezoidation::triangulate)
+    /// or returned by [Trapezoidation::monotones]. The default is [MergePolicy::Unimonotone].
+    ///
+    /// ```
+    /// # use triangulate::{formats, MergePolicy, PolygonList};
+    /// let octagon = vec![vec![[2f64, 0.], [4., 0.], [6., 2.], [6., 4.], [4., 6.], [2., 6.], [0., 4.], [0., 2.]]];
+    /// let trapezoidation = octagon.trapezoidize().expect("Trapezoidation failed").with_merge_policy(MergePolicy::Monotone);
+    ///
+    /// let mut monotones = Vec::<Vec<[usize; 2]>>::new();
+    /// trapezoidation.monotones(formats::IndexedMonotoneFormat::new(&mut monotones)).expect("Triangulation failed");
+    /// assert_eq!(monotones.len(), 1);
+    /// ```
+    pub fn with_merge_policy(mut self, merge: MergePolicy) -> Self {
+        self.merge = merge;
+        self
+    }
+
+    /// Shuffle the segments of [appended](Trapezoidation::append) polygons with a generator seeded from `seed`, rather
+    /// than a random one. A trapezoidation built with a [seed](crate::TriangulationOptions::seed) keeps it.
+    pub fn with_seed(mut self, seed: Option<u64>) -> Self {
+        self.seed = seed;
+        self
     }
 
     fn top_trapezoid(&self) -> Result<Idx<Trapezoid<P::Vertex, P::Index>>, InternalError> {
@@ -681,6 +921,12 @@ impl<'p, P: PolygonList<'p> + ?Sized> Trapezoidation<'p, P> {
     }
 
     fn triangulate_inner<FB: FanFormat<'p, P>>(&self, fbs: &mut FanBuilderState<'p, P, FB>) -> Result<(), TriangulationError<<FB::Builder as FanBuilder<'p, P>>::Error>> {
+        self.for_each_monotone(|monotone| monotone.build_fans::<K, P, FB>(self.ps, fbs).map_err(|error| error.with_stage(ErrorStage::FanTriangulation)))
+            .map_err(|error| error.with_stage(ErrorStage::MonotoneSplit))
+    }
+
+    /// Split the inside of the polygons into monotone polygons, passing each to `emit`
+    fn for_each_monotone<E: error::Error>(&self, mut emit: impl FnMut(Monotone<P::Index, <P::Vertex as Vertex>::Coordinate>) -> Result<(), TriangulationError<E>>) -> Result<(), TriangulationError<E>> {
         struct State<V: Vertex, Index: VertexIndex> {
             ti: Idx<Trapezoid<V, Index>>,
             monotones: Option<Ot<MonotoneBuilder<Index, V::Coordinate>>>,
@@ -713,11 +959,6 @@ impl<'p, P: PolygonList<'p> + ?Sized> Trapezoidation<'p, P> {
             let n_down = &self.ns[ni_down];
 
             if let Some(mut monotones_some) = monotones.take() {
-                // Add this nexus to all monotone chains
-                for monotone in monotones_some.iter_mut() {
-                    monotone.add_vertex(n_down.vertex(), n_down.coords());
-                }
-
                 let s_left = match t.left() {
                     Some(si_left) => &self.ss[si_left],
                     None => return Err(TriangulationError::internal(INNER_POLYGON_ERROR)),
@@ -732,11 +973,34 @@ impl<'p, P: PolygonList<'p> + ?Sized> Trapezoidation<'p, P> {
                     None => return Err(TriangulationError::internal(INNER_POLYGON_ERROR)),
                 };
 
+                // Add this nexus to all monotone chains, on the side of the trapezoid it is on. A nexus between the
+                // sides splits the monotones below it, on the right of the left monotone and the left of the right one
+                let on_left = ni_down == s_left.ni_min();
+                let on_right = ni_down == s_right.ni_min();
+                let chains = if on_left {
+                    [Chain::Left; 2]
+                } else if on_right {
+                    [Chain::Right; 2]
+                } else if matches!(monotones_some, Ot::Two(..)) {
+                    [Chain::Right, Chain::Left]
+                } else if ni_up == s_left.ni_max() {
+                    // A single monotone continues on the right of the split
+                    [Chain::Left; 2]
+                } else {
+                    [Chain::Right; 2]
+                };
+                for (monotone, chain) in monotones_some.iter_mut().zip(chains) {
+                    monotone.add_vertex(n_down.vertex(), n_down.coords().clone(), chain);
+                }
+
+                // A single monotone only needs the diagonals below to keep one of its sides a single segment
+                let split = self.merge == MergePolicy::Unimonotone || matches!(monotones_some, Ot::Two(..));
+
                 // If the nexus is part of the left or right segment of the trapezoid,
                 // and the previous (upper) nexus is not on the same segment,
                 // draw a diagonal, ending one of the monotone chains
                 if let Some(monotone_complete) = {
-                    if ni_down == s_left.ni_min() && ni_down != s_right.ni_min() && ni_up != s_left.ni_max() {
+                    if split && on_left && !on_right && ni_up != s_left.ni_max() {
                         Some(match monotones_some {
                             Ot::One(monotone0) => monotone0,
                             Ot::Two(monotone0, monotone1) => {
@@ -744,7 +1008,7 @@ impl<'p, P: PolygonList<'p> + ?Sized> Trapezoidation<'p, P> {
                                 monotone0
                             }
                         })
-                    } else if ni_down == s_right.ni_min() && ni_down != s_left.ni_min() && ni_up != s_right.ni_max() {
+                    } else if split && on_right && !on_left && ni_up != s_right.ni_max() {
                         Some(match monotones_some {
                             Ot::One(monotone0) => monotone0,
                             Ot::Two(monotone0, monotone1) => {
@@ -758,11 +1022,11 @@ impl<'p, P: PolygonList<'p> + ?Sized> Trapezoidation<'p, P> {
                         None
                     } 
                 } {
-                    // Triangulate the completed monotone
-                    match monotone_complete.build() {
+                    // Emit the completed monotone
+                    match monotone_complete.build::<K>(self.merge) {
                         Ok(monotone_complete) => {
                             if let Some(monotone_complete) = monotone_complete {
-                                monotone_complete.build_fans::<P, FB>(self.ps, fbs)?;
+                                emit(monotone_complete)?;
                             }
                         },
                         Err(e) => return Err(TriangulationError::InternalError(e)),
@@ -774,7 +1038,7 @@ impl<'p, P: PolygonList<'p> + ?Sized> Trapezoidation<'p, P> {
                         let vi = self.ns[ni_up].vertex().clone();
                         let c = self.ps[vi.clone()].coords();
                         let mut monotone_new = MonotoneBuilder::new(vi, c);
-                        monotone_new.add_vertex(n_down.vertex(), n_down.coords());
+                        monotone_new.add_vertex(n_down.vertex(), n_down.coords().clone(), chains[0]);
                         monotones = Some(monotone_new.into());
                     }
                 }
@@ -789,10 +1053,10 @@ impl<'p, P: PolygonList<'p> + ?Sized> Trapezoidation<'p, P> {
                         } else if ti == ti_upcenter {
                             // Finish the monotone(s)
                             for monotone in monotones_some.into_iter() {
-                                match monotone.build() {
+                                match monotone.build::<K>(self.merge) {
                                     Ok(monotone) => {
                                         if let Some(monotone) = monotone {
-                                            monotone.build_fans::<P, FB>(self.ps, fbs)?;
+                                            emit(monotone)?;
                                         }
                                     }
                                     Err(e) => return Err(TriangulationError::InternalError(e)),
@@ -836,13 +1100,14 @@ impl<'p, P: PolygonList<'p> + ?Sized> Trapezoidation<'p, P> {
 
                                 // Start a second monotone with the current and previous nexuses' vertices
                                 let n = &self.ns[ni_up];
-                                let mut monotone_new = MonotoneBuilder::new(n.vertex(), n.coords());
-                                monotone_new.add_vertex(n_down.vertex(), n_down.coords());
+                                let mut monotone_new = MonotoneBuilder::new(n.vertex(), n.coords().clone());
 
                                 // Put the new monotone on the correct side
                                 if ni_up == self.ss[t.left().ok_or_else(|| TriangulationError::internal(INNER_POLYGON_ERROR))?].ni_max() {
+                                    monotone_new.add_vertex(n_down.vertex(), n_down.coords().clone(), Chain::Right);
                                     (monotone_new, monotone)
                                 } else if ni_up == self.ss[t.right().ok_or_else(|| TriangulationError::internal(INNER_POLYGON_ERROR))?].ni_max() {
+                                    monotone_new.add_vertex(n_down.vertex(), n_down.coords().clone(), Chain::Left);
                                     (monotone, monotone_new)
                                 } else {
                                     return Err(TriangulationError::internal("Expected nexus on top of left or right segment"));
@@ -859,7 +1124,7 @@ impl<'p, P: PolygonList<'p> + ?Sized> Trapezoidation<'p, P> {
                         // The left and right trapezoids are still outside the polygon
                         branch_stack.push(State::new(ti_downright, None));
                         // Start a new monotone from the center trapezoid
-                        let monotone_new = MonotoneBuilder::new(n_down.vertex(), n_down.coords());
+                        let monotone_new = MonotoneBuilder::new(n_down.vertex(), n_down.coords().clone());
                         branch_stack.push(State::new(ti_downcenter, Some(monotone_new.into())));
                     }
                     ti_downleft
@@ -887,28 +1152,620 @@ impl<'p, P: PolygonList<'p> + ?Sized> Trapezoidation<'p, P> {
         let result = self.triangulate_inner(&mut fbs);
         fbs.complete(result)
     }
+
+    /// Split the inside of the polygons into monotone polygons, the step of triangulation before each is split into
+    /// triangles, in the layout specified by `format`.
+    /// 
+    /// See [PolygonList::monotones].
+    pub fn monotones<MF: MonotoneFormat<'p, P>>(&self, format: MF) -> MonotoneResult<'p, P, MF> {
+        let mut builder = format.initialize(self.ps.polygon_list()).map_err(TriangulationError::FanBuilder)?;
+        let result = self.for_each_monotone(|monotone| {
+            builder.add_monotone(&monotone.into_indices(MF::Builder::WINDING)).map_err(TriangulationError::FanBuilder)
+        }).map_err(|error| error.with_stage(ErrorStage::MonotoneSplit));
+        match result {
+            Ok(()) => builder.build().map_err(TriangulationError::FanBuilder),
+            Err(err) => {
+                builder.fail(&err);
+                Err(err)
+            }
+        }
+    }
+
+    /// Whether each trapezoid (by index) is inside the polygons, found by the same traversal as triangulation
+    pub(crate) fn interior_trapezoids(&self) -> Result<Vec<bool>, InternalError> {
+        let mut interior = vec![false; self.ts.len()];
+        let mut ti = self.top_trapezoid()?;
+        let mut inside = false;
+        let mut branch_stack = Vec::<(Idx<Trapezoid<P::Vertex, P::Index>>, bool)>::new();
+
+        while let Some(ni_down) = self.ts[ti].down() {
+            interior[ti.usize()] = inside;
+            ti = match self.ns[ni_down].final_type()? {
+                // The trapezoid below a 'V' is reached last from the upper right, and matches it
+                FinalNexusType::V { ti_upright, ti_down, .. } if ti == ti_upright => ti_down,
+                FinalNexusType::V { .. } => {
+                    let (ti_next, inside_next) = branch_stack.pop().ok_or_else(|| InternalError::new("Unexpected empty branch stack"))?;
+                    inside = inside_next;
+                    ti_next
+                }
+                FinalNexusType::I { ti_upleft, ti_upright, ti_downleft, ti_downright } => {
+                    if ti == ti_upleft {
+                        ti_downleft
+                    } else if ti == ti_upright {
+                        ti_downright
+                    } else {
+                        return Err(InternalError::new("Invalid 'I' nexus type"));
+                    }
+                }
+                // Only the center trapezoid below an 'A' is on the other side of a segment
+                FinalNexusType::A { ti_downleft, ti_downcenter, ti_downright, .. } => {
+                    branch_stack.push((ti_downright, inside));
+                    branch_stack.push((ti_downcenter, !inside));
+                    ti_downleft
+                }
+            };
+        }
+        interior[ti.usize()] = inside;
+        Ok(interior)
+    }
+
+    /// The index of the trapezoid containing `point`, found with the query structure in expected O(log *n*) time.
+    ///
+    /// Trapezoids are numbered in the order they were created, as in [trace](crate::trace) snapshots. A point on
+    /// a horizontal line through a vertex is in the trapezoid below it, and a point on a segment is in the trapezoid
+    /// to its right.
+    pub fn locate(&self, point: [<P::Vertex as Vertex>::Coordinate; 2]) -> usize {
+        self.find_trapezoid(&Coords::new(point)).1.usize()
+    }
+
+    fn interior(&self) -> Result<&[bool], TrapezoidationError> {
+        match self.interior.get() {
+            Some(interior) => Ok(interior),
+            None => {
+                let interior = self.interior_trapezoids().map_err(|error| TrapezoidationError::InternalError(error.with_stage(ErrorStage::TrapezoidTraversal)))?;
+                Ok(self.interior.get_or_init(|| interior.into()))
+            }
+        }
+    }
+
+    /// Whether `point` is inside the polygons by the even-odd rule, for hit-testing with the structure built for
+    /// triangulation. Points on the boundary may be inside or outside, following [Trapezoidation::locate].
+    ///
+    /// Which trapezoids are inside is found on the first call, in O(*n*) time.
+    pub fn contains_point(&self, point: [<P::Vertex as Vertex>::Coordinate; 2]) -> Result<bool, TrapezoidationError> {
+        Ok(self.interior()?[self.locate(point)])
+    }
+
+    /// The index of the trapezoid containing each of `points`, as in [Trapezoidation::locate], and whether it is inside
+    /// the polygons, as in [Trapezoidation::contains_point].
+    ///
+    /// The points are queried in Z-order, so consecutive queries take the same branches of the query structure and
+    /// find them in cache. For millions of points this is faster than querying them one at a time, in spite of the
+    /// O(*m* log *m*) time to sort them.
+    pub fn locate_batch(&self, points: &[[<P::Vertex as Vertex>::Coordinate; 2]]) -> Result<Vec<(usize, bool)>, TrapezoidationError> {
+        let interior = self.interior()?;
+        let keys = z_order_keys(points);
+        let mut order = (0..points.len()).collect::<Vec<_>>();
+        order.sort_unstable_by_key(|&i| keys[i]);
+
+        let mut located = vec![(0, false); points.len()];
+        for i in order {
+            let ti = self.locate(points[i].clone());
+            located[i] = (ti, interior[ti]);
+        }
+        Ok(located)
+    }
+
+    /// [Trapezoidation::locate_batch] on all threads of the [rayon](https://crates.io/crates/rayon) thread pool, with
+    /// each thread querying a contiguous run of the points in Z-order. Requires the `rayon` feature.
+    #[cfg(feature = "rayon")]
+    pub fn locate_batch_par(&self, points: &[[<P::Vertex as Vertex>::Coordinate; 2]]) -> Result<Vec<(usize, bool)>, TrapezoidationError>
+    where Self: Sync,
+          <P::Vertex as Vertex>::Coordinate: Send + Sync {
+        use rayon::prelude::*;
+
+        let interior = self.interior()?;
+        let keys = z_order_keys(points);
+        let mut order = (0..points.len()).collect::<Vec<_>>();
+        order.par_sort_unstable_by_key(|&i| keys[i]);
+
+        let found = order.par_iter().map(|&i| {
+            let ti = self.locate(points[i].clone());
+            (ti, interior[ti])
+        }).collect::<Vec<_>>();
+        let mut located = vec![(0, false); points.len()];
+        for (i, result) in order.into_iter().zip(found) {
+            located[i] = result;
+        }
+        Ok(located)
+    }
+
+    /// The trapezoids of the decomposition with their bounds, by index as in [Trapezoidation::locate].
+    ///
+    /// This is the vertical decomposition of the plane by the polygons, including the unbounded trapezoids outside
+    /// them; filter by [TrapezoidGeometry::inside] for a decomposition of the polygons themselves.
+    pub fn trapezoids(&self) -> Result<impl Iterator<Item=TrapezoidGeometry<<P::Vertex as Vertex>::Coordinate>> + '_, TrapezoidationError> {
+        let interior = self.interior()?;
+        Ok((0..self.ts.len()).map(move |i| TrapezoidGeometry::new(&self.ns, &self.ss, &self.ts[Idx::new(i)], interior[i])))
+    }
+
+    /// The intervals of the horizontal line at `y` which are outside the polygons, from left to right, such as to flow
+    /// text around the polygons as exclusion zones. A `None` end extends to infinity on that side.
+    ///
+    /// The intervals are read from the trapezoids crossing the line in O(*n*) time. A line through a vertex is treated
+    /// as being just above it, so a polygon's bottom edge is part of it and its top edge is not.
+    ///
+    /// ```
+    /// # use triangulate::PolygonList;
+    /// let polygons = vec![vec![[0f64, 0.], [4., 0.], [2., 4.]], vec![[6., 0.], [8., 0.], [8., 4.], [6., 4.]]];
+    /// let trapezoidation = polygons.trapezoidize().expect("Trapezoidation failed");
+    /// let free = trapezoidation.free_intervals_at(2.).expect("Trapezoidation failed");
+    /// assert_eq!(free, vec![[None, Some(1.)], [Some(3.), Some(6.)], [Some(8.), None]]);
+    /// ```
+    pub fn free_intervals_at(&self, y: <P::Vertex as Vertex>::Coordinate) -> Result<Vec<Interval<'p, P>>, TrapezoidationError>
+    where <P::Vertex as Vertex>::Coordinate: Real {
+        let mut spans: Vec<(Interval<'p, P>, bool)> = self.trapezoids()?
+            .filter(|t| t.bottom.is_none_or(|c| c[1] <= y) && t.top.is_none_or(|c| y < c[1]))
+            .map(|t| ([t.left.map(|s| x_intercept(s, y, 0)), t.right.map(|s| x_intercept(s, y, 0))], t.inside))
+            .collect();
+        spans.sort_by(|([a, _], _), ([b, _], _)| match (a, b) {
+            (Some(a), Some(b)) => a.partial_cmp(b).unwrap_or(core::cmp::Ordering::Equal),
+            _ => a.is_some().cmp(&b.is_some()),
+        });
+
+        let mut free: Vec<Interval<'p, P>> = Vec::new();
+        for ([left, right], inside) in spans {
+            if inside || matches!((left, right), (Some(left), Some(right)) if left >= right) {
+                continue;
+            }
+            match free.last_mut() {
+                // Trapezoids on either side of a vertex continue the same interval
+                Some([_, end]) if end.is_some() && *end == left => *end = right,
+                _ => free.push([left, right]),
+            }
+        }
+        Ok(free)
+    }
+
+    /// Push the trapezoids which share a horizontal boundary with `ti`, without a segment between them
+    fn push_adjacent_trapezoids(&self, ti: Idx<Trapezoid<P::Vertex, P::Index>>, adjacent: &mut Vec<Idx<Trapezoid<P::Vertex, P::Index>>>) -> Result<(), InternalError> {
+        let t = &self.ts[ti];
+        for ni in t.up().into_iter().chain(t.down()) {
+            // The trapezoids at a nexus on the same side of its segments
+            let group = match self.ns[ni].final_type()? {
+                FinalNexusType::V { ti_upleft, ti_upright, ti_down, .. } => [ti_upleft, ti_upright, ti_down],
+                FinalNexusType::A { ti_up, ti_downleft, ti_downright, .. } => [ti_up, ti_downleft, ti_downright],
+                FinalNexusType::I { ti_upleft, ti_downleft, .. } if ti == ti_upleft || ti == ti_downleft => [ti_upleft, ti_downleft, ti_downleft],
+                FinalNexusType::I { ti_upright, ti_downright, .. } => [ti_upright, ti_downright, ti_downright],
+            };
+            if group.contains(&ti) {
+                adjacent.extend(group.into_iter().filter(|&tj| tj != ti));
+            }
+        }
+        Ok(())
+    }
+
+    /// The connected part of the inside of the polygons containing `point`, as a [Region] of the contours bounding it,
+    /// such as to select the shape clicked in an editor. The region is empty if `point` is outside the polygons.
+    ///
+    /// The region is found by a flood fill from the trapezoid containing `point` over the trapezoids sharing a
+    /// horizontal boundary, in O(*n*) time. Regions touching at a vertex are separate.
+    ///
+    /// ```
+    /// # use triangulate::{formats, ListFormat, PolygonList};
+    /// let polygons = vec![vec![[0f64, 0.], [2., 0.], [2., 2.], [0., 2.]], vec![[3., 0.], [5., 0.], [4., 2.]]];
+    /// let trapezoidation = polygons.trapezoidize().expect("Trapezoidation failed");
+    /// let region = trapezoidation.extract_region([4., 1.]).expect("Trapezoidation failed");
+    /// assert_eq!(region.vertex_count(), 3);
+    /// let triangles: Vec<[usize; 2]> = region.triangulate(formats::IndexedListFormat::new(Vec::new()).into_fan_format()).expect("Triangulation failed");
+    /// assert!(triangles.iter().all(|[pi, _]| *pi == 1));
+    /// ```
+    pub fn extract_region(&self, point: [<P::Vertex as Vertex>::Coordinate; 2]) -> Result<Region<'p, P>, TrapezoidationError> {
+        let polygons = self.ps.polygon_list();
+        let ti = self.find_trapezoid(&Coords::new(point)).1;
+        if !self.interior()?[ti.usize()] {
+            return Ok(Region::new(polygons, Vec::new()));
+        }
+
+        let mut visited = vec![false; self.ts.len()];
+        visited[ti.usize()] = true;
+        let mut stack = vec![ti];
+        // The segments bounding the region, directed with the region on their left
+        let mut edges = Vec::<(Idx<Nexus<P::Vertex, P::Index>>, Idx<Nexus<P::Vertex, P::Index>>)>::new();
+        let mut bounding = vec![false; self.ss.len()];
+        let mut adjacent = Vec::new();
+        while let Some(ti) = stack.pop() {
+            let t = &self.ts[ti];
+            for (si, flip) in t.left().map(|si| (si, true)).into_iter().chain(t.right().map(|si| (si, false))) {
+                if !core::mem::replace(&mut bounding[si.usize()], true) {
+                    let s = &self.ss[si];
+                    edges.push(if flip { (s.ni_max(), s.ni_min()) } else { (s.ni_min(), s.ni_max()) });
+                }
+            }
+            self.push_adjacent_trapezoids(ti, &mut adjacent).map_err(|error| TrapezoidationError::InternalError(error.with_stage(ErrorStage::TrapezoidTraversal)))?;
+            for tj in adjacent.drain(..) {
+                if !core::mem::replace(&mut visited[tj.usize()], true) {
+                    stack.push(tj);
+                }
+            }
+        }
+
+        // Chain the edges into contours; at a vertex where contours touch, either way around gives the same region
+        edges.sort_by_key(|(ni_from, _)| ni_from.usize());
+        let mut used = vec![false; edges.len()];
+        let mut contours = Vec::new();
+        for start in 0..edges.len() {
+            let mut k = start;
+            let mut contour = Vec::new();
+            while !core::mem::replace(&mut used[k], true) {
+                let (ni_from, ni_to) = edges[k];
+                contour.push(self.ns[ni_from].vertex());
+                let first = edges.partition_point(|(ni, _)| ni.usize() < ni_to.usize());
+                match (first..edges.len()).take_while(|&j| edges[j].0 == ni_to).find(|&j| !used[j]) {
+                    Some(j) => k = j,
+                    None => break,
+                }
+            }
+            if !contour.is_empty() {
+                contours.push(contour);
+            }
+        }
+        Ok(Region::new(polygons, contours))
+    }
+
+    /// The connected components of the inside of the polygons, with the component of each trapezoid by index as in
+    /// [Trapezoidation::locate], found by joining the trapezoids sharing a horizontal boundary in O(*n* α(*n*)) time.
+    ///
+    /// See the [components](crate::components) module.
+    pub fn components(&self) -> Result<Components, TrapezoidationError> {
+        let interior = self.interior()?;
+        let mut sets = UnionFind::new(self.ts.len());
+        let mut adjacent = Vec::new();
+        for ti in (0..self.ts.len()).filter(|&ti| interior[ti]).map(Idx::new) {
+            self.push_adjacent_trapezoids(ti, &mut adjacent).map_err(|error| TrapezoidationError::InternalError(error.with_stage(ErrorStage::TrapezoidTraversal)))?;
+            for tj in adjacent.drain(..) {
+                sets.union(ti.usize(), tj.usize());
+            }
+        }
+        Ok(Components::from_union_find(&mut sets, |ti| interior[ti]))
+    }
+
+    /// The area, perimeter, bounding box and number of holes of each of the [components](Trapezoidation::components),
+    /// in the same order.
+    ///
+    /// The area and bounding box are those of the trapezoids of each component, and the perimeter the length of the
+    /// segments bounding them. The number of holes is one less than the number of closed boundaries.
+    pub fn component_summaries(&self) -> Result<Vec<ComponentSummary<<P::Vertex as Vertex>::Coordinate>>, TrapezoidationError>
+    where <P::Vertex as Vertex>::Coordinate: Real {
+        let components = self.components()?;
+        let mut summaries = vec![ComponentSummary {
+            area: <P::Vertex as Vertex>::Coordinate::zero(),
+            perimeter: <P::Vertex as Vertex>::Coordinate::zero(),
+            bounds: [[<P::Vertex as Vertex>::Coordinate::max_value(); 2], [<P::Vertex as Vertex>::Coordinate::min_value(); 2]],
+            holes: 0,
+        }; components.count];
+        // The boundaries are found by joining the ends of each bounding segment
+        let mut boundaries = UnionFind::new(self.ns.len());
+        let mut bounding = vec![false; self.ss.len()];
+        let mut boundary_nexuses = vec![Vec::new(); components.count];
+
+        for (geometry, (ti, component)) in self.trapezoids()?.zip(components.labels.iter().enumerate()) {
+            let (Some(component), Some(corners)) = (*component, geometry.corners()) else { continue };
+            let summary = &mut summaries[component];
+            // Twice the area, halved once summed
+            summary.area = summary.area + math::signed_area_2(&corners);
+            for c in corners {
+                summary.bounds = [[summary.bounds[0][0].min(c[0]), summary.bounds[0][1].min(c[1])], [summary.bounds[1][0].max(c[0]), summary.bounds[1][1].max(c[1])]];
+            }
+
+            let t = &self.ts[Idx::new(ti)];
+            for si in t.left().into_iter().chain(t.right()) {
+                if !core::mem::replace(&mut bounding[si.usize()], true) {
+                    let s = &self.ss[si];
+                    let ([x0, y0], [x1, y1]) = (self.ns[s.ni_min()].coords().array(), self.ns[s.ni_max()].coords().array());
+                    summary.perimeter = summary.perimeter + ((x1 - x0) * (x1 - x0) + (y1 - y0) * (y1 - y0)).sqrt();
+                    boundaries.union(s.ni_min().usize(), s.ni_max().usize());
+                    boundary_nexuses[component].push(s.ni_min().usize());
+                }
+            }
+        }
+
+        let two = <P::Vertex as Vertex>::Coordinate::one() + <P::Vertex as Vertex>::Coordinate::one();
+        for (summary, nexuses) in summaries.iter_mut().zip(boundary_nexuses) {
+            summary.area = summary.area / two;
+            let mut roots = nexuses.into_iter().map(|ni| boundaries.find(ni)).collect::<Vec<_>>();
+            roots.sort_unstable();
+            roots.dedup();
+            summary.holes = roots.len().saturating_sub(1);
+        }
+        Ok(summaries)
+    }
+}
+
+// The position of each point along a Z-order curve over their bounding box, which keeps points close in the plane
+// mostly close in the order
+fn z_order_keys<C: Scalar>(points: &[[C; 2]]) -> Vec<u32> {
+    let points = points.iter().map(|p| [&p[0], &p[1]].map(|x| x.to_f64().unwrap_or(0.))).collect::<Vec<_>>();
+    let Some(first) = points.first() else { return Vec::new() };
+    let (min, max) = points.iter().fold((*first, *first), |(min, max), p| {
+        ([min[0].min(p[0]), min[1].min(p[1])], [max[0].max(p[0]), max[1].max(p[1])])
+    });
+    let quantize = |x: f64, min: f64, max: f64| if max > min {
+        ((x - min) / (max - min) * f64::from(u16::MAX)) as u32
+    } else {
+        0
+    };
+    // Spread the low 16 bits of `x` to the even bits
+    let spread = |mut x: u32| {
+        x = (x | (x << 8)) & 0x00ff00ff;
+        x = (x | (x << 4)) & 0x0f0f0f0f;
+        x = (x | (x << 2)) & 0x33333333;
+        (x | (x << 1)) & 0x55555555
+    };
+    points.iter().map(|p| spread(quantize(p[0], min[0], max[0])) | (spread(quantize(p[1], min[1], max[1])) << 1)).collect()
+}
+
+// A separate impl, as bounding `P` for both `'p` and `'q` would be ambiguous
+impl<'p, P, V: Vertex, Index: VertexIndex, K: GeometryKernel<V::Coordinate>> Trapezoidation<'p, P, K>
+where P: for<'a> PolygonList<'a, Vertex=V, Index=Index> + ?Sized {
+    /// Insert the polygons of `polygons` which follow the ones already in the trapezoidation, with the same
+    /// [GeometryKernel], so that static geometry can be trapezoidized once and dynamic obstacles added to a
+    /// [fork](Trapezoidation::fork) of it each frame, without rebuilding the static part.
+    ///
+    /// The existing structure refers to vertices by index, so its polygons must be the leading polygons of
+    /// `polygons`, with the same vertices; the result triangulates into indices of `polygons`. A trapezoidation built
+    /// with a [seed](crate::TriangulationOptions::seed) shuffles the new segments with it too, so appending is as
+    /// reproducible as the first build.
+    ///
+    /// ```
+    /// # use triangulate::{formats, ListFormat, PolygonList};
+    /// let world = vec![vec![[0f64, 0.], [10., 0.], [10., 10.], [0., 10.]]];
+    /// let mut trapezoidation = world.trapezoidize().expect("Trapezoidation failed");
+    ///
+    /// let mut frame = world.clone();
+    /// frame.push(vec![[4., 4.], [4., 6.], [6., 6.], [6., 4.]]);
+    /// let trapezoidation = trapezoidation.fork().append(&frame).expect("Trapezoidation failed");
+    ///
+    /// let mut triangles = Vec::<[usize; 2]>::new();
+    /// trapezoidation.triangulate(formats::IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+    /// assert_eq!(triangles.len(), 3 * 8);
+    /// ```
+    pub fn append<'q>(self, polygons: &'q P) -> Result<Trapezoidation<'q, P, K>, TrapezoidationError> {
+        let Self { ns, ss, ts, qs, merge, seed, .. } = self;
+        let inserted = ns.len();
+        let ps = PolygonListExt::new(polygons);
+
+        #[cfg(feature = "_debugging")]
+        let debug_info = DebugInfo::new(&ps);
+
+        let mut state = TrapezoidationState::<'q, P, K> {
+            ps,
+            ns,
+            ss,
+            ts,
+            qs,
+            #[cfg(feature = "_debugging")]
+            debug_info,
+            #[cfg(feature = "trace")]
+            trace: None,
+            seed,
+            _kernel: PhantomData,
+        };
+        state.add_polygons(inserted)?;
+        Ok(Trapezoidation::new(state).with_merge_policy(merge))
+    }
 }
 
-impl<'p, P: PolygonList<'p> + ?Sized> TrapezoidationStructure<'p, P> for Trapezoidation<'p, P> {
+impl<'p, P: PolygonList<'p> + ?Sized, K: GeometryKernel<<P::Vertex as Vertex>::Coordinate>> Clone for Trapezoidation<'p, P, K> {
+    fn clone(&self) -> Self {
+        Self {
+            ps: self.ps,
+            ns: self.ns.clone(),
+            ss: self.ss.clone(),
+            ts: self.ts.clone(),
+            qs: self.qs.clone(),
+            interior: self.interior.clone(),
+            merge: self.merge,
+            seed: self.seed,
+            _kernel: PhantomData,
+        }
+    }
+}
+
+/// Only the structure of the trapezoidation is serialized, and not its polygons, which are needed again to
+/// deserialize it with a [TrapezoidationSeed].
+#[cfg(feature = "serde")]
+impl<'p, P: PolygonList<'p> + ?Sized, K: GeometryKernel<<P::Vertex as Vertex>::Coordinate>> serde::Serialize for Trapezoidation<'p, P, K>
+where <P::Vertex as Vertex>::Coordinate: serde::Serialize, P::Index: serde::Serialize {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("Trapezoidation", 5)?;
+        state.serialize_field("ns", &self.ns)?;
+        state.serialize_field("ss", &self.ss)?;
+        state.serialize_field("ts", &self.ts)?;
+        state.serialize_field("qs", &self.qs)?;
+        state.serialize_field("seed", &self.seed)?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(rename = "Trapezoidation", bound = "V::Coordinate: serde::Deserialize<'de>, Index: serde::Deserialize<'de>")]
+struct SerializedTrapezoidation<V: Vertex, Index: VertexIndex> {
+    ns: ForkableVec<Nexus<V, Index>>,
+    ss: ForkableVec<Segment<V, Index>>,
+    ts: ForkableVec<Trapezoid<V, Index>>,
+    qs: ForkableVec<QueryNode<V, Index>>,
+    #[serde(default)]
+    seed: Option<u64>,
+}
+
+/// Deserializes a [Trapezoidation] of `polygons`, so that the trapezoidation of a large static polygon list can be
+/// built once, shipped as an asset alongside the polygons, and loaded for point location queries.
+///
+/// The polygons must be the ones the trapezoidation was built from, as it refers to their vertices by index. The
+/// vertex count and the exact position of each vertex are checked, so the format must round-trip coordinates exactly
+/// (such as `serde_json` with its `float_roundtrip` feature). The links within the structure are checked too, but a
+/// vertex index which is out of range for `polygons` panics as indexing does.
+/// ```
+/// # use serde::de::DeserializeSeed;
+/// # use triangulate::{PolygonList, TrapezoidationSeed};
+/// let polygons = vec![
+///     vec![[0f64, 0.], [10., 0.], [10., 10.], [0., 10.]],
+///     vec![[4., 4.], [4., 6.], [6., 6.], [6., 4.]],
+/// ];
+/// let trapezoidation = polygons.trapezoidize().expect("Trapezoidation failed");
+/// let json = serde_json::to_string(&trapezoidation).expect("Serialization failed");
+///
+/// let mut deserializer = serde_json::Deserializer::from_str(&json);
+/// let trapezoidation = TrapezoidationSeed::new(&polygons).deserialize(&mut deserializer).expect("Deserialization failed");
+/// assert!(trapezoidation.contains_point([2., 2.]).expect("Point location failed"));
+/// assert!(!trapezoidation.contains_point([5., 5.]).expect("Point location failed"));
+/// ```
+#[cfg(feature = "serde")]
+pub struct TrapezoidationSeed<'p, P: PolygonList<'p> + ?Sized, K: GeometryKernel<<P::Vertex as Vertex>::Coordinate> = DefaultKernel> {
+    polygons: &'p P,
+    _kernel: PhantomData<K>,
+}
+
+#[cfg(feature = "serde")]
+impl<'p, P: PolygonList<'p> + ?Sized> TrapezoidationSeed<'p, P> {
+    /// Deserialize a trapezoidation of `polygons`
+    pub fn new(polygons: &'p P) -> Self {
+        Self::with_kernel(polygons)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'p, P: PolygonList<'p> + ?Sized, K: GeometryKernel<<P::Vertex as Vertex>::Coordinate>> TrapezoidationSeed<'p, P, K> {
+    /// Deserialize a trapezoidation of `polygons`, which was built with the [GeometryKernel] `K`
+    pub fn with_kernel(polygons: &'p P) -> Self {
+        Self { polygons, _kernel: PhantomData }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, 'p, P: PolygonList<'p> + ?Sized, K: GeometryKernel<<P::Vertex as Vertex>::Coordinate>> serde::de::DeserializeSeed<'de> for TrapezoidationSeed<'p, P, K>
+where <P::Vertex as Vertex>::Coordinate: serde::Deserialize<'de>, P::Index: serde::Deserialize<'de> {
+    type Value = Trapezoidation<'p, P, K>;
+
+    fn deserialize<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        let SerializedTrapezoidation { ns, ss, ts, qs, seed } = serde::Deserialize::deserialize(deserializer)?;
+        let trapezoidation = Trapezoidation { ps: PolygonListExt::new(self.polygons), ns, ss, ts, qs, interior: OnceLock::new(), merge: MergePolicy::default(), seed, _kernel: PhantomData };
+        trapezoidation.check_deserialized().map_err(serde::de::Error::custom)?;
+        Ok(trapezoidation)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'p, P: PolygonList<'p> + ?Sized, K: GeometryKernel<<P::Vertex as Vertex>::Coordinate>> Trapezoidation<'p, P, K> {
+    /// Check that a deserialized structure is linked within itself, and belongs to the polygons
+    fn check_deserialized(&self) -> Result<(), &'static str> {
+        fn within<T>(idx: Idx<T>, items: &ForkableVec<T>) -> bool {
+            idx.usize() < items.len()
+        }
+
+        if self.qs.len() == 0 {
+            return Err("A trapezoidation must have a query node");
+        }
+        if self.ns.len() != self.ps.vertex_count() {
+            return Err("The trapezoidation must have a nexus for each vertex of the polygons");
+        }
+
+        let linked = self.ns.iter_index().map(|ni| &self.ns[ni]).all(|n|
+                n.iter_up_trapezoids().chain(n.iter_down_trapezoids()).all(|ti| within(ti, &self.ts))
+                && n.iter_segments().all(|si| within(si, &self.ss)))
+            && self.ss.iter_index().map(|si| &self.ss[si]).all(|s| within(s.ni_min(), &self.ns) && within(s.ni_max(), &self.ns))
+            && self.ts.iter_index().map(|ti| &self.ts[ti]).all(|t|
+                t.left().into_iter().chain(t.right()).all(|si| within(si, &self.ss))
+                && t.up().into_iter().chain(t.down()).all(|ni| within(ni, &self.ns))
+                && within(t.sink(), &self.qs))
+            && self.qs.iter_index().map(|qi| &self.qs[qi]).all(|q| match q {
+                QueryNode::Branch(qi_left, qi_right, _) => within(*qi_left, &self.qs) && within(*qi_right, &self.qs),
+                QueryNode::Sink(ti) => within(*ti, &self.ts),
+            });
+        if !linked {
+            return Err("The trapezoidation must only refer to its own elements");
+        }
+
+        if !self.ns.iter_index().map(|ni| &self.ns[ni]).all(|n| self.ps[n.vertex()].coords() == *n.coords()) {
+            return Err("Each nexus must be at the position of its vertex in the polygons");
+        }
+        Ok(())
+    }
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, K: GeometryKernel<<P::Vertex as Vertex>::Coordinate>> TrapezoidationStructure<'p, P, K> for Trapezoidation<'p, P, K> {
     fn ps(&self) -> PolygonListExt<'p, P> { self.ps }
 
-    fn ns(&self) -> &[Nexus<<P as PolygonList<'p>>::Vertex, <P as PolygonList<'p>>::Index>] { &self.ns }
+    fn ns(&self) -> &ForkableVec<Nexus<<P as PolygonList<'p>>::Vertex, <P as PolygonList<'p>>::Index>> { &self.ns }
+
+    fn ss(&self) -> &ForkableVec<Segment<<P as PolygonList<'p>>::Vertex, <P as PolygonList<'p>>::Index>> { &self.ss }
+
+    fn ts(&self) -> &ForkableVec<Trapezoid<<P as PolygonList<'p>>::Vertex, <P as PolygonList<'p>>::Index>> { &self.ts }
+
+    fn qs(&self) -> &ForkableVec<QueryNode<<P as PolygonList<'p>>::Vertex, <P as PolygonList<'p>>::Index>> { &self.qs }
+}
+
+#[cfg(feature = "_debugging")]
+impl<'p, P: PolygonList<'p> + ?Sized, K: GeometryKernel<<P::Vertex as Vertex>::Coordinate>> Trapezoidation<'p, P, K> {
+    /// Render the trapezoidation to an SVG file at `path`: the triangles it is split into under the polygons, and its
+    /// trapezoids (yellow), segments (red) and nexuses (blue) over them. Elements are labelled with the names used in
+    /// the debug output, such as `t3 q7` for a trapezoid and its sink query node, unless
+    /// `TRIANGULATE_SVG_HIDE_LABELS` is set.
+    ///
+    /// If the trapezoidation can't be triangulated, the triangles built before the failure are drawn.
+    pub fn save_svg<Q: AsRef<std::path::Path>>(&self, path: Q) -> std::io::Result<()> {
+        let svg_context = DebugInfo::svg_context_at(&self.ps, std::path::PathBuf::new(), debug::svg::SvgOutputLevel::ResultOnly, debug::env::svg::show_labels());
+        self.write_svg(&svg_context, path)
+    }
+
+    fn write_svg<Q: AsRef<std::path::Path>>(&self, svg_context: &debug::svg::SvgContext, file_name: Q) -> std::io::Result<()> {
+        use crate::{formats, ListFormat};
 
-    fn ss(&self) -> &[Segment<<P as PolygonList<'p>>::Vertex, <P as PolygonList<'p>>::Index>] { &self.ss }
+        let mut triangles = Vec::<[P::Index; 3]>::new();
+        let _ = self.triangulate(formats::IndexedListFormat::new(&mut triangles).into_fan_format());
 
-    fn ts(&self) -> &[Trapezoid<<P as PolygonList<'p>>::Vertex, <P as PolygonList<'p>>::Index>] { &self.ts }
+        let mut svg = debug::svg::SvgOutput::new(svg_context, debug::svg::SvgTriangulationStyle::default());
+        svg.append_element(&self.svg_structure(&triangles), &())
+            .map_err(|_| std::io::Error::new(std::io::ErrorKind::Other, "Failed to render the trapezoidation"))?;
+        svg.save(file_name)
+    }
+}
 
-    fn qs(&self) -> &[QueryNode<<P as PolygonList<'p>>::Vertex, <P as PolygonList<'p>>::Index>] { &self.qs }
+/// The elements of a trapezoidation which are drawn to SVG, from a [TrapezoidationState] as it is built or from a
+/// finished [Trapezoidation] with its triangles
+#[cfg(feature = "_debugging")]
+struct SvgStructure<'t, 'p, P: PolygonList<'p> + ?Sized> {
+    ps: PolygonListExt<'p, P>,
+    ns: &'t ForkableVec<Nexus<P::Vertex, P::Index>>,
+    ss: &'t ForkableVec<Segment<P::Vertex, P::Index>>,
+    ts: &'t ForkableVec<Trapezoid<P::Vertex, P::Index>>,
+    triangles: &'t [[P::Index; 3]],
 }
 
 #[cfg(feature = "_debugging")]
-impl<'p, P: PolygonList<'p> + ?Sized> debug::svg::SvgElement<debug::svg::SvgTriangulationStyle<'p, P::Vertex, P::Index>, ()> for TrapezoidationState<'p, P> {
+impl<'t, 'p, P: PolygonList<'p> + ?Sized> debug::svg::SvgElement<debug::svg::SvgTriangulationStyle<'p, P::Vertex, P::Index>, ()> for SvgStructure<'t, 'p, P> {
     fn write_svg<'b>(&self, svg_output: &mut debug::svg::SvgOutput<'b, debug::svg::SvgTriangulationStyle<'p, P::Vertex, P::Index>>, _state: &()) -> fmt::Result {
         use svg_fmt::*;
         use fmt::Write;
 
+        for triangle in self.triangles {
+            let vs = triangle.clone().map(|vi| {
+                let v = &self.ps[vi];
+                [v.x().to_f32().unwrap(), v.y().to_f32().unwrap()]
+            });
+            writeln!(svg_output, "{}",
+                debug::svg::polygon(&vs, true)
+                    .fill(Fill::Color(rgb(200, 230, 255)))
+                    .stroke(Stroke::Color(rgb(120, 160, 200), svg_output.context.percent(0.1)))
+            )?;
+        }
+
+        let mut vs = Vec::new();
         for pv in self.ps.iter_polygon_vertices().map(Into::into).chain(iter::once(PolygonElement::NewPolygon)) {
-            let mut vs = Vec::new();
             match pv {
                 PolygonElement::ContinuePolygon(index) => {
                     let v = &self.ps[index];
@@ -917,7 +1774,8 @@ impl<'p, P: PolygonList<'p> + ?Sized> debug::svg::SvgElement<debug::svg::SvgTria
                 PolygonElement::NewPolygon => {
                     if vs.len() > 2 {
                         writeln!(svg_output, "{}", 
-                            polygon(&vs)
+                            debug::svg::polygon(&vs, true)
+                                .fill(Fill::None)
                                 .stroke(Stroke::Color(rgb(255, 0, 255), svg_output.context.percent(0.3)))
                         )?;
                     }
@@ -947,8 +1805,8 @@ impl<'p, P: PolygonList<'p> + ?Sized> debug::svg::SvgElement<debug::svg::SvgTria
 struct IndexWrap<Index>(Index);
 
 #[cfg(feature = "_debugging")]
-impl<'p, P: PolygonList<'p> + ?Sized> debug::svg::SvgElement<debug::svg::SvgTriangulationStyle<'p, P::Vertex, P::Index>, TrapezoidationState<'p, P>> for IndexWrap<P::Index> {
-    fn write_svg<'b>(&self, svg_output: &mut debug::svg::SvgOutput<'b, debug::svg::SvgTriangulationStyle<'p, P::Vertex, P::Index>>, state: &TrapezoidationState<'p, P>) -> std::fmt::Result {
+impl<'t, 'p, P: PolygonList<'p> + ?Sized> debug::svg::SvgElement<debug::svg::SvgTriangulationStyle<'p, P::Vertex, P::Index>, SvgStructure<'t, 'p, P>> for IndexWrap<P::Index> {
+    fn write_svg<'b>(&self, svg_output: &mut debug::svg::SvgOutput<'b, debug::svg::SvgTriangulationStyle<'p, P::Vertex, P::Index>>, state: &SvgStructure<'t, 'p, P>) -> core::fmt::Result {
         use svg_fmt::*;
         use fmt::Write;
 
@@ -982,8 +1840,8 @@ fn get_x_intercept<V: Vertex>(v_min: &VertexExt<V>, v_max: &VertexExt<V>, y: f32
 }
 
 #[cfg(feature = "_debugging")]
-impl<'p, P: PolygonList<'p> + ?Sized> debug::svg::SvgElement<debug::svg::SvgTriangulationStyle<'p, P::Vertex, P::Index>, TrapezoidationState<'p, P>> for Idx<Nexus<P::Vertex, P::Index>> {
-    fn write_svg<'b>(&self, svg_output: &mut debug::svg::SvgOutput<'b, debug::svg::SvgTriangulationStyle<'p, P::Vertex, P::Index>>, state: &TrapezoidationState<'p, P>) -> fmt::Result {
+impl<'t, 'p, P: PolygonList<'p> + ?Sized> debug::svg::SvgElement<debug::svg::SvgTriangulationStyle<'p, P::Vertex, P::Index>, SvgStructure<'t, 'p, P>> for Idx<Nexus<P::Vertex, P::Index>> {
+    fn write_svg<'b>(&self, svg_output: &mut debug::svg::SvgOutput<'b, debug::svg::SvgTriangulationStyle<'p, P::Vertex, P::Index>>, state: &SvgStructure<'t, 'p, P>) -> fmt::Result {
         use svg_fmt::*;
         use fmt::Write;
 
@@ -1028,7 +1886,7 @@ impl<'p, P: PolygonList<'p> + ?Sized> debug::svg::SvgElement<debug::svg::SvgTria
                 .fill(fill)
         )?;
 
-        if debug::env::svg::show_labels() {
+        if svg_output.context.show_labels {
             let gap = svg_output.context.percent(1.0);
             writeln!(svg_output, "{}",
                 text(v.x().to_f32().unwrap() - gap, v.y().to_f32().unwrap(), self.to_string())
@@ -1042,8 +1900,8 @@ impl<'p, P: PolygonList<'p> + ?Sized> debug::svg::SvgElement<debug::svg::SvgTria
 }
 
 #[cfg(feature = "_debugging")]
-impl<'p, P: PolygonList<'p> + ?Sized> debug::svg::SvgElement<debug::svg::SvgTriangulationStyle<'p, P::Vertex, P::Index>, TrapezoidationState<'p, P>> for Idx<Segment<P::Vertex, P::Index>> {
-    fn write_svg<'b>(&self, svg_output: &mut debug::svg::SvgOutput<'b, debug::svg::SvgTriangulationStyle<'p, P::Vertex, P::Index>>, state: &TrapezoidationState<'p, P>) -> fmt::Result {
+impl<'t, 'p, P: PolygonList<'p> + ?Sized> debug::svg::SvgElement<debug::svg::SvgTriangulationStyle<'p, P::Vertex, P::Index>, SvgStructure<'t, 'p, P>> for Idx<Segment<P::Vertex, P::Index>> {
+    fn write_svg<'b>(&self, svg_output: &mut debug::svg::SvgOutput<'b, debug::svg::SvgTriangulationStyle<'p, P::Vertex, P::Index>>, state: &SvgStructure<'t, 'p, P>) -> fmt::Result {
         use svg_fmt::*;
         use fmt::Write;
 
@@ -1066,13 +1924,24 @@ impl<'p, P: PolygonList<'p> + ?Sized> debug::svg::SvgElement<debug::svg::SvgTria
                 .color(color)
                 .width(width)
         )?;
+        if svg_output.context.show_labels {
+            let x = (v_min.x().to_f32().unwrap() + v_max.x().to_f32().unwrap()) / 2.0;
+            let y = (v_min.y().to_f32().unwrap() + v_max.y().to_f32().unwrap()) / 2.0;
+            let gap = svg_output.context.percent(1.0);
+            writeln!(svg_output, "{}",
+                text(x + gap, y, self.to_string())
+                    .color(red())
+                    .align(Align::Left)
+                    .size(svg_output.context.percent(1.0))
+            )?;
+        }
         Ok(())
     }
 }
 
 #[cfg(feature = "_debugging")]
-impl<'p, P: PolygonList<'p> + ?Sized> debug::svg::SvgElement<debug::svg::SvgTriangulationStyle<'p, P::Vertex, P::Index>, TrapezoidationState<'p, P>> for Idx<Trapezoid<P::Vertex, P::Index>> {
-    fn write_svg<'b>(&self, svg_output: &mut debug::svg::SvgOutput<'b, debug::svg::SvgTriangulationStyle<'p, P::Vertex, P::Index>>, state: &TrapezoidationState<'p, P>) -> fmt::Result {
+impl<'t, 'p, P: PolygonList<'p> + ?Sized> debug::svg::SvgElement<debug::svg::SvgTriangulationStyle<'p, P::Vertex, P::Index>, SvgStructure<'t, 'p, P>> for Idx<Trapezoid<P::Vertex, P::Index>> {
+    fn write_svg<'b>(&self, svg_output: &mut debug::svg::SvgOutput<'b, debug::svg::SvgTriangulationStyle<'p, P::Vertex, P::Index>>, state: &SvgStructure<'t, 'p, P>) -> fmt::Result {
         use svg_fmt::*;
         use fmt::Write;
 
@@ -1127,10 +1996,10 @@ impl<'p, P: PolygonList<'p> + ?Sized> debug::svg::SvgElement<debug::svg::SvgTria
                 .color(color)
                 .width(width)
         )?;
-        if debug::env::svg::show_labels() {
+        if svg_output.context.show_labels {
             writeln!(svg_output, "{}",
-                text(x, y, self.to_string())
-                    .size(svg_output.context.percent(0.15))
+                text(x, y, format!("{} {}", self, t.sink()))
+                    .size(svg_output.context.percent(1.0))
                     .align(Align::Center)
             )?;
         }
@@ -1140,15 +2009,15 @@ impl<'p, P: PolygonList<'p> + ?Sized> debug::svg::SvgElement<debug::svg::SvgTria
 }
 
 #[cfg(feature = "_debugging")]
-impl<'p, P: PolygonList<'p> + ?Sized> debug::svg::SvgElement<debug::svg::SvgTriangulationStyle<'p, P::Vertex, P::Index>, TrapezoidationState<'p, P>> for Monotone<P::Index, <P::Vertex as Vertex>::Coordinate> {
-    fn write_svg<'b>(&self, svg_output: &mut debug::svg::SvgOutput<'b, debug::svg::SvgTriangulationStyle<'p, P::Vertex, P::Index>>, state: &TrapezoidationState<'p, P>) -> fmt::Result {
+impl<'t, 'p, P: PolygonList<'p> + ?Sized> debug::svg::SvgElement<debug::svg::SvgTriangulationStyle<'p, P::Vertex, P::Index>, SvgStructure<'t, 'p, P>> for Monotone<P::Index, <P::Vertex as Vertex>::Coordinate> {
+    fn write_svg<'b>(&self, svg_output: &mut debug::svg::SvgOutput<'b, debug::svg::SvgTriangulationStyle<'p, P::Vertex, P::Index>>, state: &SvgStructure<'t, 'p, P>) -> fmt::Result {
         use svg_fmt::*;
         use fmt::Write;
 
         let points: Vec<_> = self.skipped_and_pending.iter().map(|(vi, _)| &state.ps[vi.clone()]).map(|v| [v.x().to_f32().unwrap(), v.y().to_f32().unwrap()]).collect();
         writeln!(svg_output, "{}",
-            polygon(&points)
-                .open()
+            debug::svg::polygon(&points, false)
+                .fill(Fill::None)
                 .stroke(Stroke::Color(black(), svg_output.context.percent(0.1)))
                 .stroke_opacity(0.5)
         )?;
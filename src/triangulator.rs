@@ -0,0 +1,54 @@
+use core::mem;
+
+use crate::{trapezoidation::{Storage, TrapezoidationState}, FanFormat, FanResult, PolygonList, TriangulationError, Vertex, VertexIndex};
+
+/// Triangulates polygons one after another, reusing the memory of each trapezoidation for the next.
+///
+/// [PolygonList::triangulate] allocates the nexuses, segments, trapezoids and query nodes of the trapezoidation anew
+/// for every call, which dominates the time spent on many small inputs, such as the outlines of glyphs. A
+/// `Triangulator` keeps them between calls, so once it has triangulated an input as large as the next, building the
+/// trapezoidation allocates almost nothing. The polygons of each call may be of different types, but they must have
+/// the same [Vertex] and [VertexIndex] types.
+///
+/// ```
+/// # use triangulate::{formats, ListFormat, Triangulator};
+/// let glyphs = vec![
+///     vec![vec![[0f32, 0.], [1., 0.], [1., 1.], [0., 1.]]],
+///     vec![vec![[0f32, 0.], [2., 0.], [1., 1.]], vec![[0.9, 0.2], [1.1, 0.2], [1., 0.4]]],
+/// ];
+/// let mut triangulator = Triangulator::new();
+/// for glyph in &glyphs {
+///     let mut triangles = Vec::<[f32; 2]>::new();
+///     triangulator.triangulate(glyph, formats::DeindexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+///     assert!(!triangles.is_empty());
+/// }
+/// ```
+pub struct Triangulator<V: Vertex, Index: VertexIndex> {
+    storage: Storage<V, Index>,
+}
+
+impl<V: Vertex, Index: VertexIndex> Triangulator<V, Index> {
+    /// A triangulator which has not allocated anything yet
+    pub fn new() -> Self {
+        Self { storage: Storage::default() }
+    }
+
+    /// Triangulate `polygons` into the layout specified by `format`, like [PolygonList::triangulate]
+    pub fn triangulate<'p, P, FB>(&mut self, polygons: &'p P, format: FB) -> FanResult<'p, P, FB>
+    where P: PolygonList<'p, Vertex=V, Index=Index> + ?Sized,
+          FB: FanFormat<'p, P> {
+        // The storage is lost if the trapezoidation fails, and allocated again by the next call
+        let trapezoidation = TrapezoidationState::<'p, P>::with_storage(polygons, mem::take(&mut self.storage))
+            .build()
+            .map_err(TriangulationError::TrapezoidationError)?;
+        let result = trapezoidation.triangulate(format);
+        self.storage = trapezoidation.into_storage();
+        result
+    }
+}
+
+impl<V: Vertex, Index: VertexIndex> Default for Triangulator<V, Index> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
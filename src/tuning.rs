@@ -0,0 +1,74 @@
+//! Choosing [TriangulationOptions] for a workload by measuring them on a sample of its inputs. Requires the `std`
+//! feature.
+//!
+//! Which method triangulates fastest depends on the shape of the inputs: many small glyph outlines, long GIS
+//! coastlines and UI shapes with rounded corners all behave differently. [auto_tune] triangulates a sample with each
+//! candidate and recommends the fastest, so an application can tune itself once per deployment, such as on startup,
+//! and keep the result.
+//!
+//! ```
+//! # use triangulate::{shapes, tuning, ListFormat, PolygonList, TriangulationMethod, TriangulationOptions, formats};
+//! let samples = vec![shapes::circle([0f64, 0.], 1., 64), shapes::ring([0., 0.], 1., 2., 32)];
+//! let methods = [TriangulationMethod::Seidel, TriangulationMethod::ConstrainedDelaunay];
+//! let options = tuning::auto_tune(&samples, TriangulationOptions::default(), &methods).expect("Triangulation failed");
+//! assert!(methods.contains(&options.method));
+//!
+//! let mut triangles = Vec::<[[usize; 2]; 3]>::new();
+//! samples[1].triangulate_with_options(formats::IndexedListFormat::new(&mut triangles).into_fan_format(), &options).expect("Triangulation failed");
+//! assert_eq!(triangles.len(), 64);
+//! ```
+
+use core::convert::Infallible;
+use alloc::vec::Vec;
+use std::time::{Duration, Instant};
+
+use crate::{formats, ListFormat, PolygonList, TriangulationError, TriangulationMethod, TriangulationOptions};
+
+/// The number of times each candidate triangulates the samples, keeping its fastest run
+const ROUNDS: usize = 3;
+
+/// `base` with the [method](TriangulationOptions::method) of `methods` which triangulates `samples` fastest.
+///
+/// The methods produce different triangulations, so only those whose output the caller accepts should be listed; the
+/// rest of `base` is kept as it is. Every candidate triangulates all samples several times, and the one with the
+/// fastest run is returned. Candidates which fail on a sample are skipped, and the first error is returned if all of
+/// them fail. Without samples or methods, this is `base`.
+pub fn auto_tune<'p, P: PolygonList<'p>>(samples: &'p [P], base: TriangulationOptions, methods: &[TriangulationMethod]) -> Result<TriangulationOptions, TriangulationError<Infallible>> {
+    if samples.is_empty() {
+        return Ok(base);
+    }
+    let mut best: Option<(Duration, TriangulationOptions)> = None;
+    let mut first_error = None;
+    for &method in methods {
+        let mut options = base;
+        options.method = method;
+        match measure(samples, &options) {
+            Ok(time) => if best.is_none_or(|(best_time, _)| time < best_time) {
+                best = Some((time, options));
+            },
+            Err(error) => {
+                first_error.get_or_insert(error);
+            }
+        }
+    }
+    match (best, first_error) {
+        (Some((_, options)), _) => Ok(options),
+        (None, Some(error)) => Err(error),
+        (None, None) => Ok(base),
+    }
+}
+
+// The fastest of the rounds of triangulating every sample with `options`
+fn measure<'p, P: PolygonList<'p>>(samples: &'p [P], options: &TriangulationOptions) -> Result<Duration, TriangulationError<Infallible>> {
+    let mut triangles = Vec::<[P::Index; 3]>::new();
+    let mut fastest = Duration::MAX;
+    for _ in 0..ROUNDS {
+        let start = Instant::now();
+        for polygons in samples {
+            triangles.clear();
+            polygons.triangulate_with_options(formats::IndexedListFormat::new(&mut triangles).into_fan_format(), options)?;
+        }
+        fastest = fastest.min(start.elapsed());
+    }
+    Ok(fastest)
+}
@@ -0,0 +1,427 @@
+//! OGC-style validity checks, following the semantics (and reason names) of PostGIS's `ST_IsValid` and `ST_IsSimple`.
+//!
+//! Polygons are interpreted with the even-odd rule, as in triangulation, so a ring nested inside another is a hole
+//! without needing any particular winding. Rings may touch each other at isolated points, as long as the filled area
+//! stays connected; crossings, overlapping edges and rings touching themselves are invalid.
+//!
+//! ```
+//! # use triangulate::validity::{self, InvalidReason};
+//! let square = vec![vec![[0f64, 0.], [2., 0.], [2., 2.], [0., 2.]]];
+//! assert!(validity::is_valid(&square));
+//!
+//! let bowtie = vec![vec![[0f64, 0.], [2., 2.], [2., 0.], [0., 2.]]];
+//! assert!(!validity::is_simple(&bowtie));
+//! assert_eq!(validity::validate(&bowtie), vec![InvalidReason::RingSelfIntersection { polygon: 0, point: [1., 1.] }]);
+//! assert_eq!(validity::validate(&bowtie)[0].to_string(), "Ring Self-intersection[1 1]");
+//! ```
+//!
+//! To find exactly which edges are at fault, [segment_intersections] (or [PolygonList::validate]) lists each pair of
+//! crossing, touching or overlapping edges:
+//!
+//! ```
+//! # use triangulate::{validity::SegmentIntersection, PolygonList};
+//! let bowtie = vec![vec![[0f64, 0.], [2., 2.], [2., 0.], [0., 2.]]];
+//! assert_eq!(bowtie.validate(), Err(vec![
+//!     SegmentIntersection { segments: [[0, 0], [0, 2]], point: [1., 1.], overlapping: false },
+//! ]));
+//! ```
+
+use core::{f64::consts::TAU, fmt};
+use alloc::vec::Vec;
+
+use num_traits::{Euclid, One, Zero, real::Real};
+
+use crate::{compat::HashSet, float_format::{Float, FloatFormat, FormatFloats}, intersection, math, PolygonList, PolygonListExt, Vertex};
+
+/// The reason a set of polygons is not valid. The [Display](fmt::Display) output matches PostGIS's `ST_IsValidReason`.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum InvalidReason<C: Real> {
+    /// A polygon has fewer than 3 distinct consecutive vertices
+    TooFewPoints {
+        /// The index of the polygon
+        polygon: usize,
+    },
+    /// A vertex has a non-finite coordinate
+    InvalidCoordinate {
+        /// The index of the polygon
+        polygon: usize,
+        /// The index of the vertex within the polygon
+        vertex: usize,
+    },
+    /// A polygon crosses or touches itself, or has overlapping edges
+    RingSelfIntersection {
+        /// The index of the polygon
+        polygon: usize,
+        /// The location of the intersection
+        point: [C; 2],
+    },
+    /// Two polygons cross or have overlapping edges
+    SelfIntersection {
+        /// The indices of the polygons
+        polygons: [usize; 2],
+        /// The location of the intersection
+        point: [C; 2],
+    },
+    /// Rings touch in a way that splits a filled area into several pieces
+    DisconnectedInterior {
+        /// The location of the touch point which disconnects the area
+        point: [C; 2],
+    },
+}
+
+impl<C: Real> InvalidReason<C> {
+    /// The PostGIS reason text, without the location
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::TooFewPoints { .. } => "Too few points",
+            Self::InvalidCoordinate { .. } => "Invalid Coordinate",
+            Self::RingSelfIntersection { .. } => "Ring Self-intersection",
+            Self::SelfIntersection { .. } => "Self-intersection",
+            Self::DisconnectedInterior { .. } => "Interior is disconnected",
+        }
+    }
+
+    /// The location of the problem, if it has one
+    pub fn point(&self) -> Option<[C; 2]> {
+        match self {
+            Self::RingSelfIntersection { point, .. } |
+            Self::SelfIntersection { point, .. } |
+            Self::DisconnectedInterior { point } => Some(*point),
+            _ => None,
+        }
+    }
+}
+
+impl<C: Real> fmt::Display for InvalidReason<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_floats(f, &FloatFormat::for_formatter(f))
+    }
+}
+
+impl<C: Real> FormatFloats for InvalidReason<C> {
+    fn fmt_floats(&self, f: &mut fmt::Formatter<'_>, format: &FloatFormat) -> fmt::Result {
+        f.write_str(self.code())?;
+        if let Some([x, y]) = self.point() {
+            write!(f, "[{} {}]", Float(x.to_f64().unwrap_or(f64::NAN), *format), Float(y.to_f64().unwrap_or(f64::NAN), *format))?;
+        }
+        Ok(())
+    }
+}
+
+/// A pair of polygon edges which meet somewhere other than at a vertex they share, found by [segment_intersections]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SegmentIntersection<C: Real> {
+    /// The edges, as `[polygon, edge]` in ascending order. Edge `k` runs from vertex `k` of the polygon to the next,
+    /// and polygons are counted in iteration order, skipping empty ones
+    pub segments: [[usize; 2]; 2],
+    /// Where the edges meet. For overlapping edges, one end of the shared part
+    pub point: [C; 2],
+    /// Whether the edges are collinear and share more than a point
+    pub overlapping: bool,
+}
+
+/// Every pair of edges which cross, touch, or overlap, other than consecutive edges of a polygon meeting at their
+/// shared vertex. Overlapping edges are reported once; edges meeting at several points are reported at each.
+///
+/// The result is ordered from the bottom to the top of the intersection points, as in
+/// [find_intersections](intersection::find_intersections).
+pub fn segment_intersections<'p, P: PolygonList<'p> + ?Sized>(polygons: &'p P) -> Vec<SegmentIntersection<<P::Vertex as Vertex>::Coordinate>>
+where <P::Vertex as Vertex>::Coordinate: Real + fmt::Debug {
+    let contours = PolygonListExt::new(polygons).contours();
+    let mut edges = Vec::with_capacity(polygons.vertex_count());
+    let mut ids = Vec::with_capacity(polygons.vertex_count());
+    for (polygon, contour) in contours.iter().enumerate() {
+        for (k, c) in contour.iter().enumerate() {
+            edges.push([*c, contour[(k + 1) % contour.len()]]);
+            ids.push([polygon, k]);
+        }
+    }
+    let adjacent = |a: [usize; 2], b: [usize; 2]| {
+        let n = contours[a[0]].len();
+        a[0] == b[0] && ((a[1] + 1) % n == b[1] || (b[1] + 1) % n == a[1])
+    };
+
+    let mut reported = HashSet::new();
+    let mut result = Vec::new();
+    for intersection in intersection::find_intersections(&edges) {
+        let segments = intersection.segments();
+        for (i, &si) in segments.iter().enumerate() {
+            for &sj in &segments[i + 1..] {
+                let overlapping = overlapping(edges[si], edges[sj]);
+                if adjacent(ids[si], ids[sj]) && !overlapping {
+                    continue;
+                }
+                if overlapping && !reported.insert([si, sj]) {
+                    continue;
+                }
+                result.push(SegmentIntersection { segments: [ids[si], ids[sj]], point: intersection.point, overlapping });
+            }
+        }
+    }
+    result
+}
+
+/// A place where the filled area of the polygons is thinner than the `epsilon` given to [necks], between two edges
+#[derive(Debug, Clone, PartialEq)]
+pub struct Neck<C: Real> {
+    /// The edges on either side of the neck, as `[polygon, edge]` in ascending order, numbered as in
+    /// [SegmentIntersection::segments]
+    pub segments: [[usize; 2]; 2],
+    /// The closest points of the edges, in the same order
+    pub points: [[C; 2]; 2],
+    /// The distance between the points
+    pub width: C,
+}
+
+/// Every place where the filled area of the polygons is thinner than `epsilon`, narrowest first.
+///
+/// This probes for the places where shrinking the polygons by `epsilon / 2` (a negative offset) would break the filled
+/// area apart: pairs of edges, other than consecutive ones, which come closer than `epsilon` across the filled area, or
+/// which touch. Such necks are valid, but are where trapezoidation is most sensitive to rounding, so inputs such as
+/// scanned drawings can be checked for them before triangulation. Edges crossing each other are not necks, and are
+/// found by [segment_intersections]. Along a long thin part, each pair of edges facing each other is reported.
+///
+/// ```
+/// # use triangulate::validity;
+/// // Two squares joined by a corridor 0.01 wide
+/// let dumbbell = vec![vec![
+///     [0f64, 0.], [2., 0.], [2., 0.995], [3., 0.995], [3., 0.], [5., 0.],
+///     [5., 2.], [3., 2.], [3., 1.005], [2., 1.005], [2., 2.], [0., 2.],
+/// ]];
+/// let necks = validity::necks(&dumbbell, 0.1);
+/// assert!(!necks.is_empty());
+/// assert!(necks.iter().all(|neck| (neck.width - 0.01).abs() < 1e-9));
+/// assert!(validity::necks(&dumbbell, 0.001).is_empty());
+/// ```
+pub fn necks<'p, P: PolygonList<'p> + ?Sized>(polygons: &'p P, epsilon: <P::Vertex as Vertex>::Coordinate) -> Vec<Neck<<P::Vertex as Vertex>::Coordinate>>
+where <P::Vertex as Vertex>::Coordinate: Real + fmt::Debug {
+    let contours = PolygonListExt::new(polygons).contours();
+    let mut edges = Vec::with_capacity(polygons.vertex_count());
+    for (polygon, contour) in contours.iter().enumerate() {
+        for (k, c) in contour.iter().enumerate() {
+            edges.push(([*c, contour[(k + 1) % contour.len()]], [polygon, k]));
+        }
+    }
+    let adjacent = |a: [usize; 2], b: [usize; 2]| {
+        let n = contours[a[0]].len();
+        a[0] == b[0] && ((a[1] + 1) % n == b[1] || (b[1] + 1) % n == a[1])
+    };
+
+    // Only edges whose bounding boxes come within `epsilon` of each other can be close, so sweep over them by x
+    let bounds = |[a, b]: [[<P::Vertex as Vertex>::Coordinate; 2]; 2]| [[a[0].min(b[0]), a[1].min(b[1])], [a[0].max(b[0]), a[1].max(b[1])]];
+    edges.sort_by(|(a, _), (b, _)| bounds(*a)[0][0].partial_cmp(&bounds(*b)[0][0]).unwrap_or(core::cmp::Ordering::Equal));
+    let two = <P::Vertex as Vertex>::Coordinate::one() + <P::Vertex as Vertex>::Coordinate::one();
+    let mut result = Vec::new();
+    for (i, &(a, id_a)) in edges.iter().enumerate() {
+        let [min_a, max_a] = bounds(a);
+        for &(b, id_b) in &edges[i + 1..] {
+            let [min_b, max_b] = bounds(b);
+            if min_b[0] > max_a[0] + epsilon {
+                break;
+            }
+            if min_b[1] > max_a[1] + epsilon || min_a[1] > max_b[1] + epsilon || adjacent(id_a, id_b) || crossing(a, b) {
+                continue;
+            }
+            // Edges which don't cross are closest at an end of one of them
+            let Some((width, points)) = [(a[0], b, false), (a[1], b, false), (b[0], a, true), (b[1], a, true)].into_iter()
+                .map(|(c, segment, flip)| {
+                    let q = closest_point(segment, c);
+                    let width = (q[0] - c[0]).hypot(q[1] - c[1]);
+                    (width, if flip { [q, c] } else { [c, q] })
+                })
+                .min_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(core::cmp::Ordering::Equal)) else { continue };
+            if width >= epsilon {
+                continue;
+            }
+            // Edges which touch pinch the filled area to a point; otherwise, the gap between them must be filled
+            let middle = [(points[0][0] + points[1][0]) / two, (points[0][1] + points[1][1]) / two];
+            if width > <P::Vertex as Vertex>::Coordinate::zero() && !math::contours_contain(&contours, middle) {
+                continue;
+            }
+            result.push(if id_a < id_b {
+                Neck { segments: [id_a, id_b], points, width }
+            } else {
+                Neck { segments: [id_b, id_a], points: [points[1], points[0]], width }
+            });
+        }
+    }
+    result.sort_by(|a, b| a.width.partial_cmp(&b.width).unwrap_or(core::cmp::Ordering::Equal).then(a.segments.cmp(&b.segments)));
+    result
+}
+
+/// Whether every polygon is simple: it has enough vertices, and does not cross or touch itself
+pub fn is_simple<'p, P: PolygonList<'p> + ?Sized>(polygons: &'p P) -> bool
+where <P::Vertex as Vertex>::Coordinate: Real + fmt::Debug {
+    !validate(polygons).iter().any(|reason| matches!(reason,
+        InvalidReason::TooFewPoints { .. } | InvalidReason::InvalidCoordinate { .. } | InvalidReason::RingSelfIntersection { .. }))
+}
+
+/// Whether the polygons are valid: each polygon is simple, no two polygons cross, and every filled area is connected
+pub fn is_valid<'p, P: PolygonList<'p> + ?Sized>(polygons: &'p P) -> bool
+where <P::Vertex as Vertex>::Coordinate: Real + fmt::Debug {
+    validate(polygons).is_empty()
+}
+
+/// Every reason the polygons are not valid. Polygons with too few points or invalid coordinates are excluded from further checks.
+pub fn validate<'p, P: PolygonList<'p> + ?Sized>(polygons: &'p P) -> Vec<InvalidReason<<P::Vertex as Vertex>::Coordinate>>
+where <P::Vertex as Vertex>::Coordinate: Real + fmt::Debug {
+    validate_contours(&PolygonListExt::new(polygons).contours())
+}
+
+pub(crate) fn validate_contours<C: Real + fmt::Debug>(contours: &[Vec<[C; 2]>]) -> Vec<InvalidReason<C>> {
+    let mut reasons = Vec::new();
+
+    // Checked rings, with consecutive duplicate points removed
+    let mut rings: Vec<(usize, Vec<[C; 2]>)> = Vec::new();
+    for (polygon, contour) in contours.iter().enumerate() {
+        if let Some(vertex) = contour.iter().position(|c| !is_finite(c[0]) || !is_finite(c[1])) {
+            reasons.push(InvalidReason::InvalidCoordinate { polygon, vertex });
+            continue;
+        }
+        let mut ring = contour.clone();
+        ring.dedup();
+        while ring.len() > 1 && ring.first() == ring.last() {
+            ring.pop();
+        }
+        if ring.len() < 3 {
+            reasons.push(InvalidReason::TooFewPoints { polygon });
+            continue;
+        }
+        rings.push((polygon, ring));
+    }
+
+    let mut edges = Vec::new();
+    let mut edge_ring = Vec::new();
+    for (ri, (_, ring)) in rings.iter().enumerate() {
+        for (k, c) in ring.iter().enumerate() {
+            edges.push([*c, ring[(k + 1) % ring.len()]]);
+            edge_ring.push(ri);
+        }
+    }
+
+    // The nesting of the rings, to find touches which disconnect a filled area
+    let ring_contours: Vec<Vec<[C; 2]>> = rings.iter().map(|(_, ring)| ring.clone()).collect();
+    let areas: Vec<C> = ring_contours.iter().map(|ring| math::signed_area_2(ring).abs()).collect();
+    let parents: Vec<Option<usize>> = (0..rings.len()).map(|ri| {
+        (0..rings.len())
+            .filter(|&rj| rj != ri && areas[rj] > areas[ri] && math::contour_contains(&ring_contours[rj], ring_contours[ri][0]))
+            .min_by(|&rj, &rk| areas[rj].partial_cmp(&areas[rk]).unwrap_or(core::cmp::Ordering::Equal))
+    }).collect();
+    let depth = |ri: usize| core::iter::successors(parents[ri], |&rj| parents[rj]).count();
+    let mut components: Vec<usize> = (0..rings.len()).collect();
+    fn find(components: &mut [usize], mut i: usize) -> usize {
+        while components[i] != i {
+            components[i] = components[components[i]];
+            i = components[i];
+        }
+        i
+    }
+
+    for intersection in intersection::find_intersections(&edges) {
+        let point = intersection.point;
+        // The directions leaving the point along each ring's edges, one pair per time the ring passes through the point
+        let mut passes: Vec<(usize, Vec<[C; 2]>)> = Vec::new();
+        for si in intersection.interior.iter().chain(intersection.endpoint.iter()) {
+            let ri = edge_ring[*si];
+            let directions = edges[*si].iter().filter(|c| **c != point).map(|c| [c[0] - point[0], c[1] - point[1]]);
+            match passes.iter_mut().find(|(rj, _)| *rj == ri) {
+                Some((_, rays)) => rays.extend(directions),
+                None => passes.push((ri, directions.collect())),
+            }
+        }
+
+        let mut self_intersecting = false;
+        for (ri, rays) in passes.iter() {
+            if rays.len() > 2 || (rays.len() == 2 && same_direction(rays[0], rays[1])) {
+                reasons.push(InvalidReason::RingSelfIntersection { polygon: rings[*ri].0, point });
+                self_intersecting = true;
+            }
+        }
+        if self_intersecting {
+            continue;
+        }
+
+        for (i, (ri, a)) in passes.iter().enumerate() {
+            for (rj, b) in passes.iter().skip(i + 1) {
+                let polygons = [rings[*ri].0, rings[*rj].0];
+                if a.len() != 2 || b.len() != 2 {
+                    continue;
+                }
+                if a.iter().any(|ra| b.iter().any(|rb| same_direction(*ra, *rb))) || crosses(a, b) {
+                    reasons.push(InvalidReason::SelfIntersection { polygons, point });
+                    continue;
+                }
+
+                // The rings touch. If they bound the same filled area, and are already connected through other touches, the area is split
+                let inside_filled = |rp: usize| depth(rp) % 2 == 0;
+                let shares_face = parents[*rj] == Some(*ri) && inside_filled(*ri) ||
+                    parents[*ri] == Some(*rj) && inside_filled(*rj) ||
+                    parents[*ri] == parents[*rj] && parents[*ri].is_some_and(inside_filled);
+                if shares_face {
+                    let (ci, cj) = (find(&mut components, *ri), find(&mut components, *rj));
+                    if ci == cj {
+                        reasons.push(InvalidReason::DisconnectedInterior { point });
+                    } else {
+                        components[ci] = cj;
+                    }
+                }
+            }
+        }
+    }
+
+    reasons
+}
+
+// `Real` has no `is_finite`; NaN fails every comparison
+fn is_finite<C: Real>(c: C) -> bool {
+    c.abs() <= C::max_value()
+}
+
+fn same_direction<C: Real>(a: [C; 2], b: [C; 2]) -> bool {
+    a[0] * b[1] - a[1] * b[0] == C::zero() && a[0] * b[0] + a[1] * b[1] > C::zero()
+}
+
+// Whether the segments are collinear and share more than a point
+fn overlapping<C: Real>(a: [[C; 2]; 2], b: [[C; 2]; 2]) -> bool {
+    let d = [a[1][0] - a[0][0], a[1][1] - a[0][1]];
+    let length_2 = d[0] * d[0] + d[1] * d[1];
+    let cross = |p: [C; 2]| d[0] * (p[1] - a[0][1]) - d[1] * (p[0] - a[0][0]);
+    if length_2 == C::zero() || cross(b[0]) != C::zero() || cross(b[1]) != C::zero() {
+        return false;
+    }
+    // The positions of the ends of `b` along `a`, where `a` runs from 0 to 1
+    let t = |p: [C; 2]| (d[0] * (p[0] - a[0][0]) + d[1] * (p[1] - a[0][1])) / length_2;
+    let (t0, t1) = (t(b[0]), t(b[1]));
+    t0.max(t1).min(C::one()) > t0.min(t1).max(C::zero())
+}
+
+// Whether the segments cross at a point inside both
+fn crossing<C: Real>(a: [[C; 2]; 2], b: [[C; 2]; 2]) -> bool {
+    let side = |[p, q]: [[C; 2]; 2], c: [C; 2]| {
+        let cross = (q[0] - p[0]) * (c[1] - p[1]) - (q[1] - p[1]) * (c[0] - p[0]);
+        if cross > C::zero() { 1 } else if cross < C::zero() { -1 } else { 0 }
+    };
+    side(a, b[0]) * side(a, b[1]) < 0 && side(b, a[0]) * side(b, a[1]) < 0
+}
+
+// The point of the segment closest to `c`
+fn closest_point<C: Real>([a, b]: [[C; 2]; 2], c: [C; 2]) -> [C; 2] {
+    let d = [b[0] - a[0], b[1] - a[1]];
+    let length_2 = d[0] * d[0] + d[1] * d[1];
+    if length_2 == C::zero() {
+        return a;
+    }
+    let t = ((c[0] - a[0]) * d[0] + (c[1] - a[1]) * d[1]) / length_2;
+    let t = t.max(C::zero()).min(C::one());
+    [a[0] + t * d[0], a[1] + t * d[1]]
+}
+
+// Whether exactly one of the rays `b` lies strictly inside the counter-clockwise sweep between the rays `a`
+fn crosses<C: Real>(a: &[[C; 2]], b: &[[C; 2]]) -> bool {
+    let angle = |r: [C; 2]| r[1].atan2(r[0]).to_f64().unwrap_or(0.);
+    let from = angle(a[0]);
+    let sweep = |r: [C; 2]| Euclid::rem_euclid(&(angle(r) - from), &TAU);
+    let to = sweep(a[1]);
+    (sweep(b[0]) < to) != (sweep(b[1]) < to)
+}
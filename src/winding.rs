@@ -0,0 +1,109 @@
+//! Detection of the ring winding convention used by a dataset.
+//!
+//! Triangulation and the [boolean](crate::boolean) operations use the even-odd rule, so they do not depend on winding.
+//! Other consumers do: GeoJSON ([RFC 7946](https://www.rfc-editor.org/rfc/rfc7946#section-3.1.6)) and OGC Simple Features
+//! wind outer rings counter-clockwise, while ESRI shapefiles wind them clockwise. [detect_convention] guesses which
+//! convention a dataset follows, and [normalize] rewinds it to counter-clockwise outer rings.
+//!
+//! ```
+//! # use triangulate::winding::{self, WindingConvention};
+//! // A clockwise outer ring with a counter-clockwise hole, as found in a shapefile
+//! let polygons = vec![
+//!     vec![[0f64, 0.], [0., 4.], [4., 4.], [4., 0.]],
+//!     vec![[1., 1.], [3., 1.], [3., 3.], [1., 3.]],
+//! ];
+//! assert_eq!(winding::detect_convention(&polygons), WindingConvention::ClockwiseOuter);
+//!
+//! let normalized = winding::normalize(&polygons, None);
+//! assert_eq!(normalized[0], vec![[4., 0.], [4., 4.], [0., 4.], [0., 0.]]);
+//! ```
+
+use core::fmt;
+use alloc::vec::Vec;
+
+use num_traits::real::Real;
+
+use crate::{boolean::Contours, math, PolygonList, PolygonListExt, TriangleWinding, Vertex};
+
+/// The most rings [detect_convention] examines. Larger datasets are sampled at even intervals
+const SAMPLE_SIZE: usize = 256;
+
+/// The winding of outer rings in a dataset. Holes are wound the opposite way
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum WindingConvention {
+    /// Outer rings are counter-clockwise and holes clockwise, as in GeoJSON and OGC Simple Features
+    CounterclockwiseOuter,
+    /// Outer rings are clockwise and holes counter-clockwise, as in ESRI shapefiles
+    ClockwiseOuter,
+}
+
+impl WindingConvention {
+    /// The winding of outer rings
+    pub const fn outer(self) -> TriangleWinding {
+        match self {
+            WindingConvention::CounterclockwiseOuter => TriangleWinding::Counterclockwise,
+            WindingConvention::ClockwiseOuter => TriangleWinding::Clockwise,
+        }
+    }
+
+    /// The winding of holes
+    pub const fn hole(self) -> TriangleWinding {
+        self.outer().reverse()
+    }
+}
+
+/// Guess the [WindingConvention] of a dataset.
+///
+/// Each sampled ring is classified as an outer ring or a hole by how many other rings contain it, and votes for the
+/// convention its winding agrees with. Degenerate rings do not vote. If the vote is tied (including when there are no
+/// rings), [WindingConvention::CounterclockwiseOuter] is returned.
+pub fn detect_convention<'p, P: PolygonList<'p> + ?Sized>(polygons: &'p P) -> WindingConvention
+where <P::Vertex as Vertex>::Coordinate: Real + fmt::Debug {
+    detect_contours(&PolygonListExt::new(polygons).contours())
+}
+
+/// The rings of `polygons`, rewound to [WindingConvention::CounterclockwiseOuter].
+///
+/// Rings keep their role under `convention`; if it is `None`, the convention is found with [detect_convention].
+/// Rings which do not follow the convention are left as they are, so they keep their role too.
+pub fn normalize<'p, P: PolygonList<'p> + ?Sized>(polygons: &'p P, convention: Option<WindingConvention>) -> Contours<<P::Vertex as Vertex>::Coordinate>
+where <P::Vertex as Vertex>::Coordinate: Real + fmt::Debug {
+    let mut contours = PolygonListExt::new(polygons).contours();
+    let convention = convention.unwrap_or_else(|| detect_contours(&contours));
+    if convention == WindingConvention::ClockwiseOuter {
+        for contour in contours.iter_mut() {
+            contour.reverse();
+        }
+    }
+    contours
+}
+
+fn detect_contours<C: Real + fmt::Debug>(contours: &[Vec<[C; 2]>]) -> WindingConvention {
+    let boxes: Vec<_> = contours.iter().map(|contour| math::bounding_box(contour.iter())).collect();
+    let step = contours.len().div_ceil(SAMPLE_SIZE).max(1);
+
+    let mut votes = 0isize;
+    for (i, contour) in contours.iter().enumerate().step_by(step) {
+        let area = math::signed_area_2(contour);
+        if contour.len() < 3 || area == C::zero() {
+            continue;
+        }
+        let c = contour[0];
+        let depth = contours.iter().zip(boxes.iter()).enumerate()
+            .filter(|(j, (other, bb))| *j != i && bb.is_some_and(|[min, max]| min[0] <= c[0] && c[0] <= max[0] && min[1] <= c[1] && c[1] <= max[1])
+                && math::contour_contains(other, c))
+            .count();
+        // A counter-clockwise outer ring or clockwise hole agrees with the counter-clockwise convention
+        if (area > C::zero()) == (depth % 2 == 0) {
+            votes += 1;
+        } else {
+            votes -= 1;
+        }
+    }
+
+    if votes < 0 {
+        WindingConvention::ClockwiseOuter
+    } else {
+        WindingConvention::CounterclockwiseOuter
+    }
+}